@@ -0,0 +1,26 @@
+use std::process::Command;
+
+/// Embed build metadata for `--version` and the `:version` popup
+fn main() {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let hash = git(&["rev-parse", "--short=12", "HEAD"]).unwrap_or_else(|| "unknown".to_string());
+    let dirty = git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|status| !status.is_empty());
+    let hash = if dirty { format!("{}-dirty", hash) } else { hash };
+
+    println!("cargo:rustc-env=CLAZYFILER_GIT_HASH={}", hash);
+    println!("cargo:rustc-env=CLAZYFILER_TARGET={}", std::env::var("TARGET").unwrap_or_default());
+    println!("cargo:rustc-env=CLAZYFILER_PROFILE={}", std::env::var("PROFILE").unwrap_or_default());
+    // Rebuilt on commits and staging; outside a git checkout these paths just don't exist
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/index");
+    println!("cargo:rerun-if-changed=.git/refs");
+    // Source edits change whether the tree is dirty
+    println!("cargo:rerun-if-changed=src");
+}
@@ -0,0 +1,18 @@
+#![no_main]
+
+//! Parquet footers (Thrift compact protocol) and Arrow IPC metadata (flatbuffers) come straight from
+//! disk; offsets and lengths in them must never index out of bounds or allocate without limit
+//! Seeds in seeds/data_files are the test fixtures
+
+use libfuzzer_sys::fuzz_target;
+
+use clazyfiler::services::data_file::DataFormat;
+use clazyfiler::services::DataFileService;
+
+fuzz_target!(|data: &[u8]| {
+    for format in [DataFormat::Parquet, DataFormat::Arrow] {
+        if let Ok(summary) = DataFileService::parse(format, data) {
+            let _ = (summary.schema.len(), summary.sample.map(|sample| sample.rows.len()));
+        }
+    }
+});
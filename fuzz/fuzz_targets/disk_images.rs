@@ -0,0 +1,16 @@
+#![no_main]
+
+//! ISO 9660 volume descriptors and directory records, QCOW2 headers and MBR/GPT partition tables;
+//! every offset, count and size in them comes from the image
+
+use libfuzzer_sys::fuzz_target;
+
+use clazyfiler::services::{DiskImageKind, DiskImageService};
+
+fuzz_target!(|data: &[u8]| {
+    for kind in [DiskImageKind::Iso, DiskImageKind::Qcow2, DiskImageKind::Raw] {
+        if let Ok(info) = DiskImageService::parse(kind, data) {
+            let _ = (info.details.len(), info.entries.iter().map(|entry| entry.size).max());
+        }
+    }
+});
@@ -0,0 +1,40 @@
+#![no_main]
+
+//! The hand-written parsers behind torrent and office document previews: bencode, zip and XML,
+//! and the text extraction on top of them
+//! Seeds in seeds/document_formats are the test fixtures
+
+use libfuzzer_sys::fuzz_target;
+
+use clazyfiler::core::{bencode, xml, zip};
+use clazyfiler::services::document_service::DocumentKind;
+use clazyfiler::services::{DocumentService, TorrentService};
+
+fuzz_target!(|data: &[u8]| {
+    if let Some(torrent) = bencode::Bencode::parse(data) {
+        let _ = torrent.get("info").and_then(|info| info.get("name")).and_then(|name| name.as_str());
+    }
+    let _ = bencode::bencode_dict_value_raw(data, "info");
+    if let Ok(torrent) = TorrentService::parse(data) {
+        let _ = torrent.total_size();
+    }
+
+    if let Some(archive) = zip::ZipArchive::parse(data) {
+        for entry in &archive.entries {
+            if let Some(contents) = archive.read(entry) {
+                let _ = xml::xml_tokens(&String::from_utf8_lossy(&contents));
+            }
+        }
+    }
+
+    for kind in [DocumentKind::Word, DocumentKind::Excel, DocumentKind::PowerPoint, DocumentKind::OpenDocumentText] {
+        let _ = DocumentService::parse(kind, data);
+    }
+
+    let text = String::from_utf8_lossy(data);
+    for token in xml::xml_tokens(&text) {
+        if let xml::XmlToken::Start { attributes, .. } = token {
+            let _ = xml::xml_attribute(attributes, "name");
+        }
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+//! The sfnt table directory and name records of font previews; every offset in them comes from the file
+//! Seeds in seeds/fonts are the test fixtures
+
+use libfuzzer_sys::fuzz_target;
+
+use clazyfiler::services::FontService;
+
+fuzz_target!(|data: &[u8]| {
+    if let Some(info) = FontService::parse(data) {
+        let _ = (info.family, info.style, info.faces);
+    }
+});
@@ -0,0 +1,30 @@
+#![no_main]
+
+//! Link and import detection over arbitrary preview text; the detail pane slices lines at the
+//! reported byte offsets, so they must stay on character boundaries
+//! Seeds in seeds/preview_links cover `from` imports with extra spaces before the module; pass the
+//! directory after the corpus: `cargo fuzz run preview_links corpus/preview_links seeds/preview_links`
+
+use libfuzzer_sys::fuzz_target;
+
+use clazyfiler::core::links::{find_import_references, find_link_candidates, SourceLanguage};
+
+fuzz_target!(|data: &[u8]| {
+    let text = String::from_utf8_lossy(data);
+    let lines: Vec<&str> = text.lines().collect();
+    let slice = |line: usize, start: usize, end: usize| {
+        // Mirrors `highlight_links`, which skips spans that don't fit the line
+        if let Some(line) = lines.get(line).filter(|line| start <= end && end <= line.len()) {
+            let _ = &line[start..end];
+        }
+    };
+
+    for candidate in find_link_candidates(&text) {
+        slice(candidate.line, candidate.start, candidate.end);
+    }
+    for (language, file_name) in [(SourceLanguage::Rust, "lib.rs"), (SourceLanguage::Rust, "util.rs"), (SourceLanguage::C, "main.c"), (SourceLanguage::Python, "app.py")] {
+        for reference in find_import_references(&text, language, file_name) {
+            slice(reference.line, reference.start, reference.end);
+        }
+    }
+});
@@ -0,0 +1,21 @@
+#![no_main]
+
+//! Arbitrary file contents through binary detection, UTF-8 decoding and line limiting,
+//! then rendered the way the detail pane draws previews
+
+use std::path::Path;
+use libfuzzer_sys::fuzz_target;
+use ratatui::{buffer::Buffer, layout::Rect, text::Text, widgets::{Paragraph, Widget, Wrap}};
+use clazyfiler::core::preview;
+
+fuzz_target!(|data: &[u8]| {
+    let content = preview::text_preview(data.to_vec(), Path::new("fuzzed.txt"));
+
+    // A narrow and a regular detail pane, as wrapping is where odd widths go wrong
+    for area in [Rect::new(0, 0, 3, 4), Rect::new(0, 0, 60, 30)] {
+        let mut buffer = Buffer::empty(area);
+        Paragraph::new(Text::from(content.as_str()))
+            .wrap(Wrap { trim: true })
+            .render(area, &mut buffer);
+    }
+});
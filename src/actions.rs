@@ -0,0 +1,160 @@
+use crate::model::SortKey;
+
+/// Something a mode can do, independent of the key that triggers it
+/// Key bindings and the `:action` command refer to actions by their identifier, e.g. "file.rename"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    // Cursor and directory navigation
+    NavDown,
+    NavUp,
+    NavLeft,
+    NavRight,
+    NavParent,
+    NavOpen,  // Enter a directory or open a file in the editor
+    NavJobDestination,
+
+    // Files
+    FileDecrypt,
+    FileChecksums,
+    FileVerify,
+    FileSendToTerminal,
+    FileOpenWithFileManager,
+    DirOpenWithFileManager,
+    FileNew,
+    DirNew,
+    FileRename,
+    FileChmod,
+    FileChown,
+    FileHardLinks,
+    FileBrowseImage,
+    FileDelete,
+    FileDeletePermanently,
+    FileShred,
+    FileUndo,
+    FileRedo,
+    FileSendTo,
+    FileArchive,
+    ClipboardCopy,
+    ClipboardCut,
+    ClipboardPaste,
+    ClipboardPasteImage,
+
+    // Listing presentation
+    ViewRefresh,
+    ViewToggleDetails,
+    ViewCycleGrouping,
+    ViewToggleProject,
+    ViewToggleReadme,
+    SortBy(SortKey),
+
+    // Marks
+    MarksToggle,
+    MarksAll,
+    MarksVisual,
+    MarksInvert,
+    MarksReview,
+    MarksUnmark,
+    MarksClear,
+    MarksReveal,
+    MarksCopyHere,
+    MarksMoveHere,
+
+    // Open history
+    HistoryRecents,
+    HistoryOpenLast,
+
+    // Other modes and popups
+    ModeCommand,
+    ModeSearch,
+    ModeFuzzyFind,
+    ModeGrid,
+    ModeLinks,
+    ModeProperties,
+    ModeJobs,
+    JobCancel,
+    ModeDownloads,
+    ModeBookmarks,
+    BookmarkSet,
+    ViewClose,  // Leave a popup or view for the listing
+
+    AppQuit,
+}
+
+/// Every action with its identifier and a description
+pub const ACTIONS: &[(Action, &str, &str)] = &[
+    (Action::NavDown, "nav.down", "Move the cursor down"),
+    (Action::NavUp, "nav.up", "Move the cursor up"),
+    (Action::NavLeft, "nav.left", "Move the cursor left"),
+    (Action::NavRight, "nav.right", "Move the cursor right"),
+    (Action::NavParent, "nav.parent", "Go to the parent directory"),
+    (Action::NavOpen, "nav.open", "Enter the selected directory or open the file in the editor"),
+    (Action::NavJobDestination, "nav.job_destination", "Go to where the last copy or move put its entries"),
+    (Action::FileDecrypt, "file.decrypt", "Decrypt the selected .gpg/.age file into the preview"),
+    (Action::FileChecksums, "file.checksums", "Compute the MD5, SHA-1 and SHA-256 of the selected file"),
+    (Action::FileVerify, "file.verify", "Check the selected file against its .sha256 or SHA256SUMS sibling again"),
+    (Action::FileSendToTerminal, "file.send_to_terminal", "Download the selected file through the terminal"),
+    (Action::FileOpenWithFileManager, "file.open_with_file_manager", "Open the selected entry with the file manager"),
+    (Action::DirOpenWithFileManager, "dir.open_with_file_manager", "Open the current directory with the file manager"),
+    (Action::FileNew, "file.new", "Create a file (:touch)"),
+    (Action::DirNew, "dir.new", "Create a directory (:mkdir)"),
+    (Action::FileRename, "file.rename", "Rename the selected entry in an inline prompt"),
+    (Action::FileChmod, "file.chmod", "Edit the permission bits of the selected entry"),
+    (Action::FileChown, "file.chown", "Give the marked entries (or the selected one) and everything in them to a picked user and group"),
+    (Action::FileHardLinks, "file.hard_links", "List the other hard links to the selected file under the current directory"),
+    (Action::FileBrowseImage, "file.browse_image", "Browse the directories of the selected ISO image and extract files from it"),
+    (Action::FileDelete, "file.delete", "Trash or delete the marked entries (or the selected one) after confirming"),
+    (Action::FileDeletePermanently, "file.delete_permanently", "Delete the marked entries (or the selected one) for good, bypassing the trash"),
+    (Action::FileShred, "file.shred", "Overwrite the marked files (or the selected one) before deleting them, when secure delete is on"),
+    (Action::FileUndo, "file.undo", "Undo the last copy, move, rename or trash"),
+    (Action::FileRedo, "file.redo", "Do the last undone operation again"),
+    (Action::FileArchive, "file.archive", "Pack the marked entries (or the selected one) into a zip or tar.gz here"),
+    (Action::FileSendTo, "file.send_to", "Copy, move or symlink the marked entries (or the selected one) to a bookmarked, recent or typed directory"),
+    (Action::ClipboardCopy, "clipboard.copy", "Yank the marked entries (or the selected one) for copying"),
+    (Action::ClipboardCut, "clipboard.cut", "Cut the marked entries (or the selected one) for moving"),
+    (Action::ClipboardPaste, "clipboard.paste", "Paste yanked or cut entries into the current directory"),
+    (Action::ClipboardPasteImage, "clipboard.paste_image", "Save the clipboard image into the current directory"),
+    (Action::ViewRefresh, "view.refresh", "Read the directory again"),
+    (Action::ViewToggleDetails, "view.toggle_details", "Toggle the columns view"),
+    (Action::ViewCycleGrouping, "view.cycle_grouping", "Group by kind, extension, git status or nothing"),
+    (Action::ViewToggleProject, "view.toggle_project", "Hide or show gitignored entries"),
+    (Action::ViewToggleReadme, "view.toggle_readme", "Preview a directory's README or its contents"),
+    (Action::SortBy(SortKey::Name), "sort.name", "Sort by name"),
+    (Action::SortBy(SortKey::Size), "sort.size", "Sort by size"),
+    (Action::SortBy(SortKey::Modified), "sort.modified", "Sort by modification time"),
+    (Action::SortBy(SortKey::Extension), "sort.type", "Sort by type"),
+    (Action::MarksToggle, "marks.toggle", "Mark or unmark the entry under the cursor and move down"),
+    (Action::MarksAll, "marks.all", "Mark every entry"),
+    (Action::MarksVisual, "marks.visual", "Start a range at the cursor that moving extends; Enter, Esc or V again marks it"),
+    (Action::MarksInvert, "marks.invert", "Invert the marks"),
+    (Action::MarksReview, "marks.review", "List the marked files"),
+    (Action::MarksUnmark, "marks.unmark", "Unmark the entry under the cursor"),
+    (Action::MarksClear, "marks.clear", "Unmark everything"),
+    (Action::MarksReveal, "marks.reveal", "Jump to the marked file in its directory"),
+    (Action::MarksCopyHere, "marks.copy_here", "Copy every marked entry, wherever it is, into the current directory"),
+    (Action::MarksMoveHere, "marks.move_here", "Move every marked entry, wherever it is, into the current directory"),
+    (Action::HistoryRecents, "history.recents", "List recently opened files"),
+    (Action::HistoryOpenLast, "history.open_last", "Open the last opened file again"),
+    (Action::ModeCommand, "mode.command", "Enter a : command"),
+    (Action::ModeSearch, "mode.search", "Search the listing"),
+    (Action::ModeFuzzyFind, "mode.fuzzy_find", "Fuzzy find in the directory tree"),
+    (Action::ModeGrid, "mode.grid", "Show thumbnails in a grid"),
+    (Action::ModeLinks, "mode.links", "Follow links found in the preview"),
+    (Action::ModeProperties, "mode.properties", "Show properties of the selected entry"),
+    (Action::ModeJobs, "mode.jobs", "List background jobs to pause or resume them"),
+    (Action::JobCancel, "job.cancel", "Cancel the running copy, move or delete"),
+    (Action::ModeDownloads, "mode.downloads", "List new arrivals in the downloads directory"),
+    (Action::ModeBookmarks, "mode.bookmarks", "Jump to a bookmarked directory, or move the selection there"),
+    (Action::BookmarkSet, "bookmark.set", "Bookmark the current directory on the next key pressed"),
+    (Action::ViewClose, "view.close", "Return to the listing"),
+    (Action::AppQuit, "app.quit", "Quit"),
+];
+
+impl Action {
+    pub fn from_id(id: &str) -> Option<Self> {
+        ACTIONS.iter().find(|(_, action_id, _)| *action_id == id).map(|(action, ..)| *action)
+    }
+
+    pub fn id(self) -> &'static str {
+        ACTIONS.iter().find(|(action, ..)| *action == self).map(|(_, id, _)| *id).unwrap_or_default()
+    }
+}
@@ -1,99 +1,661 @@
-use crossterm::event::{self, Event, KeyEvent};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use crossterm::event::{self, Event, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{prelude::Backend, Terminal};
 use crate::{
-    handlers::Handler, key::is_ctrl_c, messages::AppMessage, model::AppModel, 
-    terminal::TerminalExt
+    actions::Action, commands::Command, config::Config, core::{attribute_name, display_path, expand_path, Profiler}, handlers::Handler, key::{is_ctrl_c, is_ctrl_z}, keymap::KeymapMode, messages::AppMessage, model::{AppMode, AppModel, DeleteMode}, services::Diagnostic, 
+    signals, terminal::{terminal_hung_up, wait_for_input, InputWait, TerminalExt}, ui::{create_main_layout, header_column_at, GraphicsLayer}, update::{update, Effect}
 };
-    
+
+/// How often the main loop checks for finished background work while idle
+const BACKGROUND_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Exit status after the terminal hung up, as the shell reports a process killed by SIGHUP
+const HANGUP_EXIT_CODE: i32 = 129;
+
 pub struct App<B: Backend> {
     pub handler: Handler,
     pub model: AppModel,
     terminal: Terminal<B>,
+    graphics: GraphicsLayer,
 }
 
 impl<B: Backend> App<B> {
-    pub fn new(terminal: Terminal<B>) -> Result<Self, Box<dyn std::error::Error>> {
-        let model = AppModel::new()?;
+    pub fn new(
+        terminal: Terminal<B>,
+        profiler: Rc<Profiler>,
+        config: Config,
+        reveal: Option<PathBuf>,
+        problems: Vec<Diagnostic>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut model = AppModel::new(profiler, config)?;
+        if let Some(path) = reveal {
+            model.reveal_path(&path)?;
+        }
+        model.diagnostics.splice(0..0, problems);
+        // Start on the problems panel if anything needs attention
+        let handler = if model.diagnostics.is_empty() {
+            Handler::new_explore_handler()
+        } else {
+            Handler::new_diagnostics_handler()
+        };
         Ok(Self {
-            handler: Handler::new_explore_handler(),
+            handler,
             model,
             terminal,
+            graphics: GraphicsLayer::default(),
         })
     }
 
-    pub fn handle_key(&mut self, key: KeyEvent) -> Option<AppMessage> {
+    pub fn handle_key(&mut self, key: KeyEvent) -> Vec<Effect> {
         // Handle global key event
         if is_ctrl_c(&key) {
-            return Some(AppMessage::Quit)
+            return vec![Effect::App(AppMessage::Quit)]
+        }
+        if is_ctrl_z(&key) {
+            return vec![Effect::App(AppMessage::Suspend)]
         }
 
+        // Status messages only live until the next key press
+        self.model.status_message = None;
+
         // Handle handler specific key event
-        return self.handler.handle_key(key, &mut self.model)
+        self.handler.handle_key(key, &mut self.model)
+    }
+    
+    /// Clicking a column header of the detail view sorts by that column; clicking the status bar
+    /// while it offers a finished job's destination goes there
+    fn handle_mouse(&mut self, mouse: MouseEvent) -> Vec<Effect> {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left)
+            || !matches!(self.handler, Handler::Explore(_))
+            || self.model.mode == AppMode::FuzzyFind
+        {
+            return Vec::new();
+        }
+        // The viewport, not the whole screen: an inline viewport starts partway down
+        let (file_list_area, _, _, _, status_area) = create_main_layout(self.terminal.get_frame().area(), self.model.transfer().is_some());
+        if mouse.row == status_area.y && self.model.job_landing.is_some() && self.model.batch.is_none() {
+            self.model.status_message = None;
+            return update(&mut self.model, Action::NavJobDestination);
+        }
+        if !self.model.detail_view {
+            return Vec::new();
+        }
+        match header_column_at(file_list_area, mouse.column, mouse.row) {
+            Some(key) => update(&mut self.model, Action::SortBy(key)),
+            None => Vec::new(),
+        }
+    }
+    
+    /// Execute a `:` command line, reporting the outcome in the status bar
+    /// Returns the effects of an action run with `:action`, to be carried out like those of a key
+    fn execute_command(&mut self, input: &str) -> Vec<Effect> {
+        match Command::parse(input) {
+            Ok(Command::Select(pattern)) => {
+                let count = self.model.mark_matching(&pattern);
+                self.model.set_status(format!("Marked {} files matching '{}'", count, pattern));
+            }
+            Ok(Command::CacheStats) => {
+                let summary = self.model.cache_stats_summary();
+                self.model.set_status(summary);
+            }
+            Ok(Command::CacheClear) => {
+                self.model.clear_caches();
+                self.model.set_status("Caches cleared");
+            }
+            Ok(Command::NewFile(name)) => {
+                let path = self.model.current_dir.join(&name);
+                if path.symlink_metadata().is_ok() {
+                    self.touch_entries(vec![path]);
+                } else {
+                    self.create_entry(&name, false);
+                }
+            }
+            Ok(Command::Touch) => {
+                let paths = self.model.delete_targets();
+                self.touch_entries(paths);
+            }
+            Ok(Command::NewDirectory(name)) => self.create_entry(&name, true),
+            Ok(Command::OlderThan(age)) => match self.model.files_older_than(&age) {
+                Ok(files) if files.is_empty() => self.model.set_status(format!("No files older than {} here", age)),
+                Ok(files) => {
+                    if let Err(e) = self.handler.switch_to(&AppMessage::SwitchToOldFilesHandler(files, age), &mut self.model) {
+                        self.model.set_status(e);
+                    }
+                }
+                Err(e) => self.model.set_status(e.to_string()),
+            },
+            Ok(Command::PruneEmpty) => {
+                let empty = self.model.empty_directories();
+                if empty.is_empty() {
+                    self.model.set_status("No empty directories here");
+                } else if let Err(e) = self.handler.switch_to(&AppMessage::SwitchToConfirmDeleteHandler(empty, DeleteMode::Prune), &mut self.model) {
+                    self.model.set_status(e);
+                }
+            }
+            Ok(Command::Cleanup) => match self.model.find_leftovers() {
+                Ok(leftovers) if leftovers.is_empty() => self.model.set_status("Nothing to clean up here"),
+                Ok(leftovers) => {
+                    if let Err(e) = self.handler.switch_to(&AppMessage::SwitchToCleanupHandler(leftovers), &mut self.model) {
+                        self.model.set_status(e);
+                    }
+                }
+                Err(e) => self.model.set_status(e.to_string()),
+            },
+            Ok(Command::Index) => {
+                let indexed = self.model.switch_to_fuzzy_find_mode(true)
+                    .map_err(|e| e.to_string())
+                    .and_then(|_| self.handler.switch_to(&AppMessage::SwitchToFuzzyFindHandler, &mut self.model));
+                if let Err(e) = indexed {
+                    self.model.set_status(format!("Failed to index: {}", e));
+                }
+            }
+            Ok(Command::Version) => {
+                if let Err(e) = self.handler.switch_to(&AppMessage::SwitchToAboutHandler, &mut self.model) {
+                    self.model.set_status(e);
+                }
+            }
+            Ok(Command::EditConfig) => {
+                let edited = self.terminal.with_suspended_terminal(|| self.model.edit_config().map_err(|e| e.into()));
+                let reloaded = edited.map_err(|e| e.to_string()).and_then(|_| self.model.reload_config().map_err(|e| e.to_string()));
+                let status = match reloaded {
+                    Ok(false) => "Config reloaded".to_string(),
+                    Ok(true) => "Config reloaded; screen and instance settings apply on the next start".to_string(),
+                    Err(e) => format!("Config not reloaded: {}", e),
+                };
+                self.model.set_status(status);
+                // Refused key bindings of the new config
+                if !self.model.diagnostics.is_empty() {
+                    self.handler = Handler::new_diagnostics_handler();
+                }
+            }
+            Ok(Command::Action(action)) => {
+                // Commands run in explore mode
+                if Handler::default_bindings(KeymapMode::Explore).iter().any(|(_, built_in)| *built_in == action) {
+                    return self.handler.perform(action, &mut self.model);
+                }
+                self.model.set_status(format!("{} is not an action of explore mode", action.id()));
+            }
+            Ok(Command::Rename(name)) => self.rename_selected(&name),
+            Ok(Command::ChangeDirectory(input)) => {
+                let path = expand_path(&self.model.current_dir, &input);
+                if let Err(e) = self.model.navigate_to(&path) {
+                    self.model.set_status(e.to_string());
+                }
+            }
+            Ok(Command::BulkRename) => self.bulk_rename(),
+            Ok(Command::Connect(name)) => self.connect(&name),
+            Ok(Command::Bookmark(name)) => match self.model.add_bookmark(&name) {
+                Ok(()) => self.model.set_status(format!("Bookmarked {} as {}", display_path(&self.model.current_dir), name)),
+                Err(e) => self.model.set_status(e.to_string()),
+            },
+            Err(e) => self.model.set_status(e.to_string()),
+        }
+        Vec::new()
+    }
+
+    /// Do the work of an effect other than a message, returning a message to handle next
+    /// Successes are reported in the status bar; an error is the reason to show instead
+    fn carry_out(&mut self, effect: Effect) -> Result<Option<AppMessage>, Box<dyn std::error::Error>> {
+        let model = &mut self.model;
+        match effect {
+            Effect::App(msg) => return Ok(Some(msg)),
+            Effect::Confirmed(effect) => return self.carry_out(*effect),
+            Effect::OpenWithFileManager(target) => self.open_with_file_manager(target),
+            Effect::PasteEntries => {
+                if model.paste_entries()? {
+                    self.handler = Handler::new_paste_conflict_handler();
+                }
+            },
+            Effect::UndoOperation => {
+                let status = model.undo_operation()?;
+                model.set_status(status);
+            },
+            Effect::RedoOperation => {
+                let status = model.redo_operation()?;
+                model.set_status(status);
+            },
+            Effect::PasteClipboardImage => self.paste_clipboard_image(),
+
+            Effect::GoToParent => model.go_to_parent()?,
+            Effect::EnterDirectory(dir) => model.change_directory(dir)?,
+            Effect::GoToJobDestination => match model.go_to_job_landing()? {
+                0 => model.set_status("The copied entries are gone"),
+                1 => {}
+                count => model.set_status(format!("Marked the {} new entries", count)),
+            },
+            Effect::Reveal(path) => model.reveal_path(&path)?,
+            Effect::RevealOpened(path) => model.reveal_opened(&path)?,
+            Effect::RevealLastOpened => {
+                model.reveal_last_opened()?;
+            },
+            Effect::FollowLink(target) => model.follow_link(&target)?,
+            Effect::Refresh => model.refresh_current_directory(),
+            Effect::ReloadDownloads => model.reload_downloads(),
+            Effect::FindHardLinks => {
+                let links = model.hard_links_of_selected()?;
+                if links.is_empty() {
+                    return Err("No other hard links under the current directory".into());
+                }
+                return Ok(Some(AppMessage::SwitchToHardLinksHandler(links)));
+            },
+            Effect::BrowseDiskImage(image, trail) => {
+                let listing = model.list_disk_image(&image, trail)?;
+                return Ok(Some(AppMessage::SwitchToDiskImageHandler(listing)));
+            },
+
+            Effect::ToggleProjectView => model.toggle_project_view(),
+            Effect::CycleGrouping => model.cycle_group_by(),
+            Effect::ComputeDigests => model.compute_selected_digests(),
+            Effect::VerifyChecksums => model.verify_selected(),
+            Effect::EditPermissions(path) => {
+                let mode = model.permissions_of(&path).ok_or("No permission bits to edit here")?;
+                return Ok(Some(AppMessage::SwitchToChmodHandler(path, mode)));
+            },
+            // Refused up front when off or pointless, so the prompt only ever asks about what will happen
+            Effect::ConfirmShred(targets) => match model.shred_refusal(&targets) {
+                Some(refusal) => return Err(refusal.into()),
+                None => return Ok(Some(AppMessage::SwitchToConfirmDeleteHandler(targets, DeleteMode::Shred))),
+            },
+
+            Effect::SetBookmark(key) => {
+                model.set_bookmark(key)?;
+                model.set_status(format!("Bookmarked on {} - ' then {} jumps back here", key, key));
+            },
+            Effect::RemoveBookmark(bookmark) => {
+                model.remove_bookmark(&bookmark)?;
+                model.set_status(format!("Forgot bookmark {}", bookmark.name));
+            },
+            Effect::ArchiveEntries(name) => {
+                model.archive_entries(&name)?;
+            },
+            Effect::SendEntriesTo(dir, mode) => {
+                model.send_entries_to(&dir, mode)?;
+            },
+            Effect::LinkEntriesTo(dir) => match model.link_entries_to(&dir)? {
+                1 => model.set_status(format!("Linked 1 entry in {}", display_path(&dir))),
+                count => model.set_status(format!("Linked {} entries in {}", count, display_path(&dir))),
+            },
+            Effect::SetPermissions(path, mode) => model.set_permissions(&path, mode)?,
+            Effect::ChownEntries(user, group) => {
+                let count = model.chown_entries(user.id, group.id)?;
+                model.set_status(format!("Giving {} entries to {}:{}", count, user.name, group.name));
+            },
+            Effect::ExtractFromImage(image, entry) => {
+                let to = model.extract_from_disk_image(&image, &entry)?;
+                model.set_status(format!("Extracted {}", display_path(&to)));
+            },
+            // The dialog stays up while there are more conflicts to decide on
+            Effect::ResolvePasteConflict(choice, all) => match model.resolve_paste_conflict(choice, all) {
+                Ok(true) => {}
+                Ok(false) => return Ok(Some(AppMessage::SwitchToExploreHandlerKeepQuery)),
+                Err(e) => {
+                    model.set_status(e.to_string());
+                    return Ok(Some(AppMessage::SwitchToExploreHandlerKeepQuery));
+                },
+            },
+        }
+        Ok(None)
+    }
+
+    /// Launch the file manager on `target` and report the outcome in the status bar
+    fn open_with_file_manager(&mut self, target: PathBuf) {
+        match self.model.open_with_file_manager(target) {
+            Ok(target) => {
+                let message = format!("Opened {} with {}", target.display(), self.model.config.external_commands.file_manager);
+                self.model.set_status(message);
+            }
+            Err(e) => self.model.set_status(e.to_string()),
+        }
+    }
+
+    fn paste_clipboard_image(&mut self) {
+        match self.model.paste_clipboard_image() {
+            Ok(path) => {
+                let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                self.model.set_status(format!("Saved clipboard image as {}", name));
+            }
+            Err(e) => self.model.set_status(e.to_string()),
+        }
+    }
+
+    fn rename_selected(&mut self, name: &str) {
+        match self.model.rename_selected(name) {
+            Ok(path) => self.model.set_status(format!("Renamed to {}", display_path(&path))),
+            Err(e) => self.model.set_status(e.to_string()),
+        }
+    }
+
+    /// Set or clear a chattr attribute, through sudo on the terminal when chattr alone is not allowed to
+    fn set_attribute(&mut self, path: &Path, letter: char, on: bool) {
+        let set = self.model.set_attribute(path, letter, on, false).or_else(|_| {
+            self.terminal.with_suspended_terminal(|| self.model.set_attribute(path, letter, on, true).map_err(|e| e.into()))
+        });
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let attribute = attribute_name(letter).unwrap_or("changed");
+        match set {
+            Ok(()) if on => self.model.set_status(format!("{} is now {}", name, attribute)),
+            Ok(()) => self.model.set_status(format!("{} is no longer {}", name, attribute)),
+            Err(e) => self.model.set_status(e.to_string()),
+        }
+    }
+
+    /// Rename the marked entries (or the selected one) by editing their names in the editor
+    fn bulk_rename(&mut self) {
+        let paths = self.model.delete_targets();
+        if paths.is_empty() {
+            self.model.set_status("Nothing selected");
+            return;
+        }
+        let edited = self.terminal.with_suspended_terminal(|| self.model.edit_names(&paths).map_err(|e| e.into()));
+        let status = match edited.map_err(|e| e.to_string()).and_then(|edited| self.model.bulk_rename(&paths, &edited).map_err(|e| e.to_string())) {
+            Ok(0) => "Nothing renamed".to_string(),
+            Ok(1) => "Renamed 1 entry".to_string(),
+            Ok(count) => format!("Renamed {} entries", count),
+            Err(e) => e,
+        };
+        self.model.set_status(status);
+    }
+
+    /// Go to a configured remote location, mounting it first on the suspended terminal if needed
+    fn connect(&mut self, name: &str) {
+        let connected = self.model.connection(name).map_err(|e| e.to_string()).and_then(|(connection, needs_mount)| {
+            if needs_mount {
+                self.terminal.with_suspended_terminal(|| self.model.mount_connection(&connection).map_err(|e| e.into()))
+                    .map_err(|e| e.to_string())?;
+            }
+            self.model.navigate_to(&connection.path).map_err(|e| e.to_string())
+        });
+        match connected {
+            Ok(()) => self.model.set_status(format!("Connected to {}", name)),
+            Err(e) => self.model.set_status(e),
+        }
+    }
+
+    fn create_entry(&mut self, name: &str, directory: bool) {
+        match self.model.create_entry(name, directory) {
+            Ok(mode) => self.model.set_status(format!("Created {} {}", name, mode.describe())),
+            Err(e) => self.model.set_status(e.to_string()),
+        }
+    }
+
+    fn touch_entries(&mut self, paths: Vec<PathBuf>) {
+        let status = match self.model.touch_entries(&paths) {
+            Ok(()) if paths.len() == 1 => format!("Touched {}", display_path(&paths[0])),
+            Ok(()) => format!("Touched {} entries", paths.len()),
+            Err(e) => e.to_string(),
+        };
+        self.model.set_status(status);
     }
 
     /// Open the selected file with editor - delegates to model with terminal suspension
     fn open_file_with_editor(&mut self) -> Result<(), String> {
+        self.graphics.forget();
         self.terminal.with_suspended_terminal(|| {
             self.model.open_selected_file_with_editor().map_err(|e| e.into())
         }).map_err(|e| e.to_string())
     }
 
+    /// Decrypt the selected file for preview, handing the terminal to gpg/age for passphrase prompts
+    fn decrypt_preview(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.graphics.forget();
+        self.terminal.with_suspended_terminal(|| {
+            self.model.decrypt_selected_for_preview();
+            Ok(())
+        })
+    }
+
+    /// Stop like any job suspended with Ctrl-Z, with the shell's terminal settings restored meanwhile
+    fn suspend_to_shell(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.graphics.forget();
+        self.terminal.with_suspended_terminal(|| {
+            signals::stop_process();
+            Ok(())
+        })
+    }
+
+    /// Show paths forwarded by other launches in single-instance mode, leaving any popup for the listing
+    fn reveal_forwarded_paths(&mut self) -> Result<bool, Box<dyn std::error::Error>> {
+        let paths = self.model.take_forwarded_paths();
+        for path in &paths {
+            self.model.switch_to_explore_mode();
+            self.handler.switch_to(&AppMessage::SwitchToExploreHandler, &mut self.model)?;
+            match self.model.navigate_to(path) {
+                Ok(_) => self.model.set_status(format!("Revealed {} for another launch", display_path(path))),
+                Err(e) => self.model.set_status(e.to_string()),
+            }
+        }
+        Ok(!paths.is_empty())
+    }
+
+    /// Save state on SIGTERM, SIGHUP or a hangup, before the terminal is restored
+    fn shut_down(&mut self) {
+        self.model.save_fuzzy_index();
+        if terminal_hung_up() {
+            // Nothing left to restore, and every write to the closed terminal would fail
+            std::process::exit(HANGUP_EXIT_CODE);
+        }
+        let _ = self.terminal.release();
+    }
 
     /// Draw the current state to the terminal
     pub fn draw(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        self.terminal.draw(|f| {
+        let completed = self.terminal.draw(|f| {
             // Render directly with model - no ViewModels needed!
             self.handler.render_with_handler_context(f, &self.model);
         })?;
+        let placements = self.model.take_image_placements();
+        if let Some(protocol) = self.model.capabilities().graphics() {
+            self.graphics.present(&mut std::io::stdout(), protocol, completed.buffer, placements)?;
+        }
         Ok(())
     }
 
     /// Main application loop - handles all events and terminal management
     pub fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut needs_draw = true;
+        let mut first_draw = true;
         loop {
+            // Signals are noticed on the next wake-up, so they never interrupt an operation halfway,
+            // and before drawing, which fails once the terminal is gone
+            if signals::take_termination_request() {
+                self.shut_down();
+                return Ok(());
+            }
+            if signals::take_suspend_request() {
+                self.suspend_to_shell()?;
+                needs_draw = true;
+                continue;
+            }
+            if signals::take_resumed() {
+                // Stopped from outside: the shell put the tty back into cooked mode meanwhile
+                crossterm::terminal::enable_raw_mode()?;
+                self.terminal.clear()?;
+                self.graphics.forget();
+                needs_draw = true;
+                continue;
+            }
+
             // Draw the current state
-            self.draw()?;
+            if needs_draw {
+                let started = Instant::now();
+                self.draw()?;
+                if first_draw {
+                    first_draw = false;
+                    let profiler = &self.model.profiler;
+                    profiler.record("first draw".to_string(), started.elapsed());
+                    profiler.record("total until first frame".to_string(), profiler.elapsed());
+                }
+                // Drawing is what fills the caches, so trim them right after
+                self.model.enforce_cache_budget();
+            }
+
+            // A running batch waits for its results instead, only taking keys that are already there
+            let batch_running = self.model.batch_running();
+            let timeout = if batch_running { Duration::ZERO } else { BACKGROUND_POLL_INTERVAL };
+            // Wake up periodically so results of background work get drawn
+            match wait_for_input(timeout)? {
+                InputWait::Ready => {}
+                InputWait::Idle if batch_running => {
+                    if self.model.step_batch() {
+                        self.handler = Handler::new_batch_report_handler();
+                    }
+                    self.model.poll_background_tasks();
+                    self.model.tick();
+                    needs_draw = true;
+                    continue;
+                }
+                InputWait::Idle => {
+                    needs_draw = self.model.poll_background_tasks() | self.model.tick() | self.reveal_forwarded_paths()?;
+                    continue;
+                }
+                InputWait::HungUp => {
+                    self.shut_down();
+                    return Ok(());
+                }
+            }
+            needs_draw = true;
 
             // Handle input events
-            let Event::Key(key) = event::read()? else {
-                continue;
+            let key = match event::read()? {
+                Event::Key(key) => key,
+                Event::Mouse(mouse) => {
+                    let effects = self.handle_mouse(mouse);
+                    if self.carry_out_effects(effects)? {
+                        return Ok(());
+                    }
+                    continue;
+                }
+                _ => continue,
             };
 
-            let message = self.handle_key(key);
-            
-            // Handle message if present
-            if let Some(msg) = message {
-                match msg {
-                    AppMessage::Quit => return Ok(()),
-                    AppMessage::OpenFile => self.open_file_with_editor()?,
-                    AppMessage::SwitchToExploreHandler => {
-                        self.model.switch_to_explore_mode();
-                        self.handler.switch_to(&AppMessage::SwitchToExploreHandler, &mut self.model)?;
-                    },
-                    AppMessage::SwitchToExploreHandlerKeepQuery => {
-                        self.model.switch_to_explore_mode_keep_query();
-                        self.handler.switch_to(&AppMessage::SwitchToExploreHandler, &mut self.model)?;
-                    },
-                    AppMessage::SwitchToSearchHandler => {
-                        self.model.switch_to_search_mode();
-                        self.handler.switch_to(&msg, &mut self.model)?;
-                    },
-                    AppMessage::SwitchToFuzzyFindHandler => {
-                        // Start fuzzy find indexing when switching to fuzzy find mode
-                        if let Err(e) = self.model.switch_to_fuzzy_find_mode() {
-                            return Err(format!("Failed to start fuzzy find: {}", e).into());
-                        }
-                        self.handler.switch_to(&msg, &mut self.model)?;
-                    },
-                    AppMessage::Error(error) => Err(error)?,
+            let effects = self.handle_key(key);
+            if self.carry_out_effects(effects)? {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Carry out effects in order; a command line can run an action, which has effects of its own
+    /// One that fails shows why in the status bar and drops the rest, which were meant to follow its success
+    /// Returns whether the app quit
+    fn carry_out_effects(&mut self, effects: Vec<Effect>) -> Result<bool, Box<dyn std::error::Error>> {
+        let mut effects = VecDeque::from(effects);
+        while let Some(effect) = effects.pop_front() {
+            // Changing entries under a protected directory waits for its name, along with what was to follow
+            if let Some((root, what)) = effect.protected_root(&self.model) {
+                let deferred = std::iter::once(effect).chain(effects.drain(..)).collect();
+                let previous = std::mem::replace(&mut self.handler, Handler::new_explore_handler());
+                self.handler = Handler::new_confirm_protected_handler(root, what, deferred, previous);
+                continue;
+            }
+            let msg = match self.carry_out(effect) {
+                Ok(Some(msg)) => msg,
+                Ok(None) => continue,
+                Err(e) => {
+                    self.model.set_status(e.to_string());
+                    effects.clear();
+                    continue;
                 }
+            };
+            match msg {
+                AppMessage::Quit => {
+                    self.model.save_fuzzy_index();
+                    self.terminal.release()?;
+                    return Ok(true);
+                },
+                AppMessage::Suspend => self.suspend_to_shell()?,
+                AppMessage::OpenFile => self.open_file_with_editor()?,
+                AppMessage::OpenFileInExplore => {
+                    self.model.switch_to_explore_mode_keep_query();
+                    self.handler.switch_to(&AppMessage::SwitchToExploreHandler, &mut self.model)?;
+                    self.open_file_with_editor()?;
+                },
+                AppMessage::DecryptPreview => self.decrypt_preview()?,
+                AppMessage::SendToTerminal => {
+                    self.model.send_selected_to_terminal(&mut std::io::stdout());
+                    // Repaint in case the terminal drew anything for the transfer
+                    self.terminal.clear()?;
+                    self.graphics.forget();
+                },
+                AppMessage::SwitchToExploreHandler => {
+                    self.model.switch_to_explore_mode();
+                    self.handler.switch_to(&AppMessage::SwitchToExploreHandler, &mut self.model)?;
+                },
+                AppMessage::SwitchToExploreHandlerKeepQuery => {
+                    self.model.switch_to_explore_mode_keep_query();
+                    self.handler.switch_to(&AppMessage::SwitchToExploreHandler, &mut self.model)?;
+                },
+                AppMessage::SwitchToSearchHandler => {
+                    self.model.switch_to_search_mode();
+                    self.handler.switch_to(&msg, &mut self.model)?;
+                },
+                AppMessage::SwitchToCommandHandler(_)
+                | AppMessage::SwitchToMarksHandler
+                | AppMessage::SwitchToRecentsHandler
+                | AppMessage::SwitchToDownloadsHandler
+                | AppMessage::SwitchToBookmarksHandler
+                | AppMessage::SwitchToBookmarkSetHandler
+                | AppMessage::SwitchToSendToHandler
+                | AppMessage::SwitchToArchiveHandler(_)
+                | AppMessage::SwitchToChmodHandler(..)
+                | AppMessage::SwitchToChownHandler
+                | AppMessage::SwitchToHardLinksHandler(_)
+                | AppMessage::SwitchToDiskImageHandler(_)
+                | AppMessage::SwitchToCleanupHandler(_)
+                | AppMessage::SwitchToOldFilesHandler(..)
+                | AppMessage::SwitchToPreviewLinksHandler
+                | AppMessage::SwitchToGridHandler
+                | AppMessage::SwitchToPropertiesHandler
+                | AppMessage::SwitchToAboutHandler
+                | AppMessage::SwitchToJobsHandler
+                | AppMessage::SwitchToRenameHandler(_)
+                | AppMessage::SwitchToConfirmDeleteHandler(..) => {
+                    self.handler.switch_to(&msg, &mut self.model)?;
+                },
+                AppMessage::SetAttribute(path, letter, on) => self.set_attribute(&path, letter, on),
+                AppMessage::RenameSelected(name) => {
+                    self.model.switch_to_explore_mode_keep_query();
+                    self.handler.switch_to(&AppMessage::SwitchToExploreHandler, &mut self.model)?;
+                    self.rename_selected(&name);
+                },
+                AppMessage::DeleteEntries(paths, mode) => {
+                    self.model.switch_to_explore_mode_keep_query();
+                    self.handler.switch_to(&AppMessage::SwitchToExploreHandler, &mut self.model)?;
+                    if let Err(e) = self.model.delete_entries(paths, mode) {
+                        self.model.set_status(e.to_string());
+                    }
+                },
+                AppMessage::ExecuteCommand(command) => {
+                    self.handler.switch_to(&AppMessage::SwitchToExploreHandler, &mut self.model)?;
+                    effects.extend(self.execute_command(&command));
+                },
+                AppMessage::SwitchToFuzzyFindHandler => {
+                    // Start fuzzy find indexing when switching to fuzzy find mode
+                    if let Err(e) = self.model.switch_to_fuzzy_find_mode(false) {
+                        return Err(format!("Failed to start fuzzy find: {}", e).into());
+                    }
+                    self.handler.switch_to(&msg, &mut self.model)?;
+                },
             }
         }
+        Ok(false)
     }
 }
 
-pub fn run_app<B: Backend>(terminal: Terminal<B>) -> Result<(), Box<dyn std::error::Error>> {
-    let mut app = App::new(terminal)?;
+/// `reveal` is a file to put the cursor on at startup, in the current directory;
+/// `problems` are found before the UI started, like a config file that failed to parse
+pub fn run_app<B: Backend>(
+    terminal: Terminal<B>,
+    profiler: Rc<Profiler>,
+    config: Config,
+    reveal: Option<PathBuf>,
+    problems: Vec<Diagnostic>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    profiler.record("terminal setup".to_string(), profiler.elapsed());
+    let mut app = App::new(terminal, profiler, config, reveal, problems)?;
     app.run()
 }
 
@@ -0,0 +1,312 @@
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::{core::{JobControl, Result}, services::FileService};
+
+/// What a batch does to each of its entries, given the entry and the batch's destination directory;
+/// returns where the entry ended up
+pub type BatchStep = fn(&FileService, &Path, &Path) -> Result<PathBuf>;
+
+/// A step that carries settings of its own, like the owner to give every entry
+type StepFn = dyn Fn(&FileService, &Path, &Path) -> Result<PathBuf> + Send + Sync;
+
+/// The step of a batch, shared with its worker
+#[derive(Clone)]
+struct SharedStep(Arc<StepFn>);
+
+impl std::fmt::Debug for SharedStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SharedStep")
+    }
+}
+
+/// Copy the entry into the destination, renaming it on a clash
+pub fn copy_step(file_service: &FileService, path: &Path, destination: &Path) -> Result<PathBuf> {
+    file_service.copy_file(path, destination)
+}
+
+/// Move the entry into the destination, renaming it on a clash
+pub fn move_step(file_service: &FileService, path: &Path, destination: &Path) -> Result<PathBuf> {
+    file_service.move_file(path, destination)
+}
+
+/// Delete the entry; the destination is unused and the result is the path it had
+pub fn delete_step(file_service: &FileService, path: &Path, _: &Path) -> Result<PathBuf> {
+    file_service.delete_entry(path).map(|_| path.to_path_buf())
+}
+
+/// Overwrite the file, then remove it; the destination is unused and the result is the path it had
+pub fn shred_step(file_service: &FileService, path: &Path, _: &Path) -> Result<PathBuf> {
+    file_service.shred_file(path).map(|_| path.to_path_buf())
+}
+
+/// Remove an empty directory, failing rather than taking anything that appeared in it since
+pub fn remove_empty_step(file_service: &FileService, path: &Path, _: &Path) -> Result<PathBuf> {
+    file_service.remove_empty_directory(path).map(|_| path.to_path_buf())
+}
+
+/// Move the entry to the trash, the batch's destination; the result is where it went there
+pub fn trash_step(file_service: &FileService, path: &Path, trash: &Path) -> Result<PathBuf> {
+    file_service.trash_entry(path, trash)
+}
+
+/// Clear the way for an entry meant to replace what has its name in the destination
+fn replace_existing(file_service: &FileService, replace: &HashSet<PathBuf>, trash: Option<&Path>, path: &Path, destination: &Path) -> Result<()> {
+    let Some(target) = path.file_name().map(|name| destination.join(name)).filter(|_| replace.contains(path)) else {
+        return Ok(());
+    };
+    if target == path || target.symlink_metadata().is_err() {
+        return Ok(());
+    }
+    match trash {
+        Some(trash) => file_service.trash_entry(&target, trash).map(|_| ()),
+        None => file_service.delete_entry(&target),
+    }
+}
+
+/// An entry the batch could not process, and why
+#[derive(Debug, Clone)]
+pub struct BatchFailure {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// How far the data of a batch that measures it got, for the progress gauge
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transfer {
+    pub label: &'static str,  // What moves the data, e.g. "Copy"
+    pub done: u64,
+    pub total: u64,
+    pub eta: Option<Duration>,  // At the rate so far; unknown before any data went through and while paused
+}
+
+impl Transfer {
+    /// Progress of a job reporting its data to `control` since `started`
+    /// No ETA before any data went through and while the job is paused
+    pub fn measure(label: &'static str, control: &JobControl, started: Instant) -> Self {
+        let total = control.bytes_total();
+        let done = control.bytes_done().min(total);
+        let eta = (done > 0 && !control.is_paused())
+            .then(|| started.elapsed().mul_f64((total - done) as f64 / done as f64));
+        Transfer { label, done, total, eta }
+    }
+
+    pub fn ratio(&self) -> f64 {
+        (self.done as f64 / self.total as f64).clamp(0.0, 1.0)
+    }
+}
+
+/// A file operation over many entries (the marked files, say) that keeps going when some of them fail
+/// Entries are processed in order on a worker thread, so browsing goes on and the job survives mode switches;
+/// failures are collected for a report where they can be retried or skipped instead of aborting the whole batch
+#[derive(Debug)]
+pub struct Batch {
+    pub label: &'static str,  // What is being done, e.g. "Copy", for the progress line and the report
+    pub destination: PathBuf,
+    step: SharedStep,
+    measure: bool,              // Count the data of the entries for a byte progress gauge
+    replace: Arc<HashSet<PathBuf>>,  // Entries that take the place of one with their name in the destination
+    replaced_trash: Option<PathBuf>, // Where what they replace goes; removed for good without one
+    started: Option<Instant>,   // When the first worker set off, for the ETA
+    pending: VecDeque<PathBuf>,    // Not handed to a worker yet
+    in_flight: VecDeque<PathBuf>,  // Handed to the worker, in the order its results come back
+    results: Option<Receiver<std::result::Result<PathBuf, String>>>,
+    control: JobControl,
+    pub total: usize,
+    pub done: usize,
+    pub completed: Vec<(PathBuf, PathBuf)>,  // Entries done since last taken, with where each ended up
+    pub failures: Vec<BatchFailure>,
+    pub skipped: usize,  // Failed entries given up on
+}
+
+impl Batch {
+    pub fn new(label: &'static str, paths: Vec<PathBuf>, destination: PathBuf, step: BatchStep) -> Self {
+        Self::with_step(label, paths, destination, step)
+    }
+
+    /// Like `new`, with a step that may capture what it needs, such as the owner to give every entry
+    pub fn with_step(
+        label: &'static str,
+        paths: Vec<PathBuf>,
+        destination: PathBuf,
+        step: impl Fn(&FileService, &Path, &Path) -> Result<PathBuf> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            label,
+            destination,
+            step: SharedStep(Arc::new(step)),
+            measure: false,
+            replace: Arc::default(),
+            replaced_trash: None,
+            started: None,
+            total: paths.len(),
+            pending: paths.into(),
+            in_flight: VecDeque::new(),
+            results: None,
+            control: JobControl::new(),
+            done: 0,
+            completed: Vec::new(),
+            failures: Vec::new(),
+            skipped: 0,
+        }
+    }
+
+    /// Track the data of the entries too, as copies and moves of large files take a while each
+    pub fn measuring_bytes(mut self) -> Self {
+        self.measure = true;
+        self
+    }
+
+    /// Have `entries` replace what has their name in the destination instead of landing under a numbered name,
+    /// the replaced entries going to `trash` if there is one
+    pub fn replacing(mut self, entries: HashSet<PathBuf>, trash: Option<PathBuf>) -> Self {
+        self.replace = Arc::new(entries);
+        self.replaced_trash = trash;
+        self
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.pending.is_empty() || !self.in_flight.is_empty()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.control.is_paused()
+    }
+
+    /// Hold the job after the chunk in progress, or let it go on
+    pub fn set_paused(&self, paused: bool) {
+        self.control.set_paused(paused);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.control.is_cancelled()
+    }
+
+    /// Stop after the chunk in progress; the entry being worked on is cleaned up and everything left is skipped
+    pub fn cancel(&mut self) {
+        self.control.cancel();
+        self.skipped += self.pending.len();
+        self.pending.clear();
+    }
+
+    /// Data done and to do, once the worker has measured it
+    pub fn transfer(&self) -> Option<Transfer> {
+        if !self.measure || self.control.bytes_total() == 0 {
+            return None;
+        }
+        Some(Transfer::measure(self.label, &self.control, self.started.unwrap_or_else(Instant::now)))
+    }
+
+    /// Hand pending entries to a worker if none is busy, then collect results for up to `budget`
+    /// The worker gets its own file service configured like `file_service` (retries, throttle)
+    pub fn run_for(&mut self, file_service: &FileService, budget: Duration) {
+        if self.in_flight.is_empty() && !self.pending.is_empty() {
+            self.spawn_worker(file_service);
+        }
+        let Some(results) = &self.results else { return };
+        let deadline = Instant::now() + budget;
+        while let Some(path) = self.in_flight.front() {
+            match results.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(Ok(result)) => {
+                    self.done += 1;
+                    self.completed.push((path.clone(), result));
+                }
+                // Cancelled midway: given up on rather than failed
+                Ok(Err(_)) if self.control.is_cancelled() => self.skipped += 1,
+                Ok(Err(error)) => self.failures.push(BatchFailure { path: path.clone(), error }),
+                Err(RecvTimeoutError::Timeout) => return,
+                // The worker stopped for the cancel, leaving the rest
+                Err(RecvTimeoutError::Disconnected) if self.control.is_cancelled() => {
+                    self.skipped += self.in_flight.len();
+                    self.in_flight.clear();
+                    break;
+                }
+                // The worker panicked; what it still held counts as failed so it can be retried
+                Err(RecvTimeoutError::Disconnected) => {
+                    let error = "Stopped unexpectedly".to_string();
+                    self.failures.extend(self.in_flight.drain(..).map(|path| BatchFailure { path, error: error.clone() }));
+                    break;
+                }
+            }
+            self.in_flight.pop_front();
+        }
+        self.results = None;
+    }
+
+    fn spawn_worker(&mut self, file_service: &FileService) {
+        self.in_flight = self.pending.drain(..).collect();
+        let paths: Vec<PathBuf> = self.in_flight.iter().cloned().collect();
+        let (sender, receiver) = channel();
+        self.results = Some(receiver);
+        let worker_service = file_service.for_job(self.control.clone());
+        self.started.get_or_insert_with(Instant::now);
+        let (step, destination, control, measure) = (self.step.clone(), self.destination.clone(), self.control.clone(), self.measure);
+        let (replace, replaced_trash) = (self.replace.clone(), self.replaced_trash.clone());
+        std::thread::spawn(move || {
+            let sizes: Vec<u64> = if measure { paths.iter().map(|path| FileService::data_size(path)).collect() } else { Vec::new() };
+            control.add_bytes_total(sizes.iter().sum());
+            for (index, path) in paths.iter().enumerate() {
+                control.wait_while_paused();
+                if control.is_cancelled() {
+                    return;
+                }
+                let before = control.bytes_done();
+                let result = replace_existing(&worker_service, &replace, replaced_trash.as_deref(), path, &destination)
+                    .and_then(|_| (step.0)(&worker_service, path, &destination))
+                    .map_err(|e| e.to_string());
+                // Renames and failures move no data through the copy loop; count the entry as gone through either way
+                let size = sizes.get(index).copied().unwrap_or(0);
+                control.add_bytes_done(size.saturating_sub(control.bytes_done() - before));
+                // Nobody is listening once the app has quit
+                if sender.send(result).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Queue every failed entry again
+    pub fn retry(&mut self) {
+        self.pending.extend(self.failures.drain(..).map(|failure| failure.path));
+    }
+
+    /// Give up on one failed entry
+    pub fn skip(&mut self, index: usize) {
+        if index < self.failures.len() {
+            self.failures.remove(index);
+            self.skipped += 1;
+        }
+    }
+
+    /// Give up on every failed entry
+    pub fn skip_all(&mut self) {
+        self.skipped += self.failures.len();
+        self.failures.clear();
+    }
+
+    /// Share of the entries processed, whether they went through, failed or were skipped
+    pub fn ratio(&self) -> f64 {
+        let processed = self.done + self.failures.len() + self.skipped;
+        if self.total == 0 { 1.0 } else { processed as f64 / self.total as f64 }
+    }
+
+    /// `Copy 12/40, 1 failed, 2 skipped`, with `paused` while it is held
+    pub fn progress(&self) -> String {
+        let processed = self.done + self.failures.len() + self.skipped;
+        let mut progress = format!("{} {}/{}", self.label, processed, self.total);
+        if !self.failures.is_empty() {
+            progress.push_str(&format!(", {} failed", self.failures.len()));
+        }
+        if self.skipped > 0 {
+            progress.push_str(&format!(", {} skipped", self.skipped));
+        }
+        if self.is_cancelled() {
+            progress.push_str(", cancelled");
+        } else if self.is_running() && self.is_paused() {
+            progress.push_str(", paused");
+        }
+        progress
+    }
+}
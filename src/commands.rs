@@ -0,0 +1,132 @@
+use crate::actions::Action;
+use crate::core::{check_name, parse_age, ClazyfilerError, Result};
+
+/// Commands entered on the `:` command line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Select(String),  // Mark files in the current listing matching a glob
+    CacheStats,      // Report cache memory use in the status bar
+    CacheClear,      // Drop every cached preview
+    NewFile(String),       // Create an empty file in the current directory, or bump the modification time of one by that name
+    Touch,                 // Bump the modification time of the marked entries (or the selected one)
+    NewDirectory(String),  // Create a directory in the current directory
+    Rename(String),        // Give the selected entry a new name in the same directory
+    ChangeDirectory(String),  // Go to a directory, relative to the current one unless absolute or under ~/
+    BulkRename,            // Rename the marked entries (or the selected one) by editing their names in the editor
+    Bookmark(String),      // Bookmark the current directory under a name, for the bookmarks popup (')
+    Connect(String),       // Go to a remote location from the config by name, mounting it first; empty lists them
+    OlderThan(String),     // Review files under the current directory not modified for this long, e.g. `30d`
+    PruneEmpty,            // Remove the empty directories under the current directory after confirming
+    Cleanup,               // Review broken symlinks, empty files and empty directories under the current directory
+    Index,                 // Fuzzy find over the whole tree, even where that is not automatic
+    Version,               // Show build metadata, paths in use and terminal capabilities
+    EditConfig,            // Open config.toml in the editor and apply it on return
+    Action(Action),        // Run an explore mode action by its identifier, as its key would
+}
+
+impl Command {
+    /// For a partially typed `:touch`/`:mkdir`/`:rename`, the command and the name typed so far
+    /// Lets the prompt show name problems before Enter is pressed
+    pub fn new_name(input: &str) -> Option<(&str, &str)> {
+        let input = input.trim_start();
+        let (name, args) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+        match name {
+            "touch" | "mkdir" | "rename" => Some((name, args.trim())),
+            _ => None,
+        }
+    }
+
+    /// For a partially typed `:cd`, the path typed so far, which Tab completes
+    pub fn directory_argument(input: &str) -> Option<&str> {
+        input.trim_start().strip_prefix("cd ").map(str::trim_start)
+    }
+
+    /// For a partially typed `:mkdir`/`:touch`, whether it creates a directory and the name so far
+    pub fn creation(input: &str) -> Option<(bool, &str)> {
+        match Self::new_name(input)? {
+            ("mkdir", name) => Some((true, name)),
+            ("touch", name) => Some((false, name)),
+            _ => None,
+        }
+    }
+
+    /// Replace the name in a naming command line with its sanitized suggestion
+    pub fn with_sanitized_name(input: &str) -> Option<String> {
+        let (command, name) = Self::new_name(input)?;
+        let problem = check_name(name).filter(|_| !name.is_empty())?;
+        Some(format!("{} {}", command, problem.suggestion))
+    }
+
+    /// Parse a command line (without the leading ':')
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let (name, args) = match input.split_once(char::is_whitespace) {
+            Some((name, args)) => (name, args.trim()),
+            None => (input, ""),
+        };
+
+        match name {
+            "select" | "sel" => {
+                if args.is_empty() {
+                    Err(ClazyfilerError::command(input, "Usage: select <glob>"))
+                } else {
+                    Ok(Command::Select(args.to_string()))
+                }
+            }
+            "cache" => match args {
+                "stats" | "" => Ok(Command::CacheStats),
+                "clear" => Ok(Command::CacheClear),
+                _ => Err(ClazyfilerError::command(input, "Usage: cache [stats|clear]")),
+            },
+            "touch" if args.is_empty() => Ok(Command::Touch),
+            "touch" | "mkdir" => {
+                if args.is_empty() {
+                    Err(ClazyfilerError::command(input, &format!("Usage: {} <name>", name)))
+                } else if name == "mkdir" {
+                    Ok(Command::NewDirectory(args.to_string()))
+                } else {
+                    Ok(Command::NewFile(args.to_string()))
+                }
+            }
+            "cleanup" => Ok(Command::Cleanup),
+            "prune-empty" => Ok(Command::PruneEmpty),
+            "older" => match parse_age(args) {
+                Some(_) => Ok(Command::OlderThan(args.to_string())),
+                None => Err(ClazyfilerError::command(input, "Usage: older <age>, e.g. older 30d (s, m, h, d or w)")),
+            },
+            "index" => Ok(Command::Index),
+            "version" | "about" => Ok(Command::Version),
+            "config" => Ok(Command::EditConfig),
+            "action" => match Action::from_id(args) {
+                Some(action) => Ok(Command::Action(action)),
+                None if args.is_empty() => Err(ClazyfilerError::command(input, "Usage: action <id>, e.g. action nav.parent")),
+                None => Err(ClazyfilerError::command(input, "Unknown action")),
+            },
+            "rename" => {
+                if args.is_empty() {
+                    Err(ClazyfilerError::command(input, "Usage: rename <new name>"))
+                } else {
+                    Ok(Command::Rename(args.to_string()))
+                }
+            }
+            "cd" => {
+                if args.is_empty() {
+                    Err(ClazyfilerError::command(input, "Usage: cd <directory>"))
+                } else {
+                    Ok(Command::ChangeDirectory(args.to_string()))
+                }
+            }
+            "bulkrename" | "vidir" => Ok(Command::BulkRename),
+            "connect" => Ok(Command::Connect(args.to_string())),
+            "bookmark" => {
+                if args.is_empty() {
+                    Err(ClazyfilerError::command(input, "Usage: bookmark <name>"))
+                } else {
+                    Ok(Command::Bookmark(args.to_string()))
+                }
+            }
+            "" => Err(ClazyfilerError::command(input, "Empty command")),
+            _ => Err(ClazyfilerError::command(input, "Unknown command")),
+        }
+    }
+}
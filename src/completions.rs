@@ -0,0 +1,157 @@
+use std::fmt::Write;
+
+/// A command-line flag, as offered by the completion scripts
+pub struct Flag {
+    pub long: &'static str,
+    pub short: Option<char>,
+    pub description: &'static str,
+}
+
+/// Every flag `main` accepts; keep in sync with its USAGE text
+pub const FLAGS: &[Flag] = &[
+    Flag { long: "profile-startup", short: None, description: "Log startup timing and print it on exit" },
+    Flag { long: "no-alt-screen", short: None, description: "Run inline in the scrollback" },
+    Flag { long: "single-instance", short: None, description: "Reveal PATH in a running instance instead" },
+    Flag { long: "version", short: Some('V'), description: "Print the version" },
+    Flag { long: "about", short: None, description: "Print build metadata and paths" },
+    Flag { long: "help", short: Some('h'), description: "Show help" },
+];
+
+/// Subcommands, which are only recognized as the first argument
+pub const SUBCOMMANDS: &[(&str, &str)] = &[
+    ("completions", "Print a shell completion script"),
+];
+
+/// Shells `clazyfiler completions <shell>` can generate a script for
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish", "powershell"];
+
+/// Completion script for `shell`, or None if it isn't one of SHELLS
+pub fn script(shell: &str) -> Option<String> {
+    match shell {
+        "bash" => Some(bash()),
+        "zsh" => Some(zsh()),
+        "fish" => Some(fish()),
+        "powershell" => Some(powershell()),
+        _ => None,
+    }
+}
+
+/// Every spelling of every flag, with its description
+fn flag_words() -> Vec<(String, &'static str)> {
+    FLAGS.iter()
+        .flat_map(|flag| {
+            let short = flag.short.map(|short| format!("-{}", short));
+            short.into_iter().chain([format!("--{}", flag.long)]).map(|word| (word, flag.description))
+        })
+        .collect()
+}
+
+fn bash() -> String {
+    let subcommands: Vec<&str> = SUBCOMMANDS.iter().map(|(name, _)| *name).collect();
+    format!(r#"_clazyfiler() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    if [[ $COMP_CWORD -eq 2 && ${{COMP_WORDS[1]}} == completions ]]; then
+        COMPREPLY=($(compgen -W "{shells}" -- "$cur"))
+        return
+    fi
+    if [[ $cur == -* ]]; then
+        COMPREPLY=($(compgen -W "{flags}" -- "$cur"))
+        return
+    fi
+    COMPREPLY=()
+    if [[ $COMP_CWORD -eq 1 ]]; then
+        COMPREPLY=($(compgen -W "{subcommands}" -- "$cur"))
+    fi
+    COMPREPLY+=($(compgen -f -- "$cur"))
+}}
+complete -o filenames -F _clazyfiler clazyfiler
+"#,
+        shells = SHELLS.join(" "),
+        flags = flag_words().into_iter().map(|(word, _)| word).collect::<Vec<_>>().join(" "),
+        subcommands = subcommands.join(" "),
+    )
+}
+
+fn zsh() -> String {
+    let mut arguments = String::new();
+    for flag in FLAGS {
+        let description = flag.description.replace('\'', "'\\''");
+        match flag.short {
+            Some(short) => writeln!(arguments, "        '(-{short} --{long})'{{-{short},--{long}}}'[{description}]' \\", short = short, long = flag.long, description = description),
+            None => writeln!(arguments, "        '--{}[{}]' \\", flag.long, description),
+        }.unwrap();
+    }
+    let subcommands: Vec<String> = SUBCOMMANDS.iter()
+        .map(|(name, description)| format!("{}\\:\"{}\"", name, description))
+        .collect();
+    format!(r#"#compdef clazyfiler
+
+_clazyfiler() {{
+    if [[ ${{words[2]}} == completions ]]; then
+        (( CURRENT == 3 )) && _values 'shell' {shells}
+        return
+    fi
+    _arguments \
+{arguments}        '1: :_clazyfiler_first'
+}}
+
+_clazyfiler_first() {{
+    _alternative 'subcommands:subcommand:(({subcommands}))' 'files:path:_files'
+}}
+
+if [ "$funcstack[1]" = "_clazyfiler" ]; then
+    _clazyfiler "$@"
+else
+    compdef _clazyfiler clazyfiler
+fi
+"#,
+        shells = SHELLS.join(" "),
+        arguments = arguments,
+        subcommands = subcommands.join(" "),
+    )
+}
+
+fn fish() -> String {
+    let mut script = String::new();
+    for (name, description) in SUBCOMMANDS {
+        writeln!(script, "complete -c clazyfiler -n '__fish_use_subcommand' -a {} -d '{}'", name, fish_quote(description)).unwrap();
+    }
+    writeln!(script, "complete -c clazyfiler -n '__fish_seen_subcommand_from completions' -f -a '{}'", SHELLS.join(" ")).unwrap();
+    for flag in FLAGS {
+        let short = flag.short.map(|short| format!(" -s {}", short)).unwrap_or_default();
+        writeln!(script, "complete -c clazyfiler{} -l {} -d '{}'", short, flag.long, fish_quote(flag.description)).unwrap();
+    }
+    script
+}
+
+fn fish_quote(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+fn powershell() -> String {
+    let candidates: Vec<String> = flag_words().into_iter()
+        .chain(SUBCOMMANDS.iter().map(|(name, description)| (name.to_string(), *description)))
+        .map(|(word, description)| format!("        @('{}', '{}')", word, description.replace('\'', "''")))
+        .collect();
+    let shells: Vec<String> = SHELLS.iter().map(|shell| format!("'{}'", shell)).collect();
+    format!(r#"Register-ArgumentCompleter -Native -CommandName clazyfiler -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $words = @($commandAst.CommandElements | ForEach-Object {{ $_.ToString() }})
+    if ($words.Count -ge 2 -and $words[1] -eq 'completions') {{
+        {shells} | Where-Object {{ $_ -like "$wordToComplete*" }} | ForEach-Object {{
+            [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+        }}
+        return
+    }}
+    $candidates = @(
+{candidates}
+    )
+    $candidates | Where-Object {{ $_[0] -like "$wordToComplete*" }} | ForEach-Object {{
+        [System.Management.Automation.CompletionResult]::new($_[0], $_[0], 'ParameterName', $_[1])
+    }}
+}}
+"#,
+        shells = shells.join(", "),
+        candidates = candidates.join(",\n"),
+    )
+}
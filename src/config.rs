@@ -0,0 +1,464 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use ratatui::style::Color;
+use serde::Deserialize;
+use crate::core::{parse_mode, ClazyfilerError, Result, RetryPolicy};
+use crate::model::GroupBy;
+
+/// User configuration loaded from `~/.config/clazyfiler/config.toml`
+/// Every section is optional; missing values fall back to defaults
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub general: General,
+    pub external_commands: ExternalCommands,
+    pub encryption: Encryption,
+    pub disk: Disk,
+    pub cache: Cache,
+    pub permissions: Permissions,
+    pub explore: Explore,
+    pub hooks: Hooks,
+    pub downloads: Downloads,
+    pub bookmarks: BTreeMap<String, PathBuf>,  // Directories by a one-character key, for the bookmarks popup (')
+    pub connections: BTreeMap<String, Connection>,  // Remote locations by name, for :connect
+    pub accessibility: Accessibility,
+    pub accents: Accents,
+    pub screen: Screen,
+    pub status_bar: StatusBar,
+    pub instance: Instance,
+    pub keys: Keys,
+}
+
+/// Behaviour of file operations
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct General {
+    pub trash: bool,                  // Deleting moves entries to the trash; false removes them for good
+    pub protected_paths: Vec<String>,  // Deleting in or below these asks to type the directory's name
+    pub secure_delete: bool,           // Allow shredding files: overwriting them before they are removed
+}
+
+impl Default for General {
+    fn default() -> Self {
+        Self { trash: true, protected_paths: Vec::new(), secure_delete: false }
+    }
+}
+
+/// External command integration
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ExternalCommands {
+    pub file_manager: String,  // Launched on the selected file or current directory
+    pub browser: String,       // Opens URLs found in previews
+}
+
+impl Default for ExternalCommands {
+    fn default() -> Self {
+        Self {
+            file_manager: default_file_manager().to_string(),
+            browser: default_file_manager().to_string(),
+        }
+    }
+}
+
+/// Keys used to decrypt and re-encrypt `.age` files (GPG uses its own keyring)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Encryption {
+    pub age_identity: Option<PathBuf>,  // Defaults to ~/.config/age/keys.txt when present
+}
+
+/// Free-space warnings for the filesystem of the current directory
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Disk {
+    pub usage_warning_percent: f64,     // Warn in the status bar once usage reaches this
+    pub network_retries: u32,           // Retries of reads failing with EIO/EAGAIN on network filesystems
+    pub network_retry_backoff_ms: u64,  // Wait before the first retry, doubled for each further one
+    pub background_io_limit_mb: f64,    // MB/s shared by copies and checksum verification, 0 for no limit
+}
+
+impl Default for Disk {
+    fn default() -> Self {
+        Self { usage_warning_percent: 90.0, network_retries: 3, network_retry_backoff_ms: 100, background_io_limit_mb: 0.0 }
+    }
+}
+
+impl Disk {
+    pub fn network_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy { retries: self.network_retries, backoff: Duration::from_millis(self.network_retry_backoff_ms) }
+    }
+
+    /// Bytes per second for the background I/O throttle, 0 for no limit
+    pub fn background_io_limit(&self) -> u64 {
+        (self.background_io_limit_mb.max(0.0) * 1024.0 * 1024.0) as u64
+    }
+}
+
+/// Memory shared by all preview caches (thumbnails, parsed files, fuzzy index)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Cache {
+    pub memory_budget_mb: usize,  // Least recently used entries are evicted beyond this
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self { memory_budget_mb: 256 }
+    }
+}
+
+/// Directory listing behaviour
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Explore {
+    pub project_view: bool,     // Start with gitignored entries hidden (toggled with 'I')
+    pub collapse_chains: bool,  // Show `src/main/java/com` as one entry when each level has a single subdirectory
+    pub group_by: GroupBy,      // "none", "kind", "extension" or "git" (cycled with 'g')
+    pub readme_preview: bool,   // Preview a selected directory's README instead of its children (toggled with 'v')
+    pub permission_badges: bool,  // Flag setuid, setgid and world-writable entries in the detailed view
+}
+
+impl Default for Explore {
+    fn default() -> Self {
+        Self { project_view: false, collapse_chains: true, group_by: GroupBy::None, readme_preview: true, permission_badges: true }
+    }
+}
+
+/// How the UI takes over the terminal
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Screen {
+    pub alternate: bool,     // Full screen on the alternate screen; false runs inline in the scrollback (--no-alt-screen)
+    pub inline_height: u16,  // Lines of the inline viewport
+}
+
+impl Default for Screen {
+    fn default() -> Self {
+        Self { alternate: true, inline_height: 20 }
+    }
+}
+
+/// The single line at the bottom, built from segments shown left to right
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StatusBar {
+    pub segments: Vec<StatusSegment>,  // Unknown names are refused; segments with nothing to say are left out
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self { segments: StatusSegment::DEFAULT.to_vec() }
+    }
+}
+
+/// A piece of the status bar, by its name in `[status_bar] segments`
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusSegment {
+    Mode,       // What keys do now: EXPLORE, SEARCH, ...
+    Path,       // The current directory
+    Items,      // How many entries are listed, or in accessibility mode the selected one and its position
+    Marks,
+    Project,    // Project view and how many entries it hides
+    Jobs,       // Progress of a running copy, move or delete, then where the last one landed
+    Downloads,  // New arrivals in the downloads directory
+    Network,    // The network filesystem of the current directory
+    Disk,       // Usage of the current filesystem once above usage_warning_percent
+    Git,        // Branch of the work tree the current directory is in
+    Clock,
+    Message,    // The latest status message
+}
+
+impl StatusSegment {
+    /// What the status bar shows without configuration
+    pub const DEFAULT: &'static [StatusSegment] = &[
+        StatusSegment::Mode,
+        StatusSegment::Items,
+        StatusSegment::Marks,
+        StatusSegment::Project,
+        StatusSegment::Jobs,
+        StatusSegment::Downloads,
+        StatusSegment::Network,
+        StatusSegment::Disk,
+        StatusSegment::Message,
+    ];
+}
+
+/// Commands run after file operations, e.g. `after_create = "git add {name}"`
+/// Words are passed as arguments without a shell; `{path}` is the resulting entry, `{name}` its name,
+/// `{dir}` its directory (where the command runs) and `{old}` the entry it came from (rename, copy, move)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Hooks {
+    pub after_create: Option<String>,  // :touch and :mkdir
+    pub after_rename: Option<String>,
+    pub after_copy: Option<String>,    // Each pasted copy
+    pub after_move: Option<String>,    // Each pasted cut entry
+    pub after_delete: Option<String>,  // {path} no longer exists by then
+}
+
+/// The downloads panel ('W')
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Downloads {
+    pub dir: Option<PathBuf>,  // Defaults to the platform's downloads directory (~/Downloads)
+}
+
+/// Launching `clazyfiler <path>` while another instance runs reveals the path there instead of opening a second UI
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Instance {
+    pub single: bool,  // Also enabled with --single-instance
+}
+
+/// Extra keys per mode, each bound to an action of that mode or standing for a built-in key:
+/// `"ctrl-n" = "nav.down"` or `"ctrl-n" = "j"`
+/// Keys the mode already uses are refused rather than shadowed, as are two spellings of one key
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Keys {
+    pub explore: BTreeMap<String, String>,
+    pub grid: BTreeMap<String, String>,
+    pub marks: BTreeMap<String, String>,
+}
+
+/// Screen-reader and low-vision friendly rendering
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Accessibility {
+    pub enabled: bool,  // No emoji, monochrome high-contrast colors, textual markers and a predictable status line
+}
+
+/// Color of each mode, on the file list border, the search bar border and the mode segment of the status bar
+/// Names like "lightblue", "#5f87ff" or a 256-color index; accessibility mode drops them with every other color
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Accents {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub explore: Color,  // Also the image grid
+    #[serde(deserialize_with = "deserialize_color")]
+    pub visual: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub search: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub fuzzy: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub command: Color,  // Also the rename and archive name prompts
+    #[serde(deserialize_with = "deserialize_color")]
+    pub popup: Color,    // Every mode with a popup of its own: marks, bookmarks, properties, ...
+}
+
+impl Default for Accents {
+    fn default() -> Self {
+        Self {
+            explore: Color::Yellow,
+            visual: Color::LightMagenta,
+            search: Color::Green,
+            fuzzy: Color::Cyan,
+            command: Color::Magenta,
+            popup: Color::LightBlue,
+        }
+    }
+}
+
+fn deserialize_color<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Color, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    text.parse().map_err(|_| serde::de::Error::custom(format!("invalid color '{}'", text)))
+}
+
+/// A remote location opened by name with `:connect`, mounted first when it is not yet
+/// Credentials stay with the mount tool (ssh keys and agent, credential files), never in the config
+#[derive(Debug, Clone, Deserialize)]
+pub struct Connection {
+    pub path: PathBuf,          // Where the remote is mounted
+    pub mount: Option<String>,  // Run without a shell while `path` is not a mount point; {path} is filled in
+}
+
+/// Modes for entries created with `:touch`/`:mkdir`, as octal strings like "0640"
+/// Unset modes follow the umask (0666/0777 minus umask); set ones are applied exactly
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Permissions {
+    #[serde(deserialize_with = "deserialize_mode")]
+    pub file_mode: Option<u32>,
+    #[serde(deserialize_with = "deserialize_mode")]
+    pub dir_mode: Option<u32>,
+}
+
+fn deserialize_mode<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<u32>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    parse_mode(&text)
+        .map(Some)
+        .ok_or_else(|| serde::de::Error::custom(format!("invalid octal mode '{}'", text)))
+}
+
+/// Platform default for opening files, directories and URLs
+fn default_file_manager() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    }
+}
+
+/// Contents of a new config file: every setting at its default, commented out
+fn default_file() -> String {
+    format!(r#"# clazyfiler configuration
+# Every setting is optional; uncomment a line to change it. Saved changes apply when :config returns.
+
+[general]
+# trash = true   # Deleting ('x') moves entries to the trash; false removes them for good
+# protected_paths = ["/etc", "~/production"]   # Deleting in or below these asks to type the directory's name
+# secure_delete = false   # Allow shredding files (':action file.shred'); refused on copy-on-write filesystems
+
+[external_commands]
+# file_manager = "{opener}"   # Launched on the selected file or current directory ('o'/'O')
+# browser = "{opener}"        # Opens URLs found in previews
+
+[encryption]
+# age_identity = "/path/to/keys.txt"   # Defaults to ~/.config/age/keys.txt when present
+
+[disk]
+# usage_warning_percent = 90.0     # Warn in the status bar once usage reaches this
+# network_retries = 3              # Retries of reads failing with EIO/EAGAIN on network filesystems
+# network_retry_backoff_ms = 100   # Wait before the first retry, doubled for each further one
+# background_io_limit_mb = 0       # MB/s shared by copies and checksum verification, 0 for no limit
+
+[cache]
+# memory_budget_mb = 256   # Memory shared by all preview caches
+
+[permissions]
+# file_mode = "0644"   # Modes for :touch and :mkdir; unset follows the umask
+# dir_mode = "0755"
+
+[explore]
+# project_view = false     # Start with gitignored entries hidden ('I')
+# collapse_chains = true   # Show single-subdirectory chains as one entry
+# group_by = "none"        # "none", "kind", "extension" or "git" ('g')
+# readme_preview = true    # Preview a selected directory's README instead of its children ('v')
+# permission_badges = true   # Flag setuid, setgid and world-writable entries in the detailed view ('c')
+
+[hooks]
+# Commands run after an operation, without a shell, in the entry's directory; placeholders are
+# {{path}}, {{name}}, {{dir}} and {{old}} (the entry renamed, copied or moved from)
+# after_create = "git add {{name}}"
+# after_rename = ""
+# after_copy = ""
+# after_move = ""
+# after_delete = ""
+
+[downloads]
+# dir = "/path/to/Downloads"   # Watched for the downloads panel ('W'); defaults to ~/Downloads
+
+[bookmarks]
+# Directories by a one-character key: ' then the key jumps there, ' v then the key moves the
+# selected or marked entries there ('v' and 'q' can't be bookmarks); 's' offers them to copy or move to.
+# m then a key bookmarks the current directory from the file list, saved to bookmarks.tsv next to this file
+# d = "/path/to/Documents"
+
+[connections]
+# Remote locations by name: :connect <name> runs the mount command (on the terminal, so it can ask for
+# a password) unless path is mounted already, then goes there. Credentials stay with ssh or the mount tool
+# prod-sftp = {{ path = "/mnt/prod", mount = "sshfs deploy@prod.example.com:/srv {{path}}" }}
+
+[accessibility]
+# enabled = false   # No emoji, high-contrast colors and textual markers
+
+[accents]
+# Color of each mode on the borders and the status bar: a name like "lightblue", hex like #5f87ff or a 256-color index
+# explore = "yellow"   # Also the image grid
+# visual = "lightmagenta"
+# search = "green"
+# fuzzy = "cyan"
+# command = "magenta"   # Also the rename and archive prompts
+# popup = "lightblue"   # Modes with a popup of their own
+
+[status_bar]
+# Shown left to right; also "path" (current directory), "git" (branch) and "clock"
+# segments = ["mode", "items", "marks", "project", "jobs", "downloads", "network", "disk", "message"]
+
+# Extra keys per mode (explore, grid, marks), bound to an action or to a built-in key they stand for;
+# keys a mode already uses are refused
+[keys.explore]
+# "ctrl-n" = "nav.down"
+# "ctrl-p" = "k"
+
+# The settings below are read at startup only
+[screen]
+# alternate = true     # false runs inline in the scrollback (--no-alt-screen)
+# inline_height = 20
+
+[instance]
+# single = false   # Reveal paths in a running instance (--single-instance)
+"#,
+        opener = default_file_manager(),
+    )
+}
+
+impl Config {
+    /// Load configuration from the default location, using defaults if the file does not exist
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::config_path() else {
+            return Ok(Self::default());
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).map_err(|e| {
+                ClazyfilerError::config(&format!("Failed to parse {}: {}", path.display(), e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(ClazyfilerError::file_system("read_config", path.to_string_lossy().as_ref(), e)),
+        }
+    }
+
+    /// Create the config file with every setting commented out at its default, unless it exists
+    /// Returns the path, for opening it in the editor
+    pub fn create_if_missing() -> Result<PathBuf> {
+        let path = Self::config_path()
+            .ok_or_else(|| ClazyfilerError::config("No config directory (neither $XDG_CONFIG_HOME nor a home directory)"))?;
+        if !path.exists() {
+            let error = |e| ClazyfilerError::file_system("create_config", path.to_string_lossy().as_ref(), e);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(error)?;
+            }
+            std::fs::write(&path, default_file()).map_err(error)?;
+        }
+        Ok(path)
+    }
+
+    /// Directory for caches ($XDG_CACHE_HOME/clazyfiler or the platform equivalent)
+    pub fn cache_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("clazyfiler"))
+    }
+
+    /// Directory for state worth keeping, like the open history ($XDG_DATA_HOME/clazyfiler or the platform equivalent)
+    pub fn data_dir() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("clazyfiler"))
+    }
+
+    /// The FreeDesktop trash in the user's home ($XDG_DATA_HOME/Trash)
+    pub fn trash_dir() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("Trash"))
+    }
+
+    /// Socket of the single-instance mode ($XDG_RUNTIME_DIR/clazyfiler, falling back to the cache dir)
+    pub fn instance_socket_path() -> Option<PathBuf> {
+        dirs::runtime_dir()
+            .map(|dir| dir.join("clazyfiler"))
+            .or_else(Self::cache_dir)
+            .map(|dir| dir.join("instance.sock"))
+    }
+
+    /// Path of the config file ($XDG_CONFIG_HOME/clazyfiler/config.toml, falling back to ~/.config)
+    pub fn config_path() -> Option<PathBuf> {
+        let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".config")))?;
+        Some(config_dir.join("clazyfiler").join("config.toml"))
+    }
+}
@@ -0,0 +1,69 @@
+/// Version the kernel writes at the start of `system.posix_acl_access` and `system.posix_acl_default`
+const ACL_XATTR_VERSION: u32 = 2;
+
+/// Who a POSIX ACL entry grants permissions to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AclTag {
+    Owner,       // The owning user, mirroring the user bits of the mode
+    User(u32),   // A named user
+    OwningGroup,
+    Group(u32),  // A named group
+    Mask,        // Upper limit for named users and all groups
+    Other,
+}
+
+/// One `tag:qualifier:rwx` line as `getfacl` prints it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AclEntry {
+    pub tag: AclTag,
+    pub permissions: u8,  // rwx as 4, 2 and 1
+}
+
+impl AclEntry {
+    /// `user:alice:rw-`, with `name` giving user and group names for the ids of named entries
+    pub fn describe(&self, name: impl Fn(&AclTag) -> Option<String>) -> String {
+        let qualifier = |id: u32| name(&self.tag).unwrap_or_else(|| id.to_string());
+        let (kind, qualifier) = match self.tag {
+            AclTag::Owner => ("user", String::new()),
+            AclTag::User(id) => ("user", qualifier(id)),
+            AclTag::OwningGroup => ("group", String::new()),
+            AclTag::Group(id) => ("group", qualifier(id)),
+            AclTag::Mask => ("mask", String::new()),
+            AclTag::Other => ("other", String::new()),
+        };
+        let bit = |mask: u8, letter: char| if self.permissions & mask != 0 { letter } else { '-' };
+        format!("{}:{}:{}{}{}", kind, qualifier, bit(4, 'r'), bit(2, 'w'), bit(1, 'x'))
+    }
+}
+
+/// Entries of an ACL extended attribute: a little-endian version word, then 8 bytes per entry
+/// (tag, permissions, id); None for data the kernel would not have written
+pub fn parse_acl(data: &[u8]) -> Option<Vec<AclEntry>> {
+    let (header, entries) = data.split_at_checked(4)?;
+    if u32::from_le_bytes(header.try_into().ok()?) != ACL_XATTR_VERSION || entries.len() % 8 != 0 {
+        return None;
+    }
+    entries
+        .chunks_exact(8)
+        .map(|entry| {
+            let tag = u16::from_le_bytes([entry[0], entry[1]]);
+            let permissions = u16::from_le_bytes([entry[2], entry[3]]) as u8 & 0o7;
+            let id = u32::from_le_bytes([entry[4], entry[5], entry[6], entry[7]]);
+            let tag = match tag {
+                0x01 => AclTag::Owner,
+                0x02 => AclTag::User(id),
+                0x04 => AclTag::OwningGroup,
+                0x08 => AclTag::Group(id),
+                0x10 => AclTag::Mask,
+                0x20 => AclTag::Other,
+                _ => return None,
+            };
+            Some(AclEntry { tag, permissions })
+        })
+        .collect()
+}
+
+/// Whether an ACL grants more than the mode bits already say, i.e. has named entries or a mask
+pub fn is_extended_acl(entries: &[AclEntry]) -> bool {
+    entries.iter().any(|entry| matches!(entry.tag, AclTag::User(_) | AclTag::Group(_) | AclTag::Mask))
+}
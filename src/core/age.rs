@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+/// An age as typed for `:older`: a number and a unit, `s`, `m`, `h`, `d` (the default) or `w`
+/// `30d`, `12h` and `2w` are accepted, so is a bare `30` for days
+pub fn parse_age(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: u64 = number.parse().ok()?;
+    let seconds = match unit.trim() {
+        "s" => 1,
+        "m" | "min" => 60,
+        "h" => 3600,
+        "" | "d" => 86_400,
+        "w" => 7 * 86_400,
+        _ => return None,
+    };
+    Some(Duration::from_secs(number.checked_mul(seconds)?))
+}
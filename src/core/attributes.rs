@@ -0,0 +1,27 @@
+/// Linux inode flags shown by `lsattr` and set with `chattr`: bit, chattr letter and what it means
+pub const ATTRIBUTES: &[(u32, char, &str)] = &[
+    (0x0000_0010, 'i', "immutable"),
+    (0x0000_0020, 'a', "append-only"),
+    (0x0000_0040, 'd', "no dump"),
+    (0x0000_0080, 'A', "no atime"),
+    (0x0000_0008, 'S', "synchronous"),
+    (0x0000_0004, 'c', "compressed"),
+    (0x0000_0001, 's', "secure deletion"),
+    (0x0000_0002, 'u', "undeletable"),
+    (0x0080_0000, 'C', "no copy-on-write"),
+];
+
+/// Names of the attributes set in `flags`, in the order above
+pub fn attribute_names(flags: u32) -> Vec<&'static str> {
+    ATTRIBUTES.iter().filter(|(bit, _, _)| flags & bit != 0).map(|(_, _, name)| *name).collect()
+}
+
+/// Whether the attribute set with chattr letter `letter` is in `flags`
+pub fn has_attribute(flags: u32, letter: char) -> bool {
+    ATTRIBUTES.iter().any(|(bit, flag, _)| *flag == letter && flags & bit != 0)
+}
+
+/// What the attribute with chattr letter `letter` means
+pub fn attribute_name(letter: char) -> Option<&'static str> {
+    ATTRIBUTES.iter().find(|(_, flag, _)| *flag == letter).map(|(_, _, name)| *name)
+}
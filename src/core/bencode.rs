@@ -0,0 +1,121 @@
+/// A decoded bencode value (the BitTorrent serialization format)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Bencode {
+    Int(i64),
+    Bytes(Vec<u8>),
+    List(Vec<Bencode>),
+    Dict(Vec<(Vec<u8>, Bencode)>),
+}
+
+/// Guard against deeply nested crafted input
+const MAX_DEPTH: usize = 64;
+
+impl Bencode {
+    /// Decode a complete bencoded document
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let (value, end) = decode(data, 0, 0)?;
+        (end == data.len()).then_some(value)
+    }
+
+    /// Value of `key` in a dict
+    pub fn get(&self, key: &str) -> Option<&Bencode> {
+        match self {
+            Bencode::Dict(entries) => entries.iter().find(|(k, _)| k == key.as_bytes()).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Bencode::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Bencode::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Byte string as (lossy) UTF-8
+    pub fn as_str(&self) -> Option<String> {
+        self.as_bytes().map(|bytes| String::from_utf8_lossy(bytes).to_string())
+    }
+
+    pub fn as_list(&self) -> Option<&[Bencode]> {
+        match self {
+            Bencode::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_dict(&self) -> Option<&[(Vec<u8>, Bencode)]> {
+        match self {
+            Bencode::Dict(entries) => Some(entries),
+            _ => None,
+        }
+    }
+}
+
+/// Raw encoded bytes of `key` in a top-level dict, needed to hash a torrent's `info` exactly as stored
+pub fn bencode_dict_value_raw<'a>(data: &'a [u8], key: &str) -> Option<&'a [u8]> {
+    if data.first()? != &b'd' {
+        return None;
+    }
+    let mut pos = 1;
+    while data.get(pos)? != &b'e' {
+        let (entry_key, value_start) = decode(data, pos, 1)?;
+        let (_, value_end) = decode(data, value_start, 1)?;
+        if entry_key.as_bytes() == Some(key.as_bytes()) {
+            return data.get(value_start..value_end);
+        }
+        pos = value_end;
+    }
+    None
+}
+
+/// Decode the value starting at `pos`, returning it and the position just past it
+fn decode(data: &[u8], pos: usize, depth: usize) -> Option<(Bencode, usize)> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    match data.get(pos)? {
+        b'i' => {
+            let end = pos + 1 + data.get(pos + 1..)?.iter().position(|&b| b == b'e')?;
+            let value = std::str::from_utf8(&data[pos + 1..end]).ok()?.parse().ok()?;
+            Some((Bencode::Int(value), end + 1))
+        }
+        b'l' => {
+            let mut items = Vec::new();
+            let mut pos = pos + 1;
+            while data.get(pos)? != &b'e' {
+                let (item, next) = decode(data, pos, depth + 1)?;
+                items.push(item);
+                pos = next;
+            }
+            Some((Bencode::List(items), pos + 1))
+        }
+        b'd' => {
+            let mut entries = Vec::new();
+            let mut pos = pos + 1;
+            while data.get(pos)? != &b'e' {
+                let (key, value_start) = decode(data, pos, depth + 1)?;
+                let Bencode::Bytes(key) = key else { return None };
+                let (value, next) = decode(data, value_start, depth + 1)?;
+                entries.push((key, value));
+                pos = next;
+            }
+            Some((Bencode::Dict(entries), pos + 1))
+        }
+        b'0'..=b'9' => {
+            let colon = pos + data.get(pos..)?.iter().position(|&b| b == b':')?;
+            let len: usize = std::str::from_utf8(&data[pos..colon]).ok()?.parse().ok()?;
+            let start = colon + 1;
+            let bytes = data.get(start..start.checked_add(len)?)?;
+            Some((Bencode::Bytes(bytes.to_vec()), start + len))
+        }
+        _ => None,
+    }
+}
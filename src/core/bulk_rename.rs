@@ -0,0 +1,60 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use crate::core::{check_name, ClazyfilerError, Result};
+
+/// How an entry is listed in the file edited for a bulk rename: relative to `base` when it is inside
+pub fn bulk_rename_line(base: &Path, path: &Path) -> String {
+    path.strip_prefix(base).unwrap_or(path).to_string_lossy().into_owned()
+}
+
+/// Renames asked for by editing the lines `bulk_rename_line` wrote for `paths`, one line per entry in order
+/// Unchanged lines are left out. Fails without renaming anything when lines were added or removed, a name
+/// is invalid or would move the entry elsewhere, two entries get the same name, or a name is taken by an
+/// entry that stays (`taken` tells whether something exists at a path)
+pub fn bulk_rename_plan(base: &Path, paths: &[PathBuf], edited: &str, taken: impl Fn(&Path) -> bool) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let error = |message: String| ClazyfilerError::content("bulk rename", &message);
+    let lines: Vec<&str> = edited.lines().collect();
+    // Editors often leave a trailing empty line
+    let lines = match lines.split_last() {
+        Some((last, rest)) if last.trim().is_empty() && rest.len() == paths.len() => rest,
+        _ => &lines[..],
+    };
+    if lines.len() != paths.len() {
+        return Err(error(format!("Expected {} lines, got {}; lines must not be added or removed", paths.len(), lines.len())));
+    }
+
+    let mut renames = Vec::new();
+    for (path, line) in paths.iter().zip(lines) {
+        if *line == bulk_rename_line(base, path) {
+            continue;
+        }
+        if line.trim().is_empty() {
+            return Err(error(format!("{}: the new name is empty", bulk_rename_line(base, path))));
+        }
+        let target = base.join(line);
+        let name = target.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        if let Some(problem) = check_name(&name).filter(|problem| problem.fatal) {
+            return Err(error(format!("{}: {}", line, problem.message)));
+        }
+        if target.parent() != path.parent() {
+            return Err(error(format!("{}: only the name can change, not the directory", line)));
+        }
+        renames.push((path.clone(), target));
+    }
+
+    let mut targets = HashSet::new();
+    for (_, target) in &renames {
+        if !targets.insert(target) {
+            return Err(error(format!("{} is given to more than one entry", target.display())));
+        }
+    }
+    // A name is free if its entry is renamed away; a case-only rename finds its own entry on some filesystems
+    let moving_away: HashSet<&PathBuf> = renames.iter().map(|(source, _)| source).collect();
+    for (source, target) in &renames {
+        let same_entry = source.to_string_lossy().to_lowercase() == target.to_string_lossy().to_lowercase();
+        if !moving_away.contains(target) && !same_entry && taken(target) {
+            return Err(error(format!("{} already exists", target.display())));
+        }
+    }
+    Ok(renames)
+}
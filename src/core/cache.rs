@@ -0,0 +1,184 @@
+use std::borrow::Borrow;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Recency clock shared by every cache, so the least recently used entry can be found across caches
+static CLOCK: AtomicU64 = AtomicU64::new(0);
+
+fn tick() -> u64 {
+    CLOCK.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Approximate memory held by a cached key or value, in bytes
+/// Estimates only need to be good enough to keep the total near the configured budget
+pub trait CacheWeight {
+    fn weight(&self) -> usize;
+}
+
+impl CacheWeight for String {
+    fn weight(&self) -> usize {
+        std::mem::size_of::<Self>() + self.capacity()
+    }
+}
+
+impl CacheWeight for PathBuf {
+    fn weight(&self) -> usize {
+        std::mem::size_of::<Self>() + self.as_os_str().len()
+    }
+}
+
+impl<T: CacheWeight> CacheWeight for Option<T> {
+    fn weight(&self) -> usize {
+        std::mem::size_of::<Self>() + self.as_ref().map(CacheWeight::weight).unwrap_or(0)
+    }
+}
+
+impl<T: CacheWeight, E: CacheWeight> CacheWeight for std::result::Result<T, E> {
+    fn weight(&self) -> usize {
+        match self {
+            Ok(value) => value.weight(),
+            Err(error) => error.weight(),
+        }
+    }
+}
+
+impl<T: CacheWeight> CacheWeight for Vec<T> {
+    fn weight(&self) -> usize {
+        std::mem::size_of::<Self>() + self.iter().map(CacheWeight::weight).sum::<usize>()
+    }
+}
+
+impl<T: CacheWeight> CacheWeight for Rc<T> {
+    fn weight(&self) -> usize {
+        self.as_ref().weight()
+    }
+}
+
+impl<A: CacheWeight, B, C> CacheWeight for (A, B, C) {
+    fn weight(&self) -> usize {
+        std::mem::size_of::<Self>() + self.0.weight()
+    }
+}
+
+/// Counters reported by `:cache stats`
+#[derive(Debug, Clone, Default)]
+pub struct CacheStats {
+    pub name: &'static str,
+    pub entries: usize,
+    pub bytes: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+/// A cache that takes part in the global memory budget
+pub trait BudgetedCache {
+    fn stats(&self) -> CacheStats;
+
+    /// Clock value of the least recently used entry
+    fn oldest_use(&self) -> Option<u64>;
+
+    /// Drop the least recently used entry; false if the cache is empty
+    fn evict_oldest(&self) -> bool;
+}
+
+#[derive(Debug)]
+struct Entry<V> {
+    value: V,
+    weight: usize,
+    last_used: u64,
+}
+
+/// Memoization map with recency and size tracking
+/// It never evicts on its own; the model evicts across all caches once the shared budget is exceeded
+/// Interior mutability lets services fill it while rendering from `&AppModel`
+#[derive(Debug)]
+pub struct LruCache<K, V> {
+    name: &'static str,
+    entries: RefCell<HashMap<K, Entry<V>>>,
+    bytes: Cell<usize>,
+    hits: Cell<u64>,
+    misses: Cell<u64>,
+    evictions: Cell<u64>,
+}
+
+impl<K: Hash + Eq + Clone + CacheWeight, V: Clone + CacheWeight> LruCache<K, V> {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            entries: RefCell::new(HashMap::new()),
+            bytes: Cell::new(0),
+            hits: Cell::new(0),
+            misses: Cell::new(0),
+            evictions: Cell::new(0),
+        }
+    }
+
+    /// Cached value for `key`, marking it as recently used
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut entries = self.entries.borrow_mut();
+        match entries.get_mut(key) {
+            Some(entry) => {
+                entry.last_used = tick();
+                self.hits.set(self.hits.get() + 1);
+                Some(entry.value.clone())
+            }
+            None => {
+                self.misses.set(self.misses.get() + 1);
+                None
+            }
+        }
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        let weight = key.weight() + value.weight();
+        let entry = Entry { value, weight, last_used: tick() };
+        let mut bytes = self.bytes.get() + weight;
+        if let Some(replaced) = self.entries.borrow_mut().insert(key, entry) {
+            bytes -= replaced.weight;
+        }
+        self.bytes.set(bytes);
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+        self.bytes.set(0);
+    }
+}
+
+impl<K: Hash + Eq + Clone + CacheWeight, V: Clone + CacheWeight> BudgetedCache for LruCache<K, V> {
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            name: self.name,
+            entries: self.entries.borrow().len(),
+            bytes: self.bytes.get(),
+            hits: self.hits.get(),
+            misses: self.misses.get(),
+            evictions: self.evictions.get(),
+        }
+    }
+
+    fn oldest_use(&self) -> Option<u64> {
+        self.entries.borrow().values().map(|entry| entry.last_used).min()
+    }
+
+    fn evict_oldest(&self) -> bool {
+        let mut entries = self.entries.borrow_mut();
+        let Some(oldest) = entries.iter().min_by_key(|(_, entry)| entry.last_used).map(|(key, _)| key.clone()) else {
+            return false;
+        };
+        if let Some(entry) = entries.remove(&oldest) {
+            self.bytes.set(self.bytes.get() - entry.weight);
+        }
+        self.evictions.set(self.evictions.get() + 1);
+        true
+    }
+}
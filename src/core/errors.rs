@@ -10,7 +10,7 @@ pub enum ClazyfilerError {
         source: std::io::Error 
     },
     
-    /// Editor/external command errors
+    /// Editor errors (detection, launch, exit status)
     Editor { 
         command: String, 
         message: String 
@@ -44,6 +44,24 @@ pub enum ClazyfilerError {
         file_path: String, 
         message: String 
     },
+    
+    /// Command line (`:`) parsing/execution errors
+    Command { 
+        input: String, 
+        message: String 
+    },
+    
+    /// Values refused before anything is done with them (names, keys)
+    Validation { 
+        value: String, 
+        message: String 
+    },
+    
+    /// Helper programs the app runs for a feature (ffmpeg, df, gpg, ...)
+    ExternalTool { 
+        tool: String, 
+        message: String 
+    },
 }
 
 impl fmt::Display for ClazyfilerError {
@@ -70,6 +88,15 @@ impl fmt::Display for ClazyfilerError {
             ClazyfilerError::Content { file_path, message } => {
                 write!(f, "Content error for '{}': {}", file_path, message)
             }
+            ClazyfilerError::Command { input, message } => {
+                write!(f, "Command error for '{}': {}", input, message)
+            }
+            ClazyfilerError::Validation { value, message } => {
+                write!(f, "Invalid '{}': {}", value, message)
+            }
+            ClazyfilerError::ExternalTool { tool, message } => {
+                write!(f, "External tool error with '{}': {}", tool, message)
+            }
         }
     }
 }
@@ -136,6 +163,27 @@ impl ClazyfilerError {
             message: message.to_string(),
         }
     }
+    
+    pub fn command(input: &str, message: &str) -> Self {
+        Self::Command {
+            input: input.to_string(),
+            message: message.to_string(),
+        }
+    }
+    
+    pub fn validation(value: &str, message: &str) -> Self {
+        Self::Validation {
+            value: value.to_string(),
+            message: message.to_string(),
+        }
+    }
+    
+    pub fn external_tool(tool: &str, message: &str) -> Self {
+        Self::ExternalTool {
+            tool: tool.to_string(),
+            message: message.to_string(),
+        }
+    }
 }
 
 /// Convert from common error types
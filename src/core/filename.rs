@@ -0,0 +1,93 @@
+/// Longest file name most filesystems accept, in bytes
+const MAX_NAME_BYTES: usize = 255;
+
+/// Characters Windows refuses in file names (besides control characters)
+const WINDOWS_FORBIDDEN: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Device names Windows reserves regardless of extension
+const WINDOWS_RESERVED: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Why a proposed file name is a bad idea
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameProblem {
+    pub message: String,
+    pub fatal: bool,       // The current platform would reject it (or it escapes the directory)
+    pub suggestion: String,
+}
+
+/// Check a new name against this platform's rules, and against Windows rules so names stay portable
+/// Portability problems only block creation on Windows; elsewhere they are warnings
+pub fn check_name(name: &str) -> Option<NameProblem> {
+    let problem = |message: &str, fatal: bool| Some(NameProblem {
+        message: message.to_string(),
+        fatal,
+        suggestion: sanitize_name(name),
+    });
+    let windows = cfg!(windows);
+
+    if name.is_empty() || name == "." || name == ".." {
+        return problem("Name must not be empty, '.' or '..'", true);
+    }
+    if name.contains('/') || (windows && name.contains('\\')) {
+        return problem("Name must not contain a path separator", true);
+    }
+    if name.contains('\0') {
+        return problem("Name must not contain NUL", true);
+    }
+    if name.len() > MAX_NAME_BYTES {
+        return problem("Name is longer than 255 bytes", true);
+    }
+    if name.chars().any(char::is_control) {
+        return problem("Name contains control characters", windows);
+    }
+    if let Some(c) = name.chars().find(|c| WINDOWS_FORBIDDEN.contains(c)) {
+        return problem(&format!("'{}' is not allowed on Windows", c), windows);
+    }
+    if name.ends_with('.') || name.ends_with(' ') {
+        return problem("Trailing dots and spaces are dropped on Windows", windows);
+    }
+    if is_windows_reserved(name) {
+        return problem("Reserved device name on Windows", windows);
+    }
+    None
+}
+
+/// Closest name that passes `check_name`: forbidden characters become `_`, trailing dots and
+/// spaces are trimmed, reserved device names get a `_` suffix and long names are shortened
+pub fn sanitize_name(name: &str) -> String {
+    let replaced: String = name.chars()
+        .map(|c| if c.is_control() || WINDOWS_FORBIDDEN.contains(&c) { '_' } else { c })
+        .collect();
+    let mut sanitized = replaced.trim_end_matches(['.', ' ']).to_string();
+
+    if sanitized.is_empty() {
+        sanitized = "untitled".to_string();
+    }
+    if is_windows_reserved(&sanitized) {
+        let stem_end = sanitized.find('.').unwrap_or(sanitized.len());
+        sanitized.insert(stem_end, '_');
+    }
+    if sanitized.len() > MAX_NAME_BYTES {
+        // Shorten the stem so the extension survives
+        let extension = sanitized.rfind('.')
+            .filter(|&dot| dot > 0 && sanitized.len() - dot <= 16)
+            .map(|dot| sanitized[dot..].to_string())
+            .unwrap_or_default();
+        let mut stem_len = MAX_NAME_BYTES.saturating_sub(extension.len());
+        while !sanitized.is_char_boundary(stem_len) {
+            stem_len -= 1;
+        }
+        sanitized = format!("{}{}", sanitized[..stem_len].trim_end_matches(['.', ' ']), extension);
+    }
+    sanitized
+}
+
+/// `CON`, `nul.txt`, `Com1.tar.gz`, ... (Windows compares the part before the first dot)
+fn is_windows_reserved(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name).trim_end();
+    WINDOWS_RESERVED.iter().any(|reserved| stem.eq_ignore_ascii_case(reserved))
+}
@@ -0,0 +1,42 @@
+/// Minimal glob matching for file names
+/// Supports `*` (any run of characters) and `?` (any single character), case-insensitive
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    let mut pattern_idx = 0;
+    let mut text_idx = 0;
+    // Position of the last `*` seen and the text index it was matched against
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while text_idx < text.len() {
+        match pattern.get(pattern_idx) {
+            Some('*') => {
+                backtrack = Some((pattern_idx, text_idx));
+                pattern_idx += 1;
+            }
+            Some('?') => {
+                pattern_idx += 1;
+                text_idx += 1;
+            }
+            Some(&c) if c == text[text_idx] => {
+                pattern_idx += 1;
+                text_idx += 1;
+            }
+            _ => {
+                // Mismatch - let the last `*` swallow one more character
+                match backtrack {
+                    Some((star_idx, star_text_idx)) => {
+                        pattern_idx = star_idx + 1;
+                        text_idx = star_text_idx + 1;
+                        backtrack = Some((star_idx, star_text_idx + 1));
+                    }
+                    None => return false,
+                }
+            }
+        }
+    }
+
+    // Remaining pattern may only consist of `*`
+    pattern[pattern_idx..].iter().all(|&c| c == '*')
+}
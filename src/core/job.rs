@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often a paused job checks whether it may go on
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Pause and cancel switches of a background job, shared with the thread doing the work, along with how much
+/// data it got through
+/// Jobs check it between entries and between chunks of a copy or checksum, so even one large file can be held
+#[derive(Debug, Clone, Default)]
+pub struct JobControl {
+    paused: Arc<AtomicBool>,
+    cancelled: Arc<AtomicBool>,
+    bytes_done: Arc<AtomicU64>,
+    bytes_total: Arc<AtomicU64>,  // 0 until the worker has measured its entries
+}
+
+impl JobControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Stop the job after the chunk in progress, paused or not
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn bytes_done(&self) -> u64 {
+        self.bytes_done.load(Ordering::Relaxed)
+    }
+
+    pub fn add_bytes_done(&self, bytes: u64) {
+        self.bytes_done.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn bytes_total(&self) -> u64 {
+        self.bytes_total.load(Ordering::Relaxed)
+    }
+
+    pub fn add_bytes_total(&self, bytes: u64) {
+        self.bytes_total.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Block the calling worker thread until the job is resumed or cancelled
+    pub fn wait_while_paused(&self) {
+        while self.is_paused() && !self.is_cancelled() {
+            std::thread::sleep(PAUSE_POLL_INTERVAL);
+        }
+    }
+}
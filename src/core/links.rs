@@ -0,0 +1,253 @@
+/// A link-like token found in preview text
+/// `start`/`end` are byte offsets within line `line`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkCandidate {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+    pub is_url: bool,
+}
+
+/// URL schemes recognized in previews
+const URL_PREFIXES: &[&str] = &["http://", "https://", "ftp://", "file://", "www."];
+
+/// Characters that delimit tokens in addition to whitespace (quotes, brackets, markdown links)
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '(' | ')' | '<' | '>' | '[' | ']' | '{' | '}' | ',' | '|')
+}
+
+/// Find URLs and path-looking tokens in text
+/// Paths are only candidates - the caller decides whether they resolve to something on disk
+pub fn find_link_candidates(text: &str) -> Vec<LinkCandidate> {
+    let mut candidates = Vec::new();
+
+    for (line_idx, line) in text.lines().enumerate() {
+        let mut token_start: Option<usize> = None;
+        for (idx, c) in line.char_indices().chain(std::iter::once((line.len(), ' '))) {
+            match (token_start, is_delimiter(c)) {
+                (None, false) => token_start = Some(idx),
+                (Some(start), true) => {
+                    if let Some(candidate) = classify_token(line_idx, start, &line[start..idx]) {
+                        candidates.push(candidate);
+                    }
+                    token_start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Decide whether a token looks like a URL or a path
+fn classify_token(line: usize, start: usize, token: &str) -> Option<LinkCandidate> {
+    // Sentence punctuation is rarely part of the link itself
+    let token = token.trim_end_matches(['.', ',', ';', ':', '!', '?']);
+    if token.is_empty() {
+        return None;
+    }
+
+    let is_url = URL_PREFIXES.iter().any(|prefix| token.starts_with(prefix)) && token.len() > 8;
+    let is_path = !is_url && looks_like_path(token);
+
+    if is_url || is_path {
+        Some(LinkCandidate {
+            line,
+            start,
+            end: start + token.len(),
+            text: token.to_string(),
+            is_url,
+        })
+    } else {
+        None
+    }
+}
+
+/// Paths contain a separator, start with `~`, or look like `name.ext`
+fn looks_like_path(token: &str) -> bool {
+    if token.contains("://") {
+        return false;
+    }
+    if token.contains('/') || token.contains('\\') || token.starts_with('~') {
+        return true;
+    }
+    match token.rsplit_once('.') {
+        Some((name, ext)) => {
+            !name.is_empty()
+                && (1..=5).contains(&ext.len())
+                && ext.chars().all(|c| c.is_ascii_alphanumeric())
+                && !ext.chars().all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+/// Source languages with import/module reference detection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceLanguage {
+    Rust,
+    C,
+    Python,
+}
+
+impl SourceLanguage {
+    /// Detect the language from a file extension
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "rs" => Some(SourceLanguage::Rust),
+            "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => Some(SourceLanguage::C),
+            "py" | "pyi" => Some(SourceLanguage::Python),
+            _ => None,
+        }
+    }
+}
+
+/// A module/include reference in source code
+/// `candidates` are paths relative to the referencing file's directory, in lookup order
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportReference {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    pub candidates: Vec<String>,
+}
+
+/// Find `mod foo;`, `#include "foo.h"` and `import foo.bar` style references
+/// `file_name` is needed for Rust, where `mod` paths depend on whether the file is a module root
+pub fn find_import_references(text: &str, language: SourceLanguage, file_name: &str) -> Vec<ImportReference> {
+    text.lines()
+        .enumerate()
+        .flat_map(|(line_idx, line)| {
+            let references = match language {
+                SourceLanguage::Rust => rust_mod_reference(line, file_name).into_iter().collect(),
+                SourceLanguage::C => c_include_reference(line).into_iter().collect(),
+                SourceLanguage::Python => python_import_references(line),
+            };
+            references.into_iter().map(move |(start, end, candidates)| ImportReference {
+                line: line_idx,
+                start,
+                end,
+                candidates,
+            })
+        })
+        .collect()
+}
+
+/// Byte offset of `needle` within `line`, where `needle` is a subslice of `line`
+fn offset_in(line: &str, needle: &str) -> usize {
+    needle.as_ptr() as usize - line.as_ptr() as usize
+}
+
+/// `mod foo;` (with optional visibility) -> foo.rs, foo/mod.rs
+fn rust_mod_reference(line: &str, file_name: &str) -> Option<(usize, usize, Vec<String>)> {
+    let trimmed = line.trim_start();
+    let rest = if let Some(rest) = trimmed.strip_prefix("pub") {
+        // `pub mod`, `pub(crate) mod`, `pub(super) mod`
+        let rest = rest.trim_start();
+        match rest.strip_prefix('(') {
+            Some(restricted) => restricted.split_once(')')?.1,
+            None => rest,
+        }
+    } else {
+        trimmed
+    };
+    let name = rest.trim_start().strip_prefix("mod ")?.trim_start();
+    let name = name.strip_suffix(';').or_else(|| name.split_once(';').map(|(name, _)| name))?.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    // Module roots declare children next to themselves, other files in a same-named directory
+    let dir = match file_name {
+        "main.rs" | "lib.rs" | "mod.rs" => String::new(),
+        _ => format!("{}/", file_name.trim_end_matches(".rs")),
+    };
+    let start = offset_in(line, name);
+    Some((start, start + name.len(), vec![
+        format!("{}{}.rs", dir, name),
+        format!("{}{}/mod.rs", dir, name),
+    ]))
+}
+
+/// `#include "foo.h"` / `#include <foo.h>` -> foo.h, include/foo.h
+fn c_include_reference(line: &str) -> Option<(usize, usize, Vec<String>)> {
+    let rest = line.trim_start().strip_prefix('#')?.trim_start().strip_prefix("include")?.trim_start();
+    let (open, close) = match rest.chars().next()? {
+        '"' => ('"', '"'),
+        '<' => ('<', '>'),
+        _ => return None,
+    };
+    let inner = rest.strip_prefix(open)?;
+    let path = &inner[..inner.find(close)?];
+    if path.is_empty() {
+        return None;
+    }
+    let start = offset_in(line, path);
+    Some((start, start + path.len(), vec![
+        path.to_string(),
+        format!("include/{}", path),
+        format!("../include/{}", path),
+    ]))
+}
+
+/// `import a.b, c` / `from .a.b import c` -> a/b.py, a/b/__init__.py
+fn python_import_references(line: &str) -> Vec<(usize, usize, Vec<String>)> {
+    let trimmed = line.trim_start();
+    let mut references = Vec::new();
+
+    if let Some(rest) = trimmed.strip_prefix("import ") {
+        let mut search_from = rest;
+        for part in rest.split(',') {
+            let module = part.split_whitespace().next().unwrap_or("");
+            if module.is_empty() {
+                continue;
+            }
+            // Locate this occurrence after the previous one to get correct offsets
+            let Some(pos) = search_from.find(module) else { continue };
+            let module_str = &search_from[pos..pos + module.len()];
+            search_from = &search_from[pos + module.len()..];
+            let start = offset_in(line, module_str);
+            references.push((start, start + module.len(), python_module_candidates(module, None)));
+        }
+    } else if let Some(rest) = trimmed.strip_prefix("from ") {
+        // Trimmed first, so the module is at the start and what follows it starts at its length
+        let rest = rest.trim_start();
+        let Some(module) = rest.split_whitespace().next() else {
+            return references;
+        };
+        let Some(after_import) = rest[module.len()..].trim_start().strip_prefix("import ") else {
+            return references;
+        };
+        // `from pkg import sub` may refer to a submodule as well as a name
+        let first_name = after_import
+            .trim_start_matches('(')
+            .split([',', ' ', ')'])
+            .find(|name| !name.is_empty());
+        let module_str = &rest[..module.len()];
+        let start = offset_in(line, module_str);
+        references.push((start, start + module.len(), python_module_candidates(module, first_name)));
+    }
+
+    references
+}
+
+/// Candidate files for a (possibly relative) dotted Python module
+fn python_module_candidates(module: &str, imported_name: Option<&str>) -> Vec<String> {
+    let dots = module.chars().take_while(|&c| c == '.').count();
+    let prefix = if dots > 1 { "../".repeat(dots - 1) } else { String::new() };
+    let path = module[dots..].replace('.', "/");
+
+    let mut candidates = Vec::new();
+    if !path.is_empty() {
+        candidates.push(format!("{}{}.py", prefix, path));
+        candidates.push(format!("{}{}/__init__.py", prefix, path));
+    }
+    if let Some(name) = imported_name {
+        let base = if path.is_empty() { prefix.clone() } else { format!("{}{}/", prefix, path) };
+        candidates.push(format!("{}{}.py", base, name));
+        candidates.push(format!("{}{}/__init__.py", base, name));
+    }
+    candidates
+}
@@ -1,3 +1,49 @@
+pub mod acl;
+pub mod age;
+pub mod attributes;
+pub mod base64;
+pub mod bencode;
+pub mod bulk_rename;
+pub mod cache;
 pub mod errors;
+pub mod filename;
+pub mod glob;
+pub mod hash;
+pub mod job;
+pub mod links;
+pub mod path_completion;
+pub mod permissions;
+pub mod power_filter;
+pub mod preview;
+pub mod protection;
+pub mod profile;
+pub mod retry;
+pub mod throttle;
+pub mod winpath;
+pub mod xml;
+pub mod zip;
 
-pub use errors::*;
\ No newline at end of file
+pub use acl::{is_extended_acl, parse_acl, AclEntry, AclTag};
+pub use age::parse_age;
+pub use attributes::{attribute_name, attribute_names, has_attribute};
+pub use base64::{base64_decode, base64_encode};
+pub use bencode::{bencode_dict_value_raw, Bencode};
+pub use bulk_rename::{bulk_rename_line, bulk_rename_plan};
+pub use cache::{BudgetedCache, CacheWeight, LruCache};
+pub use errors::*;
+pub use filename::check_name;
+pub use glob::glob_match;
+pub use hash::{to_hex, Md5, Sha1, Sha256};
+pub use job::JobControl;
+pub use path_completion::{expand_path, PathCompletion};
+pub use permissions::{metadata_mode, metadata_owner, parse_mode, permission_string, risky_permissions, CreationMode, DEFAULT_DIR_MODE, DEFAULT_FILE_MODE};
+pub use power_filter::{PowerFilter, Predicate};
+pub use preview::text_preview;
+pub use protection::{confirmation_word, protected_root};
+pub use profile::Profiler;
+pub use retry::{is_transient, RetryPolicy};
+pub use throttle::IoThrottle;
+pub use winpath::{display_path, normalize_path, parent_path};
+pub use links::{find_import_references, find_link_candidates, SourceLanguage};
+pub use xml::{xml_attribute, xml_tokens, XmlToken};
+pub use zip::ZipArchive;
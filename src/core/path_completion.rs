@@ -0,0 +1,68 @@
+use std::path::{Path, PathBuf};
+
+/// Tab completion of directory paths typed into a prompt, shared by the prompts that take one
+/// The first Tab completes the last component to the first matching directory, further ones cycle
+/// through the others; hidden directories are only offered once the component starts with '.'
+#[derive(Debug, Default)]
+pub struct PathCompletion {
+    candidates: Vec<String>,  // Whole inputs to cycle through, each ending in '/'
+    index: usize,
+}
+
+impl PathCompletion {
+    /// Complete `input`, read relative to `base` unless absolute or under `~/`; `backward` cycles the other way
+    /// Returns the new input, or None when no directory matches
+    pub fn complete(&mut self, base: &Path, input: &str, backward: bool) -> Option<String> {
+        let cycling = self.candidates.get(self.index).is_some_and(|candidate| candidate == input);
+        if cycling {
+            let len = self.candidates.len();
+            self.index = if backward { (self.index + len - 1) % len } else { (self.index + 1) % len };
+        } else {
+            self.candidates = candidates(base, input);
+            self.index = if backward { self.candidates.len().saturating_sub(1) } else { 0 };
+        }
+        self.candidates.get(self.index).cloned()
+    }
+
+    /// Forget the candidates once the input is edited by hand
+    pub fn reset(&mut self) {
+        self.candidates.clear();
+        self.index = 0;
+    }
+
+    /// `2/5`-style position among the candidates of the last Tab, when there was a choice
+    pub fn position(&self) -> Option<String> {
+        (self.candidates.len() > 1).then(|| format!("{}/{}", self.index + 1, self.candidates.len()))
+    }
+}
+
+/// Where a typed path points: `~/` is the home directory, relative paths start at `base`
+pub fn expand_path(base: &Path, input: &str) -> PathBuf {
+    let home_relative = if input == "~" { Some("") } else { input.strip_prefix("~/") };
+    if let Some(rest) = home_relative {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest);
+        }
+    }
+    base.join(input)
+}
+
+/// Inputs completing the last component of `input` to each directory it is a prefix of, sorted
+fn candidates(base: &Path, input: &str) -> Vec<String> {
+    let (typed_dir, partial) = match input.rfind('/') {
+        Some(slash) => input.split_at(slash + 1),
+        None => ("", input),
+    };
+    let dir = if typed_dir.is_empty() { base.to_path_buf() } else { expand_path(base, typed_dir) };
+    let Ok(entries) = std::fs::read_dir(&dir) else { return Vec::new() };
+    let show_hidden = partial.starts_with('.');
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with(partial) && (show_hidden || !name.starts_with('.')))
+        .collect();
+    names.sort();
+    names.into_iter().map(|name| format!("{}{}/", typed_dir, name)).collect()
+}
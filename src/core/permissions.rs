@@ -0,0 +1,127 @@
+/// Mode new files get from the OS before the umask is applied
+pub const DEFAULT_FILE_MODE: u32 = 0o666;
+
+/// Mode new directories get from the OS before the umask is applied
+pub const DEFAULT_DIR_MODE: u32 = 0o777;
+
+/// Where the mode of a newly created entry comes from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModeSource {
+    Umask(u32),  // OS default with this umask applied
+    Config,      // Set explicitly in the config file, applied as-is
+}
+
+/// Permissions a "new file"/"new directory" action will produce
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CreationMode {
+    pub mode: u32,
+    pub source: ModeSource,
+}
+
+impl CreationMode {
+    /// The configured mode if there is one, otherwise `default` minus the process umask
+    pub fn resolve(configured: Option<u32>, default: u32) -> Self {
+        match configured {
+            Some(mode) => Self { mode, source: ModeSource::Config },
+            None => {
+                let umask = current_umask();
+                Self { mode: default & !umask, source: ModeSource::Umask(umask) }
+            }
+        }
+    }
+
+    /// Whether the mode has to be set after creation because the umask would change it
+    pub fn needs_chmod(&self) -> bool {
+        self.source == ModeSource::Config
+    }
+
+    /// `0644 rw-r--r-- (umask 022)` for the creation prompt
+    pub fn describe(&self) -> String {
+        let source = match self.source {
+            ModeSource::Umask(umask) => format!("umask {:03o}", umask),
+            ModeSource::Config => "from config".to_string(),
+        };
+        format!("{:04o} {} ({})", self.mode, permission_string(self.mode), source)
+    }
+}
+
+/// Octal mode as written in the config (`"644"`, `"0644"` or `"0o644"`)
+pub fn parse_mode(text: &str) -> Option<u32> {
+    let digits = text.trim().trim_start_matches("0o");
+    u32::from_str_radix(digits, 8).ok().filter(|&mode| mode <= 0o7777)
+}
+
+/// The process umask, read from /proc since std offers no way to query it without changing it
+/// Falls back to the common 022 where /proc is unavailable
+pub fn current_umask() -> u32 {
+    std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| {
+            status.lines()
+                .find_map(|line| line.strip_prefix("Umask:"))
+                .and_then(parse_mode)
+        })
+        .unwrap_or(0o022)
+}
+
+/// Owning user and group ids of an entry; None where the platform has none
+pub fn metadata_owner(metadata: &std::fs::Metadata) -> Option<(u32, u32)> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some((metadata.uid(), metadata.gid()))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+/// Permission bits of an entry, setuid/setgid/sticky included; None where the platform has none
+pub fn metadata_mode(metadata: &std::fs::Metadata) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        Some(metadata.permissions().mode() & 0o7777)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+/// Risky bits of an entry's mode, for badges: setuid, setgid and world-writable
+/// A directory with the sticky bit (like /tmp) is meant to be world-writable and is not flagged for it
+pub fn risky_permissions(mode: u32, is_directory: bool) -> Vec<&'static str> {
+    let mut risks = Vec::new();
+    if mode & 0o4000 != 0 {
+        risks.push("setuid");
+    }
+    // On directories setgid only makes new entries inherit the group
+    if mode & 0o2000 != 0 && !is_directory {
+        risks.push("setgid");
+    }
+    if mode & 0o002 != 0 && !(is_directory && mode & 0o1000 != 0) {
+        risks.push("world-writable");
+    }
+    risks
+}
+
+/// `rwxr-x---` style rendering of Unix permission bits, including setuid/setgid/sticky
+pub fn permission_string(mode: u32) -> String {
+    let mut text = String::with_capacity(9);
+    for (shift, special, special_char) in [(6, 0o4000, 's'), (3, 0o2000, 's'), (0, 0o1000, 't')] {
+        let bits = (mode >> shift) & 0o7;
+        text.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        text.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        text.push(match (bits & 0o1 != 0, mode & special != 0) {
+            (true, true) => special_char,
+            (false, true) => special_char.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    text
+}
@@ -0,0 +1,150 @@
+use std::fs::Metadata;
+use std::path::Path;
+use crate::core::{metadata_mode, metadata_owner, parse_mode};
+
+/// A `key:value` word of a search query, checked against the entry's own metadata (symlinks not followed)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    Owner(Option<u32>),  // `owner:root` or `owner:1000`; None for an unknown user, which matches nothing
+    Group(Option<u32>),
+    Perm(PermCheck),
+    Type(EntryType),
+}
+
+/// What `perm:` asks of the permission bits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PermCheck {
+    WorldWritable,  // Writable by others, symlinks excepted as their bits mean nothing
+    Setuid,
+    Setgid,
+    Sticky,
+    Executable,     // By anyone
+    Exactly(u32),   // `perm:0644`
+}
+
+/// What `type:` asks the entry to be
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntryType {
+    File,
+    Directory,
+    Symlink,
+    Fifo,
+    Socket,
+    Device,  // Block or character
+}
+
+/// A search query split into the text names have to contain and the predicates entries have to pass
+/// `owner:root perm:setuid sh` finds setuid entries of root with `sh` in their name; words that look like
+/// predicates but are not (`perm:nonsense`, `note:todo`) stay part of the text
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerFilter {
+    pub text: String,
+    pub predicates: Vec<Predicate>,
+}
+
+impl PowerFilter {
+    /// Split `query`, looking user and group names up with `user_id` and `group_id`
+    pub fn parse(query: &str, user_id: impl Fn(&str) -> Option<u32>, group_id: impl Fn(&str) -> Option<u32>) -> Self {
+        let mut words = Vec::new();
+        let mut predicates = Vec::new();
+        for word in query.split(' ') {
+            let predicate = word.split_once(':').and_then(|(key, value)| match key {
+                "owner" if !value.is_empty() => Some(Predicate::Owner(value.parse().ok().or_else(|| user_id(value)))),
+                "group" if !value.is_empty() => Some(Predicate::Group(value.parse().ok().or_else(|| group_id(value)))),
+                "perm" => perm_check(value).map(Predicate::Perm),
+                "type" => entry_type(value).map(Predicate::Type),
+                _ => None,
+            });
+            match predicate {
+                Some(predicate) => predicates.push(predicate),
+                None => words.push(word),
+            }
+        }
+        Self { text: words.join(" ").trim().to_string(), predicates }
+    }
+
+    /// Whether an entry called `name` at `path` contains the text and passes the predicates
+    pub fn matches(&self, name: &str, path: &Path) -> bool {
+        name.to_lowercase().contains(&self.text.to_lowercase()) && self.passes(path)
+    }
+
+    /// Whether the entry at `path` passes the predicates; only stats it when there are any
+    pub fn passes(&self, path: &Path) -> bool {
+        if self.predicates.is_empty() {
+            return true;
+        }
+        match path.symlink_metadata() {
+            Ok(metadata) => self.predicates.iter().all(|predicate| predicate.matches(&metadata)),
+            Err(_) => false,
+        }
+    }
+}
+
+impl Predicate {
+    pub fn matches(&self, metadata: &Metadata) -> bool {
+        let mode = metadata_mode(metadata);
+        let has = |bits: u32| mode.is_some_and(|mode| mode & bits == bits);
+        match self {
+            Predicate::Owner(uid) => uid.is_some() && metadata_owner(metadata).map(|(owner, _)| owner) == *uid,
+            Predicate::Group(gid) => gid.is_some() && metadata_owner(metadata).map(|(_, group)| group) == *gid,
+            Predicate::Perm(PermCheck::WorldWritable) => !metadata.is_symlink() && has(0o002),
+            Predicate::Perm(PermCheck::Setuid) => has(0o4000),
+            Predicate::Perm(PermCheck::Setgid) => has(0o2000),
+            Predicate::Perm(PermCheck::Sticky) => has(0o1000),
+            Predicate::Perm(PermCheck::Executable) => metadata.is_file() && mode.is_some_and(|mode| mode & 0o111 != 0),
+            Predicate::Perm(PermCheck::Exactly(bits)) => mode == Some(*bits),
+            Predicate::Type(kind) => entry_type_of(metadata) == Some(*kind),
+        }
+    }
+}
+
+fn perm_check(value: &str) -> Option<PermCheck> {
+    match value {
+        "world-writable" | "o+w" => Some(PermCheck::WorldWritable),
+        "setuid" | "suid" => Some(PermCheck::Setuid),
+        "setgid" | "sgid" => Some(PermCheck::Setgid),
+        "sticky" => Some(PermCheck::Sticky),
+        "executable" | "exec" | "+x" => Some(PermCheck::Executable),
+        _ if value.len() >= 3 => parse_mode(value).map(PermCheck::Exactly),
+        _ => None,
+    }
+}
+
+fn entry_type(value: &str) -> Option<EntryType> {
+    match value {
+        "file" | "f" => Some(EntryType::File),
+        "dir" | "directory" | "d" => Some(EntryType::Directory),
+        "symlink" | "link" | "l" => Some(EntryType::Symlink),
+        "fifo" | "pipe" | "p" => Some(EntryType::Fifo),
+        "socket" | "s" => Some(EntryType::Socket),
+        "device" | "dev" => Some(EntryType::Device),
+        _ => None,
+    }
+}
+
+fn entry_type_of(metadata: &Metadata) -> Option<EntryType> {
+    let file_type = metadata.file_type();
+    if file_type.is_symlink() {
+        return Some(EntryType::Symlink);
+    }
+    if file_type.is_dir() {
+        return Some(EntryType::Directory);
+    }
+    if file_type.is_file() {
+        return Some(EntryType::File);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        if file_type.is_fifo() {
+            return Some(EntryType::Fifo);
+        }
+        if file_type.is_socket() {
+            return Some(EntryType::Socket);
+        }
+        if file_type.is_block_device() || file_type.is_char_device() {
+            return Some(EntryType::Device);
+        }
+    }
+    None
+}
@@ -0,0 +1,42 @@
+use std::path::Path;
+
+/// Lines of a text file shown in the preview
+const MAX_PREVIEW_LINES: usize = 100;
+
+/// Whether file contents look binary: a NUL or a control character other than tab, CR and LF
+/// Escape sequences in such files would otherwise reach the terminal through the preview
+fn is_binary(bytes: &[u8]) -> bool {
+    bytes.iter().any(|&b| b == 0 || (b < 32 && b != b'\n' && b != b'\r' && b != b'\t'))
+}
+
+/// Preview text for the contents of the file at `path`: the first lines of text, or a notice for binary data
+/// and invalid UTF-8; free of side effects, so crafted contents can be fed through it directly
+pub fn text_preview(bytes: Vec<u8>, path: &Path) -> String {
+    if is_binary(&bytes) {
+        return format!(
+            "🔧 Binary file detected\n\nSize: {} bytes\nPath: {}\n\nThis appears to be a binary file and cannot be displayed as text.",
+            bytes.len(),
+            path.display()
+        );
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(content) => {
+            let lines: Vec<&str> = content.lines().collect();
+            if lines.len() > MAX_PREVIEW_LINES {
+                format!(
+                    "📝 Text File Preview (first {} lines)\n\n{}\n\n... ({} more lines)",
+                    MAX_PREVIEW_LINES,
+                    lines[..MAX_PREVIEW_LINES].join("\n"),
+                    lines.len() - MAX_PREVIEW_LINES
+                )
+            } else {
+                format!("📝 Text File Content\n\n{}", content)
+            }
+        },
+        Err(_) => format!(
+            "⚠️ Invalid UTF-8 encoding\n\nPath: {}\n\nFile contains non-UTF-8 data and cannot be displayed.",
+            path.display()
+        ),
+    }
+}
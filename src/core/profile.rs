@@ -0,0 +1,82 @@
+use std::cell::RefCell;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Timing log enabled by `--profile-startup`
+/// Each phase is appended to the log file as it finishes, so a hang still leaves a trail
+#[derive(Debug)]
+pub struct Profiler {
+    enabled: bool,
+    started: Instant,
+    log_path: Option<PathBuf>,
+    entries: RefCell<Vec<(String, Duration)>>,
+}
+
+impl Profiler {
+    pub fn new(enabled: bool, log_path: Option<PathBuf>) -> Self {
+        let profiler = Self {
+            enabled,
+            started: Instant::now(),
+            log_path,
+            entries: RefCell::new(Vec::new()),
+        };
+        if enabled {
+            profiler.append_to_log(&format!("--- clazyfiler {} started ---", chrono::Local::now().format("%Y-%m-%d %H:%M:%S")));
+        }
+        profiler
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Time since the process started profiling
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Run `f` and record how long it took
+    pub fn time<T>(&self, label: &str, f: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return f();
+        }
+        let start = Instant::now();
+        let result = f();
+        self.record(label.to_string(), start.elapsed());
+        result
+    }
+
+    /// Record a measured phase
+    pub fn record(&self, label: String, duration: Duration) {
+        if !self.enabled {
+            return;
+        }
+        self.append_to_log(&format!("{:>10.3} ms  {}", duration.as_secs_f64() * 1000.0, label));
+        self.entries.borrow_mut().push((label, duration));
+    }
+
+    /// All recorded phases, one per line, for printing after the TUI has exited
+    pub fn report(&self) -> String {
+        let mut report = String::from("Startup profile:\n");
+        for (label, duration) in self.entries.borrow().iter() {
+            report.push_str(&format!("{:>10.3} ms  {}\n", duration.as_secs_f64() * 1000.0, label));
+        }
+        if let Some(path) = &self.log_path {
+            report.push_str(&format!("Log: {}", path.display()));
+        }
+        report
+    }
+
+    fn append_to_log(&self, line: &str) {
+        let Some(path) = &self.log_path else { return };
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        // Profiling must never break the app, so write errors are ignored
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::path::{Path, PathBuf};
+use crate::core::expand_path;
+
+/// The configured protected directory that any of `paths` is, or lies within
+/// Configured paths may start with `~/`; both sides are compared as given and, when they exist,
+/// with symlinks resolved, so `/etc` still guards entries reached through a link to it
+pub fn protected_root(protected: &[String], paths: &[PathBuf]) -> Option<PathBuf> {
+    let roots: Vec<PathBuf> = protected
+        .iter()
+        .filter(|root| !root.is_empty())
+        .map(|root| expand_path(Path::new("/"), root))
+        .collect();
+    paths.iter().find_map(|path| {
+        let resolved = resolved_parent(path);
+        roots.iter().find(|root| {
+            let resolved_root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+            path.starts_with(root) || resolved.as_ref().is_some_and(|resolved| resolved.starts_with(&resolved_root))
+        }).cloned()
+    })
+}
+
+/// What has to be typed to act on entries under `root`: its name, or the whole path for `/`
+pub fn confirmation_word(root: &Path) -> String {
+    match root.file_name() {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => root.to_string_lossy().into_owned(),
+    }
+}
+
+/// The path with its parent's symlinks resolved, leaving a link itself as is
+fn resolved_parent(path: &Path) -> Option<PathBuf> {
+    let parent = path.parent()?.canonicalize().ok()?;
+    Some(match path.file_name() {
+        Some(name) => parent.join(name),
+        None => parent,
+    })
+}
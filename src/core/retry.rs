@@ -0,0 +1,46 @@
+use std::io;
+use std::time::Duration;
+
+/// How often an I/O operation failing with a transient error is tried again, waiting `backoff`
+/// before the first retry and twice as long before each one after it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub const NONE: RetryPolicy = RetryPolicy { retries: 0, backoff: Duration::ZERO };
+
+    /// Run `operation`, retrying transient failures; the last error is returned once retries run out
+    pub fn run<T>(&self, mut operation: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+        let mut delay = self.backoff;
+        let mut attempt = 0;
+        loop {
+            match operation() {
+                Err(e) if attempt < self.retries && is_transient(&e) => {
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Errors a network filesystem can return while a server is briefly unreachable or busy,
+/// which the same call may not hit a moment later; missing files and permissions are not among them
+pub fn is_transient(error: &io::Error) -> bool {
+    if matches!(error.kind(), io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) {
+        return true;
+    }
+    #[cfg(unix)]
+    {
+        matches!(error.raw_os_error(), Some(libc::EIO) | Some(libc::EAGAIN))
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
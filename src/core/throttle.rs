@@ -0,0 +1,47 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Caps the throughput of background copies and hashing, so a shared NAS stays usable while browsing
+/// Clones share one budget: all jobs together stay under the limit, and changing it affects every clone
+#[derive(Debug, Clone)]
+pub struct IoThrottle {
+    state: Arc<Mutex<ThrottleState>>,
+}
+
+#[derive(Debug)]
+struct ThrottleState {
+    bytes_per_second: u64,  // 0 for no limit
+    next_free: Instant,     // When the bytes accounted for so far would have been transferred at the limit
+}
+
+impl IoThrottle {
+    pub fn new(bytes_per_second: u64) -> Self {
+        Self { state: Arc::new(Mutex::new(ThrottleState { bytes_per_second, next_free: Instant::now() })) }
+    }
+
+    pub fn unlimited() -> Self {
+        Self::new(0)
+    }
+
+    pub fn set_limit(&self, bytes_per_second: u64) {
+        if let Ok(mut state) = self.state.lock() {
+            state.bytes_per_second = bytes_per_second;
+        }
+    }
+
+    /// Account for `bytes` just read or written, sleeping until they fit under the limit
+    /// Time spent idle is not saved up, so a job starting after a pause does not burst
+    pub fn consume(&self, bytes: u64) {
+        let delay = {
+            let Ok(mut state) = self.state.lock() else { return };
+            if state.bytes_per_second == 0 {
+                return;
+            }
+            let now = Instant::now();
+            let cost = Duration::from_secs_f64(bytes as f64 / state.bytes_per_second as f64);
+            state.next_free = state.next_free.max(now) + cost;
+            state.next_free - now
+        };
+        std::thread::sleep(delay);
+    }
+}
@@ -0,0 +1,109 @@
+use std::path::{Path, PathBuf};
+
+/// Paths longer than this need the `\\?\` prefix on Windows
+const MAX_PATH: usize = 260;
+
+/// Path as shown to the user: `\\?\C:\x` becomes `C:\x` and `\\?\UNC\server\share` becomes `\\server\share`
+/// Only Windows paths are rewritten; elsewhere this is `Path::display`
+pub fn display_path(path: &Path) -> String {
+    if cfg!(windows) {
+        windows_display(&path.to_string_lossy())
+    } else {
+        path.display().to_string()
+    }
+}
+
+/// Drop a `\\?\` prefix when the path works without it (short enough, no names only verbatim paths allow)
+/// Keeps `current_dir` in the familiar form after `canonicalize` or a launch from a long-path shell
+pub fn normalize_path(path: PathBuf) -> PathBuf {
+    if !cfg!(windows) {
+        return path;
+    }
+    let text = path.to_string_lossy();
+    let plain = windows_display(&text);
+    let root_len = windows_root_len(&plain);
+    let safe = plain.len() < MAX_PATH
+        && plain[root_len..].split('\\').all(|name| !name.ends_with('.') && !name.ends_with(' ') && !name.contains('/'));
+    if safe && plain != text {
+        PathBuf::from(plain)
+    } else {
+        path
+    }
+}
+
+/// Parent directory that never climbs above a drive or `\\server\share` root
+pub fn parent_path(path: &Path) -> Option<PathBuf> {
+    if cfg!(windows) {
+        windows_parent(&path.to_string_lossy()).map(PathBuf::from)
+    } else {
+        path.parent().map(Path::to_path_buf)
+    }
+}
+
+fn windows_display(path: &str) -> String {
+    if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{}", rest)
+    } else if let Some(rest) = path.strip_prefix(r"\\?\").filter(|rest| is_drive(rest)) {
+        rest.to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+fn windows_parent(path: &str) -> Option<String> {
+    let verbatim = path.starts_with(r"\\?\");
+    let is_separator = |c: char| c == '\\' || (!verbatim && c == '/');
+    let root_len = windows_root_len(path);
+
+    let trimmed = path.trim_end_matches(is_separator);
+    if trimmed.len() <= root_len {
+        return None;
+    }
+    let cut = trimmed[root_len..].rfind(is_separator).map(|index| root_len + index).unwrap_or(root_len);
+    let parent = trimmed[..cut].trim_end_matches(is_separator);
+    if parent.len() < root_len || cut == root_len {
+        // Keep the root's own separator so `C:\` is not turned into the drive-relative `C:`
+        Some(path[..root_len].to_string())
+    } else {
+        Some(parent.to_string())
+    }
+}
+
+/// Length of the root (`C:\`, `\\server\share\`, `\\?\UNC\server\share\`, `\\?\C:\`, `\`) including its separator
+fn windows_root_len(path: &str) -> usize {
+    if path.starts_with(r"\\?\UNC\") {
+        skip_components(path, r"\\?\UNC\".len(), 2, &['\\'])
+    } else if path.starts_with(r"\\?\") || path.starts_with(r"\\.\") {
+        // \\?\C:\, \\?\Volume{...}\ or \\.\device\
+        skip_components(path, 4, 1, &['\\'])
+    } else if path.starts_with(r"\\") || path.starts_with("//") {
+        skip_components(path, 2, 2, &['\\', '/'])
+    } else if is_drive(path) {
+        if path[2..].starts_with(['\\', '/']) { 3 } else { 2 }
+    } else if path.starts_with(['\\', '/']) {
+        1
+    } else {
+        0
+    }
+}
+
+/// Byte index just past `count` components starting at `start`, including the separator after the last one
+fn skip_components(path: &str, start: usize, count: usize, separators: &[char]) -> usize {
+    let mut end = start.min(path.len());
+    for component in 0..count {
+        if component > 0 {
+            if !path[end..].starts_with(separators) {
+                return end;
+            }
+            end += 1;
+        }
+        end = path[end..].find(separators).map(|index| end + index).unwrap_or(path.len());
+    }
+    if path[end..].starts_with(separators) { end + 1 } else { end }
+}
+
+/// `C:` at the start of `path`
+fn is_drive(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
@@ -0,0 +1,118 @@
+/// A token from a forgiving, non-validating XML scan
+#[derive(Debug, Clone, PartialEq)]
+pub enum XmlToken<'a> {
+    /// Opening tag (`self_closing` for `<tag/>`) with its raw attribute text
+    Start { name: &'a str, attributes: &'a str, self_closing: bool },
+    End { name: &'a str },
+    /// Text content with entities decoded
+    Text(String),
+}
+
+/// Split XML into tags and text, skipping declarations, comments and processing instructions
+/// Good enough for pulling text out of office documents; not a conforming parser
+pub fn xml_tokens(xml: &str) -> Vec<XmlToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = xml;
+
+    while !rest.is_empty() {
+        let Some(open) = rest.find('<') else {
+            tokens.push(XmlToken::Text(decode_entities(rest)));
+            break;
+        };
+        if open > 0 {
+            tokens.push(XmlToken::Text(decode_entities(&rest[..open])));
+        }
+        rest = &rest[open..];
+
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").unwrap_or(cdata.len());
+            tokens.push(XmlToken::Text(cdata[..end].to_string()));
+            rest = cdata.get(end + 3..).unwrap_or("");
+            continue;
+        }
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").and_then(|end| comment.get(end + 3..)).unwrap_or("");
+            continue;
+        }
+
+        let Some(close) = rest.find('>') else { break };
+        let tag = &rest[1..close];
+        rest = &rest[close + 1..];
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(XmlToken::End { name: name.trim() });
+        } else {
+            let self_closing = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            tokens.push(XmlToken::Start { name, attributes, self_closing });
+        }
+    }
+
+    tokens
+}
+
+/// Value of attribute `name` within a tag's raw attribute text
+pub fn xml_attribute(attributes: &str, name: &str) -> Option<String> {
+    let mut rest = attributes;
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim();
+        let after = rest[eq + 1..].trim_start();
+        let quote = after.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            return None;
+        }
+        let value_end = after[1..].find(quote)?;
+        if key == name {
+            return Some(decode_entities(&after[1..1 + value_end]));
+        }
+        rest = &after[value_end + 2..];
+    }
+    None
+}
+
+/// Decode the predefined and numeric character entities
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => {
+                    let code = match entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => entity.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
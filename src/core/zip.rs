@@ -0,0 +1,116 @@
+use std::io::Read;
+
+/// An entry in a zip archive's central directory
+#[derive(Debug, Clone)]
+pub struct ZipEntry {
+    pub name: String,
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
+    method: u16,
+    local_header_offset: u64,
+}
+
+/// Read-only view of an in-memory zip archive (stored and deflated entries only)
+#[derive(Debug)]
+pub struct ZipArchive<'a> {
+    data: &'a [u8],
+    pub entries: Vec<ZipEntry>,
+}
+
+const EOCD_SIGNATURE: u32 = 0x0605_4b50;
+const CENTRAL_SIGNATURE: u32 = 0x0201_4b50;
+const LOCAL_SIGNATURE: u32 = 0x0403_4b50;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+
+/// Refuse to inflate entries beyond this size (zip bombs)
+const MAX_ENTRY_SIZE: u64 = 64 * 1024 * 1024;
+
+fn u16_at(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn u32_at(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+impl<'a> ZipArchive<'a> {
+    /// Parse the central directory; returns None if this is not a zip archive
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        // The end-of-central-directory record sits within the last 64KiB (22 bytes + comment)
+        let search_start = data.len().saturating_sub(22 + u16::MAX as usize);
+        let eocd = (search_start..data.len().saturating_sub(21))
+            .rev()
+            .find(|&pos| u32_at(data, pos) == Some(EOCD_SIGNATURE))?;
+
+        let entry_count = u16_at(data, eocd + 10)? as usize;
+        let mut pos = u32_at(data, eocd + 16)? as usize;
+
+        let mut entries = Vec::with_capacity(entry_count.min(4096));
+        for _ in 0..entry_count {
+            if u32_at(data, pos)? != CENTRAL_SIGNATURE {
+                return None;
+            }
+            let method = u16_at(data, pos + 10)?;
+            let compressed_size = u32_at(data, pos + 20)? as u64;
+            let uncompressed_size = u32_at(data, pos + 24)? as u64;
+            let name_len = u16_at(data, pos + 28)? as usize;
+            let extra_len = u16_at(data, pos + 30)? as usize;
+            let comment_len = u16_at(data, pos + 32)? as usize;
+            let local_header_offset = u32_at(data, pos + 42)? as u64;
+            let name = data.get(pos + 46..pos + 46 + name_len)?;
+
+            entries.push(ZipEntry {
+                name: String::from_utf8_lossy(name).to_string(),
+                compressed_size,
+                uncompressed_size,
+                method,
+                local_header_offset,
+            });
+            pos += 46 + name_len + extra_len + comment_len;
+        }
+
+        Some(Self { data, entries })
+    }
+
+    /// Find an entry by its exact name
+    pub fn entry(&self, name: &str) -> Option<&ZipEntry> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    /// Decompress an entry's contents
+    pub fn read(&self, entry: &ZipEntry) -> Option<Vec<u8>> {
+        if entry.uncompressed_size > MAX_ENTRY_SIZE {
+            return None;
+        }
+        let header = entry.local_header_offset as usize;
+        if u32_at(self.data, header)? != LOCAL_SIGNATURE {
+            return None;
+        }
+        // Local name/extra lengths can differ from the central directory copy
+        let name_len = u16_at(self.data, header + 26)? as usize;
+        let extra_len = u16_at(self.data, header + 28)? as usize;
+        let start = header + 30 + name_len + extra_len;
+        let compressed = self.data.get(start..start.checked_add(entry.compressed_size as usize)?)?;
+
+        match entry.method {
+            METHOD_STORED => Some(compressed.to_vec()),
+            METHOD_DEFLATED => {
+                let mut out = Vec::with_capacity(entry.uncompressed_size as usize);
+                flate2::read::DeflateDecoder::new(compressed)
+                    .take(MAX_ENTRY_SIZE)
+                    .read_to_end(&mut out)
+                    .ok()?;
+                Some(out)
+            }
+            _ => None,
+        }
+    }
+
+    /// Decompress an entry by name as (lossy) UTF-8 text
+    pub fn read_text(&self, name: &str) -> Option<String> {
+        let bytes = self.read(self.entry(name)?)?;
+        Some(String::from_utf8_lossy(&bytes).to_string())
+    }
+}
@@ -0,0 +1,24 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{messages::AppMessage, model::AppModel, update::Msg};
+
+/// Handler for the `:version` popup
+#[derive(Debug)]
+pub struct AboutHandler {
+}
+
+impl AboutHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
+                vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)]
+            },
+
+            _ => Vec::new(),
+        }
+    }
+}
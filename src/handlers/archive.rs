@@ -0,0 +1,56 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{handlers::input_line::InputLine, messages::AppMessage, model::AppModel, services::ArchiveFormat, update::{Effect, Msg}};
+
+/// Handler for the prompt naming the archive the selected or marked entries are packed into
+/// Tab switches between zip and tar.gz, keeping the name
+#[derive(Debug)]
+pub struct ArchiveHandler {
+    pub input: InputLine,
+}
+
+impl ArchiveHandler {
+    /// `stem` is the suggested name without extension; zip is the default
+    pub fn new(stem: &str) -> Self {
+        let format = ArchiveFormat::Zip;
+        Self { input: InputLine::for_name(&format!("{}{}", stem, format.extension()), Some(format.extension().len())) }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        match (key.code, key.modifiers) {
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                // A failure stays in the prompt so the name can be fixed
+                vec![
+                    Msg::Do(Effect::ArchiveEntries(self.input.text.trim().to_string())),
+                    Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery),
+                ]
+            },
+            (KeyCode::Tab, KeyModifiers::NONE) => {
+                self.switch_format();
+                Vec::new()
+            },
+            (KeyCode::Esc, KeyModifiers::NONE) => {
+                vec![Msg::Status("Nothing packed".to_string()), Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)]
+            },
+            _ => {
+                self.input.handle_key(key);
+                Vec::new()
+            },
+        }
+    }
+
+    /// Swap the extension for the other format's, or add one if the name has neither
+    fn switch_format(&mut self) {
+        let text = &self.input.text;
+        let lower = text.to_lowercase();
+        let (stem, format) = match ArchiveFormat::from_name(text) {
+            Some(format) => {
+                let extension = [".tar.gz", ".tgz", ".zip"].into_iter().find(|extension| lower.ends_with(extension)).unwrap_or_default();
+                (&text[..text.len() - extension.len()], format.other())
+            },
+            None => (text.as_str(), ArchiveFormat::Zip),
+        };
+        let name = format!("{}{}", stem, format.extension());
+        self.input = InputLine::for_name(&name, Some(format.extension().len()));
+    }
+}
@@ -0,0 +1,52 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{messages::AppMessage, model::AppModel, update::Msg};
+
+/// Handler for the report of a bulk operation that finished with failures
+#[derive(Debug)]
+pub struct BatchReportHandler {
+    pub selected: usize,
+}
+
+impl BatchReportHandler {
+    pub fn new() -> Self {
+        Self { selected: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, model: &AppModel) -> Vec<Msg> {
+        let Some(batch) = model.batch.as_ref() else {
+            return vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)];
+        };
+        let count = batch.failures.len();
+        self.selected = self.selected.min(count.saturating_sub(1));
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected < count.saturating_sub(1) {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            },
+
+            // Try every failed entry again, e.g. after closing the program that held a lock
+            KeyCode::Char('r') => {
+                vec![Msg::RetryFailures, Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)]
+            },
+            // Give up on the entry under the cursor, keeping the others for a retry
+            KeyCode::Char('s') | KeyCode::Char('x') | KeyCode::Delete => {
+                vec![Msg::SkipFailure(self.selected)]
+            },
+
+            // Skip all of them
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
+                vec![Msg::DismissBatch, Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)]
+            },
+
+            _ => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{messages::AppMessage, model::AppModel, update::{Effect, Msg}};
+
+/// Handler for the key after `m`: the current directory is bookmarked on it, ESC cancels
+#[derive(Debug)]
+pub struct BookmarkSetHandler;
+
+impl BookmarkSetHandler {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => vec![Msg::Status(String::new()), Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+            // Back to exploring whether or not it could be saved
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery), Msg::Do(Effect::SetBookmark(c))]
+            },
+            _ => Vec::new(),
+        }
+    }
+}
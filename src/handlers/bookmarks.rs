@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{messages::AppMessage, model::{AppModel, PasteMode}, update::{Effect, Msg}};
+
+/// Handler for the bookmarks popup: a bookmark's key jumps there, or after `v` moves the selection there
+/// Up/Down pick a bookmark for Enter, which also reaches those without a key; Delete forgets it
+#[derive(Debug)]
+pub struct BookmarksHandler {
+    pub moving: bool,
+    pub selected: usize,
+}
+
+impl BookmarksHandler {
+    pub fn new() -> Self {
+        Self { moving: false, selected: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, model: &AppModel) -> Vec<Msg> {
+        let bookmarks = model.bookmarks();
+        // Forgetting one shortens the list
+        self.selected = self.selected.min(bookmarks.len().saturating_sub(1));
+
+        match key.code {
+            KeyCode::Char('v') if !self.moving => {
+                self.moving = true;
+                Vec::new()
+            },
+            KeyCode::Esc | KeyCode::Char('q') if !self.moving => vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+            // Back out of the move before closing
+            KeyCode::Esc => {
+                self.moving = false;
+                Vec::new()
+            },
+
+            KeyCode::Down => {
+                if self.selected + 1 < bookmarks.len() {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            },
+            KeyCode::Enter => match bookmarks.into_iter().nth(self.selected) {
+                Some(bookmark) => self.go(bookmark.path),
+                None => Vec::new(),
+            },
+            KeyCode::Delete if !self.moving => match bookmarks.len() {
+                0 => Vec::new(),
+                // Nothing left to pick from once the last one is gone
+                1 => vec![
+                    Msg::Do(Effect::RemoveBookmark(bookmarks[0].clone())),
+                    Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery),
+                ],
+                _ => vec![Msg::Do(Effect::RemoveBookmark(bookmarks[self.selected].clone()))],
+            },
+
+            KeyCode::Char(c) => match model.bookmark(c) {
+                Some(dir) => self.go(dir),
+                None => vec![Msg::Status(format!("No bookmark on {}", c))],
+            },
+
+            _ => Vec::new(),
+        }
+    }
+
+    /// Jump to `dir`, or move the selection there
+    fn go(&self, dir: PathBuf) -> Vec<Msg> {
+        let effect = if self.moving { Effect::SendEntriesTo(dir, PasteMode::Move) } else { Effect::EnterDirectory(dir) };
+        vec![Msg::Do(effect), Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)]
+    }
+}
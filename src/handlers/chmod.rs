@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{messages::AppMessage, model::AppModel, update::{Effect, Msg}};
+
+/// Bits shown in the grid, owner r/w/x first; the cursor is an index into it
+const GRID_BITS: [u32; 9] = [0o400, 0o200, 0o100, 0o040, 0o020, 0o010, 0o004, 0o002, 0o001];
+
+/// Handler for the permission editor of the selected entry
+/// Space toggles the bit under the cursor; typing 3 or 4 octal digits sets the whole mode
+#[derive(Debug)]
+pub struct ChmodHandler {
+    pub path: PathBuf,
+    pub original: u32,
+    pub mode: u32,
+    pub cursor: usize,
+    pub octal: String,  // Digits typed so far
+}
+
+impl ChmodHandler {
+    pub fn new(path: PathBuf, mode: u32) -> Self {
+        Self { path, original: mode, mode, cursor: 0, octal: String::new() }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        match (key.code, key.modifiers) {
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                if self.mode == self.original {
+                    return Self::unchanged();
+                }
+                vec![
+                    Msg::Do(Effect::SetPermissions(self.path.clone(), self.mode)),
+                    Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery),
+                ]
+            },
+            (KeyCode::Esc, KeyModifiers::NONE) | (KeyCode::Char('q'), KeyModifiers::NONE) => Self::unchanged(),
+            (KeyCode::Left, _) | (KeyCode::Char('h'), KeyModifiers::NONE) => {
+                self.cursor = (self.cursor + GRID_BITS.len() - 1) % GRID_BITS.len();
+                Vec::new()
+            },
+            (KeyCode::Right, _) | (KeyCode::Char('l'), KeyModifiers::NONE) => {
+                self.cursor = (self.cursor + 1) % GRID_BITS.len();
+                Vec::new()
+            },
+            // A whole class (owner, group, other) up or down
+            (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
+                self.cursor = (self.cursor + GRID_BITS.len() - 3) % GRID_BITS.len();
+                Vec::new()
+            },
+            (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
+                self.cursor = (self.cursor + 3) % GRID_BITS.len();
+                Vec::new()
+            },
+            (KeyCode::Char(' '), KeyModifiers::NONE) => {
+                self.mode ^= GRID_BITS[self.cursor];
+                self.octal.clear();
+                Vec::new()
+            },
+            (KeyCode::Char(digit @ '0'..='7'), KeyModifiers::NONE) => {
+                if self.octal.len() == 4 {
+                    self.octal.clear();
+                }
+                self.octal.push(digit);
+                self.apply_octal();
+                Vec::new()
+            },
+            (KeyCode::Backspace, KeyModifiers::NONE) => {
+                self.octal.pop();
+                self.apply_octal();
+                Vec::new()
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    fn unchanged() -> Vec<Msg> {
+        vec![Msg::Status("Permissions unchanged".to_string()), Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)]
+    }
+
+    /// Three digits set the permission bits and clear setuid/setgid/sticky, four set those too
+    fn apply_octal(&mut self) {
+        if self.octal.len() >= 3 {
+            if let Ok(mode) = u32::from_str_radix(&self.octal, 8) {
+                self.mode = mode;
+            }
+        }
+    }
+}
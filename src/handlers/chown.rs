@@ -0,0 +1,79 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{messages::AppMessage, model::AppModel, services::Account, update::{Effect, Msg}};
+
+/// Handler for giving the marked entries (or the selected one) to another owner, recursively
+/// A user is picked first, then a group; typing filters the list, which starts on the current owner
+#[derive(Debug)]
+pub struct ChownHandler {
+    pub users: Vec<Account>,
+    pub groups: Vec<Account>,
+    pub query: String,
+    pub selected: usize,
+    pub user: Option<Account>,  // Picked, the group is being picked now
+    owner: Option<(u32, u32)>,  // Current user and group ids of the selected entry
+}
+
+impl ChownHandler {
+    pub fn new(users: Vec<Account>, groups: Vec<Account>, owner: Option<(u32, u32)>) -> Self {
+        let selected = owner.and_then(|(uid, _)| users.iter().position(|user| user.id == uid)).unwrap_or(0);
+        Self { users, groups, query: String::new(), selected, user: None, owner }
+    }
+
+    /// The accounts of the current step matching what was typed, by name or id
+    pub fn candidates(&self) -> Vec<&Account> {
+        let accounts = if self.user.is_some() { &self.groups } else { &self.users };
+        accounts
+            .iter()
+            .filter(|account| account.name.contains(&self.query) || account.id.to_string() == self.query)
+            .collect()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        match (key.code, key.modifiers) {
+            // Back from the group to the user, then out
+            (KeyCode::Esc, _) => {
+                if self.user.take().is_none() {
+                    return vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)];
+                }
+                self.query.clear();
+                self.selected = self.owner.and_then(|(uid, _)| self.users.iter().position(|user| user.id == uid)).unwrap_or(0);
+                Vec::new()
+            },
+            (KeyCode::Enter, _) => {
+                let Some(picked) = self.candidates().get(self.selected).map(|account| (*account).clone()) else {
+                    return vec![Msg::Status("No such account".to_string())];
+                };
+                let Some(user) = &self.user else {
+                    self.user = Some(picked);
+                    self.query.clear();
+                    self.selected = self.owner.and_then(|(_, gid)| self.groups.iter().position(|group| group.id == gid)).unwrap_or(0);
+                    return Vec::new();
+                };
+                // Back to exploring whether or not it could start
+                vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery), Msg::Do(Effect::ChownEntries(user.clone(), picked))]
+            },
+            (KeyCode::Down, _) | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
+                if self.selected + 1 < self.candidates().len() {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            (KeyCode::Up, _) | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            },
+            (KeyCode::Backspace, _) => {
+                self.query.pop();
+                self.selected = 0;
+                Vec::new()
+            },
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                self.query.push(c);
+                self.selected = 0;
+                Vec::new()
+            },
+            _ => Vec::new(),
+        }
+    }
+}
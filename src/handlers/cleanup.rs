@@ -0,0 +1,71 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{messages::AppMessage, model::{AppModel, DeleteMode}, services::file_service::Leftover, update::{Effect, Msg}};
+
+/// Handler for reviewing what a maintenance scan found: broken symlinks, empty files and empty directories
+/// Every entry starts checked; `d` trashes and `D` deletes the checked ones after the usual confirmation
+#[derive(Debug)]
+pub struct CleanupHandler {
+    pub leftovers: Vec<Leftover>,
+    pub checked: Vec<bool>,
+    pub selected: usize,
+}
+
+impl CleanupHandler {
+    pub fn new(leftovers: Vec<Leftover>) -> Self {
+        let checked = vec![true; leftovers.len()];
+        Self { leftovers, checked, selected: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected + 1 < self.leftovers.len() {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            },
+            KeyCode::Char(' ') => {
+                if let Some(checked) = self.checked.get_mut(self.selected) {
+                    *checked = !*checked;
+                }
+                if self.selected + 1 < self.leftovers.len() {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            // Check everything, or nothing once everything is
+            KeyCode::Char('a') => {
+                let all = self.checked.iter().all(|checked| *checked);
+                self.checked.iter_mut().for_each(|checked| *checked = !all);
+                Vec::new()
+            },
+            KeyCode::Char('d') | KeyCode::Char('D') => {
+                let paths: Vec<_> = self.leftovers
+                    .iter()
+                    .zip(&self.checked)
+                    .filter(|(_, checked)| **checked)
+                    .map(|(leftover, _)| leftover.path.clone())
+                    .collect();
+                if paths.is_empty() {
+                    return vec![Msg::Status("Nothing checked - space checks an entry".to_string())];
+                }
+                let mode = if key.code == KeyCode::Char('D') { DeleteMode::Permanent } else { DeleteMode::Normal };
+                vec![Msg::App(AppMessage::SwitchToConfirmDeleteHandler(paths, mode))]
+            },
+            // Jump to the entry in its directory
+            KeyCode::Enter | KeyCode::Char('l') => {
+                match self.leftovers.get(self.selected) {
+                    Some(entry) => vec![Msg::Do(Effect::Reveal(entry.path.clone())), Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+                    None => Vec::new(),
+                }
+            },
+            KeyCode::Esc | KeyCode::Char('q') => vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+            _ => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{commands::Command, core::{check_name, PathCompletion}, messages::AppMessage, model::AppModel, update::Msg};
+
+/// Handler for the `:` command line
+/// Keeps its own input buffer so the search query is left untouched
+#[derive(Debug)]
+pub struct CommandHandler {
+    pub input: String,
+    pub completion: PathCompletion,  // For the directory of `:cd`
+}
+
+impl CommandHandler {
+    pub fn new(input: &str) -> Self {
+        Self {
+            input: input.to_string(),
+            completion: PathCompletion::default(),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, model: &AppModel) -> Vec<Msg> {
+        match (key.code, key.modifiers) {
+            // Run the command and return to explore mode
+            // A name the platform would reject is swapped for its sanitized form first, so the user can review it
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                let rejected = Command::new_name(&self.input)
+                    .and_then(|(_, name)| check_name(name))
+                    .is_some_and(|problem| problem.fatal);
+                match Command::with_sanitized_name(&self.input).filter(|_| rejected) {
+                    Some(sanitized) => {
+                        self.input = sanitized;
+                        Vec::new()
+                    }
+                    None => vec![Msg::App(AppMessage::ExecuteCommand(self.input.clone()))],
+                }
+            },
+            (KeyCode::Tab, KeyModifiers::NONE) | (KeyCode::BackTab, _) if Command::directory_argument(&self.input).is_some() => {
+                let typed = Command::directory_argument(&self.input).unwrap_or_default();
+                match self.completion.complete(&model.current_dir, typed, key.code == KeyCode::BackTab) {
+                    Some(completed) => {
+                        self.input = format!("cd {}", completed);
+                        Vec::new()
+                    },
+                    None => vec![Msg::Status("No directory to complete".to_string())],
+                }
+            },
+            (KeyCode::Tab, KeyModifiers::NONE) => {
+                if let Some(sanitized) = Command::with_sanitized_name(&self.input) {
+                    self.input = sanitized;
+                }
+                Vec::new()
+            },
+            (KeyCode::Esc, KeyModifiers::NONE) => vec![Msg::App(AppMessage::SwitchToExploreHandler)],
+
+            // Character manipulation
+            (KeyCode::Backspace, KeyModifiers::NONE) => {
+                self.completion.reset();
+                // Backspace on an empty line leaves command mode, like vim
+                if self.input.pop().is_none() {
+                    vec![Msg::App(AppMessage::SwitchToExploreHandler)]
+                } else {
+                    Vec::new()
+                }
+            },
+            (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+                self.completion.reset();
+                self.input.clear();
+                Vec::new()
+            },
+            (KeyCode::Char('w'), KeyModifiers::CONTROL) => {
+                self.completion.reset();
+                let trimmed = self.input.trim_end().len();
+                self.input.truncate(trimmed);
+                match self.input.rfind(' ') {
+                    Some(pos) => self.input.truncate(pos + 1),
+                    None => self.input.clear(),
+                }
+                Vec::new()
+            },
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                self.completion.reset();
+                self.input.push(c);
+                Vec::new()
+            },
+
+            _ => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{core::confirmation_word, handlers::input_line::InputLine, messages::AppMessage, model::{AppModel, DeleteMode}, update::Msg};
+
+/// Handler for the prompt asking whether to really delete the marked or selected entries
+#[derive(Debug)]
+pub struct ConfirmDeleteHandler {
+    pub paths: Vec<PathBuf>,
+    pub mode: DeleteMode,
+    pub protected: Option<PathBuf>,  // Protected directory the entries are in, whose name must be typed
+    pub typed: InputLine,
+}
+
+impl ConfirmDeleteHandler {
+    pub fn new(paths: Vec<PathBuf>, mode: DeleteMode, protected: Option<PathBuf>) -> Self {
+        Self { paths, mode, protected, typed: InputLine::new("", 0) }
+    }
+
+    /// Only `y` deletes, so a stray Enter or repeated key never does
+    /// Under a protected directory its name has to be typed and confirmed with Enter instead
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        if let Some(root) = &self.protected {
+            return match key.code {
+                KeyCode::Enter if self.typed.text == confirmation_word(root) => {
+                    vec![Msg::App(AppMessage::DeleteEntries(std::mem::take(&mut self.paths), self.mode))]
+                },
+                KeyCode::Enter => {
+                    vec![Msg::Status(format!("Type '{}' to delete", confirmation_word(root)))]
+                },
+                KeyCode::Esc => Self::keep(),
+                _ => {
+                    self.typed.handle_key(key);
+                    Vec::new()
+                },
+            };
+        }
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => vec![Msg::App(AppMessage::DeleteEntries(std::mem::take(&mut self.paths), self.mode))],
+            KeyCode::Char('n') | KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => Self::keep(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Back to exploring with nothing deleted
+    fn keep() -> Vec<Msg> {
+        vec![Msg::Status("Nothing deleted".to_string()), Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)]
+    }
+}
@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{core::confirmation_word, handlers::{input_line::InputLine, Handler}, model::AppModel, update::{Effect, Msg}};
+
+/// Handler for the prompt asking for the name of a protected directory before changing entries in it
+/// The effects held back are carried out once it is typed, after the popup that asked for them is back up
+#[derive(Debug)]
+pub struct ConfirmProtectedHandler {
+    pub root: PathBuf,
+    pub what: &'static str,  // e.g. `move entries out of`
+    pub typed: InputLine,
+    deferred: Vec<Effect>,
+    previous: Option<Box<Handler>>,
+}
+
+impl ConfirmProtectedHandler {
+    pub fn new(root: PathBuf, what: &'static str, deferred: Vec<Effect>, previous: Handler) -> Self {
+        Self { root, what, typed: InputLine::new("", 0), deferred, previous: Some(Box::new(previous)) }
+    }
+
+    /// The name has to be typed and confirmed with Enter; the first effect held back is the protected one
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        match key.code {
+            KeyCode::Enter if self.typed.text == confirmation_word(&self.root) => {
+                let mut deferred = std::mem::take(&mut self.deferred).into_iter();
+                let confirmed = deferred.next().map(|effect| Effect::Confirmed(Box::new(effect)));
+                confirmed.into_iter().chain(deferred).map(Msg::Do).collect()
+            },
+            KeyCode::Enter => {
+                vec![Msg::Status(format!("Type '{}' to proceed", confirmation_word(&self.root)))]
+            },
+            KeyCode::Esc => {
+                self.deferred.clear();
+                vec![Msg::Status("Nothing changed".to_string())]
+            },
+            _ => {
+                self.typed.handle_key(key);
+                Vec::new()
+            },
+        }
+    }
+
+    /// The handler to go back to once the prompt was answered either way
+    pub fn finished(&mut self) -> Option<Handler> {
+        if !self.deferred.is_empty() {
+            return None;
+        }
+        self.previous.take().map(|previous| *previous)
+    }
+}
@@ -0,0 +1,24 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{messages::AppMessage, model::AppModel, update::Msg};
+
+/// Handler for the panel listing problems found at startup or on a config reload
+#[derive(Debug)]
+pub struct DiagnosticsHandler {
+}
+
+impl DiagnosticsHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
+                vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)]
+            },
+
+            _ => Vec::new(),
+        }
+    }
+}
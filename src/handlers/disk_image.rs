@@ -0,0 +1,50 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{messages::AppMessage, model::AppModel, services::ImageListing, update::{Effect, Msg}};
+
+/// Handler for browsing a directory of an ISO image; Enter descends into a directory or extracts a file
+#[derive(Debug)]
+pub struct DiskImageHandler {
+    pub listing: ImageListing,
+    pub selected: usize,
+}
+
+impl DiskImageHandler {
+    pub fn new(listing: ImageListing) -> Self {
+        Self { listing, selected: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        let image = self.listing.image.clone();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected + 1 < self.listing.entries.len() {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            },
+            // The popup stays up after extracting, so several files can be taken out in a row
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => match self.listing.entries.get(self.selected) {
+                Some(entry) if entry.is_directory => {
+                    let mut trail = self.listing.trail.clone();
+                    trail.push(entry.clone());
+                    vec![Msg::Do(Effect::BrowseDiskImage(image, trail))]
+                },
+                Some(entry) => vec![Msg::Do(Effect::ExtractFromImage(image, entry.clone()))],
+                None => Vec::new(),
+            },
+            KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace if !self.listing.trail.is_empty() => {
+                let trail = self.listing.trail[..self.listing.trail.len() - 1].to_vec();
+                vec![Msg::Do(Effect::BrowseDiskImage(image, trail))]
+            },
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') | KeyCode::Left => {
+                vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)]
+            },
+            _ => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{messages::AppMessage, model::AppModel, update::{Effect, Msg}};
+
+/// Handler for the downloads panel; leaving it in any way counts what it listed as seen
+#[derive(Debug)]
+pub struct DownloadsHandler {
+    pub selected: usize,
+}
+
+impl DownloadsHandler {
+    pub fn new() -> Self {
+        Self { selected: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, model: &AppModel) -> Vec<Msg> {
+        let downloads = model.downloads();
+        let count = downloads.len();
+        let Some(current) = downloads.get(self.selected) else {
+            return match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('W') => Self::leave(AppMessage::SwitchToExploreHandlerKeepQuery),
+                _ => Vec::new(),
+            };
+        };
+        let path = current.path.clone();
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected < count.saturating_sub(1) {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            },
+
+            // Jump to the entry in the downloads directory
+            KeyCode::Enter | KeyCode::Char('l') => Self::reveal_then(path, AppMessage::SwitchToExploreHandlerKeepQuery),
+            // Open a file in the editor; a directory is entered instead
+            KeyCode::Char('o') if current.is_directory => {
+                let mut messages = vec![Msg::Do(Effect::EnterDirectory(path))];
+                messages.extend(Self::leave(AppMessage::SwitchToExploreHandlerKeepQuery));
+                messages
+            },
+            KeyCode::Char('o') => Self::reveal_then(path, AppMessage::OpenFileInExplore),
+            // Rename it where it is
+            KeyCode::Char('r') => match path.file_name() {
+                Some(name) => {
+                    let name = name.to_string_lossy().to_string();
+                    Self::reveal_then(path, AppMessage::SwitchToRenameHandler(name))
+                },
+                None => Vec::new(),
+            },
+            // Cut it, to be pasted with p wherever it belongs
+            KeyCode::Char('m') => {
+                let mut messages = vec![Msg::Cut(path)];
+                messages.extend(Self::leave(AppMessage::SwitchToExploreHandlerKeepQuery));
+                messages
+            },
+
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('W') => Self::leave(AppMessage::SwitchToExploreHandlerKeepQuery),
+
+            _ => Vec::new(),
+        }
+    }
+
+    /// Select the entry in its directory, then carry on with `message`; a failure tells why and stays here
+    fn reveal_then(path: std::path::PathBuf, message: AppMessage) -> Vec<Msg> {
+        let mut messages = vec![Msg::Do(Effect::RevealOpened(path))];
+        messages.extend(Self::leave(message));
+        messages
+    }
+
+    /// Leave the panel with `message`, counting what it listed as seen
+    fn leave(message: AppMessage) -> Vec<Msg> {
+        vec![Msg::MarkDownloadsSeen, Msg::App(message)]
+    }
+}
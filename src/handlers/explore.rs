@@ -1,67 +1,68 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::{actions::Action, model::SortKey};
 
-use crate::{messages::AppMessage, model::AppModel};
+/// Built-in keys of explore mode; `1`-`4` sort by the columns in order
+pub const BINDINGS: &[(&str, Action)] = &[
+    ("j", Action::NavDown), ("down", Action::NavDown),
+    ("k", Action::NavUp), ("up", Action::NavUp),
+    ("h", Action::NavParent), ("left", Action::NavParent), ("esc", Action::NavParent),
+    ("l", Action::NavOpen), ("right", Action::NavOpen), ("enter", Action::NavOpen),
+    ("G", Action::NavJobDestination),
+    ("D", Action::FileDecrypt),
+    ("#", Action::FileChecksums),
+    ("=", Action::FileVerify),
+    ("y", Action::ClipboardCopy),
+    ("d", Action::ClipboardCut),
+    ("p", Action::ClipboardPaste),
+    ("P", Action::ClipboardPasteImage),
+    ("E", Action::FileSendToTerminal),
+    ("o", Action::FileOpenWithFileManager),
+    ("O", Action::DirOpenWithFileManager),
+    ("r", Action::ViewRefresh), ("f5", Action::ViewRefresh),
+    ("c", Action::ViewToggleDetails),
+    ("g", Action::ViewCycleGrouping),
+    ("1", Action::SortBy(SortKey::Name)),
+    ("2", Action::SortBy(SortKey::Size)),
+    ("3", Action::SortBy(SortKey::Modified)),
+    ("4", Action::SortBy(SortKey::Extension)),
+    ("I", Action::ViewToggleProject),
+    ("v", Action::ViewToggleReadme),
+    ("space", Action::MarksToggle),
+    ("V", Action::MarksVisual),
+    ("ctrl-a", Action::MarksAll),
+    ("*", Action::MarksInvert),
+    ("M", Action::MarksReview),
+    ("H", Action::HistoryRecents),
+    ("L", Action::HistoryOpenLast),
+    (":", Action::ModeCommand),
+    ("N", Action::FileNew), ("a", Action::FileNew),
+    ("+", Action::DirNew), ("A", Action::DirNew),
+    ("R", Action::FileRename), ("f2", Action::FileRename),
+    ("C", Action::FileChmod),
+    ("ctrl-o", Action::FileChown),
+    ("ctrl-k", Action::FileHardLinks),
+    ("x", Action::FileDelete), ("delete", Action::FileDelete),
+    ("X", Action::FileDeletePermanently), ("shift-delete", Action::FileDeletePermanently),
+    ("u", Action::FileUndo),
+    ("U", Action::FileRedo),
+    ("s", Action::FileSendTo),
+    ("z", Action::FileArchive),
+    ("Z", Action::FileBrowseImage),
+    ("tab", Action::ModeLinks),
+    ("t", Action::ModeGrid),
+    ("i", Action::ModeProperties),
+    ("J", Action::ModeJobs),
+    ("W", Action::ModeDownloads),
+    ("'", Action::ModeBookmarks),
+    ("m", Action::BookmarkSet),
+    ("/", Action::ModeSearch),
+    ("f", Action::ModeFuzzyFind),
+    ("q", Action::AppQuit),
+];
 
 #[derive(Debug)]
 pub struct ExploreHandler {
 }
 
-impl ExploreHandler {
-    pub fn handle_key(&mut self, key: KeyEvent, model: &mut AppModel) -> Option<AppMessage> {
-        match key.code {
-            // Navigation keys - handle directly
-            KeyCode::Char('j') | KeyCode::Down => {
-                model.move_selection_down();
-                None
-            },
-            KeyCode::Char('k') | KeyCode::Up => {
-                model.move_selection_up();
-                None
-            },
-            
-            // Directory navigation
-            KeyCode::Char('h') | KeyCode::Left | KeyCode::Esc => {
-                match model.go_to_parent() {
-                    Ok(_) => None,
-                    Err(e) => Some(AppMessage::Error(format!("Navigation error: {}", e))),
-                }
-            },
-            
-            // Smart selection: directory navigation or file opening
-            KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
-                if let Some(selected) = model.get_selected_file() {
-                    if selected.is_directory {
-                        // Navigate into directory
-                        match model.enter_selected_directory() {
-                            Ok(_) => None,
-                            Err(e) => Some(AppMessage::Error(format!("Navigation error: {}", e))),
-                        }
-                    } else {
-                        // Open file - send message to App
-                        Some(AppMessage::OpenFile)
-                    }
-                } else {
-                    None
-                }
-            },
-            
-            // Refresh
-            KeyCode::Char('r') | KeyCode::F(5) => {
-                model.refresh_current_directory();
-                None
-            },
-            
-            // Global actions - send messages to App
-            KeyCode::Char('/') => Some(AppMessage::SwitchToSearchHandler),
-            KeyCode::Char('f') => Some(AppMessage::SwitchToFuzzyFindHandler),
-            KeyCode::Char('q') => Some(AppMessage::Quit),
-            
-            _ => None,
-        }
-    }
-}
-
-
 impl ExploreHandler {
     pub fn new() -> Self {
         Self {
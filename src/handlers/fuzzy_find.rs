@@ -1,5 +1,5 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use crate::{messages::AppMessage, model::AppModel};
+use crate::{actions::Action, messages::AppMessage, model::AppModel, update::{Effect, Msg}};
 
 #[derive(Debug)]
 pub struct FuzzyFindHandler {
@@ -10,73 +10,59 @@ impl FuzzyFindHandler {
         Self {}
     }
 
-    pub fn handle_key(&mut self, key: KeyEvent, model: &mut AppModel) -> Option<AppMessage> {
+    pub fn handle_key(&mut self, key: KeyEvent, model: &AppModel) -> Vec<Msg> {
         match (key.code, key.modifiers) {
             // Open selected file or navigate to directory
             (KeyCode::Enter, KeyModifiers::NONE) => {
-                if let Some(selected_file) = model.get_selected_file() {
-                    if selected_file.is_directory {
-                        // Navigate to directory directly, then switch back to explore mode
-                        let path = selected_file.path.clone();
-                        if let Err(e) = model.change_directory(path) {
-                            Some(AppMessage::Error(format!("Failed to navigate to directory: {}", e)))
-                        } else {
-                            Some(AppMessage::SwitchToExploreHandler)
-                        }
-                    } else {
-                        // Open file with editor
-                        Some(AppMessage::OpenFile)
-                    }
-                } else {
-                    None
+                match model.get_selected_file() {
+                    // Navigate to directory directly, then switch back to explore mode
+                    Some(selected_file) if selected_file.is_directory => vec![
+                        Msg::Do(Effect::EnterDirectory(selected_file.path.clone())),
+                        Msg::App(AppMessage::SwitchToExploreHandler),
+                    ],
+                    // Open file with editor
+                    Some(_) => vec![Msg::App(AppMessage::OpenFile)],
+                    None => Vec::new(),
                 }
             },
             
             // Navigation keys within fuzzy find results
             (KeyCode::Down, KeyModifiers::NONE) => {
-                model.move_selection_down();
-                None
+                vec![Action::NavDown.into()]
             },
             (KeyCode::Up, KeyModifiers::NONE) => {
-                model.move_selection_up();
-                None
+                vec![Action::NavUp.into()]
             },
             
             // Unix-style navigation with Ctrl+N/Ctrl+P
             (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
-                model.move_selection_down();
-                None
+                vec![Action::NavDown.into()]
             },
             (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
-                model.move_selection_up();
-                None
+                vec![Action::NavUp.into()]
             },
             
             // Exit actions
-            (KeyCode::Esc, KeyModifiers::NONE) => Some(AppMessage::SwitchToExploreHandler),
-            (KeyCode::Char('q'), KeyModifiers::NONE) => Some(AppMessage::Quit),
+            (KeyCode::Esc, KeyModifiers::NONE) => vec![Msg::App(AppMessage::SwitchToExploreHandler)],
+            (KeyCode::Char('q'), KeyModifiers::NONE) => vec![Msg::App(AppMessage::Quit)],
             
             // Character manipulation
             (KeyCode::Backspace, KeyModifiers::NONE) => {
-                model.pop_from_query();
-                None
+                vec![Msg::QueryBackspace]
             },
             (KeyCode::Char('w'), KeyModifiers::CONTROL) => {
-                model.delete_word_backward();
-                None
+                vec![Msg::QueryDeleteWord]
             },
             (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
-                model.delete_to_end();
-                None
+                vec![Msg::QueryDeleteToEnd]
             },
             
             // Edit search query - regular characters without control modifiers
             (KeyCode::Char(c), KeyModifiers::NONE) => {
-                model.append_to_query(c);
-                None
+                vec![Msg::QueryInsert(c)]
             },
             
-            _ => None,
+            _ => Vec::new(),
         }
     }
 }
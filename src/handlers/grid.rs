@@ -0,0 +1,46 @@
+use crate::{actions::Action, messages::AppMessage, model::AppModel, ui::grid_columns, update::Msg};
+
+/// Built-in keys of the thumbnail grid
+pub const BINDINGS: &[(&str, Action)] = &[
+    ("right", Action::NavRight), ("l", Action::NavRight),
+    ("left", Action::NavLeft), ("h", Action::NavLeft),
+    ("down", Action::NavDown), ("j", Action::NavDown),
+    ("up", Action::NavUp), ("k", Action::NavUp),
+    ("enter", Action::NavOpen),
+    ("backspace", Action::NavParent),
+    ("t", Action::ViewClose), ("esc", Action::ViewClose),
+    ("q", Action::AppQuit),
+];
+
+/// Handler for the thumbnail grid view
+#[derive(Debug)]
+pub struct GridHandler {
+}
+
+impl GridHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Do `action`; actions of other modes are ignored
+    pub fn perform(&mut self, action: Action, _model: &AppModel) -> Vec<Msg> {
+        // The grid spans the full terminal width minus its border
+        let columns = crossterm::terminal::size()
+            .map(|(width, _)| grid_columns(width.saturating_sub(2)))
+            .unwrap_or(1) as isize;
+
+        match action {
+            Action::NavRight => vec![Msg::MoveSelectionBy(1)],
+            Action::NavLeft => vec![Msg::MoveSelectionBy(-1)],
+            Action::NavDown => vec![Msg::MoveSelectionBy(columns)],
+            Action::NavUp => vec![Msg::MoveSelectionBy(-columns)],
+
+            // Same as in the listing
+            Action::NavOpen | Action::NavParent | Action::AppQuit => vec![action.into()],
+
+            Action::ViewClose => vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+
+            _ => Vec::new(),
+        }
+    }
+}
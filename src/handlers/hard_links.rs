@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{messages::AppMessage, model::AppModel, update::{Effect, Msg}};
+
+/// Handler for the list of other hard links to the selected file
+#[derive(Debug)]
+pub struct HardLinksHandler {
+    pub links: Vec<PathBuf>,
+    pub selected: usize,
+}
+
+impl HardLinksHandler {
+    pub fn new(links: Vec<PathBuf>) -> Self {
+        Self { links, selected: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected + 1 < self.links.len() {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            },
+            // Jump to the link in its directory
+            KeyCode::Enter | KeyCode::Char('l') => {
+                match self.links.get(self.selected) {
+                    Some(path) => vec![Msg::Do(Effect::Reveal(path.clone())), Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+                    None => Vec::new(),
+                }
+            },
+            KeyCode::Esc | KeyCode::Char('q') => vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+            _ => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// A single line of text being typed into a prompt, with a cursor that can move anywhere in it
+/// Prompts handle Enter, Esc and Tab themselves and hand every other key to `handle_key`
+#[derive(Debug)]
+pub struct InputLine {
+    pub text: String,
+    pub cursor: usize,  // Byte offset into `text`, always on a character boundary
+}
+
+impl InputLine {
+    /// Start out with `text`, the cursor at byte offset `cursor`
+    pub fn new(text: &str, cursor: usize) -> Self {
+        Self { text: text.to_string(), cursor: cursor.min(text.len()) }
+    }
+
+    /// Start out as a file name with the cursor before the extension, so the stem can be retyped
+    /// `extension_len` is how much of the end counts as extension, e.g. 7 for `.tar.gz`; None finds the last dot
+    pub fn for_name(name: &str, extension_len: Option<usize>) -> Self {
+        // Dotfiles like `.bashrc` have no extension to skip
+        let cursor = match extension_len {
+            Some(len) => name.len().saturating_sub(len),
+            None => match name.rfind('.') {
+                Some(dot) if dot > 0 => dot,
+                _ => name.len(),
+            },
+        };
+        Self::new(name, cursor)
+    }
+
+    /// Replace the text, with the cursor at its end
+    pub fn replace(&mut self, text: String) {
+        self.text = text;
+        self.cursor = self.text.len();
+    }
+
+    /// Move the cursor or edit the text around it; returns false for keys that are not for editing
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match (key.code, key.modifiers) {
+            // Cursor movement
+            (KeyCode::Left, KeyModifiers::NONE) | (KeyCode::Char('b'), KeyModifiers::CONTROL) => {
+                self.cursor = self.previous_boundary();
+            },
+            (KeyCode::Right, KeyModifiers::NONE) | (KeyCode::Char('f'), KeyModifiers::CONTROL) => {
+                self.cursor = self.next_boundary();
+            },
+            (KeyCode::Home, KeyModifiers::NONE) | (KeyCode::Char('a'), KeyModifiers::CONTROL) => {
+                self.cursor = 0;
+            },
+            (KeyCode::End, KeyModifiers::NONE) | (KeyCode::Char('e'), KeyModifiers::CONTROL) => {
+                self.cursor = self.text.len();
+            },
+
+            // Character manipulation around the cursor
+            (KeyCode::Backspace, KeyModifiers::NONE) => {
+                let start = self.previous_boundary();
+                self.text.replace_range(start..self.cursor, "");
+                self.cursor = start;
+            },
+            (KeyCode::Delete, KeyModifiers::NONE) => {
+                let end = self.next_boundary();
+                self.text.replace_range(self.cursor..end, "");
+            },
+            (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+                self.text.replace_range(..self.cursor, "");
+                self.cursor = 0;
+            },
+            (KeyCode::Char('w'), KeyModifiers::CONTROL) => {
+                let before = self.text[..self.cursor].trim_end();
+                let start = before.rfind([' ', '.', '-', '_']).map(|pos| pos + 1).unwrap_or(0);
+                self.text.replace_range(start..self.cursor, "");
+                self.cursor = start;
+            },
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                self.text.insert(self.cursor, c);
+                self.cursor += c.len_utf8();
+            },
+
+            _ => return false,
+        }
+        true
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.text[..self.cursor].char_indices().next_back().map(|(pos, _)| pos).unwrap_or(0)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.text[self.cursor..].chars().next().map(|c| self.cursor + c.len_utf8()).unwrap_or(self.cursor)
+    }
+}
@@ -0,0 +1,50 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{actions::Action, messages::AppMessage, model::{AppModel, JobKind}, update::Msg};
+
+/// Handler for the panel listing background jobs, where they can be paused and resumed, and bulk operations cancelled
+#[derive(Debug)]
+pub struct JobsHandler {
+    pub selected: usize,
+}
+
+impl JobsHandler {
+    pub fn new() -> Self {
+        Self { selected: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, model: &AppModel) -> Vec<Msg> {
+        let jobs = model.jobs();
+        // Jobs finish while the panel is open
+        self.selected = self.selected.min(jobs.len().saturating_sub(1));
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected < jobs.len().saturating_sub(1) {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            },
+
+            KeyCode::Char('p') | KeyCode::Char(' ') => match jobs.get(self.selected) {
+                Some(job) => vec![Msg::ToggleJobPause(job.kind)],
+                None => Vec::new(),
+            },
+
+            KeyCode::Char('c') => match jobs.get(self.selected) {
+                Some(job) if job.kind == JobKind::Batch => vec![Action::JobCancel.into()],
+                Some(_) => vec![Msg::Status("Checksums can only be paused".to_string())],
+                None => Vec::new(),
+            },
+
+            // Closing the panel leaves the jobs as they are
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('J') => vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+
+            _ => Vec::new(),
+        }
+    }
+}
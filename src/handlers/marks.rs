@@ -0,0 +1,73 @@
+use crate::{actions::Action, messages::AppMessage, model::{AppModel, PasteMode}, update::{Effect, Msg}};
+
+/// Built-in keys of the marked files popup
+pub const BINDINGS: &[(&str, Action)] = &[
+    ("j", Action::NavDown), ("down", Action::NavDown),
+    ("k", Action::NavUp), ("up", Action::NavUp),
+    ("d", Action::MarksUnmark), ("x", Action::MarksUnmark), ("space", Action::MarksUnmark), ("delete", Action::MarksUnmark),
+    ("D", Action::MarksClear),
+    ("enter", Action::MarksReveal), ("l", Action::MarksReveal),
+    ("c", Action::MarksCopyHere), ("m", Action::MarksMoveHere),
+    ("esc", Action::ViewClose), ("q", Action::ViewClose), ("M", Action::ViewClose),
+];
+
+/// Handler for reviewing marked files before a bulk operation
+/// Marks are kept across directories, so files picked in several places can be copied or moved here at once
+#[derive(Debug)]
+pub struct MarksHandler {
+    pub selected: usize,
+}
+
+impl MarksHandler {
+    pub fn new() -> Self {
+        Self { selected: 0 }
+    }
+
+    /// Do `action`; actions of other modes are ignored
+    pub fn perform(&mut self, action: Action, model: &AppModel) -> Vec<Msg> {
+        let marked = model.marked_files_sorted();
+        let count = marked.len();
+        let current = marked.get(self.selected).map(|path| path.to_path_buf());
+
+        match action {
+            Action::NavDown => {
+                if self.selected < count.saturating_sub(1) {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            Action::NavUp => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            },
+
+            // Unmark the entry under the cursor
+            Action::MarksUnmark => {
+                let Some(path) = current else { return Vec::new() };
+                if count == 1 {
+                    return vec![Msg::Unmark(path), Msg::App(AppMessage::SwitchToExploreHandler)];
+                }
+                self.selected = self.selected.min(count - 2);
+                vec![Msg::Unmark(path)]
+            },
+            // Unmark everything
+            Action::MarksClear => vec![Msg::ClearMarks, Msg::App(AppMessage::SwitchToExploreHandler)],
+
+            // Jump to the marked file in its directory
+            Action::MarksReveal => match current {
+                Some(path) => vec![Msg::Do(Effect::Reveal(path)), Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+                None => Vec::new(),
+            },
+
+            // Copy or move every marked entry into the current directory, as yanking and pasting would
+            Action::MarksCopyHere | Action::MarksMoveHere => {
+                let mode = if action == Action::MarksMoveHere { PasteMode::Move } else { PasteMode::Copy };
+                vec![Msg::Yank(mode), Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery), Msg::Do(Effect::PasteEntries)]
+            },
+
+            Action::ViewClose => vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+
+            _ => Vec::new(),
+        }
+    }
+}
@@ -1,9 +1,36 @@
 mod explore;
 mod search;
 mod fuzzy_find;
+mod command;
+mod marks;
+mod old_files;
+mod recents;
+mod downloads;
+mod bookmarks;
+mod bookmark_set;
+mod send_to;
+mod preview_links;
+mod grid;
+mod properties;
+mod about;
+mod diagnostics;
+mod batch_report;
+mod confirm_delete;
+mod confirm_protected;
+mod paste_conflict;
+mod jobs;
+mod rename;
+mod archive;
+mod chmod;
+mod chown;
+mod cleanup;
+mod hard_links;
+mod disk_image;
+mod input_line;
 
-use crate::{handlers::{explore::ExploreHandler, search::SearchHandler, fuzzy_find::FuzzyFindHandler}, messages::AppMessage, model::AppModel};
-use crossterm::event::{KeyEvent};
+use crate::{actions::Action, keymap::KeymapMode, handlers::{explore::ExploreHandler, search::SearchHandler, fuzzy_find::FuzzyFindHandler, command::CommandHandler, marks::MarksHandler, recents::RecentsHandler, downloads::DownloadsHandler, bookmarks::BookmarksHandler, bookmark_set::BookmarkSetHandler, send_to::SendToHandler, preview_links::PreviewLinksHandler, grid::GridHandler, properties::PropertiesHandler, about::AboutHandler, diagnostics::DiagnosticsHandler, batch_report::BatchReportHandler, confirm_delete::ConfirmDeleteHandler, confirm_protected::ConfirmProtectedHandler, paste_conflict::PasteConflictHandler, jobs::JobsHandler, rename::RenameHandler, archive::ArchiveHandler, chmod::ChmodHandler, chown::ChownHandler, cleanup::CleanupHandler, old_files::OldFilesHandler, hard_links::HardLinksHandler, disk_image::DiskImageHandler}, messages::AppMessage, model::{AppModel, DeleteMode, FileEntry, PreviewLink}, services::{file_service::Leftover, ImageListing}, update::{update, Effect, Msg}};
+use std::path::PathBuf;
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::Frame;
 
 #[derive(Debug)]
@@ -11,6 +38,31 @@ pub enum Handler {
     Explore(ExploreHandler),
     Search(SearchHandler),
     FuzzyFind(FuzzyFindHandler),
+    Command(CommandHandler),
+    Marks(MarksHandler),
+    Recents(RecentsHandler),
+    Downloads(DownloadsHandler),
+    Bookmarks(BookmarksHandler),
+    BookmarkSet(BookmarkSetHandler),
+    SendTo(SendToHandler),
+    PreviewLinks(PreviewLinksHandler),
+    Grid(GridHandler),
+    Properties(PropertiesHandler),
+    About(AboutHandler),
+    Diagnostics(DiagnosticsHandler),
+    BatchReport(BatchReportHandler),
+    ConfirmDelete(ConfirmDeleteHandler),
+    ConfirmProtected(ConfirmProtectedHandler),
+    PasteConflict(PasteConflictHandler),
+    Jobs(JobsHandler),
+    Rename(RenameHandler),
+    Archive(ArchiveHandler),
+    Chmod(ChmodHandler),
+    Chown(ChownHandler),
+    HardLinks(HardLinksHandler),
+    DiskImage(DiskImageHandler),
+    Cleanup(CleanupHandler),
+    OldFiles(OldFilesHandler),
 }
 
 impl Handler {
@@ -26,12 +78,192 @@ impl Handler {
         Handler::FuzzyFind(FuzzyFindHandler::new())
     }
     
-    /// Handle keyboard input - delegates to current handler
-    pub fn handle_key(&mut self, key: KeyEvent, model: &mut AppModel) -> Option<AppMessage> {
-        match self {
-            Handler::Explore(explore_handler) => explore_handler.handle_key(key, model),
+    pub fn new_command_handler(input: &str) -> Self {
+        Handler::Command(CommandHandler::new(input))
+    }
+    
+    pub fn new_marks_handler() -> Self {
+        Handler::Marks(MarksHandler::new())
+    }
+    
+    pub fn new_recents_handler() -> Self {
+        Handler::Recents(RecentsHandler::new())
+    }
+    
+    pub fn new_downloads_handler() -> Self {
+        Handler::Downloads(DownloadsHandler::new())
+    }
+    
+    pub fn new_bookmarks_handler() -> Self {
+        Handler::Bookmarks(BookmarksHandler::new())
+    }
+
+    pub fn new_bookmark_set_handler() -> Self {
+        Handler::BookmarkSet(BookmarkSetHandler::new())
+    }
+    
+    pub fn new_send_to_handler() -> Self {
+        Handler::SendTo(SendToHandler::new())
+    }
+    
+    pub fn new_preview_links_handler(links: Vec<PreviewLink>) -> Self {
+        Handler::PreviewLinks(PreviewLinksHandler::new(links))
+    }
+    
+    pub fn new_grid_handler() -> Self {
+        Handler::Grid(GridHandler::new())
+    }
+    
+    pub fn new_properties_handler() -> Self {
+        Handler::Properties(PropertiesHandler::new())
+    }
+    
+    pub fn new_about_handler() -> Self {
+        Handler::About(AboutHandler::new())
+    }
+    
+    pub fn new_diagnostics_handler() -> Self {
+        Handler::Diagnostics(DiagnosticsHandler::new())
+    }
+    
+    pub fn new_batch_report_handler() -> Self {
+        Handler::BatchReport(BatchReportHandler::new())
+    }
+    
+    pub fn new_jobs_handler() -> Self {
+        Handler::Jobs(JobsHandler::new())
+    }
+    
+    pub fn new_rename_handler(name: &str) -> Self {
+        Handler::Rename(RenameHandler::new(name))
+    }
+    
+    pub fn new_archive_handler(stem: &str) -> Self {
+        Handler::Archive(ArchiveHandler::new(stem))
+    }
+    
+    pub fn new_chmod_handler(path: PathBuf, mode: u32) -> Self {
+        Handler::Chmod(ChmodHandler::new(path, mode))
+    }
+    
+    pub fn new_chown_handler(model: &AppModel) -> Self {
+        let (users, groups) = model.accounts();
+        let owner = model.get_selected_file().and_then(|file| model.owner_of(&file.path));
+        Handler::Chown(ChownHandler::new(users, groups, owner))
+    }
+    
+    pub fn new_hard_links_handler(links: Vec<PathBuf>) -> Self {
+        Handler::HardLinks(HardLinksHandler::new(links))
+    }
+    
+    pub fn new_disk_image_handler(listing: ImageListing) -> Self {
+        Handler::DiskImage(DiskImageHandler::new(listing))
+    }
+    
+    pub fn new_cleanup_handler(leftovers: Vec<Leftover>) -> Self {
+        Handler::Cleanup(CleanupHandler::new(leftovers))
+    }
+    
+    pub fn new_old_files_handler(files: Vec<FileEntry>, age: String) -> Self {
+        Handler::OldFiles(OldFilesHandler::new(files, age))
+    }
+    
+    pub fn new_confirm_delete_handler(paths: Vec<PathBuf>, mode: DeleteMode, protected: Option<PathBuf>) -> Self {
+        Handler::ConfirmDelete(ConfirmDeleteHandler::new(paths, mode, protected))
+    }
+    
+    /// Ask for the name of the protected directory `root` before carrying out `deferred`, then go back to `previous`
+    pub fn new_confirm_protected_handler(root: PathBuf, what: &'static str, deferred: Vec<Effect>, previous: Handler) -> Self {
+        Handler::ConfirmProtected(ConfirmProtectedHandler::new(root, what, deferred, previous))
+    }
+    
+    pub fn new_paste_conflict_handler() -> Self {
+        Handler::PasteConflict(PasteConflictHandler::new())
+    }
+    
+    /// Handle keyboard input - delegates to current handler, then applies what it made of the key
+    /// Modes with key bindings get the action bound to the key instead
+    pub fn handle_key(&mut self, key: KeyEvent, model: &mut AppModel) -> Vec<Effect> {
+        // Esc stops a running transfer, whose gauge says so, before it means anything else
+        if key.code == KeyCode::Esc && matches!(self, Handler::Explore(_)) && model.transfer().is_some() {
+            return self.perform(Action::JobCancel, model);
+        }
+        if let Some(mode) = self.keymap_mode() {
+            return match model.keymap.action(mode, &key) {
+                Some(action) => self.perform(action, model),
+                None => Vec::new(),
+            };
+        }
+        let messages = match self {
             Handler::Search(search_handler) => search_handler.handle_key(key, model),
-            Handler::FuzzyFind(fuzzy_find_handler) => fuzzy_find_handler.handle_key(key, model)
+            Handler::FuzzyFind(fuzzy_find_handler) => fuzzy_find_handler.handle_key(key, model),
+            Handler::Command(command_handler) => command_handler.handle_key(key, model),
+            Handler::Recents(recents_handler) => recents_handler.handle_key(key, model),
+            Handler::Downloads(downloads_handler) => downloads_handler.handle_key(key, model),
+            Handler::Bookmarks(bookmarks_handler) => bookmarks_handler.handle_key(key, model),
+            Handler::BookmarkSet(bookmark_set_handler) => bookmark_set_handler.handle_key(key, model),
+            Handler::SendTo(send_to_handler) => send_to_handler.handle_key(key, model),
+            Handler::PreviewLinks(links_handler) => links_handler.handle_key(key, model),
+            Handler::Properties(properties_handler) => properties_handler.handle_key(key, model),
+            Handler::About(about_handler) => about_handler.handle_key(key, model),
+            Handler::Diagnostics(diagnostics_handler) => diagnostics_handler.handle_key(key, model),
+            Handler::BatchReport(batch_report_handler) => batch_report_handler.handle_key(key, model),
+            Handler::ConfirmDelete(confirm_delete_handler) => confirm_delete_handler.handle_key(key, model),
+            // Confirmed or not, the prompt gives way to what was up before it
+            Handler::ConfirmProtected(confirm_protected_handler) => {
+                let messages = confirm_protected_handler.handle_key(key, model);
+                if let Some(previous) = confirm_protected_handler.finished() {
+                    *self = previous;
+                }
+                messages
+            },
+            Handler::PasteConflict(paste_conflict_handler) => paste_conflict_handler.handle_key(key, model),
+            Handler::Jobs(jobs_handler) => jobs_handler.handle_key(key, model),
+            Handler::Rename(rename_handler) => rename_handler.handle_key(key, model),
+            Handler::Archive(archive_handler) => archive_handler.handle_key(key, model),
+            Handler::Chmod(chmod_handler) => chmod_handler.handle_key(key, model),
+            Handler::Chown(chown_handler) => chown_handler.handle_key(key, model),
+            Handler::HardLinks(hard_links_handler) => hard_links_handler.handle_key(key, model),
+            Handler::DiskImage(disk_image_handler) => disk_image_handler.handle_key(key, model),
+            Handler::Cleanup(cleanup_handler) => cleanup_handler.handle_key(key, model),
+            Handler::OldFiles(old_files_handler) => old_files_handler.handle_key(key, model),
+            Handler::Explore(_) | Handler::Grid(_) | Handler::Marks(_) => Vec::new(),
+        };
+        Self::apply(messages, model)
+    }
+    
+    /// Do `action` in the current mode, as if a key bound to it was pressed; nothing where it doesn't apply
+    pub fn perform(&mut self, action: Action, model: &mut AppModel) -> Vec<Effect> {
+        let messages = match self {
+            Handler::Explore(_) => vec![Msg::Action(action)],
+            Handler::Grid(grid_handler) => grid_handler.perform(action, model),
+            Handler::Marks(marks_handler) => marks_handler.perform(action, model),
+            _ => Vec::new(),
+        };
+        Self::apply(messages, model)
+    }
+
+    /// Update the model with each message in turn, collecting the effects
+    fn apply(messages: Vec<Msg>, model: &mut AppModel) -> Vec<Effect> {
+        messages.into_iter().flat_map(|message| update(model, message)).collect()
+    }
+    
+    /// Built-in keys of a mode with key bindings, and the actions they are bound to
+    pub fn default_bindings(mode: KeymapMode) -> &'static [(&'static str, Action)] {
+        match mode {
+            KeymapMode::Explore => explore::BINDINGS,
+            KeymapMode::Grid => grid::BINDINGS,
+            KeymapMode::Marks => marks::BINDINGS,
+        }
+    }
+    
+    /// Mode whose `[keys.<mode>]` bindings apply, for handlers that can be rebound
+    pub fn keymap_mode(&self) -> Option<KeymapMode> {
+        match self {
+            Handler::Explore(_) => Some(KeymapMode::Explore),
+            Handler::Grid(_) => Some(KeymapMode::Grid),
+            Handler::Marks(_) => Some(KeymapMode::Marks),
+            _ => None,
         }
     }
     
@@ -42,12 +274,41 @@ impl Handler {
     }
     
     /// Switch from current handler to a new handler
-    pub fn switch_to(&mut self, message: &AppMessage, _model: &mut AppModel) -> Result<(), String> {
+    pub fn switch_to(&mut self, message: &AppMessage, model: &mut AppModel) -> Result<(), String> {
         // Replace current handler with new handler
         *self = match message {
             AppMessage::SwitchToExploreHandler | AppMessage::SwitchToExploreHandlerKeepQuery => Self::new_explore_handler(),
             AppMessage::SwitchToSearchHandler => Self::new_search_handler(),
             AppMessage::SwitchToFuzzyFindHandler => Self::new_fuzzy_find_handler(),
+            AppMessage::SwitchToCommandHandler(input) => Self::new_command_handler(input),
+            AppMessage::SwitchToMarksHandler => Self::new_marks_handler(),
+            AppMessage::SwitchToRecentsHandler => Self::new_recents_handler(),
+            AppMessage::SwitchToDownloadsHandler => Self::new_downloads_handler(),
+            AppMessage::SwitchToBookmarksHandler => Self::new_bookmarks_handler(),
+            AppMessage::SwitchToBookmarkSetHandler => Self::new_bookmark_set_handler(),
+            AppMessage::SwitchToSendToHandler => Self::new_send_to_handler(),
+            AppMessage::SwitchToGridHandler => Self::new_grid_handler(),
+            AppMessage::SwitchToPropertiesHandler => Self::new_properties_handler(),
+            AppMessage::SwitchToAboutHandler => Self::new_about_handler(),
+            AppMessage::SwitchToJobsHandler => Self::new_jobs_handler(),
+            AppMessage::SwitchToRenameHandler(name) => Self::new_rename_handler(name),
+            AppMessage::SwitchToArchiveHandler(stem) => Self::new_archive_handler(stem),
+            AppMessage::SwitchToChmodHandler(path, mode) => Self::new_chmod_handler(path.clone(), *mode),
+            AppMessage::SwitchToChownHandler => Self::new_chown_handler(model),
+            AppMessage::SwitchToHardLinksHandler(links) => Self::new_hard_links_handler(links.clone()),
+            AppMessage::SwitchToDiskImageHandler(listing) => Self::new_disk_image_handler(listing.clone()),
+            AppMessage::SwitchToCleanupHandler(leftovers) => Self::new_cleanup_handler(leftovers.clone()),
+            AppMessage::SwitchToOldFilesHandler(files, age) => Self::new_old_files_handler(files.clone(), age.clone()),
+            AppMessage::SwitchToConfirmDeleteHandler(paths, mode) => Self::new_confirm_delete_handler(paths.clone(), *mode, model.protected_root(paths)),
+            AppMessage::SwitchToPreviewLinksHandler => {
+                let links = model.preview_links();
+                if links.is_empty() {
+                    model.set_status("No links found in preview");
+                    Self::new_explore_handler()
+                } else {
+                    Self::new_preview_links_handler(links)
+                }
+            },
             _ => return Err("Invalid switch message".to_string()),
         };
         
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{messages::AppMessage, model::{AppModel, DeleteMode, FileEntry}, update::{Effect, Msg}};
+
+/// Handler for reviewing files not modified for a while, as found by `:older`
+/// Every file starts checked; the checked ones can be trashed, deleted or packed into an archive
+#[derive(Debug)]
+pub struct OldFilesHandler {
+    pub files: Vec<FileEntry>,
+    pub checked: Vec<bool>,
+    pub selected: usize,
+    pub age: String,  // As typed, e.g. `30d`
+}
+
+impl OldFilesHandler {
+    pub fn new(files: Vec<FileEntry>, age: String) -> Self {
+        let checked = vec![true; files.len()];
+        Self { files, checked, selected: 0, age }
+    }
+
+    fn checked_paths(&self) -> Vec<PathBuf> {
+        self.files
+            .iter()
+            .zip(&self.checked)
+            .filter(|(_, checked)| **checked)
+            .map(|(file, _)| file.path.clone())
+            .collect()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected + 1 < self.files.len() {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            },
+            KeyCode::Char(' ') => {
+                if let Some(checked) = self.checked.get_mut(self.selected) {
+                    *checked = !*checked;
+                }
+                if self.selected + 1 < self.files.len() {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            // Check everything, or nothing once everything is
+            KeyCode::Char('a') => {
+                let all = self.checked.iter().all(|checked| *checked);
+                self.checked.iter_mut().for_each(|checked| *checked = !all);
+                Vec::new()
+            },
+            KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Char('z') => {
+                let paths = self.checked_paths();
+                if paths.is_empty() {
+                    return vec![Msg::Status("Nothing checked - space checks a file".to_string())];
+                }
+                if key.code == KeyCode::Char('z') {
+                    // Archiving packs the marked entries
+                    return vec![Msg::MarkOnly(paths), Msg::App(AppMessage::SwitchToArchiveHandler(format!("older-than-{}", self.age)))];
+                }
+                let mode = if key.code == KeyCode::Char('D') { DeleteMode::Permanent } else { DeleteMode::Normal };
+                vec![Msg::App(AppMessage::SwitchToConfirmDeleteHandler(paths, mode))]
+            },
+            // Jump to the file in its directory
+            KeyCode::Enter | KeyCode::Char('l') => {
+                match self.files.get(self.selected) {
+                    Some(entry) => vec![Msg::Do(Effect::Reveal(entry.path.clone())), Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+                    None => Vec::new(),
+                }
+            },
+            KeyCode::Esc | KeyCode::Char('q') => vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+            _ => Vec::new(),
+        }
+    }
+}
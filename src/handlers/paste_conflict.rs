@@ -0,0 +1,32 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{messages::AppMessage, model::{AppModel, ConflictChoice}, update::{Effect, Msg}};
+
+/// Handler for the dialog asking what to do with each pasted entry whose name is taken in the destination
+#[derive(Debug)]
+pub struct PasteConflictHandler {
+}
+
+impl PasteConflictHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Lowercase keys decide about the entry asked about now, uppercase ones about it and every one left
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        let (choice, all) = match key.code {
+            KeyCode::Char('o') => (ConflictChoice::Overwrite, false),
+            KeyCode::Char('O') | KeyCode::Char('a') => (ConflictChoice::Overwrite, true),
+            KeyCode::Char('s') => (ConflictChoice::Skip, false),
+            KeyCode::Char('S') => (ConflictChoice::Skip, true),
+            KeyCode::Char('r') => (ConflictChoice::Rename, false),
+            KeyCode::Char('R') => (ConflictChoice::Rename, true),
+            KeyCode::Esc | KeyCode::Char('q') => {
+                return vec![Msg::CancelPaste, Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)];
+            },
+            _ => return Vec::new(),
+        };
+        // The dialog closes once the paste went ahead or failed
+        vec![Msg::Do(Effect::ResolvePasteConflict(choice, all))]
+    }
+}
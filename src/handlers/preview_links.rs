@@ -0,0 +1,47 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{messages::AppMessage, model::{AppModel, PreviewLink}, update::{Effect, Msg}};
+
+/// Handler for moving a sub-cursor between links detected in the preview
+#[derive(Debug)]
+pub struct PreviewLinksHandler {
+    pub links: Vec<PreviewLink>,
+    pub selected: usize,
+}
+
+impl PreviewLinksHandler {
+    pub fn new(links: Vec<PreviewLink>) -> Self {
+        Self { links, selected: 0 }
+    }
+
+    /// Currently highlighted link
+    pub fn selected_link(&self) -> Option<&PreviewLink> {
+        self.links.get(self.selected)
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        match (key.code, key.modifiers) {
+            // Cycle through links
+            (KeyCode::Tab, _) | (KeyCode::Char('j'), _) | (KeyCode::Down, _) | (KeyCode::Char('n'), _) => {
+                self.selected = (self.selected + 1) % self.links.len().max(1);
+                Vec::new()
+            },
+            (KeyCode::BackTab, _) | (KeyCode::Char('k'), _) | (KeyCode::Up, _) | (KeyCode::Char('p'), _) => {
+                self.selected = self.selected.checked_sub(1).unwrap_or(self.links.len().saturating_sub(1));
+                Vec::new()
+            },
+
+            // Open the URL or jump to the referenced path
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                match self.selected_link() {
+                    Some(link) => vec![Msg::Do(Effect::FollowLink(link.target.clone())), Msg::App(AppMessage::SwitchToExploreHandler)],
+                    None => Vec::new(),
+                }
+            },
+
+            (KeyCode::Esc, _) | (KeyCode::Char('q'), _) => vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+
+            _ => Vec::new(),
+        }
+    }
+}
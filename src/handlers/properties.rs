@@ -0,0 +1,42 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{actions::Action, core::has_attribute, messages::AppMessage, model::AppModel, update::Msg};
+
+/// Handler for the properties panel of the selected entry
+/// `I` and `A` toggle the immutable and append-only attributes on Linux
+#[derive(Debug)]
+pub struct PropertiesHandler {
+}
+
+impl PropertiesHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, model: &AppModel) -> Vec<Msg> {
+        match key.code {
+            // Keep browsing with the panel open
+            KeyCode::Char('j') | KeyCode::Down => vec![Action::NavDown.into()],
+            KeyCode::Char('k') | KeyCode::Up => vec![Action::NavUp.into()],
+
+            KeyCode::Char('I') | KeyCode::Char('A') => {
+                let letter = if key.code == KeyCode::Char('I') { 'i' } else { 'a' };
+                let status = match model.selected_properties() {
+                    Some(Ok(properties)) => match properties.attributes {
+                        Some(flags) => return vec![Msg::App(AppMessage::SetAttribute(properties.path, letter, !has_attribute(flags, letter)))],
+                        None => "No attributes here (Linux filesystems only)".to_string(),
+                    },
+                    Some(Err(e)) => e.to_string(),
+                    None => "Nothing selected".to_string(),
+                };
+                vec![Msg::Status(status)]
+            },
+
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('i') => {
+                vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)]
+            },
+
+            _ => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{messages::AppMessage, model::{AppModel, RECENTS_LIMIT}, update::{Effect, Msg}};
+
+/// Handler for the list of recently opened files
+#[derive(Debug)]
+pub struct RecentsHandler {
+    pub selected: usize,
+}
+
+impl RecentsHandler {
+    pub fn new() -> Self {
+        Self { selected: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, model: &AppModel) -> Vec<Msg> {
+        let recent = model.recent_opens(RECENTS_LIMIT);
+        let current = recent.get(self.selected).map(|open| open.path.clone());
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected < recent.len().saturating_sub(1) {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            },
+
+            // Jump to the file in its directory
+            KeyCode::Enter | KeyCode::Char('l') => match current {
+                Some(path) => vec![Msg::Do(Effect::RevealOpened(path)), Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+                None => Vec::new(),
+            },
+            // Jump to the file and open it in the editor again
+            KeyCode::Char('o') => match current {
+                Some(path) if path.is_dir() => vec![Msg::Status("Directories open with the file manager - Enter to jump there".to_string())],
+                Some(path) => vec![Msg::Do(Effect::RevealOpened(path)), Msg::App(AppMessage::OpenFileInExplore)],
+                None => Vec::new(),
+            },
+
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('H') => {
+                vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)]
+            },
+
+            _ => Vec::new(),
+        }
+    }
+}
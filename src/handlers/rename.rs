@@ -0,0 +1,50 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{core::check_name, handlers::input_line::InputLine, messages::AppMessage, model::AppModel, update::Msg};
+
+/// Handler for the inline prompt renaming the selected entry
+/// The input starts out as the current name with the cursor before the extension, so the stem can be retyped
+#[derive(Debug)]
+pub struct RenameHandler {
+    pub original: String,
+    pub input: InputLine,
+}
+
+impl RenameHandler {
+    pub fn new(name: &str) -> Self {
+        Self {
+            original: name.to_string(),
+            input: InputLine::for_name(name, None),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
+        match (key.code, key.modifiers) {
+            // A name the platform would reject is swapped for its sanitized form first, so the user can review it
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                if self.input.text == self.original || self.input.text.is_empty() {
+                    return vec![Msg::Status("Not renamed".to_string()), Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)];
+                }
+                match check_name(&self.input.text).filter(|problem| problem.fatal) {
+                    Some(problem) => {
+                        self.input.replace(problem.suggestion);
+                        Vec::new()
+                    }
+                    None => vec![Msg::App(AppMessage::RenameSelected(self.input.text.clone()))],
+                }
+            },
+            (KeyCode::Tab, KeyModifiers::NONE) => {
+                if let Some(problem) = check_name(&self.input.text) {
+                    self.input.replace(problem.suggestion);
+                }
+                Vec::new()
+            },
+            (KeyCode::Esc, KeyModifiers::NONE) => vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+
+            _ => {
+                self.input.handle_key(key);
+                Vec::new()
+            },
+        }
+    }
+}
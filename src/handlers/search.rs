@@ -1,97 +1,83 @@
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::{messages::AppMessage, model::AppModel};
+use crate::{actions::Action, messages::AppMessage, model::AppModel, update::Msg};
 
 #[derive(Debug)]
 pub struct SearchHandler {
 }
 
 impl SearchHandler {
-    pub fn handle_key(&mut self, key: KeyEvent, model: &mut AppModel) -> Option<AppMessage> {
+    pub fn handle_key(&mut self, key: KeyEvent, _model: &AppModel) -> Vec<Msg> {
         match (key.code, key.modifiers) {
             // Exit actions - send messages to App
             (KeyCode::Enter, KeyModifiers::NONE) => {
-                Some(AppMessage::SwitchToExploreHandlerKeepQuery)  // Keep search results
+                vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)]  // Keep search results
             },
             (KeyCode::Esc, KeyModifiers::NONE) => {
-                model.clear_query();
-                Some(AppMessage::SwitchToExploreHandler)
+                vec![Msg::QueryClear, Msg::App(AppMessage::SwitchToExploreHandler)]
             },
             (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
-                model.clear_query();
-                Some(AppMessage::SwitchToExploreHandler)
+                vec![Msg::QueryClear, Msg::App(AppMessage::SwitchToExploreHandler)]
             },
             
             // Navigation keys within search results
             (KeyCode::Down, KeyModifiers::NONE) => {
-                model.move_selection_down();
-                None
+                vec![Action::NavDown.into()]
             },
             (KeyCode::Up, KeyModifiers::NONE) => {
-                model.move_selection_up();
-                None
+                vec![Action::NavUp.into()]
             },
             
             // Unix-style navigation with Ctrl+N/Ctrl+P
             (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
-                model.move_selection_down();
-                None
+                vec![Action::NavDown.into()]
             },
             (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
-                model.move_selection_up();
-                None
+                vec![Action::NavUp.into()]
             },
             
             // Character manipulation - handle locally
             (KeyCode::Backspace, KeyModifiers::NONE) => {
-                model.pop_from_query();
-                None
+                vec![Msg::QueryBackspace]
             },
             (KeyCode::Char('h'), KeyModifiers::CONTROL) => {
-                model.pop_from_query();
-                None
+                vec![Msg::QueryBackspace]
             },
-            (KeyCode::Char(c), KeyModifiers::NONE) => {
-                model.append_to_query(c);
-                None
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                vec![Msg::QueryInsert(c)]
             },
             
             // Unix terminal shortcuts - handle locally
             (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
-                model.clear_query();
-                None
+                vec![Msg::QueryClear]
             },
             (KeyCode::Char('k'), KeyModifiers::CONTROL) => {
-                model.delete_to_end();
-                None
+                vec![Msg::QueryDeleteToEnd]
             },
             (KeyCode::Char('w'), KeyModifiers::CONTROL) => {
-                model.delete_word_backward();
-                None
+                vec![Msg::QueryDeleteWord]
             },
             (KeyCode::Char('a'), KeyModifiers::CONTROL) => {
                 // For search mode, home/end don't make sense since we're not editing cursor position
-                // For now, just return none - could extend later for "go to first search result"
-                None
+                // For now, just do nothing - could extend later for "go to first search result"
+                Vec::new()
             },
             (KeyCode::Char('e'), KeyModifiers::CONTROL) => {
                 // Similar to Ctrl+A - could be "go to last search result"  
-                // For now, just return none - could extend later
-                None
+                // For now, just do nothing - could extend later
+                Vec::new()
             },
             
             // Additional shortcuts - handle locally
             (KeyCode::Char('l'), KeyModifiers::CONTROL) => {
-                model.clear_query();
-                None
+                vec![Msg::QueryClear]
             },
             (KeyCode::Delete, KeyModifiers::NONE) => {
-                model.pop_from_query();
-                None
+                vec![Msg::QueryBackspace]
             },
             
-            _ => None,
+            _ => Vec::new(),
         }
     }
 }
@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{core::PathCompletion, messages::AppMessage, model::{AppModel, PasteMode}, update::{Effect, Msg}};
+
+/// Handler for the send-to picker: first a destination is picked from bookmarks, recent directories
+/// or a typed path, then whether the selection is copied, moved or linked there
+#[derive(Debug)]
+pub struct SendToHandler {
+    pub query: String,
+    pub selected: usize,
+    pub destination: Option<PathBuf>,  // Picked, waiting for copy, move or link
+    pub completion: PathCompletion,    // For a typed path
+}
+
+impl SendToHandler {
+    pub fn new() -> Self {
+        Self { query: String::new(), selected: 0, destination: None, completion: PathCompletion::default() }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, model: &AppModel) -> Vec<Msg> {
+        let Some(destination) = &self.destination else { return self.pick(key, model) };
+        let effect = match key.code {
+            KeyCode::Char('l') => Effect::LinkEntriesTo(destination.clone()),
+            KeyCode::Char('c') | KeyCode::Char('y') => Effect::SendEntriesTo(destination.clone(), PasteMode::Copy),
+            KeyCode::Char('m') | KeyCode::Char('d') | KeyCode::Enter => Effect::SendEntriesTo(destination.clone(), PasteMode::Move),
+            // Back to picking another destination
+            KeyCode::Esc => {
+                self.destination = None;
+                return Vec::new();
+            },
+            _ => return Vec::new(),
+        };
+        // Back to exploring whether or not it worked
+        vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery), Msg::Do(effect)]
+    }
+
+    /// Typing filters the destinations, Tab completes a typed path; arrows move between them
+    fn pick(&mut self, key: KeyEvent, model: &AppModel) -> Vec<Msg> {
+        let targets = model.send_targets(&self.query);
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => vec![Msg::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+            (KeyCode::Enter, _) => {
+                match targets.get(self.selected) {
+                    Some(target) => {
+                        self.destination = Some(target.path.clone());
+                        Vec::new()
+                    },
+                    None => vec![Msg::Status("No such directory".to_string())],
+                }
+            },
+            (KeyCode::Down, _) | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
+                if self.selected < targets.len().saturating_sub(1) {
+                    self.selected += 1;
+                }
+                Vec::new()
+            },
+            (KeyCode::Up, _) | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                self.selected = self.selected.saturating_sub(1);
+                Vec::new()
+            },
+            (KeyCode::Tab, _) | (KeyCode::BackTab, _) => {
+                self.selected = 0;
+                match self.completion.complete(&model.current_dir, &self.query, key.code == KeyCode::BackTab) {
+                    Some(completed) => {
+                        self.query = completed;
+                        Vec::new()
+                    },
+                    None => vec![Msg::Status("No directory to complete".to_string())],
+                }
+            },
+            (KeyCode::Backspace, _) => {
+                self.completion.reset();
+                self.query.pop();
+                self.selected = 0;
+                Vec::new()
+            },
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                self.completion.reset();
+                self.query.push(c);
+                self.selected = 0;
+                Vec::new()
+            },
+            _ => Vec::new(),
+        }
+    }
+}
@@ -4,3 +4,8 @@ pub fn is_ctrl_c(key: &KeyEvent) -> bool {
     key.code == KeyCode::Char('c')
         && key.modifiers == KeyModifiers::CONTROL
 }
+
+pub fn is_ctrl_z(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Char('z')
+        && key.modifiers == KeyModifiers::CONTROL
+}
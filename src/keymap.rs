@@ -0,0 +1,216 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::actions::Action;
+use crate::config::Keys;
+use crate::handlers::Handler;
+use crate::services::Diagnostic;
+
+/// Keys the App handles before any mode sees them (quit and suspend)
+const GLOBAL_KEYS: &[&str] = &["ctrl-c", "ctrl-z"];
+
+/// Modes whose keys can be rebound under `[keys.<mode>]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeymapMode {
+    Explore,
+    Grid,
+    Marks,
+}
+
+impl KeymapMode {
+    pub const ALL: [KeymapMode; 3] = [KeymapMode::Explore, KeymapMode::Grid, KeymapMode::Marks];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            KeymapMode::Explore => "explore",
+            KeymapMode::Grid => "grid",
+            KeymapMode::Marks => "marks",
+        }
+    }
+
+    fn bindings(self, keys: &Keys) -> &BTreeMap<String, String> {
+        match self {
+            KeymapMode::Explore => &keys.explore,
+            KeymapMode::Grid => &keys.grid,
+            KeymapMode::Marks => &keys.marks,
+        }
+    }
+}
+
+/// A key with its modifiers, as written in the config ("ctrl-n", "alt-enter", "J", "f5")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Chord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Chord {
+    /// Parse a chord; modifiers are `ctrl-`/`c-`, `alt-`/`a-`/`m-` and `shift-`/`s-`, names are case-insensitive
+    /// while single characters are taken as typed, so "J" and "shift-j" are the same chord
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        // A trailing "-" is the key itself, as in "ctrl--"
+        while let Some((prefix, key)) = rest.split_once('-').filter(|(_, key)| !key.is_empty()) {
+            modifiers |= match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" | "c" => KeyModifiers::CONTROL,
+                "alt" | "a" | "m" | "meta" => KeyModifiers::ALT,
+                "shift" | "s" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+            rest = key;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::Char(c.to_ascii_uppercase()),
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" | "bs" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                name => KeyCode::F(name.strip_prefix('f')?.parse().ok().filter(|n| (1..=24).contains(n))?),
+            },
+        };
+        Some(Self::new(code, modifiers))
+    }
+
+    /// The chord of a key press
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+
+    /// Shift is part of the character (or of BackTab), and terminals disagree on whether they report it too
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers: modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT) }
+    }
+
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (modifier, prefix) in [(KeyModifiers::CONTROL, "ctrl-"), (KeyModifiers::ALT, "alt-"), (KeyModifiers::SHIFT, "shift-")] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(prefix)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            code => f.write_str(&format!("{:?}", code).to_ascii_lowercase()),
+        }
+    }
+}
+
+/// The actions keys are bound to in each mode: the built-in keys plus the user's `[keys.<mode>]`
+#[derive(Debug, Default)]
+pub struct Keymap {
+    bindings: HashMap<(KeymapMode, Chord), Action>,
+}
+
+impl Keymap {
+    /// Build the keymap, refusing user bindings that would be ambiguous or silently shadow something:
+    /// keys the mode already uses, the global keys, and the same chord bound twice under different spellings
+    /// A binding names an action ("nav.down") or a built-in key standing for one ("j")
+    pub fn new(keys: &Keys) -> (Self, Vec<Diagnostic>) {
+        let global: Vec<Chord> = GLOBAL_KEYS.iter().filter_map(|key| Chord::parse(key)).collect();
+        let mut keymap = Self::default();
+        let mut problems = Vec::new();
+
+        for mode in KeymapMode::ALL {
+            let section = format!("[keys.{}]", mode.name());
+            let builtin: Vec<(Chord, Action)> = Handler::default_bindings(mode)
+                .iter()
+                .filter_map(|(key, action)| Some((Chord::parse(key)?, *action)))
+                .collect();
+            let builtin_action = |chord: &Chord| builtin.iter().find(|(key, _)| key == chord).map(|(_, action)| *action);
+            let resolve = |target: &str| match Action::from_id(target) {
+                Some(action) => builtin.iter().any(|(_, builtin)| *builtin == action).then_some(action),
+                None => builtin_action(&Chord::parse(target)?),
+            };
+            // Spellings per chord, in file order, to find duplicates once all are parsed
+            let mut spellings: Vec<(Chord, Vec<(&str, Action)>)> = Vec::new();
+
+            for (key, target) in mode.bindings(keys) {
+                let Some(chord) = Chord::parse(key) else {
+                    problems.push(Diagnostic::new(
+                        format!("\"{}\" in {} is not a key", key, section),
+                        "The binding is ignored",
+                    ));
+                    continue;
+                };
+                let Some(action) = resolve(target) else {
+                    problems.push(Diagnostic::new(
+                        format!("\"{}\" = \"{}\" in {}: \"{}\" is neither an action nor a key of {} mode", key, target, section, target, mode.name()),
+                        "The binding is ignored",
+                    ));
+                    continue;
+                };
+                if global.contains(&chord) {
+                    problems.push(Diagnostic::new(
+                        format!("\"{}\" in {} is reserved for quitting or suspending", key, section),
+                        "The binding is ignored",
+                    ));
+                    continue;
+                }
+                if builtin_action(&chord).is_some_and(|builtin| builtin != action) {
+                    problems.push(Diagnostic::new(
+                        format!("\"{}\" in {} already does something else in {} mode", key, section, mode.name()),
+                        format!("\"{}\" keeps its built-in action; bind an unused key to {} instead", key, action.id()),
+                    ));
+                    continue;
+                }
+                match spellings.iter_mut().find(|(existing, _)| *existing == chord) {
+                    Some((_, entries)) => entries.push((key, action)),
+                    None => spellings.push((chord, vec![(key, action)])),
+                }
+            }
+
+            for (chord, action) in &builtin {
+                keymap.bindings.insert((mode, *chord), *action);
+            }
+            for (chord, entries) in spellings {
+                let action = entries[0].1;
+                if entries.iter().any(|(_, other)| *other != action) {
+                    let keys: Vec<String> = entries.iter().map(|(key, _)| format!("\"{}\"", key)).collect();
+                    problems.push(Diagnostic::new(
+                        format!("{} in {} are the same key ({}) bound to different actions", keys.join(" and "), section, chord),
+                        format!("None of them is used until only one binding for {} is left", chord),
+                    ));
+                } else {
+                    keymap.bindings.insert((mode, chord), action);
+                }
+            }
+        }
+        (keymap, problems)
+    }
+
+    /// The action `key` is bound to in `mode`
+    /// Built-in keys also work with modifiers nothing is bound to, e.g. ctrl-j moves down like j
+    pub fn action(&self, mode: KeymapMode, key: &KeyEvent) -> Option<Action> {
+        let chord = Chord::from_event(key);
+        self.bindings
+            .get(&(mode, chord))
+            .or_else(|| self.bindings.get(&(mode, Chord::new(chord.code, KeyModifiers::NONE))))
+            .copied()
+    }
+}
@@ -0,0 +1,24 @@
+//! clazyfiler as a library, so integration tests can exercise services on real directory trees
+//! The program itself is `main.rs`
+
+// Services are built once by AppModel with their own state, never defaulted
+#![allow(clippy::new_without_default)]
+
+pub mod actions;
+pub mod app;
+pub mod batch;
+pub mod commands;
+pub mod completions;
+pub mod config;
+pub mod core;
+pub mod handlers;
+pub mod key;
+pub mod keymap;
+pub mod messages;
+pub mod model;
+pub mod services;
+pub mod signals;
+pub mod terminal;
+pub mod ui;
+pub mod update;
+pub mod version;
@@ -1,22 +1,140 @@
-mod app;
-mod core;
-mod handlers;
-mod key;
-mod messages;
-mod model;
-mod services;
-mod terminal;
-mod ui;
-
-use crate::{app::run_app, terminal::with_terminal};
+use std::path::PathBuf;
+use std::rc::Rc;
+use clazyfiler::{
+    app::run_app, completions, config::Config, core::{display_path, normalize_path, Profiler}, services::{Diagnostic, InstanceService},
+    signals, terminal::{with_terminal, ScreenMode}, version,
+};
+
+/// Smallest inline viewport that still fits the list, search bar and status bar
+const MIN_INLINE_HEIGHT: u16 = 8;
+
+const USAGE: &str = "Usage: clazyfiler [--profile-startup] [--no-alt-screen] [--single-instance] [PATH]
+       clazyfiler --version | --about
+       clazyfiler completions <bash|zsh|fish|powershell>
+
+Starts in PATH if it is a directory, or in its directory with the cursor on it.
+
+Options:
+  --profile-startup  Log timing of config load, first directory read and first draw
+                     (plus later directory scans) to the cache dir and print it on exit
+  --no-alt-screen    Run inline in the scrollback with a fixed-height viewport
+                     (height from screen.inline_height in the config)
+  --single-instance  If another instance runs, reveal PATH (or the current directory) there
+                     and exit instead of opening a second UI (instance.single in the config)
+  -V, --version      Print the version, git commit and build target
+  --about            Print build metadata and the config, cache and data paths
+  -h, --help         Show this help
+
+Commands:
+  completions SHELL  Print a completion script, e.g. `clazyfiler completions bash > ~/.local/share/bash-completion/completions/clazyfiler`";
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let result = with_terminal(run_app);
+    if std::env::args().nth(1).as_deref() == Some("completions") {
+        let shell = std::env::args().nth(2).unwrap_or_default();
+        match completions::script(&shell) {
+            Some(script) => print!("{}", script),
+            None => {
+                eprintln!("Unknown shell: '{}' (expected one of {})", shell, completions::SHELLS.join(", "));
+                std::process::exit(2);
+            }
+        }
+        return Ok(());
+    }
+    
+    let mut profile_startup = false;
+    let mut no_alt_screen = false;
+    let mut single_instance = false;
+    let mut start_path = None;
+    for arg in std::env::args_os().skip(1) {
+        let Some(arg) = arg.to_str().map(str::to_string).filter(|arg| arg.starts_with('-')) else {
+            if start_path.replace(PathBuf::from(arg)).is_some() {
+                eprintln!("Only one path can be given\n\n{}", USAGE);
+                std::process::exit(2);
+            }
+            continue;
+        };
+        match arg.as_str() {
+            "--profile-startup" => profile_startup = true,
+            "--no-alt-screen" => no_alt_screen = true,
+            "--single-instance" => single_instance = true,
+            "-V" | "--version" => {
+                println!("{}", version::version_line());
+                return Ok(());
+            }
+            "--about" => {
+                println!("{}", version::version_line());
+                for (label, value) in version::about() {
+                    println!("  {:<12}{}", label, value);
+                }
+                return Ok(());
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => {
+                eprintln!("Unknown argument: {}\n\n{}", arg, USAGE);
+                std::process::exit(2);
+            }
+        }
+    }
+    
+    let log_path = if profile_startup { Config::cache_dir().map(|dir| dir.join("profile.log")) } else { None };
+    let profiler = Rc::new(Profiler::new(profile_startup, log_path));
+    // Loaded before the terminal is set up, since it decides how the screen is used
+    let mut problems = Vec::new();
+    let mut config = profiler.time("config load", Config::load).unwrap_or_else(|err| {
+        problems.push(Diagnostic::new(err.to_string(), "Using the default configuration until the file is fixed"));
+        Config::default()
+    });
+    config.instance.single |= single_instance;
+    
+    // Absolute, since a running instance resolves it from its own working directory
+    let start_path = match start_path.map_or_else(std::env::current_dir, std::path::absolute) {
+        Ok(path) if path.exists() => normalize_path(path),
+        Ok(path) => {
+            eprintln!("No such file or directory: {}", display_path(&path));
+            std::process::exit(2);
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+    };
+    if config.instance.single {
+        if let Some(socket_path) = Config::instance_socket_path() {
+            if InstanceService::forward(&socket_path, &start_path) {
+                println!("Revealed {} in the running instance", display_path(&start_path));
+                return Ok(());
+            }
+        }
+    }
+    let reveal = if start_path.is_dir() {
+        std::env::set_current_dir(&start_path)?;
+        None
+    } else {
+        if let Some(parent) = start_path.parent() {
+            std::env::set_current_dir(parent)?;
+        }
+        Some(start_path)
+    };
+
+    let mode = if no_alt_screen || !config.screen.alternate {
+        ScreenMode::Inline(config.screen.inline_height.max(MIN_INLINE_HEIGHT))
+    } else {
+        ScreenMode::Alternate
+    };
+    signals::install();
+    let result = with_terminal(mode, |terminal| run_app(terminal, profiler.clone(), config, reveal, problems));
     
     if let Err(err) = result {
         println!("{:?}", err);
     }
     
+    if profiler.is_enabled() {
+        eprintln!("{}", profiler.report());
+    }
+    
     Ok(())
 }
 
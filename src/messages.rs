@@ -1,11 +1,43 @@
+use std::path::PathBuf;
+use crate::model::{DeleteMode, FileEntry};
+use crate::services::{file_service::Leftover, ImageListing};
+
 /// Messages sent from handlers to App for global processing
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum AppMessage {
     Quit,
+    Suspend,                          // Hand the terminal back to the shell and stop until `fg`
     OpenFile,
+    OpenFileInExplore,                // Leave the current popup, then open the selected file
+    SendToTerminal,                   // Transfer the selected file via terminal escape sequences
+    DecryptPreview,                   // Decrypt the selected file; needs the terminal for prompts
     SwitchToExploreHandler,
     SwitchToExploreHandlerKeepQuery,  // Keep search results when switching to explore mode
     SwitchToSearchHandler,
     SwitchToFuzzyFindHandler,
-    Error(String),
+    SwitchToCommandHandler(String),  // Initial command line input
+    SwitchToMarksHandler,
+    SwitchToRecentsHandler,
+    SwitchToDownloadsHandler,
+    SwitchToBookmarksHandler,
+    SwitchToBookmarkSetHandler,       // Wait for the key to bookmark the current directory on
+    SwitchToSendToHandler,
+    SwitchToArchiveHandler(String),   // Suggested archive name, without extension
+    SwitchToPreviewLinksHandler,
+    SwitchToGridHandler,
+    SwitchToPropertiesHandler,
+    SwitchToAboutHandler,
+    SwitchToJobsHandler,
+    SwitchToRenameHandler(String),    // Current name of the selected entry
+    RenameSelected(String),           // New name entered in the rename prompt
+    SwitchToChmodHandler(PathBuf, u32),  // Entry whose permission bits are edited, and its current ones
+    SetAttribute(PathBuf, char, bool),  // Set or clear the chattr attribute with this letter
+    SwitchToChownHandler,             // Pick a new owner for the marked entries (or the selected one)
+    SwitchToHardLinksHandler(Vec<PathBuf>),  // Other names of the selected file, to jump to
+    SwitchToDiskImageHandler(ImageListing),  // Directory of an ISO image to browse and extract files from
+    SwitchToOldFilesHandler(Vec<FileEntry>, String),  // Files older than the age typed, which is kept for the title
+    SwitchToCleanupHandler(Vec<Leftover>),  // Entries a maintenance scan found, to review and delete
+    SwitchToConfirmDeleteHandler(Vec<PathBuf>, DeleteMode),  // Entries to ask about, and how they would go
+    DeleteEntries(Vec<PathBuf>, DeleteMode),  // Confirmed in the prompt
+    ExecuteCommand(String),           // Command line entered in command mode (without ':')
 }
@@ -1,14 +1,77 @@
-use std::path::PathBuf;
-use crate::core::Result;
-use crate::services::{EditorService, FileService};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::path::{Path, PathBuf};
+use crate::batch::{copy_step, delete_step, move_step, remove_empty_step, shred_step, trash_step, Batch, BatchStep, Transfer};
+use crate::config::{Config, Connection, StatusSegment};
+use crate::keymap::Keymap;
+use crate::core::{metadata_owner, parse_age, AclEntry, AclTag, BudgetedCache, bulk_rename_line, bulk_rename_plan, check_name, ClazyfilerError, CreationMode, DEFAULT_DIR_MODE, IoThrottle, DEFAULT_FILE_MODE, display_path, permission_string, protected_root, find_import_references, find_link_candidates, glob_match, normalize_path, PowerFilter, Profiler, Result, SourceLanguage};
+use std::rc::Rc;
+use crate::services::{Account, AccountService, Bookmark, BookmarkService, ArchiveFormat, ArchiveService, CapabilityService, GraphicsProtocol, ClipboardService, DataFileService, Diagnostic, DiskImageKind, DiskImageService, ImageEntry, ImageListing, DiskService, DocumentService, EditorService, FileService, FontService, FuzzyIndexService, HistoryService, IgnoreService, GitService, GitStatus, HealthService, Download, DownloadsService, HookEvent, HookService, InstanceService, Operation, OperationHistory, OperationKind, TerminalCapabilities, TorrentService, TransferService, TreeWatcher, WatchEvent, VerificationService, EncryptionService, ImageService, OpenerService, VideoService, VideoState};
+use crate::services::document_service::DocumentKind;
+use crate::services::disk_service::DiskUsage;
+use crate::services::file_service::{DirectoryStats, FileProperties, Leftover};
+use crate::services::history_service::RecentOpen;
+use crate::services::encryption_service::{EncryptionKind, KeySource};
+use crate::services::image_service::{ImagePlacement, Thumbnail};
+use crate::services::verification_service::{CheckState, DigestState};
+
+/// Directory scans slower than this get a hint in the status bar
+const SLOW_SCAN_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// Listings with more directories than this are shown without collapsing single-child chains
+const MAX_CHAIN_PROBES: usize = 256;
+
+/// How many distinct recently opened files the recents list shows
+pub const RECENTS_LIMIT: usize = 100;
+
+/// Most fuzzy-find points frequently and recently opened files can gain
+const MAX_FRECENCY_BOOST: i32 = 60;
+
+/// On network filesystems the preview is only loaded once the selection has rested this long
+const NETWORK_PREVIEW_DELAY: Duration = Duration::from_millis(400);
+
+/// How long to wait for results of a running batch between redraws and key presses
+const BATCH_SLICE: Duration = Duration::from_millis(50);
+
+/// Time between frames of the spinners shown while something works in the background
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long entries that changed on disk keep their badge; it dims for the second half
+const CHANGE_BADGE_DURATION: Duration = Duration::from_secs(4);
+
+/// How many directories visited this session the send-to picker remembers
+const RECENT_DIRS_LIMIT: usize = 30;
 
 /// File entry information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct FileEntry {
     pub name: String,
     pub path: PathBuf,
     pub is_directory: bool,
     pub size: Option<u64>,
+    pub modified: Option<SystemTime>,  // None where listings skip the stat (network mounts, saved indexes)
+    pub mode: Option<u32>,             // Permission bits, unknown under the same conditions and off Unix
+    pub link_target: Option<PathBuf>,  // Where a symlink points, as stored in the link; None for anything else
+    pub metadata_error: Option<String>,  // Why the stat failed; the entry is listed anyway, without size and time
+}
+
+/// Where a link found in the preview points to
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkTarget {
+    Url(String),
+    Path(PathBuf),
+}
+
+/// A navigable link in the preview of the selected file
+/// `start`/`end` are byte offsets within preview line `line`
+#[derive(Debug, Clone)]
+pub struct PreviewLink {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    pub target: LinkTarget,
 }
 
 /// Application mode determines how files are sourced and displayed
@@ -19,6 +82,219 @@ pub enum AppMode {
     FuzzyFind,  // Fuzzy search across directory tree
 }
 
+/// Column the listing is sorted by; directories always stay above files
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortKey {
+    Name,
+    Size,
+    Modified,
+    Extension,
+}
+
+impl SortKey {
+    /// In column order, so `1`-`4` select them
+    pub const ALL: [SortKey; 4] = [SortKey::Name, SortKey::Size, SortKey::Modified, SortKey::Extension];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortKey::Name => "Name",
+            SortKey::Size => "Size",
+            SortKey::Modified => "Modified",
+            SortKey::Extension => "Type",
+        }
+    }
+}
+
+/// Order of directory listings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ListSort {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl ListSort {
+    /// A fresh sort starts with names A-Z but the biggest and newest entries first
+    pub fn new(key: SortKey) -> Self {
+        Self { key, descending: matches!(key, SortKey::Size | SortKey::Modified) }
+    }
+
+    /// `▲` or `▼` for column headers and the status bar
+    pub fn arrow(&self) -> &'static str {
+        if self.descending { "▼" } else { "▲" }
+    }
+}
+
+impl Default for ListSort {
+    fn default() -> Self {
+        Self::new(SortKey::Name)
+    }
+}
+
+/// Optional grouping of listings under separator headers
+#[derive(Debug, Clone, Copy, PartialEq, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    #[default]
+    None,
+    Kind,       // Directories / Files
+    Extension,
+    Git,        // Working-tree status
+}
+
+impl GroupBy {
+    fn next(self) -> Self {
+        match self {
+            GroupBy::None => GroupBy::Kind,
+            GroupBy::Kind => GroupBy::Extension,
+            GroupBy::Extension => GroupBy::Git,
+            GroupBy::Git => GroupBy::None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GroupBy::None => "none",
+            GroupBy::Kind => "kind",
+            GroupBy::Extension => "extension",
+            GroupBy::Git => "git status",
+        }
+    }
+}
+
+/// How long a directory read or tree scan took and how many entries it could not read, for the list title
+#[derive(Debug, Clone, Copy)]
+pub struct ReadMetrics {
+    pub elapsed: Duration,
+    pub unreadable: usize,  // Entries with broken metadata, and directories a scan could not enter
+}
+
+/// Whether pasting yanked entries copies or moves them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PasteMode {
+    Copy,  // Yanked with y
+    Move,  // Cut with d
+}
+
+impl PasteMode {
+    pub fn hook(self) -> HookEvent {
+        match self {
+            PasteMode::Copy => HookEvent::Copy,
+            PasteMode::Move => HookEvent::Move,
+        }
+    }
+
+    pub fn operation(self) -> OperationKind {
+        match self {
+            PasteMode::Copy => OperationKind::Copy,
+            PasteMode::Move => OperationKind::Move,
+        }
+    }
+}
+
+/// How confirmed entries are deleted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeleteMode {
+    Normal,     // Into the trash unless that is turned off
+    Permanent,  // For good, bypassing the trash
+    Shred,      // Files overwritten before they are removed
+    Prune,      // Empty directories removed one by one, children first; anything with contents is left alone
+}
+
+/// What to do with a pasted entry whose name is taken in the destination
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictChoice {
+    Overwrite,  // Replace the existing entry, which goes to the trash when that is on
+    Skip,
+    Rename,     // Keep both, the pasted one under a numbered name
+}
+
+/// A paste held until every name it would take in the destination is decided about
+#[derive(Debug, Clone)]
+pub struct PendingPaste {
+    pub clipboard: FileClipboard,
+    pub destination: PathBuf,
+    pub conflicts: Vec<PathBuf>,        // Sources whose name is taken, in the order they are asked about
+    pub choices: Vec<ConflictChoice>,   // For the conflicts decided so far
+}
+
+impl PendingPaste {
+    /// The source asked about now
+    pub fn current(&self) -> Option<&PathBuf> {
+        self.conflicts.get(self.choices.len())
+    }
+}
+
+/// Entries yanked or cut for pasting into another directory; unrelated to the system clipboard
+#[derive(Debug, Clone)]
+pub struct FileClipboard {
+    pub paths: Vec<PathBuf>,
+    pub mode: PasteMode,
+}
+
+/// A directory the send-to picker offers, and why
+#[derive(Debug, Clone, PartialEq)]
+pub struct SendTarget {
+    pub path: PathBuf,
+    pub origin: String,  // `typed`, `bookmark d` or `recent`
+}
+
+/// How an entry of the listing changed on disk while it was shown
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    New,
+    Modified,
+    Deleted,  // Still shown for a moment, below the listing
+}
+
+impl ChangeKind {
+    pub fn badge(self) -> &'static str {
+        match self {
+            ChangeKind::New => "+ new",
+            ChangeKind::Modified => "~ changed",
+            ChangeKind::Deleted => "- deleted",
+        }
+    }
+}
+
+/// A change the listing watcher saw, badged until CHANGE_BADGE_DURATION has passed
+#[derive(Debug, Clone)]
+pub struct EntryChange {
+    pub kind: ChangeKind,
+    pub is_directory: bool,
+    pub at: Instant,
+}
+
+impl EntryChange {
+    /// Past the first half of its time, when the badge dims before it goes
+    pub fn fading(&self) -> bool {
+        self.at.elapsed() >= CHANGE_BADGE_DURATION / 2
+    }
+}
+
+/// Where a finished copy or move put its entries, offered as a jump until taken or replaced
+#[derive(Debug, Clone)]
+pub struct JobLanding {
+    pub label: &'static str,
+    pub destination: PathBuf,
+    pub entries: Vec<PathBuf>,
+}
+
+/// Background work that can be paused from the jobs panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JobKind {
+    Batch,      // The bulk copy, move or delete
+    Checksums,  // SHA-256 verification of previewed files
+    Archive,    // Packing entries into a zip or tar.gz
+}
+
+/// A running job as listed in the jobs panel
+#[derive(Debug, Clone)]
+pub struct JobSummary {
+    pub kind: JobKind,
+    pub description: String,
+    pub paused: bool,
+}
+
 /// Source of files currently being displayed
 #[derive(Debug, Clone, PartialEq)]
 pub enum FilesSource {
@@ -44,37 +320,271 @@ pub struct AppModel {
     // Source data for filtering
     pub directory_files: Vec<FileEntry>, // Original unfiltered directory files
     
+    // Project view: gitignored entries (target/, node_modules/, ...) are left out of listings
+    pub project_view: bool,
+    pub ignored_paths: HashSet<PathBuf>,  // Entries of the current listing git ignores, while project_view is on
+    
+    // Listing presentation: order of directory listings and whether the columns view is shown
+    pub sort: ListSort,
+    pub detail_view: bool,
+    pub group_by: GroupBy,
+    pub readme_preview: bool,  // A selected directory with a README previews it rather than its children
+    git_statuses: Option<HashMap<PathBuf, GitStatus>>,  // Changed entries of the listing while grouping by git status
+    pub git_branch: Option<String>,  // Of the work tree holding current_dir, while the status bar shows it
+    clock_minute: u64,  // Minute the status bar clock was last drawn at
+    pub ticks: u64,     // Frames spinners have advanced by
+    last_tick: Instant,
+    
     // Background state for fuzzy find
     pub all_files_cache: Vec<FileEntry>,  // All files from recursive scan
     pub is_indexing: bool,                // Whether fuzzy find is still scanning
     
+    // Outcome of the last read of the listing and of the last scan behind all_files_cache (None if it was loaded)
+    pub listing_read: Option<ReadMetrics>,
+    pub tree_scan: Option<ReadMetrics>,
+    
+    // Marked files for bulk operations, keyed by absolute path
+    pub marked_paths: HashSet<PathBuf>,
+    pub visual_anchor: Option<usize>,  // Listing index where visual mode started; the range to the cursor is marked when it ends
+    
+    // Watch on current_dir (local filesystems on Linux) and what it saw change recently, for badges
+    listing_watcher: Option<TreeWatcher>,
+    pub entry_changes: HashMap<PathBuf, EntryChange>,
+    
+    // Hook run for each entry the batch finishes, and how its finished entries are recorded for undo
+    batch_hook: Option<HookEvent>,
+    batch_operation: Option<OperationKind>,
+    batch_done: Vec<(PathBuf, PathBuf)>,
+    
+    // Copies, moves, renames and trashed entries that `u` undoes and `U` redoes
+    operation_history: OperationHistory,
+    
+    // Entries the copy or move batch has put in place so far, and where the last finished one put them
+    landed: Vec<PathBuf>,
+    pub job_landing: Option<JobLanding>,
+    
+    // Entries waiting to be pasted with p
+    pub file_clipboard: Option<FileClipboard>,
+    pub pending_paste: Option<PendingPaste>,
+    
+    // Directories visited this session, most recent first, as send-to destinations
+    recent_dirs: Vec<PathBuf>,
+    
+    // Bulk operation in progress, or finished with failures the report still shows
+    pub batch: Option<Batch>,
+    
+    // One-shot feedback shown in the status bar, cleared on the next key press
+    pub status_message: Option<String>,
+    
+    // Problems found at startup or on a config reload, shown in a panel until dismissed
+    pub diagnostics: Vec<Diagnostic>,
+    
+    // Usage of the filesystem holding current_dir, refreshed on navigation
+    pub disk_usage: Option<DiskUsage>,
+    
+    // Type of network filesystem holding current_dir (e.g. "nfs4"); enables degraded mode:
+    // listings without per-entry stats, delayed previews and no automatic recursive scans
+    pub network_filesystem: Option<String>,
+    
+    // Selected path whose preview waits for NETWORK_PREVIEW_DELAY, and whether the redraw was requested
+    preview_pending: RefCell<Option<(PathBuf, Instant, bool)>>,
+    
+    // Thumbnails the frame being drawn reserved cells for, to go out through the graphics protocol after it
+    image_placements: RefCell<Vec<ImagePlacement>>,
+    
+    // User configuration
+    pub config: Config,
+    
+    // Key bindings from the config's [keys] sections
+    pub keymap: Keymap,
+    
+    // Throughput limit shared by the file service's copies and checksum verification
+    io_throttle: IoThrottle,
+    
+    // Timing log for --profile-startup and slow-scan diagnostics
+    pub profiler: Rc<Profiler>,
+    
     // Services
     file_service: FileService,
     editor_service: EditorService,
+    opener_service: OpenerService,
+    image_service: ImageService,
+    video_service: VideoService,
+    data_file_service: DataFileService,
+    document_service: DocumentService,
+    font_service: FontService,
+    torrent_service: TorrentService,
+    disk_image_service: DiskImageService,
+    verification_service: VerificationService,
+    hook_service: HookService,
+    downloads_service: DownloadsService,
+    archive_service: ArchiveService,
+    encryption_service: EncryptionService,
+    clipboard_service: ClipboardService,
+    transfer_service: TransferService,
+    disk_service: DiskService,
+    account_service: AccountService,
+    fuzzy_index_service: FuzzyIndexService,
+    history_service: HistoryService,
+    bookmark_service: BookmarkService,
+    instance_service: Option<InstanceService>,  // Listening for reveal requests in single-instance mode
+    capability_service: CapabilityService,
+    ignore_service: IgnoreService,
+    git_service: GitService,
 }
 
 impl AppModel {
-    pub fn new() -> Result<Self> {
-        let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-        let file_service = FileService::new();
-        let editor_service = EditorService::new();
+    pub fn new(profiler: Rc<Profiler>, config: Config) -> Result<Self> {
+        let current_dir = normalize_path(std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+        let services_started = Instant::now();
+        let mut model = Self::with_listing(current_dir.clone(), Vec::new(), config);
+        model.profiler = profiler.clone();
+        model.video_service = VideoService::new(Config::cache_dir());
+        model.font_service = FontService::new(Config::cache_dir());
+        model.encryption_service = EncryptionService::new(model.config.encryption.age_identity.clone());
+        model.downloads_service = DownloadsService::new(model.config.downloads.dir.clone());
+        model.fuzzy_index_service = FuzzyIndexService::new(Config::cache_dir());
+        model.history_service = HistoryService::new(Config::data_dir());
+        model.bookmark_service = BookmarkService::new(Config::config_path().and_then(|path| path.parent().map(Path::to_path_buf)));
+        model.instance_service = model.config.instance.single
+            .then(Config::instance_socket_path)
+            .flatten()
+            .and_then(|socket_path| InstanceService::listen(&socket_path));
+        profiler.record("service setup".to_string(), services_started.elapsed());
+        model.capability_service = profiler.time("terminal capability probe", CapabilityService::new);
+        let health = profiler.time("health check", || {
+            let health_service = HealthService::new();
+            [
+                health_service.check_editor(&model.editor_service),
+                health_service.check_writable(
+                    "cache", Config::cache_dir().as_deref(),
+                    "The fuzzy index and rendered previews are not kept between sessions",
+                ),
+                health_service.check_writable(
+                    "data", Config::data_dir().as_deref(),
+                    "Opened files are not remembered for the recents list and open-last",
+                ),
+            ].into_iter().flatten().collect::<Vec<_>>()
+        });
+        model.diagnostics.splice(0..0, health);
+        model.disk_usage = profiler.time("disk usage (df)", || model.disk_service.usage(&current_dir).ok());
+        model.network_filesystem = profiler.time("mount detection", || model.disk_service.network_filesystem(&current_dir));
+        model.file_service.set_network(model.network_filesystem.is_some(), model.config.disk.network_retry_policy());
         
         // Load initial directory
-        let directory_files = file_service.read_directory(&current_dir)?;
+        let started = Instant::now();
+        let mut directory_files = model.file_service.read_directory(&current_dir)?;
+        if model.config.explore.collapse_chains && model.network_filesystem.is_none() {
+            model.file_service.collapse_directory_chains(&mut directory_files, MAX_CHAIN_PROBES);
+        }
+        profiler.record(
+            format!("initial read_directory {} ({} entries)", current_dir.display(), directory_files.len()),
+            started.elapsed(),
+        );
+        model.listing_read = Some(ReadMetrics { elapsed: started.elapsed(), unreadable: model.file_service.unreadable_entries().0 });
+        model.files = directory_files.clone();
+        model.directory_files = directory_files;
+        
+        if model.project_view || model.group_by == GroupBy::Git {
+            profiler.time("git listing state", || {
+                model.update_ignored_paths();
+                model.update_git_statuses();
+            });
+        }
+        if model.project_view || model.group_by != GroupBy::None {
+            model.refresh_files_for_current_mode();
+        }
+        model.update_git_branch();
+        model.watch_listing();
+        Ok(model)
+    }
+    
+    /// A model listing `files` as the contents of `current_dir`, without reading anything from disk,
+    /// probing the terminal or starting watchers; for driving updates in tests
+    pub fn with_listing(current_dir: PathBuf, files: Vec<FileEntry>, config: Config) -> Self {
+        let io_throttle = IoThrottle::new(config.disk.background_io_limit());
+        let mut file_service = FileService::new();
+        file_service.set_throttle(io_throttle.clone());
+        let mut verification_service = VerificationService::new();
+        verification_service.set_throttle(io_throttle.clone());
+        let mut archive_service = ArchiveService::new();
+        archive_service.set_throttle(io_throttle.clone());
+        let (keymap, diagnostics) = Keymap::new(&config.keys);
         
-        Ok(Self {
+        Self {
             current_dir,
             query_text: String::new(),
-            files: directory_files.clone(),      // Initially same as directory files
+            files: files.clone(),                // Initially same as directory files
             selected_index: 0,
             mode: AppMode::Explore,
             files_source: FilesSource::CurrentDir,
-            directory_files,                     // Store original files for filtering
+            directory_files: files,              // Store original files for filtering
+            project_view: config.explore.project_view,
+            ignored_paths: HashSet::new(),
+            sort: ListSort::default(),
+            detail_view: false,
+            group_by: config.explore.group_by,
+            readme_preview: config.explore.readme_preview,
+            git_statuses: None,
+            git_branch: None,
+            clock_minute: 0,
+            ticks: 0,
+            last_tick: Instant::now(),
             all_files_cache: Vec::new(),
             is_indexing: false,
+            listing_read: None,
+            tree_scan: None,
+            marked_paths: HashSet::new(),
+            visual_anchor: None,
+            listing_watcher: None,
+            entry_changes: HashMap::new(),
+            file_clipboard: None,
+            pending_paste: None,
+            recent_dirs: Vec::new(),
+            batch_hook: None,
+            batch_operation: None,
+            batch_done: Vec::new(),
+            operation_history: OperationHistory::new(),
+            landed: Vec::new(),
+            job_landing: None,
+            batch: None,
+            status_message: None,
+            diagnostics,
+            disk_usage: None,
+            network_filesystem: None,
+            preview_pending: RefCell::new(None),
+            image_placements: RefCell::new(Vec::new()),
+            encryption_service: EncryptionService::with_identity(config.encryption.age_identity.clone()),
+            downloads_service: DownloadsService::in_dir(config.downloads.dir.clone()),
+            config,
+            keymap,
+            io_throttle,
+            profiler: Rc::new(Profiler::new(false, None)),
             file_service,
-            editor_service,
-        })
+            editor_service: EditorService::new(),
+            opener_service: OpenerService::new(),
+            image_service: ImageService::new(),
+            video_service: VideoService::new(None),
+            data_file_service: DataFileService::new(),
+            document_service: DocumentService::new(),
+            font_service: FontService::new(None),
+            torrent_service: TorrentService::new(),
+            disk_image_service: DiskImageService::new(),
+            verification_service,
+            hook_service: HookService::new(),
+            archive_service,
+            clipboard_service: ClipboardService::new(),
+            transfer_service: TransferService::new(),
+            disk_service: DiskService::new(),
+            account_service: AccountService::new(),
+            fuzzy_index_service: FuzzyIndexService::new(None),
+            history_service: HistoryService::new(None),
+            bookmark_service: BookmarkService::new(None),
+            instance_service: None,
+            capability_service: CapabilityService::unprobed(),
+            ignore_service: IgnoreService::new(),
+            git_service: GitService::new(),
+        }
     }
     
     /// Get currently selected file
@@ -82,12 +592,6 @@ impl AppModel {
         self.files.get(self.selected_index)
     }
     
-    /// Update query text and refresh files based on current mode
-    pub fn update_query(&mut self, new_query: String) {
-        self.query_text = new_query;
-        self.refresh_files_for_current_mode();
-    }
-    
     /// Append character to query
     pub fn append_to_query(&mut self, c: char) {
         self.query_text.push(c);
@@ -136,175 +640,1250 @@ impl AppModel {
         }
     }
     
-    /// Change directory and update files
-    pub fn change_directory(&mut self, new_dir: PathBuf) -> Result<()> {
-        let directory_files = self.file_service.read_directory(&new_dir)?;
-        
-        self.current_dir = new_dir;
-        self.directory_files = directory_files.clone();
-        self.files = directory_files;  // Initially show all files
-        self.selected_index = 0;
-        self.query_text.clear(); // Clear query when changing directories
-        self.mode = AppMode::Explore;
-        self.files_source = FilesSource::CurrentDir;
-        self.all_files_cache.clear(); // Clear fuzzy find cache
-        self.is_indexing = false;
-        
-        Ok(())
+    /// Check whether a file is marked
+    pub fn is_marked(&self, file: &FileEntry) -> bool {
+        self.marked_paths.contains(&file.path)
     }
     
-    /// Navigate to parent directory
-    pub fn go_to_parent(&mut self) -> Result<()> {
-        if let Some(parent) = self.current_dir.parent() {
-            self.change_directory(parent.to_path_buf())
-        } else {
-            Ok(())
+    /// Mark or unmark the selected entry, then move on so consecutive entries take one key each
+    pub fn toggle_mark(&mut self) {
+        let Some(path) = self.get_selected_file().map(|file| file.path.clone()) else { return };
+        if !self.marked_paths.remove(&path) {
+            self.marked_paths.insert(path);
         }
+        self.move_selection_down();
     }
     
-    /// Enter selected directory
-    pub fn enter_selected_directory(&mut self) -> Result<()> {
-        if let Some(selected_file) = self.get_selected_file() {
-            if selected_file.is_directory {
-                let path = selected_file.path.clone();
-                self.change_directory(path)?;
-            }
+    /// Start visual mode at the cursor, so moving it spans a range of entries
+    pub fn start_visual(&mut self) {
+        if !self.files.is_empty() {
+            self.visual_anchor = Some(self.selected_index);
         }
-        Ok(())
-    }
-    
-    /// Switch to explore mode
-    pub fn switch_to_explore_mode(&mut self) {
-        self.mode = AppMode::Explore;
-        self.query_text.clear();
-        self.refresh_files_for_current_mode();
     }
     
-    /// Switch to explore mode but keep current query (for maintaining search results)
-    pub fn switch_to_explore_mode_keep_query(&mut self) {
-        self.mode = AppMode::Explore;
-        // Don't clear query_text - keep the current search results
-        self.refresh_files_for_current_mode();
+    /// Listing indices from the visual anchor to the cursor, in either direction
+    pub fn visual_range(&self) -> Option<std::ops::RangeInclusive<usize>> {
+        let anchor = self.visual_anchor?.min(self.files.len().checked_sub(1)?);
+        Some(anchor.min(self.selected_index)..=anchor.max(self.selected_index))
     }
     
-    /// Switch to search mode
-    pub fn switch_to_search_mode(&mut self) {
-        self.mode = AppMode::Search;
-        self.refresh_files_for_current_mode();
+    /// Leave visual mode, marking the entries of its range; returns how many that were
+    pub fn end_visual(&mut self) -> usize {
+        let Some(range) = self.visual_range() else {
+            self.visual_anchor = None;
+            return 0;
+        };
+        self.visual_anchor = None;
+        let count = range.clone().count();
+        self.marked_paths.extend(self.files[range].iter().map(|file| file.path.clone()));
+        count
     }
     
-    /// Switch to fuzzy find mode and start indexing
-    pub fn switch_to_fuzzy_find_mode(&mut self) -> Result<()> {
-        self.mode = AppMode::FuzzyFind;
-        self.start_fuzzy_indexing()?;
-        Ok(())
+    /// Mark every file in the current listing
+    pub fn mark_all(&mut self) {
+        self.marked_paths.extend(self.files.iter().map(|file| file.path.clone()));
     }
     
-    /// Start fuzzy find indexing
-    fn start_fuzzy_indexing(&mut self) -> Result<()> {
-        self.is_indexing = true;
-        self.all_files_cache.clear();
-        
-        // Perform recursive scan
-        match self.file_service.scan_directory_tree(&self.current_dir) {
-            Ok(all_files) => {
-                self.all_files_cache = all_files;
-                self.is_indexing = false;
-                self.refresh_files_for_current_mode();
-                Ok(())
-            }
-            Err(e) => {
-                self.is_indexing = false;
-                Err(e)
+    /// Invert marks within the current listing
+    pub fn invert_marks(&mut self) {
+        for file in &self.files {
+            if !self.marked_paths.remove(&file.path) {
+                self.marked_paths.insert(file.path.clone());
             }
         }
     }
     
-    /// Refresh files based on current mode and query
-    fn refresh_files_for_current_mode(&mut self) {
-        match self.mode {
-            AppMode::Explore => {
-                // Use stored directory files, optionally filtered by query
-                if self.query_text.is_empty() {
-                    self.files = self.directory_files.clone();
-                    self.files_source = FilesSource::CurrentDir;
-                } else {
-                    self.files = self.filter_files(&self.directory_files, &self.query_text);
-                    self.files_source = FilesSource::SearchResults;
-                }
-            }
-            AppMode::Search => {
-                // Filter current directory files by query
-                self.files = self.filter_files(&self.directory_files, &self.query_text);
-                self.files_source = FilesSource::SearchResults;
-            }
-            AppMode::FuzzyFind => {
-                // Fuzzy filter cached files
-                self.files = self.fuzzy_filter_files(&self.all_files_cache, &self.query_text);
-                self.files_source = FilesSource::FuzzyResults;
-            }
-        }
-        
-        // Reset selection if out of bounds
-        if self.selected_index >= self.files.len() && !self.files.is_empty() {
-            self.selected_index = self.files.len() - 1;
-        } else if self.files.is_empty() {
-            self.selected_index = 0;
-        }
+    /// Mark files in the current listing whose name matches a glob, returning how many matched
+    pub fn mark_matching(&mut self, pattern: &str) -> usize {
+        let matching: Vec<PathBuf> = self.files
+            .iter()
+            .filter(|file| glob_match(pattern, &file.name))
+            .map(|file| file.path.clone())
+            .collect();
+        let count = matching.len();
+        self.marked_paths.extend(matching);
+        count
     }
     
-    /// Simple text filtering for search mode
-    fn filter_files(&self, files: &[FileEntry], query: &str) -> Vec<FileEntry> {
-        if query.is_empty() {
-            return files.to_vec();
-        }
-        
-        files
+    /// Marked files sorted by path, for stable display
+    pub fn marked_files_sorted(&self) -> Vec<&PathBuf> {
+        let mut marked: Vec<&PathBuf> = self.marked_paths.iter().collect();
+        marked.sort();
+        marked
+    }
+    
+    /// Number of marked files that live outside the current directory
+    pub fn marks_outside_current_dir(&self) -> usize {
+        self.marked_paths
             .iter()
-            .filter(|file| file.name.to_lowercase().contains(&query.to_lowercase()))
-            .cloned()
-            .collect()
+            .filter(|path| path.parent() != Some(self.current_dir.as_path()))
+            .count()
     }
     
-    /// Fuzzy filtering with scoring for fuzzy find mode
-    fn fuzzy_filter_files(&self, files: &[FileEntry], query: &str) -> Vec<FileEntry> {
-        if query.is_empty() {
-            return files.to_vec();
+    /// Put the marked entries (or the selected one) on the file clipboard, returning how many
+    /// The marks are cleared since the clipboard holds them now
+    pub fn yank_entries(&mut self, mode: PasteMode) -> usize {
+        let paths = self.delete_targets();
+        self.marked_paths.clear();
+        let count = paths.len();
+        if count > 0 {
+            self.file_clipboard = Some(FileClipboard { paths, mode });
         }
-        
-        let mut matches: Vec<(FileEntry, i32)> = files
+        count
+    }
+
+    /// Copy or move the file clipboard's entries into the current directory, asking what to do on a name clash
+    /// A single entry is pasted right away and selected; several run as a batch. Cut entries are pasted once
+    /// Returns true when names the entries would take are in use, so the paste waits in `pending_paste`
+    /// until each of them is decided about
+    pub fn paste_entries(&mut self) -> Result<bool> {
+        let Some(clipboard) = self.file_clipboard.clone() else {
+            return Err(ClazyfilerError::content(&display_path(&self.current_dir), "Nothing yanked to paste"));
+        };
+        if self.batch.is_some() {
+            return Err(ClazyfilerError::content(&display_path(&self.current_dir), "Another bulk operation is still running"));
+        }
+        // An entry pasted where it already is (duplicating a file, say) is not in anyone's way
+        let conflicts: Vec<PathBuf> = clipboard
+            .paths
             .iter()
-            .filter_map(|file| {
-                let score = self.fuzzy_match(&file.path.to_string_lossy(), query);
-                if score > 0 {
-                    Some((file.clone(), score))
-                } else {
-                    None
-                }
+            .filter(|path| {
+                path.file_name()
+                    .map(|name| self.current_dir.join(name))
+                    .is_some_and(|target| target != **path && target.symlink_metadata().is_ok())
             })
+            .cloned()
             .collect();
-        
-        // Sort by score (higher is better)
-        matches.sort_by(|a, b| b.1.cmp(&a.1));
-        matches.into_iter().map(|(file, _)| file).collect()
+        let pending = PendingPaste { clipboard, destination: self.current_dir.clone(), conflicts, choices: Vec::new() };
+        if pending.current().is_some() {
+            self.pending_paste = Some(pending);
+            return Ok(true);
+        }
+        self.start_paste(pending)?;
+        Ok(false)
     }
-    
-    /// Fuzzy matching algorithm
-    fn fuzzy_match(&self, text: &str, pattern: &str) -> i32 {
-        let text = text.to_lowercase();
-        let pattern = pattern.to_lowercase();
-        
-        if pattern.is_empty() {
-            return 100;
+
+    /// Decide about the name the pending paste asks about now, or about all that are left;
+    /// the paste starts once nothing is left to ask; returns true while something is
+    pub fn resolve_paste_conflict(&mut self, choice: ConflictChoice, all: bool) -> Result<bool> {
+        let Some(pending) = self.pending_paste.as_mut() else { return Ok(false) };
+        let left = if all { pending.conflicts.len() - pending.choices.len() } else { 1 };
+        pending.choices.extend(std::iter::repeat_n(choice, left));
+        if pending.current().is_some() {
+            return Ok(true);
+        }
+        if let Some(pending) = self.pending_paste.take() {
+            self.start_paste(pending)?;
         }
+        Ok(false)
+    }
 
-        let mut score: i32 = 0;
-        let text_chars = text.chars().collect::<Vec<_>>();
-        let pattern_chars = pattern.chars().collect::<Vec<_>>();
-        
-        let mut text_idx = 0;
-        let mut pattern_idx = 0;
-        let mut consecutive_matches = 0;
+    /// Entries the pending paste would replace when overwriting the one asked about now, or all that are left
+    pub fn paste_overwrites(&self, all: bool) -> Vec<PathBuf> {
+        let Some(pending) = &self.pending_paste else { return Vec::new() };
+        let left = &pending.conflicts[pending.choices.len().min(pending.conflicts.len())..];
+        let asked = if all { left } else { &left[..left.len().min(1)] };
+        asked.iter().filter_map(|path| path.file_name()).map(|name| pending.destination.join(name)).collect()
+    }
+
+    /// Drop the pending paste; what was yanked or cut stays for another try
+    pub fn cancel_pending_paste(&mut self) {
+        self.pending_paste = None;
+        self.set_status("Nothing pasted");
+    }
+
+    /// Copy or move the entries of a paste with every conflict decided, leaving out the skipped ones
+    fn start_paste(&mut self, pending: PendingPaste) -> Result<()> {
+        let PendingPaste { clipboard, destination, conflicts, choices } = pending;
+        let chosen = |choice| conflicts.iter().zip(&choices).filter(move |(_, c)| **c == choice).map(|(path, _)| path.clone());
+        let skipped: HashSet<PathBuf> = chosen(ConflictChoice::Skip).collect();
+        let replaced: HashSet<PathBuf> = chosen(ConflictChoice::Overwrite).collect();
+        let paths: Vec<PathBuf> = clipboard.paths.iter().filter(|path| !skipped.contains(*path)).cloned().collect();
+        if clipboard.mode == PasteMode::Move {
+            self.file_clipboard = None;
+        }
+        if paths.is_empty() {
+            self.set_status("Nothing pasted, every entry was skipped");
+            return Ok(());
+        }
+        let trash = if self.config.general.trash && !replaced.is_empty() { Config::trash_dir() } else { None };
+
+        // Even a single entry goes to a worker, as one large file would hold up the whole app
+        let (label, step): (&str, BatchStep) = match clipboard.mode {
+            PasteMode::Copy => ("Copy", copy_step),
+            PasteMode::Move => ("Move", move_step),
+        };
+        let batch = Batch::new(label, paths, destination, step).measuring_bytes().replacing(replaced, trash);
+        self.set_status(batch.progress());
+        self.start_batch(batch, Some(clipboard.mode.hook()), Some(clipboard.mode.operation()));
+        Ok(())
+    }
+
+    /// Copy or move the selected or marked entries into `destination` as a batch, without going there; undoable
+    pub fn send_entries_to(&mut self, destination: &Path, mode: PasteMode) -> Result<usize> {
+        if self.batch.is_some() {
+            return Err(ClazyfilerError::content(&display_path(&self.current_dir), "Another bulk operation is still running"));
+        }
+        if !destination.is_dir() {
+            return Err(ClazyfilerError::content(&display_path(destination), "Not a directory"));
+        }
+        let paths = self.delete_targets();
+        if paths.is_empty() {
+            return Err(ClazyfilerError::content(&display_path(&self.current_dir), "Nothing selected"));
+        }
+        for path in &paths {
+            self.marked_paths.remove(path);
+        }
+        let count = paths.len();
+        let (label, step): (&str, BatchStep) = match mode {
+            PasteMode::Copy => ("Copy", copy_step),
+            PasteMode::Move => ("Move", move_step),
+        };
+        let batch = Batch::new(label, paths, destination.to_path_buf(), step).measuring_bytes();
+        self.set_status(batch.progress());
+        self.start_batch(batch, Some(mode.hook()), Some(mode.operation()));
+        Ok(count)
+    }
+
+    /// Create symlinks in `destination` to the selected or marked entries, under their own names
+    pub fn link_entries_to(&mut self, destination: &Path) -> Result<usize> {
+        if !destination.is_dir() {
+            return Err(ClazyfilerError::content(&display_path(destination), "Not a directory"));
+        }
+        let paths = self.delete_targets();
+        if paths.is_empty() {
+            return Err(ClazyfilerError::content(&display_path(&self.current_dir), "Nothing selected"));
+        }
+        for path in &paths {
+            let Some(name) = path.file_name() else { continue };
+            self.file_service.create_symlink(path, &destination.join(name))?;
+            self.marked_paths.remove(path);
+        }
+        if destination == self.current_dir {
+            self.refresh_current_directory();
+        }
+        Ok(paths.len())
+    }
+
+    /// Bookmarks set while browsing, after those of the config whose keys they don't take over
+    pub fn bookmarks(&self) -> Vec<Bookmark> {
+        let saved = self.bookmark_service.bookmarks();
+        let configured = self.config.bookmarks
+            .iter()
+            .filter_map(|(key, path)| {
+                let mut chars = key.chars();
+                let key = chars.next().filter(|_| chars.next().is_none())?;
+                let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_else(|| display_path(path));
+                Some(Bookmark { name, path: path.clone(), key: Some(key) })
+            })
+            .filter(|bookmark| !saved.iter().any(|other| other.key == bookmark.key));
+        configured.chain(saved.iter().cloned()).collect()
+    }
+
+    /// Directory of the bookmark on `key`
+    pub fn bookmark(&self, key: char) -> Option<PathBuf> {
+        self.bookmarks().into_iter().find(|bookmark| bookmark.key == Some(key)).map(|bookmark| bookmark.path)
+    }
+
+    /// Bookmark the current directory on `key`
+    pub fn set_bookmark(&mut self, key: char) -> Result<()> {
+        let dir = self.current_dir.clone();
+        self.bookmark_service.set(key, &dir)
+    }
+
+    /// Bookmark the current directory under `name`, without a key
+    pub fn add_bookmark(&mut self, name: &str) -> Result<()> {
+        let dir = self.current_dir.clone();
+        self.bookmark_service.add(name, &dir)
+    }
+
+    /// Forget a bookmark set while browsing; those of the config file stay
+    pub fn remove_bookmark(&mut self, bookmark: &Bookmark) -> Result<()> {
+        let Some(index) = self.bookmark_service.bookmarks().iter().position(|saved| saved == bookmark) else {
+            return Err(ClazyfilerError::validation(&bookmark.name, "Set in the config file, remove it there"));
+        };
+        self.bookmark_service.remove(index).map(|_| ())
+    }
+
+    /// Destinations for the send-to picker matching `query`, best first: the typed path when it is a directory,
+    /// then bookmarks, directories visited this session and those of recently opened files
+    pub fn send_targets(&self, query: &str) -> Vec<SendTarget> {
+        let mut targets = Vec::new();
+        let typed = Some(query.trim())
+            .filter(|query| !query.is_empty())
+            .and_then(|query| self.file_service.resolve_reference(&self.current_dir, query))
+            .filter(|path| path.is_dir());
+        if let Some(path) = typed {
+            targets.push(SendTarget { path: normalize_path(path), origin: "typed".to_string() });
+        }
+
+        let mut candidates: Vec<SendTarget> = self.bookmarks()
+            .into_iter()
+            .map(|bookmark| {
+                let origin = format!("bookmark {}", bookmark.key.map(String::from).unwrap_or(bookmark.name));
+                SendTarget { path: bookmark.path, origin }
+            })
+            .collect();
+        let opened_dirs = self.history_service.recent(RECENTS_LIMIT).into_iter().filter_map(|open| open.path.parent().map(Path::to_path_buf));
+        for path in self.recent_dirs.iter().cloned().chain(opened_dirs) {
+            if !candidates.iter().any(|target| target.path == path) {
+                candidates.push(SendTarget { path, origin: "recent".to_string() });
+            }
+        }
+
+        let mut matches: Vec<(SendTarget, i32)> = candidates
+            .into_iter()
+            .filter(|target| target.path != self.current_dir && target.path.is_dir())
+            .filter(|target| !targets.iter().any(|typed: &SendTarget| typed.path == target.path))
+            .filter_map(|target| {
+                let score = self.fuzzy_match(&target.path.to_string_lossy(), query.trim());
+                (score > 0).then_some((target, score))
+            })
+            .collect();
+        // Stable, so equally good matches keep bookmarks first and recent directories in order
+        if !query.trim().is_empty() {
+            matches.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        }
+        targets.extend(matches.into_iter().map(|(target, _)| target));
+        targets
+    }
+
+    /// Pack the selected or marked entries into an archive named `name` in the current directory, in the background
+    /// The format follows the extension: `.zip`, or `.tar.gz`/`.tgz`
+    pub fn archive_entries(&mut self, name: &str) -> Result<usize> {
+        let Some(format) = ArchiveFormat::from_name(name) else {
+            return Err(ClazyfilerError::content(name, "Name the archive .zip, .tar.gz or .tgz"));
+        };
+        if let Some(problem) = check_name(name).filter(|problem| problem.fatal) {
+            return Err(ClazyfilerError::content(name, &format!("{} (try '{}')", problem.message, problem.suggestion)));
+        }
+        let target = self.current_dir.join(name);
+        if target.symlink_metadata().is_ok() {
+            return Err(ClazyfilerError::content(name, "Already exists"));
+        }
+        let paths = self.delete_targets();
+        if paths.is_empty() {
+            return Err(ClazyfilerError::content(&display_path(&self.current_dir), "Nothing selected"));
+        }
+        let count = paths.len();
+        self.archive_service.start(paths, target, format)?;
+        self.marked_paths.clear();
+        self.set_status(format!("Packing {} {} into {}", count, if count == 1 { "entry" } else { "entries" }, name));
+        Ok(count)
+    }
+
+    /// Collect the archive finished since the last call, showing it in the listing; true while one is being packed
+    fn poll_archive(&mut self) -> bool {
+        let Some((target, result)) = self.archive_service.poll() else {
+            return self.archive_service.running().is_some();
+        };
+        match result {
+            Ok(()) => {
+                self.run_hook(HookEvent::Create, &target, None);
+                let size = target.metadata().map(|metadata| FileService::format_file_size(metadata.len())).unwrap_or_default();
+                self.set_status(format!("Packed {} ({})", display_path(&target), size));
+            }
+            Err(e) => self.set_status(format!("Archive not packed: {}", e)),
+        }
+        if target.parent() == Some(self.current_dir.as_path()) && self.mode == AppMode::Explore {
+            self.refresh_current_directory();
+        }
+        true
+    }
+
+    /// Entries a delete would remove: the marked ones, or the selected one when nothing is marked
+    pub fn delete_targets(&self) -> Vec<PathBuf> {
+        if self.marked_paths.is_empty() {
+            self.get_selected_file().map(|file| file.path.clone()).into_iter().collect()
+        } else {
+            self.marked_files_sorted().into_iter().cloned().collect()
+        }
+    }
+
+    /// Set or clear a chattr attribute of an entry, through sudo when `elevate`d
+    pub fn set_attribute(&self, path: &Path, letter: char, on: bool, elevate: bool) -> Result<()> {
+        self.file_service.set_attribute(path, letter, on, elevate)
+    }
+
+    /// Files under the current directory last modified longer than `age` (as in `:older 30d`) ago, oldest first
+    pub fn files_older_than(&self, age: &str) -> Result<Vec<FileEntry>> {
+        let age = parse_age(age).ok_or_else(|| ClazyfilerError::command(age, "Not an age like 30d"))?;
+        let cutoff = SystemTime::now().checked_sub(age).unwrap_or(std::time::UNIX_EPOCH);
+        let mut files: Vec<FileEntry> = self.file_service.scan_directory_tree(&self.current_dir)?
+            .into_iter()
+            .filter(|file| !file.is_directory && file.modified.is_some_and(|modified| modified < cutoff))
+            .collect();
+        files.sort_by_key(|file| file.modified);
+        Ok(files)
+    }
+
+    /// Directories under the current one with nothing but empty directories in them, children before their parents
+    pub fn empty_directories(&self) -> Vec<PathBuf> {
+        self.file_service.find_empty_directories(&self.current_dir)
+    }
+
+    /// Broken symlinks, empty files and empty directories under the current directory, for the cleanup review
+    pub fn find_leftovers(&self) -> Result<Vec<Leftover>> {
+        self.file_service.find_leftovers(&self.current_dir)
+    }
+
+    /// Other hard links to the selected file under the current directory
+    pub fn hard_links_of_selected(&self) -> Result<Vec<PathBuf>> {
+        let Some(file) = self.get_selected_file() else {
+            return Err(ClazyfilerError::content(&display_path(&self.current_dir), "Nothing selected"));
+        };
+        FileService::find_hard_links(&file.path, &self.current_dir)
+    }
+
+    /// Owning user and group ids of an entry, where the platform has them
+    pub fn owner_of(&self, path: &Path) -> Option<(u32, u32)> {
+        path.symlink_metadata().ok().as_ref().and_then(metadata_owner)
+    }
+
+    /// Users and groups the chown pickers offer
+    pub fn accounts(&self) -> (Vec<Account>, Vec<Account>) {
+        (self.account_service.users(), self.account_service.groups())
+    }
+
+    /// `user:alice:rw-` for an ACL entry of the properties panel, with names for the ids where known
+    pub fn describe_acl_entry(&self, entry: &AclEntry) -> String {
+        entry.describe(|tag| match *tag {
+            AclTag::User(uid) => self.account_service.user_name(uid),
+            AclTag::Group(gid) => self.account_service.group_name(gid),
+            _ => None,
+        })
+    }
+
+    /// Give the marked entries (or the selected one), with everything under directories, to `uid` and `gid`
+    /// as a batch; returns how many entries it covers
+    pub fn chown_entries(&mut self, uid: u32, gid: u32) -> Result<usize> {
+        if self.batch.is_some() {
+            return Err(ClazyfilerError::content(&display_path(&self.current_dir), "Another bulk operation is still running"));
+        }
+        let targets = self.delete_targets();
+        if targets.is_empty() {
+            return Err(ClazyfilerError::content(&display_path(&self.current_dir), "Nothing selected"));
+        }
+        let paths: Vec<PathBuf> = targets.iter().flat_map(|path| FileService::subtree(path)).collect();
+        let count = paths.len();
+        let batch = Batch::with_step("Chown", paths, self.current_dir.clone(), move |file_service, path, _| {
+            file_service.set_owner(path, uid, gid).map(|_| path.to_path_buf())
+        });
+        self.set_status(batch.progress());
+        self.start_batch(batch, None, None);
+        Ok(count)
+    }
+
+    /// The configured protected directory holding any of `paths`, whose name must be typed to delete them
+    pub fn protected_root(&self, paths: &[PathBuf]) -> Option<PathBuf> {
+        protected_root(&self.config.general.protected_paths, paths)
+    }
+
+    /// Why the entries can't be shredded: secure delete is off, one is not a regular file,
+    /// or they are on a copy-on-write filesystem where overwriting leaves the old blocks behind
+    pub fn shred_refusal(&self, paths: &[PathBuf]) -> Option<String> {
+        if !self.config.general.secure_delete {
+            return Some("Secure delete is off (set secure_delete = true under [general])".to_string());
+        }
+        if let Some(path) = paths.iter().find(|path| !path.symlink_metadata().is_ok_and(|metadata| metadata.is_file())) {
+            return Some(format!("Only files can be shredded, not {}", display_path(path)));
+        }
+        let filesystem = paths.iter().find_map(|path| self.disk_service.copy_on_write_filesystem(path))?;
+        Some(format!("Not shredding on {}: copy-on-write keeps the old contents elsewhere on disk, use regular delete", filesystem))
+    }
+
+    /// Delete entries once confirmed, directories with everything in them, into the trash unless configured
+    /// otherwise or deleting for good; several run as a batch, and shredding always does as it takes a while
+    pub fn delete_entries(&mut self, paths: Vec<PathBuf>, mode: DeleteMode) -> Result<()> {
+        if self.batch.is_some() {
+            return Err(ClazyfilerError::content(&display_path(&self.current_dir), "Another bulk operation is still running"));
+        }
+        if mode == DeleteMode::Shred {
+            if let Some(refusal) = self.shred_refusal(&paths) {
+                return Err(ClazyfilerError::content(&display_path(&self.current_dir), &refusal));
+            }
+            for path in &paths {
+                self.marked_paths.remove(path);
+            }
+            let batch = Batch::new("Shred", paths, self.current_dir.clone(), shred_step).measuring_bytes();
+            self.set_status(batch.progress());
+            self.start_batch(batch, Some(HookEvent::Delete), None);
+            return Ok(());
+        }
+        if mode == DeleteMode::Prune {
+            let batch = Batch::new("Remove empty", paths, self.current_dir.clone(), remove_empty_step);
+            self.set_status(batch.progress());
+            self.start_batch(batch, Some(HookEvent::Delete), None);
+            return Ok(());
+        }
+        let trash = if self.config.general.trash && mode == DeleteMode::Normal {
+            Some(Config::trash_dir().ok_or_else(|| ClazyfilerError::config("No trash directory (no home directory)"))?)
+        } else {
+            None
+        };
+        for path in &paths {
+            self.marked_paths.remove(path);
+        }
+        if let [path] = paths.as_slice() {
+            match &trash {
+                Some(trash) => {
+                    let trashed = self.file_service.trash_entry(path, trash)?;
+                    self.operation_history.record(OperationKind::Trash, vec![(path.clone(), trashed)]);
+                }
+                None => self.file_service.delete_entry(path)?,
+            }
+            self.run_hook(HookEvent::Delete, path, None);
+            self.refresh_current_directory();
+            let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+            match trash {
+                Some(_) => self.set_status(format!("Moved {} to the trash", name)),
+                None => self.set_status(format!("Deleted {}", name)),
+            }
+        } else {
+            let (batch, operation) = match trash {
+                Some(trash) => (Batch::new("Trash", paths, trash, trash_step), Some(OperationKind::Trash)),
+                None => (Batch::new("Delete", paths, self.current_dir.clone(), delete_step), None),
+            };
+            self.set_status(batch.progress());
+            self.start_batch(batch, Some(HookEvent::Delete), operation);
+        }
+        Ok(())
+    }
+
+    /// Start a bulk operation; it runs on a worker thread while the UI stays responsive
+    /// `hook` runs for every entry it finishes, and the finished entries are recorded as `operation` for undo
+    pub fn start_batch(&mut self, batch: Batch, hook: Option<HookEvent>, operation: Option<OperationKind>) {
+        self.batch = Some(batch);
+        self.batch_hook = hook;
+        self.batch_operation = operation;
+        self.batch_done.clear();
+        self.landed.clear();
+    }
+
+    /// Run the hook configured for `event` on the entry at `path`, which came from `old`
+    fn run_hook(&self, event: HookEvent, path: &Path, old: Option<&Path>) {
+        if let Some(command) = event.command(&self.config.hooks) {
+            self.hook_service.run(event, command, path, old);
+        }
+    }
+
+    /// Whether a batch still has entries to process and is not paused
+    pub fn batch_running(&self) -> bool {
+        self.batch.as_ref().is_some_and(|batch| batch.is_running() && !batch.is_paused())
+    }
+
+    /// Jobs still working, paused or not, for the jobs panel
+    pub fn jobs(&self) -> Vec<JobSummary> {
+        let mut jobs = Vec::new();
+        if let Some(batch) = self.batch.as_ref().filter(|batch| batch.is_running()) {
+            jobs.push(JobSummary {
+                kind: JobKind::Batch,
+                description: format!("{} into {}", batch.progress(), display_path(&batch.destination)),
+                paused: batch.is_paused(),
+            });
+        }
+        if let (Some(target), Some(control)) = (self.archive_service.running(), self.archive_service.control()) {
+            jobs.push(JobSummary {
+                kind: JobKind::Archive,
+                description: format!("Pack {}", display_path(target)),
+                paused: control.is_paused(),
+            });
+        }
+        let checksums = self.verification_service.pending_checksums();
+        if checksums > 0 {
+            jobs.push(JobSummary {
+                kind: JobKind::Checksums,
+                description: format!("Verify {} checksum{}", checksums, if checksums == 1 { "" } else { "s" }),
+                paused: self.verification_service.control().is_paused(),
+            });
+        }
+        jobs
+    }
+
+    /// Pause a job, or resume it if it is paused; its state persists across mode switches until it finishes
+    pub fn toggle_job_pause(&mut self, kind: JobKind) {
+        let paused = match kind {
+            JobKind::Batch => {
+                let Some(batch) = self.batch.as_ref() else { return };
+                batch.set_paused(!batch.is_paused());
+                batch.is_paused()
+            }
+            JobKind::Checksums => {
+                let control = self.verification_service.control();
+                control.set_paused(!control.is_paused());
+                control.is_paused()
+            }
+            JobKind::Archive => {
+                let Some(control) = self.archive_service.control() else { return };
+                control.set_paused(!control.is_paused());
+                control.is_paused()
+            }
+        };
+        self.set_status(if paused { "Job paused" } else { "Job resumed" });
+    }
+
+    /// Collect what the batch finished meanwhile; true when it has just finished with failures to report
+    pub fn step_batch(&mut self) -> bool {
+        let Some(batch) = self.batch.as_mut().filter(|batch| batch.is_running()) else { return false };
+        batch.run_for(&self.file_service, BATCH_SLICE);
+        let completed = std::mem::take(&mut batch.completed);
+        let running = batch.is_running();
+        if let Some(hook) = self.batch_hook {
+            for (source, result) in &completed {
+                match hook {
+                    // The result of a trashed entry is its place in the trash, not the path it was deleted from
+                    HookEvent::Delete => self.run_hook(hook, source, None),
+                    _ => self.run_hook(hook, result, Some(source.as_path()).filter(|source| source != result)),
+                }
+            }
+            if matches!(hook, HookEvent::Copy | HookEvent::Move) {
+                self.landed.extend(completed.iter().map(|(_, result)| result.clone()));
+            }
+        }
+        if self.batch_operation.is_some() {
+            self.batch_done.extend(completed.into_iter().filter(|(source, result)| source != result));
+        }
+        let Some(batch) = self.batch.as_ref().filter(|_| !running) else { return false };
+        // A single pasted entry that landed here is selected instead of offered
+        let pasted = match (batch.total, self.batch_done.as_slice()) {
+            (1, [(source, result)]) if result.parent() == Some(self.current_dir.as_path()) => Some((source.clone(), result.clone())),
+            _ => None,
+        };
+        // A retry of the failures later is recorded as an operation of its own
+        if let Some(operation) = self.batch_operation {
+            self.operation_history.record(operation, std::mem::take(&mut self.batch_done));
+        }
+        let (mut summary, failed) = (batch.progress(), !batch.failures.is_empty());
+        // Entries a retry puts in place later are added to the same offer
+        if !self.landed.is_empty() && pasted.is_none() {
+            self.job_landing = Some(JobLanding {
+                label: batch.label,
+                destination: batch.destination.clone(),
+                entries: self.landed.clone(),
+            });
+        }
+        if !failed {
+            self.batch = None;
+        }
+        self.refresh_current_directory();
+        if let Some((source, result)) = pasted {
+            let name = |path: &Path| path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+            let verb = if self.batch_hook == Some(HookEvent::Copy) { "Copied" } else { "Moved" };
+            self.select_path(&result);
+            summary = if name(&source) == name(&result) {
+                format!("{} {}", verb, name(&source))
+            } else {
+                format!("{} {} as {}", verb, name(&source), name(&result))
+            };
+        }
+        self.set_status(summary);
+        failed
+    }
+
+    /// The data done and to do of a running copy or move
+    pub fn transfer(&self) -> Option<Transfer> {
+        self.batch.as_ref().filter(|batch| batch.is_running()).and_then(Batch::transfer)
+            .or_else(|| self.archive_service.transfer())
+    }
+
+    /// Stop the running bulk operation, leaving what it already did in place, or else the archive being packed
+    pub fn cancel_batch(&mut self) {
+        if let Some(control) = self.archive_service.control().filter(|_| !self.batch.as_ref().is_some_and(Batch::is_running)) {
+            control.cancel();
+            self.set_status("Cancelling the archive");
+            return;
+        }
+        match self.batch.as_mut().filter(|batch| batch.is_running()) {
+            Some(batch) => {
+                batch.cancel();
+                let status = format!("Cancelling the {}", batch.label.to_lowercase());
+                self.set_status(status);
+            }
+            None => self.set_status("Nothing to cancel"),
+        }
+    }
+
+    /// Close the report, giving up on the entries that still failed
+    pub fn dismiss_batch(&mut self) {
+        if let Some(mut batch) = self.batch.take() {
+            batch.skip_all();
+            self.set_status(batch.progress());
+        }
+    }
+
+    /// Show a one-shot message in the status bar
+    pub fn set_status(&mut self, message: impl Into<String>) {
+        self.status_message = Some(message.into());
+    }
+    
+    /// Move selection by a signed offset, clamped to the listing (used by the grid view)
+    pub fn move_selection_by(&mut self, delta: isize) {
+        if self.files.is_empty() {
+            return;
+        }
+        let max = self.files.len() - 1;
+        self.selected_index = self.selected_index.saturating_add_signed(delta).min(max);
+    }
+    
+    /// Thumbnail for an image entry fitting in `width` x `height` pixels
+    pub fn thumbnail(&self, file: &FileEntry, width: u32, height: u32) -> Option<Rc<Thumbnail>> {
+        if file.is_directory || !ImageService::is_image(&file.path) {
+            return None;
+        }
+        self.image_service.thumbnail(&file.path, width, height)
+    }
+
+    /// Whether the thumbnail of an image entry is still being decoded
+    pub fn thumbnail_pending(&self, file: &FileEntry) -> bool {
+        !file.is_directory && ImageService::is_image(&file.path) && self.image_service.is_decoding(&file.path)
+    }
+    
+    /// Thumbnail shown above the preview text: the image itself, an extracted video frame or a font specimen
+    pub fn preview_thumbnail(&self, file: &FileEntry, width: u32, height: u32) -> Option<Rc<Thumbnail>> {
+        if file.is_directory || self.preview_settling(file) {
+            return None;
+        }
+        if ImageService::is_image(&file.path) {
+            return self.image_service.thumbnail(&file.path, width, height);
+        }
+        if VideoService::is_video(&file.path) && self.video_service.is_available() {
+            let VideoState::Done(frame) = self.video_service.frame(&file.path) else { return None };
+            return self.image_service.thumbnail(&frame, width, height);
+        }
+        if FontService::is_font(&file.path) {
+            let specimen = self.font_service.specimen(&file.path)?;
+            return self.image_service.thumbnail(&specimen, width, height);
+        }
+        None
+    }
+    
+    /// Queue a thumbnail to go out through the graphics protocol once the current frame is on screen
+    pub fn place_image(&self, placement: ImagePlacement) {
+        self.image_placements.borrow_mut().push(placement);
+    }
+    
+    /// Thumbnails placed while drawing the last frame
+    pub fn take_image_placements(&self) -> Vec<ImagePlacement> {
+        std::mem::take(&mut self.image_placements.borrow_mut())
+    }
+    
+    /// Change directory and update files
+    pub fn change_directory(&mut self, new_dir: PathBuf) -> Result<()> {
+        let new_dir = normalize_path(new_dir);
+        self.visual_anchor = None;
+        // Decide on degraded mode before listing, so a network directory is never read with full metadata
+        let network_filesystem = self.disk_service.network_filesystem(&new_dir);
+        self.file_service.set_network(network_filesystem.is_some(), self.config.disk.network_retry_policy());
+        let (mut directory_files, metrics) = match self.timed_scan("read_directory", &new_dir, FileService::read_directory) {
+            Ok(read) => read,
+            Err(e) => {
+                self.file_service.set_network(self.network_filesystem.is_some(), self.config.disk.network_retry_policy());
+                return Err(e);
+            }
+        };
+        self.network_filesystem = network_filesystem;
+        self.listing_read = Some(metrics);
+        self.prepare_listing(&mut directory_files);
+        
+        // Arriving at a finished job's destination takes up its offer
+        if self.job_landing.as_ref().is_some_and(|landing| landing.destination == new_dir) {
+            self.job_landing = None;
+        }
+        self.recent_dirs.retain(|dir| *dir != new_dir);
+        self.recent_dirs.insert(0, new_dir.clone());
+        self.recent_dirs.truncate(RECENT_DIRS_LIMIT);
+        self.current_dir = new_dir;
+        self.directory_files = directory_files;
+        self.update_ignored_paths();
+        self.update_git_statuses();
+        self.files = self.visible_directory_files();  // Initially show all files
+        self.selected_index = 0;
+        self.query_text.clear(); // Clear query when changing directories
+        self.mode = AppMode::Explore;
+        self.files_source = FilesSource::CurrentDir;
+        self.group_files();
+        self.drop_fuzzy_index(); // Saved for the next fuzzy find under the old directory
+        self.is_indexing = false;
+        self.update_disk_usage();
+        self.update_git_branch();
+        self.watch_listing();
+        // Marks are keyed by absolute path, so they intentionally survive directory changes
+        
+        // Suggest the thumbnail grid for photo directories
+        let images = self.files.iter().filter(|file| ImageService::is_image(&file.path)).count();
+        if images >= 2 && images * 2 > self.files.len() {
+            self.set_status("Mostly images here - press 't' for the thumbnail grid");
+        }
+        
+        Ok(())
+    }
+    
+    /// Navigate to the directory containing `path` and put the cursor on it
+    pub fn reveal_path(&mut self, path: &Path) -> Result<()> {
+        if let Some(parent) = self.file_service.get_parent_dir(path) {
+            self.change_directory(parent)?;
+        }
+        self.select_path(path);
+        Ok(())
+    }
+    
+    /// Enter `path` if it is a directory, otherwise reveal it in its directory
+    pub fn navigate_to(&mut self, path: &Path) -> Result<()> {
+        if path.is_dir() {
+            self.change_directory(path.to_path_buf())
+        } else {
+            self.reveal_path(path)
+        }
+    }
+    
+    /// Paths other launches asked this instance to reveal since the last call (single-instance mode)
+    pub fn take_forwarded_paths(&self) -> Vec<PathBuf> {
+        self.instance_service.as_ref().map(InstanceService::requests).unwrap_or_default()
+    }
+    
+    /// Move the cursor to the entry with the given path, if it is listed
+    pub fn select_path(&mut self, path: &Path) {
+        if let Some(index) = self.files.iter().position(|file| file.path == path) {
+            self.selected_index = index;
+        }
+    }
+    
+    /// Navigate to parent directory
+    pub fn go_to_parent(&mut self) -> Result<()> {
+        if let Some(parent) = self.file_service.get_parent_dir(&self.current_dir) {
+            self.change_directory(parent)
+        } else {
+            Ok(())
+        }
+    }
+    
+    /// Enter selected directory
+    pub fn enter_selected_directory(&mut self) -> Result<()> {
+        if let Some(selected_file) = self.get_selected_file() {
+            if selected_file.is_directory {
+                let path = selected_file.path.clone();
+                self.change_directory(path)?;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Switch to explore mode
+    pub fn switch_to_explore_mode(&mut self) {
+        self.mode = AppMode::Explore;
+        self.query_text.clear();
+        self.refresh_files_for_current_mode();
+    }
+    
+    /// Switch to explore mode but keep current query (for maintaining search results)
+    pub fn switch_to_explore_mode_keep_query(&mut self) {
+        self.mode = AppMode::Explore;
+        // Don't clear query_text - keep the current search results
+        self.refresh_files_for_current_mode();
+    }
+    
+    /// Switch to search mode
+    pub fn switch_to_search_mode(&mut self) {
+        self.mode = AppMode::Search;
+        self.refresh_files_for_current_mode();
+    }
+    
+    /// Switch to fuzzy find mode and start indexing
+    /// On network filesystems only the current listing is indexed unless `recursive` asks for the full tree
+    pub fn switch_to_fuzzy_find_mode(&mut self, recursive: bool) -> Result<()> {
+        self.mode = AppMode::FuzzyFind;
+        let shallow = self.network_filesystem.is_some() && !self.fuzzy_index_service.has_saved(&self.current_dir);
+        if recursive || !shallow {
+            self.start_fuzzy_indexing(recursive)?;
+        } else {
+            self.drop_fuzzy_index();
+            self.all_files_cache = self.directory_files.clone();
+            self.refresh_files_for_current_mode();
+            self.set_status("Network filesystem: fuzzy find covers this directory only (:index scans subdirectories)");
+        }
+        Ok(())
+    }
+    
+    /// Start fuzzy find indexing
+    /// A watched index is reused as is and a saved one is revalidated; only `rescan` or a missing index scans the tree
+    fn start_fuzzy_indexing(&mut self, rescan: bool) -> Result<()> {
+        let current_dir = self.current_dir.clone();
+        if !rescan && self.fuzzy_index_service.is_live(&current_dir) && !self.all_files_cache.is_empty() {
+            self.refresh_files_for_current_mode();
+            return Ok(());
+        }
+        self.drop_fuzzy_index();
+        self.is_indexing = true;
+        
+        let watch = self.network_filesystem.is_none();
+        if !rescan {
+            let started = Instant::now();
+            if let Some(all_files) = self.fuzzy_index_service.load(&current_dir, &self.file_service, watch) {
+                self.profiler.record(
+                    format!("load saved fuzzy index {} ({} entries)", current_dir.display(), all_files.len()),
+                    started.elapsed(),
+                );
+                self.all_files_cache = all_files;
+                self.is_indexing = false;
+                self.refresh_files_for_current_mode();
+                return Ok(());
+            }
+        }
+        
+        // Perform recursive scan
+        match self.timed_scan("scan_directory_tree", &current_dir, FileService::scan_directory_tree) {
+            Ok((all_files, metrics)) => {
+                self.tree_scan = Some(metrics);
+                self.fuzzy_index_service.track(&current_dir, &all_files, watch);
+                self.fuzzy_index_service.save(&all_files);
+                self.all_files_cache = all_files;
+                self.is_indexing = false;
+                self.refresh_files_for_current_mode();
+                Ok(())
+            }
+            Err(e) => {
+                self.is_indexing = false;
+                Err(e)
+            }
+        }
+    }
+    
+    /// Release the fuzzy index from memory, saving it first so it can be loaded instead of rescanned
+    fn drop_fuzzy_index(&mut self) {
+        self.fuzzy_index_service.save(&self.all_files_cache);
+        self.fuzzy_index_service.forget();
+        self.all_files_cache = Vec::new();
+        self.tree_scan = None;
+    }
+    
+    /// Save the fuzzy index on exit, so the next session starts from it
+    pub fn save_fuzzy_index(&self) {
+        self.fuzzy_index_service.save(&self.all_files_cache);
+    }
+    
+    /// Run a directory scan, logging its duration and hinting at slow filesystems and unreadable entries in the status bar
+    fn timed_scan(
+        &mut self,
+        label: &str,
+        dir: &Path,
+        scan: impl FnOnce(&FileService, &Path) -> Result<Vec<FileEntry>>,
+    ) -> Result<(Vec<FileEntry>, ReadMetrics)> {
+        let started = Instant::now();
+        let result = scan(&self.file_service, dir);
+        let elapsed = started.elapsed();
+        let (unreadable, first_error) = self.file_service.unreadable_entries();
+
+        let entries = result.as_ref().map(Vec::len).unwrap_or(0);
+        self.profiler.record(format!("{} {} ({} entries)", label, dir.display(), entries), elapsed);
+        if elapsed >= SLOW_SCAN_THRESHOLD {
+            self.set_status(format!(
+                "Reading {} took {:.1} s ({} entries) - slow or network filesystem?",
+                display_path(dir),
+                elapsed.as_secs_f64(),
+                entries
+            ));
+        }
+        if let Some(error) = first_error {
+            let entries = if unreadable == 1 { "entry" } else { "entries" };
+            self.set_status(format!("{} unreadable {}, first: {}", unreadable, entries, error));
+        }
+        result.map(|files| (files, ReadMetrics { elapsed, unreadable }))
+    }
+    
+    /// Re-check free space of the current filesystem (df can stall on unresponsive mounts, so it is timed)
+    fn update_disk_usage(&mut self) {
+        let label = format!("disk usage (df) {}", self.current_dir.display());
+        self.disk_usage = self.profiler.time(&label, || self.disk_service.usage(&self.current_dir).ok());
+    }
+    
+    /// Re-read the branch of the current directory, only while the status bar shows it
+    fn update_git_branch(&mut self) {
+        self.git_branch = if self.config.status_bar.segments.contains(&StatusSegment::Git) {
+            self.git_service.branch(&self.current_dir)
+        } else {
+            None
+        };
+    }
+
+    /// Whether the minute changed since the status bar clock was last drawn, when it shows one
+    fn clock_ticked(&mut self) -> bool {
+        if !self.config.status_bar.segments.contains(&StatusSegment::Clock) {
+            return false;
+        }
+        let minute = SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs() / 60).unwrap_or(0);
+        minute != std::mem::replace(&mut self.clock_minute, minute)
+    }
+
+    /// Advance spinners by a frame once one is due; whether anything animated needs drawing again
+    pub fn tick(&mut self) -> bool {
+        if self.last_tick.elapsed() < TICK_INTERVAL {
+            return false;
+        }
+        self.last_tick = Instant::now();
+        self.ticks = self.ticks.wrapping_add(1);
+        self.is_indexing || !self.jobs().is_empty()
+    }
+
+    /// Refresh files based on current mode and query
+    fn refresh_files_for_current_mode(&mut self) {
+        match self.mode {
+            AppMode::Explore => {
+                // Use stored directory files, optionally filtered by query
+                if self.query_text.is_empty() {
+                    self.files = self.visible_directory_files();
+                    self.files_source = FilesSource::CurrentDir;
+                } else {
+                    self.files = self.filter_files(&self.visible_directory_files(), &self.query_text);
+                    self.files_source = FilesSource::SearchResults;
+                }
+            }
+            AppMode::Search => {
+                // Filter current directory files by query
+                self.files = self.filter_files(&self.visible_directory_files(), &self.query_text);
+                self.files_source = FilesSource::SearchResults;
+            }
+            AppMode::FuzzyFind => {
+                // Fuzzy filter cached files
+                self.files = self.fuzzy_filter_files(&self.all_files_cache, &self.query_text);
+                self.files_source = FilesSource::FuzzyResults;
+            }
+        }
+        self.group_files();
+        
+        // Reset selection if out of bounds
+        if self.selected_index >= self.files.len() && !self.files.is_empty() {
+            self.selected_index = self.files.len() - 1;
+        } else if self.files.is_empty() {
+            self.selected_index = 0;
+        }
+    }
+    
+    /// Merge single-child directory chains when configured (skipped on network mounts, where each level
+    /// is a round trip) and apply the current sort
+    fn prepare_listing(&self, files: &mut [FileEntry]) {
+        if self.config.explore.collapse_chains && self.network_filesystem.is_none() {
+            self.file_service.collapse_directory_chains(files, MAX_CHAIN_PROBES);
+        }
+        FileService::sort_entries(files, self.sort);
+    }
+    
+    /// Sort listings by `key`; choosing the current key again flips the direction
+    pub fn sort_by(&mut self, key: SortKey) {
+        self.sort = if self.sort.key == key {
+            ListSort { key, descending: !self.sort.descending }
+        } else {
+            ListSort::new(key)
+        };
+        let selected = self.get_selected_file().map(|file| file.path.clone());
+        FileService::sort_entries(&mut self.directory_files, self.sort);
+        self.refresh_files_for_current_mode();
+        if let Some(path) = selected {
+            self.select_path(&path);
+        }
+        if self.network_filesystem.is_some() && key != SortKey::Name && key != SortKey::Extension {
+            self.set_status(format!("Sorted by {} {} (sizes and dates are not read on network mounts)", key.label(), self.sort.arrow()));
+        } else {
+            self.set_status(format!("Sorted by {} {}", key.label(), self.sort.arrow()));
+        }
+    }
+    
+    /// Directory listing as shown in explore and search mode, without gitignored entries in project view
+    fn visible_directory_files(&self) -> Vec<FileEntry> {
+        if self.ignored_paths.is_empty() {
+            return self.directory_files.clone();
+        }
+        self.directory_files
+            .iter()
+            .filter(|file| !self.ignored_paths.contains(&file.path))
+            .cloned()
+            .collect()
+    }
+    
+    /// Re-read `git status` for the listing when grouping by it
+    fn update_git_statuses(&mut self) {
+        self.git_statuses = if self.group_by == GroupBy::Git {
+            self.git_service.entry_statuses(&self.current_dir)
+        } else {
+            None
+        };
+    }
+    
+    /// Switch to the next grouping, keeping the cursor on the same entry
+    pub fn cycle_group_by(&mut self) {
+        let selected = self.get_selected_file().map(|file| file.path.clone());
+        self.group_by = self.group_by.next();
+        self.update_git_statuses();
+        self.refresh_files_for_current_mode();
+        if let Some(path) = selected {
+            self.select_path(&path);
+        }
+        if self.group_by == GroupBy::Git && self.git_statuses.is_none() {
+            self.set_status("Grouped by git status (not inside a git work tree)");
+        } else {
+            self.set_status(format!("Grouped by {}", self.group_by.label()));
+        }
+    }
+    
+    /// Header of the group `file` belongs to, or None when listings are not grouped (or fuzzy results are shown)
+    pub fn group_label(&self, file: &FileEntry) -> Option<String> {
+        if self.mode == AppMode::FuzzyFind {
+            return None;
+        }
+        match self.group_by {
+            GroupBy::None => None,
+            GroupBy::Kind => Some(if file.is_directory { "Directories" } else { "Files" }.to_string()),
+            GroupBy::Extension => Some(match Self::group_extension(file) {
+                _ if file.is_directory => "Directories".to_string(),
+                Some(extension) => format!(".{}", extension),
+                None => "No extension".to_string(),
+            }),
+            GroupBy::Git => Some(self.git_status(file).label().to_string()),
+        }
+    }
+    
+    /// Order the shown entries so every group is contiguous, keeping the sort within each group
+    fn group_files(&mut self) {
+        if self.mode == AppMode::FuzzyFind {
+            return;
+        }
+        match self.group_by {
+            GroupBy::None | GroupBy::Kind => {}  // Listings already put directories first
+            GroupBy::Extension => self.files.sort_by_cached_key(|file| {
+                let extension = Self::group_extension(file);
+                ((!file.is_directory, extension.is_none()), extension)
+            }),
+            GroupBy::Git => {
+                let mut files = std::mem::take(&mut self.files);
+                files.sort_by_cached_key(|file| self.git_status(file));
+                self.files = files;
+            }
+        }
+    }
+    
+    fn group_extension(file: &FileEntry) -> Option<String> {
+        file.path.extension().map(|ext| ext.to_string_lossy().to_lowercase())
+    }
+    
+    /// Status of the listing entry holding `file` (collapsed chains are keyed by their first directory)
+    fn git_status(&self, file: &FileEntry) -> GitStatus {
+        let entry = file.path.strip_prefix(&self.current_dir).ok()
+            .and_then(|relative| relative.components().next())
+            .map(|first| self.current_dir.join(first));
+        entry.and_then(|entry| self.git_statuses.as_ref()?.get(&entry).copied())
+            .unwrap_or(GitStatus::Clean)
+    }
+    
+    /// Ask git which entries of the current listing it ignores; false outside a work tree
+    fn update_ignored_paths(&mut self) -> bool {
+        if !self.project_view {
+            self.ignored_paths.clear();
+            return true;
+        }
+        match self.ignore_service.ignored_entries(&self.current_dir, &self.directory_files) {
+            Some(ignored) => {
+                self.ignored_paths = ignored;
+                true
+            }
+            None => {
+                self.ignored_paths.clear();
+                false
+            }
+        }
+    }
+    
+    /// Turn the project view on or off, keeping the cursor on the same entry when it stays visible
+    pub fn toggle_project_view(&mut self) {
+        let selected = self.get_selected_file().map(|file| file.path.clone());
+        self.project_view = !self.project_view;
+        let in_work_tree = self.update_ignored_paths();
+        self.refresh_files_for_current_mode();
+        if let Some(path) = selected {
+            self.select_path(&path);
+        }
+        
+        let message = match (self.project_view, in_work_tree) {
+            (false, _) => "Project view off: showing all entries".to_string(),
+            (true, false) => "Project view on (not inside a git work tree, nothing to hide)".to_string(),
+            (true, true) => format!("Project view on: {} gitignored entries hidden", self.ignored_paths.len()),
+        };
+        self.set_status(message);
+    }
+    
+    /// Preview READMEs of selected directories or their children
+    pub fn toggle_readme_preview(&mut self) {
+        self.readme_preview = !self.readme_preview;
+        self.set_status(if self.readme_preview { "Directories preview their README" } else { "Directories preview their contents" });
+    }
+    
+    /// Text filtering for search mode, narrowed further by `owner:`, `group:`, `perm:` and `type:` words
+    fn filter_files(&self, files: &[FileEntry], query: &str) -> Vec<FileEntry> {
+        if query.is_empty() {
+            return files.to_vec();
+        }
+        
+        let filter = self.power_filter(query);
+        files
+            .iter()
+            .filter(|file| filter.matches(&file.name, &file.path))
+            .cloned()
+            .collect()
+    }
+    
+    /// The query split into text and predicates, user and group names looked up
+    fn power_filter(&self, query: &str) -> PowerFilter {
+        let id_of = |accounts: Vec<Account>, name: &str| accounts.into_iter().find(|account| account.name == name).map(|account| account.id);
+        PowerFilter::parse(
+            query,
+            |name| id_of(self.account_service.users(), name),
+            |name| id_of(self.account_service.groups(), name),
+        )
+    }
+
+    /// Fuzzy filtering with scoring for fuzzy find mode; predicates narrow down the whole tree, e.g. `perm:setuid`
+    fn fuzzy_filter_files(&self, files: &[FileEntry], query: &str) -> Vec<FileEntry> {
+        if query.is_empty() {
+            return files.to_vec();
+        }
+        let filter = self.power_filter(query);
+        let query = filter.text.as_str();
+        
+        // Files opened often, and lately, rank above equally good matches
+        let frecency = self.history_service.frecency_scores();
+        let mut matches: Vec<(FileEntry, i32)> = files
+            .iter()
+            .filter_map(|file| {
+                let score = self.fuzzy_match(&file.path.to_string_lossy(), query);
+                if score > 0 && filter.passes(&file.path) {
+                    let boost = frecency.get(&file.path)
+                        .map_or(0, |points| ((points * 10.0) as i32).min(MAX_FRECENCY_BOOST));
+                    Some((file.clone(), score + boost))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        
+        // Sort by score (higher is better)
+        matches.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        matches.into_iter().map(|(file, _)| file).collect()
+    }
+    
+    /// Fuzzy matching algorithm
+    fn fuzzy_match(&self, text: &str, pattern: &str) -> i32 {
+        let text = text.to_lowercase();
+        let pattern = pattern.to_lowercase();
+        
+        if pattern.is_empty() {
+            return 100;
+        }
+
+        let mut score: i32 = 0;
+        let text_chars = text.chars().collect::<Vec<_>>();
+        let pattern_chars = pattern.chars().collect::<Vec<_>>();
+        
+        let mut text_idx = 0;
+        let mut pattern_idx = 0;
+        let mut consecutive_matches = 0;
 
         while text_idx < text_chars.len() && pattern_idx < pattern_chars.len() {
             if text_chars[text_idx] == pattern_chars[pattern_idx] {
@@ -314,56 +1893,1166 @@ impl AppModel {
             } else {
                 consecutive_matches = 0;
             }
-            text_idx += 1;
+            text_idx += 1;
+        }
+
+        if pattern_idx == pattern_chars.len() {
+            let path_depth_penalty = text.matches('/').count() as i32;
+            score = score.saturating_sub(path_depth_penalty);
+            
+            if text.contains(&pattern) {
+                score += 50;
+            }
+            
+            score.max(1)
+        } else {
+            0
+        }
+    }
+    
+    /// Open selected file with editor
+    pub fn open_selected_file_with_editor(&mut self) -> Result<()> {
+        let selected_file = match self.get_selected_file() {
+            Some(file) => file.clone(),
+            None => return Err(crate::core::ClazyfilerError::editor("selection", "No file selected")),
+        };
+        
+        if selected_file.is_directory {
+            return Err(crate::core::ClazyfilerError::editor("editor", "Cannot open directory with editor"));
+        }
+        
+        self.history_service.record_open(&selected_file.path);
+        if EncryptionService::is_encrypted(&selected_file.path) {
+            let outcome = self.encryption_service.edit(&selected_file.path, &self.editor_service);
+            self.refresh_current_directory();
+            match outcome {
+                Ok(true) => self.set_status(format!("Saved and re-encrypted {}", selected_file.name)),
+                Ok(false) => self.set_status(format!("No changes; {} left untouched", selected_file.name)),
+                Err(e) => self.set_status(e.to_string()),
+            }
+            return Ok(());
+        }
+        
+        let result = self.editor_service.open_file(&selected_file);
+        
+        // Refresh files after editor operation
+        self.refresh_current_directory();
+        
+        result
+    }
+    
+    /// Files opened through clazyfiler, most recent first
+    pub fn recent_opens(&self, limit: usize) -> Vec<RecentOpen> {
+        self.history_service.recent(limit)
+    }
+    
+    /// When the file was last opened through clazyfiler, for the detail panel
+    pub fn open_history(&self, file: &FileEntry) -> Option<RecentOpen> {
+        self.history_service.opens_of(&file.path)
+    }
+    
+    /// Navigate to the most recently opened file and select it, ready for `open_selected_file_with_editor`
+    pub fn reveal_last_opened(&mut self) -> Result<PathBuf> {
+        let Some(path) = self.history_service.last_opened().map(Path::to_path_buf) else {
+            return Err(ClazyfilerError::content("history", "No file opened yet"));
+        };
+        self.reveal_opened(&path)?;
+        Ok(path)
+    }
+    
+    /// Go to where the last finished copy or move put its entries, selecting the first and marking them all
+    /// if there were several; returns how many are still listed there
+    pub fn go_to_job_landing(&mut self) -> Result<usize> {
+        let Some(landing) = self.job_landing.clone() else {
+            return Err(ClazyfilerError::content("jobs", "No finished copy or move to go to"));
+        };
+        self.change_directory(landing.destination)?;
+        let listed: Vec<PathBuf> = landing.entries.into_iter()
+            .filter(|path| self.files.iter().any(|file| &file.path == path))
+            .collect();
+        if let Some(first) = listed.first() {
+            self.select_path(first);
+        }
+        if listed.len() > 1 {
+            self.marked_paths.extend(listed.iter().cloned());
+        }
+        Ok(listed.len())
+    }
+    
+    /// The watched downloads directory, if there is one
+    pub fn downloads_dir(&self) -> Option<&Path> {
+        self.downloads_service.dir()
+    }
+
+    /// Entries of the downloads directory, newest first
+    pub fn downloads(&self) -> &[Download] {
+        self.downloads_service.entries()
+    }
+
+    /// Whether a download arrived since the panel was last open
+    pub fn is_new_download(&self, download: &Download) -> bool {
+        self.downloads_service.is_new(download)
+    }
+
+    pub fn new_download_count(&self) -> usize {
+        self.downloads_service.new_count()
+    }
+
+    /// Read the downloads directory for the panel, as the watcher may be unavailable
+    pub fn reload_downloads(&mut self) {
+        self.downloads_service.reload();
+    }
+
+    /// Leaving the panel: what it listed is no longer new
+    pub fn mark_downloads_seen(&mut self) {
+        self.downloads_service.mark_seen();
+    }
+
+    /// Cut one entry for pasting elsewhere with p, like `d` does for the selection
+    pub fn cut_entry(&mut self, path: PathBuf) {
+        self.file_clipboard = Some(FileClipboard { paths: vec![path], mode: PasteMode::Move });
+    }
+
+    /// Revert the latest copy, move, rename or trash, e.g. `Undid rename of notes.txt`
+    pub fn undo_operation(&mut self) -> Result<String> {
+        self.check_no_batch()?;
+        let operation = self.operation_history.undo(&self.file_service, Config::trash_dir().as_deref());
+        self.show_operation_result(operation, true).map(|operation| format!("Undid {}", operation))
+    }
+
+    /// Do the latest undone operation again
+    pub fn redo_operation(&mut self) -> Result<String> {
+        self.check_no_batch()?;
+        let operation = self.operation_history.redo(&self.file_service, Config::trash_dir().as_deref());
+        self.show_operation_result(operation, false).map(|operation| format!("Redid {}", operation))
+    }
+
+    /// Undoing entries a running batch is still working on would race with it
+    fn check_no_batch(&self) -> Result<()> {
+        match self.batch.as_ref().filter(|batch| batch.is_running()) {
+            Some(_) => Err(ClazyfilerError::content("history", "Wait for the bulk operation to finish")),
+            None => Ok(()),
+        }
+    }
+
+    /// Refresh the listing after an undo (or redo) and select the first entry it put in the current directory
+    fn show_operation_result(&mut self, operation: Result<Operation>, undone: bool) -> Result<String> {
+        self.refresh_current_directory();
+        let operation = operation?;
+        let mut selected = None;
+        for (before, after) in &operation.entries {
+            let (old, new) = if undone { (after, before) } else { (before, after) };
+            // Marks follow the entries to where they are now
+            if self.marked_paths.remove(old) {
+                self.marked_paths.insert(new.clone());
+            }
+            if selected.is_none() && new.parent() == Some(self.current_dir.as_path()) {
+                selected = Some(new.clone());
+            }
+        }
+        if let Some(path) = selected {
+            self.select_path(&path);
+        }
+        Ok(operation.describe())
+    }
+
+    /// Navigate to a previously opened file; fails if it is no longer listed (deleted or hidden)
+    pub fn reveal_opened(&mut self, path: &Path) -> Result<()> {
+        self.reveal_path(path)?;
+        if self.get_selected_file().map(|file| file.path.as_path()) == Some(path) {
+            Ok(())
+        } else {
+            Err(ClazyfilerError::content(&display_path(path), "No longer in its directory listing"))
+        }
+    }
+    
+    /// Save the image on the system clipboard into the current directory as a timestamped PNG
+    pub fn paste_clipboard_image(&mut self) -> Result<PathBuf> {
+        let png = self.clipboard_service.read_image()?;
+        self.disk_service.ensure_space(&self.current_dir, png.len() as u64)?;
+        let stem = format!("clipboard-{}", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+        let path = self.file_service.write_new_file(&self.current_dir, &stem, "png", &png)?;
+        self.refresh_current_directory();
+        self.select_path(&path);
+        Ok(path)
+    }
+    
+    /// Permissions `:touch` (or `:mkdir` when `directory`) would give a new entry
+    pub fn creation_mode(&self, directory: bool) -> CreationMode {
+        let permissions = &self.config.permissions;
+        if directory {
+            CreationMode::resolve(permissions.dir_mode, DEFAULT_DIR_MODE)
+        } else {
+            CreationMode::resolve(permissions.file_mode, DEFAULT_FILE_MODE)
+        }
+    }
+    
+    /// Create an empty file or a directory named `name` in the current directory and select it
+    pub fn create_entry(&mut self, name: &str, directory: bool) -> Result<CreationMode> {
+        if let Some(problem) = check_name(name).filter(|problem| problem.fatal) {
+            return Err(ClazyfilerError::content(name, &format!("{} (try '{}')", problem.message, problem.suggestion)));
+        }
+        let path = self.current_dir.join(name);
+        let mode = self.creation_mode(directory);
+        if directory {
+            self.file_service.create_directory(&path, mode)?;
+        } else {
+            self.file_service.create_file(&path, mode)?;
+        }
+        self.run_hook(HookEvent::Create, &path, None);
+        self.refresh_current_directory();
+        self.select_path(&path);
+        Ok(mode)
+    }
+    
+    /// Bump the modification time of entries to now, keeping the cursor where it is
+    pub fn touch_entries(&mut self, paths: &[PathBuf]) -> Result<()> {
+        if paths.is_empty() {
+            return Err(ClazyfilerError::content(&display_path(&self.current_dir), "Nothing selected"));
+        }
+        let selected = self.get_selected_file().map(|file| file.path.clone());
+        let touched = paths.iter().try_for_each(|path| self.file_service.touch(path));
+        self.refresh_current_directory();
+        if let Some(path) = selected {
+            self.select_path(&path);
+        }
+        touched
+    }
+
+    /// Rename the selected entry within the current directory, keeping it selected (and marked)
+    pub fn rename_selected(&mut self, new_name: &str) -> Result<PathBuf> {
+        let Some(from) = self.get_selected_file().map(|file| file.path.clone()) else {
+            return Err(ClazyfilerError::content(new_name, "Nothing selected to rename"));
+        };
+        if let Some(problem) = check_name(new_name).filter(|problem| problem.fatal) {
+            return Err(ClazyfilerError::content(new_name, &format!("{} (try '{}')", problem.message, problem.suggestion)));
+        }
+        let to = from.with_file_name(new_name);
+        self.file_service.rename(&from, &to)?;
+        self.run_hook(HookEvent::Rename, &to, Some(&from));
+        self.operation_history.record(OperationKind::Rename, vec![(from.clone(), to.clone())]);
+        if self.marked_paths.remove(&from) {
+            self.marked_paths.insert(to.clone());
+        }
+        self.refresh_current_directory();
+        self.select_path(&to);
+        Ok(to)
+    }
+    
+    /// Have the editor change the names of `paths`, listed one per line in a temporary file; returns the edited lines
+    pub fn edit_names(&self, paths: &[PathBuf]) -> Result<String> {
+        let file = std::env::temp_dir().join(format!("clazyfiler-rename-{}.txt", std::process::id()));
+        let lines: Vec<String> = paths.iter().map(|path| bulk_rename_line(&self.current_dir, path)).collect();
+        std::fs::write(&file, lines.join("\n") + "\n")
+            .map_err(|e| ClazyfilerError::file_system("write", &file.to_string_lossy(), e))?;
+        let edited = self.editor_service.open_path(&file).and_then(|_| {
+            std::fs::read_to_string(&file).map_err(|e| ClazyfilerError::file_system("read", &file.to_string_lossy(), e))
+        });
+        let _ = std::fs::remove_file(&file);
+        edited
+    }
+
+    /// Rename `paths` as their lines were edited by `edit_names`, all or nothing when checking the new names;
+    /// entries swapping names go through temporary ones. Undoable as one operation; returns how many were renamed
+    pub fn bulk_rename(&mut self, paths: &[PathBuf], edited: &str) -> Result<usize> {
+        let renames = bulk_rename_plan(&self.current_dir, paths, edited, |path| path.symlink_metadata().is_ok())?;
+        let sources: HashSet<&PathBuf> = renames.iter().map(|(source, _)| source).collect();
+        let staged: Vec<(PathBuf, PathBuf)> = if renames.iter().any(|(_, target)| sources.contains(target)) {
+            renames.iter().enumerate().map(|(index, (source, target))| {
+                (source.with_file_name(format!(".clazyfiler-rename-{}-{}", std::process::id(), index)), target.clone())
+            }).collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut done = Vec::new();
+        let mut result = Ok(());
+        for (index, (source, target)) in renames.iter().enumerate() {
+            let first_to = staged.get(index).map_or(target, |(temp, _)| temp);
+            if let Err(e) = self.file_service.rename(source, first_to) {
+                result = Err(e);
+                break;
+            }
+            done.push((source.clone(), first_to.clone()));
+        }
+        if result.is_ok() {
+            for (index, (temp, target)) in staged.iter().enumerate() {
+                if let Err(e) = self.file_service.rename(temp, target) {
+                    result = Err(e);
+                    break;
+                }
+                done[index].1 = target.clone();
+            }
         }
 
-        if pattern_idx == pattern_chars.len() {
-            let path_depth_penalty = text.matches('/').count() as i32;
-            score = score.saturating_sub(path_depth_penalty);
-            
-            if text.contains(&pattern) {
-                score += 50;
+        for (from, to) in &done {
+            self.run_hook(HookEvent::Rename, to, Some(from));
+            if self.marked_paths.remove(from) {
+                self.marked_paths.insert(to.clone());
             }
-            
-            score.max(1)
-        } else {
-            0
         }
+        let count = done.len();
+        if !done.is_empty() {
+            self.operation_history.record(OperationKind::Rename, done);
+        }
+        self.refresh_current_directory();
+        result.map(|_| count)
     }
     
-    /// Open selected file with editor
-    pub fn open_selected_file_with_editor(&mut self) -> Result<()> {
-        let selected_file = match self.get_selected_file() {
-            Some(file) => file.clone(),
-            None => return Err(crate::core::ClazyfilerError::editor("selection", "No file selected")),
+    /// Disk usage of the current filesystem when it is above the configured warning threshold
+    pub fn disk_usage_warning(&self) -> Option<&DiskUsage> {
+        self.disk_usage
+            .as_ref()
+            .filter(|usage| usage.used_percent() >= self.config.disk.usage_warning_percent)
+    }
+    
+    /// What the terminal supports, detected at startup
+    pub fn capabilities(&self) -> &TerminalCapabilities {
+        self.capability_service.capabilities()
+    }
+    
+    /// What is known about the terminal, for the `:version` popup
+    pub fn terminal_summary(&self) -> Vec<(&'static str, String)> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty()).unwrap_or_else(|| "-".to_string());
+        let yes_no = |value: bool| if value { "yes" } else { "no" }.to_string();
+        let capabilities = self.capabilities();
+        vec![
+            ("TERM", var("TERM")),
+            ("Program", var("TERM_PROGRAM")),
+            ("tmux", yes_no(std::env::var_os("TMUX").is_some())),
+            ("Queries", if capabilities.answered { "answered" } else { "no reply, environment only" }.to_string()),
+            ("Colors", capabilities.colors.label().to_string()),
+            ("Kitty gfx", yes_no(capabilities.kitty_graphics)),
+            ("Sixel", yes_no(capabilities.sixel)),
+            ("Thumbnails", capabilities.graphics().map(GraphicsProtocol::label).unwrap_or("block art").to_string()),
+            ("OSC 52", yes_no(capabilities.osc52)),
+            ("Emoji", match capabilities.wide_emoji {
+                Some(true) => "two columns".to_string(),
+                Some(false) => "narrow, shown as text".to_string(),
+                None => "not measured".to_string(),
+            }),
+            ("Transfer", self.transfer_service.terminal().map_or("unsupported", |terminal| terminal.label()).to_string()),
+        ]
+    }
+    
+    /// Permission bits of an entry as they are now, for editing them
+    pub fn permissions_of(&self, path: &Path) -> Option<u32> {
+        self.file_service.properties(path).ok()?.mode.map(|mode| mode & 0o7777)
+    }
+    
+    /// Change the permission bits of an entry and read the listing again to show them
+    pub fn set_permissions(&mut self, path: &Path, mode: u32) -> Result<()> {
+        self.file_service.set_permissions(path, mode)?;
+        self.refresh_current_directory();
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        self.set_status(format!("{} is now {:04o} {}", name, mode, permission_string(mode)));
+        Ok(())
+    }
+    
+    /// The connection named `name` in the config, and whether its mount command has to run first
+    pub fn connection(&self, name: &str) -> Result<(Connection, bool)> {
+        let Some(connection) = self.config.connections.get(name).cloned() else {
+            let known: Vec<&str> = self.config.connections.keys().map(String::as_str).collect();
+            let message = match known.as_slice() {
+                [] => "No connections configured; add them under [connections] in :config".to_string(),
+                _ => format!("Connections: {}", known.join(", ")),
+            };
+            return Err(ClazyfilerError::command(&format!("connect {}", name), &message));
         };
-        
-        if selected_file.is_directory {
-            return Err(crate::core::ClazyfilerError::editor("editor", "Cannot open directory with editor"));
+        let needs_mount = connection.mount.is_some() && !self.disk_service.is_mount_point(&connection.path);
+        Ok((connection, needs_mount))
+    }
+    
+    /// Run the mount command of a connection; must run with the terminal suspended
+    pub fn mount_connection(&self, connection: &Connection) -> Result<()> {
+        match &connection.mount {
+            Some(command) => self.disk_service.mount(command, &connection.path),
+            None => Ok(()),
+        }
+    }
+    
+    /// Properties of the selected entry for the properties panel
+    pub fn selected_properties(&self) -> Option<Result<FileProperties>> {
+        self.get_selected_file().map(|file| self.file_service.properties(&file.path))
+    }
+    
+    /// Hand the selected file to the local machine through the terminal's file-transfer sequence
+    pub fn send_selected_to_terminal(&mut self, out: &mut impl Write) {
+        let Some(file) = self.get_selected_file().cloned() else {
+            return;
+        };
+        if file.is_directory {
+            self.set_status("Only files can be sent through the terminal");
+            return;
+        }
+        match self.transfer_service.send_file(&file.path, out) {
+            Ok(terminal) => self.set_status(format!("Sent {} to {}", file.name, terminal.label())),
+            Err(e) => self.set_status(e.to_string()),
+        }
+    }
+    
+    /// Open `target` with the configured file manager, without waiting for it
+    pub fn open_with_file_manager(&mut self, target: PathBuf) -> Result<PathBuf> {
+        self.opener_service.open_detached(&self.config.external_commands.file_manager, &target)?;
+        self.history_service.record_open(&target);
+        Ok(target)
+    }
+    
+    /// Detect URLs, existing paths and source imports in the preview of the selected file
+    pub fn preview_links(&self) -> Vec<PreviewLink> {
+        let Some(selected) = self.get_selected_file() else {
+            return Vec::new();
+        };
+        if selected.is_directory {
+            return Vec::new();
+        }
+
+        let base_dir = selected.path.parent().unwrap_or(&self.current_dir);
+        let content = self.get_file_content(selected);
+
+        let mut links: Vec<PreviewLink> = find_link_candidates(&content)
+            .into_iter()
+            .filter_map(|candidate| {
+                let target = if candidate.is_url {
+                    let url = if candidate.text.starts_with("www.") {
+                        format!("https://{}", candidate.text)
+                    } else {
+                        candidate.text.clone()
+                    };
+                    LinkTarget::Url(url)
+                } else {
+                    let path = self.file_service.resolve_reference(base_dir, &candidate.text)?;
+                    // A file mentioning its own name is not a useful link
+                    if path == selected.path {
+                        return None;
+                    }
+                    LinkTarget::Path(path)
+                };
+                Some(PreviewLink {
+                    line: candidate.line,
+                    start: candidate.start,
+                    end: candidate.end,
+                    target,
+                })
+            })
+            .collect();
+
+        // Module/include references turn the preview into a lightweight code navigator
+        let language = selected.path
+            .extension()
+            .and_then(|ext| SourceLanguage::from_extension(&ext.to_string_lossy()));
+        if let Some(language) = language {
+            for reference in find_import_references(&content, language, &selected.name) {
+                let overlaps = links.iter().any(|link| {
+                    link.line == reference.line && link.start < reference.end && reference.start < link.end
+                });
+                if overlaps {
+                    continue;
+                }
+                let target = reference.candidates
+                    .iter()
+                    .find_map(|candidate| self.file_service.resolve_reference(base_dir, candidate));
+                if let Some(path) = target {
+                    links.push(PreviewLink {
+                        line: reference.line,
+                        start: reference.start,
+                        end: reference.end,
+                        target: LinkTarget::Path(path),
+                    });
+                }
+            }
+            links.sort_by_key(|link| (link.line, link.start));
+        }
+
+        links
+    }
+    
+    /// Follow a preview link: URLs go to the browser, paths are revealed in the file list
+    pub fn follow_link(&mut self, target: &LinkTarget) -> Result<()> {
+        match target {
+            LinkTarget::Url(url) => {
+                self.opener_service.open_detached(&self.config.external_commands.browser, url)?;
+                self.set_status(format!("Opened {} in browser", url));
+                Ok(())
+            }
+            LinkTarget::Path(path) if path.is_dir() => self.change_directory(path.clone()),
+            LinkTarget::Path(path) => self.reveal_path(path),
         }
-        
-        let result = self.editor_service.open_file(&selected_file);
-        
-        // Refresh files after editor operation
-        self.refresh_current_directory();
-        
-        result
     }
     
+    /// Open config.toml in the editor, creating it with commented defaults first; needs the terminal suspended
+    pub fn edit_config(&self) -> Result<()> {
+        let path = Config::create_if_missing()?;
+        self.editor_service.open_path(&path)
+    }
+
+    /// Re-read config.toml and apply it; on errors the running configuration stays in effect
+    /// Screen and instance settings only take effect at startup, so they are kept; returns whether they changed
+    pub fn reload_config(&mut self) -> Result<bool> {
+        let mut config = Config::load()?;
+        let restart_needed = (config.screen.alternate, config.screen.inline_height, config.instance.single)
+            != (self.config.screen.alternate, self.config.screen.inline_height, self.config.instance.single);
+        config.screen = self.config.screen.clone();
+        config.instance = self.config.instance.clone();
+
+        // Starting states of toggles follow the file when it changes them
+        if config.explore.project_view != self.config.explore.project_view {
+            self.project_view = config.explore.project_view;
+        }
+        if config.explore.group_by != self.config.explore.group_by {
+            self.group_by = config.explore.group_by;
+        }
+        if config.explore.readme_preview != self.config.explore.readme_preview {
+            self.readme_preview = config.explore.readme_preview;
+        }
+        // The panel is shown again if bindings of the new file are refused
+        let (keymap, problems) = Keymap::new(&config.keys);
+        self.keymap = keymap;
+        self.diagnostics = problems;
+        if config.encryption.age_identity != self.config.encryption.age_identity {
+            self.encryption_service = EncryptionService::new(config.encryption.age_identity.clone());
+        }
+        if config.downloads.dir != self.config.downloads.dir {
+            self.downloads_service = DownloadsService::new(config.downloads.dir.clone());
+        }
+        self.config = config;
+        self.file_service.set_network(self.network_filesystem.is_some(), self.config.disk.network_retry_policy());
+        self.io_throttle.set_limit(self.config.disk.background_io_limit());
+        // Re-reading applies collapse_chains and the toggles to the listing
+        self.refresh_current_directory();
+        self.enforce_cache_budget();
+        Ok(restart_needed)
+    }
+
     /// Refresh current directory files
     pub fn refresh_current_directory(&mut self) {
         // Re-read directory files from disk
-        if let Ok(directory_files) = self.file_service.read_directory(&self.current_dir) {
+        let current_dir = self.current_dir.clone();
+        if let Ok((mut directory_files, metrics)) = self.timed_scan("read_directory", &current_dir, FileService::read_directory) {
+            self.listing_read = Some(metrics);
+            self.prepare_listing(&mut directory_files);
             self.directory_files = directory_files;
+            self.update_ignored_paths();
+            self.update_git_statuses();
         }
+        self.clear_caches();
+        self.update_disk_usage();
+        self.update_git_branch();
         self.refresh_files_for_current_mode();
     }
     
-    /// Get file content for display
+    /// Drop every cached preview so files are read again
+    pub fn clear_caches(&mut self) {
+        self.file_service.clear_cache();
+        self.image_service.clear_cache();
+        self.video_service.clear_cache();
+        self.data_file_service.clear_cache();
+        self.document_service.clear_cache();
+        self.font_service.clear_cache();
+        self.torrent_service.clear_cache();
+        self.verification_service.clear_cache();
+        self.encryption_service.clear_cache();
+        // A watched index is current anyway; any other idle one is revalidated from its saved copy later
+        if self.mode != AppMode::FuzzyFind && !self.fuzzy_index_service.is_live(&self.current_dir) {
+            self.drop_fuzzy_index();
+        }
+    }
+    
+    /// Every cache counted against the memory budget
+    fn caches(&self) -> Vec<&dyn BudgetedCache> {
+        [
+            self.file_service.caches(),
+            self.image_service.caches(),
+            self.video_service.caches(),
+            self.data_file_service.caches(),
+            self.document_service.caches(),
+            self.font_service.caches(),
+            self.torrent_service.caches(),
+            self.encryption_service.caches(),
+        ]
+        .concat()
+    }
+    
+    /// Approximate memory held by the fuzzy-find index
+    fn fuzzy_index_bytes(&self) -> usize {
+        self.all_files_cache
+            .iter()
+            .map(|file| std::mem::size_of::<FileEntry>() + file.name.len() + file.path.as_os_str().len())
+            .sum()
+    }
+    
+    /// Evict least recently used entries across all caches until they fit the configured budget
+    /// An idle fuzzy-find index goes first, since it is saved and can be loaded again
+    pub fn enforce_cache_budget(&mut self) {
+        let limit = self.config.cache.memory_budget_mb.saturating_mul(1024 * 1024);
+        let cached = |model: &Self| model.caches().iter().map(|cache| cache.stats().bytes).sum::<usize>();
+        if self.mode != AppMode::FuzzyFind && cached(self) + self.fuzzy_index_bytes() > limit {
+            self.drop_fuzzy_index();
+        }
+
+        let mut used = cached(self) + self.fuzzy_index_bytes();
+        let caches = self.caches();
+        while used > limit {
+            let Some(oldest) = caches
+                .iter()
+                .filter_map(|cache| cache.oldest_use().map(|last_used| (last_used, cache)))
+                .min_by_key(|(last_used, _)| *last_used)
+                .map(|(_, cache)| cache)
+            else {
+                break;
+            };
+            let before = oldest.stats().bytes;
+            oldest.evict_oldest();
+            used -= before - oldest.stats().bytes;
+        }
+    }
+    
+    /// One-line report of cache sizes and hit rates for `:cache stats`
+    pub fn cache_stats_summary(&self) -> String {
+        let stats: Vec<_> = self.caches().iter().map(|cache| cache.stats()).collect();
+        let fuzzy_bytes = self.fuzzy_index_bytes();
+        let bytes = stats.iter().map(|stat| stat.bytes).sum::<usize>() + fuzzy_bytes;
+        let hits = stats.iter().map(|stat| stat.hits).sum::<u64>();
+        let lookups = hits + stats.iter().map(|stat| stat.misses).sum::<u64>();
+        let evictions = stats.iter().map(|stat| stat.evictions).sum::<u64>();
+        let size = |bytes: usize| FileService::format_file_size(bytes as u64);
+
+        let mut summary = format!(
+            "Caches {} of {} MB, {:.0}% hits, {} evicted",
+            size(bytes),
+            self.config.cache.memory_budget_mb,
+            if lookups == 0 { 0.0 } else { hits as f64 * 100.0 / lookups as f64 },
+            evictions
+        );
+        for stat in stats.iter().filter(|stat| stat.entries > 0) {
+            summary.push_str(&format!(" | {} {} ({})", stat.name, stat.entries, size(stat.bytes)));
+        }
+        if !self.all_files_cache.is_empty() {
+            summary.push_str(&format!(" | fuzzy index {} ({})", self.all_files_cache.len(), size(fuzzy_bytes)));
+        }
+        summary
+    }
+    
+    /// Whether the preview of `file` is still held back because the selection may move on
+    /// Only on network filesystems, where scrolling past a file should not cost a read
+    fn preview_settling(&self, file: &FileEntry) -> bool {
+        if self.network_filesystem.is_none() {
+            return false;
+        }
+        let mut pending = self.preview_pending.borrow_mut();
+        match pending.as_ref() {
+            Some((path, since, _)) if *path == file.path => since.elapsed() < NETWORK_PREVIEW_DELAY,
+            _ => {
+                *pending = Some((file.path.clone(), Instant::now(), false));
+                true
+            }
+        }
+    }
+    
+    /// Get file content for display, headed by sidecar verification results
     pub fn get_file_content(&self, file: &FileEntry) -> String {
+        if self.preview_settling(file) {
+            return "⏳ Preview loads when the selection settles (network filesystem)".to_string();
+        }
+        let content = self.preview_content(file);
+        if file.is_directory {
+            return content;
+        }
+
+        let checks = self.verification_service.checks(&file.path);
+        let digests = self.verification_service.digests(&file.path);
+        if checks.is_empty() && digests.is_none() {
+            return content;
+        }
+        let mut header = String::new();
+        match digests {
+            Some(DigestState::Pending) => header.push_str("#️⃣ Computing checksums...\n"),
+            Some(DigestState::Done(digests)) => {
+                header.push_str(&format!("#️⃣ MD5: {}\n#️⃣ SHA-1: {}\n#️⃣ SHA-256: {}\n", digests.md5, digests.sha1, digests.sha256));
+            }
+            Some(DigestState::Failed(detail)) => header.push_str(&format!("#️⃣ Checksums: ⚠ {}\n", detail)),
+            None => {}
+        }
+        for check in checks {
+            let sidecar = check.sidecar.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+            let state = match &check.state {
+                CheckState::Pending => "⏳ Verifying...".to_string(),
+                CheckState::Passed(detail) => format!("✅ {}", detail),
+                CheckState::Failed(detail) => format!("❌ {}", detail),
+                CheckState::Unavailable(detail) => format!("⚠ {}", detail),
+            };
+            header.push_str(&format!("🔐 {} ({}): {}\n", check.kind.label(), sidecar, state));
+        }
+        format!("{}\n{}", header, content)
+    }
+
+    /// Compute the MD5, SHA-1 and SHA-256 of the selected file in the background, shown atop its preview
+    pub fn compute_selected_digests(&mut self) {
+        let Some(file) = self.get_selected_file().filter(|file| !file.is_directory).cloned() else {
+            self.set_status("Select a file to compute its checksums");
+            return;
+        };
+        if self.verification_service.compute_digests(&file.path) {
+            self.set_status(format!("Computing checksums of {}", file.name));
+        } else {
+            self.set_status(format!("Checksums of {} are still being computed", file.name));
+        }
+    }
+
+    /// Check the selected file against its `.sha256` or `SHA256SUMS` sibling again, and its signature
+    pub fn verify_selected(&mut self) {
+        let Some(file) = self.get_selected_file().filter(|file| !file.is_directory).cloned() else {
+            self.set_status("Select a file to verify");
+            return;
+        };
+        match self.verification_service.verify_again(&file.path) {
+            0 => self.set_status(format!("No .sha256, SHA256SUMS or .asc next to {}", file.name)),
+            _ => self.set_status(format!("Verifying {}", file.name)),
+        }
+    }
+
+    /// Watch the current directory for changes made outside, dropping the badges of the previous one
+    fn watch_listing(&mut self) {
+        self.entry_changes.clear();
+        self.listing_watcher = TreeWatcher::new()
+            .filter(|_| self.network_filesystem.is_none())
+            .and_then(|mut watcher| watcher.watch(&self.current_dir).then_some(watcher));
+    }
+
+    /// Badge what the listing watcher saw change and read the listing again if anything did
+    /// True while badges are shown, so they are redrawn as they fade
+    fn poll_listing_changes(&mut self) -> bool {
+        let Some(watcher) = self.listing_watcher.as_mut() else { return false };
+        let events = watcher.events();
+        let had_badges = !self.entry_changes.is_empty();
+        self.entry_changes.retain(|_, change| change.at.elapsed() < CHANGE_BADGE_DURATION);
+        if events.is_empty() {
+            return had_badges;
+        }
+
+        let now = Instant::now();
+        for event in events {
+            let previous = |path: &Path| self.entry_changes.get(path).map(|change| change.kind);
+            let (path, kind, is_directory) = match event {
+                // Back right after being deleted: replaced, as editors and downloads renaming into place do
+                WatchEvent::Created { path, is_directory } => match previous(&path) {
+                    Some(ChangeKind::Deleted) => (path, ChangeKind::Modified, is_directory),
+                    _ => (path, ChangeKind::New, is_directory),
+                },
+                WatchEvent::Written(path) if previous(&path) == Some(ChangeKind::New) => continue,
+                WatchEvent::Written(path) => (path, ChangeKind::Modified, false),
+                // Gone again right after it appeared, like a temporary file: nothing worth showing
+                WatchEvent::Removed(path) if previous(&path) == Some(ChangeKind::New) => {
+                    self.entry_changes.remove(&path);
+                    continue;
+                }
+                WatchEvent::Removed(path) => {
+                    let Some(file) = self.directory_files.iter().find(|file| file.path == path) else { continue };
+                    let is_directory = file.is_directory;
+                    (path, ChangeKind::Deleted, is_directory)
+                }
+                WatchEvent::Overflow => continue,
+            };
+            self.entry_changes.insert(path, EntryChange { kind, is_directory, at: now });
+        }
+
+        let selected = self.get_selected_file().map(|file| file.path.clone());
+        self.refresh_current_directory();
+        if let Some(selected) = selected {
+            self.select_path(&selected);
+        }
+        true
+    }
+
+    /// Deleted entries still shown below the listing, by name
+    pub fn deleted_entries(&self) -> Vec<(&Path, &EntryChange)> {
+        let mut deleted: Vec<(&Path, &EntryChange)> = self.entry_changes
+            .iter()
+            .filter(|(_, change)| change.kind == ChangeKind::Deleted)
+            .map(|(path, change)| (path.as_path(), change))
+            .collect();
+        deleted.sort_by(|a, b| a.0.cmp(b.0));
+        deleted
+    }
+
+    /// Collect results of background work (e.g. checksum verification); true if the view changed
+    pub fn poll_background_tasks(&mut self) -> bool {
+        let index_changed = match self.fuzzy_index_service.apply_events(&mut self.all_files_cache, &self.file_service) {
+            Some(changed) => changed,
+            None => {
+                // The watcher lost events: rebuild from the saved copy if fuzzy find is open, drop it otherwise
+                self.drop_fuzzy_index();
+                if self.mode == AppMode::FuzzyFind {
+                    if let Err(e) = self.start_fuzzy_indexing(false) {
+                        self.set_status(format!("Failed to rebuild fuzzy index: {}", e));
+                    }
+                }
+                true
+            }
+        };
+        if index_changed && self.mode == AppMode::FuzzyFind {
+            self.refresh_files_for_current_mode();
+        }
+
+        let mut preview_due = false;
+        if let Some((_, since, requested)) = self.preview_pending.borrow_mut().as_mut() {
+            if !*requested && since.elapsed() >= NETWORK_PREVIEW_DELAY {
+                *requested = true;
+                preview_due = true;
+            }
+        }
+        let hook_failed = match self.hook_service.poll() {
+            Some(failure) => {
+                self.set_status(failure);
+                true
+            }
+            None => false,
+        };
+        let listing_changed = self.poll_listing_changes();
+        let downloads_changed = self.downloads_service.poll();
+        let archive_changed = self.poll_archive();
+        let clock_ticked = self.clock_ticked();
+        let video_probed = self.video_service.poll();
+        let specimen_rendered = self.font_service.poll();
+        let data_summarized = self.data_file_service.poll();
+        let thumbnails_decoded = self.image_service.poll();
+        self.verification_service.poll() | video_probed | specimen_rendered | data_summarized | thumbnails_decoded | clock_ticked | archive_changed | preview_due | index_changed | hook_failed | listing_changed | downloads_changed
+    }
+
+    /// Type-specific preview text for a file
+    fn preview_content(&self, file: &FileEntry) -> String {
+        if let Some(kind) = EncryptionKind::from_path(&file.path).filter(|_| !file.is_directory) {
+            return self.encrypted_description(file, kind);
+        }
+        if !file.is_directory && TorrentService::is_torrent(&file.path) {
+            return self.torrent_description(file);
+        }
+        if let Some(kind) = DiskImageKind::from_path(&file.path).filter(|_| !file.is_directory) {
+            return self.disk_image_description(file, kind);
+        }
+        if !file.is_directory && DataFileService::is_data_file(&file.path) {
+            return self.data_file_description(file);
+        }
+        if !file.is_directory && FontService::is_font(&file.path) {
+            return self.font_description(file);
+        }
+        if let Some(kind) = DocumentKind::from_path(&file.path).filter(|_| !file.is_directory) {
+            return match self.document_service.extract_text(&file.path) {
+                Ok(text) => format!("📝 {} (text preview)\n\n{}", kind.label(), text),
+                Err(e) => format!("❌ Error reading document: {}", e),
+            };
+        }
+        if !file.is_directory && VideoService::is_video(&file.path) {
+            if let Some(content) = self.video_description(file) {
+                return content;
+            }
+        }
+        if !file.is_directory && ImageService::is_image(&file.path) {
+            if let Some((width, height)) = ImageService::dimensions(&file.path) {
+                return format!("🖼 Image File\n\nDimensions: {}x{}\nPath: {}", width, height, file.path.display());
+            }
+        }
+        
+        if file.is_directory && self.readme_preview {
+            if let Some(content) = self.readme_description(file) {
+                return content;
+            }
+        }
+        
         match self.file_service.read_file_content(file) {
             Ok(content) => content,
             Err(e) => format!("❌ Error reading file: {}", e),
         }
     }
+    
+    /// Counts of the selected directory's children for the detail pane; None for files and while previews settle
+    pub fn directory_stats(&self, file: &FileEntry) -> Option<DirectoryStats> {
+        if !file.is_directory || self.preview_settling(file) {
+            return None;
+        }
+        self.file_service.directory_stats(&file.path).ok()
+    }
+
+    /// The README of a directory, which says more about it than the names of its children
+    fn readme_description(&self, dir: &FileEntry) -> Option<String> {
+        let path = self.file_service.find_readme(&dir.path)?;
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let readme = FileEntry { name: name.clone(), path, is_directory: false, size: None, modified: None, mode: None, link_target: None, metadata_error: None };
+        Some(match self.file_service.read_file_content(&readme) {
+            Ok(content) => format!("📖 {} (v shows the contents)\n\n{}", name, content),
+            Err(e) => format!("❌ Error reading {}: {}", name, e),
+        })
+    }
+    
+    /// Describe an encrypted file, or show its plaintext once the user decrypted it
+    fn encrypted_description(&self, file: &FileEntry, kind: EncryptionKind) -> String {
+        if let Some(plaintext) = self.encryption_service.decrypted_preview(&file.path) {
+            return format!("🔓 {} Encrypted File (decrypted, hidden again on refresh)\n\n{}", kind.label(), plaintext);
+        }
+
+        let mut content = format!("🔒 {} Encrypted File\n\n", kind.label());
+        match self.encryption_service.info(&file.path) {
+            Ok(info) => {
+                let keys = match &info.keys {
+                    KeySource::Passphrase => "passphrase".to_string(),
+                    KeySource::Recipients(recipients) => recipients.join(", "),
+                };
+                content.push_str(&format!("Encrypted to: {}\n", keys));
+                content.push_str(&format!("Format: {}\n", if info.armored { "ASCII armored" } else { "binary" }));
+            }
+            Err(e) => content.push_str(&format!("❌ {}\n", e)),
+        }
+        content.push_str("\nPress D to decrypt into the preview, Enter to edit (re-encrypted on save)");
+        content
+    }
+
+    /// Decrypt the selected file into the preview; prompts happen on the suspended terminal
+    pub fn decrypt_selected_for_preview(&mut self) {
+        let Some(file) = self.get_selected_file().cloned() else {
+            return;
+        };
+        match self.encryption_service.decrypt_preview(&file.path) {
+            Ok(()) => self.set_status(format!("Decrypted {} into the preview", file.name)),
+            Err(e) => self.set_status(e.to_string()),
+        }
+    }
+
+    /// Describe a Parquet/Arrow file: row count, schema and a few sample rows
+    fn data_file_description(&self, file: &FileEntry) -> String {
+        let summary = match self.data_file_service.summary(&file.path) {
+            Some(Ok(summary)) => summary,
+            Some(Err(e)) => return format!("❌ Error reading data file: {}", e),
+            None => return format!("📊 Data File\n\n⏳ Reading the schema and sample rows...\nPath: {}", file.path.display()),
+        };
+
+        let mut content = format!("📊 {} File\n\n", summary.format);
+        match summary.row_count {
+            Some(rows) => content.push_str(&format!("Rows: {}\n", rows)),
+            None => content.push_str("Rows: unknown\n"),
+        }
+        for (label, value) in &summary.details {
+            content.push_str(&format!("{}: {}\n", label, value));
+        }
+
+        content.push_str("\nSchema:\n");
+        for line in &summary.schema {
+            content.push_str(&format!("{}\n", line));
+        }
+
+        if let Some(sample) = &summary.sample {
+            content.push_str(&format!("\nFirst {} rows:\n", sample.rows.len()));
+            for line in format_table(&sample.columns, &sample.rows) {
+                content.push_str(&format!("{}\n", line));
+            }
+        } else if let Some(note) = &summary.sample_note {
+            content.push_str(&format!("\n({})\n", note));
+        }
+        content
+    }
+
+    /// List a directory of the ISO image `image`, reached from its root through the directories in `trail`
+    pub fn list_disk_image(&self, image: &Path, trail: Vec<ImageEntry>) -> Result<ImageListing> {
+        self.disk_image_service.list_directory(image, trail)
+    }
+
+    /// Copy a file out of the ISO image `image` into the current directory, under a free name
+    pub fn extract_from_disk_image(&mut self, image: &Path, entry: &ImageEntry) -> Result<PathBuf> {
+        let to = FileService::free_name(Path::new(&entry.name), &self.current_dir)?;
+        self.disk_image_service.extract(image, entry, &to)?;
+        self.refresh_current_directory();
+        Ok(to)
+    }
+
+    /// Describe a disk image from its headers, listing an ISO's root directory or a raw image's partitions
+    fn disk_image_description(&self, file: &FileEntry, kind: DiskImageKind) -> String {
+        const MAX_LISTED_ENTRIES: usize = 100;
+        let info = match self.disk_image_service.inspect(&file.path, kind) {
+            Ok(info) => info,
+            Err(e) => return format!("❌ Error reading disk image: {}", e),
+        };
+
+        let mut content = format!("💿 {} (read-only)\n\n", kind.label());
+        for (label, value) in &info.details {
+            content.push_str(&format!("{}: {}\n", label, value));
+        }
+        if !info.entries.is_empty() {
+            let heading = if kind == DiskImageKind::Iso { "Root directory" } else { "Partitions" };
+            content.push_str(&format!("\n{} ({}):\n", heading, info.entries.len()));
+        }
+        for entry in info.entries.iter().take(MAX_LISTED_ENTRIES) {
+            if entry.is_directory {
+                content.push_str(&format!("📁 {}/\n", entry.name));
+            } else {
+                content.push_str(&format!("📄 {} ({})\n", entry.name, FileService::format_file_size(entry.size)));
+            }
+        }
+        if info.entries.len() > MAX_LISTED_ENTRIES {
+            content.push_str(&format!("... and {} more\n", info.entries.len() - MAX_LISTED_ENTRIES));
+        }
+        content
+    }
+
+    /// Describe a torrent: name, size, trackers and the files it contains
+    fn torrent_description(&self, file: &FileEntry) -> String {
+        const MAX_LISTED_FILES: usize = 100;
+        let info = match self.torrent_service.info(&file.path) {
+            Ok(info) => info,
+            Err(e) => return format!("❌ Error reading torrent: {}", e),
+        };
+
+        let mut content = String::from("🧲 Torrent File\n\n");
+        content.push_str(&format!("Name: {}\n", info.name));
+        content.push_str(&format!("Total size: {} in {} files\n", FileService::format_file_size(info.total_size()), info.files.len()));
+        if let Some(hash) = &info.info_hash_v1 {
+            content.push_str(&format!("Info hash: {}\n", hash));
+        }
+        if let Some(hash) = &info.info_hash_v2 {
+            content.push_str(&format!("Info hash (v2): {}\n", hash));
+        }
+        if let Some(piece_length) = info.piece_length {
+            content.push_str(&format!("Piece length: {}\n", FileService::format_file_size(piece_length)));
+        }
+        if info.private {
+            content.push_str("Private: yes\n");
+        }
+        if let Some(date) = info.creation_date.and_then(|date| chrono::DateTime::from_timestamp(date, 0)) {
+            content.push_str(&format!("Created: {}\n", date.format("%Y-%m-%d %H:%M:%S UTC")));
+        }
+        if let Some(created_by) = &info.created_by {
+            content.push_str(&format!("Created by: {}\n", created_by));
+        }
+        if let Some(comment) = &info.comment {
+            content.push_str(&format!("Comment: {}\n", comment));
+        }
+        if !info.trackers.is_empty() {
+            content.push_str("\nTrackers:\n");
+            for tracker in &info.trackers {
+                content.push_str(&format!("{}\n", tracker));
+            }
+        }
+
+        content.push_str("\nFiles:\n");
+        for (path, size) in info.files.iter().take(MAX_LISTED_FILES) {
+            content.push_str(&format!("{} ({})\n", path, FileService::format_file_size(*size)));
+        }
+        if info.files.len() > MAX_LISTED_FILES {
+            content.push_str(&format!("... and {} more files\n", info.files.len() - MAX_LISTED_FILES));
+        }
+        content
+    }
+
+    /// Describe a font: names, style and metrics
+    fn font_description(&self, file: &FileEntry) -> String {
+        let info = match self.font_service.info(&file.path) {
+            Ok(info) => info,
+            Err(e) => return format!("❌ Error reading font: {}", e),
+        };
+
+        let mut content = String::from("🔤 Font File\n\n");
+        let fields = [
+            ("Family", info.family),
+            ("Style", info.style),
+            ("Full name", info.full_name),
+            ("PostScript name", info.postscript_name),
+            ("Version", info.version),
+            ("Designer", info.designer),
+            ("Weight", info.weight.map(|weight| weight.to_string())),
+            ("Glyphs", info.glyph_count.map(|count| count.to_string())),
+            ("Units per em", info.units_per_em.map(|units| units.to_string())),
+        ];
+        for (label, value) in fields {
+            if let Some(value) = value {
+                content.push_str(&format!("{}: {}\n", label, value));
+            }
+        }
+        content.push_str(&format!("Format: {}\n", info.format));
+        if info.faces > 1 {
+            content.push_str(&format!("Faces: {} (showing the first)\n", info.faces));
+        }
+        if !self.font_service.can_render() {
+            content.push_str("\n(Install hb-view or ImageMagick to render a specimen)\n");
+        } else if self.font_service.is_rendering(&file.path) {
+            content.push_str("\n⏳ Rendering specimen...\n");
+        }
+        content.push_str(&format!("Path: {}", file.path.display()));
+        content
+    }
+
+    /// Describe a video using ffprobe metadata, if ffprobe is installed
+    fn video_description(&self, file: &FileEntry) -> Option<String> {
+        if !self.video_service.is_available() {
+            return None;
+        }
+        let info = match self.video_service.info(&file.path) {
+            VideoState::Done(info) => info,
+            VideoState::Pending => return Some(format!("🎬 Video File\n\n⏳ Probing with ffprobe...\nPath: {}", file.path.display())),
+            VideoState::Failed => return None,
+        };
+
+        let mut content = String::from("🎬 Video File\n\n");
+        if let Some(duration) = info.duration_secs {
+            let total = duration.round() as u64;
+            content.push_str(&format!("Duration: {}:{:02}:{:02}\n", total / 3600, total / 60 % 60, total % 60));
+        }
+        if let (Some(width), Some(height)) = (info.width, info.height) {
+            content.push_str(&format!("Resolution: {}x{}\n", width, height));
+        }
+        if let Some(codec) = &info.video_codec {
+            content.push_str(&format!("Video codec: {}\n", codec));
+        }
+        if let Some(rate) = &info.frame_rate {
+            content.push_str(&format!("Frame rate: {}\n", rate));
+        }
+        if let Some(codec) = &info.audio_codec {
+            content.push_str(&format!("Audio codec: {}\n", codec));
+        }
+        if let Some(bit_rate) = info.bit_rate {
+            content.push_str(&format!("Bit rate: {} kb/s\n", bit_rate / 1000));
+        }
+        if let Some(format) = &info.format {
+            content.push_str(&format!("Container: {}\n", format));
+        }
+        content.push_str(&format!("Path: {}", file.path.display()));
+        Some(content)
+    }
+}
+
+/// Lay out rows as aligned columns, truncating long cells
+fn format_table(columns: &[String], rows: &[Vec<String>]) -> Vec<String> {
+    const MAX_CELL: usize = 24;
+    let clip = |cell: &str| -> String {
+        let cell = cell.replace(['\n', '\r', '\t'], " ");
+        if cell.chars().count() > MAX_CELL {
+            format!("{}…", cell.chars().take(MAX_CELL - 1).collect::<String>())
+        } else {
+            cell
+        }
+    };
+    let header: Vec<String> = columns.iter().map(|column| clip(column)).collect();
+    let body: Vec<Vec<String>> = rows.iter().map(|row| row.iter().map(|cell| clip(cell)).collect()).collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|col| {
+            body.iter()
+                .filter_map(|row| row.get(col))
+                .chain(std::iter::once(&header[col]))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let render = |cells: &[String]| -> String {
+        cells.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join(" │ ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![render(&header)];
+    lines.push(widths.iter().map(|width| "─".repeat(*width)).collect::<Vec<_>>().join("─┼─"));
+    lines.extend(body.iter().map(|row| render(row)));
+    lines
 }
@@ -0,0 +1,98 @@
+use std::process::{Command, Stdio};
+
+/// A user or group of the system, by name and numeric id
+#[derive(Debug, Clone, PartialEq)]
+pub struct Account {
+    pub name: String,
+    pub id: u32,
+}
+
+/// Service listing the users and groups entries can be given to
+/// Asks `getent` first, which includes directory services such as LDAP, then falls back to the files in /etc
+#[derive(Debug)]
+pub struct AccountService;
+
+impl AccountService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Users sorted by name
+    pub fn users(&self) -> Vec<Account> {
+        Self::database("passwd", "/etc/passwd")
+    }
+
+    /// Groups sorted by name
+    pub fn groups(&self) -> Vec<Account> {
+        Self::database("group", "/etc/group")
+    }
+
+    /// Name of the user with `uid` through the C library, which is cheap enough to ask while rendering
+    pub fn user_name(&self, uid: u32) -> Option<String> {
+        #[cfg(unix)]
+        {
+            let mut entry: libc::passwd = unsafe { std::mem::zeroed() };
+            let mut buffer = vec![0 as libc::c_char; 4096];
+            let mut found = std::ptr::null_mut();
+            // SAFETY: entry, buffer and found outlive the call and buffer's length is passed along
+            let result = unsafe { libc::getpwuid_r(uid, &mut entry, buffer.as_mut_ptr(), buffer.len(), &mut found) };
+            // SAFETY: on success pw_name points into buffer as a NUL-terminated string
+            (result == 0 && !found.is_null()).then(|| unsafe { std::ffi::CStr::from_ptr(entry.pw_name) }.to_string_lossy().into_owned())
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = uid;
+            None
+        }
+    }
+
+    /// Name of the group with `gid`, like `user_name`
+    pub fn group_name(&self, gid: u32) -> Option<String> {
+        #[cfg(unix)]
+        {
+            let mut entry: libc::group = unsafe { std::mem::zeroed() };
+            let mut buffer = vec![0 as libc::c_char; 4096];
+            let mut found = std::ptr::null_mut();
+            // SAFETY: entry, buffer and found outlive the call and buffer's length is passed along
+            let result = unsafe { libc::getgrgid_r(gid, &mut entry, buffer.as_mut_ptr(), buffer.len(), &mut found) };
+            // SAFETY: on success gr_name points into buffer as a NUL-terminated string
+            (result == 0 && !found.is_null()).then(|| unsafe { std::ffi::CStr::from_ptr(entry.gr_name) }.to_string_lossy().into_owned())
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = gid;
+            None
+        }
+    }
+
+    fn database(name: &str, file: &str) -> Vec<Account> {
+        let listed = Command::new("getent")
+            .arg(name)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned());
+        let text = listed.or_else(|| std::fs::read_to_string(file).ok()).unwrap_or_default();
+        parse_accounts(&text)
+    }
+}
+
+/// Names and ids of `name:password:id:...` lines, as in passwd and group files, sorted by name
+/// Comments, NIS `+`/`-` lines and names listed twice are left out
+pub fn parse_accounts(text: &str) -> Vec<Account> {
+    let mut accounts: Vec<Account> = text
+        .lines()
+        .filter(|line| !line.starts_with(['#', '+', '-']))
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let name = fields.next().filter(|name| !name.is_empty())?;
+            let id = fields.nth(1)?.parse().ok()?;
+            Some(Account { name: name.to_string(), id })
+        })
+        .collect();
+    accounts.sort_by(|a, b| a.name.cmp(&b.name));
+    accounts.dedup_by(|a, b| a.name == b.name);
+    accounts
+}
@@ -0,0 +1,452 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Instant, UNIX_EPOCH};
+use flate2::{write::{DeflateEncoder, GzEncoder}, Compression, Crc};
+use crate::batch::Transfer;
+use crate::core::{display_path, ClazyfilerError, IoThrottle, JobControl, Result};
+use crate::services::FileService;
+
+/// Largest size or offset the zip format holds without ZIP64 extensions, which are not written
+const ZIP_LIMIT: u64 = u32::MAX as u64;
+
+const ZIP_LOCAL_SIGNATURE: u32 = 0x0403_4b50;
+const ZIP_DESCRIPTOR_SIGNATURE: u32 = 0x0807_4b50;
+const ZIP_CENTRAL_SIGNATURE: u32 = 0x0201_4b50;
+const ZIP_EOCD_SIGNATURE: u32 = 0x0605_4b50;
+
+/// Kind of archive to pack, told by the name it is given
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveFormat {
+    Zip,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// `.zip`, or `.tar.gz`/`.tgz`; None for other names
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if name.ends_with(".zip") {
+            Some(ArchiveFormat::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else {
+            None
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => ".zip",
+            ArchiveFormat::TarGz => ".tar.gz",
+        }
+    }
+
+    pub fn other(self) -> Self {
+        match self {
+            ArchiveFormat::Zip => ArchiveFormat::TarGz,
+            ArchiveFormat::TarGz => ArchiveFormat::Zip,
+        }
+    }
+}
+
+/// An archive being packed on a worker thread
+#[derive(Debug)]
+struct ArchiveJob {
+    target: PathBuf,
+    control: JobControl,
+    started: Instant,
+    result: Receiver<Result<()>>,
+}
+
+/// Service packing entries into a zip or tar.gz archive in the background, one archive at a time
+/// Progress counts the file data read, like copies, and the job can be paused or cancelled
+#[derive(Debug)]
+pub struct ArchiveService {
+    job: Option<ArchiveJob>,
+    throttle: IoThrottle,  // Limits how fast entries are read
+}
+
+impl Default for ArchiveService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArchiveService {
+    pub fn new() -> Self {
+        Self { job: None, throttle: IoThrottle::unlimited() }
+    }
+
+    /// Share a throttle with the other background jobs
+    pub fn set_throttle(&mut self, throttle: IoThrottle) {
+        self.throttle = throttle;
+    }
+
+    /// Start packing `sources` into `target`; each is stored under its own name, directories with everything in them
+    pub fn start(&mut self, sources: Vec<PathBuf>, target: PathBuf, format: ArchiveFormat) -> Result<()> {
+        if let Some(job) = &self.job {
+            return Err(ClazyfilerError::content(&display_path(&job.target), "Another archive is still being packed"));
+        }
+        let (sender, result) = channel();
+        let control = JobControl::new();
+        let (worker_control, throttle, worker_target) = (control.clone(), self.throttle.clone(), target.clone());
+        std::thread::spawn(move || {
+            worker_control.add_bytes_total(sources.iter().map(|path| FileService::data_size(path)).sum());
+            let written = write_archive(&sources, &worker_target, format, &worker_control, &throttle);
+            if written.is_err() {
+                // A partial archive is of no use to anyone
+                let _ = fs::remove_file(&worker_target);
+            }
+            // The receiver is gone once the app exits
+            let _ = sender.send(written);
+        });
+        self.job = Some(ArchiveJob { target, control, started: Instant::now(), result });
+        Ok(())
+    }
+
+    /// The archive being packed, if any
+    pub fn running(&self) -> Option<&Path> {
+        self.job.as_ref().map(|job| job.target.as_path())
+    }
+
+    /// Pause and cancel switch of the archive being packed
+    pub fn control(&self) -> Option<&JobControl> {
+        self.job.as_ref().map(|job| &job.control)
+    }
+
+    /// How much of the data went into the archive so far
+    pub fn transfer(&self) -> Option<Transfer> {
+        let job = self.job.as_ref().filter(|job| job.control.bytes_total() > 0)?;
+        Some(Transfer::measure("Archive", &job.control, job.started))
+    }
+
+    /// The archive that was finished or failed since the last call, and how it went
+    pub fn poll(&mut self) -> Option<(PathBuf, Result<()>)> {
+        let result = self.job.as_ref()?.result.try_recv().ok()?;
+        self.job.take().map(|job| (job.target, result))
+    }
+}
+
+/// Pack `sources` into a new archive at `target`, reporting the data read to `control`
+/// Gives up with an error when the job is cancelled; the caller removes what was written
+pub fn write_archive(sources: &[PathBuf], target: &Path, format: ArchiveFormat, control: &JobControl, throttle: &IoThrottle) -> Result<()> {
+    let target_str = display_path(target);
+    let file = File::options()
+        .write(true)
+        .create_new(true)
+        .open(target)
+        .map_err(|e| ClazyfilerError::file_system("create", &target_str, e))?;
+
+    let mut entries = Vec::new();
+    for source in sources {
+        let name = source.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        collect_entries(source, name, target, &mut entries).map_err(|e| ClazyfilerError::file_system("read", &display_path(source), e))?;
+    }
+    let pump = Pump { control, throttle };
+    let written = match format {
+        ArchiveFormat::Zip => write_zip(BufWriter::new(file), &entries, &pump),
+        ArchiveFormat::TarGz => write_tar_gz(BufWriter::new(file), &entries, &pump),
+    };
+    written.map_err(|e| ClazyfilerError::file_system("write", &target_str, e))
+}
+
+/// What goes into the archive for one file, directory or symlink
+struct ArchiveEntry {
+    path: PathBuf,
+    name: String,  // Relative, `/`-separated, directories ending in `/`
+    kind: EntryKind,
+    mode: u32,
+    modified: u64,  // Unix seconds
+    size: u64,
+}
+
+#[derive(PartialEq)]
+enum EntryKind {
+    File,
+    Directory,
+    Symlink(String),
+}
+
+/// Every entry under `path` in the order they are stored, leaving out the archive being written
+fn collect_entries(path: &Path, name: String, target: &Path, entries: &mut Vec<ArchiveEntry>) -> io::Result<()> {
+    if path == target {
+        return Ok(());
+    }
+    let metadata = fs::symlink_metadata(path)?;
+    let modified = metadata.modified().ok().and_then(|time| time.duration_since(UNIX_EPOCH).ok()).map_or(0, |age| age.as_secs());
+    let mode = unix_mode(&metadata);
+    if metadata.is_dir() {
+        let name = format!("{}/", name);
+        entries.push(ArchiveEntry { path: path.to_path_buf(), name: name.clone(), kind: EntryKind::Directory, mode, modified, size: 0 });
+        let mut children: Vec<PathBuf> = fs::read_dir(path)?.map(|entry| entry.map(|entry| entry.path())).collect::<io::Result<_>>()?;
+        children.sort();
+        for child in children {
+            let child_name = format!("{}{}", name, child.file_name().unwrap_or_default().to_string_lossy());
+            collect_entries(&child, child_name, target, entries)?;
+        }
+    } else if metadata.file_type().is_symlink() {
+        let link = fs::read_link(path)?.to_string_lossy().into_owned();
+        entries.push(ArchiveEntry { path: path.to_path_buf(), name, kind: EntryKind::Symlink(link), mode, modified, size: 0 });
+    } else if metadata.is_file() {
+        entries.push(ArchiveEntry { path: path.to_path_buf(), name, kind: EntryKind::File, mode, modified, size: metadata.len() });
+    }
+    // Sockets, devices and pipes have nothing to pack
+    Ok(())
+}
+
+#[cfg(unix)]
+fn unix_mode(metadata: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn unix_mode(metadata: &fs::Metadata) -> u32 {
+    match (metadata.is_dir(), metadata.permissions().readonly()) {
+        (true, _) => 0o755,
+        (false, true) => 0o444,
+        (false, false) => 0o644,
+    }
+}
+
+/// Moves file data into the archive in chunks, paced, counted, and held while the job is paused
+struct Pump<'a> {
+    control: &'a JobControl,
+    throttle: &'a IoThrottle,
+}
+
+impl Pump<'_> {
+    /// Copy the file at `path` into `out`, returning its CRC-32 and how many bytes it had
+    fn copy(&self, path: &Path, out: &mut impl Write) -> io::Result<(u32, u64)> {
+        let mut source = File::open(path)?;
+        let mut buffer = vec![0u8; 256 * 1024];
+        let (mut crc, mut size) = (Crc::new(), 0);
+        loop {
+            let read = source.read(&mut buffer)?;
+            if read == 0 {
+                return Ok((crc.sum(), size));
+            }
+            out.write_all(&buffer[..read])?;
+            crc.update(&buffer[..read]);
+            size += read as u64;
+            self.throttle.consume(read as u64);
+            self.control.add_bytes_done(read as u64);
+            self.control.wait_while_paused();
+            if self.control.is_cancelled() {
+                return Err(io::Error::other("Cancelled"));
+            }
+        }
+    }
+}
+
+/// Counts what goes through, for offsets into the zip
+struct Counting<W> {
+    inner: W,
+    count: u64,
+}
+
+impl<W: Write> Write for Counting<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Zip with deflated files, sizes in data descriptors after each file, and Unix modes in the external attributes
+/// Symlinks are stored as their target, like `zip -y`
+fn write_zip(out: impl Write, entries: &[ArchiveEntry], pump: &Pump) -> io::Result<()> {
+    let mut out = Counting { inner: out, count: 0 };
+    let mut central = Vec::new();
+    for entry in entries {
+        let offset = out.count;
+        let (method, flags): (u16, u16) = if entry.kind == EntryKind::File { (8, 0x0808) } else { (0, 0x0800) };
+        let (crc, compressed, size) = match &entry.kind {
+            EntryKind::Directory => (0, 0, 0),
+            EntryKind::Symlink(link) => {
+                let mut crc = Crc::new();
+                crc.update(link.as_bytes());
+                (crc.sum(), link.len() as u64, link.len() as u64)
+            }
+            EntryKind::File => (0, 0, 0),  // In the data descriptor
+        };
+        let (time, date) = dos_time(entry.modified);
+        let name = entry.name.as_bytes();
+
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend(ZIP_LOCAL_SIGNATURE.to_le_bytes());
+        header.extend(20u16.to_le_bytes());
+        header.extend(flags.to_le_bytes());
+        header.extend(method.to_le_bytes());
+        header.extend(time.to_le_bytes());
+        header.extend(date.to_le_bytes());
+        header.extend(crc.to_le_bytes());
+        header.extend((compressed as u32).to_le_bytes());
+        header.extend((size as u32).to_le_bytes());
+        header.extend((name.len() as u16).to_le_bytes());
+        header.extend(0u16.to_le_bytes());
+        header.extend(name);
+        out.write_all(&header)?;
+
+        let (crc, compressed, size) = match &entry.kind {
+            EntryKind::File => {
+                let start = out.count;
+                let mut encoder = DeflateEncoder::new(&mut out, Compression::default());
+                let (crc, size) = pump.copy(&entry.path, &mut encoder)?;
+                encoder.finish()?;
+                let compressed = out.count - start;
+                if size > ZIP_LIMIT || compressed > ZIP_LIMIT {
+                    return Err(io::Error::other(format!("{} is too large for a zip archive; use tar.gz", entry.name)));
+                }
+                out.write_all(&ZIP_DESCRIPTOR_SIGNATURE.to_le_bytes())?;
+                out.write_all(&crc.to_le_bytes())?;
+                out.write_all(&(compressed as u32).to_le_bytes())?;
+                out.write_all(&(size as u32).to_le_bytes())?;
+                (crc, compressed, size)
+            }
+            EntryKind::Symlink(link) => {
+                out.write_all(link.as_bytes())?;
+                (crc, compressed, size)
+            }
+            EntryKind::Directory => (crc, compressed, size),
+        };
+        if offset > ZIP_LIMIT {
+            return Err(io::Error::other("Too much data for a zip archive; use tar.gz"));
+        }
+
+        let file_type: u32 = match entry.kind {
+            EntryKind::File => 0o100000,
+            EntryKind::Directory => 0o040000,
+            EntryKind::Symlink(_) => 0o120000,
+        };
+        let dos_directory: u32 = if entry.kind == EntryKind::Directory { 0x10 } else { 0 };
+        central.extend(ZIP_CENTRAL_SIGNATURE.to_le_bytes());
+        central.extend(((3u16 << 8) | 20).to_le_bytes());  // Made on Unix, so the mode is read
+        central.extend(20u16.to_le_bytes());
+        central.extend(flags.to_le_bytes());
+        central.extend(method.to_le_bytes());
+        central.extend(time.to_le_bytes());
+        central.extend(date.to_le_bytes());
+        central.extend(crc.to_le_bytes());
+        central.extend((compressed as u32).to_le_bytes());
+        central.extend((size as u32).to_le_bytes());
+        central.extend((name.len() as u16).to_le_bytes());
+        central.extend([0u8; 8]);  // Extra and comment lengths, disk number, internal attributes
+        central.extend((((file_type | entry.mode) << 16) | dos_directory).to_le_bytes());
+        central.extend((offset as u32).to_le_bytes());
+        central.extend(name);
+    }
+
+    let central_offset = out.count;
+    if entries.len() > u16::MAX as usize || central_offset > ZIP_LIMIT {
+        return Err(io::Error::other("Too many entries for a zip archive; use tar.gz"));
+    }
+    out.write_all(&central)?;
+    let mut end = Vec::with_capacity(22);
+    end.extend(ZIP_EOCD_SIGNATURE.to_le_bytes());
+    end.extend([0u8; 4]);  // Disk numbers
+    end.extend((entries.len() as u16).to_le_bytes());
+    end.extend((entries.len() as u16).to_le_bytes());
+    end.extend((central.len() as u32).to_le_bytes());
+    end.extend((central_offset as u32).to_le_bytes());
+    end.extend(0u16.to_le_bytes());
+    out.write_all(&end)?;
+    out.flush()
+}
+
+/// MS-DOS time and date of Unix seconds, in UTC; the format starts in 1980
+fn dos_time(seconds: u64) -> (u16, u16) {
+    let days = seconds / 86400;
+    let secs = seconds % 86400;
+    // Civil date from days since 1970 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    if year < 1980 {
+        return (0, (1 << 5) | 1);
+    }
+    let time = ((secs / 3600) << 11) | ((secs % 3600 / 60) << 5) | ((secs % 60) / 2);
+    let date = (((year - 1980) as u64) << 9) | ((month as u64) << 5) | day as u64;
+    (time as u16, date as u16)
+}
+
+/// POSIX tar, gzip-compressed; names over 100 bytes get a GNU long name record
+fn write_tar_gz(out: impl Write, entries: &[ArchiveEntry], pump: &Pump) -> io::Result<()> {
+    let mut out = GzEncoder::new(out, Compression::default());
+    for entry in entries {
+        let link = match &entry.kind {
+            EntryKind::Symlink(link) => link.as_str(),
+            _ => "",
+        };
+        if entry.name.len() > 100 {
+            write_long_record(&mut out, b'L', &entry.name)?;
+        }
+        if link.len() > 100 {
+            write_long_record(&mut out, b'K', link)?;
+        }
+        let typeflag = match entry.kind {
+            EntryKind::File => b'0',
+            EntryKind::Directory => b'5',
+            EntryKind::Symlink(_) => b'2',
+        };
+        out.write_all(&tar_header(&entry.name, entry.mode, entry.size, entry.modified, typeflag, link))?;
+        if entry.kind == EntryKind::File {
+            let (_, size) = pump.copy(&entry.path, &mut out)?;
+            // A file that changed size meanwhile would break every header after it
+            if size != entry.size {
+                return Err(io::Error::other(format!("{} changed while it was packed", entry.name)));
+            }
+            out.write_all(&vec![0u8; padding(size)])?;
+        }
+    }
+    // Two empty blocks end the archive
+    out.write_all(&[0u8; 1024])?;
+    out.finish()?.flush()
+}
+
+/// A GNU `././@LongLink` record holding a name or link target too long for the header
+fn write_long_record(out: &mut impl Write, typeflag: u8, text: &str) -> io::Result<()> {
+    let size = text.len() as u64 + 1;
+    out.write_all(&tar_header("././@LongLink", 0o644, size, 0, typeflag, ""))?;
+    out.write_all(text.as_bytes())?;
+    out.write_all(&vec![0u8; 1 + padding(size)])
+}
+
+/// Zeros after `size` bytes of data up to the next 512-byte block
+fn padding(size: u64) -> usize {
+    ((512 - size % 512) % 512) as usize
+}
+
+fn tar_header(name: &str, mode: u32, size: u64, modified: u64, typeflag: u8, link: &str) -> [u8; 512] {
+    let mut header = [0u8; 512];
+    let mut put = |offset: usize, len: usize, bytes: &[u8]| {
+        let len = bytes.len().min(len);
+        header[offset..offset + len].copy_from_slice(&bytes[..len]);
+    };
+    let octal = |value: u64, width: usize| format!("{:0width$o}\0", value, width = width - 1);
+    put(0, 100, name.as_bytes());
+    put(100, 8, octal(mode as u64, 8).as_bytes());
+    put(108, 8, octal(0, 8).as_bytes());
+    put(116, 8, octal(0, 8).as_bytes());
+    put(124, 12, octal(size, 12).as_bytes());
+    put(136, 12, octal(modified, 12).as_bytes());
+    put(148, 8, b"        ");
+    put(156, 1, &[typeflag]);
+    put(157, 100, link.as_bytes());
+    put(257, 8, b"ustar\x0000");
+    let checksum: u32 = header.iter().map(|&byte| byte as u32).sum();
+    header[148..156].copy_from_slice(format!("{:06o}\0 ", checksum).as_bytes());
+    header
+}
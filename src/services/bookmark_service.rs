@@ -0,0 +1,117 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use crate::core::{ClazyfilerError, Result};
+
+/// Keys the bookmarks popup takes before looking up a bookmark, with what they do there; none can be set on them
+pub const RESERVED_KEYS: &[(char, &str)] = &[('v', "moving entries"), ('q', "closing")];
+
+/// A directory saved to jump back to, by name and optionally by a one-character key
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bookmark {
+    pub name: String,
+    pub path: PathBuf,
+    pub key: Option<char>,
+}
+
+/// Service keeping the bookmarks set while browsing (`m` then a key, or `:bookmark <name>`)
+/// Stored as `key\tname\tpath` lines in `bookmarks.tsv` next to the config file, the key empty for
+/// bookmarks that only have a name; those under `[bookmarks]` in the config stay where they are
+#[derive(Debug)]
+pub struct BookmarkService {
+    file_path: Option<PathBuf>,
+    bookmarks: Vec<Bookmark>,  // In the order they were set
+}
+
+impl BookmarkService {
+    /// Load the bookmarks saved in `config_dir`
+    pub fn new(config_dir: Option<PathBuf>) -> Self {
+        let file_path = config_dir.map(|dir| dir.join("bookmarks.tsv"));
+        let bookmarks = file_path.as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|text| text.lines().filter_map(parse_line).collect())
+            .unwrap_or_default();
+        Self { file_path, bookmarks }
+    }
+
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    /// The bookmark on `key`
+    pub fn get(&self, key: char) -> Option<&Bookmark> {
+        self.bookmarks.iter().find(|bookmark| bookmark.key == Some(key))
+    }
+
+    /// Put `path` on `key`, replacing what was there; named after the directory
+    pub fn set(&mut self, key: char, path: &Path) -> Result<()> {
+        if key.is_whitespace() || key.is_control() {
+            return Err(ClazyfilerError::validation(&key.to_string(), "Not a key a bookmark can be on"));
+        }
+        if let Some((_, use_of_key)) = RESERVED_KEYS.iter().find(|(reserved, _)| *reserved == key) {
+            let message = format!("Taken by {} in the bookmarks popup", use_of_key);
+            return Err(ClazyfilerError::validation(&key.to_string(), &message));
+        }
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_else(|| path.display().to_string());
+        self.bookmarks.retain(|bookmark| bookmark.key != Some(key));
+        self.bookmarks.push(Bookmark { name, path: path.to_path_buf(), key: Some(key) });
+        self.save()
+    }
+
+    /// Save `path` under `name` without a key, replacing a bookmark of the same name
+    pub fn add(&mut self, name: &str, path: &Path) -> Result<()> {
+        if name.contains(['\t', '\n']) {
+            return Err(ClazyfilerError::validation(name, "Bookmark names can't hold tabs or line breaks"));
+        }
+        self.bookmarks.retain(|bookmark| bookmark.name != name || bookmark.key.is_some());
+        self.bookmarks.push(Bookmark { name: name.to_string(), path: path.to_path_buf(), key: None });
+        self.save()
+    }
+
+    /// Forget the bookmark at `index` of the list
+    pub fn remove(&mut self, index: usize) -> Result<Option<Bookmark>> {
+        if index >= self.bookmarks.len() {
+            return Ok(None);
+        }
+        let removed = self.bookmarks.remove(index);
+        self.save()?;
+        Ok(Some(removed))
+    }
+
+    /// Replace the file with the bookmarks in memory (written to a temp file, then renamed over)
+    fn save(&self) -> Result<()> {
+        let Some(file_path) = &self.file_path else { return Ok(()) };
+        let path_str = file_path.to_string_lossy();
+        let text: String = self.bookmarks.iter()
+            .filter_map(|bookmark| {
+                let key = bookmark.key.map(String::from).unwrap_or_default();
+                Some(format!("{}\t{}\t{}\n", key, bookmark.name, bookmark.path.to_str()?))
+            })
+            .collect();
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| ClazyfilerError::file_system("create_dir", &path_str, e))?;
+        }
+        let temp = file_path.with_extension("tmp");
+        let written = fs::File::create(&temp).and_then(|mut file| file.write_all(text.as_bytes()));
+        if let Err(e) = written.and_then(|_| fs::rename(&temp, file_path)) {
+            let _ = fs::remove_file(&temp);
+            return Err(ClazyfilerError::file_system("write", &path_str, e));
+        }
+        Ok(())
+    }
+}
+
+/// `d\tDocuments\t/home/me/Documents`, or `\tnotes\t/home/me/notes` without a key
+fn parse_line(line: &str) -> Option<Bookmark> {
+    let mut fields = line.splitn(3, '\t');
+    let key = fields.next()?;
+    let name = fields.next()?;
+    let path = fields.next().filter(|path| !path.is_empty())?;
+    let mut chars = key.chars();
+    let key = match (chars.next(), chars.next()) {
+        (None, _) => None,
+        (Some(key), None) => Some(key),
+        _ => return None,
+    };
+    Some(Bookmark { name: name.to_string(), path: PathBuf::from(path), key })
+}
@@ -0,0 +1,193 @@
+#[cfg(unix)]
+use std::time::Duration;
+
+/// Terminals are queried once at startup; replies normally take a few milliseconds, but ssh adds latency
+#[cfg(unix)]
+const PROBE_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// How many colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    Basic,       // The 16 ANSI colors
+    Indexed256,  // The xterm 256-color palette
+    TrueColor,   // 24-bit RGB
+}
+
+impl ColorDepth {
+    pub fn label(self) -> &'static str {
+        match self {
+            ColorDepth::Basic => "16 colors",
+            ColorDepth::Indexed256 => "256 colors",
+            ColorDepth::TrueColor => "truecolor",
+        }
+    }
+}
+
+/// Ways of drawing real pixels, used for thumbnails instead of block art
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Sixel,
+}
+
+impl GraphicsProtocol {
+    pub fn label(self) -> &'static str {
+        match self {
+            GraphicsProtocol::Kitty => "kitty graphics",
+            GraphicsProtocol::Sixel => "sixel",
+        }
+    }
+}
+
+/// What the terminal supports, so UI modules can degrade gracefully
+#[derive(Debug, Clone)]
+pub struct TerminalCapabilities {
+    pub colors: ColorDepth,
+    pub kitty_graphics: bool,
+    pub sixel: bool,
+    pub osc52: bool,               // Accepts clipboard writes through escape sequences
+    pub wide_emoji: Option<bool>,  // Whether emoji take two columns, as the layout assumes; None if not measured
+    pub answered: bool,            // Whether the terminal replied to the startup queries at all
+}
+
+impl TerminalCapabilities {
+    /// Protocol thumbnails are drawn with; kitty is preferred as its images sit apart from the text
+    pub fn graphics(&self) -> Option<GraphicsProtocol> {
+        if self.kitty_graphics {
+            Some(GraphicsProtocol::Kitty)
+        } else if self.sixel {
+            Some(GraphicsProtocol::Sixel)
+        } else {
+            None
+        }
+    }
+}
+
+/// Service detecting terminal capabilities at startup, from the environment and from query replies
+#[derive(Debug)]
+pub struct CapabilityService {
+    capabilities: TerminalCapabilities,
+}
+
+impl CapabilityService {
+    /// Must run in raw mode and before the event loop starts reading input, which would swallow the replies
+    pub fn new() -> Self {
+        let mut capabilities = from_environment();
+        #[cfg(unix)]
+        unix::probe(&mut capabilities);
+        Self { capabilities }
+    }
+
+    /// Only what the environment tells, without querying the terminal
+    pub fn unprobed() -> Self {
+        Self { capabilities: from_environment() }
+    }
+
+    pub fn capabilities(&self) -> &TerminalCapabilities {
+        &self.capabilities
+    }
+}
+
+/// What the environment tells: COLORTERM, TERM and the variables terminals export about themselves
+fn from_environment() -> TerminalCapabilities {
+    let var = |name: &str| std::env::var(name).unwrap_or_default();
+    let (term, program) = (var("TERM"), var("TERM_PROGRAM"));
+    let known = |terms: &[&str], programs: &[&str]| terms.contains(&term.as_str()) || programs.contains(&program.as_str());
+
+    let kitty = term == "xterm-kitty" || std::env::var_os("KITTY_WINDOW_ID").is_some();
+    let colors = if matches!(var("COLORTERM").as_str(), "truecolor" | "24bit")
+        || term.ends_with("-direct")
+        || kitty
+        || known(&["alacritty", "foot", "xterm-ghostty"], &["iTerm.app", "WezTerm", "vscode", "ghostty"])
+        || std::env::var_os("WT_SESSION").is_some()
+    {
+        ColorDepth::TrueColor
+    } else if term.contains("256color") {
+        ColorDepth::Indexed256
+    } else {
+        ColorDepth::Basic
+    };
+
+    // tmux does not pass kitty graphics through, though it keeps the variables of the terminal around it;
+    // the probe still turns it on if the query is answered
+    let multiplexed = std::env::var_os("TMUX").is_some();
+    TerminalCapabilities {
+        colors,
+        kitty_graphics: !multiplexed && (kitty || known(&["xterm-ghostty"], &["WezTerm", "ghostty"])),
+        sixel: false,
+        osc52: kitty
+            || multiplexed
+            || known(&["alacritty", "foot", "xterm-ghostty"], &["iTerm.app", "WezTerm", "ghostty"]),
+        wide_emoji: None,
+        answered: false,
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::io::{self, Write};
+    use std::time::Instant;
+    use super::{TerminalCapabilities, PROBE_TIMEOUT};
+
+    /// Ask the terminal directly, ending with Primary Device Attributes, which every terminal answers
+    /// - a kitty graphics query (`a=q` only checks support, nothing is shown)
+    /// - an emoji followed by a cursor position report, measuring how wide it was drawn; the line is cleared after
+    /// - DA1, whose attribute 4 means sixel
+    pub fn probe(capabilities: &mut TerminalCapabilities) {
+        if unsafe { libc::isatty(libc::STDIN_FILENO) } != 1 || unsafe { libc::isatty(libc::STDOUT_FILENO) } != 1 {
+            return;
+        }
+        let mut out = io::stdout();
+        let queries = "\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\\r📁\x1b[6n\r\x1b[K\x1b[c";
+        if out.write_all(queries.as_bytes()).and_then(|_| out.flush()).is_err() {
+            return;
+        }
+
+        let Some(replies) = read_until_device_attributes() else { return };
+        capabilities.answered = true;
+        let replies = String::from_utf8_lossy(&replies);
+        if replies.contains("\x1b_Gi=31;OK") {
+            capabilities.kitty_graphics = true;
+        }
+        if let Some(column) = cursor_column(&replies) {
+            capabilities.wide_emoji = Some(column == 3);
+        }
+        if let Some(attributes) = replies.rsplit("\x1b[?").next().and_then(|reply| reply.strip_suffix('c')) {
+            capabilities.sixel = attributes.split(';').any(|attribute| attribute == "4");
+        }
+    }
+
+    /// Everything the terminal sent up to and including its DA1 reply (`ESC [ ? ... c`); None on timeout
+    fn read_until_device_attributes() -> Option<Vec<u8>> {
+        let started = Instant::now();
+        let mut replies = Vec::new();
+        loop {
+            let remaining = PROBE_TIMEOUT.checked_sub(started.elapsed())?;
+            let mut fd = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+            if unsafe { libc::poll(&mut fd, 1, remaining.as_millis() as libc::c_int) } <= 0 {
+                return None;
+            }
+            let mut buffer = [0u8; 256];
+            let read = unsafe { libc::read(libc::STDIN_FILENO, buffer.as_mut_ptr().cast(), buffer.len()) };
+            if read <= 0 {
+                return None;
+            }
+            replies.extend_from_slice(&buffer[..read as usize]);
+            if let Some(start) = replies.windows(3).rposition(|window| window == b"\x1b[?") {
+                if replies[start..].ends_with(b"c") {
+                    return Some(replies);
+                }
+            }
+        }
+    }
+
+    /// Column of a cursor position report `ESC [ row ; column R`
+    fn cursor_column(replies: &str) -> Option<u16> {
+        replies.split("\x1b[").find_map(|reply| {
+            let (row, rest) = reply.split_once(';')?;
+            let (column, _) = rest.split_once('R')?;
+            row.parse::<u16>().ok()?;
+            column.parse().ok()
+        })
+    }
+}
@@ -0,0 +1,124 @@
+use std::io::Cursor;
+use std::process::{Command, Stdio};
+use crate::core::{ClazyfilerError, Result};
+
+/// Command-line clipboard readers, tried in order when their session is available
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ClipboardTool {
+    WlPaste,     // Wayland
+    Xclip,       // X11
+    Pngpaste,    // macOS
+}
+
+impl ClipboardTool {
+    fn command(&self) -> &'static str {
+        match self {
+            ClipboardTool::WlPaste => "wl-paste",
+            ClipboardTool::Xclip => "xclip",
+            ClipboardTool::Pngpaste => "pngpaste",
+        }
+    }
+
+    /// Tools worth trying in the current session
+    fn candidates() -> Vec<ClipboardTool> {
+        let mut tools = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            tools.push(ClipboardTool::WlPaste);
+        }
+        if std::env::var_os("DISPLAY").is_some() {
+            tools.push(ClipboardTool::Xclip);
+        }
+        if cfg!(target_os = "macos") {
+            tools.push(ClipboardTool::Pngpaste);
+        }
+        tools
+    }
+}
+
+/// Service responsible for reading images from the system clipboard
+/// Shells out to wl-paste, xclip or pngpaste; other image types are converted to PNG
+#[derive(Debug)]
+pub struct ClipboardService;
+
+impl ClipboardService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Image currently on the clipboard, encoded as PNG
+    pub fn read_image(&self) -> Result<Vec<u8>> {
+        let tools = ClipboardTool::candidates();
+        if tools.is_empty() {
+            return Err(ClazyfilerError::external_tool("clipboard", "No graphical session to read the clipboard from"));
+        }
+
+        let mut last_error = None;
+        for tool in tools {
+            match Self::read_with(tool) {
+                Ok(Some(data)) => return Self::as_png(tool, data),
+                Ok(None) => return Err(ClazyfilerError::external_tool(tool.command(), "Clipboard does not contain an image")),
+                // Tool missing or failing: fall through to the next one
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| ClazyfilerError::external_tool("clipboard", "No clipboard tool available")))
+    }
+
+    /// Raw image bytes from one tool; Ok(None) if the clipboard holds no image
+    fn read_with(tool: ClipboardTool) -> Result<Option<Vec<u8>>> {
+        let mime = match tool {
+            ClipboardTool::WlPaste => Self::pick_image_type(&Self::run(tool, &["--list-types"])?),
+            ClipboardTool::Xclip => Self::pick_image_type(&Self::run(tool, &["-selection", "clipboard", "-t", "TARGETS", "-o"])?),
+            // pngpaste converts whatever image is there and fails if there is none
+            ClipboardTool::Pngpaste => return Ok(Self::run(tool, &["-"]).ok().filter(|data| !data.is_empty())),
+        };
+        let Some(mime) = mime else {
+            return Ok(None);
+        };
+
+        let data = match tool {
+            ClipboardTool::WlPaste => Self::run(tool, &["--no-newline", "--type", &mime])?,
+            _ => Self::run(tool, &["-selection", "clipboard", "-t", &mime, "-o"])?,
+        };
+        Ok(Some(data).filter(|data| !data.is_empty()))
+    }
+
+    /// Prefer PNG among the offered types, then any other image type
+    fn pick_image_type(listing: &[u8]) -> Option<String> {
+        let listing = String::from_utf8_lossy(listing);
+        let types: Vec<&str> = listing.lines().map(str::trim).collect();
+        types
+            .iter()
+            .find(|mime| **mime == "image/png")
+            .or_else(|| types.iter().find(|mime| mime.starts_with("image/")))
+            .map(|mime| mime.to_string())
+    }
+
+    fn run(tool: ClipboardTool, args: &[&str]) -> Result<Vec<u8>> {
+        let output = Command::new(tool.command())
+            .args(args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .map_err(|e| ClazyfilerError::external_tool(tool.command(), &format!("Failed to launch: {}", e)))?;
+        if output.status.success() {
+            Ok(output.stdout)
+        } else {
+            Err(ClazyfilerError::external_tool(tool.command(), &format!("Exited with status: {}", output.status)))
+        }
+    }
+
+    /// Pass PNG through untouched, re-encode anything else the image crate can decode
+    fn as_png(tool: ClipboardTool, data: Vec<u8>) -> Result<Vec<u8>> {
+        if image::guess_format(&data).ok() == Some(image::ImageFormat::Png) {
+            return Ok(data);
+        }
+        let image = image::load_from_memory(&data)
+            .map_err(|e| ClazyfilerError::external_tool(tool.command(), &format!("Unsupported clipboard image: {}", e)))?;
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .map_err(|e| ClazyfilerError::external_tool(tool.command(), &format!("PNG encoding failed: {}", e)))?;
+        Ok(png)
+    }
+}
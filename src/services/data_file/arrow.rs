@@ -0,0 +1,505 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use crate::core::{ClazyfilerError, Result};
+use super::flatbuffers::{Table, Vector};
+use super::{DataSample, DataSummary, SAMPLE_ROWS};
+
+/// Arrow IPC files (and Feather v2) start and end with this magic
+const MAGIC: &[u8] = b"ARROW1";
+const FEATHER_V1_MAGIC: &[u8] = b"FEA1";
+
+/// Metadata and record batch bodies above these sizes are skipped
+const MAX_FOOTER_LEN: u64 = 64 * 1024 * 1024;
+const MAX_BODY_LEN: u64 = 64 * 1024 * 1024;
+
+/// Record batches inspected when counting rows
+const MAX_COUNTED_BATCHES: usize = 10_000;
+
+/// Size of the Block struct in the footer: offset i64, metaDataLength i32 (+ padding), bodyLength i64
+const BLOCK_SIZE: usize = 24;
+
+/// `Type` union discriminants from Schema.fbs
+mod type_id {
+    pub const NULL: u8 = 1;
+    pub const INT: u8 = 2;
+    pub const FLOATING_POINT: u8 = 3;
+    pub const BINARY: u8 = 4;
+    pub const UTF8: u8 = 5;
+    pub const BOOL: u8 = 6;
+    pub const DECIMAL: u8 = 7;
+    pub const DATE: u8 = 8;
+    pub const TIME: u8 = 9;
+    pub const TIMESTAMP: u8 = 10;
+    pub const INTERVAL: u8 = 11;
+    pub const LIST: u8 = 12;
+    pub const STRUCT: u8 = 13;
+    pub const UNION: u8 = 14;
+    pub const FIXED_SIZE_BINARY: u8 = 15;
+    pub const FIXED_SIZE_LIST: u8 = 16;
+    pub const MAP: u8 = 17;
+    pub const DURATION: u8 = 18;
+    pub const LARGE_BINARY: u8 = 19;
+    pub const LARGE_UTF8: u8 = 20;
+    pub const LARGE_LIST: u8 = 21;
+    pub const RUN_END_ENCODED: u8 = 22;
+}
+
+/// `MessageHeader` discriminant for record batches
+const RECORD_BATCH_HEADER: u8 = 3;
+
+const TIME_UNITS: &[&str] = &["s", "ms", "us", "ns"];
+
+#[derive(Debug, Clone, Copy)]
+struct Block {
+    offset: u64,
+    metadata_len: u64,
+    body_len: u64,
+}
+
+/// Read schema, row count and a few sample rows from an Arrow IPC / Feather v2 file
+/// `path` only labels errors, so in-memory data can be summarized too
+pub fn read_summary<R: Read + Seek>(mut file: R, path: &Path) -> Result<DataSummary> {
+    let io_error = |e| ClazyfilerError::file_system("read_arrow", path.to_string_lossy().as_ref(), e);
+    let file_len = file.seek(SeekFrom::End(0)).map_err(io_error)?;
+    file.seek(SeekFrom::Start(0)).map_err(io_error)?;
+
+    let mut head = [0u8; 6];
+    file.read_exact(&mut head).map_err(|_| invalid(path, "File too small to be Arrow"))?;
+    if head.starts_with(FEATHER_V1_MAGIC) {
+        return Err(invalid(path, "Feather v1 files are not supported"));
+    }
+    if head != MAGIC || file_len < 18 {
+        return Err(invalid(path, "Missing ARROW1 magic (streaming format files have no footer)"));
+    }
+
+    // File layout ends with: footer flatbuffer, i32 footer length, "ARROW1"
+    let mut tail = [0u8; 10];
+    file.seek(SeekFrom::End(-10)).map_err(io_error)?;
+    file.read_exact(&mut tail).map_err(io_error)?;
+    if &tail[4..] != MAGIC {
+        return Err(invalid(path, "Missing ARROW1 footer magic"));
+    }
+    let footer_len = i32::from_le_bytes([tail[0], tail[1], tail[2], tail[3]]).max(0) as u64;
+    if footer_len + 10 > file_len || footer_len > MAX_FOOTER_LEN {
+        return Err(invalid(path, "Footer length out of range"));
+    }
+    let mut footer_buf = vec![0u8; footer_len as usize];
+    file.seek(SeekFrom::End(-10 - footer_len as i64)).map_err(io_error)?;
+    file.read_exact(&mut footer_buf).map_err(io_error)?;
+
+    // Footer: 0 version, 1 schema, 2 dictionaries, 3 recordBatches
+    let footer = Table::root(&footer_buf).ok_or_else(|| invalid(path, "Malformed footer"))?;
+    let schema_table = footer.table(1).ok_or_else(|| invalid(path, "Footer has no schema"))?;
+    let fields = read_fields(schema_table.vector(1), 0);
+    let batches = footer.vector(3).map(read_blocks).unwrap_or_default();
+
+    let mut schema = Vec::new();
+    for field in &fields {
+        render_field(field, 0, &mut schema);
+    }
+
+    // Row counts live in each record batch message, not in the footer
+    let mut row_count = Some(0u64);
+    for block in batches.iter().take(MAX_COUNTED_BATCHES) {
+        match read_batch_metadata(&mut file, block).and_then(|metadata| batch_length(&metadata)) {
+            Some(rows) => row_count = row_count.map(|count| count + rows),
+            None => {
+                row_count = None;
+                break;
+            }
+        }
+    }
+    if batches.len() > MAX_COUNTED_BATCHES {
+        row_count = None;
+    }
+
+    let mut details = vec![
+        ("Columns".to_string(), fields.len().to_string()),
+        ("Record batches".to_string(), batches.len().to_string()),
+    ];
+    if let Some(dictionaries) = footer.vector(2).filter(|dictionaries| dictionaries.len() > 0) {
+        details.push(("Dictionaries".to_string(), dictionaries.len().to_string()));
+    }
+
+    let big_endian = schema_table.i16(0, 0) == 1;
+    let (sample, sample_note) = match batches.first() {
+        None => (None, None),
+        Some(_) if big_endian => (None, Some("Big-endian data is not decoded".to_string())),
+        Some(block) => match read_sample(&mut file, block, &fields) {
+            Ok(sample) => (Some(sample), None),
+            Err(note) => (None, Some(note)),
+        },
+    };
+
+    Ok(DataSummary {
+        format: "Arrow IPC",
+        row_count,
+        details,
+        schema,
+        sample,
+        sample_note,
+    })
+}
+
+/// Schema field with the parts needed for display and decoding
+#[derive(Debug, Clone)]
+struct Field {
+    name: String,
+    nullable: bool,
+    type_id: u8,
+    type_name: String,
+    int_width: u32,
+    signed: bool,
+    float_precision: i16,
+    time_unit: i16,
+    dictionary_encoded: bool,
+    children: Vec<Field>,
+}
+
+fn read_fields(vector: Option<Vector>, depth: usize) -> Vec<Field> {
+    let Some(vector) = vector else { return Vec::new() };
+    if depth > 32 {
+        return Vec::new();
+    }
+    (0..vector.len()).filter_map(|index| vector.table(index)).map(|table| read_field(table, depth)).collect()
+}
+
+/// Field: 0 name, 1 nullable, 2 type_type, 3 type, 4 dictionary, 5 children
+fn read_field(table: Table, depth: usize) -> Field {
+    let type_id = table.u8(2, 0);
+    let type_table = table.table(3);
+    let children = read_fields(table.vector(5), depth + 1);
+
+    let int_width = type_table.map(|t| t.i32(0, 0).max(0) as u32).unwrap_or(0);
+    let signed = type_table.map(|t| t.bool(1, false)).unwrap_or(false);
+    let float_precision = type_table.map(|t| t.i16(0, 0)).unwrap_or(0);
+    // Timestamp units default to seconds, Date/Time/Duration units to milliseconds
+    let default_unit = if type_id == type_id::TIMESTAMP { 0 } else { 1 };
+    let time_unit = type_table.map(|t| t.i16(0, default_unit)).unwrap_or(default_unit);
+    let unit_name = TIME_UNITS.get(time_unit as usize).copied().unwrap_or("?");
+
+    let child_type = |index: usize| {
+        children
+            .get(index)
+            .map(|child: &Field| child.type_name.clone())
+            .unwrap_or_else(|| "?".to_string())
+    };
+    let type_name = match type_id {
+        type_id::NULL => "null".to_string(),
+        type_id::INT => format!("{}int{}", if signed { "" } else { "u" }, int_width),
+        type_id::FLOATING_POINT => match float_precision {
+            0 => "float16".to_string(),
+            1 => "float32".to_string(),
+            _ => "float64".to_string(),
+        },
+        type_id::BINARY => "binary".to_string(),
+        type_id::UTF8 => "utf8".to_string(),
+        type_id::BOOL => "bool".to_string(),
+        type_id::DECIMAL => {
+            let precision = type_table.map(|t| t.i32(0, 0)).unwrap_or(0);
+            let scale = type_table.map(|t| t.i32(1, 0)).unwrap_or(0);
+            format!("decimal({},{})", precision, scale)
+        }
+        type_id::DATE => if time_unit == 0 { "date32".to_string() } else { "date64".to_string() },
+        type_id::TIME => format!("time{}[{}]", type_table.map(|t| t.i32(1, 32)).unwrap_or(32), unit_name),
+        type_id::TIMESTAMP => match type_table.and_then(|t| t.string(1)) {
+            Some(timezone) => format!("timestamp[{}, {}]", unit_name, timezone),
+            None => format!("timestamp[{}]", unit_name),
+        },
+        type_id::INTERVAL => "interval".to_string(),
+        type_id::LIST => format!("list<{}>", child_type(0)),
+        type_id::STRUCT => "struct".to_string(),
+        type_id::UNION => "union".to_string(),
+        type_id::FIXED_SIZE_BINARY => format!("fixed_size_binary[{}]", type_table.map(|t| t.i32(0, 0)).unwrap_or(0)),
+        type_id::FIXED_SIZE_LIST => format!("fixed_size_list<{}>[{}]", child_type(0), type_table.map(|t| t.i32(0, 0)).unwrap_or(0)),
+        type_id::MAP => "map".to_string(),
+        type_id::DURATION => format!("duration[{}]", unit_name),
+        type_id::LARGE_BINARY => "large_binary".to_string(),
+        type_id::LARGE_UTF8 => "large_utf8".to_string(),
+        type_id::LARGE_LIST => format!("large_list<{}>", child_type(0)),
+        type_id::RUN_END_ENCODED => "run_end_encoded".to_string(),
+        _ => format!("type#{}", type_id),
+    };
+    let dictionary_encoded = table.table(4).is_some();
+    let type_name = if dictionary_encoded { format!("dictionary<{}>", type_name) } else { type_name };
+
+    Field {
+        name: table.string(0).unwrap_or("").to_string(),
+        nullable: table.bool(1, false),
+        type_id,
+        type_name,
+        int_width,
+        signed,
+        float_precision,
+        time_unit,
+        dictionary_encoded,
+        children,
+    }
+}
+
+fn render_field(field: &Field, depth: usize, lines: &mut Vec<String>) {
+    let nullability = if field.nullable { "" } else { " not null" };
+    lines.push(format!("{}{}: {}{}", "│ ".repeat(depth), field.name, field.type_name, nullability));
+    // List item types are already part of the type name
+    if matches!(field.type_id, type_id::STRUCT | type_id::MAP | type_id::UNION) {
+        for child in &field.children {
+            render_field(child, depth + 1, lines);
+        }
+    }
+}
+
+fn read_blocks(vector: Vector) -> Vec<Block> {
+    (0..vector.len())
+        .filter_map(|index| {
+            let bytes = vector.struct_bytes(index, BLOCK_SIZE)?;
+            let offset = i64::from_le_bytes(bytes[0..8].try_into().ok()?);
+            let metadata_len = i32::from_le_bytes(bytes[8..12].try_into().ok()?);
+            let body_len = i64::from_le_bytes(bytes[16..24].try_into().ok()?);
+            Some(Block {
+                offset: u64::try_from(offset).ok()?,
+                metadata_len: u64::try_from(metadata_len).ok()?,
+                body_len: u64::try_from(body_len).ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Read the flatbuffer of the message a block points at, without its length prefix
+fn read_batch_metadata<R: Read + Seek>(file: &mut R, block: &Block) -> Option<Vec<u8>> {
+    if block.metadata_len < 8 || block.metadata_len > MAX_FOOTER_LEN {
+        return None;
+    }
+    let mut buf = vec![0u8; block.metadata_len as usize];
+    file.seek(SeekFrom::Start(block.offset)).ok()?;
+    file.read_exact(&mut buf).ok()?;
+
+    // Newer writers prefix the length with a 0xFFFFFFFF continuation marker
+    let skip = if buf[0..4] == [0xff; 4] { 8 } else { 4 };
+    Some(buf.split_off(skip))
+}
+
+/// Message: 1 header_type, 2 header; RecordBatch: 0 length
+fn record_batch(metadata: &[u8]) -> Option<Table<'_>> {
+    let message = Table::root(metadata)?;
+    if message.u8(1, 0) != RECORD_BATCH_HEADER {
+        return None;
+    }
+    message.table(2)
+}
+
+fn batch_length(metadata: &[u8]) -> Option<u64> {
+    u64::try_from(record_batch(metadata)?.i64(0, 0)).ok()
+}
+
+/// Decode the first rows of the first record batch; the error is a note for the preview
+fn read_sample<R: Read + Seek>(file: &mut R, block: &Block, fields: &[Field]) -> std::result::Result<DataSample, String> {
+    let unreadable = || "Record batch could not be read".to_string();
+    let metadata = read_batch_metadata(file, block).ok_or_else(unreadable)?;
+    let batch = record_batch(&metadata).ok_or_else(unreadable)?;
+    // RecordBatch: 0 length, 1 nodes, 2 buffers, 3 compression
+    if batch.table(3).is_some() {
+        return Err("Record batches are compressed - sample not decoded".to_string());
+    }
+    if block.body_len > MAX_BODY_LEN {
+        return Err("Record batch too large to sample".to_string());
+    }
+
+    let mut body = vec![0u8; block.body_len as usize];
+    file.seek(SeekFrom::Start(block.offset + block.metadata_len)).map_err(|_| unreadable())?;
+    file.read_exact(&mut body).map_err(|_| unreadable())?;
+
+    let rows = (batch.i64(0, 0).max(0) as usize).min(SAMPLE_ROWS);
+    let mut cursor = BatchCursor {
+        nodes: batch.vector(1).ok_or_else(unreadable)?,
+        buffers: batch.vector(2).ok_or_else(unreadable)?,
+        body: &body,
+        node: 0,
+        buffer: 0,
+    };
+
+    let mut columns = Vec::new();
+    let mut cells: Vec<Vec<String>> = vec![Vec::new(); rows];
+    for field in fields {
+        // Once a column with an unknown layout is hit the buffer positions of later columns are unknown too
+        let Some(values) = cursor.decode(field, rows) else { break };
+        columns.push(field.name.clone());
+        for (row, value) in cells.iter_mut().zip(values) {
+            row.push(value);
+        }
+    }
+    if columns.is_empty() && !fields.is_empty() {
+        return Err("Column types not supported for sampling".to_string());
+    }
+
+    Ok(DataSample { columns, rows: cells })
+}
+
+/// Walks the FieldNode and Buffer lists of a record batch in schema order
+struct BatchCursor<'a> {
+    nodes: Vector<'a>,
+    buffers: Vector<'a>,
+    body: &'a [u8],
+    node: usize,
+    buffer: usize,
+}
+
+impl<'a> BatchCursor<'a> {
+    fn next_node(&mut self) -> Option<usize> {
+        // FieldNode: length i64, null_count i64
+        let bytes = self.nodes.struct_bytes(self.node, 16)?;
+        self.node += 1;
+        usize::try_from(i64::from_le_bytes(bytes[0..8].try_into().ok()?)).ok()
+    }
+
+    fn next_buffer(&mut self) -> Option<&'a [u8]> {
+        // Buffer: offset i64, length i64, relative to the body
+        let bytes = self.buffers.struct_bytes(self.buffer, 16)?;
+        self.buffer += 1;
+        let offset = usize::try_from(i64::from_le_bytes(bytes[0..8].try_into().ok()?)).ok()?;
+        let len = usize::try_from(i64::from_le_bytes(bytes[8..16].try_into().ok()?)).ok()?;
+        self.body.get(offset..offset.checked_add(len)?)
+    }
+
+    /// Decode `rows` values of a column, or placeholders for types that are only skipped
+    fn decode(&mut self, field: &Field, rows: usize) -> Option<Vec<String>> {
+        let length = self.next_node()?;
+        let rows = rows.min(length);
+
+        // Types without their own buffers
+        match field.type_id {
+            type_id::NULL => return Some(vec!["null".to_string(); rows]),
+            type_id::RUN_END_ENCODED => {
+                self.skip_children(field)?;
+                return Some(vec!["…".to_string(); rows]);
+            }
+            type_id::UNION => return None,
+            _ => {}
+        }
+
+        let validity = self.next_buffer()?;
+        let is_valid = |row: usize| validity.is_empty() || validity.get(row / 8).is_some_and(|byte| byte & (1 << (row % 8)) != 0);
+        let format_rows = |format: &dyn Fn(usize) -> Option<String>| -> Vec<String> {
+            (0..rows)
+                .map(|row| if is_valid(row) { format(row).unwrap_or_else(|| "?".to_string()) } else { "null".to_string() })
+                .collect()
+        };
+
+        if field.dictionary_encoded {
+            // Dictionary indices are plain integers; the values live in separate dictionary batches
+            self.next_buffer()?;
+            return Some(format_rows(&|_| Some("…".to_string())));
+        }
+
+        let values = match field.type_id {
+            type_id::INT => {
+                let data = self.next_buffer()?;
+                let width = (field.int_width / 8) as usize;
+                format_rows(&|row| read_int(data, row, width, field.signed).map(|value| value.to_string()))
+            }
+            type_id::FLOATING_POINT => {
+                let data = self.next_buffer()?;
+                match field.float_precision {
+                    1 => format_rows(&|row| read_fixed::<4>(data, row).map(|bytes| f32::from_le_bytes(bytes).to_string())),
+                    2 => format_rows(&|row| read_fixed::<8>(data, row).map(|bytes| f64::from_le_bytes(bytes).to_string())),
+                    _ => format_rows(&|_| Some("…".to_string())),
+                }
+            }
+            type_id::BOOL => {
+                let data = self.next_buffer()?;
+                format_rows(&|row| data.get(row / 8).map(|byte| (byte & (1 << (row % 8)) != 0).to_string()))
+            }
+            type_id::UTF8 | type_id::BINARY | type_id::LARGE_UTF8 | type_id::LARGE_BINARY => {
+                let offsets = self.next_buffer()?;
+                let data = self.next_buffer()?;
+                let large = matches!(field.type_id, type_id::LARGE_UTF8 | type_id::LARGE_BINARY);
+                let width = if large { 8 } else { 4 };
+                let text = matches!(field.type_id, type_id::UTF8 | type_id::LARGE_UTF8);
+                format_rows(&|row| {
+                    let start = usize::try_from(read_int(offsets, row, width, true)?).ok()?;
+                    let end = usize::try_from(read_int(offsets, row + 1, width, true)?).ok()?;
+                    let bytes = data.get(start..end)?;
+                    Some(if text {
+                        String::from_utf8_lossy(bytes).to_string()
+                    } else {
+                        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+                    })
+                })
+            }
+            type_id::DATE => {
+                let data = self.next_buffer()?;
+                if field.time_unit == 0 {
+                    format_rows(&|row| {
+                        let days = i64::try_from(read_int(data, row, 4, true)?).ok()?;
+                        Some(chrono::DateTime::from_timestamp(days * 86_400, 0)?.format("%Y-%m-%d").to_string())
+                    })
+                } else {
+                    format_rows(&|row| {
+                        let millis = i64::try_from(read_int(data, row, 8, true)?).ok()?;
+                        Some(chrono::DateTime::from_timestamp_millis(millis)?.format("%Y-%m-%d").to_string())
+                    })
+                }
+            }
+            type_id::TIMESTAMP => {
+                let data = self.next_buffer()?;
+                let per_second: i64 = match field.time_unit {
+                    0 => 1,
+                    1 => 1_000,
+                    2 => 1_000_000,
+                    _ => 1_000_000_000,
+                };
+                format_rows(&|row| {
+                    let value = i64::try_from(read_int(data, row, 8, true)?).ok()?;
+                    let nanos = (value.rem_euclid(per_second) * (1_000_000_000 / per_second)) as u32;
+                    let timestamp = chrono::DateTime::from_timestamp(value.div_euclid(per_second), nanos)?;
+                    Some(timestamp.naive_utc().to_string())
+                })
+            }
+            type_id::DECIMAL | type_id::TIME | type_id::INTERVAL | type_id::DURATION | type_id::FIXED_SIZE_BINARY => {
+                self.next_buffer()?;
+                format_rows(&|_| Some("…".to_string()))
+            }
+            type_id::LIST | type_id::LARGE_LIST | type_id::MAP => {
+                self.next_buffer()?;
+                self.skip_children(field)?;
+                format_rows(&|_| Some("[…]".to_string()))
+            }
+            type_id::FIXED_SIZE_LIST => {
+                self.skip_children(field)?;
+                format_rows(&|_| Some("[…]".to_string()))
+            }
+            type_id::STRUCT => {
+                self.skip_children(field)?;
+                format_rows(&|_| Some("{…}".to_string()))
+            }
+            _ => return None,
+        };
+        Some(values)
+    }
+
+    fn skip_children(&mut self, field: &Field) -> Option<()> {
+        for child in &field.children {
+            self.decode(child, 0)?;
+        }
+        Some(())
+    }
+}
+
+fn read_fixed<const N: usize>(data: &[u8], row: usize) -> Option<[u8; N]> {
+    let start = row.checked_mul(N)?;
+    data.get(start..start + N)?.try_into().ok()
+}
+
+/// Little-endian integer of `width` bytes at element `row`
+fn read_int(data: &[u8], row: usize, width: usize, signed: bool) -> Option<i128> {
+    match width {
+        1 => read_fixed::<1>(data, row).map(|b| if signed { i8::from_le_bytes(b) as i128 } else { u8::from_le_bytes(b) as i128 }),
+        2 => read_fixed::<2>(data, row).map(|b| if signed { i16::from_le_bytes(b) as i128 } else { u16::from_le_bytes(b) as i128 }),
+        4 => read_fixed::<4>(data, row).map(|b| if signed { i32::from_le_bytes(b) as i128 } else { u32::from_le_bytes(b) as i128 }),
+        8 => read_fixed::<8>(data, row).map(|b| if signed { i64::from_le_bytes(b) as i128 } else { u64::from_le_bytes(b) as i128 }),
+        _ => None,
+    }
+}
+
+fn invalid(path: &Path, message: &str) -> ClazyfilerError {
+    ClazyfilerError::content(path.to_string_lossy().as_ref(), message)
+}
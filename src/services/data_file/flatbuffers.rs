@@ -0,0 +1,141 @@
+/// Minimal read-only flatbuffers accessors, enough to walk Arrow IPC metadata
+/// Every access is bounds checked since the buffers come straight from disk
+
+#[derive(Debug, Clone, Copy)]
+pub struct Table<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Vector<'a> {
+    buf: &'a [u8],
+    start: usize,
+    len: usize,
+}
+
+fn read_u16(buf: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(buf.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(buf.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+fn read_i64(buf: &[u8], pos: usize) -> Option<i64> {
+    Some(i64::from_le_bytes(buf.get(pos..pos + 8)?.try_into().ok()?))
+}
+
+/// Follow a uoffset stored at `pos`
+fn follow(buf: &[u8], pos: usize) -> Option<usize> {
+    pos.checked_add(read_u32(buf, pos)? as usize)
+}
+
+impl<'a> Table<'a> {
+    /// The root table of a finished buffer
+    pub fn root(buf: &'a [u8]) -> Option<Self> {
+        let pos = follow(buf, 0)?;
+        Self::at(buf, pos)
+    }
+
+    fn at(buf: &'a [u8], pos: usize) -> Option<Self> {
+        // The vtable must be readable for this to be a valid table
+        let table = Self { buf, pos };
+        table.vtable()?;
+        Some(table)
+    }
+
+    fn vtable(&self) -> Option<usize> {
+        let soffset = read_u32(self.buf, self.pos)? as i32 as i64;
+        let vtable = self.pos as i64 - soffset;
+        if vtable < 0 {
+            return None;
+        }
+        Some(vtable as usize)
+    }
+
+    /// Absolute position of field `index`, or None when it is absent
+    fn field_pos(&self, index: usize) -> Option<usize> {
+        let vtable = self.vtable()?;
+        let vtable_len = read_u16(self.buf, vtable)? as usize;
+        let entry = 4 + index * 2;
+        if entry + 2 > vtable_len {
+            return None;
+        }
+        match read_u16(self.buf, vtable + entry)? {
+            0 => None,
+            offset => Some(self.pos + offset as usize),
+        }
+    }
+
+    pub fn u8(&self, index: usize, default: u8) -> u8 {
+        self.field_pos(index)
+            .and_then(|pos| self.buf.get(pos).copied())
+            .unwrap_or(default)
+    }
+
+    pub fn bool(&self, index: usize, default: bool) -> bool {
+        self.u8(index, default as u8) != 0
+    }
+
+    pub fn i16(&self, index: usize, default: i16) -> i16 {
+        self.field_pos(index)
+            .and_then(|pos| read_u16(self.buf, pos))
+            .map(|value| value as i16)
+            .unwrap_or(default)
+    }
+
+    pub fn i32(&self, index: usize, default: i32) -> i32 {
+        self.field_pos(index)
+            .and_then(|pos| read_u32(self.buf, pos))
+            .map(|value| value as i32)
+            .unwrap_or(default)
+    }
+
+    pub fn i64(&self, index: usize, default: i64) -> i64 {
+        self.field_pos(index)
+            .and_then(|pos| read_i64(self.buf, pos))
+            .unwrap_or(default)
+    }
+
+    pub fn table(&self, index: usize) -> Option<Table<'a>> {
+        let pos = follow(self.buf, self.field_pos(index)?)?;
+        Table::at(self.buf, pos)
+    }
+
+    pub fn string(&self, index: usize) -> Option<&'a str> {
+        let pos = follow(self.buf, self.field_pos(index)?)?;
+        let len = read_u32(self.buf, pos)? as usize;
+        std::str::from_utf8(self.buf.get(pos + 4..(pos + 4).checked_add(len)?)?).ok()
+    }
+
+    pub fn vector(&self, index: usize) -> Option<Vector<'a>> {
+        let pos = follow(self.buf, self.field_pos(index)?)?;
+        let len = read_u32(self.buf, pos)? as usize;
+        Some(Vector { buf: self.buf, start: pos + 4, len })
+    }
+}
+
+impl<'a> Vector<'a> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Element `index` of a vector of tables
+    pub fn table(&self, index: usize) -> Option<Table<'a>> {
+        if index >= self.len {
+            return None;
+        }
+        let pos = follow(self.buf, self.start + index * 4)?;
+        Table::at(self.buf, pos)
+    }
+
+    /// Raw bytes of element `index` of a vector of fixed-size structs
+    pub fn struct_bytes(&self, index: usize, size: usize) -> Option<&'a [u8]> {
+        if index >= self.len {
+            return None;
+        }
+        let pos = self.start.checked_add(index.checked_mul(size)?)?;
+        self.buf.get(pos..pos.checked_add(size)?)
+    }
+}
@@ -0,0 +1,218 @@
+mod arrow;
+mod flatbuffers;
+mod parquet;
+mod thrift;
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, OnceLock};
+use crate::core::{BudgetedCache, CacheWeight, ClazyfilerError, LruCache, Result};
+
+/// Rows shown in the preview sample
+pub const SAMPLE_ROWS: usize = 5;
+
+/// Columnar data formats with schema previews
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataFormat {
+    Parquet,
+    Arrow,
+}
+
+impl DataFormat {
+    /// Detect the format from a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_string_lossy().to_lowercase();
+        match ext.as_str() {
+            "parquet" | "pq" => Some(DataFormat::Parquet),
+            "arrow" | "feather" | "ipc" => Some(DataFormat::Arrow),
+            _ => None,
+        }
+    }
+}
+
+/// Schema, size and sample rows of a data file
+#[derive(Debug, Clone)]
+pub struct DataSummary {
+    pub format: &'static str,
+    pub row_count: Option<u64>,
+    pub details: Vec<(String, String)>,
+    pub schema: Vec<String>,               // One line per field, nested fields prefixed with guides
+    pub sample: Option<DataSample>,
+    pub sample_note: Option<String>,       // Why no sample is shown, if there is a reason worth telling
+}
+
+impl CacheWeight for DataSummary {
+    fn weight(&self) -> usize {
+        let sample = self.sample.as_ref().map(|sample| sample.columns.weight() + sample.rows.weight()).unwrap_or(0);
+        std::mem::size_of::<Self>()
+            + self.details.iter().map(|(key, value)| key.weight() + value.weight()).sum::<usize>()
+            + self.schema.weight()
+            + sample
+            + self.sample_note.weight()
+    }
+}
+
+/// First rows of a data file as display strings
+#[derive(Debug, Clone)]
+pub struct DataSample {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Summary read on a worker thread: (generation, file, result)
+type SummaryResult = (u64, PathBuf, std::result::Result<DataSummary, String>);
+
+/// Service responsible for Parquet and Arrow/Feather previews
+/// Footers are parsed natively; Parquet sample rows need the optional `duckdb` CLI
+/// since decoding Parquet pages requires a compression codec stack. Both are read on background threads
+#[derive(Debug)]
+pub struct DataFileService {
+    duckdb_available: Arc<OnceLock<bool>>,   // Checked once, by the first worker that needs to know
+    cache: LruCache<PathBuf, std::result::Result<DataSummary, String>>,
+    reading: RefCell<HashSet<PathBuf>>,      // Summaries started and not collected yet
+    summary_sender: Sender<SummaryResult>,
+    summary_receiver: Receiver<SummaryResult>,
+    generation: Cell<u64>,    // Bumped on clear so late results from old threads are dropped
+}
+
+impl DataFileService {
+    pub fn new() -> Self {
+        let (summary_sender, summary_receiver) = channel();
+        Self {
+            duckdb_available: Arc::new(OnceLock::new()),
+            cache: LruCache::new("data files"),
+            reading: RefCell::new(HashSet::new()),
+            summary_sender,
+            summary_receiver,
+            generation: Cell::new(0),
+        }
+    }
+
+    /// Check whether a path looks like a supported data file
+    pub fn is_data_file(path: &Path) -> bool {
+        DataFormat::from_path(path).is_some()
+    }
+
+    /// Summarize a data file in the background on first request, caching the result (or the error message) per path
+    /// None until the summary is collected by `poll`; a duckdb query over a large or remote file may take a while
+    pub fn summary(&self, path: &Path) -> Option<std::result::Result<DataSummary, String>> {
+        if let Some(cached) = self.cache.get(path) {
+            return Some(cached);
+        }
+        if self.reading.borrow_mut().insert(path.to_path_buf()) {
+            let sender = self.summary_sender.clone();
+            let generation = self.generation.get();
+            let duckdb_available = Arc::clone(&self.duckdb_available);
+            let file = path.to_path_buf();
+            std::thread::spawn(move || {
+                let summary = read_summary(&file, &duckdb_available).map_err(|e| e.to_string());
+                // The receiver is gone once the app exits
+                let _ = sender.send((generation, file, summary));
+            });
+        }
+        None
+    }
+
+    /// Whether the summary of this file is being read
+    pub fn is_reading(&self, path: &Path) -> bool {
+        self.reading.borrow().contains(path)
+    }
+
+    /// Collect finished summaries; returns true if any arrived
+    pub fn poll(&self) -> bool {
+        let mut updated = false;
+        while let Ok((generation, file, summary)) = self.summary_receiver.try_recv() {
+            if generation == self.generation.get() {
+                self.reading.borrow_mut().remove(&file);
+                self.cache.insert(file, summary);
+                updated = true;
+            }
+        }
+        updated
+    }
+
+    /// Forget cached summaries (e.g. after a refresh)
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+        self.reading.borrow_mut().clear();
+        self.generation.set(self.generation.get() + 1);
+    }
+
+    /// Caches counted against the memory budget
+    pub fn caches(&self) -> Vec<&dyn BudgetedCache> {
+        vec![&self.cache]
+    }
+
+    /// Summarize data already in memory, without the duckdb sample rows
+    pub fn parse(format: DataFormat, data: &[u8]) -> Result<DataSummary> {
+        let label = Path::new("<memory>");
+        match format {
+            DataFormat::Parquet => parquet::read_summary(Cursor::new(data), label),
+            DataFormat::Arrow => arrow::read_summary(Cursor::new(data), label),
+        }
+    }
+}
+
+/// Footer summary of a data file, with sample rows from duckdb for Parquet
+fn read_summary(path: &Path, duckdb_available: &OnceLock<bool>) -> Result<DataSummary> {
+    let file = File::open(path)
+        .map_err(|e| ClazyfilerError::file_system("open", path.to_string_lossy().as_ref(), e))?;
+    match DataFormat::from_path(path) {
+        Some(DataFormat::Arrow) => arrow::read_summary(file, path),
+        _ => {
+            let mut summary = parquet::read_summary(file, path)?;
+            if *duckdb_available.get_or_init(is_duckdb_installed) {
+                match duckdb_sample(path) {
+                    Some(sample) => summary.sample = Some(sample),
+                    None => summary.sample_note = Some("duckdb could not read sample rows".to_string()),
+                }
+            } else {
+                summary.sample_note = Some("Install duckdb to see sample rows".to_string());
+            }
+            Ok(summary)
+        }
+    }
+}
+
+/// Whether the duckdb CLI is installed
+fn is_duckdb_installed() -> bool {
+    Command::new("duckdb")
+        .arg("-version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+/// Read the first rows of a Parquet file with duckdb
+/// ASCII mode separates columns with 0x1F and rows with 0x1E, so values may contain commas and newlines
+fn duckdb_sample(path: &Path) -> Option<DataSample> {
+    let quoted = path.to_string_lossy().replace('\'', "''");
+    let query = format!("SELECT * FROM read_parquet('{}') LIMIT {}", quoted, SAMPLE_ROWS);
+    let output = Command::new("duckdb")
+        .args(["-ascii", "-header", "-c", &query])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut records = text
+        .split('\u{1e}')
+        .filter(|record| !record.trim().is_empty())
+        .map(|record| record.split('\u{1f}').map(str::to_string).collect::<Vec<_>>());
+    let columns = records.next()?;
+    Some(DataSample {
+        columns,
+        rows: records.collect(),
+    })
+}
@@ -0,0 +1,180 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use crate::core::{ClazyfilerError, Result};
+use super::thrift::{CompactReader, ThriftValue};
+use super::DataSummary;
+
+const MAGIC: &[u8] = b"PAR1";
+const ENCRYPTED_MAGIC: &[u8] = b"PARE";
+
+/// Footers above this size are not worth parsing for a preview
+const MAX_FOOTER_LEN: u64 = 64 * 1024 * 1024;
+
+/// Physical types, indexed by the Thrift enum value
+const PHYSICAL_TYPES: &[&str] = &[
+    "BOOLEAN", "INT32", "INT64", "INT96", "FLOAT", "DOUBLE", "BYTE_ARRAY", "FIXED_LEN_BYTE_ARRAY",
+];
+
+/// Legacy converted types, indexed by the Thrift enum value
+const CONVERTED_TYPES: &[&str] = &[
+    "UTF8", "MAP", "MAP_KEY_VALUE", "LIST", "ENUM", "DECIMAL", "DATE", "TIME_MILLIS", "TIME_MICROS",
+    "TIMESTAMP_MILLIS", "TIMESTAMP_MICROS", "UINT_8", "UINT_16", "UINT_32", "UINT_64", "INT_8",
+    "INT_16", "INT_32", "INT_64", "JSON", "BSON", "INTERVAL",
+];
+
+const REPETITIONS: &[&str] = &["required", "optional", "repeated"];
+
+/// Read schema, row count and row group layout from a Parquet footer
+/// `path` only labels errors, so in-memory data can be summarized too
+pub fn read_summary<R: Read + Seek>(mut reader: R, path: &Path) -> Result<DataSummary> {
+    let footer = read_footer(&mut reader, path)?;
+    let metadata = CompactReader::new(&footer)
+        .read_struct()
+        .ok_or_else(|| invalid(path, "Malformed file metadata"))?;
+
+    // FileMetaData: 1 version, 2 schema, 3 num_rows, 4 row_groups, 6 created_by
+    let elements = metadata.field(2).and_then(ThriftValue::as_list).unwrap_or(&[]);
+    let mut schema = Vec::new();
+    let mut index = 1;
+    let root_children = elements.first().and_then(|root| root.field(5)).and_then(ThriftValue::as_int).unwrap_or(0);
+    for _ in 0..root_children {
+        if !render_element(elements, &mut index, 0, &mut schema) {
+            break;
+        }
+    }
+    let leaf_columns = elements.iter().skip(1).filter(|element| element.field(5).is_none()).count();
+
+    let mut details = vec![
+        ("Columns".to_string(), leaf_columns.to_string()),
+    ];
+    if let Some(row_groups) = metadata.field(4).and_then(ThriftValue::as_list) {
+        details.push(("Row groups".to_string(), row_groups.len().to_string()));
+    }
+    if let Some(version) = metadata.field(1).and_then(ThriftValue::as_int) {
+        details.push(("Format version".to_string(), version.to_string()));
+    }
+    if let Some(created_by) = metadata.field(6).and_then(ThriftValue::as_string) {
+        details.push(("Created by".to_string(), created_by));
+    }
+
+    Ok(DataSummary {
+        format: "Parquet",
+        row_count: metadata.field(3).and_then(ThriftValue::as_int).map(|rows| rows.max(0) as u64),
+        details,
+        schema,
+        sample: None,
+        sample_note: None,
+    })
+}
+
+/// Read the Thrift-encoded FileMetaData block at the end of the file
+fn read_footer<R: Read + Seek>(file: &mut R, path: &Path) -> Result<Vec<u8>> {
+    let io_error = |e| ClazyfilerError::file_system("read_parquet", path.to_string_lossy().as_ref(), e);
+    let file_len = file.seek(SeekFrom::End(0)).map_err(io_error)?;
+    if file_len < 12 {
+        return Err(invalid(path, "File too small to be Parquet"));
+    }
+
+    let mut tail = [0u8; 8];
+    file.seek(SeekFrom::End(-8)).map_err(io_error)?;
+    file.read_exact(&mut tail).map_err(io_error)?;
+    if &tail[4..] == ENCRYPTED_MAGIC {
+        return Err(invalid(path, "Footer is encrypted"));
+    }
+    if &tail[4..] != MAGIC {
+        return Err(invalid(path, "Missing PAR1 footer magic"));
+    }
+
+    let footer_len = u32::from_le_bytes([tail[0], tail[1], tail[2], tail[3]]) as u64;
+    if footer_len + 8 > file_len || footer_len > MAX_FOOTER_LEN {
+        return Err(invalid(path, "Footer length out of range"));
+    }
+    let mut footer = vec![0u8; footer_len as usize];
+    file.seek(SeekFrom::End(-8 - footer_len as i64)).map_err(io_error)?;
+    file.read_exact(&mut footer).map_err(io_error)?;
+    Ok(footer)
+}
+
+/// Render the element at `index` and its children depth-first, returning false when the schema is truncated
+fn render_element(elements: &[ThriftValue], index: &mut usize, depth: usize, lines: &mut Vec<String>) -> bool {
+    // A crafted schema can claim more children than it has or nest absurdly deep
+    let Some(element) = elements.get(*index) else { return false };
+    if depth > 32 {
+        return false;
+    }
+    *index += 1;
+
+    // SchemaElement: 1 type, 2 type_length, 3 repetition, 4 name, 5 num_children, 6 converted_type, 7 scale, 8 precision, 10 logicalType
+    let name = element.field(4).and_then(ThriftValue::as_string).unwrap_or_default();
+    let num_children = element.field(5).and_then(ThriftValue::as_int);
+    let annotation = annotation(element);
+
+    let type_name = match (num_children, element.field(1).and_then(ThriftValue::as_int)) {
+        (Some(_), _) => "group".to_string(),
+        (None, Some(physical)) => {
+            let name = PHYSICAL_TYPES.get(physical as usize).copied().unwrap_or("UNKNOWN");
+            match element.field(2).and_then(ThriftValue::as_int) {
+                Some(length) if physical == 7 => format!("{}({})", name, length),
+                _ => name.to_string(),
+            }
+        }
+        (None, None) => "UNKNOWN".to_string(),
+    };
+    let type_name = match annotation {
+        Some(annotation) => format!("{} {}", type_name, annotation),
+        None => type_name,
+    };
+    let repetition = element
+        .field(3)
+        .and_then(ThriftValue::as_int)
+        .and_then(|repetition| REPETITIONS.get(repetition as usize))
+        .map(|repetition| format!(" ({})", repetition))
+        .unwrap_or_default();
+    lines.push(format!("{}{}: {}{}", "│ ".repeat(depth), name, type_name, repetition));
+
+    for _ in 0..num_children.unwrap_or(0) {
+        if !render_element(elements, index, depth + 1, lines) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Converted type annotation, falling back to the newer logical type union
+fn annotation(element: &ThriftValue) -> Option<String> {
+    if let Some(converted) = element.field(6).and_then(ThriftValue::as_int) {
+        let name = CONVERTED_TYPES.get(converted as usize).copied()?;
+        if name == "DECIMAL" {
+            let scale = element.field(7).and_then(ThriftValue::as_int).unwrap_or(0);
+            let precision = element.field(8).and_then(ThriftValue::as_int).unwrap_or(0);
+            return Some(format!("DECIMAL({},{})", precision, scale));
+        }
+        return Some(name.to_string());
+    }
+
+    // LogicalType is a union: the set field id identifies the variant
+    let ThriftValue::Struct(variants) = element.field(10)? else { return None };
+    let (id, _) = variants.first()?;
+    let name = match id {
+        1 => "STRING",
+        2 => "MAP",
+        3 => "LIST",
+        4 => "ENUM",
+        5 => "DECIMAL",
+        6 => "DATE",
+        7 => "TIME",
+        8 => "TIMESTAMP",
+        10 => "INTEGER",
+        11 => "NULL",
+        12 => "JSON",
+        13 => "BSON",
+        14 => "UUID",
+        15 => "FLOAT16",
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+fn invalid(path: &Path, message: &str) -> ClazyfilerError {
+    ClazyfilerError::content(path.to_string_lossy().as_ref(), message)
+}
@@ -0,0 +1,191 @@
+/// Minimal Thrift compact protocol decoder, enough to read Parquet footers
+/// Values are decoded into a generic tree and inspected by field id afterwards
+
+#[derive(Debug, Clone)]
+pub enum ThriftValue {
+    Int(i64),
+    Binary(Vec<u8>),
+    List(Vec<ThriftValue>),
+    Struct(Vec<(i16, ThriftValue)>),
+    Skipped,    // Booleans, doubles and maps are parsed past but never inspected
+}
+
+impl ThriftValue {
+    /// Field of a struct by id
+    pub fn field(&self, id: i16) -> Option<&ThriftValue> {
+        match self {
+            ThriftValue::Struct(fields) => fields.iter().find(|(field_id, _)| *field_id == id).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            ThriftValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_string(&self) -> Option<String> {
+        match self {
+            ThriftValue::Binary(bytes) => Some(String::from_utf8_lossy(bytes).to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[ThriftValue]> {
+        match self {
+            ThriftValue::List(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// Compact protocol element types
+const TYPE_BOOL_TRUE: u8 = 1;
+const TYPE_BOOL_FALSE: u8 = 2;
+const TYPE_BYTE: u8 = 3;
+const TYPE_I16: u8 = 4;
+const TYPE_I32: u8 = 5;
+const TYPE_I64: u8 = 6;
+const TYPE_DOUBLE: u8 = 7;
+const TYPE_BINARY: u8 = 8;
+const TYPE_LIST: u8 = 9;
+const TYPE_SET: u8 = 10;
+const TYPE_MAP: u8 = 11;
+const TYPE_STRUCT: u8 = 12;
+
+/// Guard against malicious nesting in crafted files
+const MAX_DEPTH: usize = 64;
+
+pub struct CompactReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> CompactReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Read a struct starting at the current position
+    pub fn read_struct(&mut self) -> Option<ThriftValue> {
+        self.read_struct_at_depth(0)
+    }
+
+    fn read_struct_at_depth(&mut self, depth: usize) -> Option<ThriftValue> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let mut fields = Vec::new();
+        let mut last_id: i16 = 0;
+        loop {
+            let header = self.read_byte()?;
+            if header == 0 {
+                return Some(ThriftValue::Struct(fields));
+            }
+            let delta = (header >> 4) as i16;
+            let field_type = header & 0x0f;
+            let id = if delta == 0 {
+                zigzag(self.read_varint()?) as i16
+            } else {
+                last_id.checked_add(delta)?
+            };
+            last_id = id;
+
+            let value = match field_type {
+                // Struct fields encode booleans in the type nibble itself
+                TYPE_BOOL_TRUE | TYPE_BOOL_FALSE => ThriftValue::Skipped,
+                _ => self.read_value(field_type, depth + 1)?,
+            };
+            fields.push((id, value));
+        }
+    }
+
+    fn read_value(&mut self, value_type: u8, depth: usize) -> Option<ThriftValue> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        Some(match value_type {
+            TYPE_BOOL_TRUE | TYPE_BOOL_FALSE => {
+                self.read_byte()?;
+                ThriftValue::Skipped
+            }
+            TYPE_BYTE => ThriftValue::Int(self.read_byte()? as i8 as i64),
+            TYPE_I16 | TYPE_I32 | TYPE_I64 => ThriftValue::Int(zigzag(self.read_varint()?)),
+            TYPE_DOUBLE => {
+                self.read_bytes(8)?;
+                ThriftValue::Skipped
+            }
+            TYPE_BINARY => {
+                let len = self.read_varint()? as usize;
+                ThriftValue::Binary(self.read_bytes(len)?.to_vec())
+            }
+            TYPE_LIST | TYPE_SET => {
+                let header = self.read_byte()?;
+                let size = match header >> 4 {
+                    15 => self.read_varint()? as usize,
+                    size => size as usize,
+                };
+                let element_type = header & 0x0f;
+                // Every element takes at least one byte, which bounds bogus sizes
+                if size > self.remaining() {
+                    return None;
+                }
+                let mut items = Vec::with_capacity(size);
+                for _ in 0..size {
+                    items.push(self.read_value(element_type, depth + 1)?);
+                }
+                ThriftValue::List(items)
+            }
+            TYPE_MAP => {
+                let size = self.read_varint()? as usize;
+                if size > self.remaining() {
+                    return None;
+                }
+                if size > 0 {
+                    let types = self.read_byte()?;
+                    for _ in 0..size {
+                        self.read_value(types >> 4, depth + 1)?;
+                        self.read_value(types & 0x0f, depth + 1)?;
+                    }
+                }
+                ThriftValue::Skipped
+            }
+            TYPE_STRUCT => self.read_struct_at_depth(depth + 1)?,
+            _ => return None,
+        })
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        let byte = *self.data.get(self.pos)?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn read_varint(&mut self) -> Option<u64> {
+        let mut result: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            result |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Some(result);
+            }
+        }
+        None
+    }
+}
+
+fn zigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
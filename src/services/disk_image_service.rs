@@ -0,0 +1,335 @@
+use std::fs::{self, File};
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use crate::core::{check_name, ClazyfilerError, Result};
+use crate::services::FileService;
+
+/// ISO 9660 sectors are always this size; the volume descriptors start at sector 16
+const ISO_SECTOR: u64 = 2048;
+
+/// Entries of a directory read from an ISO image, beyond which the listing is cut
+const MAX_ISO_ENTRIES: usize = 1000;
+
+/// Bytes of an ISO directory read at most
+const MAX_ISO_DIRECTORY: u32 = 1024 * 1024;
+
+/// Sector size assumed for partition tables of raw images
+const DISK_SECTOR: u64 = 512;
+
+/// GPT entries are 128 bytes or a larger multiple of it; anything beyond this is not a real table
+const MAX_GPT_ENTRY_SIZE: u32 = 4096;
+
+/// Bytes of a GPT partition table read at most
+const MAX_GPT_TABLE: u32 = 1024 * 1024;
+
+/// Kinds of disk images whose layout is shown in the preview
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiskImageKind {
+    Iso,
+    Qcow2,
+    Raw,
+}
+
+impl DiskImageKind {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        match extension.as_str() {
+            "iso" => Some(Self::Iso),
+            "qcow2" | "qcow" => Some(Self::Qcow2),
+            "img" | "raw" => Some(Self::Raw),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Iso => "ISO 9660 Image",
+            Self::Qcow2 => "QCOW2 Disk Image",
+            Self::Raw => "Raw Disk Image",
+        }
+    }
+}
+
+/// An entry of the image: a file or directory of an ISO, a partition of a raw image
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageEntry {
+    pub name: String,
+    pub size: u64,
+    pub is_directory: bool,
+    pub offset: u64,  // Where its data starts in the image
+}
+
+/// A directory of an ISO image being browsed: the directories descended into from the root, and its entries
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageListing {
+    pub image: PathBuf,
+    pub trail: Vec<ImageEntry>,
+    pub entries: Vec<ImageEntry>,
+}
+
+impl ImageListing {
+    /// Where the listed directory is inside the image, e.g. `/BOOT/GRUB`
+    pub fn location(&self) -> String {
+        let names: Vec<&str> = self.trail.iter().map(|directory| directory.name.as_str()).collect();
+        format!("/{}", names.join("/"))
+    }
+}
+
+/// What the headers of a disk image say, read without mounting it
+#[derive(Debug, Clone, Default)]
+pub struct DiskImageInfo {
+    pub details: Vec<(&'static str, String)>,  // Label and value, in display order
+    pub entries: Vec<ImageEntry>,               // Root directory of an ISO, partitions of a raw image
+}
+
+/// Service reading disk images read-only: the directories and files of ISO 9660 images,
+/// the header of QCOW2 images and the MBR or GPT partition table of raw images
+#[derive(Debug)]
+pub struct DiskImageService {
+}
+
+impl DiskImageService {
+    pub fn new() -> Self {
+        Self {
+        }
+    }
+
+    /// Read what the headers of the image at `path` describe
+    pub fn inspect(&self, path: &Path, kind: DiskImageKind) -> Result<DiskImageInfo> {
+        let path_str = path.to_string_lossy();
+        let mut file = File::open(path).map_err(|e| ClazyfilerError::file_system("open", &path_str, e))?;
+        read_image(&mut file, kind).map_err(|message| ClazyfilerError::content(&path_str, &message))
+    }
+
+    /// List the directory of the ISO image at `image` reached through `trail`, its root when empty
+    pub fn list_directory(&self, image: &Path, trail: Vec<ImageEntry>) -> Result<ImageListing> {
+        let path_str = image.to_string_lossy();
+        let mut file = File::open(image).map_err(|e| ClazyfilerError::file_system("open", &path_str, e))?;
+        let entries = match trail.last() {
+            Some(directory) => read_iso_block_size(&mut file).and_then(|block_size| read_iso_directory(&mut file, directory, block_size)),
+            None => read_iso(&mut file).map(|info| info.entries),
+        };
+        let entries = entries.map_err(|message| ClazyfilerError::content(&path_str, &message))?;
+        Ok(ImageListing { image: image.to_path_buf(), trail, entries })
+    }
+
+    /// Copy a file of the ISO image at `image` out to `to`, which must not exist yet
+    pub fn extract(&self, image: &Path, entry: &ImageEntry, to: &Path) -> Result<()> {
+        let path_str = image.to_string_lossy();
+        if entry.is_directory {
+            return Err(ClazyfilerError::content(&entry.name, "Only files can be extracted"));
+        }
+        if let Some(problem) = check_name(&entry.name).filter(|problem| problem.fatal) {
+            return Err(ClazyfilerError::content(&entry.name, &problem.message));
+        }
+        let to_str = to.to_string_lossy();
+        let mut source = File::open(image).map_err(|e| ClazyfilerError::file_system("open", &path_str, e))?;
+        let mut target = File::create_new(to).map_err(|e| ClazyfilerError::file_system("create", &to_str, e))?;
+        let copied = source.seek(SeekFrom::Start(entry.offset))
+            .and_then(|_| io::copy(&mut (&mut source).take(entry.size), &mut target));
+        let result = match copied {
+            Ok(copied) if copied == entry.size => Ok(()),
+            Ok(_) => Err(ClazyfilerError::content(&path_str, "Image is truncated")),
+            Err(e) => Err(ClazyfilerError::file_system("copy", &path_str, e)),
+        };
+        if result.is_err() {
+            let _ = fs::remove_file(to);
+        }
+        result
+    }
+
+    /// Read the headers of an image already in memory
+    pub fn parse(kind: DiskImageKind, data: &[u8]) -> Result<DiskImageInfo> {
+        read_image(&mut Cursor::new(data), kind).map_err(|message| ClazyfilerError::content("<memory>", &message))
+    }
+}
+
+fn read_image<R: Read + Seek>(file: &mut R, kind: DiskImageKind) -> std::result::Result<DiskImageInfo, String> {
+    match kind {
+        DiskImageKind::Iso => read_iso(file),
+        DiskImageKind::Qcow2 => read_qcow2(file),
+        DiskImageKind::Raw => read_partitions(file),
+    }
+}
+
+/// Read `len` bytes at `offset`, failing when the image is shorter
+fn read_at<R: Read + Seek>(file: &mut R, offset: u64, len: usize) -> std::result::Result<Vec<u8>, String> {
+    let mut buffer = vec![0; len];
+    file.seek(SeekFrom::Start(offset))
+        .and_then(|_| file.read_exact(&mut buffer))
+        .map_err(|_| "Image is truncated".to_string())?;
+    Ok(buffer)
+}
+
+fn u16_le(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn u32_le(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap_or_default())
+}
+
+fn u64_le(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap_or_default())
+}
+
+fn u32_be(bytes: &[u8], at: usize) -> u32 {
+    u32::from_be_bytes(bytes[at..at + 4].try_into().unwrap_or_default())
+}
+
+fn u64_be(bytes: &[u8], at: usize) -> u64 {
+    u64::from_be_bytes(bytes[at..at + 8].try_into().unwrap_or_default())
+}
+
+/// Space-padded text of a volume descriptor
+fn padded_text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).trim_end_matches([' ', '\0']).to_string()
+}
+
+/// Volume details from the primary volume descriptor and the entries of the root directory
+fn read_iso<R: Read + Seek>(file: &mut R) -> std::result::Result<DiskImageInfo, String> {
+    let descriptor = read_iso_descriptor(file)?;
+    let block_size = u16_le(&descriptor, 128) as u64;
+    let mut info = DiskImageInfo::default();
+    info.details.push(("Volume", padded_text(&descriptor[40..72])));
+    info.details.push(("Volume size", FileService::format_file_size(u32_le(&descriptor, 80) as u64 * block_size)));
+    for (label, range) in [("System", 8..40), ("Publisher", 318..446), ("Application", 574..702)] {
+        let text = padded_text(&descriptor[range]);
+        if !text.is_empty() {
+            info.details.push((label, text));
+        }
+    }
+
+    let root = &descriptor[156..190];
+    let root = ImageEntry {
+        name: String::new(),
+        size: u32_le(root, 10) as u64,
+        is_directory: true,
+        offset: u32_le(root, 2) as u64 * block_size,
+    };
+    info.entries = read_iso_directory(file, &root, block_size)?;
+    Ok(info)
+}
+
+/// The primary volume descriptor of an ISO image
+fn read_iso_descriptor<R: Read + Seek>(file: &mut R) -> std::result::Result<Vec<u8>, String> {
+    let descriptor = read_at(file, 16 * ISO_SECTOR, ISO_SECTOR as usize)?;
+    if descriptor[0] != 1 || &descriptor[1..6] != b"CD001" {
+        return Err("No ISO 9660 primary volume descriptor".to_string());
+    }
+    Ok(descriptor)
+}
+
+/// Logical block size of an ISO image, the unit of extent locations
+fn read_iso_block_size<R: Read + Seek>(file: &mut R) -> std::result::Result<u64, String> {
+    read_iso_descriptor(file).map(|descriptor| u16_le(&descriptor, 128) as u64)
+}
+
+/// Entries of an ISO directory from its records
+fn read_iso_directory<R: Read + Seek>(file: &mut R, directory: &ImageEntry, block_size: u64) -> std::result::Result<Vec<ImageEntry>, String> {
+    // Directory records never straddle a sector; a zero length pads to the next one
+    let len = directory.size.min(MAX_ISO_DIRECTORY as u64);
+    let records = read_at(file, directory.offset, len as usize)?;
+    let mut entries = Vec::new();
+    let mut at = 0;
+    while at < records.len() && entries.len() < MAX_ISO_ENTRIES {
+        let len = records[at] as usize;
+        if len == 0 {
+            at = (at / ISO_SECTOR as usize + 1) * ISO_SECTOR as usize;
+            continue;
+        }
+        let Some(record) = records.get(at..at + len).filter(|record| record.len() > 33) else { break };
+        at += len;
+        let name = &record[33..(33 + record[32] as usize).min(record.len())];
+        // `.` and `..` are the names 0 and 1
+        if name == [0] || name == [1] {
+            continue;
+        }
+        let name = String::from_utf8_lossy(name);
+        let name = name.split(';').next().unwrap_or_default();
+        entries.push(ImageEntry {
+            name: name.strip_suffix('.').unwrap_or(name).to_string(),
+            size: u32_le(record, 10) as u64,
+            is_directory: record[25] & 0x02 != 0,
+            offset: u32_le(record, 2) as u64 * block_size,
+        });
+    }
+    Ok(entries)
+}
+
+/// Version, virtual size and backing file from the QCOW2 header
+fn read_qcow2<R: Read + Seek>(file: &mut R) -> std::result::Result<DiskImageInfo, String> {
+    let header = read_at(file, 0, 72)?;
+    if &header[0..4] != b"QFI\xfb" {
+        return Err("No QCOW2 header".to_string());
+    }
+    let mut info = DiskImageInfo::default();
+    info.details.push(("Version", u32_be(&header, 4).to_string()));
+    info.details.push(("Virtual size", FileService::format_file_size(u64_be(&header, 24))));
+    info.details.push(("Cluster size", FileService::format_file_size(1u64.checked_shl(u32_be(&header, 20)).unwrap_or(0))));
+    let encryption = match u32_be(&header, 32) {
+        0 => "none",
+        1 => "AES",
+        2 => "LUKS",
+        _ => "unknown",
+    };
+    info.details.push(("Encryption", encryption.to_string()));
+    info.details.push(("Snapshots", u32_be(&header, 60).to_string()));
+
+    let (backing_offset, backing_len) = (u64_be(&header, 8), u32_be(&header, 16));
+    if backing_offset != 0 && backing_len > 0 && backing_len <= 1023 {
+        let backing = read_at(file, backing_offset, backing_len as usize)?;
+        info.details.push(("Backing file", String::from_utf8_lossy(&backing).to_string()));
+    }
+    Ok(info)
+}
+
+/// Partitions of the GPT, or of the MBR when there is none
+fn read_partitions<R: Read + Seek>(file: &mut R) -> std::result::Result<DiskImageInfo, String> {
+    let mbr = read_at(file, 0, DISK_SECTOR as usize)?;
+    if mbr[510..512] != [0x55, 0xAA] {
+        return Err("No partition table found".to_string());
+    }
+    let mut info = DiskImageInfo::default();
+    let records: Vec<&[u8]> = (0..4).map(|index| &mbr[446 + index * 16..462 + index * 16]).collect();
+    // A protective MBR partition of type 0xEE covers a GPT
+    if records.iter().any(|record| record[4] == 0xEE) {
+        let header = read_at(file, DISK_SECTOR, 92)?;
+        if &header[0..8] != b"EFI PART" {
+            return Err("Protective MBR without a GPT header".to_string());
+        }
+        info.details.push(("Partition table", "GPT".to_string()));
+        let (entries_lba, count, entry_size) = (u64_le(&header, 72), u32_le(&header, 80).min(256), u32_le(&header, 84));
+        if !(128..=MAX_GPT_ENTRY_SIZE).contains(&entry_size) || entry_size % 128 != 0 {
+            return Err(format!("Unsupported GPT entry size {}", entry_size));
+        }
+        let table_len = count.checked_mul(entry_size).filter(|len| *len <= MAX_GPT_TABLE)
+            .ok_or_else(|| "GPT partition table is too large".to_string())?;
+        let table_offset = entries_lba.checked_mul(DISK_SECTOR)
+            .ok_or_else(|| "GPT partition table lies beyond the image".to_string())?;
+        let table = read_at(file, table_offset, table_len as usize)?;
+        for entry in table.chunks_exact(entry_size as usize).filter(|entry| entry[0..16].iter().any(|byte| *byte != 0)) {
+            let units: Vec<u16> = entry[56..128].chunks_exact(2).map(|unit| u16_le(unit, 0)).take_while(|unit| *unit != 0).collect();
+            let (first, last) = (u64_le(entry, 32), u64_le(entry, 40));
+            let sectors = last.saturating_sub(first).saturating_add(1);
+            let name = String::from_utf16_lossy(&units);
+            info.entries.push(ImageEntry {
+                name: if name.is_empty() { format!("Partition {}", info.entries.len() + 1) } else { name },
+                size: sectors.saturating_mul(DISK_SECTOR),
+                is_directory: false,
+                offset: first.saturating_mul(DISK_SECTOR),
+            });
+        }
+    } else {
+        info.details.push(("Partition table", "MBR".to_string()));
+        for (index, record) in records.iter().enumerate().filter(|(_, record)| record[4] != 0) {
+            info.entries.push(ImageEntry {
+                name: format!("Partition {} (type 0x{:02x})", index + 1, record[4]),
+                size: u32_le(record, 12) as u64 * DISK_SECTOR,
+                is_directory: false,
+                offset: u32_le(record, 8) as u64 * DISK_SECTOR,
+            });
+        }
+    }
+    Ok(info)
+}
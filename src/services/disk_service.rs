@@ -0,0 +1,170 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use crate::core::{ClazyfilerError, Result};
+use crate::services::{hook_service::expand, FileService};
+
+/// Filesystem types served over the network, where every stat is a round trip
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs", "nfs4", "cifs", "smb3", "smbfs", "fuse.sshfs", "sshfs", "osxfuse", "macfuse",
+    "afpfs", "webdav", "davfs", "fuse.davfs2", "fuse.rclone", "9p", "afs", "ceph", "fuse.ceph", "glusterfs", "fuse.glusterfs",
+];
+
+/// Filesystem types that write changed data to new blocks, so overwriting a file leaves its old contents on disk
+const COPY_ON_WRITE_FILESYSTEMS: &[&str] = &["btrfs", "zfs", "apfs", "bcachefs", "nilfs2", "fuse.zfs"];
+
+/// Space on the filesystem holding a directory, as reported by `df`
+#[derive(Debug, Clone)]
+pub struct DiskUsage {
+    pub mount_point: PathBuf,
+    pub used: u64,
+    pub available: u64,     // Usable by the current user (excludes root-reserved blocks)
+}
+
+impl DiskUsage {
+    /// Percentage in use, computed like df's Capacity column
+    pub fn used_percent(&self) -> f64 {
+        let usable = self.used + self.available;
+        if usable == 0 { 0.0 } else { self.used as f64 * 100.0 / usable as f64 }
+    }
+}
+
+/// Service responsible for free-space checks and network-mount detection for the filesystem of a path
+/// Shells out to POSIX `df`, so it works on any mount type (including network filesystems)
+#[derive(Debug)]
+pub struct DiskService;
+
+impl DiskService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Usage of the filesystem containing `path`
+    pub fn usage(&self, path: &Path) -> Result<DiskUsage> {
+        let output = Command::new("df")
+            .args(["-P", "-k"])
+            .arg(path)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .map_err(|e| ClazyfilerError::external_tool("df", &format!("Failed to launch: {}", e)))?;
+
+        // Filesystem 1024-blocks Used Available Capacity Mounted-on (which may contain spaces)
+        let listing = String::from_utf8_lossy(&output.stdout);
+        let parse = |line: &str| -> Option<DiskUsage> {
+            let mut rest = line.trim_start();
+            let mut fields = Vec::new();
+            for _ in 0..5 {
+                let end = rest.find(char::is_whitespace)?;
+                fields.push(&rest[..end]);
+                rest = rest[end..].trim_start();
+            }
+            let kib = |field: &str| field.parse::<u64>().ok().map(|blocks| blocks * 1024);
+            Some(DiskUsage {
+                mount_point: PathBuf::from(rest),
+                used: kib(fields[2])?,
+                available: kib(fields[3])?,
+            })
+        };
+        listing
+            .lines()
+            .nth(1)
+            .and_then(parse)
+            .ok_or_else(|| ClazyfilerError::external_tool("df", "Could not read filesystem usage"))
+    }
+
+    /// Type of the filesystem containing `path` (e.g. "nfs4") if it is a network filesystem
+    pub fn network_filesystem(&self, path: &Path) -> Option<String> {
+        Self::filesystem_type(path).filter(|fs_type| NETWORK_FILESYSTEMS.contains(&fs_type.as_str()))
+    }
+
+    /// Type of the filesystem containing `path` (e.g. "btrfs") if it is copy-on-write
+    pub fn copy_on_write_filesystem(&self, path: &Path) -> Option<String> {
+        Self::filesystem_type(path).filter(|fs_type| COPY_ON_WRITE_FILESYSTEMS.contains(&fs_type.as_str()))
+    }
+
+    /// Type of the filesystem of the innermost mount holding `path`
+    fn filesystem_type(path: &Path) -> Option<String> {
+        let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        Self::mounts()
+            .into_iter()
+            .filter(|(mount_point, _)| path.starts_with(mount_point))
+            .max_by_key(|(mount_point, _)| mount_point.as_os_str().len())
+            .map(|(_, fs_type)| fs_type)
+    }
+
+    /// Whether something is mounted exactly at `path`
+    pub fn is_mount_point(&self, path: &Path) -> bool {
+        let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        Self::mounts().iter().any(|(mount_point, _)| *mount_point == path)
+    }
+
+    /// Run `command` to mount a remote at `path`, created first if missing; {path} in it is filled in
+    /// Inherits the terminal, which must be suspended, so the tool can ask for a password
+    pub fn mount(&self, command: &str, path: &Path) -> Result<()> {
+        let words = expand(command, path, None);
+        let Some((program, args)) = words.split_first() else {
+            return Err(ClazyfilerError::config("Empty mount command"));
+        };
+        std::fs::create_dir_all(path)
+            .map_err(|e| ClazyfilerError::file_system("create_dir", path.to_string_lossy().as_ref(), e))?;
+        let status = Command::new(program)
+            .args(args)
+            .status()
+            .map_err(|e| ClazyfilerError::external_tool(program, &format!("Failed to launch: {}", e)))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(ClazyfilerError::external_tool(program, &format!("Mount failed ({})", status)))
+        }
+    }
+
+    /// Mount points with their filesystem types, from /proc on Linux or `mount` elsewhere
+    fn mounts() -> Vec<(PathBuf, String)> {
+        if let Ok(table) = std::fs::read_to_string("/proc/self/mounts") {
+            // device mount-point type options ... with spaces escaped as \040
+            return table
+                .lines()
+                .filter_map(|line| {
+                    let mut fields = line.split(' ');
+                    let _device = fields.next()?;
+                    let mount_point = fields.next()?.replace("\\040", " ").replace("\\011", "\t");
+                    Some((PathBuf::from(mount_point), fields.next()?.to_string()))
+                })
+                .collect();
+        }
+
+        // BSD/macOS: "device on /mount point (type, options...)"
+        let Ok(output) = Command::new("mount").stdin(Stdio::null()).stderr(Stdio::null()).output() else {
+            return Vec::new();
+        };
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (_, rest) = line.split_once(" on ")?;
+                let (mount_point, details) = rest.rsplit_once(" (")?;
+                let fs_type = details.split([',', ')']).next()?.trim();
+                Some((PathBuf::from(mount_point), fs_type.to_string()))
+            })
+            .collect()
+    }
+
+    /// Fail before writing `bytes` under `dir` if they would not fit
+    pub fn ensure_space(&self, dir: &Path, bytes: u64) -> Result<()> {
+        // Without df there is nothing to check against; let the write itself fail if it must
+        let Ok(usage) = self.usage(dir) else {
+            return Ok(());
+        };
+        if bytes > usage.available {
+            return Err(ClazyfilerError::content(
+                dir.to_string_lossy().as_ref(),
+                &format!(
+                    "Not enough space on {}: {} needed, {} free",
+                    usage.mount_point.display(),
+                    FileService::format_file_size(bytes),
+                    FileService::format_file_size(usage.available)
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
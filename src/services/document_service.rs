@@ -0,0 +1,393 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::core::{xml_attribute, xml_tokens, BudgetedCache, ClazyfilerError, LruCache, Result, XmlToken, ZipArchive};
+
+/// Archives above this size are not read for previews
+const MAX_DOCUMENT_SIZE: u64 = 50 * 1024 * 1024;
+
+/// Lines of extracted text kept for the preview
+const MAX_PREVIEW_LINES: usize = 200;
+
+/// Rows read from each spreadsheet sheet
+const MAX_SHEET_ROWS: usize = 50;
+
+/// Columns read from each spreadsheet row
+const MAX_SHEET_COLUMNS: usize = 50;
+
+/// Zip+XML office formats with text extraction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocumentKind {
+    Word,
+    Excel,
+    PowerPoint,
+    OpenDocumentText,
+    OpenDocumentPresentation,
+}
+
+impl DocumentKind {
+    /// Detect the document kind from a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_string_lossy().to_lowercase();
+        match ext.as_str() {
+            "docx" | "docm" => Some(DocumentKind::Word),
+            "xlsx" | "xlsm" => Some(DocumentKind::Excel),
+            "pptx" => Some(DocumentKind::PowerPoint),
+            "odt" => Some(DocumentKind::OpenDocumentText),
+            "odp" => Some(DocumentKind::OpenDocumentPresentation),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DocumentKind::Word => "Word Document",
+            DocumentKind::Excel => "Excel Workbook",
+            DocumentKind::PowerPoint => "PowerPoint Presentation",
+            DocumentKind::OpenDocumentText => "OpenDocument Text",
+            DocumentKind::OpenDocumentPresentation => "OpenDocument Presentation",
+        }
+    }
+}
+
+/// Tags that carry text in a document's XML
+struct TextMarkup {
+    text: &'static [&'static str],        // Text is only collected inside these
+    paragraph: &'static [&'static str],   // Closing one ends a line
+    tab: &'static [&'static str],
+    line_break: &'static [&'static str],
+    space: &'static [&'static str],       // ODF `<text:s text:c="N"/>` runs of spaces
+}
+
+const WORD_MARKUP: TextMarkup = TextMarkup {
+    text: &["w:t"],
+    paragraph: &["w:p"],
+    tab: &["w:tab"],
+    line_break: &["w:br", "w:cr"],
+    space: &[],
+};
+
+const DRAWING_MARKUP: TextMarkup = TextMarkup {
+    text: &["a:t"],
+    paragraph: &["a:p"],
+    tab: &[],
+    line_break: &["a:br"],
+    space: &[],
+};
+
+const OPEN_DOCUMENT_MARKUP: TextMarkup = TextMarkup {
+    text: &["text:p", "text:h"],
+    paragraph: &["text:p", "text:h"],
+    tab: &["text:tab"],
+    line_break: &["text:line-break"],
+    space: &["text:s"],
+};
+
+/// Service responsible for rough text previews of office documents
+/// Extraction reads the whole archive, so results (including failures) are cached per path
+#[derive(Debug)]
+pub struct DocumentService {
+    cache: LruCache<PathBuf, std::result::Result<String, String>>,
+}
+
+impl DocumentService {
+    pub fn new() -> Self {
+        Self {
+            cache: LruCache::new("documents"),
+        }
+    }
+
+    /// Extracted text of a document, limited to the first lines
+    pub fn extract_text(&self, path: &Path) -> std::result::Result<String, String> {
+        if let Some(cached) = self.cache.get(path) {
+            return cached;
+        }
+        let text = Self::read_text(path).map_err(|e| e.to_string());
+        self.cache.insert(path.to_path_buf(), text.clone());
+        text
+    }
+
+    /// Forget cached extractions (e.g. after a refresh)
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
+    /// Caches counted against the memory budget
+    pub fn caches(&self) -> Vec<&dyn BudgetedCache> {
+        vec![&self.cache]
+    }
+
+    /// Text of a document already in memory, limited like `extract_text`
+    pub fn parse(kind: DocumentKind, data: &[u8]) -> Result<String> {
+        read_archive_text(kind, data, "<memory>")
+    }
+
+    fn read_text(path: &Path) -> Result<String> {
+        let path_str = path.to_string_lossy();
+        let kind = DocumentKind::from_path(path)
+            .ok_or_else(|| ClazyfilerError::content(&path_str, "Unsupported document type"))?;
+
+        let size = std::fs::metadata(path)
+            .map_err(|e| ClazyfilerError::file_system("metadata", &path_str, e))?
+            .len();
+        if size > MAX_DOCUMENT_SIZE {
+            return Err(ClazyfilerError::content(&path_str, "Document too large to preview"));
+        }
+        let data = std::fs::read(path).map_err(|e| ClazyfilerError::file_system("read", &path_str, e))?;
+        read_archive_text(kind, &data, &path_str)
+    }
+}
+
+/// Extract the text of a zipped document; `label` names it in errors
+fn read_archive_text(kind: DocumentKind, data: &[u8], label: &str) -> Result<String> {
+    let archive = ZipArchive::parse(data)
+        .ok_or_else(|| ClazyfilerError::content(label, "Not a valid zip archive"))?;
+    let missing = |name: &str| ClazyfilerError::content(label, &format!("Missing {}", name));
+
+    let text = match kind {
+        DocumentKind::Word => {
+            let xml = archive.read_text("word/document.xml").ok_or_else(|| missing("word/document.xml"))?;
+            extract_markup_text(&xml, &WORD_MARKUP)
+        }
+        DocumentKind::Excel => excel_text(&archive).ok_or_else(|| missing("xl/workbook.xml"))?,
+        DocumentKind::PowerPoint => powerpoint_text(&archive),
+        DocumentKind::OpenDocumentText | DocumentKind::OpenDocumentPresentation => {
+            let xml = archive.read_text("content.xml").ok_or_else(|| missing("content.xml"))?;
+            extract_markup_text(&xml, &OPEN_DOCUMENT_MARKUP)
+        }
+    };
+
+    Ok(limit_lines(&text))
+}
+
+/// Collect text inside the markup's text tags, turning paragraphs, tabs and breaks into whitespace
+fn extract_markup_text(xml: &str, markup: &TextMarkup) -> String {
+    let mut out = String::new();
+    let mut text_depth = 0usize;
+
+    for token in xml_tokens(xml) {
+        match token {
+            XmlToken::Start { name, attributes, self_closing } => {
+                if markup.tab.contains(&name) {
+                    out.push('\t');
+                } else if markup.line_break.contains(&name) {
+                    out.push('\n');
+                } else if markup.space.contains(&name) {
+                    let count = xml_attribute(attributes, "text:c").and_then(|c| c.parse().ok()).unwrap_or(1usize);
+                    out.push_str(&" ".repeat(count.min(80)));
+                } else if markup.paragraph.contains(&name) && self_closing {
+                    out.push('\n');
+                } else if markup.text.contains(&name) && !self_closing {
+                    text_depth += 1;
+                }
+            }
+            XmlToken::End { name } => {
+                if markup.text.contains(&name) {
+                    text_depth = text_depth.saturating_sub(1);
+                }
+                if markup.paragraph.contains(&name) {
+                    out.push('\n');
+                }
+            }
+            XmlToken::Text(text) if text_depth > 0 => out.push_str(&text),
+            XmlToken::Text(_) => {}
+        }
+    }
+
+    out
+}
+
+/// Slides in presentation order (slide1, slide2, ... slide10)
+fn powerpoint_text(archive: &ZipArchive) -> String {
+    let mut slides: Vec<(u32, &str)> = archive
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let number = entry.name.strip_prefix("ppt/slides/slide")?.strip_suffix(".xml")?.parse().ok()?;
+            Some((number, entry.name.as_str()))
+        })
+        .collect();
+    slides.sort();
+
+    let mut out = String::new();
+    for (number, name) in slides {
+        let Some(xml) = archive.read_text(name) else { continue };
+        out.push_str(&format!("── Slide {} ──\n", number));
+        out.push_str(&extract_markup_text(&xml, &DRAWING_MARKUP));
+        out.push('\n');
+    }
+    out
+}
+
+/// Sheets as `|`-separated rows, with shared strings resolved
+fn excel_text(archive: &ZipArchive) -> Option<String> {
+    let workbook = archive.read_text("xl/workbook.xml")?;
+    let shared_strings = archive
+        .read_text("xl/sharedStrings.xml")
+        .map(|xml| shared_strings(&xml))
+        .unwrap_or_default();
+
+    // Sheet order and names come from the workbook, their files from the relationships
+    let targets: HashMap<String, String> = archive
+        .read_text("xl/_rels/workbook.xml.rels")
+        .map(|xml| {
+            xml_tokens(&xml)
+                .into_iter()
+                .filter_map(|token| match token {
+                    XmlToken::Start { name: "Relationship", attributes, .. } => {
+                        Some((xml_attribute(attributes, "Id")?, xml_attribute(attributes, "Target")?))
+                    }
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let sheets: Vec<(String, Option<String>)> = xml_tokens(&workbook)
+        .into_iter()
+        .filter_map(|token| match token {
+            XmlToken::Start { name: "sheet", attributes, .. } => {
+                Some((xml_attribute(attributes, "name")?, xml_attribute(attributes, "r:id")))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let mut out = String::new();
+    for (index, (sheet_name, relationship)) in sheets.iter().enumerate() {
+        let part = relationship
+            .as_ref()
+            .and_then(|id| targets.get(id))
+            .map(|target| match target.strip_prefix('/') {
+                Some(absolute) => absolute.to_string(),
+                None => format!("xl/{}", target),
+            })
+            .unwrap_or_else(|| format!("xl/worksheets/sheet{}.xml", index + 1));
+        let Some(xml) = archive.read_text(&part) else { continue };
+
+        out.push_str(&format!("── {} ──\n", sheet_name));
+        for row in sheet_rows(&xml, &shared_strings) {
+            out.push_str(&row.join(" | "));
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    Some(out)
+}
+
+/// Entries of the shared string table, rich text runs concatenated
+fn shared_strings(xml: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut current: Option<String> = None;
+    let mut in_text = false;
+    let mut in_phonetic = false;
+
+    for token in xml_tokens(xml) {
+        match token {
+            XmlToken::Start { name: "si", .. } => current = Some(String::new()),
+            XmlToken::End { name: "si" } => strings.extend(current.take()),
+            XmlToken::Start { name: "rPh", self_closing: false, .. } => in_phonetic = true,
+            XmlToken::End { name: "rPh" } => in_phonetic = false,
+            XmlToken::Start { name: "t", self_closing: false, .. } => in_text = true,
+            XmlToken::End { name: "t" } => in_text = false,
+            XmlToken::Text(text) if in_text && !in_phonetic => {
+                if let Some(current) = current.as_mut() {
+                    current.push_str(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+    strings
+}
+
+/// Cell values of a worksheet, placed by their column reference
+fn sheet_rows(xml: &str, shared_strings: &[String]) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut cell_column = 0usize;
+    let mut cell_type = String::new();
+    let mut value = String::new();
+    let mut in_value = false;
+
+    for token in xml_tokens(xml) {
+        match token {
+            XmlToken::Start { name: "row", self_closing, .. } => {
+                row.clear();
+                if self_closing {
+                    rows.push(Vec::new());
+                }
+            }
+            XmlToken::End { name: "row" } => {
+                rows.push(std::mem::take(&mut row));
+                if rows.len() >= MAX_SHEET_ROWS {
+                    break;
+                }
+            }
+            XmlToken::Start { name: "c", attributes, .. } => {
+                cell_column = xml_attribute(attributes, "r")
+                    .and_then(|reference| column_index(&reference))
+                    .unwrap_or(row.len());
+                cell_type = xml_attribute(attributes, "t").unwrap_or_default();
+                value.clear();
+            }
+            XmlToken::End { name: "c" } => {
+                let display = match cell_type.as_str() {
+                    "s" => value.trim().parse::<usize>().ok().and_then(|i| shared_strings.get(i).cloned()).unwrap_or_default(),
+                    "b" => if value.trim() == "1" { "TRUE".to_string() } else { "FALSE".to_string() },
+                    _ => value.clone(),
+                };
+                if cell_column < MAX_SHEET_COLUMNS {
+                    if row.len() <= cell_column {
+                        row.resize(cell_column + 1, String::new());
+                    }
+                    row[cell_column] = display;
+                }
+            }
+            XmlToken::Start { name: "v" | "t", self_closing: false, .. } => in_value = true,
+            XmlToken::End { name: "v" | "t" } => in_value = false,
+            XmlToken::Text(text) if in_value => value.push_str(&text),
+            _ => {}
+        }
+    }
+    rows
+}
+
+/// Zero-based column of a cell reference like `AB12`
+fn column_index(reference: &str) -> Option<usize> {
+    let letters: String = reference.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+    if letters.is_empty() || letters.len() > 3 {
+        return None;
+    }
+    let number = letters
+        .chars()
+        .fold(0usize, |acc, c| acc * 26 + (c.to_ascii_uppercase() as usize - 'A' as usize + 1));
+    Some(number - 1)
+}
+
+/// Keep the first lines, collapsing runs of blank lines
+fn limit_lines(text: &str) -> String {
+    let mut lines = Vec::new();
+    let mut blank_run = 0;
+    let mut total = 0;
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_run += 1;
+            if blank_run > 1 {
+                continue;
+            }
+        } else {
+            blank_run = 0;
+        }
+        total += 1;
+        if lines.len() < MAX_PREVIEW_LINES {
+            lines.push(line);
+        }
+    }
+
+    let mut out = lines.join("\n").trim().to_string();
+    if total > MAX_PREVIEW_LINES {
+        out.push_str(&format!("\n\n... ({} more lines)", total - MAX_PREVIEW_LINES));
+    }
+    out
+}
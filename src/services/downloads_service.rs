@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use crate::services::TreeWatcher;
+
+/// Entries of the downloads directory kept; older ones are left to the regular listing
+const DOWNLOADS_LIMIT: usize = 100;
+
+/// Extensions browsers and download managers use while a download is still coming in
+const PARTIAL_EXTENSIONS: &[&str] = &["part", "crdownload", "download", "partial", "opdownload"];
+
+/// An entry of the downloads directory
+#[derive(Debug, Clone)]
+pub struct Download {
+    pub path: PathBuf,
+    pub modified: SystemTime,
+    pub is_directory: bool,
+    pub in_progress: bool,  // Still being written under a temporary extension
+}
+
+impl Download {
+    /// Seconds since the epoch, for `format_age`
+    pub fn modified_secs(&self) -> i64 {
+        self.modified.duration_since(SystemTime::UNIX_EPOCH).map(|age| age.as_secs() as i64).unwrap_or_default()
+    }
+}
+
+/// Service keeping track of the downloads directory, newest entries first
+/// A watcher re-reads it as files arrive; whatever finished arriving since the panel was last open counts as new
+#[derive(Debug)]
+pub struct DownloadsService {
+    dir: Option<PathBuf>,
+    watcher: Option<TreeWatcher>,
+    entries: Vec<Download>,
+    seen: SystemTime,
+}
+
+impl DownloadsService {
+    /// Watch `dir`, or the platform's downloads directory when unset
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self::in_dir(dir.or_else(dirs::download_dir))
+    }
+
+    /// Watch `dir` only; None has no downloads panel
+    pub fn in_dir(dir: Option<PathBuf>) -> Self {
+        let dir = dir.filter(|dir| dir.is_dir());
+        let watcher = dir.as_deref().and_then(|dir| {
+            let mut watcher = TreeWatcher::new()?;
+            watcher.watch(dir).then_some(watcher)
+        });
+        let entries = dir.as_deref().map(read_downloads).unwrap_or_default();
+        Self { dir, watcher, entries, seen: SystemTime::now() }
+    }
+
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    pub fn entries(&self) -> &[Download] {
+        &self.entries
+    }
+
+    /// Whether the entry finished arriving since the panel was last open
+    pub fn is_new(&self, download: &Download) -> bool {
+        !download.in_progress && download.modified > self.seen
+    }
+
+    pub fn new_count(&self) -> usize {
+        self.entries.iter().filter(|download| self.is_new(download)).count()
+    }
+
+    /// Everything listed now has been seen
+    pub fn mark_seen(&mut self) {
+        self.seen = SystemTime::now();
+    }
+
+    /// Re-read the directory if the watcher saw it change; true if it did
+    pub fn poll(&mut self) -> bool {
+        let changed = self.watcher.as_mut().is_some_and(|watcher| !watcher.events().is_empty());
+        if changed {
+            self.reload();
+        }
+        changed
+    }
+
+    /// Read the directory again, for opening the panel where nothing watches it
+    pub fn reload(&mut self) {
+        if let Some(dir) = self.dir.as_deref() {
+            self.entries = read_downloads(dir);
+        }
+    }
+}
+
+/// Visible entries of `dir`, most recently modified first
+fn read_downloads(dir: &Path) -> Vec<Download> {
+    let Ok(read) = fs::read_dir(dir) else { return Vec::new() };
+    let mut entries: Vec<Download> = read
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let path = entry.path();
+            let in_progress = path
+                .extension()
+                .is_some_and(|extension| PARTIAL_EXTENSIONS.contains(&extension.to_string_lossy().to_lowercase().as_str()));
+            Some(Download {
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                is_directory: metadata.is_dir(),
+                in_progress,
+                path,
+            })
+        })
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.modified));
+    entries.truncate(DOWNLOADS_LIMIT);
+    entries
+}
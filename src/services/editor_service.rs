@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::process::Command;
 use crate::core::{ClazyfilerError, Result};
 use crate::model::FileEntry;
@@ -17,7 +18,11 @@ impl EditorService {
         if file.is_directory {
             return Err(ClazyfilerError::editor("editor", "Cannot open directory with editor"));
         }
+        self.open_path(&file.path)
+    }
 
+    /// Open an arbitrary path with the editor and wait for it to exit
+    pub fn open_path(&self, path: &Path) -> Result<()> {
         let editor = self.detect_editor()?;
         
         // Handle cases where $EDITOR might contain arguments (e.g., "code -w")
@@ -27,11 +32,11 @@ impl EditorService {
             for arg in &parts[1..] {
                 command.arg(arg);
             }
-            command.arg(&file.path);
+            command.arg(path);
             command
         } else {
             let mut command = Command::new(&editor);
-            command.arg(&file.path);
+            command.arg(path);
             command
         };
 
@@ -47,7 +52,7 @@ impl EditorService {
     }
 
     /// Detect available editor ($EDITOR first, then vim, then vi)
-    fn detect_editor(&self) -> Result<String> {
+    pub fn detect_editor(&self) -> Result<String> {
         // Check $EDITOR environment variable first
         if let Ok(editor) = std::env::var("EDITOR") {
             if !editor.trim().is_empty() {
@@ -71,10 +76,11 @@ impl EditorService {
         Err(ClazyfilerError::editor("detection", "No suitable editor found ($EDITOR, vim, or vi)"))
     }
 
-    /// Check if a command exists and is executable
+    /// Check if a command exists and is executable; for "code -w" and the like, the program is checked
     fn command_exists(&self, command: &str) -> bool {
+        let Some(program) = command.split_whitespace().next() else { return false };
         Command::new("which")
-            .arg(command)
+            .arg(program)
             .output()
             .map(|output| output.status.success())
             .unwrap_or(false)
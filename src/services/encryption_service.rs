@@ -0,0 +1,458 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use crate::core::{base64_decode, BudgetedCache, CacheWeight, ClazyfilerError, LruCache, Result};
+use crate::services::{EditorService, FileService};
+
+/// First line of a binary age file
+const AGE_HEADER: &str = "age-encryption.org/v1";
+
+const AGE_ARMOR_BEGIN: &str = "-----BEGIN AGE ENCRYPTED FILE-----";
+const PGP_ARMOR_BEGIN: &str = "-----BEGIN PGP MESSAGE-----";
+
+/// Only this much of an age file is read to find its recipient stanzas
+const AGE_HEADER_LIMIT: u64 = 64 * 1024;
+
+/// Decrypted previews longer than this are cut off
+const MAX_PREVIEW_LINES: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EncryptionKind {
+    Gpg,
+    Age,
+}
+
+impl EncryptionKind {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_string_lossy().to_lowercase().as_str() {
+            "gpg" | "pgp" => Some(EncryptionKind::Gpg),
+            "age" => Some(EncryptionKind::Age),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EncryptionKind::Gpg => "GPG",
+            EncryptionKind::Age => "age",
+        }
+    }
+
+    fn command(&self) -> &'static str {
+        match self {
+            EncryptionKind::Gpg => "gpg",
+            EncryptionKind::Age => "age",
+        }
+    }
+}
+
+/// What a file was encrypted to, so it can be encrypted the same way after editing
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeySource {
+    Recipients(Vec<String>),    // GPG key ids, or age stanza types (age does not reveal recipients)
+    Passphrase,
+}
+
+#[derive(Debug, Clone)]
+pub struct EncryptionInfo {
+    pub kind: EncryptionKind,
+    pub keys: KeySource,
+    pub armored: bool,
+}
+
+impl CacheWeight for EncryptionInfo {
+    fn weight(&self) -> usize {
+        match &self.keys {
+            KeySource::Recipients(recipients) => std::mem::size_of::<Self>() + recipients.weight(),
+            KeySource::Passphrase => std::mem::size_of::<Self>(),
+        }
+    }
+}
+
+/// Service responsible for `.gpg`/`.age` files
+/// Decryption runs `gpg`/`age` with the terminal handed over, so pinentry or age can prompt
+#[derive(Debug)]
+pub struct EncryptionService {
+    age_identity: Option<PathBuf>,
+    info_cache: LruCache<PathBuf, std::result::Result<EncryptionInfo, String>>,
+    decrypted: RefCell<HashMap<PathBuf, String>>,    // Previews the user asked to decrypt
+}
+
+impl EncryptionService {
+    pub fn new(age_identity: Option<PathBuf>) -> Self {
+        Self::with_identity(age_identity.or_else(|| {
+            dirs::home_dir().map(|home| home.join(".config").join("age").join("keys.txt")).filter(|path| path.is_file())
+        }))
+    }
+
+    /// Decrypt age files with `age_identity` only, without looking for the default one
+    pub fn with_identity(age_identity: Option<PathBuf>) -> Self {
+        Self {
+            age_identity,
+            info_cache: LruCache::new("encryption"),
+            decrypted: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Check whether a path looks like an encrypted file
+    pub fn is_encrypted(path: &Path) -> bool {
+        EncryptionKind::from_path(path).is_some()
+    }
+
+    /// How the file is encrypted, read without decrypting it
+    pub fn info(&self, path: &Path) -> std::result::Result<EncryptionInfo, String> {
+        if let Some(cached) = self.info_cache.get(path) {
+            return cached;
+        }
+        let info = Self::read_info(path).map_err(|e| e.to_string());
+        self.info_cache.insert(path.to_path_buf(), info.clone());
+        info
+    }
+
+    /// Plaintext preview of a file decrypted with `decrypt_preview`
+    pub fn decrypted_preview(&self, path: &Path) -> Option<String> {
+        self.decrypted.borrow().get(path).cloned()
+    }
+
+    /// Decrypt into memory for the preview; must run with the terminal suspended
+    pub fn decrypt_preview(&self, path: &Path) -> Result<()> {
+        let info = self.info(path).map_err(|e| ClazyfilerError::content(&path.to_string_lossy(), &e))?;
+        let plaintext = self.decrypt(path, &info)?;
+
+        let preview = match String::from_utf8(plaintext) {
+            Ok(text) if !text.contains('\0') => {
+                let mut lines: Vec<&str> = text.lines().take(MAX_PREVIEW_LINES + 1).collect();
+                if lines.len() > MAX_PREVIEW_LINES {
+                    lines.truncate(MAX_PREVIEW_LINES);
+                    lines.push("…");
+                }
+                lines.join("\n")
+            }
+            Ok(text) => binary_notice(text.len()),
+            Err(e) => binary_notice(e.as_bytes().len()),
+        };
+        self.decrypted.borrow_mut().insert(path.to_path_buf(), preview);
+        Ok(())
+    }
+
+    /// Decrypt to a private temporary file, edit it and re-encrypt to the same keys if it changed
+    /// If the original was replaced meanwhile (a sync client, another machine), the edit is kept beside it instead
+    /// Returns whether the file was rewritten; must run with the terminal suspended
+    pub fn edit(&self, path: &Path, editor: &EditorService) -> Result<bool> {
+        let path_str = path.to_string_lossy();
+        let info = self.info(path).map_err(|e| ClazyfilerError::content(&path_str, &e))?;
+        // Fail before decrypting if the edit could not be saved
+        if info.kind == EncryptionKind::Age && info.keys != KeySource::Passphrase {
+            self.identity()?;
+        }
+        let version = file_version(path);
+        let plaintext = self.decrypt(path, &info)?;
+
+        let workspace = PrivateDir::create()?;
+        let file_stem = path.file_stem().map(|stem| stem.to_os_string()).unwrap_or_else(|| "decrypted".into());
+        let plain_path = workspace.path.join(file_stem);
+        write_private(&plain_path, &plaintext)?;
+
+        editor.open_path(&plain_path)?;
+        let edited = std::fs::read(&plain_path)
+            .map_err(|e| ClazyfilerError::file_system("read", plain_path.to_string_lossy().as_ref(), e))?;
+        if edited == plaintext {
+            return Ok(false);
+        }
+
+        // Encrypt next to the original and swap it in, so a failed encryption never loses the file
+        let file_name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let staged = path.with_file_name(format!(".{}.clazyfiler-tmp", file_name));
+        let result = self.encrypt(&info, &plain_path, &staged).and_then(|_| {
+            if let Ok(metadata) = std::fs::metadata(path) {
+                let _ = std::fs::set_permissions(&staged, metadata.permissions());
+            }
+            if file_version(path) != version {
+                let kept = conflict_path(path);
+                std::fs::rename(&staged, &kept).map_err(|e| ClazyfilerError::file_system("rename", &path_str, e))?;
+                let message = format!("Changed while being edited; the edit was saved as {}", kept.display());
+                return Err(ClazyfilerError::content(&path_str, &message));
+            }
+            std::fs::rename(&staged, path).map_err(|e| ClazyfilerError::file_system("rename", &path_str, e))
+        });
+        if result.is_err() {
+            let _ = std::fs::remove_file(&staged);
+        }
+        result.map(|_| true)
+    }
+
+    /// Forget file details and decrypted previews
+    pub fn clear_cache(&self) {
+        self.info_cache.clear();
+        self.decrypted.borrow_mut().clear();
+    }
+
+    /// Caches counted against the memory budget
+    pub fn caches(&self) -> Vec<&dyn BudgetedCache> {
+        vec![&self.info_cache]
+    }
+
+    fn identity(&self) -> Result<&Path> {
+        self.age_identity
+            .as_deref()
+            .ok_or_else(|| ClazyfilerError::config("No age identity found; set encryption.age_identity"))
+    }
+
+    fn decrypt(&self, path: &Path, info: &EncryptionInfo) -> Result<Vec<u8>> {
+        let mut command = Command::new(info.kind.command());
+        match info.kind {
+            EncryptionKind::Gpg => {
+                command.args(["--quiet", "--decrypt"]);
+            }
+            EncryptionKind::Age => {
+                command.arg("--decrypt");
+                // Passphrase-encrypted files are prompted for instead
+                if info.keys != KeySource::Passphrase {
+                    command.arg("-i").arg(self.identity()?);
+                }
+            }
+        }
+        let output = with_gpg_tty(&mut command)
+            .arg(path)
+            .stdin(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .stdout(Stdio::piped())
+            .output()
+            .map_err(|e| ClazyfilerError::external_tool(info.kind.command(), &format!("Failed to launch: {}", e)))?;
+
+        if output.status.success() {
+            Ok(output.stdout)
+        } else {
+            Err(ClazyfilerError::external_tool(info.kind.command(), &format!("Decryption failed ({})", output.status)))
+        }
+    }
+
+    fn encrypt(&self, info: &EncryptionInfo, plain_path: &Path, output: &Path) -> Result<()> {
+        let mut command = Command::new(info.kind.command());
+        match (info.kind, &info.keys) {
+            (EncryptionKind::Gpg, KeySource::Recipients(key_ids)) => {
+                command.args(["--yes", "--encrypt"]);
+                for key_id in key_ids {
+                    command.arg("--recipient").arg(key_id);
+                }
+            }
+            (EncryptionKind::Gpg, KeySource::Passphrase) => {
+                command.args(["--yes", "--symmetric"]);
+            }
+            (EncryptionKind::Age, KeySource::Recipients(_)) => {
+                // age encrypts to the recipient of the identity it decrypted with
+                command.arg("--encrypt").arg("-i").arg(self.identity()?);
+            }
+            (EncryptionKind::Age, KeySource::Passphrase) => {
+                command.args(["--encrypt", "--passphrase"]);
+            }
+        }
+        if info.armored {
+            command.arg("--armor");
+        }
+        let status = with_gpg_tty(&mut command)
+            .arg("--output")
+            .arg(output)
+            .arg(plain_path)
+            .status()
+            .map_err(|e| ClazyfilerError::external_tool(info.kind.command(), &format!("Failed to launch: {}", e)))?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(ClazyfilerError::external_tool(info.kind.command(), &format!("Re-encryption failed ({}); original kept", status)))
+        }
+    }
+
+    fn read_info(path: &Path) -> Result<EncryptionInfo> {
+        let path_str = path.to_string_lossy();
+        let kind = EncryptionKind::from_path(path)
+            .ok_or_else(|| ClazyfilerError::content(&path_str, "Not an encrypted file"))?;
+
+        let mut head = Vec::new();
+        File::open(path)
+            .and_then(|file| file.take(AGE_HEADER_LIMIT).read_to_end(&mut head))
+            .map_err(|e| ClazyfilerError::file_system("read", &path_str, e))?;
+        let text = String::from_utf8_lossy(&head);
+
+        match kind {
+            EncryptionKind::Gpg => {
+                let armored = text.trim_start().starts_with(PGP_ARMOR_BEGIN);
+                let keys = gpg_key_source(path).ok_or_else(|| ClazyfilerError::content(&path_str, "Not an OpenPGP encrypted message"))?;
+                Ok(EncryptionInfo { kind, keys, armored })
+            }
+            EncryptionKind::Age => {
+                let armored = text.trim_start().starts_with(AGE_ARMOR_BEGIN);
+                let header = if armored { decode_age_armor(&text) } else { head };
+                let keys = age_key_source(&header).ok_or_else(|| ClazyfilerError::content(&path_str, "Not an age encrypted file"))?;
+                Ok(EncryptionInfo { kind, keys, armored })
+            }
+        }
+    }
+}
+
+/// Ask gpg which keys a message is encrypted to, without decrypting it
+fn gpg_key_source(path: &Path) -> Option<KeySource> {
+    let output = Command::new("gpg")
+        .args(["--batch", "--no-tty", "--status-fd", "1", "--list-only", "--list-packets"])
+        .arg(path)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    let status = String::from_utf8_lossy(&output.stdout);
+    let mut key_ids = Vec::new();
+    let mut encrypted = false;
+    for line in status.lines().filter_map(|line| line.strip_prefix("[GNUPG:] ")) {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("ENC_TO") => key_ids.extend(words.next().map(str::to_string)),
+            Some("BEGIN_DECRYPTION") => encrypted = true,
+            _ => {}
+        }
+    }
+
+    if !key_ids.is_empty() {
+        Some(KeySource::Recipients(key_ids))
+    } else if encrypted {
+        Some(KeySource::Passphrase)
+    } else {
+        None
+    }
+}
+
+/// Read the recipient stanzas (`-> <type> ...`) from an age header
+fn age_key_source(header: &[u8]) -> Option<KeySource> {
+    let text = String::from_utf8_lossy(header);
+    let mut lines = text.lines();
+    if lines.next()? != AGE_HEADER {
+        return None;
+    }
+
+    let mut stanzas = Vec::new();
+    for line in lines {
+        if line.starts_with("---") {
+            break;
+        }
+        if let Some(stanza) = line.strip_prefix("-> ") {
+            stanzas.extend(stanza.split_whitespace().next().map(str::to_string));
+        }
+    }
+
+    // age only allows scrypt as the sole stanza
+    match stanzas.first().map(String::as_str) {
+        Some("scrypt") => Some(KeySource::Passphrase),
+        Some(_) => Some(KeySource::Recipients(stanzas)),
+        None => None,
+    }
+}
+
+/// Base64 body of an armored age file (only the header is needed, so a truncated body is fine)
+fn decode_age_armor(text: &str) -> Vec<u8> {
+    let body: Vec<&str> = text
+        .lines()
+        .skip_while(|line| line.trim() != AGE_ARMOR_BEGIN)
+        .skip(1)
+        .take_while(|line| !line.starts_with("-----"))
+        .collect();
+    base64_decode(&body.concat())
+}
+
+/// pinentry-curses needs to know the terminal; gpg picks it up from $GPG_TTY
+fn with_gpg_tty(command: &mut Command) -> &mut Command {
+    if std::env::var_os("GPG_TTY").is_none() {
+        if let Ok(tty) = std::fs::read_link("/proc/self/fd/0") {
+            command.env("GPG_TTY", tty);
+        }
+    }
+    command
+}
+
+fn binary_notice(size: usize) -> String {
+    format!("🔧 Decrypted content is binary ({})", FileService::format_file_size(size as u64))
+}
+
+/// Size and modification time, compared to tell whether a file was replaced during an edit
+fn file_version(path: &Path) -> Option<(u64, Option<std::time::SystemTime>)> {
+    std::fs::metadata(path).ok().map(|metadata| (metadata.len(), metadata.modified().ok()))
+}
+
+/// `notes.edited.gpg` beside `notes.gpg`, numbered when taken, so the kept edit still decrypts by extension
+fn conflict_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = path.extension().map(|extension| format!(".{}", extension.to_string_lossy())).unwrap_or_default();
+    (1..)
+        .map(|number| match number {
+            1 => path.with_file_name(format!("{}.edited{}", stem, extension)),
+            _ => path.with_file_name(format!("{}.edited-{}{}", stem, number, extension)),
+        })
+        .find(|candidate| !candidate.exists())
+        .unwrap_or_else(|| path.with_file_name(format!("{}.edited{}", stem, extension)))
+}
+
+/// Write plaintext readable only by the current user
+fn write_private(path: &Path, data: &[u8]) -> Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(data))
+        .map_err(|e| ClazyfilerError::file_system("write", path.to_string_lossy().as_ref(), e))
+}
+
+/// Owner-only directory for decrypted files, wiped and removed on drop
+/// Editor swap and backup files land here too, so they go with it
+struct PrivateDir {
+    path: PathBuf,
+}
+
+impl PrivateDir {
+    fn create() -> Result<Self> {
+        // Prefer the per-user runtime dir, which is usually a tmpfs
+        let base = dirs::runtime_dir().unwrap_or_else(std::env::temp_dir);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.subsec_nanos())
+            .unwrap_or(0);
+        let path = base.join(format!("clazyfiler-{}-{:08x}", std::process::id(), nanos));
+
+        let mut builder = std::fs::DirBuilder::new();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            builder.mode(0o700);
+        }
+        builder
+            .create(&path)
+            .map_err(|e| ClazyfilerError::file_system("create_dir", path.to_string_lossy().as_ref(), e))?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for PrivateDir {
+    fn drop(&mut self) {
+        if let Ok(entries) = std::fs::read_dir(&self.path) {
+            for entry in entries.flatten() {
+                let Ok(metadata) = entry.metadata() else { continue };
+                if !metadata.is_file() {
+                    continue;
+                }
+                // Overwrite before unlinking so the plaintext does not linger in freed blocks
+                if let Ok(mut file) = OpenOptions::new().write(true).open(entry.path()) {
+                    let _ = file.write_all(&vec![0u8; metadata.len() as usize]);
+                    let _ = file.sync_all();
+                }
+            }
+        }
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
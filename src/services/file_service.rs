@@ -1,22 +1,276 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::io::Read;
-use crate::core::{ClazyfilerError, Result};
-use crate::model::FileEntry;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+use crate::core::{is_extended_acl, is_transient, parse_acl, AclEntry, BudgetedCache, CacheWeight, LruCache, metadata_mode, parent_path, text_preview, ClazyfilerError, CreationMode, IoThrottle, JobControl, Result, RetryPolicy};
+use crate::model::{FileEntry, ListSort, SortKey};
+
+/// Allocated and apparent sizes closer than this (or 10% of the length) are treated as equal
+const SIGNIFICANT_SIZE_DIFFERENCE: u64 = 64 * 1024;
+
+/// Single-child directory chains are collapsed at most this many levels deep
+const MAX_CHAIN_DEPTH: usize = 16;
+
+/// Interrupted copies of files this large keep what they wrote, so copying again resumes there
+const RESUMABLE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Overwrites of a shredded file: noise for all but the last, which writes zeros
+const SHRED_PASSES: u64 = 3;
+
+/// Tail of a kept partial copy compared with the source before resuming from it
+const RESUME_CHECK_LEN: u64 = 64 * 1024;
+
+/// Metadata shown in the properties panel
+#[derive(Debug, Clone)]
+pub struct FileProperties {
+    pub path: PathBuf,
+    pub kind: &'static str,
+    pub link_target: Option<PathBuf>,
+    pub size: u64,                  // Apparent size (the file's length)
+    pub allocated: Option<u64>,     // Bytes of disk blocks in use (Unix only)
+    pub mode: Option<u32>,
+    pub hard_links: Option<u64>,
+    pub attributes: Option<u32>,    // Inode flags set with chattr (Linux only)
+    pub security_context: Option<String>,  // SELinux label, e.g. `system_u:object_r:httpd_sys_content_t:s0`
+    pub acl: Vec<AclEntry>,         // POSIX ACL, only when it says more than the mode bits
+    pub default_acl: Vec<AclEntry>, // ACL new entries of a directory inherit
+    pub modified: Option<SystemTime>,
+    pub accessed: Option<SystemTime>,
+}
+
+impl FileProperties {
+    /// Length divided by on-disk size, above 1 for sparse or compressed files
+    pub fn compression_ratio(&self) -> Option<f64> {
+        let allocated = self.allocated.filter(|&allocated| allocated > 0)?;
+        Some(self.size as f64 / allocated as f64)
+    }
+
+    /// Explain a significant difference between on-disk and apparent size of a file
+    pub fn storage_note(&self) -> Option<String> {
+        let allocated = self.allocated.filter(|_| self.kind != "Directory")?;
+        let slack = (self.size / 10).max(SIGNIFICANT_SIZE_DIFFERENCE);
+        if allocated == 0 && self.size > slack {
+            Some("Entirely sparse: no blocks allocated".to_string())
+        } else if allocated + slack < self.size {
+            Some(format!(
+                "Sparse or compressed: {:.0}% of its length is stored on disk",
+                allocated as f64 * 100.0 / self.size as f64
+            ))
+        } else if allocated > self.size + slack {
+            Some(format!(
+                "Preallocated: {} on disk beyond its length",
+                FileService::format_file_size(allocated - self.size)
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Summary of a directory's immediate children for the detail pane
+#[derive(Debug, Clone, Default)]
+pub struct DirectoryStats {
+    pub files: usize,
+    pub directories: usize,
+    pub total_size: u64,                      // Of the files among the children whose size is known
+    pub newest: Option<(String, SystemTime)>, // Most recently modified child
+    pub extensions: Vec<(String, usize)>,     // Lowercased extension ("" for none) and how many files have it, most common first
+    pub modified: Option<SystemTime>,         // Of the directory itself when counted, telling when the counts went stale
+}
+
+impl DirectoryStats {
+    pub fn from_children(children: &[FileEntry]) -> Self {
+        let mut stats = Self::default();
+        let mut extensions: HashMap<String, usize> = HashMap::new();
+        for child in children {
+            if child.is_directory {
+                stats.directories += 1;
+            } else {
+                stats.files += 1;
+                stats.total_size += child.size.unwrap_or(0);
+                let extension = Path::new(&child.name).extension().map(|ext| ext.to_string_lossy().to_lowercase()).unwrap_or_default();
+                *extensions.entry(extension).or_default() += 1;
+            }
+            if let Some(modified) = child.modified {
+                if stats.newest.as_ref().is_none_or(|(_, newest)| modified > *newest) {
+                    stats.newest = Some((child.name.clone(), modified));
+                }
+            }
+        }
+        stats.extensions = extensions.into_iter().collect();
+        stats.extensions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        stats
+    }
+}
+
+impl CacheWeight for DirectoryStats {
+    fn weight(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.newest.as_ref().map(|(name, _)| name.len()).unwrap_or(0)
+            + self.extensions.iter().map(|(extension, _)| extension.len() + std::mem::size_of::<(String, usize)>()).sum::<usize>()
+    }
+}
+
+/// Empty files that mark something by existing, never offered as leftovers
+const MARKER_FILES: &[&str] = &["__init__.py", "py.typed", ".gitkeep", ".keep", ".nojekyll"];
+
+/// Why a maintenance scan offers an entry for cleanup
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeftoverKind {
+    BrokenSymlink,
+    EmptyFile,
+    EmptyDirectory,
+}
+
+impl LeftoverKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LeftoverKind::BrokenSymlink => "broken link",
+            LeftoverKind::EmptyFile => "empty file",
+            LeftoverKind::EmptyDirectory => "empty dir",
+        }
+    }
+}
+
+/// An entry a maintenance scan found that most likely serves no purpose
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leftover {
+    pub path: PathBuf,
+    pub kind: LeftoverKind,
+}
 
 /// Service responsible for all file system operations
 /// Extracted from AppState to separate concerns and improve testability
 #[derive(Debug)]
-pub struct FileService;
+pub struct FileService {
+    lazy_metadata: Cell<bool>,        // Skip per-entry stats (network filesystems)
+    retry_policy: Cell<RetryPolicy>,  // Retries of transient errors (network filesystems)
+    retries_exhausted: Cell<bool>,    // A call already failed after all retries in this listing or scan
+    unreadable: Cell<usize>,          // Entries of the last listing or scan whose metadata or contents could not be read
+    first_unreadable_error: RefCell<Option<String>>,
+    throttle: IoThrottle,             // Limits the throughput of copies
+    job: Option<JobControl>,          // Pause switch of the job this service works for, on a worker thread
+    stats_cache: LruCache<PathBuf, DirectoryStats>,
+}
 
 impl FileService {
     pub fn new() -> Self {
-        Self
+        Self {
+            lazy_metadata: Cell::new(false),
+            retry_policy: Cell::new(RetryPolicy::NONE),
+            retries_exhausted: Cell::new(false),
+            unreadable: Cell::new(0),
+            first_unreadable_error: RefCell::new(None),
+            throttle: IoThrottle::unlimited(),
+            job: None,
+            stats_cache: LruCache::new("directory stats"),
+        }
+    }
+
+    /// A service for a job's worker thread, retrying and throttled like this one, that holds copies while paused
+    pub fn for_job(&self, job: JobControl) -> Self {
+        Self {
+            lazy_metadata: Cell::new(self.lazy_metadata.get()),
+            retry_policy: Cell::new(self.retry_policy.get()),
+            throttle: self.throttle.clone(),
+            job: Some(job),
+            ..Self::new()
+        }
+    }
+
+    /// Share a throttle with the other background jobs
+    pub fn set_throttle(&mut self, throttle: IoThrottle) {
+        self.throttle = throttle;
+    }
+
+    /// Counts of a directory's children, read once and again only after the directory changed
+    pub fn directory_stats(&self, dir: &Path) -> Result<DirectoryStats> {
+        let modified = fs::metadata(dir).and_then(|metadata| metadata.modified()).ok();
+        if let Some(stats) = self.stats_cache.get(dir).filter(|stats| modified.is_some() && stats.modified == modified) {
+            return Ok(stats);
+        }
+        let stats = DirectoryStats { modified, ..DirectoryStats::from_children(&self.read_directory(dir)?) };
+        self.stats_cache.insert(dir.to_path_buf(), stats.clone());
+        Ok(stats)
+    }
+
+    /// Forget counted directories (e.g. after a refresh, as sizes change without touching the directory)
+    pub fn clear_cache(&self) {
+        self.stats_cache.clear();
+    }
+
+    /// Caches counted against the memory budget
+    pub fn caches(&self) -> Vec<&dyn BudgetedCache> {
+        vec![&self.stats_cache]
+    }
+
+    /// Read listings without a stat per entry from now on (or go back to full metadata)
+    pub fn set_lazy_metadata(&self, lazy: bool) {
+        self.lazy_metadata.set(lazy);
+    }
+
+    /// Degraded mode for network filesystems: listings without per-entry stats, and transient errors retried
+    pub fn set_network(&self, network: bool, retries: RetryPolicy) {
+        self.set_lazy_metadata(network);
+        self.retry_policy.set(if network { retries } else { RetryPolicy::NONE });
+    }
+
+    /// Run an I/O call under the retry policy; once retries ran out during a listing or scan the rest of it
+    /// fails fast, since a mount that stays unreachable would otherwise stall on every entry
+    fn retrying<T>(&self, operation: impl FnMut() -> std::io::Result<T>) -> std::io::Result<T> {
+        let policy = if self.retries_exhausted.get() { RetryPolicy::NONE } else { self.retry_policy.get() };
+        let result = policy.run(operation);
+        if result.as_ref().is_err_and(is_transient) {
+            self.retries_exhausted.set(true);
+        }
+        result
+    }
+
+    /// How many entries the last `read_directory` or `scan_directory_tree` could not read, and why the first one
+    pub fn unreadable_entries(&self) -> (usize, Option<String>) {
+        (self.unreadable.get(), self.first_unreadable_error.borrow().clone())
+    }
+
+    /// Start counting for a new listing or scan
+    fn reset_unreadable(&self) {
+        self.retries_exhausted.set(false);
+        self.unreadable.set(0);
+        self.first_unreadable_error.replace(None);
+    }
+
+    /// Count an entry that could not be read, instead of printing over the terminal
+    fn note_unreadable(&self, error: String) {
+        self.unreadable.set(self.unreadable.get() + 1);
+        self.first_unreadable_error.borrow_mut().get_or_insert(error);
+    }
+
+    /// Entry whose metadata failed: the listing still shows it, typed from the directory listing itself
+    fn unreadable_entry(&self, entry: &fs::DirEntry, error: std::io::Error) -> FileEntry {
+        let name = entry.file_name().to_string_lossy().to_string();
+        self.note_unreadable(format!("{}: {}", name, error));
+        FileEntry {
+            name,
+            path: entry.path(),
+            is_directory: entry.file_type().is_ok_and(|file_type| file_type.is_dir()),
+            size: None,
+            modified: None,
+            mode: None,
+            link_target: link_target(entry),
+            metadata_error: Some(error.to_string()),
+        }
     }
 
     /// Read directory contents and return sorted file entries
     pub fn read_directory(&self, dir_path: &Path) -> Result<Vec<FileEntry>> {
-        let entries = fs::read_dir(dir_path)
+        self.reset_unreadable();
+        if self.lazy_metadata.get() {
+            return self.read_directory_lazy(dir_path);
+        }
+
+        let entries = self.retrying(|| fs::read_dir(dir_path))
             .map_err(|e| ClazyfilerError::file_system("read_dir", dir_path.to_string_lossy().as_ref(), e))?;
 
         let mut files = Vec::new();
@@ -24,41 +278,117 @@ impl FileService {
         for entry in entries {
             match entry {
                 Ok(entry) => {
-                    match entry.metadata() {
+                    match self.retrying(|| entry.metadata()) {
                         Ok(metadata) => {
                             let file_entry = FileEntry {
                                 name: entry.file_name().to_string_lossy().to_string(),
                                 path: entry.path(),
                                 is_directory: metadata.is_dir(),
                                 size: if metadata.is_file() { Some(metadata.len()) } else { None },
+                                modified: metadata.modified().ok(),
+                                mode: metadata_mode(&metadata),
+                                link_target: link_target(&entry),
+                                metadata_error: None,
                             };
                             files.push(file_entry);
                         }
-                        Err(e) => {
-                            // Log warning but continue processing other files
-                            eprintln!("Warning: Failed to read metadata for {}: {}", entry.path().display(), e);
-                        }
+                        // Listed with its error rather than dropped, so it can still be renamed, marked or removed
+                        Err(e) => files.push(self.unreadable_entry(&entry, e)),
                     }
                 }
                 Err(e) => {
-                    // Log warning but continue processing other files
-                    eprintln!("Warning: Failed to read directory entry: {}", e);
+                    // Continue with the other files
+                    self.note_unreadable(e.to_string());
                 }
             }
         }
 
-        // Sort: directories first, then files, both alphabetically
-        files.sort_by(|a, b| {
-            match (a.is_directory, b.is_directory) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-            }
-        });
+        Self::sort_entries(&mut files, ListSort::default());
+        Ok(files)
+    }
 
+    /// Listing without a stat per entry: types come from the directory listing itself
+    /// and sizes are left unknown (filled in on preview)
+    fn read_directory_lazy(&self, dir_path: &Path) -> Result<Vec<FileEntry>> {
+        let entries = self.retrying(|| fs::read_dir(dir_path))
+            .map_err(|e| ClazyfilerError::file_system("read_dir", dir_path.to_string_lossy().as_ref(), e))?;
+
+        let mut files: Vec<FileEntry> = entries
+            .filter_map(|entry| entry.map_err(|e| self.note_unreadable(e.to_string())).ok())
+            .map(|entry| {
+                let path = entry.path();
+                let is_directory = match entry.file_type() {
+                    // Only symlinks need a stat to find out what they point to
+                    Ok(file_type) if file_type.is_symlink() => path.is_dir(),
+                    Ok(file_type) => file_type.is_dir(),
+                    Err(_) => path.is_dir(),
+                };
+                FileEntry {
+                    name: entry.file_name().to_string_lossy().to_string(),
+                    path,
+                    is_directory,
+                    size: None,
+                    modified: None,
+                    mode: None,
+                    link_target: link_target(&entry),
+                    metadata_error: None,
+                }
+            })
+            .collect();
+
+        Self::sort_entries(&mut files, ListSort::default());
         Ok(files)
     }
 
+    /// Collapse directories that hold nothing but a single subdirectory into one entry, GitHub style:
+    /// `src` containing only `main/java/com` becomes `src/main/java/com`, pointing at the deepest directory
+    /// Breadth-limited: listings with more than `max_probes` directories are left alone, since every
+    /// directory costs a listing of its own
+    pub fn collapse_directory_chains(&self, files: &mut [FileEntry], max_probes: usize) {
+        if files.iter().filter(|file| file.is_directory).count() > max_probes {
+            return;
+        }
+        for file in files.iter_mut().filter(|file| file.is_directory) {
+            let mut deepest = file.path.clone();
+            let mut name = file.name.clone();
+            for _ in 0..MAX_CHAIN_DEPTH {
+                let Some(child) = Self::only_subdirectory(&deepest) else { break };
+                name = format!("{}/{}", name, child.file_name().unwrap_or_default().to_string_lossy());
+                deepest = child;
+            }
+            file.name = name;
+            file.path = deepest;
+        }
+        Self::sort_entries(files, ListSort::default());
+    }
+
+    /// The single entry of `dir` when it is a real directory (symlinks are not followed, so chains cannot loop)
+    fn only_subdirectory(dir: &Path) -> Option<PathBuf> {
+        let mut entries = fs::read_dir(dir).ok()?.flatten();
+        let only = entries.next()?;
+        if entries.next().is_some() || !only.file_type().ok()?.is_dir() {
+            return None;
+        }
+        Some(only.path())
+    }
+
+    /// Sort: directories first, then files, both by `sort` with ties broken alphabetically
+    pub fn sort_entries(files: &mut [FileEntry], sort: ListSort) {
+        let extension = |file: &FileEntry| file.path.extension().map(|ext| ext.to_string_lossy().to_lowercase());
+        files.sort_by(|a, b| {
+            let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+            let by_key = match sort.key {
+                SortKey::Name => by_name(),
+                SortKey::Size => a.size.cmp(&b.size),
+                SortKey::Modified => a.modified.cmp(&b.modified),
+                SortKey::Extension => extension(a).cmp(&extension(b)),
+            };
+            let by_key = if sort.descending { by_key.reverse() } else { by_key };
+            // Directories first, regardless of direction
+            b.is_directory.cmp(&a.is_directory).then(by_key).then_with(by_name)
+        });
+    }
+
     /// Read file content for display, with size and binary detection
     pub fn read_file_content(&self, file: &FileEntry) -> Result<String> {
         if file.is_directory {
@@ -66,10 +396,9 @@ impl FileService {
         }
 
         const MAX_FILE_SIZE: u64 = 1024 * 1024; // 1MB limit
-        const MAX_PREVIEW_LINES: usize = 100;
 
-        // Check file size
-        if let Some(size) = file.size {
+        // Check file size (stat now if the listing was read without metadata)
+        if let Some(size) = file.size.or_else(|| fs::metadata(&file.path).ok().map(|metadata| metadata.len())) {
             if size > MAX_FILE_SIZE {
                 return Ok(format!(
                     "📄 File too large to preview\n\nSize: {}\nPath: {}\n\nUse external editor to view this file.",
@@ -79,42 +408,10 @@ impl FileService {
             }
         }
 
-        let mut file_handle = fs::File::open(&file.path)
-            .map_err(|e| ClazyfilerError::file_system("open", file.path.to_string_lossy().as_ref(), e))?;
-
-        let mut buffer = Vec::new();
-        file_handle.read_to_end(&mut buffer)
+        let buffer = self.retrying(|| fs::read(&file.path))
             .map_err(|e| ClazyfilerError::file_system("read", file.path.to_string_lossy().as_ref(), e))?;
 
-        // Check if file contains binary data
-        if buffer.iter().any(|&b| b == 0 || (b < 32 && b != b'\n' && b != b'\r' && b != b'\t')) {
-            return Ok(format!(
-                "🔧 Binary file detected\n\nSize: {} bytes\nPath: {}\n\nThis appears to be a binary file and cannot be displayed as text.",
-                buffer.len(),
-                file.path.display()
-            ));
-        }
-
-        // Convert to string and limit lines
-        match String::from_utf8(buffer) {
-            Ok(content) => {
-                let lines: Vec<&str> = content.lines().collect();
-                if lines.len() > MAX_PREVIEW_LINES {
-                    Ok(format!(
-                        "📝 Text File Preview (first {} lines)\n\n{}\n\n... ({} more lines)",
-                        MAX_PREVIEW_LINES,
-                        lines[..MAX_PREVIEW_LINES].join("\n"),
-                        lines.len() - MAX_PREVIEW_LINES
-                    ))
-                } else {
-                    Ok(format!("📝 Text File Content\n\n{}", content))
-                }
-            },
-            Err(_) => Ok(format!(
-                "⚠️ Invalid UTF-8 encoding\n\nPath: {}\n\nFile contains non-UTF-8 data and cannot be displayed.",
-                file.path.display()
-            ))
-        }
+        Ok(text_preview(buffer, &file.path))
     }
 
     /// List directory children for display
@@ -145,7 +442,7 @@ impl FileService {
     }
 
     /// Format file size helper
-    fn format_file_size(size: u64) -> String {
+    pub fn format_file_size(size: u64) -> String {
         const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
         let mut size_f = size as f64;
         let mut unit_index = 0;
@@ -163,14 +460,926 @@ impl FileService {
     }
 
 
+    /// The README of `dir` in any case and with any extension, previewed in place of the listing
+    /// Markdown is preferred when there are several, then plain text
+    pub fn find_readme(&self, dir: &Path) -> Option<PathBuf> {
+        const PREFERRED: &[&str] = &["md", "markdown", "rst", "txt", "adoc", "org", ""];
+        let rank = |path: &Path| {
+            let extension = path.extension().map(|extension| extension.to_string_lossy().to_lowercase()).unwrap_or_default();
+            PREFERRED.iter().position(|preferred| *preferred == extension).unwrap_or(PREFERRED.len())
+        };
+        fs::read_dir(dir).ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.file_stem().is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case("readme")))
+            .filter(|path| path.is_file())
+            .min_by(|a, b| rank(a).cmp(&rank(b)).then_with(|| a.cmp(b)))
+    }
+
     /// Get parent directory of a given path
+    /// Stops at drive and `\\server\share` roots on Windows
     pub fn get_parent_dir(&self, path: &Path) -> Option<PathBuf> {
-        path.parent().map(|p| p.to_path_buf())
+        parent_path(path)
+    }
+
+    /// Resolve a path mentioned in a file's content relative to `base_dir`
+    /// Handles `~/`, absolute paths and `path:line[:col]` suffixes; returns None if nothing exists there
+    pub fn resolve_reference(&self, base_dir: &Path, reference: &str) -> Option<PathBuf> {
+        let expand = |raw: &str| -> PathBuf {
+            if let Some(rest) = raw.strip_prefix("~/") {
+                if let Some(home) = dirs::home_dir() {
+                    return home.join(rest);
+                }
+            }
+            let path = Path::new(raw);
+            if path.is_absolute() { path.to_path_buf() } else { base_dir.join(path) }
+        };
+
+        // Try the reference as-is, then without trailing `:line` / `:line:col` suffixes
+        let mut candidate = reference;
+        loop {
+            let path = expand(candidate);
+            if path.exists() {
+                return Some(path);
+            }
+            match candidate.rsplit_once(':') {
+                Some((rest, suffix)) if !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_digit()) => {
+                    candidate = rest;
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    /// Metadata for the properties panel; symlinks are described and then followed
+    pub fn properties(&self, path: &Path) -> Result<FileProperties> {
+        let path_str = path.to_string_lossy();
+        let link_metadata = fs::symlink_metadata(path)
+            .map_err(|e| ClazyfilerError::file_system("symlink_metadata", &path_str, e))?;
+        let link_target = if link_metadata.file_type().is_symlink() { fs::read_link(path).ok() } else { None };
+        // A dangling link still gets its own metadata shown
+        let metadata = fs::metadata(path).unwrap_or_else(|_| link_metadata.clone());
+
+        let kind = if link_target.is_some() {
+            "Symlink"
+        } else if metadata.is_dir() {
+            "Directory"
+        } else if metadata.is_file() {
+            "File"
+        } else {
+            "Special file"
+        };
+
+        #[cfg(unix)]
+        let (allocated, mode, hard_links) = {
+            use std::os::unix::fs::MetadataExt;
+            // st_blocks is always counted in 512-byte units
+            (Some(metadata.blocks() * 512), Some(metadata.mode()), Some(metadata.nlink()))
+        };
+        #[cfg(not(unix))]
+        let (allocated, mode, hard_links) = (None, None, None);
+        let attributes = if link_target.is_some() { None } else { Self::attributes(path) };
+        let security_context = Self::extended_attribute(path, "security.selinux")
+            .map(|label| String::from_utf8_lossy(&label).trim_end_matches('\0').to_string());
+        let acl = |name: &str| Self::extended_attribute(path, name).as_deref().and_then(parse_acl).unwrap_or_default();
+        let access_acl = acl("system.posix_acl_access");
+
+        Ok(FileProperties {
+            path: path.to_path_buf(),
+            kind,
+            link_target,
+            size: metadata.len(),
+            allocated,
+            mode,
+            hard_links,
+            attributes,
+            security_context,
+            acl: if is_extended_acl(&access_acl) { access_acl } else { Vec::new() },
+            default_acl: acl("system.posix_acl_default"),
+            modified: metadata.modified().ok(),
+            accessed: metadata.accessed().ok(),
+        })
+    }
+
+    /// Write `data` to `<dir>/<stem>.<extension>`, adding `-1`, `-2`, ... if that name is taken
+    pub fn write_new_file(&self, dir: &Path, stem: &str, extension: &str, data: &[u8]) -> Result<PathBuf> {
+        for attempt in 0..1000 {
+            let name = if attempt == 0 {
+                format!("{}.{}", stem, extension)
+            } else {
+                format!("{}-{}.{}", stem, attempt, extension)
+            };
+            let path = dir.join(name);
+            // create_new so a file appearing in the meantime is never overwritten
+            match fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(data)
+                        .map_err(|e| ClazyfilerError::file_system("write", path.to_string_lossy().as_ref(), e))?;
+                    return Ok(path);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(ClazyfilerError::file_system("create", path.to_string_lossy().as_ref(), e)),
+            }
+        }
+        Err(ClazyfilerError::content(dir.to_string_lossy().as_ref(), &format!("No free name for {}.{}", stem, extension)))
+    }
+
+    /// Create an empty file, failing if something already exists at `path`
+    pub fn create_file(&self, path: &Path, mode: CreationMode) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(mode.mode);
+        }
+        options.open(path).map_err(|e| ClazyfilerError::file_system("create", &path_str, e))?;
+        Self::apply_mode(path, mode)
+    }
+
+    /// Create a directory, failing if something already exists at `path`
+    pub fn create_directory(&self, path: &Path, mode: CreationMode) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        let mut builder = fs::DirBuilder::new();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            builder.mode(mode.mode);
+        }
+        builder.create(path).map_err(|e| ClazyfilerError::file_system("create_dir", &path_str, e))?;
+        Self::apply_mode(path, mode)
+    }
+
+    /// The umask only ever removes bits, so configured modes are set again after creation
+    fn apply_mode(path: &Path, mode: CreationMode) -> Result<()> {
+        #[cfg(unix)]
+        if mode.needs_chmod() {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(mode.mode))
+                .map_err(|e| ClazyfilerError::file_system("chmod", path.to_string_lossy().as_ref(), e))?;
+        }
+        #[cfg(not(unix))]
+        let _ = (path, mode);
+        Ok(())
+    }
+
+    /// Create a symlink at `link` pointing to `target`, refusing to replace an existing entry
+    pub fn create_symlink(&self, target: &Path, link: &Path) -> Result<()> {
+        let link_str = link.to_string_lossy();
+        if link.symlink_metadata().is_ok() {
+            return Err(ClazyfilerError::content(&link_str, "Already exists"));
+        }
+        #[cfg(unix)]
+        let created = std::os::unix::fs::symlink(target, link);
+        #[cfg(windows)]
+        let created = if target.is_dir() {
+            std::os::windows::fs::symlink_dir(target, link)
+        } else {
+            std::os::windows::fs::symlink_file(target, link)
+        };
+        created.map_err(|e| ClazyfilerError::file_system("symlink", &link_str, e))
+    }
+
+    /// Set the modification time of an existing entry to now, as `touch` does (following symlinks)
+    pub fn touch(&self, path: &Path) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        fs::File::open(path)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+            .map_err(|e| ClazyfilerError::file_system("touch", &path_str, e))
+    }
+
+    /// Set the permission bits of an entry (following symlinks, as chmod does)
+    pub fn set_permissions(&self, path: &Path, mode: u32) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(mode & 0o7777))
+                .map_err(|e| ClazyfilerError::file_system("chmod", &path_str, e))
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = mode;
+            Err(ClazyfilerError::content(&path_str, "Permission bits are only supported on Unix"))
+        }
+    }
+
+    /// Give an entry to another user and group, the link itself for a symlink
+    pub fn set_owner(&self, path: &Path, uid: u32, gid: u32) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::lchown(path, Some(uid), Some(gid))
+                .map_err(|e| ClazyfilerError::file_system("chown", &path_str, e))
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (uid, gid);
+            Err(ClazyfilerError::content(&path_str, "Ownership is only supported on Unix"))
+        }
+    }
+
+    /// Inode flags of an entry as `lsattr` reads them; None off Linux and on filesystems without them
+    pub fn attributes(path: &Path) -> Option<u32> {
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::fd::AsRawFd;
+            use std::os::unix::fs::OpenOptionsExt;
+            // Non-blocking so FIFOs and devices don't hang the open
+            let file = fs::OpenOptions::new().read(true).custom_flags(libc::O_NONBLOCK).open(path).ok()?;
+            let mut flags: libc::c_int = 0;
+            // SAFETY: the descriptor is open for the duration of the call and `flags` outlives it
+            let result = unsafe { libc::ioctl(file.as_raw_fd(), libc::FS_IOC_GETFLAGS, &mut flags) };
+            (result == 0).then_some(flags as u32)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = path;
+            None
+        }
+    }
+
+    /// Value of the extended attribute `name` of an entry itself (symlinks not followed); None off Linux
+    /// and when it is not set
+    pub fn extended_attribute(path: &Path, name: &str) -> Option<Vec<u8>> {
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+            let name = std::ffi::CString::new(name).ok()?;
+            // SAFETY: both strings are NUL-terminated; a null buffer of length 0 only asks for the size
+            let size = unsafe { libc::lgetxattr(path.as_ptr(), name.as_ptr(), std::ptr::null_mut(), 0) };
+            let mut value = vec![0u8; usize::try_from(size).ok()?];
+            // SAFETY: value is writable for the length passed; a larger value since the size query fails with ERANGE
+            let read = unsafe { libc::lgetxattr(path.as_ptr(), name.as_ptr(), value.as_mut_ptr().cast(), value.len()) };
+            value.truncate(usize::try_from(read).ok()?);
+            Some(value)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (path, name);
+            None
+        }
+    }
+
+    /// Set or clear the chattr attribute `letter` of an entry by running `chattr`
+    /// `elevate` runs it through sudo on the inherited terminal, which must be suspended so sudo can ask
+    /// for a password; changing `i` and `a` needs root
+    pub fn set_attribute(&self, path: &Path, letter: char, on: bool, elevate: bool) -> Result<()> {
+        let flag = format!("{}{}", if on { '+' } else { '-' }, letter);
+        let failed = |reason: &str| ClazyfilerError::external_tool("chattr", reason);
+        let launch = |e: std::io::Error| failed(&format!("Failed to launch: {}", e));
+        if elevate {
+            let status = Command::new("sudo").arg("chattr").arg(&flag).arg("--").arg(path).status().map_err(launch)?;
+            return if status.success() { Ok(()) } else { Err(failed(&format!("Failed ({})", status))) };
+        }
+        let output = Command::new("chattr").arg(&flag).arg("--").arg(path).stdin(Stdio::null()).output().map_err(launch)?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(failed(String::from_utf8_lossy(&output.stderr).trim()))
+        }
+    }
+
+    /// Rename `from` to `to`, refusing to replace an existing entry
+    /// On case-insensitive filesystems (macOS, Windows) a case-only change like `readme.md` -> `README.md`
+    /// finds `to` already "existing" as the file itself, so it is moved through a temporary name instead
+    pub fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        let to_str = to.to_string_lossy();
+        if from == to {
+            return Ok(());
+        }
+        if fs::symlink_metadata(to).is_err() {
+            return fs::rename(from, to).map_err(|e| ClazyfilerError::file_system("rename", &to_str, e));
+        }
+        // Hard links differing only in case on a case-sensitive filesystem are separate entries
+        if !Self::is_case_only_rename(from, to) || !Self::is_same_entry(from, to) || Self::lists_entry(to) {
+            let exists = std::io::Error::from(std::io::ErrorKind::AlreadyExists);
+            return Err(ClazyfilerError::file_system("rename", &to_str, exists));
+        }
+
+        let name = from.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let temp = from.with_file_name(format!(".{}.clazyfiler-rename", name));
+        fs::rename(from, &temp).map_err(|e| ClazyfilerError::file_system("rename", temp.to_string_lossy().as_ref(), e))?;
+        fs::rename(&temp, to).map_err(|e| {
+            // Put the entry back under its old name rather than leaving the temporary one behind
+            let _ = fs::rename(&temp, from);
+            ClazyfilerError::file_system("rename", &to_str, e)
+        })
+    }
+
+    fn is_case_only_rename(from: &Path, to: &Path) -> bool {
+        let (Some(from_name), Some(to_name)) = (from.file_name(), to.file_name()) else {
+            return false;
+        };
+        from.parent() == to.parent()
+            && from_name != to_name
+            && from_name.to_string_lossy().to_lowercase() == to_name.to_string_lossy().to_lowercase()
+    }
+
+    /// Whether the directory literally lists `path`'s name; a case-insensitive lookup hit does not count
+    fn lists_entry(path: &Path) -> bool {
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+            return false;
+        };
+        fs::read_dir(parent)
+            .map(|entries| entries.flatten().any(|entry| entry.file_name() == name))
+            .unwrap_or(false)
+    }
+
+    /// Whether both paths resolve to the same directory entry (not just the same contents)
+    fn is_same_entry(a: &Path, b: &Path) -> bool {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            match (fs::symlink_metadata(a), fs::symlink_metadata(b)) {
+                (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+                _ => false,
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            matches!((fs::canonicalize(a), fs::canonicalize(b)), (Ok(a), Ok(b)) if a == b)
+        }
+    }
+
+    /// Copy a file, symlink or directory tree into `dest_dir`, returning where the copy went
+    /// A name already taken there gets `-1`, `-2`, ... like `write_new_file`, so nothing is ever overwritten
+    pub fn copy_file(&self, from: &Path, dest_dir: &Path) -> Result<PathBuf> {
+        Self::check_destination(from, dest_dir)?;
+        let to = Self::free_name(from, dest_dir)?;
+        self.copy_entry_or_clean_up(from, &to)?;
+        Ok(to)
+    }
+
+    /// Move an entry into `dest_dir`, renaming it on a name clash like `copy_file`
+    /// Across filesystems it is copied and the original removed once the copy is complete
+    pub fn move_file(&self, from: &Path, dest_dir: &Path) -> Result<PathBuf> {
+        if from.parent() == Some(dest_dir) {
+            return Ok(from.to_path_buf());
+        }
+        Self::check_destination(from, dest_dir)?;
+        let to = Self::free_name(from, dest_dir)?;
+        self.relocate(from, &to)?;
+        Ok(to)
+    }
+
+    /// Put an entry back at exactly `to` (undoing a move or rename), refusing to replace what is there now
+    /// Missing parent directories are created again
+    pub fn restore(&self, from: &Path, to: &Path) -> Result<()> {
+        if fs::symlink_metadata(to).is_ok() {
+            let exists = std::io::Error::from(std::io::ErrorKind::AlreadyExists);
+            return Err(ClazyfilerError::file_system("restore", to.to_string_lossy().as_ref(), exists));
+        }
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent).map_err(|e| ClazyfilerError::file_system("create_dir", parent.to_string_lossy().as_ref(), e))?;
+        }
+        self.relocate(from, to)
+    }
+
+    /// Take an entry out of the trash back to where it was deleted from, dropping its trashinfo record
+    pub fn untrash(&self, trashed: &Path, original: &Path) -> Result<()> {
+        self.restore(trashed, original)?;
+        if let (Some(trash), Some(name)) = (trashed.parent().and_then(Path::parent), trashed.file_name()) {
+            let _ = fs::remove_file(trash.join("info").join(format!("{}.trashinfo", name.to_string_lossy())));
+        }
+        Ok(())
+    }
+
+    /// Rename `from` to the free path `to`; across filesystems copy it and remove the original once complete
+    fn relocate(&self, from: &Path, to: &Path) -> Result<()> {
+        match fs::rename(from, to) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+                self.copy_entry_or_clean_up(from, to)?;
+                let removed = if fs::symlink_metadata(from).is_ok_and(|metadata| metadata.is_dir()) {
+                    fs::remove_dir_all(from)
+                } else {
+                    fs::remove_file(from)
+                };
+                removed.map_err(|e| ClazyfilerError::file_system("remove", from.to_string_lossy().as_ref(), e))
+            }
+            Err(e) => Err(ClazyfilerError::file_system("rename", to.to_string_lossy().as_ref(), e)),
+        }
+    }
+
+    /// Delete a file or symlink, or a directory with everything in it; a symlink to a directory is removed, not followed
+    pub fn delete_entry(&self, path: &Path) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        let metadata = self.retrying(|| fs::symlink_metadata(path))
+            .map_err(|e| ClazyfilerError::file_system("metadata", &path_str, e))?;
+        if metadata.is_dir() {
+            self.retrying(|| fs::remove_dir_all(path)).map_err(|e| ClazyfilerError::file_system("remove_dir", &path_str, e))
+        } else {
+            self.retrying(|| fs::remove_file(path)).map_err(|e| ClazyfilerError::file_system("remove", &path_str, e))
+        }
+    }
+
+    /// Remove a directory only if it is empty
+    pub fn remove_empty_directory(&self, path: &Path) -> Result<()> {
+        self.retrying(|| fs::remove_dir(path)).map_err(|e| ClazyfilerError::file_system("remove_dir", path.to_string_lossy().as_ref(), e))
+    }
+
+    /// Overwrite a regular file in place, syncing after each pass, then rename it to hide its name and remove it
+    /// This only erases the data where the filesystem writes in place, so callers check for copy-on-write first
+    pub fn shred_file(&self, path: &Path) -> Result<()> {
+        let path_str = path.to_string_lossy();
+        let metadata = fs::symlink_metadata(path).map_err(|e| ClazyfilerError::file_system("metadata", &path_str, e))?;
+        if !metadata.is_file() {
+            return Err(ClazyfilerError::content(&path_str, "Only regular files can be shredded"));
+        }
+        let mut file = fs::OpenOptions::new().write(true).open(path)
+            .map_err(|e| ClazyfilerError::file_system("open", &path_str, e))?;
+        let length = metadata.len();
+        // Seeded per file so no two shreds write the same noise
+        let mut state = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map(|since| since.as_nanos() as u64).unwrap_or(1) | 1;
+        let mut buffer = vec![0u8; 256 * 1024];
+        for pass in 1..=SHRED_PASSES {
+            file.seek(SeekFrom::Start(0)).map_err(|e| ClazyfilerError::file_system("seek", &path_str, e))?;
+            let mut written = 0;
+            while written < length {
+                let chunk = (length - written).min(buffer.len() as u64) as usize;
+                if pass < SHRED_PASSES {
+                    for word in buffer[..chunk].chunks_mut(8) {
+                        // xorshift64: fast noise, which is all overwriting needs
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        word.copy_from_slice(&state.to_le_bytes()[..word.len()]);
+                    }
+                } else {
+                    buffer[..chunk].fill(0);
+                }
+                file.write_all(&buffer[..chunk]).map_err(|e| ClazyfilerError::file_system("write", &path_str, e))?;
+                written += chunk as u64;
+                self.throttle.consume(chunk as u64);
+                if let Some(job) = &self.job {
+                    job.add_bytes_done(chunk as u64 / SHRED_PASSES);
+                    job.wait_while_paused();
+                    if job.is_cancelled() {
+                        return Err(ClazyfilerError::content(&path_str, "Cancelled while shredding"));
+                    }
+                }
+            }
+            file.sync_all().map_err(|e| ClazyfilerError::file_system("sync", &path_str, e))?;
+        }
+        drop(file);
+
+        let name_len = path.file_name().map_or(1, |name| name.len());
+        let hidden = path.with_file_name("0".repeat(name_len));
+        let removed = if hidden.symlink_metadata().is_err() && fs::rename(path, &hidden).is_ok() { hidden } else { path.to_path_buf() };
+        fs::remove_file(&removed).map_err(|e| ClazyfilerError::file_system("remove", &path_str, e))
+    }
+
+    /// Move an entry into the FreeDesktop trash, returning where it went there
+    /// Entries on the filesystem of `home_trash` go there, others to `.Trash-<uid>` at the top of their own
+    /// filesystem, so trashing is always a rename and never a copy. `info/<name>.trashinfo` records the
+    /// original path and deletion time for file managers to restore it from
+    pub fn trash_entry(&self, path: &Path, home_trash: &Path) -> Result<PathBuf> {
+        let path_str = path.to_string_lossy();
+        let path = std::path::absolute(path).map_err(|e| ClazyfilerError::file_system("absolute", &path_str, e))?;
+        let Some(name) = path.file_name().map(Path::new) else {
+            return Err(ClazyfilerError::content(&path_str, "Has no name to trash under"));
+        };
+        self.retrying(|| fs::symlink_metadata(&path)).map_err(|e| ClazyfilerError::file_system("metadata", &path_str, e))?;
+
+        let trash = Self::trash_for(&path, home_trash);
+        let (files, info) = (trash.join("files"), trash.join("info"));
+        let mut builder = fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            builder.mode(0o700);
+        }
+        for dir in [&files, &info] {
+            builder.create(dir).map_err(|e| ClazyfilerError::file_system("create_dir", dir.to_string_lossy().as_ref(), e))?;
+        }
+
+        let record = format!(
+            "[Trash Info]\nPath={}\nDeletionDate={}\n",
+            Self::encode_trash_path(&path),
+            chrono::Local::now().format("%Y-%m-%dT%H:%M:%S")
+        );
+        for attempt in 0..1000 {
+            let trashed_name = Self::numbered_name(name, attempt);
+            let trashed = files.join(&trashed_name);
+            let info_path = info.join(format!("{}.trashinfo", trashed_name.to_string_lossy()));
+            // Creating the info file reserves the name against other programs trashing at the same time
+            let mut info_file = match fs::OpenOptions::new().write(true).create_new(true).open(&info_path) {
+                Ok(file) => file,
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(ClazyfilerError::file_system("create", info_path.to_string_lossy().as_ref(), e)),
+            };
+            // Left behind by a trash whose info went missing
+            if fs::symlink_metadata(&trashed).is_ok() {
+                let _ = fs::remove_file(&info_path);
+                continue;
+            }
+            let moved = info_file.write_all(record.as_bytes()).and_then(|_| fs::rename(&path, &trashed));
+            return match moved {
+                Ok(()) => Ok(trashed),
+                Err(e) => {
+                    let _ = fs::remove_file(&info_path);
+                    Err(ClazyfilerError::file_system("trash", &path_str, e))
+                }
+            };
+        }
+        Err(ClazyfilerError::content(&path_str, &format!("No free name in {}", files.to_string_lossy())))
+    }
+
+    /// The trash directory for an entry: `home_trash` when it is on the same filesystem, the
+    /// `.Trash-<uid>` directory at the top of the entry's filesystem otherwise
+    fn trash_for(path: &Path, home_trash: &Path) -> PathBuf {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            // The trash itself may not exist yet; its nearest existing ancestor tells the filesystem
+            let device = |path: &Path| path.ancestors().find_map(|dir| fs::metadata(dir).ok()).map(|metadata| metadata.dev());
+            let parent = path.parent().unwrap_or(path);
+            let entry_device = device(parent);
+            if device(home_trash) == entry_device {
+                return home_trash.to_path_buf();
+            }
+            let top = parent
+                .ancestors()
+                .take_while(|dir| fs::metadata(dir).ok().map(|metadata| metadata.dev()) == entry_device)
+                .last()
+                .unwrap_or(parent);
+            // SAFETY: getuid has no preconditions and cannot fail
+            let uid = unsafe { libc::getuid() };
+            top.join(format!(".Trash-{}", uid))
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = path;
+            home_trash.to_path_buf()
+        }
+    }
+
+    /// The path as the `Path=` key of a trashinfo file: percent-encoded bytes, `/` kept
+    fn encode_trash_path(path: &Path) -> String {
+        #[cfg(unix)]
+        let bytes = {
+            use std::os::unix::ffi::OsStrExt;
+            path.as_os_str().as_bytes().to_vec()
+        };
+        #[cfg(not(unix))]
+        let bytes = path.to_string_lossy().into_owned().into_bytes();
+        bytes
+            .iter()
+            .map(|&byte| match byte {
+                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+                _ => format!("%{:02X}", byte),
+            })
+            .collect()
+    }
+
+    /// A directory cannot be copied or moved into itself or one of its subdirectories
+    fn check_destination(from: &Path, dest_dir: &Path) -> Result<()> {
+        if dest_dir.starts_with(from) {
+            return Err(ClazyfilerError::content(from.to_string_lossy().as_ref(), "Cannot be put inside itself"));
+        }
+        Ok(())
+    }
+
+    /// `dest_dir` joined with `from`'s name, or its first free `<stem>-N.<extension>` variant
+    pub fn free_name(from: &Path, dest_dir: &Path) -> Result<PathBuf> {
+        let from_str = from.to_string_lossy();
+        let Some(name) = from.file_name() else {
+            return Err(ClazyfilerError::content(&from_str, "Has no name to copy under"));
+        };
+        for attempt in 0..1000 {
+            let path = dest_dir.join(Self::numbered_name(Path::new(name), attempt));
+            if fs::symlink_metadata(&path).is_err() {
+                return Ok(path);
+            }
+        }
+        Err(ClazyfilerError::content(&from_str, &format!("No free name in {}", dest_dir.to_string_lossy())))
+    }
+
+    /// `name` itself for attempt 0, `<stem>-N.<extension>` for attempt N
+    fn numbered_name(name: &Path, attempt: usize) -> PathBuf {
+        if attempt == 0 {
+            return name.to_path_buf();
+        }
+        let stem = name.file_stem().unwrap_or_default().to_string_lossy();
+        let extension = name.extension().map(|extension| format!(".{}", extension.to_string_lossy())).unwrap_or_default();
+        PathBuf::from(format!("{}-{}{}", stem, attempt, extension))
+    }
+
+    /// `copy_entry`, removing what was copied of a directory tree when the job is cancelled halfway
+    fn copy_entry_or_clean_up(&self, from: &Path, to: &Path) -> Result<()> {
+        let copied = self.copy_entry(from, to);
+        let cancelled = copied.is_err() && self.job.as_ref().is_some_and(JobControl::is_cancelled);
+        if cancelled && fs::symlink_metadata(to).is_ok_and(|metadata| metadata.is_dir()) {
+            let _ = fs::remove_dir_all(to);
+        }
+        copied
+    }
+
+    /// Copy `from` to the free path `to`: symlinks as links, directories recursively with their permissions
+    /// set last (a read-only directory could not be filled otherwise), files created with create_new
+    fn copy_entry(&self, from: &Path, to: &Path) -> Result<()> {
+        let from_str = from.to_string_lossy();
+        let to_str = to.to_string_lossy();
+        let metadata = self.retrying(|| fs::symlink_metadata(from))
+            .map_err(|e| ClazyfilerError::file_system("metadata", &from_str, e))?;
+
+        if metadata.file_type().is_symlink() {
+            let target = fs::read_link(from).map_err(|e| ClazyfilerError::file_system("read_link", &from_str, e))?;
+            #[cfg(unix)]
+            let linked = std::os::unix::fs::symlink(&target, to);
+            #[cfg(not(unix))]
+            let linked = fs::copy(from.parent().unwrap_or(from).join(&target), to).map(|_| ());
+            return linked.map_err(|e| ClazyfilerError::file_system("symlink", &to_str, e));
+        }
+
+        if metadata.is_dir() {
+            fs::create_dir(to).map_err(|e| ClazyfilerError::file_system("create_dir", &to_str, e))?;
+            let entries = self.retrying(|| fs::read_dir(from))
+                .map_err(|e| ClazyfilerError::file_system("read_dir", &from_str, e))?;
+            for entry in entries {
+                let entry = entry.map_err(|e| ClazyfilerError::file_system("read_dir", &from_str, e))?;
+                self.copy_entry(&entry.path(), &to.join(entry.file_name()))?;
+            }
+        } else if metadata.len() >= RESUMABLE_SIZE {
+            self.copy_resumable(from, to)?;
+        } else {
+            let mut source = self.retrying(|| fs::File::open(from))
+                .map_err(|e| ClazyfilerError::file_system("open", &from_str, e))?;
+            let mut target = fs::OpenOptions::new().write(true).create_new(true).open(to)
+                .map_err(|e| ClazyfilerError::file_system("create", &to_str, e))?;
+            if let Err(e) = self.copy_contents(&mut source, &mut target) {
+                // A half-written copy would pass for the real thing
+                let _ = fs::remove_file(to);
+                return Err(ClazyfilerError::file_system("copy", &from_str, e));
+            }
+        }
+        fs::set_permissions(to, metadata.permissions()).map_err(|e| ClazyfilerError::file_system("chmod", &to_str, e))
+    }
+
+    /// Where an interrupted copy to `to` keeps its data: hidden beside it, so it never passes for the copy
+    pub fn partial_copy_path(to: &Path) -> PathBuf {
+        let name = to.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        to.with_file_name(format!(".{}.clazyfiler-part", name))
+    }
+
+    /// Beside a partial copy, the identity of the source it was copied from, so it is only continued from that file
+    fn partial_copy_source_path(part: &Path) -> PathBuf {
+        let name = part.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        part.with_file_name(format!("{}.source", name))
+    }
+
+    /// Size, modification time and, on Unix, device and inode: a replaced or rewritten source changes at least one
+    fn source_identity(metadata: &fs::Metadata) -> String {
+        let modified = metadata.modified().ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|since| since.as_nanos())
+            .unwrap_or_default();
+        #[cfg(unix)]
+        let (device, inode) = {
+            use std::os::unix::fs::MetadataExt;
+            (metadata.dev(), metadata.ino())
+        };
+        #[cfg(not(unix))]
+        let (device, inode) = (0, 0);
+        format!("{} {} {} {}", metadata.len(), modified, device, inode)
+    }
+
+    /// Copy a large file through its partial copy, continuing a kept one copied from this very source
+    /// whose tail still matches it; any other kept data is discarded
+    /// The partial copy is kept when the copy fails or is cancelled, and renamed into place once complete
+    fn copy_resumable(&self, from: &Path, to: &Path) -> Result<()> {
+        let from_str = from.to_string_lossy();
+        let part = Self::partial_copy_path(to);
+        let part_str = part.to_string_lossy();
+        let sidecar = Self::partial_copy_source_path(&part);
+        let mut source = self.retrying(|| fs::File::open(from))
+            .map_err(|e| ClazyfilerError::file_system("open", &from_str, e))?;
+        let identity = source.metadata()
+            .map(|metadata| Self::source_identity(&metadata))
+            .map_err(|e| ClazyfilerError::file_system("metadata", &from_str, e))?;
+        let mut target = fs::OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&part)
+            .map_err(|e| ClazyfilerError::file_system("create", &part_str, e))?;
+
+        let same_source = fs::read_to_string(&sidecar).is_ok_and(|kept| kept == identity);
+        let resume_at = if same_source { Self::resumable_length(&mut source, &mut target).unwrap_or(0) } else { 0 };
+        let positioned = target.set_len(resume_at)
+            .and_then(|_| target.seek(SeekFrom::Start(resume_at)))
+            .and_then(|_| source.seek(SeekFrom::Start(resume_at)));
+        if let Err(e) = positioned {
+            return Err(ClazyfilerError::file_system("seek", &part_str, e));
+        }
+        if resume_at == 0 {
+            fs::write(&sidecar, &identity)
+                .map_err(|e| ClazyfilerError::file_system("write", &sidecar.to_string_lossy(), e))?;
+        }
+        if let Some(job) = &self.job {
+            job.add_bytes_done(resume_at);
+        }
+        self.copy_contents(&mut source, &mut target)
+            .map_err(|e| ClazyfilerError::file_system("copy", &from_str, e))?;
+        drop(target);
+        fs::rename(&part, to).map_err(|e| ClazyfilerError::file_system("rename", &part_str, e))?;
+        let _ = fs::remove_file(&sidecar);
+        Ok(())
+    }
+
+    /// How much of a kept partial copy can be reused: its length, if that is within the source and its
+    /// last bytes match the source's
+    fn resumable_length(source: &mut fs::File, part: &mut fs::File) -> std::io::Result<u64> {
+        let length = part.metadata()?.len();
+        if length == 0 || length > source.metadata()?.len() {
+            return Ok(0);
+        }
+        let check = RESUME_CHECK_LEN.min(length);
+        let (mut expected, mut written) = (vec![0u8; check as usize], vec![0u8; check as usize]);
+        source.seek(SeekFrom::Start(length - check))?;
+        source.read_exact(&mut expected)?;
+        part.seek(SeekFrom::Start(length - check))?;
+        part.read_exact(&mut written)?;
+        Ok(if expected == written { length } else { 0 })
+    }
+
+    /// Copy a file's data in chunks paced by the throttle, holding between chunks while the job is paused
+    /// and giving up when it is cancelled
+    fn copy_contents(&self, source: &mut fs::File, target: &mut fs::File) -> std::io::Result<()> {
+        let mut buffer = vec![0u8; 256 * 1024];
+        loop {
+            let read = self.retrying(|| source.read(&mut buffer))?;
+            if read == 0 {
+                return Ok(());
+            }
+            target.write_all(&buffer[..read])?;
+            self.throttle.consume(read as u64);
+            if let Some(job) = &self.job {
+                job.add_bytes_done(read as u64);
+                job.wait_while_paused();
+                if job.is_cancelled() {
+                    return Err(std::io::Error::other("Cancelled"));
+                }
+            }
+        }
+    }
+
+    /// Bytes of file data in an entry: a file's size, everything under a directory; symlinks count as nothing
+    /// Unreadable parts are left out, so this is for progress, not for checking free space
+    pub fn data_size(path: &Path) -> u64 {
+        let Ok(metadata) = fs::symlink_metadata(path) else { return 0 };
+        if metadata.is_dir() {
+            fs::read_dir(path)
+                .map(|entries| entries.flatten().map(|entry| Self::data_size(&entry.path())).sum())
+                .unwrap_or(0)
+        } else if metadata.is_file() {
+            metadata.len()
+        } else {
+            0
+        }
+    }
+
+    /// `path` and, for a directory, everything under it, parents before their contents; symlinks are not followed
+    /// Unlike scans nothing is skipped, as this is for changing every entry of a tree
+    pub fn subtree(path: &Path) -> Vec<PathBuf> {
+        let mut paths = vec![path.to_path_buf()];
+        if fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_dir()) {
+            for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+                paths.extend(Self::subtree(&entry.path()));
+            }
+        }
+        paths
+    }
+
+    /// Other names of the file at `path` under `root`, found by device and inode; the walk stays on the file's
+    /// filesystem, as hard links cannot cross it, and stops once every link the file counts is found
+    pub fn find_hard_links(path: &Path, root: &Path) -> Result<Vec<PathBuf>> {
+        let path_str = path.to_string_lossy();
+        let metadata = fs::symlink_metadata(path).map_err(|e| ClazyfilerError::file_system("metadata", &path_str, e))?;
+        if metadata.is_dir() {
+            return Err(ClazyfilerError::content(&path_str, "Directories have no hard links of their own"));
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let (device, inode) = (metadata.dev(), metadata.ino());
+            let wanted = metadata.nlink().saturating_sub(1) as usize;
+            if wanted == 0 {
+                return Ok(Vec::new());
+            }
+            let mut links = Vec::new();
+            let mut pending = vec![root.to_path_buf()];
+            'walk: while let Some(dir) = pending.pop() {
+                for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+                    let Ok(child) = entry.metadata() else { continue };
+                    if child.dev() != device {
+                        continue;
+                    }
+                    if child.is_dir() {
+                        pending.push(entry.path());
+                    } else if child.ino() == inode && entry.path() != path {
+                        links.push(entry.path());
+                        if links.len() >= wanted {
+                            break 'walk;
+                        }
+                    }
+                }
+            }
+            links.sort();
+            Ok(links)
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = root;
+            Err(ClazyfilerError::content(&path_str, "Hard links are only found on Unix"))
+        }
+    }
+
+    /// Hidden directories and common build/cache directories are listed but not descended into by scans
+    pub fn is_skipped_directory(name: &str) -> bool {
+        name.starts_with('.') || matches!(name, "node_modules" | "target" | ".git" | "build" | "dist")
+    }
+
+    /// Directories under `root` holding nothing but directories that are empty in the same way, children
+    /// before their parents so removing them in order empties each parent first
+    /// Skipped directories count as contents, as `.git` keeps empty directories it needs
+    pub fn find_empty_directories(&self, root: &Path) -> Vec<PathBuf> {
+        let mut empty = Vec::new();
+        Self::collect_empty_directories(root, &mut empty);
+        empty
+    }
+
+    /// Whether `dir` ends up empty once the empty directories in it (added to `empty`) are gone
+    fn collect_empty_directories(dir: &Path, empty: &mut Vec<PathBuf>) -> bool {
+        let Ok(entries) = fs::read_dir(dir) else { return false };
+        let mut entries: Vec<fs::DirEntry> = entries.flatten().collect();
+        entries.sort_by_key(|entry| entry.file_name());
+        let mut only_empty = true;
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+            if is_dir && !Self::is_skipped_directory(&name) && Self::collect_empty_directories(&entry.path(), empty) {
+                empty.push(entry.path());
+            } else {
+                only_empty = false;
+            }
+        }
+        only_empty
+    }
+
+    /// Broken symlinks, zero-byte files and empty directories under `root`, in walk order
+    /// Skipped directories are not descended into, like in scans; marker files such as `__init__.py` are kept
+    pub fn find_leftovers(&self, root: &Path) -> Result<Vec<Leftover>> {
+        self.reset_unreadable();
+        let mut leftovers = Vec::new();
+        self.find_leftovers_recursive(root, &mut leftovers)?;
+        Ok(leftovers)
+    }
+
+    fn find_leftovers_recursive(&self, dir_path: &Path, leftovers: &mut Vec<Leftover>) -> Result<()> {
+        let mut entries: Vec<fs::DirEntry> = self.retrying(|| fs::read_dir(dir_path))
+            .map_err(|e| ClazyfilerError::file_system("read_dir", dir_path.to_string_lossy().as_ref(), e))?
+            .flatten()
+            .collect();
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let metadata = match entry.metadata() {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    self.note_unreadable(format!("{}: {}", name, e));
+                    continue;
+                }
+            };
+            let kind = if metadata.is_symlink() {
+                fs::metadata(&path).is_err().then_some(LeftoverKind::BrokenSymlink)
+            } else if metadata.is_file() {
+                (metadata.len() == 0 && !MARKER_FILES.contains(&name.as_str())).then_some(LeftoverKind::EmptyFile)
+            } else if metadata.is_dir() && !Self::is_skipped_directory(&name) {
+                if fs::read_dir(&path).is_ok_and(|mut children| children.next().is_none()) {
+                    Some(LeftoverKind::EmptyDirectory)
+                } else {
+                    if let Err(e) = self.find_leftovers_recursive(&path, leftovers) {
+                        self.note_unreadable(e.to_string());
+                    }
+                    None
+                }
+            } else {
+                None
+            };
+            if let Some(kind) = kind {
+                leftovers.push(Leftover { path, kind });
+            }
+        }
+        Ok(())
     }
 
     /// Recursively scan directory tree and return all files
     /// This is used for fuzzy finding across the entire directory structure
     pub fn scan_directory_tree(&self, root_path: &Path) -> Result<Vec<FileEntry>> {
+        self.reset_unreadable();
         let mut all_files = Vec::new();
         self.scan_directory_recursive(root_path, &mut all_files)?;
         Ok(all_files)
@@ -178,50 +1387,52 @@ impl FileService {
 
     /// Recursive helper for directory tree scanning
     fn scan_directory_recursive(&self, dir_path: &Path, all_files: &mut Vec<FileEntry>) -> Result<()> {
-        let entries = fs::read_dir(dir_path)
+        let entries = self.retrying(|| fs::read_dir(dir_path))
             .map_err(|e| ClazyfilerError::file_system("read_dir", dir_path.to_string_lossy().as_ref(), e))?;
 
         for entry in entries {
             match entry {
                 Ok(entry) => {
-                    match entry.metadata() {
+                    match self.retrying(|| entry.metadata()) {
                         Ok(metadata) => {
                             let file_entry = FileEntry {
                                 name: entry.file_name().to_string_lossy().to_string(),
                                 path: entry.path(),
                                 is_directory: metadata.is_dir(),
                                 size: if metadata.is_file() { Some(metadata.len()) } else { None },
+                                modified: metadata.modified().ok(),
+                                mode: metadata_mode(&metadata),
+                                link_target: link_target(&entry),
+                                metadata_error: None,
                             };
 
                             // Add this entry to our results
                             all_files.push(file_entry.clone());
 
                             // If it's a directory, recursively scan it
-                            if metadata.is_dir() {
-                                // Skip hidden directories and common build/cache directories to avoid slowdown
-                                let file_name = entry.file_name();
-                                let dir_name = file_name.to_string_lossy();
-                                if !dir_name.starts_with('.') && 
-                                   !matches!(dir_name.as_ref(), "node_modules" | "target" | ".git" | "build" | "dist") {
-                                    if let Err(e) = self.scan_directory_recursive(&entry.path(), all_files) {
-                                        // Log error but continue scanning other directories
-                                        eprintln!("Warning: Failed to scan directory {}: {}", entry.path().display(), e);
-                                    }
+                            if metadata.is_dir() && !Self::is_skipped_directory(&file_entry.name) {
+                                if let Err(e) = self.scan_directory_recursive(&entry.path(), all_files) {
+                                    // The directory is listed, its contents are not; continue with the others
+                                    self.note_unreadable(e.to_string());
                                 }
                             }
                         }
-                        Err(e) => {
-                            // Log warning but continue processing other files
-                            eprintln!("Warning: Failed to read metadata for {}: {}", entry.path().display(), e);
-                        }
+                        // Listed with its error but not descended into, even if it is a directory
+                        Err(e) => all_files.push(self.unreadable_entry(&entry, e)),
                     }
                 }
                 Err(e) => {
-                    // Log warning but continue processing other files
-                    eprintln!("Warning: Failed to read directory entry: {}", e);
+                    // Continue with the other files
+                    self.note_unreadable(e.to_string());
                 }
             }
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Where the entry points when it is a symlink; the type comes from the listing itself, so only links are read
+fn link_target(entry: &fs::DirEntry) -> Option<PathBuf> {
+    entry.file_type().ok().filter(|file_type| file_type.is_symlink())?;
+    fs::read_link(entry.path()).ok()
+}
@@ -0,0 +1,338 @@
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use crate::core::{BudgetedCache, CacheWeight, ClazyfilerError, LruCache, Result};
+
+/// Extensions treated as font files
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc", "otc"];
+
+/// Text rendered for the specimen image
+const SPECIMEN_LINES: &[&str] = &["Aa Gg Qq 0123", "The quick brown fox", "jumps over the lazy dog"];
+
+/// Font files above this size are not parsed
+const MAX_FONT_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Names and metrics read from the font's sfnt tables
+#[derive(Debug, Clone, Default)]
+pub struct FontInfo {
+    pub format: &'static str,
+    pub family: Option<String>,
+    pub style: Option<String>,
+    pub full_name: Option<String>,
+    pub postscript_name: Option<String>,
+    pub version: Option<String>,
+    pub designer: Option<String>,
+    pub weight: Option<u16>,
+    pub glyph_count: Option<u16>,
+    pub units_per_em: Option<u16>,
+    pub faces: usize,           // Number of fonts in a collection (1 for plain files)
+}
+
+impl CacheWeight for FontInfo {
+    fn weight(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + [&self.family, &self.style, &self.full_name, &self.postscript_name, &self.version, &self.designer]
+                .iter()
+                .map(|text| text.as_ref().map(String::len).unwrap_or(0))
+                .sum::<usize>()
+    }
+}
+
+/// External tools able to render a specimen, in order of preference
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Renderer {
+    HbView,
+    Magick,
+    Convert,
+}
+
+impl Renderer {
+    fn command(&self) -> &'static str {
+        match self {
+            Renderer::HbView => "hb-view",
+            Renderer::Magick => "magick",
+            Renderer::Convert => "convert",
+        }
+    }
+}
+
+/// Specimen rendered on a worker thread: (generation, font, result)
+type SpecimenResult = (u64, PathBuf, Option<PathBuf>);
+
+/// Service responsible for font metadata and rendered specimens
+/// Names are parsed natively; specimens need hb-view or ImageMagick, render on background threads
+/// collected by `poll`, and are cached as PNGs
+#[derive(Debug)]
+pub struct FontService {
+    cache_dir: Option<PathBuf>,
+    renderer: RefCell<Option<Option<Renderer>>>,
+    info_cache: LruCache<PathBuf, std::result::Result<FontInfo, String>>,
+    specimen_cache: LruCache<PathBuf, Option<PathBuf>>,
+    rendering: RefCell<HashSet<PathBuf>>,    // Specimens started and not collected yet
+    specimen_sender: Sender<SpecimenResult>,
+    specimen_receiver: Receiver<SpecimenResult>,
+    generation: Cell<u64>,    // Bumped on clear so late results from old threads are dropped
+}
+
+impl FontService {
+    pub fn new(cache_dir: Option<PathBuf>) -> Self {
+        let (specimen_sender, specimen_receiver) = channel();
+        Self {
+            cache_dir,
+            renderer: RefCell::new(None),
+            info_cache: LruCache::new("fonts"),
+            specimen_cache: LruCache::new("font specimens"),
+            rendering: RefCell::new(HashSet::new()),
+            specimen_sender,
+            specimen_receiver,
+            generation: Cell::new(0),
+        }
+    }
+
+    /// Check whether a path looks like a font file
+    pub fn is_font(path: &Path) -> bool {
+        path.extension()
+            .map(|ext| FONT_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()))
+            .unwrap_or(false)
+    }
+
+    /// Whether a specimen renderer is installed (checked once)
+    pub fn can_render(&self) -> bool {
+        self.renderer().is_some()
+    }
+
+    /// Family/style names and basic metrics
+    pub fn info(&self, path: &Path) -> std::result::Result<FontInfo, String> {
+        if let Some(cached) = self.info_cache.get(path) {
+            return cached;
+        }
+        let info = Self::read_info(path).map_err(|e| e.to_string());
+        self.info_cache.insert(path.to_path_buf(), info.clone());
+        info
+    }
+
+    /// Names and metrics of a font already in memory
+    pub fn parse(data: &[u8]) -> Option<FontInfo> {
+        parse_font(data)
+    }
+
+    /// PNG of the specimen text set in this font, rendered in the background on first request
+    /// None until the render is collected by `poll`, and for good if it failed
+    pub fn specimen(&self, path: &Path) -> Option<PathBuf> {
+        if let Some(cached) = self.specimen_cache.get(path) {
+            return cached;
+        }
+        let renderer = self.renderer()?;
+        let cache_dir = self.cache_dir.clone()?;
+        if self.rendering.borrow_mut().insert(path.to_path_buf()) {
+            let sender = self.specimen_sender.clone();
+            let generation = self.generation.get();
+            let file = path.to_path_buf();
+            std::thread::spawn(move || {
+                let specimen = render_specimen(renderer, &cache_dir, &file).ok();
+                // The receiver is gone once the app exits
+                let _ = sender.send((generation, file, specimen));
+            });
+        }
+        None
+    }
+
+    /// Whether a specimen for this font is being rendered
+    pub fn is_rendering(&self, path: &Path) -> bool {
+        self.rendering.borrow().contains(path)
+    }
+
+    /// Collect finished specimens; returns true if any arrived
+    pub fn poll(&self) -> bool {
+        let mut updated = false;
+        while let Ok((generation, file, specimen)) = self.specimen_receiver.try_recv() {
+            if generation == self.generation.get() {
+                self.rendering.borrow_mut().remove(&file);
+                self.specimen_cache.insert(file, specimen);
+                updated = true;
+            }
+        }
+        updated
+    }
+
+    /// Drop cached results (e.g. after files changed on disk)
+    pub fn clear_cache(&self) {
+        self.info_cache.clear();
+        self.specimen_cache.clear();
+        self.rendering.borrow_mut().clear();
+        self.generation.set(self.generation.get() + 1);
+    }
+
+    /// Caches counted against the memory budget
+    pub fn caches(&self) -> Vec<&dyn BudgetedCache> {
+        vec![&self.info_cache, &self.specimen_cache]
+    }
+
+    fn renderer(&self) -> Option<Renderer> {
+        *self.renderer.borrow_mut().get_or_insert_with(|| {
+            [Renderer::HbView, Renderer::Magick, Renderer::Convert].into_iter().find(|renderer| {
+                Command::new(renderer.command())
+                    .arg("--version")
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status()
+                    .map(|status| status.success())
+                    .unwrap_or(false)
+            })
+        })
+    }
+
+    fn read_info(path: &Path) -> Result<FontInfo> {
+        let path_str = path.to_string_lossy();
+        let size = std::fs::metadata(path)
+            .map_err(|e| ClazyfilerError::file_system("metadata", &path_str, e))?
+            .len();
+        if size > MAX_FONT_SIZE {
+            return Err(ClazyfilerError::content(&path_str, "Font too large to inspect"));
+        }
+        let data = std::fs::read(path).map_err(|e| ClazyfilerError::file_system("read", &path_str, e))?;
+        parse_font(&data).ok_or_else(|| ClazyfilerError::content(&path_str, "Not a TrueType/OpenType font"))
+    }
+}
+
+fn render_specimen(renderer: Renderer, cache_dir: &Path, path: &Path) -> Result<PathBuf> {
+    let specimens_dir = cache_dir.join("fonts");
+    std::fs::create_dir_all(&specimens_dir)
+        .map_err(|e| ClazyfilerError::file_system("create_dir", specimens_dir.to_string_lossy().as_ref(), e))?;
+
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    let specimen_path = specimens_dir.join(format!("{:016x}.png", hasher.finish()));
+
+    let text = SPECIMEN_LINES.join("\n");
+    let mut command = Command::new(renderer.command());
+    match renderer {
+        Renderer::HbView => {
+            command
+                .args(["--font-size=64", "--margin=16", "--background=#000000", "--foreground=#ffffff", "-O", "png", "-o"])
+                .arg(&specimen_path)
+                .arg(path)
+                .arg(&text);
+        }
+        Renderer::Magick | Renderer::Convert => {
+            command
+                .args(["-background", "black", "-fill", "white", "-pointsize", "64", "-font"])
+                .arg(path)
+                .arg(format!("label:{}", text))
+                .arg(&specimen_path);
+        }
+    }
+    let status = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| ClazyfilerError::external_tool(renderer.command(), &format!("Failed to launch: {}", e)))?;
+
+    if status.success() && specimen_path.exists() {
+        Ok(specimen_path)
+    } else {
+        Err(ClazyfilerError::external_tool(renderer.command(), &format!("Rendering exited with status: {}", status)))
+    }
+}
+
+fn u16_at(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn u32_at(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+/// Parse the first face of a font file or collection
+fn parse_font(data: &[u8]) -> Option<FontInfo> {
+    let (offset, faces) = if data.get(0..4)? == b"ttcf" {
+        let faces = u32_at(data, 8)? as usize;
+        (u32_at(data, 12)? as usize, faces)
+    } else {
+        (0, 1)
+    };
+
+    let format = match data.get(offset..offset + 4)? {
+        [0, 1, 0, 0] | b"true" => "TrueType",
+        b"OTTO" => "OpenType (CFF outlines)",
+        _ => return None,
+    };
+    let tables = table_directory(data, offset)?;
+    let table = |tag: &[u8; 4]| tables.iter().find(|(t, _, _)| t == tag).map(|(_, start, len)| (*start, *len));
+
+    let mut info = FontInfo { format, faces, ..FontInfo::default() };
+    if let Some((start, len)) = table(b"name") {
+        let names = data.get(start..start + len)?;
+        let name = |id: u16| font_name(names, id);
+        // Typographic family/subfamily (16/17) group styles better than the legacy ids (1/2)
+        info.family = name(16).or_else(|| name(1));
+        info.style = name(17).or_else(|| name(2));
+        info.full_name = name(4);
+        info.version = name(5);
+        info.postscript_name = name(6);
+        info.designer = name(9);
+    }
+    if let Some((start, _)) = table(b"maxp") {
+        info.glyph_count = u16_at(data, start + 4);
+    }
+    if let Some((start, _)) = table(b"head") {
+        info.units_per_em = u16_at(data, start + 18);
+    }
+    if let Some((start, _)) = table(b"OS/2") {
+        info.weight = u16_at(data, start + 4);
+    }
+    Some(info)
+}
+
+/// (tag, offset, length) of every table in an sfnt header at `offset`
+fn table_directory(data: &[u8], offset: usize) -> Option<Vec<([u8; 4], usize, usize)>> {
+    let count = u16_at(data, offset + 4)? as usize;
+    (0..count)
+        .map(|index| {
+            let record = offset + 12 + index * 16;
+            let tag: [u8; 4] = data.get(record..record + 4)?.try_into().ok()?;
+            Some((tag, u32_at(data, record + 8)? as usize, u32_at(data, record + 12)? as usize))
+        })
+        .collect()
+}
+
+/// Look up a name record, preferring Windows English, then any Unicode entry, then Mac Roman
+fn font_name(table: &[u8], name_id: u16) -> Option<String> {
+    let count = u16_at(table, 2)? as usize;
+    let storage = u16_at(table, 4)? as usize;
+
+    let mut best: Option<(u8, String)> = None;
+    for index in 0..count {
+        let record = 6 + index * 12;
+        let platform = u16_at(table, record)?;
+        let language = u16_at(table, record + 4)?;
+        if u16_at(table, record + 6)? != name_id {
+            continue;
+        }
+        let len = u16_at(table, record + 8)? as usize;
+        let start = storage + u16_at(table, record + 10)? as usize;
+        let Some(bytes) = table.get(start..start + len) else { continue };
+
+        let (rank, text) = match platform {
+            3 => (if language == 0x409 { 0 } else { 1 }, decode_utf16_be(bytes)),
+            0 => (1, decode_utf16_be(bytes)),
+            // Mac Roman: ASCII covers nearly all real-world names
+            1 => (2, bytes.iter().map(|&b| if b.is_ascii() { b as char } else { '?' }).collect()),
+            _ => continue,
+        };
+        if best.as_ref().is_none_or(|(best_rank, _)| rank < *best_rank) && !text.trim().is_empty() {
+            best = Some((rank, text));
+        }
+    }
+    best.map(|(_, text)| text.trim().to_string())
+}
+
+fn decode_utf16_be(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect();
+    String::from_utf16_lossy(&units)
+}
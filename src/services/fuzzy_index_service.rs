@@ -0,0 +1,344 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::core::{metadata_mode, to_hex, Sha1};
+use crate::model::FileEntry;
+use crate::services::{FileService, TreeWatcher, WatchEvent};
+
+/// First line of a saved index; bump the version when the layout changes
+const FORMAT_HEADER: &str = "clazyfiler-fuzzy-index 1";
+
+/// Service that keeps the fuzzy-find index of one root warm across directory changes and sessions
+/// The index is saved in the cache dir with the mtime of every scanned directory, so loading it only
+/// lists directories that changed since. While it is in use, a watcher patches it from create/delete/
+/// rename events instead of the index being thrown away and rescanned
+#[derive(Debug)]
+pub struct FuzzyIndexService {
+    index_dir: Option<PathBuf>,
+    root: Option<PathBuf>,                      // Root of the index currently in memory
+    dir_mtimes: HashMap<PathBuf, SystemTime>,   // Scanned directories and their mtime when last listed
+    watcher: Option<TreeWatcher>,
+}
+
+impl FuzzyIndexService {
+    pub fn new(cache_dir: Option<PathBuf>) -> Self {
+        Self {
+            index_dir: cache_dir.map(|dir| dir.join("fuzzy-index")),
+            root: None,
+            dir_mtimes: HashMap::new(),
+            watcher: None,
+        }
+    }
+
+    /// Whether the index of `root` in memory is being kept current by the watcher
+    pub fn is_live(&self, root: &Path) -> bool {
+        self.watcher.is_some() && self.root.as_deref() == Some(root)
+    }
+
+    /// Whether an index of `root` was saved by an earlier scan
+    pub fn has_saved(&self, root: &Path) -> bool {
+        self.index_path(root).is_some_and(|path| path.exists())
+    }
+
+    /// Adopt a freshly scanned index of `root`, watching it unless `watch` is false (network mounts)
+    pub fn track(&mut self, root: &Path, files: &[FileEntry], watch: bool) {
+        self.root = Some(root.to_path_buf());
+        self.dir_mtimes = std::iter::once(root.to_path_buf())
+            .chain(files.iter().filter(|file| Self::is_scanned(file)).map(|file| file.path.clone()))
+            .filter_map(|dir| Some((dir.clone(), directory_mtime(&dir)?)))
+            .collect();
+        self.start_watcher(watch);
+    }
+
+    /// Stop keeping the in-memory index; the saved copy stays on disk for next time
+    pub fn forget(&mut self) {
+        self.root = None;
+        self.dir_mtimes.clear();
+        self.watcher = None;
+    }
+
+    /// Saved index of `root`, with directories that changed since it was saved listed again
+    pub fn load(&mut self, root: &Path, file_service: &FileService, watch: bool) -> Option<Vec<FileEntry>> {
+        let text = fs::read_to_string(self.index_path(root)?).ok()?;
+        let (mut files, dir_mtimes) = parse_index(&text, root)?;
+        self.root = Some(root.to_path_buf());
+        self.dir_mtimes = dir_mtimes;
+
+        let mut changed = Vec::new();
+        let mut gone = Vec::new();
+        for (dir, saved) in &self.dir_mtimes {
+            match directory_mtime(dir) {
+                Some(mtime) if mtime == *saved => {}
+                Some(_) => changed.push(dir.clone()),
+                None => gone.push(dir.clone()),
+            }
+        }
+        for dir in &gone {
+            self.remove_subtree(&mut files, dir);
+        }
+        // Parents first, so a relisted parent has already dropped children that vanished
+        changed.sort();
+        for dir in &changed {
+            if self.dir_mtimes.contains_key(dir) {
+                self.relist(&mut files, dir, file_service);
+            }
+        }
+
+        self.start_watcher(watch);
+        if !changed.is_empty() || !gone.is_empty() {
+            self.save(&files);
+        }
+        Some(files)
+    }
+
+    /// Write the index in memory to the cache dir (written to a temporary file, then renamed)
+    pub fn save(&self, files: &[FileEntry]) {
+        let Some(root) = &self.root else { return };
+        let Some(path) = self.index_path(root) else { return };
+        let Some(root_text) = root.to_str() else { return };
+
+        let mut text = format!("{}\nr\t{}\t{}\n", FORMAT_HEADER, encode_mtime(self.dir_mtimes.get(root)), root_text);
+        for file in files {
+            // Names the line format cannot hold are rare enough to leave to the next rescan
+            let Some(relative) = file.path.strip_prefix(root).ok().and_then(Path::to_str) else { continue };
+            if relative.contains(['\n', '\t']) {
+                continue;
+            }
+            let field = if file.is_directory {
+                encode_mtime(self.dir_mtimes.get(&file.path))
+            } else {
+                file.size.map(|size| size.to_string()).unwrap_or_else(|| "-".to_string())
+            };
+            text.push_str(&format!("{}\t{}\t{}\n", if file.is_directory { 'd' } else { 'f' }, field, relative));
+        }
+
+        let Some(dir) = path.parent() else { return };
+        let temp = path.with_extension("tmp");
+        // The index is only an accelerator, so failing to save it is not worth reporting
+        let written = fs::create_dir_all(dir)
+            .and_then(|_| fs::File::create(&temp))
+            .and_then(|mut file| file.write_all(text.as_bytes()));
+        if written.and_then(|_| fs::rename(&temp, &path)).is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+    }
+
+    /// Apply pending watcher events to `files`
+    /// Returns whether anything changed, or None if events were lost and the index must be rebuilt
+    pub fn apply_events(&mut self, files: &mut Vec<FileEntry>, file_service: &FileService) -> Option<bool> {
+        let Some(watcher) = self.watcher.as_mut() else { return Some(false) };
+        let events = watcher.events();
+        if events.is_empty() {
+            return Some(false);
+        }
+
+        let mut touched = HashSet::new();
+        for event in events {
+            match event {
+                WatchEvent::Overflow => {
+                    self.watcher = None;
+                    return None;
+                }
+                WatchEvent::Created { path, .. } => {
+                    touched.extend(path.parent().map(Path::to_path_buf));
+                    if !files.iter().any(|file| file.path == path) {
+                        if let Some(entry) = entry_for(&path) {
+                            self.add_entry(files, entry, file_service);
+                        }
+                    }
+                }
+                WatchEvent::Removed(path) => {
+                    touched.extend(path.parent().map(Path::to_path_buf));
+                    self.remove_subtree(files, &path);
+                }
+                WatchEvent::Written(path) => {
+                    if let Some(file) = files.iter_mut().find(|file| file.path == path) {
+                        file.size = fs::metadata(&path).ok().filter(|metadata| metadata.is_file()).map(|metadata| metadata.len());
+                    }
+                }
+            }
+        }
+        // The index reflects these directories as they are now, so a later load need not relist them
+        for dir in touched {
+            if let (Some(saved), Some(mtime)) = (self.dir_mtimes.get_mut(&dir), directory_mtime(&dir)) {
+                *saved = mtime;
+            }
+        }
+        Some(true)
+    }
+
+    fn index_path(&self, root: &Path) -> Option<PathBuf> {
+        let mut hasher = Sha1::new();
+        hasher.update(root.as_os_str().as_encoded_bytes());
+        Some(self.index_dir.as_ref()?.join(format!("{}.idx", to_hex(&hasher.finish()))))
+    }
+
+    /// Directories whose contents scans include (hidden and build directories are listed but not entered)
+    fn is_scanned(file: &FileEntry) -> bool {
+        file.is_directory && !FileService::is_skipped_directory(&file.name)
+    }
+
+    fn start_watcher(&mut self, watch: bool) {
+        self.watcher = None;
+        if !watch {
+            return;
+        }
+        let Some(mut watcher) = TreeWatcher::new() else { return };
+        // With some directories unwatched the index would silently go stale, so it is all or nothing
+        if self.dir_mtimes.keys().all(|dir| watcher.watch(dir)) {
+            self.watcher = Some(watcher);
+        }
+    }
+
+    /// List `dir` again, dropping children that vanished and scanning new ones
+    fn relist(&mut self, files: &mut Vec<FileEntry>, dir: &Path, file_service: &FileService) {
+        let Ok(listing) = file_service.read_directory(dir) else {
+            self.remove_subtree(files, dir);
+            return;
+        };
+        let listed: HashSet<&Path> = listing.iter().map(|entry| entry.path.as_path()).collect();
+        let vanished: Vec<PathBuf> = files.iter()
+            .filter(|file| file.path.parent() == Some(dir) && !listed.contains(file.path.as_path()))
+            .map(|file| file.path.clone())
+            .collect();
+        for path in vanished {
+            self.remove_subtree(files, &path);
+        }
+
+        let known: HashMap<PathBuf, usize> = files.iter()
+            .enumerate()
+            .filter(|(_, file)| file.path.parent() == Some(dir))
+            .map(|(index, file)| (file.path.clone(), index))
+            .collect();
+        for entry in listing {
+            match known.get(&entry.path) {
+                Some(&index) if files[index].is_directory == entry.is_directory => files[index].size = entry.size,
+                Some(_) => {
+                    // Replaced by an entry of the other kind
+                    self.remove_subtree(files, &entry.path);
+                    self.add_entry(files, entry, file_service);
+                }
+                None => self.add_entry(files, entry, file_service),
+            }
+        }
+        if let Some(mtime) = directory_mtime(dir) {
+            self.dir_mtimes.insert(dir.to_path_buf(), mtime);
+        }
+    }
+
+    /// Add a new entry, scanning (and watching) its contents if it is a directory
+    fn add_entry(&mut self, files: &mut Vec<FileEntry>, entry: FileEntry, file_service: &FileService) {
+        let scanned = Self::is_scanned(&entry);
+        let path = entry.path.clone();
+        files.push(entry);
+        if !scanned {
+            return;
+        }
+
+        let mut new_dirs = vec![path.clone()];
+        // Watch before scanning, so entries created during the scan are reported rather than missed
+        if let Some(watcher) = self.watcher.as_mut() {
+            watcher.watch(&path);
+        }
+        if let Ok(contents) = file_service.scan_directory_tree(&path) {
+            for file in contents.iter().filter(|file| Self::is_scanned(file)) {
+                if let Some(watcher) = self.watcher.as_mut() {
+                    watcher.watch(&file.path);
+                }
+                new_dirs.push(file.path.clone());
+            }
+            // Nothing under a new directory can be indexed yet, so no duplicate check is needed
+            files.extend(contents);
+        }
+        for dir in new_dirs {
+            if let Some(mtime) = directory_mtime(&dir) {
+                self.dir_mtimes.insert(dir, mtime);
+            }
+        }
+    }
+
+    fn remove_subtree(&mut self, files: &mut Vec<FileEntry>, path: &Path) {
+        files.retain(|file| !file.path.starts_with(path));
+        self.dir_mtimes.retain(|dir, _| !dir.starts_with(path));
+    }
+}
+
+/// Entry for a path reported by the watcher, described the same way directory scans do (symlinks not followed)
+fn entry_for(path: &Path) -> Option<FileEntry> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    Some(FileEntry {
+        name: path.file_name()?.to_string_lossy().to_string(),
+        path: path.to_path_buf(),
+        is_directory: metadata.is_dir(),
+        size: if metadata.is_file() { Some(metadata.len()) } else { None },
+        modified: metadata.modified().ok(),
+        mode: metadata_mode(&metadata),
+        link_target: if metadata.is_symlink() { fs::read_link(path).ok() } else { None },
+        metadata_error: None,
+    })
+}
+
+fn directory_mtime(dir: &Path) -> Option<SystemTime> {
+    fs::metadata(dir).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// `seconds.nanoseconds` since the epoch, or `-` for directories scans do not enter
+fn encode_mtime(mtime: Option<&SystemTime>) -> String {
+    match mtime.and_then(|mtime| mtime.duration_since(UNIX_EPOCH).ok()) {
+        Some(since_epoch) => format!("{}.{}", since_epoch.as_secs(), since_epoch.subsec_nanos()),
+        None => "-".to_string(),
+    }
+}
+
+fn decode_mtime(field: &str) -> Option<SystemTime> {
+    let (secs, nanos) = field.split_once('.')?;
+    Some(UNIX_EPOCH + Duration::new(secs.parse().ok()?, nanos.parse().ok()?))
+}
+
+/// Entries and scanned-directory mtimes of a saved index, if it belongs to `root`
+fn parse_index(text: &str, root: &Path) -> Option<(Vec<FileEntry>, HashMap<PathBuf, SystemTime>)> {
+    let mut lines = text.lines();
+    if lines.next()? != FORMAT_HEADER {
+        return None;
+    }
+    let mut dir_mtimes = HashMap::new();
+    let (kind, field, saved_root) = split_line(lines.next()?)?;
+    // Guards against hash collisions and moved cache dirs
+    if kind != "r" || Path::new(saved_root) != root {
+        return None;
+    }
+    if let Some(mtime) = decode_mtime(field) {
+        dir_mtimes.insert(root.to_path_buf(), mtime);
+    }
+
+    let mut files = Vec::new();
+    for line in lines {
+        let Some((kind, field, relative)) = split_line(line) else { continue };
+        let path = root.join(relative);
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let is_directory = kind == "d";
+        if is_directory {
+            if let Some(mtime) = decode_mtime(field) {
+                dir_mtimes.insert(path.clone(), mtime);
+            }
+        }
+        files.push(FileEntry {
+            name,
+            path,
+            is_directory,
+            size: if is_directory { None } else { field.parse().ok() },
+            modified: None,
+            mode: None,
+            link_target: None,
+            metadata_error: None,
+        });
+    }
+    Some((files, dir_mtimes))
+}
+
+fn split_line(line: &str) -> Option<(&str, &str, &str)> {
+    let mut parts = line.splitn(3, '\t');
+    Some((parts.next()?, parts.next()?, parts.next()?))
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Working-tree state of a listing entry; a directory takes the most pressing state of anything below it
+/// Ordered by how urgently it needs attention, which is also the order of the grouped listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GitStatus {
+    Conflicted,
+    Modified,   // Changed in the working tree, not staged
+    Staged,
+    Untracked,
+    Clean,
+}
+
+impl GitStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            GitStatus::Conflicted => "Conflicted",
+            GitStatus::Modified => "Modified",
+            GitStatus::Staged => "Staged",
+            GitStatus::Untracked => "Untracked",
+            GitStatus::Clean => "Unchanged",
+        }
+    }
+
+    /// From the two-letter `XY` code of `git status --porcelain`
+    fn from_code(code: &str) -> Option<Self> {
+        let mut chars = code.chars();
+        let (index, worktree) = (chars.next()?, chars.next()?);
+        Some(match (index, worktree) {
+            ('?', '?') => GitStatus::Untracked,
+            ('!', '!') => return None,
+            ('U', _) | (_, 'U') | ('A', 'A') | ('D', 'D') => GitStatus::Conflicted,
+            (_, worktree) if worktree != ' ' => GitStatus::Modified,
+            _ => GitStatus::Staged,
+        })
+    }
+}
+
+/// Service reading `git status` for the entries of a listing, for grouping by status
+#[derive(Debug)]
+pub struct GitService;
+
+impl GitService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Status of every changed entry directly inside `dir`, keyed by its path; entries not listed are clean
+    /// None outside a work tree or without git
+    pub fn entry_statuses(&self, dir: &Path) -> Option<HashMap<PathBuf, GitStatus>> {
+        let top_level = git_output(dir, &["rev-parse", "--show-toplevel"])?;
+        let top_level = PathBuf::from(top_level.trim_end_matches(['\n', '\r']));
+        let prefix = dir.canonicalize().ok()?
+            .strip_prefix(top_level.canonicalize().ok()?).ok()?
+            .to_path_buf();
+        let output = git_output(dir, &["status", "--porcelain=v1", "-z", "--untracked-files=normal", "--", "."])?;
+
+        let mut statuses: HashMap<PathBuf, GitStatus> = HashMap::new();
+        let mut records = output.split('\0').filter(|record| !record.is_empty());
+        while let Some(record) = records.next() {
+            let (Some(code), Some(path)) = (record.get(..2), record.get(3..)) else { continue };
+            if code.starts_with(['R', 'C']) {
+                // Renames and copies are followed by the original path
+                records.next();
+            }
+            let Some(status) = GitStatus::from_code(code) else { continue };
+            // Attribute the change to the entry of `dir` it lives in
+            let Ok(relative) = Path::new(path).strip_prefix(&prefix) else { continue };
+            let Some(entry) = relative.components().next() else { continue };
+            let entry_path = dir.join(entry);
+            let current = statuses.entry(entry_path).or_insert(status);
+            *current = (*current).min(status);
+        }
+        Some(statuses)
+    }
+
+    /// Branch checked out in the work tree holding `dir`, or the short commit id when detached
+    /// None outside a work tree or without git
+    pub fn branch(&self, dir: &Path) -> Option<String> {
+        let head = git_output(dir, &["symbolic-ref", "--short", "-q", "HEAD"])
+            .or_else(|| git_output(dir, &["rev-parse", "--short", "HEAD"]))?;
+        Some(head.trim_end_matches(['\n', '\r']).to_string()).filter(|head| !head.is_empty())
+    }
+}
+
+/// Stdout of a successful git command run in `dir`
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,60 @@
+use std::path::Path;
+use crate::services::EditorService;
+
+/// A problem found at startup or on a config reload, and what the program does instead
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub problem: String,
+    pub fallback: String,
+}
+
+impl Diagnostic {
+    pub fn new(problem: impl Into<String>, fallback: impl Into<String>) -> Self {
+        Self { problem: problem.into(), fallback: fallback.into() }
+    }
+}
+
+/// Service checking the environment at startup, so misconfiguration shows up in a panel instead of failing later
+#[derive(Debug)]
+pub struct HealthService;
+
+impl HealthService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Whether Enter can open files: $EDITOR, or else vim or vi, must be installed
+    pub fn check_editor(&self, editor_service: &EditorService) -> Option<Diagnostic> {
+        let configured = std::env::var("EDITOR").ok().filter(|editor| !editor.trim().is_empty());
+        match (configured, editor_service.detect_editor()) {
+            (Some(editor), Ok(found)) if editor != found => Some(Diagnostic::new(
+                format!("$EDITOR '{}' is not installed", editor),
+                format!("Opening files with {} instead", found),
+            )),
+            (_, Ok(_)) => None,
+            (configured, Err(_)) => Some(Diagnostic::new(
+                match configured {
+                    Some(editor) => format!("$EDITOR '{}' is not installed, and neither is vim or vi", editor),
+                    None => "No editor found: $EDITOR is unset and neither vim nor vi is installed".to_string(),
+                },
+                "Enter can't open files; 'o' opens them with the file manager instead",
+            )),
+        }
+    }
+
+    /// Whether `dir` can be created and written to; `label` names it and `fallback` says what is lost otherwise
+    pub fn check_writable(&self, label: &str, dir: Option<&Path>, fallback: &str) -> Option<Diagnostic> {
+        let Some(dir) = dir else {
+            return Some(Diagnostic::new(format!("No {} directory could be determined", label), fallback));
+        };
+        let probe = dir.join(format!(".write-test-{}", std::process::id()));
+        let written = std::fs::create_dir_all(dir).and_then(|_| std::fs::write(&probe, b""));
+        let _ = std::fs::remove_file(&probe);
+        written.err().map(|e| Diagnostic::new(format!("{} directory {} is not writable: {}", capitalize(label), dir.display(), e), fallback))
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Opens kept after compaction
+const KEEP_ENTRIES: usize = 1000;
+
+/// The log is rewritten once it grows past this many lines
+const COMPACT_AT: usize = 2 * KEEP_ENTRIES;
+
+/// Frecency weight of one open by its age in seconds, newest first
+const AGE_WEIGHTS: &[(i64, f64)] = &[(3600, 4.0), (86_400, 2.0), (7 * 86_400, 1.0)];
+
+/// Weight of opens older than a week
+const OLD_OPEN_WEIGHT: f64 = 0.25;
+
+/// A file opened through clazyfiler
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentOpen {
+    pub path: PathBuf,
+    pub opened_at: i64,  // Unix seconds of the latest open
+    pub count: usize,    // Opens still in the log
+}
+
+/// Service recording every file opened in the editor or file manager
+/// Stored as an append-only `timestamp\tpath` log in the data dir; it powers the recents list,
+/// frecency boosts in fuzzy find and "open last file"
+#[derive(Debug)]
+pub struct HistoryService {
+    log_path: Option<PathBuf>,
+    entries: Vec<(i64, PathBuf)>,  // Oldest first
+}
+
+impl HistoryService {
+    /// Load the log from `data_dir`, compacting it when it has grown too long
+    pub fn new(data_dir: Option<PathBuf>) -> Self {
+        let log_path = data_dir.map(|dir| dir.join("history.tsv"));
+        let mut entries: Vec<(i64, PathBuf)> = log_path.as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|text| text.lines().filter_map(parse_line).collect())
+            .unwrap_or_default();
+
+        let mut service = Self { log_path, entries: Vec::new() };
+        if entries.len() > COMPACT_AT {
+            entries.drain(..entries.len() - KEEP_ENTRIES);
+            service.entries = entries;
+            service.rewrite();
+        } else {
+            service.entries = entries;
+        }
+        service
+    }
+
+    /// Remember that `path` was just opened
+    pub fn record_open(&mut self, path: &Path) {
+        // Paths the line format cannot hold are left out rather than corrupting the log
+        let Some(text) = path.to_str().filter(|text| !text.contains('\n')) else { return };
+        let timestamp = now();
+        self.entries.push((timestamp, path.to_path_buf()));
+
+        let Some(log_path) = &self.log_path else { return };
+        // History is a convenience, so failing to persist it is not worth interrupting the open
+        let _ = log_path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| OpenOptions::new().create(true).append(true).open(log_path))
+            .and_then(|mut file| writeln!(file, "{}\t{}", timestamp, text));
+    }
+
+    /// Distinct opened paths, most recently opened first
+    pub fn recent(&self, limit: usize) -> Vec<RecentOpen> {
+        let mut counts: HashMap<&Path, usize> = HashMap::new();
+        for (_, path) in &self.entries {
+            *counts.entry(path).or_default() += 1;
+        }
+
+        let mut recent = Vec::new();
+        for (opened_at, path) in self.entries.iter().rev() {
+            if recent.len() >= limit {
+                break;
+            }
+            let Some(count) = counts.remove(path.as_path()) else { continue };
+            recent.push(RecentOpen { path: path.clone(), opened_at: *opened_at, count });
+        }
+        recent
+    }
+
+    /// When `path` was last opened and how often, if it was at all
+    pub fn opens_of(&self, path: &Path) -> Option<RecentOpen> {
+        let mut opens = self.entries.iter().filter(|(_, opened)| opened == path);
+        let (opened_at, _) = opens.next_back()?;
+        Some(RecentOpen { path: path.to_path_buf(), opened_at: *opened_at, count: opens.count() + 1 })
+    }
+
+    /// Most recently opened path that is still a file
+    pub fn last_opened(&self) -> Option<&Path> {
+        self.entries.iter().rev()
+            .map(|(_, path)| path.as_path())
+            .find(|path| path.is_file())
+    }
+
+    /// Frecency of every opened path: each open counts more the more recent it is
+    pub fn frecency_scores(&self) -> HashMap<PathBuf, f64> {
+        let now = now();
+        let mut scores: HashMap<PathBuf, f64> = HashMap::new();
+        for (opened_at, path) in &self.entries {
+            let age = now.saturating_sub(*opened_at);
+            let weight = AGE_WEIGHTS.iter()
+                .find(|(max_age, _)| age < *max_age)
+                .map_or(OLD_OPEN_WEIGHT, |(_, weight)| *weight);
+            *scores.entry(path.clone()).or_default() += weight;
+        }
+        scores
+    }
+
+    /// Replace the log with the entries in memory (written to a temp file, then renamed over)
+    fn rewrite(&self) {
+        let Some(log_path) = &self.log_path else { return };
+        let text: String = self.entries.iter()
+            .filter_map(|(timestamp, path)| Some(format!("{}\t{}\n", timestamp, path.to_str()?)))
+            .collect();
+        let temp = log_path.with_extension("tmp");
+        let written = fs::File::create(&temp).and_then(|mut file| file.write_all(text.as_bytes()));
+        if written.and_then(|_| fs::rename(&temp, log_path)).is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+    }
+}
+
+/// `1712345678\t/home/me/notes.md`
+fn parse_line(line: &str) -> Option<(i64, PathBuf)> {
+    let (timestamp, path) = line.split_once('\t')?;
+    Some((timestamp.parse().ok()?, PathBuf::from(path)))
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Entries modified longer ago than this show their date rather than how many days ago
+const RELATIVE_DAYS: i64 = 30;
+
+/// `5m ago` or `2d ago` for a recent modification time, `2024-03-01` for an older one
+pub fn format_modified(modified: SystemTime) -> String {
+    let seconds = modified.duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs() as i64).unwrap_or(0);
+    if now() - seconds < RELATIVE_DAYS * 86_400 {
+        format_age(seconds)
+    } else {
+        chrono::DateTime::<chrono::Local>::from(modified).format("%Y-%m-%d").to_string()
+    }
+}
+
+/// `5m ago`, `3h ago`, `2d ago` for the recents list
+pub fn format_age(opened_at: i64) -> String {
+    let age = now().saturating_sub(opened_at).max(0);
+    match age {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", age / 60),
+        3600..=86_399 => format!("{}h ago", age / 3600),
+        _ => format!("{}d ago", age / 86_400),
+    }
+}
@@ -0,0 +1,109 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use crate::config::Hooks;
+
+/// File operations that can have a hook, each configured under `[hooks]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HookEvent {
+    Create,
+    Rename,
+    Copy,
+    Move,
+    Delete,
+}
+
+impl HookEvent {
+    /// The config key, as shown in error messages
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::Create => "after_create",
+            HookEvent::Rename => "after_rename",
+            HookEvent::Copy => "after_copy",
+            HookEvent::Move => "after_move",
+            HookEvent::Delete => "after_delete",
+        }
+    }
+
+    /// The configured command, if any
+    pub fn command(self, hooks: &Hooks) -> Option<&str> {
+        let command = match self {
+            HookEvent::Create => &hooks.after_create,
+            HookEvent::Rename => &hooks.after_rename,
+            HookEvent::Copy => &hooks.after_copy,
+            HookEvent::Move => &hooks.after_move,
+            HookEvent::Delete => &hooks.after_delete,
+        };
+        command.as_deref().filter(|command| !command.trim().is_empty())
+    }
+}
+
+/// Service responsible for running the configured hooks after file operations
+/// Hooks run on background threads so a slow `git add` never holds up the UI; `poll` collects their failures
+#[derive(Debug)]
+pub struct HookService {
+    sender: Sender<String>,
+    receiver: Receiver<String>,
+}
+
+impl HookService {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self { sender, receiver }
+    }
+
+    /// Run `command` for the entry at `path` (and the one it came from) in the entry's directory
+    pub fn run(&self, event: HookEvent, command: &str, path: &Path, old: Option<&Path>) {
+        let args = expand(command, path, old);
+        let Some((program, args)) = args.split_first() else { return };
+        let mut process = Command::new(program);
+        process.args(args).stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::piped());
+        if let Some(dir) = path.parent().filter(|dir| dir.is_dir()) {
+            process.current_dir(dir);
+        }
+
+        let sender = self.sender.clone();
+        let program = program.clone();
+        std::thread::spawn(move || {
+            let failure = match process.output() {
+                Ok(output) if output.status.success() => return,
+                Ok(output) => {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    match stderr.lines().find(|line| !line.trim().is_empty()) {
+                        Some(line) => line.trim().to_string(),
+                        None => output.status.to_string(),
+                    }
+                }
+                Err(e) => format!("{}: {}", program, e),
+            };
+            // The receiver is gone once the app exits
+            let _ = sender.send(format!("Hook {} failed: {}", event.name(), failure));
+        });
+    }
+
+    /// The latest failure of a finished hook, if any finished with one since the last poll
+    pub fn poll(&self) -> Option<String> {
+        self.receiver.try_iter().last()
+    }
+}
+
+/// Split `command` into words and fill in the placeholders of each
+/// Paths are substituted after splitting, so names with spaces stay one argument
+pub fn expand(command: &str, path: &Path, old: Option<&Path>) -> Vec<String> {
+    let text = |path: Option<&Path>| path.map(|path| path.to_string_lossy().to_string()).unwrap_or_default();
+    let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let placeholders = [
+        ("{path}", text(Some(path))),
+        ("{name}", name),
+        ("{dir}", text(path.parent())),
+        ("{old}", text(old)),
+    ];
+    command
+        .split_whitespace()
+        .map(|word| {
+            placeholders
+                .iter()
+                .fold(word.to_string(), |word, (placeholder, value)| word.replace(placeholder, value))
+        })
+        .collect()
+}
@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use crate::model::FileEntry;
+
+/// Service answering which entries of a listing git ignores, for the project view
+/// Asks `git check-ignore` so nested .gitignore files, negations and info/exclude behave exactly as in git
+#[derive(Debug)]
+pub struct IgnoreService;
+
+impl IgnoreService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Paths of the entries of `dir` that git ignores
+    /// None outside a work tree or without git, so callers can tell that nothing could be hidden
+    pub fn ignored_entries(&self, dir: &Path, entries: &[FileEntry]) -> Option<HashSet<PathBuf>> {
+        if entries.is_empty() {
+            return Some(HashSet::new());
+        }
+        let mut child = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["check-ignore", "-z", "--stdin"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+
+        let names: Vec<u8> = entries.iter()
+            .flat_map(|entry| entry.name.bytes().chain(std::iter::once(0)))
+            .collect();
+        // Written from a thread so git never blocks on a full stdout pipe while we are still writing;
+        // dropping stdin closes it, which tells git the list is complete
+        let mut stdin = child.stdin.take()?;
+        let writer = std::thread::spawn(move || stdin.write_all(&names));
+        let output = child.wait_with_output().ok()?;
+        writer.join().ok()?.ok()?;
+
+        // 0: some entries ignored, 1: none ignored, 128: not a work tree
+        match output.status.code() {
+            Some(0) => Some(
+                String::from_utf8_lossy(&output.stdout)
+                    .split('\0')
+                    .filter(|name| !name.is_empty())
+                    .map(|name| dir.join(name))
+                    .collect(),
+            ),
+            Some(1) => Some(HashSet::new()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use crate::core::{BudgetedCache, CacheWeight, LruCache};
+
+/// Extensions decoded for thumbnails
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "webp"];
+
+/// Images decoded at once; a grid of large photos asks for many, and each holds its full-size pixels while decoding
+const MAX_DECODING: usize = 4;
+
+/// Downscaled image, drawn as block art (two stacked pixels per cell) or through a graphics protocol
+#[derive(Debug, Clone)]
+pub struct Thumbnail {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<[u8; 3]>,
+}
+
+impl Thumbnail {
+    /// RGB value at pixel (x, y)
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 3] {
+        self.pixels[(y * self.width + x) as usize]
+    }
+}
+
+/// A thumbnail drawn with a graphics protocol over reserved cells, once the frame is on screen
+#[derive(Debug, Clone)]
+pub struct ImagePlacement {
+    pub x: u16,
+    pub y: u16,
+    pub cols: u16,
+    pub rows: u16,
+    pub thumbnail: Rc<Thumbnail>,
+}
+
+impl CacheWeight for Thumbnail {
+    fn weight(&self) -> usize {
+        std::mem::size_of::<Self>() + self.pixels.len() * std::mem::size_of::<[u8; 3]>()
+    }
+}
+
+/// Thumbnail cache key: image path and bounds in pixels
+type ThumbnailKey = (PathBuf, u32, u32);
+
+/// Thumbnail decoded on a worker thread: (generation, key, result)
+type DecodeResult = (u64, ThumbnailKey, Option<Thumbnail>);
+
+/// Service responsible for decoding images into thumbnails
+/// Decoding is expensive, so it happens on background threads and results (including failures)
+/// are cached per path and size
+#[derive(Debug)]
+pub struct ImageService {
+    cache: LruCache<ThumbnailKey, Option<Rc<Thumbnail>>>,
+    decoding: RefCell<HashSet<ThumbnailKey>>,   // Decodes started and not collected yet
+    waiting: RefCell<HashSet<ThumbnailKey>>,    // Asked for while MAX_DECODING were running; started when asked again
+    decode_sender: Sender<DecodeResult>,
+    decode_receiver: Receiver<DecodeResult>,
+    generation: Cell<u64>,    // Bumped on clear so late results from old threads are dropped
+}
+
+impl ImageService {
+    pub fn new() -> Self {
+        let (decode_sender, decode_receiver) = channel();
+        Self {
+            cache: LruCache::new("thumbnails"),
+            decoding: RefCell::new(HashSet::new()),
+            waiting: RefCell::new(HashSet::new()),
+            decode_sender,
+            decode_receiver,
+            generation: Cell::new(0),
+        }
+    }
+
+    /// Check whether a path looks like a decodable image
+    pub fn is_image(path: &Path) -> bool {
+        path.extension()
+            .map(|ext| IMAGE_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()))
+            .unwrap_or(false)
+    }
+
+    /// Thumbnail fitting within `width` x `height` pixels, preserving aspect ratio, decoded in the background on first request
+    /// None until the decode is collected by `poll`, and for good if the image could not be decoded
+    pub fn thumbnail(&self, path: &Path, width: u32, height: u32) -> Option<Rc<Thumbnail>> {
+        let key = (path.to_path_buf(), width, height);
+        if let Some(cached) = self.cache.get(&key) {
+            return cached;
+        }
+        if self.decoding.borrow().contains(&key) {
+            return None;
+        }
+        // Asking again once a decode finished starts it; draws follow every poll that collected one
+        if self.decoding.borrow().len() >= MAX_DECODING {
+            self.waiting.borrow_mut().insert(key);
+            return None;
+        }
+        self.waiting.borrow_mut().remove(&key);
+        self.decoding.borrow_mut().insert(key.clone());
+        let sender = self.decode_sender.clone();
+        let generation = self.generation.get();
+        std::thread::spawn(move || {
+            let thumbnail = Self::decode(&key.0, key.1, key.2);
+            // The receiver is gone once the app exits
+            let _ = sender.send((generation, key, thumbnail));
+        });
+        None
+    }
+
+    /// Whether a thumbnail of this image is being decoded or waits for its turn
+    pub fn is_decoding(&self, path: &Path) -> bool {
+        let pending = |keys: &HashSet<ThumbnailKey>| keys.iter().any(|(pending, _, _)| pending == path);
+        pending(&self.decoding.borrow()) || pending(&self.waiting.borrow())
+    }
+
+    /// Collect decoded thumbnails; returns true if any arrived
+    pub fn poll(&self) -> bool {
+        let mut updated = false;
+        while let Ok((generation, key, thumbnail)) = self.decode_receiver.try_recv() {
+            if generation == self.generation.get() {
+                self.decoding.borrow_mut().remove(&key);
+                self.cache.insert(key, thumbnail.map(Rc::new));
+                updated = true;
+            }
+        }
+        // Those still on screen are asked for again by the draw that follows
+        if updated {
+            self.waiting.borrow_mut().clear();
+        }
+        updated
+    }
+
+    /// Pixel dimensions read from the image header
+    pub fn dimensions(path: &Path) -> Option<(u32, u32)> {
+        image::image_dimensions(path).ok()
+    }
+
+    /// Drop cached thumbnails (e.g. after files changed on disk)
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+        self.decoding.borrow_mut().clear();
+        self.waiting.borrow_mut().clear();
+        self.generation.set(self.generation.get() + 1);
+    }
+
+    /// Caches counted against the memory budget
+    pub fn caches(&self) -> Vec<&dyn BudgetedCache> {
+        vec![&self.cache]
+    }
+
+    fn decode(path: &Path, width: u32, height: u32) -> Option<Thumbnail> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let image = image::open(path).ok()?;
+        let scaled = image.thumbnail(width, height).to_rgb8();
+        Some(Thumbnail {
+            width: scaled.width(),
+            height: scaled.height(),
+            pixels: scaled.pixels().map(|pixel| pixel.0).collect(),
+        })
+    }
+}
@@ -0,0 +1,130 @@
+use std::path::{Path, PathBuf};
+
+/// Request sent over the socket: this prefix, the path's bytes, then a NUL
+const REVEAL_PREFIX: &[u8] = b"reveal ";
+const REPLY_OK: &[u8] = b"ok\n";
+
+/// Socket of the single-instance mode, where a running instance takes over reveal requests from new launches
+/// Only available on Unix; elsewhere every launch opens its own UI
+#[derive(Debug)]
+pub struct InstanceService {
+    #[cfg(unix)]
+    listener: std::os::unix::net::UnixListener,
+    #[cfg(unix)]
+    socket_path: PathBuf,
+}
+
+impl InstanceService {
+    /// Hand `path` to the instance listening on `socket_path`; false if none is running
+    pub fn forward(socket_path: &Path, path: &Path) -> bool {
+        #[cfg(unix)]
+        {
+            unix::forward(socket_path, path).is_ok()
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (socket_path, path);
+            false
+        }
+    }
+
+    /// Become the running instance; None if the socket can't be bound, e.g. another instance just took it
+    pub fn listen(socket_path: &Path) -> Option<Self> {
+        #[cfg(unix)]
+        {
+            let listener = unix::bind(socket_path)?;
+            Some(Self { listener, socket_path: socket_path.to_path_buf() })
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = socket_path;
+            None
+        }
+    }
+
+    /// Paths forwarded by other launches since the last call, without blocking
+    pub fn requests(&self) -> Vec<PathBuf> {
+        #[cfg(unix)]
+        {
+            std::iter::from_fn(|| self.listener.accept().ok())
+                .filter_map(|(stream, _)| unix::answer(stream))
+                .collect()
+        }
+        #[cfg(not(unix))]
+        {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for InstanceService {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.socket_path);
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::ffi::OsStr;
+    use std::io::{self, BufRead, BufReader, Read, Write};
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+    use super::{REPLY_OK, REVEAL_PREFIX};
+
+    /// A running instance answers right away between frames; anything slower is hung
+    const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+    pub fn forward(socket_path: &Path, path: &Path) -> io::Result<()> {
+        let mut stream = UnixStream::connect(socket_path)?;
+        stream.set_read_timeout(Some(REPLY_TIMEOUT))?;
+        stream.set_write_timeout(Some(REPLY_TIMEOUT))?;
+        let mut request = REVEAL_PREFIX.to_vec();
+        request.extend_from_slice(path.as_os_str().as_bytes());
+        request.push(0);
+        stream.write_all(&request)?;
+
+        let mut reply = Vec::new();
+        stream.take(REPLY_OK.len() as u64).read_to_end(&mut reply)?;
+        if reply == REPLY_OK {
+            Ok(())
+        } else {
+            Err(io::Error::new(io::ErrorKind::InvalidData, "unexpected reply"))
+        }
+    }
+
+    pub fn bind(socket_path: &Path) -> Option<UnixListener> {
+        if let Some(parent) = socket_path.parent() {
+            std::fs::create_dir_all(parent).ok()?;
+        }
+        let listener = match UnixListener::bind(socket_path) {
+            Ok(listener) => listener,
+            Err(err) if err.kind() == io::ErrorKind::AddrInUse => {
+                // Left behind by an instance that didn't exit cleanly, unless someone still answers
+                if UnixStream::connect(socket_path).is_ok() {
+                    return None;
+                }
+                std::fs::remove_file(socket_path).ok()?;
+                UnixListener::bind(socket_path).ok()?
+            }
+            Err(_) => return None,
+        };
+        listener.set_nonblocking(true).ok()?;
+        Some(listener)
+    }
+
+    /// Read one request from a new connection and confirm it
+    pub fn answer(stream: UnixStream) -> Option<PathBuf> {
+        stream.set_nonblocking(false).ok()?;
+        stream.set_read_timeout(Some(REPLY_TIMEOUT)).ok()?;
+        stream.set_write_timeout(Some(REPLY_TIMEOUT)).ok()?;
+        let mut request = Vec::new();
+        BufReader::new(&stream).read_until(0, &mut request).ok()?;
+        let path = request.strip_prefix(REVEAL_PREFIX)?.strip_suffix(&[0])?;
+        let path = PathBuf::from(OsStr::from_bytes(path));
+        (&stream).write_all(REPLY_OK).ok()?;
+        Some(path)
+    }
+}
@@ -1,5 +1,59 @@
 pub mod file_service;
 pub mod editor_service;
+pub mod opener_service;
+pub mod image_service;
+pub mod video_service;
+pub mod data_file;
+pub mod document_service;
+pub mod font_service;
+pub mod torrent_service;
+pub mod verification_service;
+pub mod encryption_service;
+pub mod clipboard_service;
+pub mod transfer_service;
+pub mod disk_service;
+pub mod watch_service;
+pub mod fuzzy_index_service;
+pub mod history_service;
+pub mod ignore_service;
+pub mod git_service;
+pub mod instance_service;
+pub mod capability_service;
+pub mod health_service;
+pub mod hook_service;
+pub mod operation_history;
+pub mod downloads_service;
+pub mod archive_service;
+pub mod disk_image_service;
+pub mod account_service;
+pub mod bookmark_service;
 
 pub use file_service::FileService;
-pub use editor_service::EditorService;
\ No newline at end of file
+pub use editor_service::EditorService;
+pub use opener_service::OpenerService;
+pub use image_service::ImageService;
+pub use video_service::{VideoService, VideoState};
+pub use data_file::DataFileService;
+pub use document_service::DocumentService;
+pub use font_service::FontService;
+pub use torrent_service::TorrentService;
+pub use verification_service::VerificationService;
+pub use encryption_service::EncryptionService;
+pub use clipboard_service::ClipboardService;
+pub use transfer_service::TransferService;
+pub use disk_service::DiskService;
+pub use watch_service::{TreeWatcher, WatchEvent};
+pub use fuzzy_index_service::FuzzyIndexService;
+pub use history_service::HistoryService;
+pub use ignore_service::IgnoreService;
+pub use git_service::{GitService, GitStatus};
+pub use instance_service::InstanceService;
+pub use capability_service::{CapabilityService, ColorDepth, GraphicsProtocol, TerminalCapabilities};
+pub use health_service::{Diagnostic, HealthService};
+pub use hook_service::{HookEvent, HookService};
+pub use operation_history::{Operation, OperationHistory, OperationKind};
+pub use downloads_service::{Download, DownloadsService};
+pub use archive_service::{ArchiveFormat, ArchiveService};
+pub use disk_image_service::{DiskImageKind, DiskImageService, ImageEntry, ImageListing};
+pub use account_service::{Account, AccountService};
+pub use bookmark_service::{Bookmark, BookmarkService};
\ No newline at end of file
@@ -0,0 +1,37 @@
+use std::ffi::OsStr;
+use std::process::{Command, Stdio};
+use crate::core::{ClazyfilerError, Result};
+
+/// Service responsible for handing paths to external openers
+/// Used for the configured file manager (xdg-open, Finder, Explorer, ...) and browser
+#[derive(Debug)]
+pub struct OpenerService;
+
+impl OpenerService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Launch `command` on `target` (a path or URL) without waiting for it to exit
+    /// The command may contain arguments (e.g. "nautilus --new-window")
+    pub fn open_detached(&self, command: &str, target: impl AsRef<OsStr>) -> Result<()> {
+        let mut parts = command.split_whitespace();
+        let Some(program) = parts.next() else {
+            return Err(ClazyfilerError::config("No file manager configured"));
+        };
+
+        let mut child = Command::new(program)
+            .args(parts)
+            .arg(target)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| ClazyfilerError::external_tool(program, &format!("Failed to launch: {}", e)))?;
+
+        // Reap the child in the background so it doesn't linger as a zombie
+        std::thread::spawn(move || child.wait());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use std::path::{Path, PathBuf};
+use crate::core::{ClazyfilerError, Result};
+use crate::services::FileService;
+
+/// Operations kept for undo; older ones are forgotten
+const MAX_OPERATIONS: usize = 100;
+
+/// File operations that can be undone; permanent deletes can't and are never recorded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OperationKind {
+    Copy,
+    Move,
+    Rename,
+    Trash,
+}
+
+impl OperationKind {
+    fn noun(self) -> &'static str {
+        match self {
+            OperationKind::Copy => "copy",
+            OperationKind::Move => "move",
+            OperationKind::Rename => "rename",
+            OperationKind::Trash => "trash",
+        }
+    }
+}
+
+/// One recorded operation with each entry before and after it: source and copy, old and new path,
+/// or original path and place in the trash
+#[derive(Debug, Clone)]
+pub struct Operation {
+    pub kind: OperationKind,
+    pub entries: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Operation {
+    /// `rename of notes.txt` or `copy of 3 entries`, for the status bar
+    pub fn describe(&self) -> String {
+        match self.entries.as_slice() {
+            [(before, _)] => {
+                let name = before.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                format!("{} of {}", self.kind.noun(), name)
+            }
+            entries => format!("{} of {} entries", self.kind.noun(), entries.len()),
+        }
+    }
+}
+
+/// Undo and redo stacks of file operations
+/// Undoing puts entries back where they were: moved and renamed ones at their old path, trashed ones out of
+/// the trash; copies go to the trash rather than being deleted, in case they were changed since
+#[derive(Debug)]
+pub struct OperationHistory {
+    done: Vec<Operation>,
+    undone: Vec<Operation>,
+}
+
+impl OperationHistory {
+    pub fn new() -> Self {
+        Self { done: Vec::new(), undone: Vec::new() }
+    }
+
+    /// Remember an operation that just happened; whatever was undone before can't be redone after it
+    pub fn record(&mut self, kind: OperationKind, entries: Vec<(PathBuf, PathBuf)>) {
+        if entries.is_empty() {
+            return;
+        }
+        self.undone.clear();
+        self.done.push(Operation { kind, entries });
+        if self.done.len() > MAX_OPERATIONS {
+            self.done.remove(0);
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Revert the latest operation, returning it as it can now be redone
+    /// Entries that can't be reverted (their old path is taken, say) stay on the undo stack with the error
+    pub fn undo(&mut self, file_service: &FileService, trash: Option<&Path>) -> Result<Operation> {
+        let Some(operation) = self.done.pop() else {
+            return Err(ClazyfilerError::content("history", "Nothing to undo"));
+        };
+        let (reverted, failed) = Self::apply(operation, |kind, (before, after)| {
+            match kind {
+                OperationKind::Copy => match trash {
+                    Some(trash) => file_service.trash_entry(after, trash).map(|_| ())?,
+                    None => file_service.delete_entry(after)?,
+                },
+                OperationKind::Move | OperationKind::Rename => file_service.restore(after, before)?,
+                OperationKind::Trash => file_service.untrash(after, before)?,
+            }
+            Ok((before.clone(), after.clone()))
+        });
+        Self::settle(reverted, failed, &mut self.undone, &mut self.done)
+    }
+
+    /// Do the latest undone operation again, returning it as it can now be undone
+    pub fn redo(&mut self, file_service: &FileService, trash: Option<&Path>) -> Result<Operation> {
+        let Some(operation) = self.undone.pop() else {
+            return Err(ClazyfilerError::content("history", "Nothing to redo"));
+        };
+        let (redone, failed) = Self::apply(operation, |kind, (before, after)| {
+            // Copies and trashed entries may end up under another name than the first time
+            let after = match kind {
+                OperationKind::Copy => file_service.copy_file(before, after.parent().unwrap_or(after))?,
+                OperationKind::Move | OperationKind::Rename => {
+                    file_service.restore(before, after)?;
+                    after.clone()
+                }
+                OperationKind::Trash => {
+                    let trash = trash.ok_or_else(|| ClazyfilerError::content("history", "No trash directory"))?;
+                    file_service.trash_entry(before, trash)?
+                }
+            };
+            Ok((before.clone(), after))
+        });
+        Self::settle(redone, failed, &mut self.done, &mut self.undone)
+    }
+
+    /// Run `step` on every entry, latest first, splitting the operation into the entries it went through
+    /// for and those it failed for, along with the first error
+    fn apply(
+        operation: Operation,
+        mut step: impl FnMut(OperationKind, &(PathBuf, PathBuf)) -> Result<(PathBuf, PathBuf)>,
+    ) -> (Operation, Option<(Operation, ClazyfilerError)>) {
+        let kind = operation.kind;
+        let (mut applied, mut failed, mut error) = (Vec::new(), Vec::new(), None);
+        for entry in operation.entries.into_iter().rev() {
+            match step(kind, &entry) {
+                Ok(entry) => applied.push(entry),
+                Err(e) => {
+                    error.get_or_insert(e);
+                    failed.push(entry);
+                }
+            }
+        }
+        applied.reverse();
+        failed.reverse();
+        let failed = error.map(|error| (Operation { kind, entries: failed }, error));
+        (Operation { kind, entries: applied }, failed)
+    }
+
+    /// Move what was applied onto `to` and put what failed back onto `from`
+    fn settle(
+        applied: Operation,
+        failed: Option<(Operation, ClazyfilerError)>,
+        to: &mut Vec<Operation>,
+        from: &mut Vec<Operation>,
+    ) -> Result<Operation> {
+        if !applied.entries.is_empty() {
+            to.push(applied.clone());
+        }
+        match failed {
+            Some((failed, error)) => {
+                from.push(failed);
+                Err(error)
+            }
+            None => Ok(applied),
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::path::{Path, PathBuf};
+use crate::core::{bencode_dict_value_raw, to_hex, Bencode, BudgetedCache, CacheWeight, ClazyfilerError, LruCache, Result, Sha1, Sha256};
+
+/// Torrent files above this size are not parsed
+const MAX_TORRENT_SIZE: u64 = 16 * 1024 * 1024;
+
+/// What a .torrent file describes
+#[derive(Debug, Clone, Default)]
+pub struct TorrentInfo {
+    pub name: String,
+    pub info_hash_v1: Option<String>,
+    pub info_hash_v2: Option<String>,
+    pub files: Vec<(String, u64)>,    // Path within the torrent and size in bytes
+    pub piece_length: Option<u64>,
+    pub private: bool,
+    pub trackers: Vec<String>,
+    pub comment: Option<String>,
+    pub created_by: Option<String>,
+    pub creation_date: Option<i64>,   // Unix timestamp
+}
+
+impl CacheWeight for TorrentInfo {
+    fn weight(&self) -> usize {
+        let strings = [&self.comment, &self.created_by, &self.info_hash_v1, &self.info_hash_v2]
+            .iter()
+            .map(|text| text.as_ref().map(String::len).unwrap_or(0))
+            .sum::<usize>();
+        std::mem::size_of::<Self>()
+            + self.name.len()
+            + strings
+            + self.files.iter().map(|(path, _)| path.len() + std::mem::size_of::<(String, u64)>()).sum::<usize>()
+            + self.trackers.iter().map(String::weight).sum::<usize>()
+    }
+}
+
+impl TorrentInfo {
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|(_, size)| size).sum()
+    }
+}
+
+/// Service responsible for reading .torrent metadata
+#[derive(Debug)]
+pub struct TorrentService {
+    cache: LruCache<PathBuf, std::result::Result<TorrentInfo, String>>,
+}
+
+impl TorrentService {
+    pub fn new() -> Self {
+        Self {
+            cache: LruCache::new("torrents"),
+        }
+    }
+
+    /// Check whether a path looks like a torrent file
+    pub fn is_torrent(path: &Path) -> bool {
+        path.extension()
+            .map(|ext| ext.to_string_lossy().eq_ignore_ascii_case("torrent"))
+            .unwrap_or(false)
+    }
+
+    /// Parsed torrent metadata, cached per path
+    pub fn info(&self, path: &Path) -> std::result::Result<TorrentInfo, String> {
+        if let Some(cached) = self.cache.get(path) {
+            return cached;
+        }
+        let info = Self::read_info(path).map_err(|e| e.to_string());
+        self.cache.insert(path.to_path_buf(), info.clone());
+        info
+    }
+
+    /// Metadata of a torrent already in memory
+    pub fn parse(data: &[u8]) -> Result<TorrentInfo> {
+        parse_torrent(data, "<memory>")
+    }
+
+    /// Forget cached metadata (e.g. after a refresh)
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
+    /// Caches counted against the memory budget
+    pub fn caches(&self) -> Vec<&dyn BudgetedCache> {
+        vec![&self.cache]
+    }
+
+    fn read_info(path: &Path) -> Result<TorrentInfo> {
+        let path_str = path.to_string_lossy();
+        let size = std::fs::metadata(path)
+            .map_err(|e| ClazyfilerError::file_system("metadata", &path_str, e))?
+            .len();
+        if size > MAX_TORRENT_SIZE {
+            return Err(ClazyfilerError::content(&path_str, "Torrent file too large"));
+        }
+        let data = std::fs::read(path).map_err(|e| ClazyfilerError::file_system("read", &path_str, e))?;
+        parse_torrent(&data, &path_str)
+    }
+}
+
+/// Read torrent metadata from bencoded data; `label` names it in errors
+fn parse_torrent(data: &[u8], label: &str) -> Result<TorrentInfo> {
+    let invalid = |message: &str| ClazyfilerError::content(label, message);
+
+    let torrent = Bencode::parse(data).ok_or_else(|| invalid("Not a valid bencoded file"))?;
+    let info = torrent.get("info").ok_or_else(|| invalid("Missing info dictionary"))?;
+    let raw_info = bencode_dict_value_raw(data, "info").ok_or_else(|| invalid("Missing info dictionary"))?;
+
+    let name = info.get("name.utf-8").or_else(|| info.get("name")).and_then(Bencode::as_str).unwrap_or_default();
+    let version = info.get("meta version").and_then(Bencode::as_int).unwrap_or(1);
+
+    // v1 lists files (or a single length); v2 nests them in a "file tree"
+    let mut files = Vec::new();
+    if let Some(length) = info.get("length").and_then(Bencode::as_int) {
+        files.push((name.clone(), length.max(0) as u64));
+    } else if let Some(entries) = info.get("files").and_then(Bencode::as_list) {
+        for entry in entries {
+            let path = entry.get("path.utf-8").or_else(|| entry.get("path")).and_then(Bencode::as_list).unwrap_or(&[]);
+            let path: Vec<String> = path.iter().filter_map(Bencode::as_str).collect();
+            // BEP 47 padding files are an implementation detail
+            if entry.get("attr").and_then(Bencode::as_str).is_some_and(|attr| attr.contains('p')) {
+                continue;
+            }
+            let length = entry.get("length").and_then(Bencode::as_int).unwrap_or(0);
+            files.push((path.join("/"), length.max(0) as u64));
+        }
+    } else if let Some(tree) = info.get("file tree") {
+        collect_file_tree(tree, String::new(), &mut files, 0);
+    }
+
+    let mut trackers: Vec<String> = Vec::new();
+    if let Some(announce) = torrent.get("announce").and_then(Bencode::as_str) {
+        trackers.push(announce);
+    }
+    for tier in torrent.get("announce-list").and_then(Bencode::as_list).unwrap_or(&[]) {
+        for tracker in tier.as_list().unwrap_or(&[]).iter().filter_map(Bencode::as_str) {
+            if !trackers.contains(&tracker) {
+                trackers.push(tracker);
+            }
+        }
+    }
+
+    let has_v1 = version == 1 || info.get("pieces").is_some();
+    Ok(TorrentInfo {
+        name,
+        info_hash_v1: has_v1.then(|| {
+            let mut hasher = Sha1::new();
+            hasher.update(raw_info);
+            to_hex(&hasher.finish())
+        }),
+        info_hash_v2: (version >= 2).then(|| {
+            let mut hasher = Sha256::new();
+            hasher.update(raw_info);
+            to_hex(&hasher.finish())
+        }),
+        files,
+        piece_length: info.get("piece length").and_then(Bencode::as_int).map(|length| length.max(0) as u64),
+        private: info.get("private").and_then(Bencode::as_int) == Some(1),
+        trackers,
+        comment: torrent.get("comment").and_then(Bencode::as_str),
+        created_by: torrent.get("created by").and_then(Bencode::as_str),
+        creation_date: torrent.get("creation date").and_then(Bencode::as_int),
+    })
+}
+
+/// Flatten a v2 file tree: directories are dicts, files are dicts keyed by "" holding their length
+fn collect_file_tree(node: &Bencode, prefix: String, files: &mut Vec<(String, u64)>, depth: usize) {
+    if depth > 32 {
+        return;
+    }
+    for (key, child) in node.as_dict().unwrap_or(&[]) {
+        if key.is_empty() {
+            let length = child.get("length").and_then(Bencode::as_int).unwrap_or(0);
+            files.push((prefix.clone(), length.max(0) as u64));
+            continue;
+        }
+        let name = String::from_utf8_lossy(key);
+        let path = if prefix.is_empty() { name.to_string() } else { format!("{}/{}", prefix, name) };
+        collect_file_tree(child, path, files, depth + 1);
+    }
+}
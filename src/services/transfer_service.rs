@@ -0,0 +1,106 @@
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use crate::core::{base64_encode, ClazyfilerError, Result};
+
+/// Files above this size are not pushed through the terminal
+const MAX_TRANSFER_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Bytes encoded per write; a multiple of 3 so the base64 pieces join without padding
+const CHUNK_SIZE: usize = 3 * 64 * 1024;
+
+/// Terminals known to accept iTerm2's `OSC 1337 ; File=` download sequence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferTerminal {
+    ITerm2,
+    WezTerm,
+}
+
+impl TransferTerminal {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TransferTerminal::ITerm2 => "iTerm2",
+            TransferTerminal::WezTerm => "WezTerm",
+        }
+    }
+
+    /// Identify the terminal from the environment it exports (which survives ssh and tmux)
+    fn detect() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        if var("TERM_PROGRAM") == "iTerm.app" || var("LC_TERMINAL") == "iTerm2" {
+            Some(TransferTerminal::ITerm2)
+        } else if var("TERM_PROGRAM") == "WezTerm" || std::env::var_os("WEZTERM_PANE").is_some() {
+            Some(TransferTerminal::WezTerm)
+        } else {
+            None
+        }
+    }
+}
+
+/// Service responsible for handing files to the local machine through the terminal
+/// The file is sent inline as an escape sequence; the terminal saves it or offers it for drag and drop
+#[derive(Debug)]
+pub struct TransferService {
+    terminal: Option<TransferTerminal>,
+    in_tmux: bool,
+}
+
+impl TransferService {
+    pub fn new() -> Self {
+        Self {
+            terminal: TransferTerminal::detect(),
+            in_tmux: std::env::var_os("TMUX").is_some(),
+        }
+    }
+
+    /// Terminal that receives transfers, if a supported one was detected
+    pub fn terminal(&self) -> Option<TransferTerminal> {
+        self.terminal
+    }
+
+    /// Write the file to `out` as an `OSC 1337 ; File=` download, returning the terminal used
+    pub fn send_file(&self, path: &Path, out: &mut impl Write) -> Result<TransferTerminal> {
+        let path_str = path.to_string_lossy();
+        let terminal = self.terminal.ok_or_else(|| {
+            ClazyfilerError::terminal("file_transfer", "File transfer needs iTerm2 or WezTerm")
+        })?;
+
+        let mut file = File::open(path).map_err(|e| ClazyfilerError::file_system("open", &path_str, e))?;
+        let size = file.metadata().map_err(|e| ClazyfilerError::file_system("metadata", &path_str, e))?.len();
+        if size > MAX_TRANSFER_SIZE {
+            return Err(ClazyfilerError::content(&path_str, "File too large to send through the terminal"));
+        }
+
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let write_error = |e| ClazyfilerError::file_system("write", "terminal", e);
+        // tmux only forwards the sequence inside a DCS passthrough, with ESC doubled
+        let (start, end) = if self.in_tmux { ("\x1bPtmux;\x1b\x1b", "\x07\x1b\\") } else { ("\x1b", "\x07") };
+        write!(out, "{}]1337;File=name={};size={};inline=0:", start, base64_encode(name.as_bytes()), size).map_err(write_error)?;
+
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        loop {
+            let filled = fill_buffer(&mut file, &mut buffer).map_err(|e| ClazyfilerError::file_system("read", &path_str, e))?;
+            if filled == 0 {
+                break;
+            }
+            out.write_all(base64_encode(&buffer[..filled]).as_bytes()).map_err(write_error)?;
+            if filled < buffer.len() {
+                break;
+            }
+        }
+        write!(out, "{}", end).and_then(|_| out.flush()).map_err(write_error)?;
+        Ok(terminal)
+    }
+}
+
+/// Read until the buffer is full or the file ends, so only the last chunk can be short
+fn fill_buffer(file: &mut File, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match file.read(&mut buffer[filled..])? {
+            0 => break,
+            read => filled += read,
+        }
+    }
+    Ok(filled)
+}
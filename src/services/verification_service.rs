@@ -0,0 +1,377 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use crate::core::{to_hex, IoThrottle, JobControl, Md5, Sha1, Sha256};
+
+/// Sibling suffixes holding a SHA-256 digest for `<file><suffix>`
+const CHECKSUM_SUFFIXES: &[&str] = &[".sha256", ".sha256sum"];
+
+/// Directory-wide checksum lists, as published next to release downloads
+const CHECKSUM_LISTS: &[&str] = &["SHA256SUMS"];
+
+/// Sibling suffixes holding a detached OpenPGP signature
+const SIGNATURE_SUFFIXES: &[&str] = &[".asc"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckKind {
+    Sha256,
+    Signature,
+}
+
+impl CheckKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CheckKind::Sha256 => "SHA-256",
+            CheckKind::Signature => "Signature",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckState {
+    Pending,
+    Passed(String),
+    Failed(String),
+    Unavailable(String),    // Could not be checked (missing tool, unreadable sidecar, ...)
+}
+
+/// One sidecar-based verification of a file
+#[derive(Debug, Clone)]
+pub struct VerificationCheck {
+    pub kind: CheckKind,
+    pub sidecar: PathBuf,
+    pub state: CheckState,
+}
+
+/// MD5, SHA-1 and SHA-256 of a file as lowercase hex, computed in one read
+#[derive(Debug, Clone, PartialEq)]
+pub struct Digests {
+    pub md5: String,
+    pub sha1: String,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DigestState {
+    Pending,
+    Done(Digests),
+    Failed(String),
+}
+
+/// Finished check sent back from a worker thread: (generation, file, check index, result)
+type CheckResult = (u64, PathBuf, usize, CheckState);
+
+/// Digests sent back from a worker thread: (generation, file, result)
+type DigestResult = (u64, PathBuf, DigestState);
+
+/// Service responsible for verifying files against `.sha256`/`.asc` siblings and computing digests on request
+/// Checks run on background threads; `poll` collects their results so the UI can redraw
+#[derive(Debug)]
+pub struct VerificationService {
+    checks: RefCell<HashMap<PathBuf, Vec<VerificationCheck>>>,
+    sender: Sender<CheckResult>,
+    receiver: Receiver<CheckResult>,
+    digests: RefCell<HashMap<PathBuf, DigestState>>,
+    digest_sender: Sender<DigestResult>,
+    digest_receiver: Receiver<DigestResult>,
+    generation: Cell<u64>,    // Bumped on clear so late results from old threads are dropped
+    throttle: IoThrottle,     // Limits how fast checksums read their files
+    control: JobControl,      // Pauses every checksum being computed
+}
+
+impl Default for VerificationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VerificationService {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        let (digest_sender, digest_receiver) = channel();
+        Self {
+            checks: RefCell::new(HashMap::new()),
+            sender,
+            receiver,
+            digests: RefCell::new(HashMap::new()),
+            digest_sender,
+            digest_receiver,
+            generation: Cell::new(0),
+            throttle: IoThrottle::unlimited(),
+            control: JobControl::new(),
+        }
+    }
+
+    /// Pause switch of the checksums, which run as one background job
+    pub fn control(&self) -> &JobControl {
+        &self.control
+    }
+
+    /// Checksums still being computed, for sidecars and on request
+    pub fn pending_checksums(&self) -> usize {
+        let sidecars = self.checks
+            .borrow()
+            .values()
+            .flatten()
+            .filter(|check| check.kind == CheckKind::Sha256 && check.state == CheckState::Pending)
+            .count();
+        sidecars + self.digests.borrow().values().filter(|state| **state == DigestState::Pending).count()
+    }
+
+    /// Share a throttle with the other background jobs
+    pub fn set_throttle(&mut self, throttle: IoThrottle) {
+        self.throttle = throttle;
+    }
+
+    /// Checks for a file, starting them in the background on first request
+    pub fn checks(&self, path: &Path) -> Vec<VerificationCheck> {
+        if let Some(checks) = self.checks.borrow().get(path) {
+            return checks.clone();
+        }
+
+        let checks = find_sidecars(path);
+        for (index, check) in checks.iter().enumerate() {
+            let sender = self.sender.clone();
+            let generation = self.generation.get();
+            let (kind, file, sidecar) = (check.kind, path.to_path_buf(), check.sidecar.clone());
+            let (throttle, control) = (self.throttle.clone(), self.control.clone());
+            std::thread::spawn(move || {
+                let state = match kind {
+                    CheckKind::Sha256 => verify_sha256(&file, &sidecar, &throttle, &control),
+                    CheckKind::Signature => verify_signature(&file, &sidecar),
+                };
+                // The receiver is gone once the app exits
+                let _ = sender.send((generation, file, index, state));
+            });
+        }
+        self.checks.borrow_mut().insert(path.to_path_buf(), checks.clone());
+        checks
+    }
+
+    /// Check the file against its sidecars again, as it may have changed since; returns how many there are
+    pub fn verify_again(&self, path: &Path) -> usize {
+        self.checks.borrow_mut().remove(path);
+        self.checks(path).len()
+    }
+
+    /// Digests of a file computed with `compute_digests`
+    pub fn digests(&self, path: &Path) -> Option<DigestState> {
+        self.digests.borrow().get(path).cloned()
+    }
+
+    /// Start computing the MD5, SHA-1 and SHA-256 of a file in the background, again if they are known
+    /// Returns false while they are already being computed
+    pub fn compute_digests(&self, path: &Path) -> bool {
+        if self.digests.borrow().get(path) == Some(&DigestState::Pending) {
+            return false;
+        }
+        self.digests.borrow_mut().insert(path.to_path_buf(), DigestState::Pending);
+        let sender = self.digest_sender.clone();
+        let generation = self.generation.get();
+        let file = path.to_path_buf();
+        let (throttle, control) = (self.throttle.clone(), self.control.clone());
+        std::thread::spawn(move || {
+            let state = match file_digests(&file, &throttle, &control) {
+                Ok(digests) => DigestState::Done(digests),
+                Err(e) => DigestState::Failed(format!("Read failed: {}", e)),
+            };
+            let _ = sender.send((generation, file, state));
+        });
+        true
+    }
+
+    /// Collect finished checks and digests; returns true if any result arrived
+    pub fn poll(&self) -> bool {
+        let mut updated = false;
+        let mut checks = self.checks.borrow_mut();
+        while let Ok((generation, file, index, state)) = self.receiver.try_recv() {
+            if generation != self.generation.get() {
+                continue;
+            }
+            if let Some(check) = checks.get_mut(&file).and_then(|checks| checks.get_mut(index)) {
+                check.state = state;
+                updated = true;
+            }
+        }
+        let mut digests = self.digests.borrow_mut();
+        while let Ok((generation, file, state)) = self.digest_receiver.try_recv() {
+            if generation == self.generation.get() {
+                digests.insert(file, state);
+                updated = true;
+            }
+        }
+        updated
+    }
+
+    /// Forget results so files are verified again (e.g. after a refresh)
+    pub fn clear_cache(&self) {
+        self.checks.borrow_mut().clear();
+        self.digests.borrow_mut().clear();
+        self.generation.set(self.generation.get() + 1);
+    }
+}
+
+/// Sidecars that exist next to `path`
+fn find_sidecars(path: &Path) -> Vec<VerificationCheck> {
+    let Some(file_name) = path.file_name().map(|name| name.to_string_lossy().to_string()) else {
+        return Vec::new();
+    };
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let check = |kind, sidecar: PathBuf| VerificationCheck { kind, sidecar, state: CheckState::Pending };
+
+    let mut checks = Vec::new();
+    // Lists only count when they actually mention this file
+    let checksum = CHECKSUM_SUFFIXES
+        .iter()
+        .map(|suffix| dir.join(format!("{}{}", file_name, suffix)))
+        .find(|sidecar| sidecar.is_file())
+        .or_else(|| {
+            CHECKSUM_LISTS.iter().map(|list| dir.join(list)).find(|list| {
+                list != path
+                    && std::fs::read_to_string(list)
+                        .map(|listing| expected_digest(&listing, &file_name, false).is_some())
+                        .unwrap_or(false)
+            })
+        });
+    if let Some(sidecar) = checksum {
+        checks.push(check(CheckKind::Sha256, sidecar));
+    }
+    let signature = SIGNATURE_SUFFIXES
+        .iter()
+        .map(|suffix| dir.join(format!("{}{}", file_name, suffix)))
+        .find(|sidecar| sidecar.is_file());
+    if let Some(sidecar) = signature {
+        checks.push(check(CheckKind::Signature, sidecar));
+    }
+    checks
+}
+
+/// Compare the file's digest with the one recorded for it in the sidecar
+fn verify_sha256(path: &Path, sidecar: &Path, throttle: &IoThrottle, control: &JobControl) -> CheckState {
+    let file_name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let Ok(listing) = std::fs::read_to_string(sidecar) else {
+        return CheckState::Unavailable("Checksum file unreadable".to_string());
+    };
+    let is_list = sidecar
+        .file_name()
+        .is_some_and(|name| CHECKSUM_LISTS.iter().any(|list| name.to_string_lossy() == *list));
+    let Some(expected) = expected_digest(&listing, &file_name, !is_list) else {
+        return CheckState::Unavailable(format!("No entry for {}", file_name));
+    };
+
+    let Ok(mut file) = File::open(path) else {
+        return CheckState::Unavailable("File unreadable".to_string());
+    };
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => {
+                hasher.update(&buffer[..read]);
+                throttle.consume(read as u64);
+                control.wait_while_paused();
+            }
+            Err(e) => return CheckState::Unavailable(format!("Read failed: {}", e)),
+        }
+    }
+
+    let actual = to_hex(&hasher.finish());
+    if actual.eq_ignore_ascii_case(&expected) {
+        CheckState::Passed("Checksum matches".to_string())
+    } else {
+        CheckState::Failed(format!("Checksum mismatch (got {}…)", &actual[..16]))
+    }
+}
+
+/// Read the file once, feeding every digest
+fn file_digests(path: &Path, throttle: &IoThrottle, control: &JobControl) -> std::io::Result<Digests> {
+    let mut file = File::open(path)?;
+    let (mut md5, mut sha1, mut sha256) = (Md5::new(), Sha1::new(), Sha256::new());
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        md5.update(&buffer[..read]);
+        sha1.update(&buffer[..read]);
+        sha256.update(&buffer[..read]);
+        throttle.consume(read as u64);
+        control.wait_while_paused();
+    }
+    Ok(Digests { md5: to_hex(&md5.finish()), sha1: to_hex(&sha1.finish()), sha256: to_hex(&sha256.finish()) })
+}
+
+/// Find the digest for `file_name` in `sha256sum` (`<hex>  name`) or BSD (`SHA256 (name) = <hex>`) format
+/// A single bare digest is accepted too (`allow_bare`) for per-file sidecars, as written by many release scripts
+fn expected_digest(listing: &str, file_name: &str, allow_bare: bool) -> Option<String> {
+    let is_digest = |text: &str| text.len() == 64 && text.chars().all(|c| c.is_ascii_hexdigit());
+    let mut bare = Vec::new();
+
+    for line in listing.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        if let Some(rest) = line.strip_prefix("SHA256 (") {
+            if let Some((name, digest)) = rest.split_once(") = ") {
+                if name.rsplit('/').next() == Some(file_name) && is_digest(digest.trim()) {
+                    return Some(digest.trim().to_string());
+                }
+            }
+            continue;
+        }
+        let mut parts = line.splitn(2, char::is_whitespace);
+        let digest = parts.next().unwrap_or("");
+        if !is_digest(digest) {
+            continue;
+        }
+        match parts.next().map(|name| name.trim().trim_start_matches('*')) {
+            Some(name) if !name.is_empty() => {
+                if name.rsplit('/').next() == Some(file_name) {
+                    return Some(digest.to_string());
+                }
+            }
+            _ => bare.push(digest.to_string()),
+        }
+    }
+
+    if allow_bare && bare.len() == 1 { bare.pop() } else { None }
+}
+
+/// Run `gpg --verify` and interpret its machine-readable status lines
+fn verify_signature(path: &Path, sidecar: &Path) -> CheckState {
+    let output = Command::new("gpg")
+        .args(["--batch", "--no-tty", "--status-fd", "1", "--verify"])
+        .arg(sidecar)
+        .arg(path)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output();
+    let Ok(output) = output else {
+        return CheckState::Unavailable("gpg not installed".to_string());
+    };
+
+    let status = String::from_utf8_lossy(&output.stdout);
+    let mut state = None;
+    for line in status.lines() {
+        let Some(line) = line.strip_prefix("[GNUPG:] ") else { continue };
+        let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+        // rest is "<keyid> <user id>" for signature results
+        let signer = rest.split_once(' ').map(|(_, user)| user).unwrap_or(rest);
+        state = match keyword {
+            "GOODSIG" => Some(CheckState::Passed(format!("Good signature from {}", signer))),
+            "EXPKEYSIG" => Some(CheckState::Passed(format!("Good signature from {} (key expired)", signer))),
+            "REVKEYSIG" => Some(CheckState::Failed(format!("Signed by revoked key of {}", signer))),
+            "BADSIG" => Some(CheckState::Failed(format!("Bad signature from {}", signer))),
+            "NO_PUBKEY" => Some(CheckState::Unavailable(format!("Public key {} not in keyring", rest))),
+            _ => continue,
+        };
+        if matches!(state, Some(CheckState::Failed(_))) {
+            break;
+        }
+    }
+
+    state.unwrap_or_else(|| CheckState::Unavailable("gpg could not check the signature".to_string()))
+}
@@ -0,0 +1,279 @@
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use crate::core::{BudgetedCache, CacheWeight, ClazyfilerError, LruCache, Result};
+
+/// Extensions treated as video files
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "m4v", "mkv", "webm", "mov", "avi", "wmv", "flv", "mpg", "mpeg", "ts"];
+
+/// Metadata reported by ffprobe
+#[derive(Debug, Clone, Default)]
+pub struct VideoInfo {
+    pub duration_secs: Option<f64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    pub frame_rate: Option<String>,
+    pub bit_rate: Option<u64>,
+    pub format: Option<String>,
+}
+
+impl CacheWeight for VideoInfo {
+    fn weight(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + [&self.video_codec, &self.audio_codec, &self.frame_rate, &self.format]
+                .iter()
+                .map(|text| text.as_ref().map(String::len).unwrap_or(0))
+                .sum::<usize>()
+    }
+}
+
+/// A probe or frame, as far as its background run has got
+#[derive(Debug, Clone)]
+pub enum VideoState<T> {
+    Pending,
+    Done(T),
+    Failed,
+}
+
+/// Probe finished on a worker thread: (generation, file, result)
+type InfoResult = (u64, PathBuf, Option<VideoInfo>);
+
+/// Frame extracted on a worker thread: (generation, file, result)
+type FrameResult = (u64, PathBuf, Option<PathBuf>);
+
+/// Service responsible for video metadata and frame extraction via ffprobe/ffmpeg
+/// Both tools are optional; they run on background threads so drawing never waits on them,
+/// and `poll` collects their results into caches per path
+#[derive(Debug)]
+pub struct VideoService {
+    cache_dir: Option<PathBuf>,
+    tools_available: RefCell<Option<bool>>,
+    info_cache: LruCache<PathBuf, Option<VideoInfo>>,
+    frame_cache: LruCache<PathBuf, Option<PathBuf>>,
+    probing: RefCell<HashSet<PathBuf>>,       // Probes started and not collected yet
+    extracting: RefCell<HashSet<PathBuf>>,    // Same for frames
+    info_sender: Sender<InfoResult>,
+    info_receiver: Receiver<InfoResult>,
+    frame_sender: Sender<FrameResult>,
+    frame_receiver: Receiver<FrameResult>,
+    generation: Cell<u64>,    // Bumped on clear so late results from old threads are dropped
+}
+
+impl VideoService {
+    pub fn new(cache_dir: Option<PathBuf>) -> Self {
+        let (info_sender, info_receiver) = channel();
+        let (frame_sender, frame_receiver) = channel();
+        Self {
+            cache_dir,
+            tools_available: RefCell::new(None),
+            info_cache: LruCache::new("videos"),
+            frame_cache: LruCache::new("video frames"),
+            probing: RefCell::new(HashSet::new()),
+            extracting: RefCell::new(HashSet::new()),
+            info_sender,
+            info_receiver,
+            frame_sender,
+            frame_receiver,
+            generation: Cell::new(0),
+        }
+    }
+
+    /// Check whether a path looks like a video file
+    pub fn is_video(path: &Path) -> bool {
+        path.extension()
+            .map(|ext| VIDEO_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()))
+            .unwrap_or(false)
+    }
+
+    /// Whether ffprobe is installed (checked once)
+    pub fn is_available(&self) -> bool {
+        *self.tools_available.borrow_mut().get_or_insert_with(|| {
+            Command::new("ffprobe")
+                .arg("-version")
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .map(|status| status.success())
+                .unwrap_or(false)
+        })
+    }
+
+    /// Duration, resolution and codecs of a video, probed in the background on first request
+    pub fn info(&self, path: &Path) -> VideoState<VideoInfo> {
+        if let Some(cached) = self.info_cache.get(path) {
+            return cached.map_or(VideoState::Failed, VideoState::Done);
+        }
+        if self.probing.borrow_mut().insert(path.to_path_buf()) {
+            let sender = self.info_sender.clone();
+            let generation = self.generation.get();
+            let file = path.to_path_buf();
+            std::thread::spawn(move || {
+                let info = probe(&file).ok();
+                // The receiver is gone once the app exits
+                let _ = sender.send((generation, file, info));
+            });
+        }
+        VideoState::Pending
+    }
+
+    /// A representative frame as PNG in the cache directory, extracted in the background on first request
+    pub fn frame(&self, path: &Path) -> VideoState<PathBuf> {
+        if let Some(cached) = self.frame_cache.get(path) {
+            return cached.map_or(VideoState::Failed, VideoState::Done);
+        }
+        let Some(cache_dir) = self.cache_dir.clone() else {
+            return VideoState::Failed;
+        };
+        if self.extracting.borrow_mut().insert(path.to_path_buf()) {
+            let sender = self.frame_sender.clone();
+            let generation = self.generation.get();
+            let file = path.to_path_buf();
+            let duration = self.info_cache.get(path).flatten().and_then(|info| info.duration_secs);
+            std::thread::spawn(move || {
+                let frame = extract_frame(&cache_dir, &file, duration).ok();
+                let _ = sender.send((generation, file, frame));
+            });
+        }
+        VideoState::Pending
+    }
+
+    /// Collect finished probes and frames; returns true if any result arrived
+    pub fn poll(&self) -> bool {
+        let mut updated = false;
+        while let Ok((generation, file, info)) = self.info_receiver.try_recv() {
+            if generation == self.generation.get() {
+                self.probing.borrow_mut().remove(&file);
+                self.info_cache.insert(file, info);
+                updated = true;
+            }
+        }
+        while let Ok((generation, file, frame)) = self.frame_receiver.try_recv() {
+            if generation == self.generation.get() {
+                self.extracting.borrow_mut().remove(&file);
+                self.frame_cache.insert(file, frame);
+                updated = true;
+            }
+        }
+        updated
+    }
+
+    /// Drop cached results (e.g. after files changed on disk)
+    pub fn clear_cache(&self) {
+        self.info_cache.clear();
+        self.frame_cache.clear();
+        self.probing.borrow_mut().clear();
+        self.extracting.borrow_mut().clear();
+        self.generation.set(self.generation.get() + 1);
+    }
+
+    /// Caches counted against the memory budget
+    pub fn caches(&self) -> Vec<&dyn BudgetedCache> {
+        vec![&self.info_cache, &self.frame_cache]
+    }
+}
+
+fn probe(path: &Path) -> Result<VideoInfo> {
+    let output = Command::new("ffprobe")
+        .args(["-v", "error", "-show_entries"])
+        .arg("format=duration,bit_rate,format_long_name:stream=codec_type,codec_name,width,height,r_frame_rate")
+        .args(["-of", "default=noprint_wrappers=1"])
+        .arg(path)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| ClazyfilerError::external_tool("ffprobe", &format!("Failed to launch: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(ClazyfilerError::content(
+            path.to_string_lossy().as_ref(),
+            String::from_utf8_lossy(&output.stderr).trim(),
+        ));
+    }
+
+    Ok(parse_probe_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `key=value` lines; stream entries follow their `codec_type`
+fn parse_probe_output(output: &str) -> VideoInfo {
+    let mut info = VideoInfo::default();
+    let mut stream_type = String::new();
+    let mut codec_name = None;
+
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once('=') else { continue };
+        if value == "N/A" {
+            continue;
+        }
+        match key {
+            "codec_name" => codec_name = Some(value.to_string()),
+            "codec_type" => {
+                stream_type = value.to_string();
+                match stream_type.as_str() {
+                    "video" if info.video_codec.is_none() => info.video_codec = codec_name.take(),
+                    "audio" if info.audio_codec.is_none() => info.audio_codec = codec_name.take(),
+                    _ => {}
+                }
+            }
+            "width" if stream_type == "video" => info.width = info.width.or(value.parse().ok()),
+            "height" if stream_type == "video" => info.height = info.height.or(value.parse().ok()),
+            "r_frame_rate" if stream_type == "video" && info.frame_rate.is_none() => {
+                info.frame_rate = format_frame_rate(value);
+            }
+            "duration" => info.duration_secs = value.parse().ok(),
+            "bit_rate" => info.bit_rate = value.parse().ok(),
+            "format_long_name" => info.format = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    info
+}
+
+/// "30000/1001" -> "29.97 fps"
+fn format_frame_rate(rate: &str) -> Option<String> {
+    let (num, den) = rate.split_once('/')?;
+    let (num, den): (f64, f64) = (num.parse().ok()?, den.parse().ok()?);
+    if den == 0.0 || num == 0.0 {
+        return None;
+    }
+    Some(format!("{:.2} fps", num / den))
+}
+
+/// Runs on a worker thread; probes for the duration itself unless it is already known
+fn extract_frame(cache_dir: &Path, path: &Path, duration: Option<f64>) -> Result<PathBuf> {
+    let frames_dir = cache_dir.join("frames");
+    std::fs::create_dir_all(&frames_dir)
+        .map_err(|e| ClazyfilerError::file_system("create_dir", frames_dir.to_string_lossy().as_ref(), e))?;
+
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    let frame_path = frames_dir.join(format!("{:016x}.png", hasher.finish()));
+
+    // Seek 10% in to skip black intro frames
+    let seek = duration
+        .or_else(|| probe(path).ok().and_then(|info| info.duration_secs))
+        .map(|duration| duration * 0.1)
+        .unwrap_or(0.0);
+
+    let status = Command::new("ffmpeg")
+        .args(["-v", "error", "-y", "-ss", &format!("{:.2}", seek), "-i"])
+        .arg(path)
+        .args(["-frames:v", "1", "-vf", "scale=320:-1"])
+        .arg(&frame_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| ClazyfilerError::external_tool("ffmpeg", &format!("Failed to launch: {}", e)))?;
+
+    if status.success() && frame_path.exists() {
+        Ok(frame_path)
+    } else {
+        Err(ClazyfilerError::external_tool("ffmpeg", &format!("Frame extraction exited with status: {}", status)))
+    }
+}
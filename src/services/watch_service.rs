@@ -0,0 +1,185 @@
+use std::path::{Path, PathBuf};
+
+/// Watching more directories than this costs more kernel memory than rescanning is worth
+const MAX_WATCHED_DIRS: usize = 8192;
+
+/// A change reported for a watched directory tree
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchEvent {
+    Created { path: PathBuf, is_directory: bool },  // Also the destination of a rename
+    Removed(PathBuf),                               // Also the source of a rename
+    Written(PathBuf),                               // A file was closed after writing
+    Overflow,                                       // Events were lost; the tree must be rescanned
+}
+
+/// Directory watcher built on inotify, so the fuzzy index can be patched instead of rescanned
+/// Only available on Linux; elsewhere `new` returns None and callers fall back to rescans
+#[derive(Debug)]
+pub struct TreeWatcher {
+    #[cfg(target_os = "linux")]
+    inner: inotify::Inotify,
+}
+
+impl TreeWatcher {
+    pub fn new() -> Option<Self> {
+        #[cfg(target_os = "linux")]
+        {
+            inotify::Inotify::new().map(|inner| Self { inner })
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+
+    /// Start watching `dir` (not recursively); false once the watch limit is reached or the kernel refuses
+    pub fn watch(&mut self, dir: &Path) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            self.inner.watched() < MAX_WATCHED_DIRS && self.inner.add_watch(dir)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (dir, MAX_WATCHED_DIRS);
+            false
+        }
+    }
+
+    /// Changes since the last call, without blocking
+    pub fn events(&mut self) -> Vec<WatchEvent> {
+        #[cfg(target_os = "linux")]
+        {
+            self.inner.read_events()
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod inotify {
+    use std::collections::HashMap;
+    use std::ffi::{CString, OsStr};
+    use std::fs::File;
+    use std::io::Read;
+    use std::os::fd::{AsRawFd, FromRawFd};
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::{Path, PathBuf};
+    use super::WatchEvent;
+
+    const WATCH_MASK: u32 = libc::IN_CREATE
+        | libc::IN_DELETE
+        | libc::IN_MOVED_FROM
+        | libc::IN_MOVED_TO
+        | libc::IN_CLOSE_WRITE
+        | libc::IN_DELETE_SELF
+        | libc::IN_MOVE_SELF
+        | libc::IN_ONLYDIR;
+
+    /// Size of `struct inotify_event` before the variable-length name
+    const EVENT_HEADER: usize = 16;
+
+    #[derive(Debug)]
+    pub struct Inotify {
+        file: File,                        // Non-blocking inotify descriptor, closed on drop
+        watches: HashMap<i32, PathBuf>,    // Watch descriptor -> watched directory
+    }
+
+    impl Inotify {
+        pub fn new() -> Option<Self> {
+            // SAFETY: inotify_init1 takes no pointers; a valid descriptor is owned by the File from here on
+            let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+            if fd < 0 {
+                return None;
+            }
+            // SAFETY: fd was just created and nothing else owns it
+            let file = unsafe { File::from_raw_fd(fd) };
+            Some(Self { file, watches: HashMap::new() })
+        }
+
+        pub fn watched(&self) -> usize {
+            self.watches.len()
+        }
+
+        pub fn add_watch(&mut self, dir: &Path) -> bool {
+            let Ok(path) = CString::new(dir.as_os_str().as_bytes()) else {
+                return false;
+            };
+            // SAFETY: the descriptor is open for the lifetime of self and path is NUL-terminated
+            let wd = unsafe { libc::inotify_add_watch(self.file.as_raw_fd(), path.as_ptr(), WATCH_MASK) };
+            if wd < 0 {
+                return false;
+            }
+            self.watches.insert(wd, dir.to_path_buf());
+            true
+        }
+
+        fn remove_watches_under(&mut self, root: &Path) {
+            let stale: Vec<i32> = self.watches.iter()
+                .filter(|(_, dir)| dir.starts_with(root))
+                .map(|(&wd, _)| wd)
+                .collect();
+            for wd in stale {
+                // SAFETY: plain syscall on our own descriptor; an already removed wd just fails
+                unsafe { libc::inotify_rm_watch(self.file.as_raw_fd(), wd) };
+                self.watches.remove(&wd);
+            }
+        }
+
+        pub fn read_events(&mut self) -> Vec<WatchEvent> {
+            let mut events = Vec::new();
+            let mut buffer = vec![0u8; 64 * 1024];
+            // Non-blocking: read until the kernel reports WouldBlock
+            while let Ok(read) = self.file.read(&mut buffer) {
+                if read == 0 {
+                    break;
+                }
+                let mut offset = 0;
+                while offset + EVENT_HEADER <= read {
+                    let field = |index: usize| {
+                        let start = offset + index * 4;
+                        u32::from_ne_bytes([buffer[start], buffer[start + 1], buffer[start + 2], buffer[start + 3]])
+                    };
+                    let (wd, mask, name_len) = (field(0) as i32, field(1), field(3) as usize);
+                    let name_bytes = &buffer[offset + EVENT_HEADER..(offset + EVENT_HEADER + name_len).min(read)];
+                    let name = OsStr::from_bytes(name_bytes.split(|&b| b == 0).next().unwrap_or_default());
+                    offset += EVENT_HEADER + name_len;
+
+                    if mask & libc::IN_Q_OVERFLOW != 0 {
+                        events.push(WatchEvent::Overflow);
+                        continue;
+                    }
+                    if mask & libc::IN_IGNORED != 0 {
+                        self.watches.remove(&wd);
+                        continue;
+                    }
+                    let Some(dir) = self.watches.get(&wd).cloned() else { continue };
+                    if mask & (libc::IN_DELETE_SELF | libc::IN_MOVE_SELF) != 0 {
+                        // The parent's watch reports the same change by name; a lost root means rescanning
+                        if !self.watches.values().any(|other| dir.parent() == Some(other.as_path())) {
+                            events.push(WatchEvent::Overflow);
+                        }
+                        continue;
+                    }
+
+                    let path = dir.join(name);
+                    let is_directory = mask & libc::IN_ISDIR != 0;
+                    if mask & (libc::IN_CREATE | libc::IN_MOVED_TO) != 0 {
+                        events.push(WatchEvent::Created { path, is_directory });
+                    } else if mask & (libc::IN_DELETE | libc::IN_MOVED_FROM) != 0 {
+                        if is_directory {
+                            // A directory moved elsewhere keeps its watches, which would report under stale paths
+                            self.remove_watches_under(&path);
+                        }
+                        events.push(WatchEvent::Removed(path));
+                    } else if mask & libc::IN_CLOSE_WRITE != 0 {
+                        events.push(WatchEvent::Written(path));
+                    }
+                }
+            }
+            events
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set from the signal handler, picked up by the event loop on its next wake-up
+static SUSPEND_REQUESTED: AtomicBool = AtomicBool::new(false);
+static RESUMED: AtomicBool = AtomicBool::new(false);
+static TERMINATION_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Catch job-control and termination signals, so the terminal is restored before the process stops
+/// and state is saved before it exits
+/// Raw mode turns off the tty's own Ctrl-Z handling, so SIGTSTP only arrives from `kill`;
+/// the key itself is read as a normal key event
+pub fn install() {
+    #[cfg(unix)]
+    unix::install();
+}
+
+/// Whether a SIGTSTP arrived since the last call
+pub fn take_suspend_request() -> bool {
+    SUSPEND_REQUESTED.swap(false, Ordering::Relaxed)
+}
+
+/// Whether a SIGTERM or SIGHUP (the terminal window was closed) arrived since the last call
+pub fn take_termination_request() -> bool {
+    TERMINATION_REQUESTED.swap(false, Ordering::Relaxed)
+}
+
+/// Whether the process was continued since the last call, e.g. after an uncatchable SIGSTOP
+pub fn take_resumed() -> bool {
+    RESUMED.swap(false, Ordering::Relaxed)
+}
+
+/// Stop the process like the shell's Ctrl-Z would, returning once it is continued with `fg`
+/// The terminal must already be handed back to the shell
+pub fn stop_process() {
+    #[cfg(unix)]
+    unix::stop_process();
+    // Continuing is already handled here, so the SIGCONT it caused needs no further repaint
+    RESUMED.store(false, Ordering::Relaxed);
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::sync::atomic::Ordering;
+    use super::{RESUMED, SUSPEND_REQUESTED, TERMINATION_REQUESTED};
+
+    extern "C" fn on_signal(signal: libc::c_int) {
+        // Only async-signal-safe work here: record the signal and let the event loop act on it
+        match signal {
+            libc::SIGTSTP => SUSPEND_REQUESTED.store(true, Ordering::Relaxed),
+            libc::SIGCONT => RESUMED.store(true, Ordering::Relaxed),
+            libc::SIGTERM | libc::SIGHUP => TERMINATION_REQUESTED.store(true, Ordering::Relaxed),
+            _ => {}
+        }
+    }
+
+    fn set_handler(signal: libc::c_int, handler: libc::sighandler_t) {
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = handler;
+            // Restart interrupted reads so the event loop doesn't see spurious errors
+            action.sa_flags = libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+            libc::sigaction(signal, &action, std::ptr::null_mut());
+        }
+    }
+
+    pub fn install() {
+        let handler = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        set_handler(libc::SIGTSTP, handler);
+        set_handler(libc::SIGCONT, handler);
+        set_handler(libc::SIGTERM, handler);
+        set_handler(libc::SIGHUP, handler);
+    }
+
+    pub fn stop_process() {
+        // With the default action restored, raising SIGTSTP stops us until SIGCONT
+        set_handler(libc::SIGTSTP, libc::SIG_DFL);
+        unsafe { libc::raise(libc::SIGTSTP) };
+        install();
+    }
+}
@@ -1,75 +1,127 @@
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    event::{self, DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    Terminal,
+    Terminal, TerminalOptions, Viewport,
 };
 use std::io::{self, Stdout};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
-/// Terminal wrapper that handles setup and cleanup automatically
-pub struct TerminalManager {
-    terminal: Terminal<CrosstermBackend<Stdout>>,
+/// How the UI occupies the terminal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScreenMode {
+    Alternate,    // Full screen on the alternate screen, which restores the shell's output on exit
+    Inline(u16),  // A viewport of this many lines in the normal scrollback
 }
 
-impl TerminalManager {
-    
-    /// Clean shutdown of terminal (called automatically on Drop)
-    fn cleanup(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Disable raw mode
-        disable_raw_mode()?;
-        
-        // Restore terminal state
-        execute!(
-            self.terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        
-        // Show cursor
-        self.terminal.show_cursor()?;
-        
-        Ok(())
+/// Whether the UI runs inline, so suspending for external commands leaves the alternate screen alone
+static INLINE: AtomicBool = AtomicBool::new(false);
+
+/// Whether crossterm reads input from stdin, which then is what the event loop polls
+/// Decided at setup, since a hung-up tty no longer answers `isatty`
+#[cfg(unix)]
+static STDIN_TTY: AtomicBool = AtomicBool::new(false);
+
+/// Outcome of waiting for terminal input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputWait {
+    Ready,   // An event can be read without blocking
+    Idle,    // Timed out, or a signal arrived
+    HungUp,  // The terminal is gone, e.g. its window was closed
+}
+
+/// Wait up to `timeout` for an input event
+/// crossterm keeps retrying reads from a hung-up tty forever, so on Unix the tty is polled here first
+/// and a hangup reported instead; a signal also ends the wait, so its flag is acted on right away
+pub fn wait_for_input(timeout: Duration) -> io::Result<InputWait> {
+    #[cfg(unix)]
+    if STDIN_TTY.load(Ordering::Relaxed) {
+        if terminal_hung_up() {
+            return Ok(InputWait::HungUp);
+        }
+        // Events left over from an earlier read, and resizes, never show up on stdin
+        if event::poll(Duration::ZERO)? {
+            return Ok(InputWait::Ready);
+        }
+        return match poll_stdin(timeout)? {
+            InputWait::Ready if event::poll(Duration::ZERO)? => Ok(InputWait::Ready),
+            InputWait::HungUp => Ok(InputWait::HungUp),
+            _ => Ok(InputWait::Idle),
+        };
     }
+    // crossterm reads /dev/tty when stdin is redirected, which is left to crossterm itself
+    Ok(if event::poll(timeout)? { InputWait::Ready } else { InputWait::Idle })
 }
 
-impl Drop for TerminalManager {
-    /// Automatically cleanup terminal when TerminalManager is dropped
-    fn drop(&mut self) {
-        if let Err(e) = self.cleanup() {
-            eprintln!("Error during terminal cleanup: {:?}", e);
+/// Whether the terminal on stdin is gone, so there is nothing left to restore on exit
+pub fn terminal_hung_up() -> bool {
+    #[cfg(unix)]
+    {
+        STDIN_TTY.load(Ordering::Relaxed) && matches!(poll_stdin(Duration::ZERO), Ok(InputWait::HungUp))
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+#[cfg(unix)]
+fn poll_stdin(timeout: Duration) -> io::Result<InputWait> {
+    let mut fd = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+    let timeout = timeout.as_millis().min(libc::c_int::MAX as u128) as libc::c_int;
+    match unsafe { libc::poll(&mut fd, 1, timeout) } {
+        -1 => {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted { Ok(InputWait::Idle) } else { Err(err) }
         }
+        0 => Ok(InputWait::Idle),
+        _ if fd.revents & (libc::POLLHUP | libc::POLLERR | libc::POLLNVAL) != 0 => Ok(InputWait::HungUp),
+        _ => Ok(InputWait::Ready),
     }
 }
 
-/// RAII-style terminal management
+/// Scoped terminal management
 /// 
-/// This provides automatic setup and cleanup of terminal resources:
+/// This provides setup and cleanup of terminal resources around `f`:
 /// - Enables raw mode for character input
-/// - Sets up alternate screen to preserve terminal state
-/// - Enables mouse capture for potential future features  
-/// - Automatically restores terminal state on drop
+/// - Sets up alternate screen to preserve terminal state, or an inline viewport in `ScreenMode::Inline`
+/// - Enables mouse capture for clickable column headers
+/// - Restores terminal state once `f` returns
 /// 
 /// Example usage:
-/// ```rust
-/// let mut term_manager = TerminalManager::new()?;
-/// let terminal = term_manager.terminal();
-/// // Use terminal...
-/// // Cleanup happens automatically when term_manager goes out of scope
+/// ```rust,ignore
+/// with_terminal(ScreenMode::Alternate, |terminal| {
+///     // Use terminal...
+///     Ok(())
+/// })?;
 /// ```
-pub fn with_terminal<F, R>(f: F) -> Result<R, Box<dyn std::error::Error>>
+pub fn with_terminal<F, R>(mode: ScreenMode, f: F) -> Result<R, Box<dyn std::error::Error>>
 where
     F: FnOnce(Terminal<CrosstermBackend<Stdout>>) -> Result<R, Box<dyn std::error::Error>>,
 {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let viewport = match mode {
+        ScreenMode::Alternate => {
+            execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+            Viewport::Fullscreen
+        }
+        ScreenMode::Inline(height) => {
+            execute!(stdout, EnableMouseCapture)?;
+            Viewport::Inline(height)
+        }
+    };
+    INLINE.store(matches!(mode, ScreenMode::Inline(_)), Ordering::Relaxed);
+    #[cfg(unix)]
+    STDIN_TTY.store(unsafe { libc::isatty(libc::STDIN_FILENO) } == 1, Ordering::Relaxed);
     
     let backend = CrosstermBackend::new(stdout);
-    let terminal = Terminal::new(backend)?;
+    let terminal = Terminal::with_options(backend, TerminalOptions { viewport })?;
     
     // Run the function with the terminal
     let result = f(terminal);
@@ -77,7 +129,11 @@ where
     // Cleanup terminal state
     disable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
+    if mode == ScreenMode::Alternate {
+        execute!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
+    } else {
+        execute!(stdout, DisableMouseCapture)?;
+    }
     
     result
 }
@@ -90,6 +146,9 @@ pub trait TerminalExt {
     /// Resume the TUI terminal state after external command finishes
     fn resume_from_external_command(&mut self) -> Result<(), Box<dyn std::error::Error>>;
     
+    /// Leave the screen tidy on exit: an inline viewport is cleared so the shell prompt continues where it started
+    fn release(&mut self) -> Result<(), Box<dyn std::error::Error>>;
+    
     /// Execute a closure with suspended terminal (convenience method)
     fn with_suspended_terminal<F, R>(&mut self, f: F) -> Result<R, Box<dyn std::error::Error>>
     where
@@ -100,24 +159,47 @@ impl<B: ratatui::backend::Backend> TerminalExt for Terminal<B>
 where B: ratatui::backend::Backend,
 {
     fn suspend_for_external_command(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Leave alternate screen and disable raw mode for external command
-        let mut stdout = io::stdout();
-        execute!(stdout, LeaveAlternateScreen)?;
+        if INLINE.load(Ordering::Relaxed) {
+            // The command's output replaces the viewport in the scrollback
+            self.release()?;
+        } else {
+            // Leave alternate screen for external command
+            execute!(io::stdout(), LeaveAlternateScreen)?;
+        }
+        // Mouse reports would otherwise show up as garbage in the shell
+        execute!(io::stdout(), DisableMouseCapture)?;
+        self.show_cursor()?;
         disable_raw_mode()?;
         Ok(())
     }
     
     fn resume_from_external_command(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Re-enter alternate screen and enable raw mode
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
         enable_raw_mode()?;
+        execute!(io::stdout(), EnableMouseCapture)?;
+        if INLINE.load(Ordering::Relaxed) {
+            // Anchor a fresh viewport below whatever the command printed
+            let size = self.size()?;
+            self.resize(ratatui::layout::Rect::new(0, 0, size.width, size.height))?;
+            return Ok(());
+        }
+        
+        // Re-enter alternate screen
+        execute!(io::stdout(), EnterAlternateScreen)?;
         
         // Clear terminal to remove any artifacts from external command
         self.clear()?;
         Ok(())
     }
     
+    fn release(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if INLINE.load(Ordering::Relaxed) {
+            let top = self.get_frame().area().y;
+            self.clear()?;
+            self.set_cursor_position((0, top))?;
+        }
+        Ok(())
+    }
+    
     fn with_suspended_terminal<F, R>(&mut self, f: F) -> Result<R, Box<dyn std::error::Error>>
     where
         F: FnOnce() -> Result<R, Box<dyn std::error::Error>>
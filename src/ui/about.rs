@@ -0,0 +1,32 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use crate::{model::AppModel, ui::popup::centered_rect, version};
+
+/// Renders build metadata, paths in use and what was detected about the terminal as an overlay
+pub fn render_about(frame: &mut Frame, area: Rect, model: &AppModel) {
+    let popup_area = centered_rect(70, 60, area);
+    let block = Block::default()
+        .title("Version - ESC: close")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let label = |name: &str| Span::styled(format!("{:<12}", name), Style::default().fg(Color::Gray));
+    let heading = |text: &str| Line::from(Span::styled(text.to_string(), Style::default().add_modifier(Modifier::BOLD)));
+    let rows = |pairs: Vec<(&'static str, String)>| {
+        pairs.into_iter().map(move |(name, value)| Line::from(vec![label(name), Span::raw(value)]))
+    };
+
+    let mut lines = vec![heading(&version::version_line())];
+    lines.extend(rows(version::about()));
+    lines.push(Line::from(""));
+    lines.push(heading("Terminal"));
+    lines.extend(rows(model.terminal_summary()));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), popup_area);
+}
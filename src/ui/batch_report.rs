@@ -0,0 +1,37 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use crate::{core::display_path, model::AppModel, ui::{popup::centered_rect, theme::{highlight_style, plain}}};
+
+/// Renders the entries a bulk operation could not process, each with its error, as an overlay
+pub fn render_batch_report(frame: &mut Frame, area: Rect, model: &AppModel, selected: usize) {
+    let Some(batch) = &model.batch else { return };
+    let popup_area = centered_rect(80, 60, area);
+
+    let items: Vec<ListItem> = batch.failures
+        .iter()
+        .map(|failure| {
+            ListItem::new(vec![
+                Line::from(plain(model, &format!("⚠ {}", display_path(&failure.path))).into_owned()),
+                Line::from(Span::styled(format!("  {}", failure.error), Style::default().fg(Color::Gray))),
+            ])
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(format!("{} - r: retry failed, s: skip selected, ESC: skip all", batch.progress()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightRed));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(highlight_style(model))
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut ListState::default().with_selected(Some(selected)));
+}
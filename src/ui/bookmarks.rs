@@ -0,0 +1,48 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use crate::{core::display_path, model::AppModel, ui::popup::centered_rect};
+
+/// Renders the bookmarks popup, each directory after its key and name
+/// While moving, the title says how many entries go and bookmarks that are gone are greyed out
+pub fn render_bookmarks(frame: &mut Frame, area: Rect, model: &AppModel, moving: bool, selected: usize) {
+    let popup_area = centered_rect(60, 50, area);
+
+    let bookmarks = model.bookmarks();
+    let name_width = bookmarks.iter().map(|bookmark| bookmark.name.chars().count()).max().unwrap_or(0);
+    let items: Vec<ListItem> = bookmarks
+        .iter()
+        .map(|bookmark| {
+            let style = if bookmark.path.is_dir() { Style::default() } else { Style::default().fg(Color::DarkGray) };
+            let key = bookmark.key.map(String::from).unwrap_or_else(|| " ".to_string());
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {}  ", key), Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{:<width$}  ", bookmark.name, width = name_width), Style::default().fg(Color::Cyan)),
+                Span::styled(display_path(&bookmark.path), style),
+            ]))
+        })
+        .collect();
+
+    let title = if moving {
+        let count = model.delete_targets().len();
+        format!("Move {} {} to - press a bookmark's key or Enter, ESC: back", count, if count == 1 { "entry" } else { "entries" })
+    } else {
+        "Bookmarks - key/Enter: jump, v: move the selection there, Del: forget, ESC: close".to_string()
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if moving { Color::LightMagenta } else { Color::LightBlue }));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+    let mut state = ListState::default().with_selected(Some(selected));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut state);
+}
@@ -0,0 +1,56 @@
+use std::path::Path;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+use crate::{core::permission_string, model::AppModel, ui::{dialog::{render_dialog, Dialog}, theme::plain}};
+
+const CHOICES: &[(&str, &str)] = &[
+    ("h/j/k/l", "move"),
+    ("space", "toggle"),
+    ("0-7", "octal"),
+    ("Enter", "apply"),
+    ("ESC", "cancel"),
+];
+
+const CLASSES: [&str; 3] = ["Owner", "Group", "Other"];
+
+/// Renders the permission editor: a grid of the r/w/x bits of each class with the cursor on one of them
+pub fn render_chmod(frame: &mut Frame, area: Rect, model: &AppModel, path: &Path, mode: u32, cursor: usize, octal: &str) {
+    let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let label = |name: &str| Span::styled(format!("{:<10}", name), Style::default().fg(Color::Gray));
+
+    let mut lines = vec![
+        Line::from(format!("Permissions of {}", plain(model, &name))),
+        Line::from(""),
+        Line::from(vec![label(""), Span::raw(" r  w  x")]),
+    ];
+    for (class, name) in CLASSES.iter().enumerate() {
+        let mut spans = vec![label(name)];
+        for column in 0..3 {
+            let index = class * 3 + column;
+            let bit = 0o400 >> index;
+            let text = if mode & bit != 0 { "[x]" } else { "[ ]" };
+            let style = if index == cursor {
+                Style::default().fg(Color::Black).bg(Color::LightCyan).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            spans.push(Span::styled(text, style));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    let mut mode_line = vec![label("Mode"), Span::raw(format!("{:04o} {}", mode, permission_string(mode)))];
+    if !octal.is_empty() {
+        mode_line.push(Span::styled(format!("  typed {}", octal), Style::default().fg(Color::Gray)));
+    }
+    lines.push(Line::from(mode_line));
+    if let Some(current) = model.permissions_of(path).filter(|current| *current != mode) {
+        lines.push(Line::from(vec![label("Was"), Span::raw(format!("{:04o} {}", current, permission_string(current)))]));
+    }
+
+    render_dialog(frame, area, model, Dialog { title: "Permissions", lines, choices: CHOICES, color: Color::LightCyan });
+}
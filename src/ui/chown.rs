@@ -0,0 +1,48 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use crate::{model::AppModel, services::Account, ui::{popup::centered_rect, theme::highlight_style}};
+
+/// Renders the owner picker: the users, or once one is picked the groups, matching what was typed
+pub fn render_chown(frame: &mut Frame, area: Rect, model: &AppModel, candidates: &[&Account], selected: usize, query: &str, user: Option<&Account>) {
+    let count = model.delete_targets().len();
+    let what = if count == 1 { "1 entry".to_string() } else { format!("{} entries", count) };
+
+    let popup_area = centered_rect(50, 60, area);
+    let [input_area, list_area] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .areas(popup_area);
+
+    let title = match user {
+        Some(user) => format!("Give {} to {}: pick a group - Enter: apply recursively, ESC: back", what, user.name),
+        None => format!("Give {} to a user - type to filter, Enter: pick, ESC: close", what),
+    };
+    let input = Paragraph::new(query).block(
+        Block::default().title(title).borders(Borders::ALL).border_style(Style::default().fg(Color::LightYellow)),
+    );
+
+    let items: Vec<ListItem> = candidates
+        .iter()
+        .map(|account| {
+            ListItem::new(Line::from(vec![
+                Span::raw(account.name.clone()),
+                Span::styled(format!("  {}", account.id), Style::default().fg(Color::Gray)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::LightYellow)))
+        .highlight_style(highlight_style(model))
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(input, input_area);
+    frame.render_stateful_widget(list, list_area, &mut ListState::default().with_selected(Some(selected)));
+    let column = Line::from(query).width() as u16;
+    frame.set_cursor_position((input_area.x + 1 + column.min(input_area.width.saturating_sub(3)), input_area.y + 1));
+}
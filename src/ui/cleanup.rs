@@ -0,0 +1,51 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use crate::{core::display_path, model::AppModel, services::file_service::Leftover, ui::{popup::centered_rect, theme::highlight_style}};
+
+/// Renders the leftovers of a maintenance scan with their checkboxes, paths relative to the current directory
+pub fn render_cleanup(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+    leftovers: &[Leftover],
+    checked: &[bool],
+    selected: usize,
+) {
+    let popup_area = centered_rect(80, 70, area);
+
+    let items: Vec<ListItem> = leftovers
+        .iter()
+        .zip(checked)
+        .map(|(leftover, checked)| {
+            let path = leftover.path.strip_prefix(&model.current_dir).unwrap_or(&leftover.path);
+            let text = format!("[{}] {:<11}  {}", if *checked { 'x' } else { ' ' }, leftover.kind.label(), display_path(path));
+            if *checked {
+                ListItem::new(text)
+            } else {
+                ListItem::new(text).style(Style::default().fg(Color::DarkGray))
+            }
+        })
+        .collect();
+
+    let count = checked.iter().filter(|checked| **checked).count();
+    let block = Block::default()
+        .title(format!("Cleanup: {} of {} checked - space: check, a: all, d: trash, D: delete, Enter: jump, ESC: close", count, leftovers.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(highlight_style(model))
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(
+        list,
+        popup_area,
+        &mut ListState::default().with_selected(Some(selected)),
+    );
+}
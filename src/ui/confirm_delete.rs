@@ -0,0 +1,86 @@
+use std::path::{Path, PathBuf};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+use crate::{core::{confirmation_word, display_path}, model::{AppModel, DeleteMode}, services::FileService, ui::{popup::centered_rect, theme::plain}};
+
+/// Renders the entries about to be deleted, directories flagged as going with everything in them,
+/// above a summary of the whole selection
+/// Under a protected directory, `protection` is that directory and what has been typed of its name so far
+pub fn render_confirm_delete(frame: &mut Frame, area: Rect, model: &AppModel, paths: &[PathBuf], mode: DeleteMode, protection: Option<(&Path, &str)>) {
+    let popup_area = centered_rect(70, 50, area);
+    let [list_area, input_area] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(if protection.is_some() { 3 } else { 0 })])
+        .areas(popup_area);
+
+    let items: Vec<ListItem> = paths
+        .iter()
+        .map(|path| {
+            let mut spans = vec![Span::raw(plain(model, &display_path(path)).into_owned())];
+            if mode != DeleteMode::Prune && path.symlink_metadata().is_ok_and(|metadata| metadata.is_dir()) {
+                spans.push(Span::styled("  (directory and everything in it)", Style::default().fg(Color::Gray)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let what = match paths {
+        [_] => "1 entry".to_string(),
+        _ => format!("{} entries", paths.len()),
+    };
+    let (question, keys) = match mode {
+        DeleteMode::Normal if model.config.general.trash => (format!("Move {} to the trash?", what), "y: trash, n/ESC: cancel"),
+        DeleteMode::Shred => (format!("Overwrite {} and delete for good? Unrecoverable.", what), "y: shred, n/ESC: cancel"),
+        DeleteMode::Prune => (format!("Remove {} empty {}?", paths.len(), if paths.len() == 1 { "directory" } else { "directories" }), "y: remove, n/ESC: cancel"),
+        _ => (format!("Delete {} for good?", what), "y: delete, n/ESC: cancel"),
+    };
+    let keys = if protection.is_some() { "protected" } else { keys };
+    let title = format!("{} {}", question, keys);
+    let block = Block::default()
+        .title(title)
+        .title_bottom(summary(paths))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightRed));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(List::new(items).block(block), list_area);
+
+    if let Some((root, typed)) = protection {
+        let input = Paragraph::new(typed).block(
+            Block::default()
+                .title(format!("{} is protected - type '{}' and Enter to proceed, ESC: cancel", plain(model, &display_path(root)), confirmation_word(root)))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::LightRed)),
+        );
+        frame.render_widget(input, input_area);
+        let column = Line::from(typed).width() as u16;
+        frame.set_cursor_position((input_area.x + 1 + column.min(input_area.width.saturating_sub(3)), input_area.y + 1));
+    }
+}
+
+/// ` 12 files (3.4 MB), 2 directories ` without reading the directories' contents
+fn summary(paths: &[PathBuf]) -> String {
+    let (mut files, mut directories, mut size) = (0, 0, 0);
+    for metadata in paths.iter().filter_map(|path| path.symlink_metadata().ok()) {
+        if metadata.is_dir() {
+            directories += 1;
+        } else {
+            files += 1;
+            size += metadata.len();
+        }
+    }
+    let count = |count: usize, one: &str, many: &str| format!("{} {}", count, if count == 1 { one } else { many });
+    let mut parts = Vec::new();
+    if files > 0 {
+        parts.push(format!("{} ({})", count(files, "file", "files"), FileService::format_file_size(size)));
+    }
+    if directories > 0 {
+        parts.push(count(directories, "directory", "directories"));
+    }
+    format!(" {} ", parts.join(", "))
+}
@@ -0,0 +1,31 @@
+use std::path::Path;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use crate::{core::{confirmation_word, display_path}, model::AppModel, ui::{popup::centered_rect, theme::plain}};
+
+/// Renders the prompt for the name of a protected directory, with what has been typed of it so far
+pub fn render_confirm_protected(frame: &mut Frame, area: Rect, model: &AppModel, root: &Path, what: &str, typed: &str) {
+    let width = centered_rect(70, 100, area).width;
+    let input_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + area.height.saturating_sub(3) / 2,
+        width,
+        height: 3.min(area.height),
+    };
+
+    let input = Paragraph::new(typed).block(
+        Block::default()
+            .title(format!("{} is protected - type '{}' and Enter to {} it, ESC: cancel", plain(model, &display_path(root)), confirmation_word(root), what))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::LightRed)),
+    );
+    frame.render_widget(Clear, input_area);
+    frame.render_widget(input, input_area);
+    let column = Line::from(typed).width() as u16;
+    frame.set_cursor_position((input_area.x + 1 + column.min(input_area.width.saturating_sub(3)), input_area.y + 1));
+}
@@ -0,0 +1,31 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use crate::{model::AppModel, ui::{popup::centered_rect, theme::plain}};
+
+/// Renders the problems found at startup or on a config reload, each with the fallback in effect, as an overlay
+pub fn render_diagnostics(frame: &mut Frame, area: Rect, model: &AppModel) {
+    let popup_area = centered_rect(70, 50, area);
+    let block = Block::default()
+        .title("Problems - Enter/ESC: dismiss")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let mut lines = Vec::new();
+    for diagnostic in &model.diagnostics {
+        // Parse errors come with a multi-line excerpt of the file
+        for (index, text) in diagnostic.problem.lines().enumerate() {
+            let text = if index == 0 { plain(model, &format!("⚠ {}", text)).into_owned() } else { format!("  {}", text) };
+            lines.push(Line::from(Span::styled(text, Style::default().fg(Color::Yellow))));
+        }
+        lines.push(Line::from(Span::styled(format!("  -> {}", diagnostic.fallback), Style::default().fg(Color::Gray))));
+        lines.push(Line::from(""));
+    }
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), popup_area);
+}
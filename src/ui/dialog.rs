@@ -0,0 +1,37 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use crate::{model::AppModel, ui::{popup::centered_rect, theme::plain}};
+
+/// A modal question: what it is about, then the keys that answer it
+pub struct Dialog<'a> {
+    pub title: &'a str,
+    pub lines: Vec<Line<'a>>,
+    pub choices: &'a [(&'a str, &'a str)],  // Key and what it does, shown in this order
+    pub color: Color,
+}
+
+/// Renders `dialog` centered in `area`, its choices on the last line
+pub fn render_dialog(frame: &mut Frame, area: Rect, model: &AppModel, dialog: Dialog) {
+    let popup_area = centered_rect(60, 40, area);
+    let block = Block::default()
+        .title(plain(model, dialog.title).into_owned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(dialog.color));
+
+    let mut choices = Vec::new();
+    for (key, label) in dialog.choices {
+        choices.push(Span::styled(format!("[{}]", key), Style::default().fg(dialog.color).add_modifier(Modifier::BOLD)));
+        choices.push(Span::raw(format!(" {}  ", label)));
+    }
+    let mut lines = dialog.lines;
+    lines.push(Line::from(""));
+    lines.push(Line::from(choices));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), popup_area);
+}
@@ -0,0 +1,44 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use crate::{model::AppModel, services::{FileService, ImageListing}, ui::{popup::centered_rect, theme::highlight_style}};
+
+/// Renders a directory of an ISO image, its subdirectories first as in the image
+pub fn render_disk_image(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+    listing: &ImageListing,
+    selected: usize,
+) {
+    let popup_area = centered_rect(80, 60, area);
+
+    let items: Vec<ListItem> = listing.entries
+        .iter()
+        .map(|entry| match entry.is_directory {
+            true => ListItem::new(format!("📁 {}/", entry.name)),
+            false => ListItem::new(format!("📄 {} ({})", entry.name, FileService::format_file_size(entry.size))),
+        })
+        .collect();
+
+    let name = listing.image.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let block = Block::default()
+        .title(format!("{}:{} - Enter: open or extract here, h: up, ESC: close", name, listing.location()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(highlight_style(model))
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(
+        list,
+        popup_area,
+        &mut ListState::default().with_selected(Some(selected)),
+    );
+}
@@ -0,0 +1,53 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use crate::{core::display_path, model::AppModel, services::history_service::format_age, ui::{popup::centered_rect, theme::{entry_label, highlight_style}}};
+
+/// Renders the downloads overlay, newest first
+/// Arrivals since the panel was last open get a badge; entries still coming in are greyed out
+pub fn render_downloads(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+    selected: usize,
+) {
+    let popup_area = centered_rect(80, 70, area);
+
+    let items: Vec<ListItem> = model
+        .downloads()
+        .iter()
+        .map(|download| {
+            let name = download.path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+            let label = entry_label(model, download.is_directory, &name);
+            let mut spans = vec![Span::raw(format!("{:>9}  {}", format_age(download.modified_secs()), label))];
+            if download.in_progress {
+                spans.push(Span::styled("  downloading…", Style::default().fg(Color::DarkGray)));
+            } else if model.is_new_download(download) {
+                spans.push(Span::styled("  ● new", Style::default().fg(Color::LightGreen)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let dir = model.downloads_dir().map(display_path).unwrap_or_default();
+    let block = Block::default()
+        .title(format!("Downloads ({}) - Enter: jump, o: open, r: rename, m: move to…, ESC: close", dir))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(highlight_style(model))
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(
+        list,
+        popup_area,
+        &mut ListState::default().with_selected(Some(selected)),
+    );
+}
@@ -1,24 +1,22 @@
 use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use crate::model::AppModel;
+use crate::{core::permission_string, handlers::Handler, model::{AppModel, LinkTarget, PreviewLink}, services::{file_service::DirectoryStats, history_service::{format_age, format_modified}, FileService}, ui::{theme::{accessible, file_label, plain}, thumbnail::{render_thumbnail, thumbnail_bounds}}};
 
 /// Renders the file description component on the right side
 pub fn render_file_description(
     frame: &mut Frame,
     area: Rect,
     model: &AppModel,
+    handler: &Handler,
 ) {
     // Generate title and content directly from model
-    let (title, content) = if let Some(selected_file) = model.get_selected_file() {
-        let title = if selected_file.is_directory {
-            format!("📁 {}", selected_file.name)
-        } else {
-            format!("📄 {}", selected_file.name)
-        };
+    let (mut title, content) = if let Some(selected_file) = model.get_selected_file() {
+        let title = file_label(model, selected_file, &selected_file.name);
         let content = model.get_file_content(selected_file);
         (title, content)
     } else {
@@ -28,14 +26,131 @@ pub fn render_file_description(
         )
     };
 
-    let block = Block::default()
+    // Row and the column just past the selected link, so it can be scrolled into view once the layout is known
+    let mut link_at = None;
+    let mut text = match handler {
+        Handler::PreviewLinks(links_handler) => {
+            if let Some(link) = links_handler.selected_link() {
+                let target = match &link.target {
+                    LinkTarget::Url(url) => url.clone(),
+                    LinkTarget::Path(path) => path.display().to_string(),
+                };
+                title = format!("{} - link {}/{}: {}", title, links_handler.selected + 1, links_handler.links.len(), target);
+                let end = content.lines().nth(link.line).and_then(|line| line.get(..link.end)).map_or(0, |before| Span::raw(before).width());
+                link_at = Some((link.line, end));
+            }
+            highlight_links(&content, &links_handler.links, links_handler.selected)
+        }
+        // Links are located by byte offset, so only previews without them get their emoji spelled out
+        _ => Text::from(plain(model, &content).into_owned()),
+    };
+    if let Some(stats) = model.get_selected_file().and_then(|file| model.directory_stats(file)) {
+        let lines = directory_stats_lines(&stats);
+        link_at = link_at.map(|(row, end)| (row + lines.len(), end));
+        text.lines.splice(0..0, lines);
+    }
+
+    let mut block = Block::default()
         .title(title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::White));
+    if let Some(mode) = model.get_selected_file().and_then(|file| file.mode) {
+        block = block.title_bottom(Line::from(format!(" {} {:04o} ", permission_string(mode), mode)));
+    }
+    if let Some(modified) = model.get_selected_file().and_then(|file| file.modified) {
+        let date = chrono::DateTime::<chrono::Local>::from(modified).format("%Y-%m-%d %H:%M");
+        let summary = format!(" Modified {} ({}) ", format_modified(modified), date);
+        block = block.title_bottom(Line::from(summary).alignment(Alignment::Center));
+    }
+    // Tells apart which of several similar files was touched last
+    if let Some(open) = model.get_selected_file().and_then(|file| model.open_history(file)) {
+        let times = if open.count == 1 { "once".to_string() } else { format!("{} times", open.count) };
+        let summary = format!(" Opened {}, {} ", format_age(open.opened_at), times);
+        block = block.title_bottom(Line::from(summary).alignment(Alignment::Right));
+    }
+    let mut text_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Images and video frames get the top half of the panel
+    let selected = model.get_selected_file().filter(|_| !accessible(model));
+    let thumbnail = selected.and_then(|file| {
+        let (width, height) = thumbnail_bounds(model, text_area.width, text_area.height / 2);
+        model.preview_thumbnail(file, width, height)
+    });
+    if let Some(thumbnail) = thumbnail {
+        let rows = render_thumbnail(frame, text_area, thumbnail, model);
+        text_area.y += rows + 1;
+        text_area.height = text_area.height.saturating_sub(rows + 1);
+    } else if selected.is_some_and(|file| model.thumbnail_pending(file)) && text_area.height > 2 {
+        frame.render_widget(Paragraph::new("⏳ Decoding image..."), Rect { height: 1, ..text_area });
+        text_area.y += 2;
+        text_area.height -= 2;
+    }
+
+    // Wrapping would move the link off the row it's scrolled to, so link mode scrolls sideways instead
+    let paragraph = match link_at {
+        Some((row, end)) => {
+            // Keep the highlighted link roughly centered vertically and its end on screen
+            let y = row.saturating_sub(usize::from(text_area.height / 2));
+            let x = end.saturating_sub(usize::from(text_area.width));
+            Paragraph::new(text).scroll((u16::try_from(y).unwrap_or(u16::MAX), u16::try_from(x).unwrap_or(u16::MAX)))
+        }
+        None => Paragraph::new(text).wrap(ratatui::widgets::Wrap { trim: true }),
+    };
+
+    frame.render_widget(paragraph, text_area);
+}
+
+/// Item counts, size, newest child and the most common extensions, above the directory listing
+fn directory_stats_lines(stats: &DirectoryStats) -> Vec<Line<'static>> {
+    const MAX_EXTENSIONS: usize = 6;
+    let label = |name: &str| Span::styled(format!("{:<10}", name), Style::default().fg(Color::Gray));
+    let mut lines = vec![Line::from(vec![
+        label("Items"),
+        Span::raw(format!("{} files, {} directories, {}", stats.files, stats.directories, FileService::format_file_size(stats.total_size))),
+    ])];
+    if let Some((name, modified)) = &stats.newest {
+        lines.push(Line::from(vec![label("Newest"), Span::raw(format!("{} ({})", name, format_modified(*modified)))]));
+    }
+    if !stats.extensions.is_empty() {
+        let mut kinds: Vec<String> = stats.extensions
+            .iter()
+            .take(MAX_EXTENSIONS)
+            .map(|(extension, count)| format!("{} {}", if extension.is_empty() { "(none)" } else { extension }, count))
+            .collect();
+        if stats.extensions.len() > MAX_EXTENSIONS {
+            kinds.push(format!("+{} more", stats.extensions.len() - MAX_EXTENSIONS));
+        }
+        lines.push(Line::from(vec![label("Types"), Span::raw(kinds.join(", "))]));
+    }
+    lines.push(Line::default());
+    lines
+}
+
+/// Underline every detected link and highlight the selected one
+fn highlight_links<'a>(content: &'a str, links: &[PreviewLink], selected: usize) -> Text<'a> {
+    let link_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::UNDERLINED);
+    let selected_style = Style::default().fg(Color::Black).bg(Color::Cyan);
 
-    let paragraph = Paragraph::new(content)
-        .block(block)
-        .wrap(ratatui::widgets::Wrap { trim: true });
+    let lines = content
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| {
+            let mut spans = Vec::new();
+            let mut cursor = 0;
+            for (link_idx, link) in links.iter().enumerate().filter(|(_, link)| link.line == line_idx) {
+                if link.start < cursor || link.end > line.len() {
+                    continue;
+                }
+                spans.push(Span::raw(&line[cursor..link.start]));
+                let style = if link_idx == selected { selected_style } else { link_style };
+                spans.push(Span::styled(&line[link.start..link.end], style));
+                cursor = link.end;
+            }
+            spans.push(Span::raw(&line[cursor..]));
+            Line::from(spans)
+        })
+        .collect::<Vec<_>>();
 
-    frame.render_widget(paragraph, area);
-}
\ No newline at end of file
+    Text::from(lines)
+}
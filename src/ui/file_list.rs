@@ -1,10 +1,42 @@
 use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
-    widgets::{Block, Borders, List, ListItem},
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, HighlightSpacing, List, ListItem, Paragraph, Row, Table, TableState},
     Frame,
 };
-use crate::{handlers::Handler, model::{AppModel, AppMode}};
+use crate::{core::{display_path, risky_permissions}, handlers::Handler, model::{AppModel, AppMode, ChangeKind, EntryChange, FileEntry, FilesSource, GroupBy, ReadMetrics, SortKey}, services::{history_service::format_modified, FileService}, ui::{progress::spinner, theme::{accent, entry_label, file_label, highlight_style, plain}}};
+
+/// Rows of the pinned path/badge header at the top of the list, which never scrolls away
+const STICKY_HEADER_HEIGHT: u16 = 1;
+
+/// Width of the `> ` selection marker in front of every row
+const HIGHLIGHT_WIDTH: u16 = 2;
+
+/// Marks entries whose metadata could not be read
+const UNREADABLE_BADGE: &str = "⚠ unreadable";
+
+/// Columns of the detail view, in `SortKey::ALL` order
+const COLUMN_WIDTHS: [Constraint; 4] = [
+    Constraint::Fill(1),     // Name
+    Constraint::Length(9),   // Size
+    Constraint::Length(16),  // Modified
+    Constraint::Length(6),   // Type
+];
+
+/// ` (12 ms, 2 unreadable)` after the title, so slow reads and silently skipped entries are visible
+fn read_summary(metrics: Option<ReadMetrics>) -> String {
+    let Some(metrics) = metrics else { return String::new() };
+    let elapsed = if metrics.elapsed.as_secs() >= 1 {
+        format!("{:.1} s", metrics.elapsed.as_secs_f64())
+    } else {
+        format!("{} ms", metrics.elapsed.as_millis())
+    };
+    match metrics.unreadable {
+        0 => format!(" ({})", elapsed),
+        unreadable => format!(" ({}, {} unreadable)", elapsed, unreadable),
+    }
+}
 
 /// Renders the file list component on the left side  
 pub fn render_file_list(
@@ -13,32 +45,48 @@ pub fn render_file_list(
     model: &AppModel,
     handler: &Handler,
 ) {
-    // Generate title directly from model
+    // Generate title directly from model; the path itself lives in the sticky header below it
     let title = match model.mode {
         AppMode::Explore => {
             if model.query_text.is_empty() {
-                format!("Files - {}", model.current_dir.display())
+                format!("Files{}", read_summary(model.listing_read))
             } else {
-                format!("Search - {}", model.current_dir.display())
+                "Search".to_string()
             }
         }
-        AppMode::Search => {
-            format!("Search - {}", model.current_dir.display())
-        }
+        AppMode::Search => "Search".to_string(),
         AppMode::FuzzyFind => {
             if model.is_indexing {
-                format!("🔍 Fuzzy Find - Indexing... ({} files)", model.all_files_cache.len())
+                format!("🔍 Fuzzy Find - {} Indexing, {} files so far", spinner(model), model.all_files_cache.len())
             } else {
-                format!("🔍 Fuzzy Find - {} total files", model.all_files_cache.len())
+                format!("🔍 Fuzzy Find - {} total files{}", model.all_files_cache.len(), read_summary(model.tree_scan))
             }
         }
     };
 
-    let items: Vec<ListItem> = model
-        .files
+    let block = Block::default()
+        .title(plain(model, &title).into_owned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent(model, handler)));
+    let [header_area, list_area] = Layout::vertical([Constraint::Length(STICKY_HEADER_HEIGHT), Constraint::Fill(1)])
+        .areas(block.inner(area));
+    frame.render_widget(block, area);
+    render_sticky_header(frame, header_area, model);
+
+    if model.detail_view && model.mode != AppMode::FuzzyFind {
+        render_detail_table(frame, list_area, model);
+        return;
+    }
+
+    let rows = display_rows(model);
+    let mut items: Vec<ListItem> = rows
         .iter()
-        .map(|file| {
-            let icon = if file.is_directory { "📁" } else { "📄" };
+        .map(|row| {
+            let file = match row {
+                DisplayRow::Header(label, count) => return ListItem::new(header_text(model, label, *count)).style(header_style()),
+                DisplayRow::File(index) => &model.files[*index],
+            };
+            let marked = model.is_marked(file) || in_visual_range(model, row);
 
             // Show relative path for fuzzy find, just name for others
             let display_name = match handler {
@@ -51,35 +99,257 @@ pub fn render_file_list(
                 _ => file.name.clone(),
             };
 
-            ListItem::new(format!("{} {}", icon, display_name))
+            let mut label = file_label(model, file, &display_name);
+            let change = model.entry_changes.get(&file.path);
+            if let Some(change) = change {
+                label = format!("{}  {}", label, change.kind.badge());
+            }
+            if file.metadata_error.is_some() {
+                let label = format!("{}{} {}", if marked { "* " } else { "  " }, label, UNREADABLE_BADGE);
+                ListItem::new(plain(model, &label).into_owned()).style(unreadable_style())
+            } else if in_visual_range(model, row) {
+                ListItem::new(format!("* {}", label)).style(visual_style())
+            } else if marked {
+                ListItem::new(format!("* {}", label))
+                    .style(Style::default().fg(Color::LightMagenta))
+            } else if let Some(change) = change {
+                ListItem::new(format!("  {}", label)).style(change_style(change))
+            } else {
+                ListItem::new(format!("  {}", label))
+            }
         })
         .collect();
+    if shows_deleted(model) {
+        items.extend(model.deleted_entries().into_iter().map(|(path, change)| {
+            let label = format!("  {}  {}", deleted_label(model, path, change), change.kind.badge());
+            ListItem::new(label).style(change_style(change))
+        }));
+    }
 
-    let block = Block::default()
-        .title(title)
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::White));
-
-    let mut list = List::new(items).block(block).highlight_style(
-        Style::default()
-            .bg(Color::DarkGray)
-            .fg(Color::Yellow),
-    );
+    let mut list = List::new(items).highlight_style(highlight_style(model));
 
     // Always show highlight symbol if we have files
     if !model.files.is_empty() {
         list = list.highlight_symbol("> ");
     }
 
-    let selected_index = if !model.files.is_empty() {
-        Some(model.selected_index)
-    } else {
-        None
-    };
-
     frame.render_stateful_widget(
         list,
-        area,
-        &mut ratatui::widgets::ListState::default().with_selected(selected_index),
+        list_area,
+        &mut ratatui::widgets::ListState::default().with_selected(selected_row(&rows, model)),
     );
-}
\ No newline at end of file
+}
+
+/// Pinned line with the full current path and badges for whatever shapes the listing
+/// A path too long for the line is scrolled to its end, since the deepest directories matter most
+fn render_sticky_header(frame: &mut Frame, area: Rect, model: &AppModel) {
+    let badge_style = Style::default().fg(Color::Black).bg(Color::Cyan);
+    let mut badges: Vec<String> = Vec::new();
+    if !model.query_text.is_empty() {
+        badges.push(format!("filter: {}", model.query_text));
+    }
+    if model.mode != AppMode::FuzzyFind {
+        // Fuzzy results are ranked by score, so sorting and grouping only apply to listings
+        if model.sort != Default::default() {
+            badges.push(format!("{} {}", model.sort.key.label(), model.sort.arrow()));
+        }
+        if model.group_by != GroupBy::None {
+            badges.push(format!("by {}", model.group_by.label()));
+        }
+        if model.project_view {
+            badges.push(format!("project: {} hidden", model.ignored_paths.len()));
+        }
+    }
+
+    let badges_width: usize = badges.iter().map(|badge| badge.chars().count() + 3).sum();
+    let path = display_path(&model.current_dir);
+    let room = (area.width as usize).saturating_sub(badges_width).max(1);
+    let length = path.chars().count();
+    let path = if length > room {
+        format!("…{}", path.chars().skip(length + 1 - room).collect::<String>())
+    } else {
+        path
+    };
+
+    let mut spans = vec![Span::styled(path, Style::default().fg(Color::White).add_modifier(Modifier::BOLD))];
+    for badge in badges {
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(format!(" {} ", plain(model, &badge)), badge_style));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// A line of the listing: a group separator, or an entry of `model.files` by index
+enum DisplayRow {
+    Header(String, usize),  // Group label and how many entries follow
+    File(usize),
+}
+
+/// Entries interleaved with a header before every group when the listing is grouped
+/// Headers are display-only, so selection keeps indexing `model.files` and skips over them
+fn display_rows(model: &AppModel) -> Vec<DisplayRow> {
+    let labels: Vec<Option<String>> = model.files.iter().map(|file| model.group_label(file)).collect();
+    let mut rows = Vec::with_capacity(model.files.len());
+    for (index, label) in labels.iter().enumerate() {
+        if let Some(label) = label.as_ref().filter(|_| index == 0 || labels[index - 1] != *label) {
+            let count = labels[index..].iter().take_while(|other| *other == &labels[index]).count();
+            rows.push(DisplayRow::Header(label.clone(), count));
+        }
+        rows.push(DisplayRow::File(index));
+    }
+    rows
+}
+
+/// Row of the selected entry among the display rows
+fn selected_row(rows: &[DisplayRow], model: &AppModel) -> Option<usize> {
+    rows.iter().position(|row| matches!(row, DisplayRow::File(index) if *index == model.selected_index))
+}
+
+fn header_text(model: &AppModel, label: &str, count: usize) -> String {
+    plain(model, &format!("── {} ({}) ──", label, count)).into_owned()
+}
+
+fn header_style() -> Style {
+    Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+}
+
+fn unreadable_style() -> Style {
+    Style::default().fg(Color::LightRed)
+}
+
+/// Fresh changes stand out in their own color, then dim before the badge goes
+fn change_style(change: &EntryChange) -> Style {
+    if change.fading() {
+        return Style::default().fg(Color::DarkGray);
+    }
+    match change.kind {
+        ChangeKind::New => Style::default().fg(Color::LightGreen),
+        ChangeKind::Modified => Style::default().fg(Color::LightYellow),
+        ChangeKind::Deleted => Style::default().fg(Color::Red).add_modifier(Modifier::CROSSED_OUT),
+    }
+}
+
+/// Deleted entries trail the plain directory listing; searches and fuzzy results never had them
+fn shows_deleted(model: &AppModel) -> bool {
+    model.mode == AppMode::Explore && model.files_source == FilesSource::CurrentDir
+}
+
+fn deleted_label(model: &AppModel, path: &std::path::Path, change: &EntryChange) -> String {
+    let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    entry_label(model, change.is_directory, &name)
+}
+
+/// Columns view: name, size, modification time and type, with the sorted column marked in the header
+fn render_detail_table(frame: &mut Frame, area: Rect, model: &AppModel) {
+    let header = Row::new(SortKey::ALL.iter().map(|&key| {
+        if model.sort.key == key {
+            Cell::from(plain(model, &format!("{} {}", key.label(), model.sort.arrow())).into_owned()).style(Style::default().fg(Color::Yellow))
+        } else {
+            Cell::from(key.label())
+        }
+    }))
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let display = display_rows(model);
+    let mut rows: Vec<Row> = display.iter().map(|row| {
+        let file = match row {
+            DisplayRow::Header(label, count) => return Row::new([header_text(model, label, *count)]).style(header_style()),
+            DisplayRow::File(index) => &model.files[*index],
+        };
+        let change = model.entry_changes.get(&file.path);
+        let visual = in_visual_range(model, row);
+        let mut cells = detail_cells(model, file, model.is_marked(file) || visual);
+        if let Some(change) = change {
+            cells[0] = format!("{}  {}", cells[0], change.kind.badge());
+        }
+        let risks = permission_risks(model, file);
+        if !risks.is_empty() {
+            cells[0] = plain(model, &format!("{}  ⚠ {}", cells[0], risks.join(" "))).into_owned();
+        }
+        let row = Row::new(cells);
+        if file.metadata_error.is_some() {
+            row.style(unreadable_style())
+        } else if visual {
+            row.style(visual_style())
+        } else if model.is_marked(file) {
+            row.style(Style::default().fg(Color::LightMagenta))
+        } else if let Some(change) = change {
+            row.style(change_style(change))
+        } else if !risks.is_empty() {
+            row.style(Style::default().fg(Color::LightRed))
+        } else {
+            row
+        }
+    }).collect();
+    if shows_deleted(model) {
+        rows.extend(model.deleted_entries().into_iter().map(|(path, change)| {
+            let name = format!("{}  {}", deleted_label(model, path, change), change.kind.badge());
+            Row::new([name]).style(change_style(change))
+        }));
+    }
+
+    let table = Table::new(rows, COLUMN_WIDTHS)
+        .header(header)
+        .flex(Flex::Start)
+        .column_spacing(1)
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_symbol("> ")
+        .row_highlight_style(highlight_style(model));
+
+    let selected = selected_row(&display, model);
+    frame.render_stateful_widget(table, area, &mut TableState::default().with_selected(selected));
+}
+
+/// Setuid, setgid and world-writable flags of an entry, unless turned off; symlinks have no bits of their own
+fn permission_risks(model: &AppModel, file: &FileEntry) -> Vec<&'static str> {
+    match file.mode {
+        Some(mode) if model.config.explore.permission_badges && file.link_target.is_none() => risky_permissions(mode, file.is_directory),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether a row is an entry in the range of visual mode, which becomes marked when it ends
+fn in_visual_range(model: &AppModel, row: &DisplayRow) -> bool {
+    match row {
+        DisplayRow::File(index) => model.visual_range().is_some_and(|range| range.contains(index)),
+        DisplayRow::Header(..) => false,
+    }
+}
+
+/// Entries about to be marked, set apart from marked ones by their background
+fn visual_style() -> Style {
+    Style::default().fg(Color::Black).bg(Color::LightMagenta)
+}
+
+fn detail_cells(model: &AppModel, file: &FileEntry, marked: bool) -> [String; 4] {
+    let mut name = format!("{}{}", if marked { "* " } else { "" }, file_label(model, file, &file.name));
+    if file.metadata_error.is_some() {
+        // Unknown rather than empty, which would read as a directory or a skipped stat
+        name = plain(model, &format!("{} {}", name, UNREADABLE_BADGE)).into_owned();
+        let placeholder = "?".to_string();
+        return [name, placeholder.clone(), placeholder, String::new()];
+    }
+    let size = file.size.map(FileService::format_file_size).unwrap_or_default();
+    let modified = file.modified.map(format_modified).unwrap_or_default();
+    let extension = if file.is_directory {
+        String::new()
+    } else {
+        file.path.extension().map(|ext| ext.to_string_lossy().to_lowercase()).unwrap_or_default()
+    };
+    [name, size, modified, extension]
+}
+
+/// Column whose header is at terminal cell (`column`, `row`) when the detail view fills `area`
+/// Mirrors the table's own layout so mouse clicks land on the header that is drawn there
+pub fn header_column_at(area: Rect, column: u16, row: u16) -> Option<SortKey> {
+    let inner = area.inner(Margin::new(1, 1));
+    let inner = Rect { y: inner.y + STICKY_HEADER_HEIGHT, ..inner };
+    if row != inner.y || column < inner.x + HIGHLIGHT_WIDTH || column >= inner.right() {
+        return None;
+    }
+    let columns_area = Rect { x: inner.x + HIGHLIGHT_WIDTH, width: inner.width - HIGHLIGHT_WIDTH, ..inner };
+    let cells = Layout::horizontal(COLUMN_WIDTHS).flex(Flex::Start).spacing(1).split(columns_area);
+    cells.iter()
+        .position(|cell| column >= cell.x && column < cell.right())
+        .map(|index| SortKey::ALL[index])
+}
@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use crate::{core::display_path, model::AppModel, ui::{popup::centered_rect, theme::highlight_style}};
+
+/// Renders the other hard links to the selected file, which share its contents and inode
+pub fn render_hard_links(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+    links: &[PathBuf],
+    selected: usize,
+) {
+    let popup_area = centered_rect(80, 60, area);
+
+    let items: Vec<ListItem> = links
+        .iter()
+        .map(|link| ListItem::new(display_path(link)))
+        .collect();
+
+    let name = model.get_selected_file().map(|file| file.name.as_str()).unwrap_or_default();
+    let block = Block::default()
+        .title(format!("Other hard links to {} - Enter: jump, ESC: close", name))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(highlight_style(model))
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(
+        list,
+        popup_area,
+        &mut ListState::default().with_selected(Some(selected)),
+    );
+}
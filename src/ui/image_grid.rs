@@ -0,0 +1,86 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Line, Text},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use crate::{core::display_path, model::AppModel, ui::{theme::{accessible, entry_label, plain}, thumbnail::{render_thumbnail, thumbnail_bounds}}};
+
+/// Size of one grid tile in terminal cells, including its border
+const TILE_WIDTH: u16 = 22;
+const TILE_HEIGHT: u16 = 12;
+
+/// Number of tiles per row for a grid of the given width
+pub fn grid_columns(width: u16) -> usize {
+    (width / TILE_WIDTH).max(1) as usize
+}
+
+/// Renders the current listing as a grid of thumbnails
+pub fn render_image_grid(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+) {
+    let outer = Block::default()
+        .title(plain(model, &format!("🖼 Thumbnails - {} (arrows to move, Enter to open, t/ESC to exit)", display_path(&model.current_dir))).into_owned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(model.config.accents.explore));
+    let inner = outer.inner(area);
+    frame.render_widget(outer, area);
+
+    let columns = grid_columns(inner.width);
+    let visible_rows = (inner.height / TILE_HEIGHT).max(1) as usize;
+    // Scroll just enough to keep the selected row visible
+    let selected_row = model.selected_index / columns;
+    let first_row = selected_row.saturating_sub(visible_rows - 1);
+
+    for (index, file) in model.files.iter().enumerate().skip(first_row * columns).take(visible_rows * columns) {
+        let row = (index / columns - first_row) as u16;
+        let col = (index % columns) as u16;
+        let tile = Rect {
+            x: inner.x + col * TILE_WIDTH,
+            y: inner.y + row * TILE_HEIGHT,
+            width: TILE_WIDTH.min(inner.width),
+            height: TILE_HEIGHT.min(inner.height),
+        };
+
+        let selected = index == model.selected_index;
+        let border_color = if selected {
+            Color::Yellow
+        } else if model.is_marked(file) {
+            Color::LightMagenta
+        } else {
+            Color::DarkGray
+        };
+        let block = Block::default()
+            .title_bottom(Line::from(file.name.clone()).alignment(Alignment::Center))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color));
+        let tile_inner = block.inner(tile);
+
+        // Thumbnails are nothing but color, so accessibility mode shows the name alone
+        let (width, height) = thumbnail_bounds(model, tile_inner.width, tile_inner.height);
+        let thumbnail = model.thumbnail(file, width, height).filter(|_| !accessible(model));
+        if let Some(thumbnail) = thumbnail {
+            frame.render_widget(block, tile);
+            render_thumbnail(frame, tile_inner, thumbnail, model);
+            continue;
+        }
+        let content = if accessible(model) {
+            Text::from(vec![Line::from(""), Line::from(entry_label(model, file.is_directory, &file.name))])
+        } else {
+            let icon = match file.is_directory {
+                true => "📁",
+                false if model.thumbnail_pending(file) => "⏳",
+                false => "📄",
+            };
+            Text::from(vec![Line::from(""), Line::from(icon), Line::from(file.name.clone())])
+        };
+
+        frame.render_widget(
+            Paragraph::new(content).alignment(Alignment::Center).block(block),
+            tile,
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use crate::{model::AppModel, ui::{popup::centered_rect, progress::spinner, theme::{highlight_style, plain}}};
+
+/// Renders background jobs with whether each is running or paused, as an overlay
+pub fn render_jobs(frame: &mut Frame, area: Rect, model: &AppModel, selected: usize) {
+    let popup_area = centered_rect(70, 40, area);
+    let jobs = model.jobs();
+
+    let items: Vec<ListItem> = if jobs.is_empty() {
+        vec![ListItem::new(Span::styled("No background jobs", Style::default().fg(Color::Gray)))]
+    } else {
+        jobs.iter()
+            .map(|job| {
+                let (state, color) = if job.paused { ("paused ", Color::Yellow) } else { ("running", Color::Green) };
+                let spinner = if job.paused { "" } else { spinner(model) };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:1} {}  ", spinner, state), Style::default().fg(color)),
+                    Span::raw(plain(model, &job.description).into_owned()),
+                ]))
+            })
+            .collect()
+    };
+
+    let block = Block::default()
+        .title("Jobs - p: pause/resume, c: cancel, ESC: close")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(highlight_style(model))
+        .highlight_symbol("> ");
+
+    let selected = (!jobs.is_empty()).then_some(selected.min(jobs.len() - 1));
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut ListState::default().with_selected(selected));
+}
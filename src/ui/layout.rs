@@ -2,14 +2,17 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
 };
 
-/// Creates the main layout with three areas: file list, description, and search bar
-pub fn create_main_layout(area: Rect) -> (Rect, Rect, Rect) {
-    // Create vertical layout: main area + search bar
+/// Creates the main layout with five areas: file list, description, search bar, transfer gauge, and status bar
+/// The gauge has no height unless `transfer` is set
+pub fn create_main_layout(area: Rect, transfer: bool) -> (Rect, Rect, Rect, Rect, Rect) {
+    // Create vertical layout: main area + search bar + gauge + status bar
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Min(3),    // Main content area
             Constraint::Length(3), // Search bar (fixed height)
+            Constraint::Length(u16::from(transfer)), // Transfer gauge (single line while copying)
+            Constraint::Length(1), // Status bar (single line)
         ])
         .split(area);
 
@@ -22,5 +25,24 @@ pub fn create_main_layout(area: Rect) -> (Rect, Rect, Rect) {
         ])
         .split(main_chunks[0]);
 
-    (content_chunks[0], content_chunks[1], main_chunks[1])
-}
\ No newline at end of file
+    (content_chunks[0], content_chunks[1], main_chunks[1], main_chunks[2], main_chunks[3])
+}
+
+/// Splits the detail area to make room for the marked files panel when anything is marked
+pub fn split_detail_area(area: Rect, marked_count: usize) -> (Rect, Option<Rect>) {
+    if marked_count == 0 {
+        return (area, None);
+    }
+
+    // Borders take two rows; cap the panel so the preview keeps most of the space
+    let panel_height = (marked_count as u16 + 2).min(area.height / 3).max(3);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),               // File description
+            Constraint::Length(panel_height), // Marked files panel
+        ])
+        .split(area);
+
+    (chunks[0], Some(chunks[1]))
+}
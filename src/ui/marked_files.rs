@@ -0,0 +1,74 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use crate::{model::AppModel, ui::{popup::centered_rect, theme::{entry_label, highlight_style}}};
+
+/// Renders the marked files panel below the file description
+/// Marks persist across directories, so paths are shown relative to the current directory when possible
+pub fn render_marked_files(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+) {
+    let items: Vec<ListItem> = model
+        .marked_files_sorted()
+        .into_iter()
+        .map(|path| {
+            let display = path
+                .strip_prefix(&model.current_dir)
+                .unwrap_or(path)
+                .display()
+                .to_string();
+            ListItem::new(format!("* {}", display))
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(format!("Marked files ({})", model.marked_paths.len()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightMagenta));
+
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+/// Renders the interactive marked files review overlay with full paths
+pub fn render_marked_files_review(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+    selected: usize,
+) {
+    let popup_area = centered_rect(80, 70, area);
+
+    let items: Vec<ListItem> = model
+        .marked_files_sorted()
+        .into_iter()
+        .map(|path| {
+            ListItem::new(entry_label(model, path.is_dir(), &path.display().to_string()))
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(format!(
+            "Marked files ({}, {} elsewhere) - Enter: jump, c: copy here, m: move here, d: unmark, D: unmark all, ESC: close",
+            model.marked_paths.len(),
+            model.marks_outside_current_dir()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightMagenta));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(highlight_style(model))
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(
+        list,
+        popup_area,
+        &mut ListState::default().with_selected(Some(selected)),
+    );
+}
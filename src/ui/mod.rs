@@ -1,17 +1,68 @@
+mod about;
+mod batch_report;
+mod bookmarks;
+mod chmod;
+mod chown;
+mod cleanup;
+mod confirm_delete;
+mod confirm_protected;
+mod dialog;
+mod jobs;
+mod diagnostics;
+mod disk_image;
+mod downloads;
 mod file_detail;
 mod file_list;
+mod hard_links;
+mod image_grid;
 mod layout;
+mod marked_files;
+mod old_files;
+mod paste_conflict;
+mod popup;
+mod progress;
+mod properties;
+mod recent_files;
 mod search_bar;
+mod send_to;
+mod status_bar;
+mod theme;
+mod thumbnail;
+mod transfer_gauge;
 
 use ratatui::Frame;
 
 use crate::{handlers::Handler, model::AppModel};
 
 // Re-export individual render functions for direct access if needed
+pub use about::render_about;
+pub use batch_report::render_batch_report;
+pub use bookmarks::render_bookmarks;
+pub use chmod::render_chmod;
+pub use chown::render_chown;
+pub use cleanup::render_cleanup;
+pub use confirm_delete::render_confirm_delete;
+pub use confirm_protected::render_confirm_protected;
+pub use dialog::{render_dialog, Dialog};
+pub use jobs::render_jobs;
+pub use diagnostics::render_diagnostics;
+pub use disk_image::render_disk_image;
+pub use downloads::render_downloads;
 pub use file_detail::render_file_description;
-pub use file_list::render_file_list;
-pub use layout::create_main_layout;
+pub use file_list::{header_column_at, render_file_list};
+pub use hard_links::render_hard_links;
+pub use image_grid::{grid_columns, render_image_grid};
+pub use layout::{create_main_layout, split_detail_area};
+pub use marked_files::{render_marked_files, render_marked_files_review};
+pub use old_files::render_old_files;
+pub use paste_conflict::render_paste_conflict;
+pub use properties::render_properties;
+pub use recent_files::render_recent_files;
 pub use search_bar::render_search_bar;
+pub use send_to::render_send_to;
+pub use status_bar::render_status_bar;
+pub use thumbnail::{kitty_sequence, sixel_sequence, GraphicsLayer};
+pub use transfer_gauge::render_transfer_gauge;
 
 pub struct UI;
 
@@ -19,11 +70,89 @@ impl UI {
     /// Complete UI render function that orchestrates all components
     pub fn render_complete_ui(frame: &mut Frame, model: &AppModel, handler: &Handler) {
         let area = frame.area();
-        let (file_list_area, description_area, search_area) = create_main_layout(area);
+        let transfer = model.transfer();
+        let (file_list_area, description_area, search_area, gauge_area, status_area) = create_main_layout(area, transfer.is_some());
 
         // Render all components directly with model - much simpler!
-        render_file_list(frame, file_list_area, model, handler);
-        render_file_description(frame, description_area, model);
+        if let Handler::Grid(_) = handler {
+            // The grid takes over both panels
+            render_image_grid(frame, file_list_area.union(description_area), model);
+        } else {
+            render_file_list(frame, file_list_area, model, handler);
+            let (description_area, marked_area) = split_detail_area(description_area, model.marked_paths.len());
+            render_file_description(frame, description_area, model, handler);
+            if let Some(marked_area) = marked_area {
+                render_marked_files(frame, marked_area, model);
+            }
+        }
         render_search_bar(frame, search_area, model, handler);
+        if let Some(transfer) = transfer {
+            render_transfer_gauge(frame, gauge_area, model, transfer);
+        }
+        render_status_bar(frame, status_area, model, handler);
+
+        // Overlays are drawn last so they sit on top of everything else
+        if let Handler::Marks(marks_handler) = handler {
+            render_marked_files_review(frame, area, model, marks_handler.selected);
+        }
+        if let Handler::Recents(recents_handler) = handler {
+            render_recent_files(frame, area, model, recents_handler.selected);
+        }
+        if let Handler::Downloads(downloads_handler) = handler {
+            render_downloads(frame, area, model, downloads_handler.selected);
+        }
+        if let Handler::Bookmarks(bookmarks_handler) = handler {
+            render_bookmarks(frame, area, model, bookmarks_handler.moving, bookmarks_handler.selected);
+        }
+        if let Handler::SendTo(send_to_handler) = handler {
+            render_send_to(frame, area, model, &send_to_handler.query, send_to_handler.selected, send_to_handler.destination.as_deref(), send_to_handler.completion.position());
+        }
+        if let Handler::Properties(_) = handler {
+            render_properties(frame, area, model);
+        }
+        if let Handler::About(_) = handler {
+            render_about(frame, area, model);
+        }
+        if let Handler::Diagnostics(_) = handler {
+            render_diagnostics(frame, area, model);
+        }
+        if let Handler::BatchReport(batch_report_handler) = handler {
+            render_batch_report(frame, area, model, batch_report_handler.selected);
+        }
+        if let Handler::Jobs(jobs_handler) = handler {
+            render_jobs(frame, area, model, jobs_handler.selected);
+        }
+        if let Handler::ConfirmDelete(confirm_delete_handler) = handler {
+            let protection = confirm_delete_handler.protected.as_deref().map(|root| (root, confirm_delete_handler.typed.text.as_str()));
+            render_confirm_delete(frame, area, model, &confirm_delete_handler.paths, confirm_delete_handler.mode, protection);
+        }
+        if let Handler::ConfirmProtected(confirm_protected_handler) = handler {
+            render_confirm_protected(frame, area, model, &confirm_protected_handler.root, confirm_protected_handler.what, &confirm_protected_handler.typed.text);
+        }
+        if let Handler::PasteConflict(_) = handler {
+            render_paste_conflict(frame, area, model);
+        }
+        if let Handler::Chmod(chmod_handler) = handler {
+            render_chmod(frame, area, model, &chmod_handler.path, chmod_handler.mode, chmod_handler.cursor, &chmod_handler.octal);
+        }
+        if let Handler::Chown(chown_handler) = handler {
+            render_chown(frame, area, model, &chown_handler.candidates(), chown_handler.selected, &chown_handler.query, chown_handler.user.as_ref());
+        }
+        if let Handler::Cleanup(cleanup_handler) = handler {
+            render_cleanup(frame, area, model, &cleanup_handler.leftovers, &cleanup_handler.checked, cleanup_handler.selected);
+        }
+        if let Handler::OldFiles(old_files_handler) = handler {
+            render_old_files(frame, area, model, &old_files_handler.files, &old_files_handler.checked, old_files_handler.selected, &old_files_handler.age);
+        }
+        if let Handler::HardLinks(hard_links_handler) = handler {
+            render_hard_links(frame, area, model, &hard_links_handler.links, hard_links_handler.selected);
+        }
+        if let Handler::DiskImage(disk_image_handler) = handler {
+            render_disk_image(frame, area, model, &disk_image_handler.listing, disk_image_handler.selected);
+        }
+
+        if theme::accessible(model) {
+            theme::apply_high_contrast(frame.buffer_mut());
+        }
     }
 }
\ No newline at end of file
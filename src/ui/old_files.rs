@@ -0,0 +1,62 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use crate::{core::display_path, model::{AppModel, FileEntry}, services::{history_service::format_modified, FileService}, ui::{popup::centered_rect, theme::highlight_style}};
+
+/// Renders the files found by `:older`, oldest first, with the size of those checked in the title
+pub fn render_old_files(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+    files: &[FileEntry],
+    checked: &[bool],
+    selected: usize,
+    age: &str,
+) {
+    let popup_area = centered_rect(80, 70, area);
+
+    let items: Vec<ListItem> = files
+        .iter()
+        .zip(checked)
+        .map(|(file, checked)| {
+            let path = file.path.strip_prefix(&model.current_dir).unwrap_or(&file.path);
+            let modified = file.modified.map(format_modified).unwrap_or_default();
+            let size = FileService::format_file_size(file.size.unwrap_or(0));
+            let text = format!("[{}] {:>10}  {:>9}  {}", if *checked { 'x' } else { ' ' }, modified, size, display_path(path));
+            if *checked {
+                ListItem::new(text)
+            } else {
+                ListItem::new(text).style(Style::default().fg(Color::DarkGray))
+            }
+        })
+        .collect();
+
+    let (count, size) = files
+        .iter()
+        .zip(checked)
+        .filter(|(_, checked)| **checked)
+        .fold((0, 0), |(count, size), (file, _)| (count + 1, size + file.size.unwrap_or(0)));
+    let title = format!(
+        "Older than {}: {} of {} checked ({}) - space: check, a: all, d: trash, D: delete, z: archive, Enter: jump, ESC: close",
+        age, count, files.len(), FileService::format_file_size(size),
+    );
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(highlight_style(model))
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(
+        list,
+        popup_area,
+        &mut ListState::default().with_selected(Some(selected)),
+    );
+}
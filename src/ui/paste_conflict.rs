@@ -0,0 +1,40 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    Frame,
+};
+use crate::{core::display_path, model::AppModel, services::FileService, ui::{dialog::{render_dialog, Dialog}, theme::plain}};
+
+const CHOICES: &[(&str, &str)] = &[
+    ("o", "overwrite"),
+    ("s", "skip"),
+    ("r", "rename"),
+    ("a", "overwrite all"),
+    ("S", "skip all"),
+    ("ESC", "cancel"),
+];
+
+/// Renders the question about the pasted entry whose name is taken, the existing entry beside the incoming one
+pub fn render_paste_conflict(frame: &mut Frame, area: Rect, model: &AppModel) {
+    let Some(pending) = &model.pending_paste else { return };
+    let Some(source) = pending.current() else { return };
+    let Some(name) = source.file_name() else { return };
+    let target = pending.destination.join(name);
+
+    let label = |name: &str| Span::styled(format!("{:<10}", name), Style::default().fg(Color::Gray));
+    let describe = |path: &std::path::Path| match path.symlink_metadata() {
+        Ok(metadata) if metadata.is_dir() => format!("{} (directory)", display_path(path)),
+        Ok(metadata) => format!("{} ({})", display_path(path), FileService::format_file_size(metadata.len())),
+        Err(_) => display_path(path),
+    };
+    let lines = vec![
+        Line::from(format!("{} already exists here", plain(model, &name.to_string_lossy()))),
+        Line::from(""),
+        Line::from(vec![label("Existing"), Span::raw(plain(model, &describe(&target)).into_owned())]),
+        Line::from(vec![label("Incoming"), Span::raw(plain(model, &describe(source)).into_owned())]),
+    ];
+
+    let title = format!("Name taken ({} of {})", pending.choices.len() + 1, pending.conflicts.len());
+    render_dialog(frame, area, model, Dialog { title: &title, lines, choices: CHOICES, color: Color::LightYellow });
+}
@@ -0,0 +1,36 @@
+use crate::{model::AppModel, ui::theme::{accessible, emoji}};
+
+/// Braille frames of the spinner, one per tick
+const SPINNER: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+/// The same for terminals without emoji, whose fonts may lack braille
+const PLAIN_SPINNER: &[&str] = &["|", "/", "-", "\\"];
+/// Partly filled cells of the gauge, by eighths
+const EIGHTHS: &[&str] = &[" ", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// The spinner at the current tick, to put before what is being worked on
+/// Nothing in accessibility mode, where a changing character only gets read out again and again
+pub fn spinner(model: &AppModel) -> &'static str {
+    if accessible(model) {
+        return "";
+    }
+    let frames = if emoji(model) { SPINNER } else { PLAIN_SPINNER };
+    frames[(model.ticks % frames.len() as u64) as usize]
+}
+
+/// `ratio` (0 to 1) as a bar `width` cells wide followed by its percentage; just the percentage in accessibility mode
+pub fn gauge(model: &AppModel, ratio: f64, width: usize) -> String {
+    let ratio = ratio.clamp(0.0, 1.0);
+    let percent = format!("{:.0}%", ratio * 100.0);
+    if accessible(model) {
+        return percent;
+    }
+    let eighths = (ratio * (width * 8) as f64).round() as usize;
+    let (full, part) = (eighths / 8, eighths % 8);
+    let bar = if emoji(model) {
+        let partial = if full < width { EIGHTHS[part] } else { "" };
+        format!("{}{}{}", "█".repeat(full), partial, " ".repeat(width.saturating_sub(full + 1)))
+    } else {
+        format!("{}{}", "#".repeat(full), ".".repeat(width - full))
+    };
+    format!("[{}] {}", bar, percent)
+}
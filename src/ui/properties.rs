@@ -0,0 +1,78 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use crate::{core::{attribute_names, has_attribute, permission_string}, model::AppModel, services::FileService, ui::{popup::centered_rect, theme::plain}};
+
+/// Renders the properties panel for the selected entry as an overlay
+pub fn render_properties(frame: &mut Frame, area: Rect, model: &AppModel) {
+    let popup_area = centered_rect(70, 60, area);
+    let block = Block::default()
+        .title("Properties - j/k: next entry, I/A: immutable/append-only, ESC: close")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let lines = match model.selected_properties() {
+        None => vec![Line::from("No entry selected")],
+        Some(Err(e)) => vec![Line::from(Span::styled(format!("❌ {}", e), Style::default().fg(Color::Red)))],
+        Some(Ok(properties)) => {
+            let label = |name: &str| Span::styled(format!("{:<12}", name), Style::default().fg(Color::Gray));
+            let time = |time: Option<std::time::SystemTime>| {
+                time.map(|time| chrono::DateTime::<chrono::Local>::from(time).format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            };
+            let size = |bytes: u64| format!("{} ({} bytes)", FileService::format_file_size(bytes), bytes);
+
+            let mut lines = vec![
+                Line::from(vec![label("Path"), Span::raw(properties.path.display().to_string())]),
+                Line::from(vec![label("Type"), Span::raw(properties.kind)]),
+            ];
+            if let Some(target) = &properties.link_target {
+                lines.push(Line::from(vec![label("Target"), Span::raw(target.display().to_string())]));
+            }
+            lines.push(Line::from(vec![label("Size"), Span::raw(size(properties.size))]));
+            if let Some(allocated) = properties.allocated {
+                let ratio = properties.compression_ratio()
+                    .filter(|_| properties.kind != "Directory")
+                    .map(|ratio| format!(", ratio {:.2}:1", ratio))
+                    .unwrap_or_default();
+                lines.push(Line::from(vec![label("On disk"), Span::raw(format!("{}{}", size(allocated), ratio))]));
+            }
+            if let Some(note) = properties.storage_note() {
+                lines.push(Line::from(vec![label(""), Span::styled(plain(model, &format!("⚠ {}", note)).into_owned(), Style::default().fg(Color::Yellow))]));
+            }
+            if let Some(mode) = properties.mode {
+                lines.push(Line::from(vec![label("Permissions"), Span::raw(format!("{} ({:o})", permission_string(mode), mode & 0o7777))]));
+            }
+            if let Some(flags) = properties.attributes {
+                let names = attribute_names(flags);
+                let text = if names.is_empty() { "none".to_string() } else { names.join(", ") };
+                // Immutable and append-only entries refuse writes even to root, a common "permission denied" puzzle
+                let style = if has_attribute(flags, 'i') || has_attribute(flags, 'a') { Style::default().fg(Color::Yellow) } else { Style::default() };
+                lines.push(Line::from(vec![label("Attributes"), Span::styled(text, style)]));
+            }
+            if let Some(context) = &properties.security_context {
+                lines.push(Line::from(vec![label("SELinux"), Span::raw(context.clone())]));
+            }
+            // Mode bits alone often don't explain who can get in: named ACL entries, capped by the mask, add to them
+            for (name, acl) in [("ACL", &properties.acl), ("Default ACL", &properties.default_acl)] {
+                if !acl.is_empty() {
+                    let entries: Vec<String> = acl.iter().map(|entry| model.describe_acl_entry(entry)).collect();
+                    lines.push(Line::from(vec![label(name), Span::raw(entries.join(", "))]));
+                }
+            }
+            if let Some(hard_links) = properties.hard_links {
+                lines.push(Line::from(vec![label("Hard links"), Span::raw(hard_links.to_string())]));
+            }
+            lines.push(Line::from(vec![label("Modified"), Span::raw(time(properties.modified))]));
+            lines.push(Line::from(vec![label("Accessed"), Span::raw(time(properties.accessed))]));
+            lines
+        }
+    };
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), popup_area);
+}
@@ -0,0 +1,49 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use crate::{core::display_path, model::{AppModel, RECENTS_LIMIT}, services::history_service::format_age, ui::{popup::centered_rect, theme::{entry_label, highlight_style}}};
+
+/// Renders the recently opened files overlay, most recent first
+/// Files that have since been deleted stay listed, greyed out and marked as missing
+pub fn render_recent_files(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+    selected: usize,
+) {
+    let popup_area = centered_rect(80, 70, area);
+
+    let items: Vec<ListItem> = model
+        .recent_opens(RECENTS_LIMIT)
+        .into_iter()
+        .map(|open| {
+            let label = entry_label(model, open.path.is_dir(), &display_path(&open.path));
+            let text = format!("{:>9}  {}  ({}x)", format_age(open.opened_at), label, open.count);
+            if open.path.exists() {
+                ListItem::new(text)
+            } else {
+                ListItem::new(format!("{}  (missing)", text)).style(Style::default().fg(Color::DarkGray))
+            }
+        })
+        .collect();
+
+    let block = Block::default()
+        .title("Recently opened - Enter: jump, o: open again, ESC: close")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(highlight_style(model))
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(
+        list,
+        popup_area,
+        &mut ListState::default().with_selected(Some(selected)),
+    );
+}
@@ -1,10 +1,11 @@
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
+    text::Line,
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use crate::{handlers::Handler, model::AppModel};
+use crate::{commands::Command, core::check_name, handlers::Handler, model::AppModel, ui::theme::{accent, plain}};
 
 /// Renders the search bar component at the bottom
 pub fn render_search_bar(
@@ -13,20 +14,22 @@ pub fn render_search_bar(
     model: &AppModel,
     handler: &Handler,
 ) {
-    let (title, border_color, text_color, search_text) = match handler {
+    let command_line;
+    let command_title;
+    let rename_title;
+    let archive_title;
+    let (title, text_color, search_text) = match handler {
         Handler::Search(_) => (
             "🔍 Search Mode (Active)",
-            Color::Green,
             Color::White,
             if model.query_text.is_empty() {
-                "Type to search..."
+                "Type to search... (owner:root perm:world-writable type:symlink narrow it down)"
             } else {
                 &model.query_text
             },
         ),
         Handler::FuzzyFind(_) => (
             "🔍 Fuzzy Find Mode (Active) - ESC to exit",
-            Color::Cyan,
             Color::White,
             if model.query_text.is_empty() {
                 "Type to fuzzy search files..."
@@ -34,22 +37,93 @@ pub fn render_search_bar(
                 &model.query_text
             },
         ),
-        Handler::Explore(_) => (
+        Handler::Command(command_handler) => {
+            command_line = format!(":{}", command_handler.input);
+            // Creation commands show the permissions the new entry will get
+            let input = &command_handler.input;
+            let problem = Command::new_name(input)
+                .and_then(|(_, name)| check_name(name).filter(|_| !name.is_empty()));
+            command_title = match (problem, Command::creation(input)) {
+                (None, None) if Command::directory_argument(input).is_some() => match command_handler.completion.position() {
+                    Some(position) => format!("Go to directory {} (Tab: next, Enter to go)", position),
+                    None => "Go to directory (Tab: complete, Enter to go, ESC to cancel)".to_string(),
+                },
+                (Some(problem), _) => format!(
+                    "⚠ {} - Tab: use '{}'{}",
+                    problem.message,
+                    problem.suggestion,
+                    if problem.fatal { "" } else { ", Enter: keep" }
+                ),
+                (None, Some((false, name))) if !name.is_empty() && model.current_dir.join(name).symlink_metadata().is_ok() => {
+                    format!("Touch {}: set its modification time to now (Enter, ESC to cancel)", name)
+                },
+                (None, Some((directory, _))) => format!(
+                    "New {} {} (Enter to create, ESC to cancel)",
+                    if directory { "directory" } else { "file" },
+                    model.creation_mode(directory).describe()
+                ),
+                (None, None) => "Command (Enter to run, ESC to cancel)".to_string(),
+            };
+            (
+                command_title.as_str(),
+                Color::White,
+                command_line.as_str(),
+            )
+        },
+        Handler::Rename(rename_handler) => {
+            let input = &rename_handler.input.text;
+            rename_title = match check_name(input).filter(|_| !input.is_empty()) {
+                Some(problem) => format!(
+                    "⚠ {} - Tab: use '{}'{}",
+                    problem.message,
+                    problem.suggestion,
+                    if problem.fatal { "" } else { ", Enter: keep" }
+                ),
+                None => format!("Rename {} (Enter to rename, ESC to cancel)", rename_handler.original),
+            };
+            (rename_title.as_str(), Color::White, input.as_str())
+        },
+        Handler::Archive(archive_handler) => {
+            let count = model.delete_targets().len();
+            archive_title = format!(
+                "Pack {} {} into (Enter to pack, Tab: zip/tar.gz, ESC to cancel)",
+                count,
+                if count == 1 { "entry" } else { "entries" }
+            );
+            (archive_title.as_str(), Color::White, archive_handler.input.text.as_str())
+        },
+        Handler::PreviewLinks(_) => (
+            "Links (Tab/j/k to move, Enter to open, ESC to exit)",
+            Color::White,
+            "Enter opens URLs in the browser and jumps to paths",
+        ),
+        Handler::Explore(_) | Handler::Marks(_) | Handler::Recents(_) | Handler::Downloads(_) | Handler::Bookmarks(_) | Handler::BookmarkSet(_) | Handler::SendTo(_) | Handler::Grid(_) | Handler::Properties(_) | Handler::About(_)
+        | Handler::Diagnostics(_) | Handler::BatchReport(_) | Handler::ConfirmDelete(_) | Handler::ConfirmProtected(_) | Handler::PasteConflict(_) | Handler::Jobs(_) | Handler::Chmod(_) | Handler::Chown(_) | Handler::HardLinks(_) | Handler::DiskImage(_) | Handler::Cleanup(_) | Handler::OldFiles(_) => (
             "Search (Press '/' to search, 'f' for fuzzy find)",
-            Color::Yellow,
             Color::DarkGray,
             "Press '/' to search or 'f' for fuzzy find...",
         ),
     };
 
     let block = Block::default()
-        .title(title)
+        .title(plain(model, title).into_owned())
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(border_color));
+        .border_style(Style::default().fg(accent(model, handler)));
 
     let paragraph = Paragraph::new(search_text)
         .block(block)
         .style(Style::default().fg(text_color));
 
     frame.render_widget(paragraph, area);
+
+    // Name prompts edit anywhere in the name, so show where
+    let input = match handler {
+        Handler::Rename(rename_handler) => Some(&rename_handler.input),
+        Handler::Archive(archive_handler) => Some(&archive_handler.input),
+        _ => None,
+    };
+    if let Some(input) = input {
+        let column = Line::from(&input.text[..input.cursor]).width() as u16;
+        frame.set_cursor_position((area.x + 1 + column.min(area.width.saturating_sub(3)), area.y + 1));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,61 @@
+use std::path::Path;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use crate::{core::display_path, model::AppModel, ui::{dialog::{render_dialog, Dialog}, popup::centered_rect, theme::{highlight_style, plain}}};
+
+const CHOICES: &[(&str, &str)] = &[("c", "copy"), ("m", "move"), ("l", "link"), ("ESC", "back")];
+
+/// Renders the destinations matching what was typed, each with where it comes from,
+/// or once one is picked the question whether to copy, move or link there
+pub fn render_send_to(frame: &mut Frame, area: Rect, model: &AppModel, query: &str, selected: usize, destination: Option<&Path>, completion: Option<String>) {
+    let count = model.delete_targets().len();
+    let what = if count == 1 { "1 entry".to_string() } else { format!("{} entries", count) };
+
+    if let Some(destination) = destination {
+        let lines = vec![Line::from(format!("Send {} to", what)), Line::from(plain(model, &display_path(destination)).into_owned())];
+        render_dialog(frame, area, model, Dialog { title: "Send to", lines, choices: CHOICES, color: Color::LightMagenta });
+        return;
+    }
+
+    let popup_area = centered_rect(70, 60, area);
+    let [input_area, list_area] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .areas(popup_area);
+
+    let input = Paragraph::new(query).block(
+        Block::default()
+            .title(match completion {
+                Some(position) => format!("Send {} to - directory {}, Tab: next", what, position),
+                None => format!("Send {} to - type to filter or a path, Tab: complete, Enter: pick, ESC: close", what),
+            })
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::LightMagenta)),
+    );
+
+    let items: Vec<ListItem> = model
+        .send_targets(query)
+        .into_iter()
+        .map(|target| {
+            ListItem::new(Line::from(vec![
+                Span::raw(plain(model, &display_path(&target.path)).into_owned()),
+                Span::styled(format!("  {}", target.origin), Style::default().fg(Color::Gray)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::LightMagenta)))
+        .highlight_style(highlight_style(model))
+        .highlight_symbol("> ");
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(input, input_area);
+    frame.render_stateful_widget(list, list_area, &mut ListState::default().with_selected(Some(selected)));
+    let column = Line::from(query).width() as u16;
+    frame.set_cursor_position((input_area.x + 1 + column.min(input_area.width.saturating_sub(3)), input_area.y + 1));
+}
@@ -0,0 +1,117 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use crate::{config::StatusSegment, core::display_path, handlers::Handler, model::AppModel, services::FileService, ui::{progress::{gauge, spinner}, theme::{accent, accessible, entry_label, mode_label, plain}}};
+
+/// Renders the single-line status bar at the very bottom, from the segments of `[status_bar]` in the config
+pub fn render_status_bar(
+    frame: &mut Frame,
+    area: Rect,
+    model: &AppModel,
+    handler: &Handler,
+) {
+    // Accessibility mode reads the same way every time: mode, latest message, position, then the rest
+    let accessible = accessible(model);
+    let mut segments = model.config.status_bar.segments.clone();
+    if accessible {
+        let first = [StatusSegment::Mode, StatusSegment::Message, StatusSegment::Items];
+        segments.sort_by_key(|segment| first.iter().position(|front| front == segment).unwrap_or(first.len()));
+    }
+
+    let mut spans = Vec::new();
+    let mut after_text = false;  // Text segments are set apart from each other by a bar
+    for segment in segments {
+        if segment == StatusSegment::Mode {
+            if after_text {
+                spans.push(Span::raw(" "));
+            }
+            spans.push(Span::styled(format!(" {} ", mode_label(model, handler)), Style::default().fg(Color::Black).bg(accent(model, handler))));
+            after_text = false;
+            continue;
+        }
+        let Some((text, style)) = segment_text(segment, model, accessible) else { continue };
+        let separator = if after_text { " | " } else { " " };
+        spans.push(Span::styled(format!("{}{}", separator, text), style));
+        after_text = true;
+    }
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// What a segment shows and how, None when it has nothing to say
+fn segment_text(segment: StatusSegment, model: &AppModel, accessible: bool) -> Option<(String, Style)> {
+    let plain_style = Style::default();
+    match segment {
+        StatusSegment::Mode => None,
+        StatusSegment::Path => Some((display_path(&model.current_dir), Style::default().fg(Color::LightBlue))),
+        StatusSegment::Items if accessible => match model.get_selected_file() {
+            Some(file) => Some((
+                format!("{} of {}: {}", model.selected_index + 1, model.files.len(), entry_label(model, file.is_directory, &file.name)),
+                plain_style,
+            )),
+            None => Some(("no items".to_string(), plain_style)),
+        },
+        StatusSegment::Items => Some((format!("{} items", model.files.len()), plain_style)),
+        StatusSegment::Marks => {
+            if model.marked_paths.is_empty() {
+                return None;
+            }
+            let elsewhere = model.marks_outside_current_dir();
+            let marked = if elsewhere > 0 {
+                format!("{} marked ({} elsewhere)", model.marked_paths.len(), elsewhere)
+            } else {
+                format!("{} marked", model.marked_paths.len())
+            };
+            Some((marked, Style::default().fg(Color::LightMagenta)))
+        },
+        StatusSegment::Project => model.project_view.then(|| {
+            (format!("project view ({} ignored hidden)", model.ignored_paths.len()), Style::default().fg(Color::LightGreen))
+        }),
+        StatusSegment::Jobs => {
+            if let Some(batch) = model.batch.as_ref().filter(|batch| batch.is_running()) {
+                let text = format!("{} {} {}", spinner(model), batch.progress(), gauge(model, batch.ratio(), 10));
+                return Some((text.trim_start().to_string(), Style::default().fg(Color::LightYellow)));
+            }
+            if let Some(landing) = model.job_landing.as_ref().filter(|_| model.batch.is_none()) {
+                let name = landing.destination.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                return Some((format!("{} done, G (or click) to go to {}", landing.label, name), Style::default().fg(Color::LightCyan)));
+            }
+            // Packing and checksums have no count to put in a bar; the transfer gauge shows how far packing got
+            let job = model.jobs().into_iter().next()?;
+            let text = format!("{} {}", spinner(model), plain(model, &job.description));
+            Some((text.trim_start().to_string(), Style::default().fg(Color::LightYellow)))
+        },
+        StatusSegment::Downloads => {
+            let new_downloads = model.new_download_count();
+            if new_downloads == 0 {
+                return None;
+            }
+            let noun = if new_downloads == 1 { "download" } else { "downloads" };
+            let indicator = plain(model, &format!("⬇ {} new {} (W)", new_downloads, noun)).into_owned();
+            Some((indicator, Style::default().fg(Color::LightGreen)))
+        },
+        StatusSegment::Network => model.network_filesystem.as_ref().map(|fs_type| {
+            let indicator = plain(model, &format!("🌐 {} (lazy metadata, delayed preview)", fs_type)).into_owned();
+            (indicator, Style::default().fg(Color::LightBlue))
+        }),
+        StatusSegment::Disk => model.disk_usage_warning().map(|usage| {
+            let warning = format!(
+                "⚠ {} {:.0}% full, {} free",
+                usage.mount_point.display(),
+                usage.used_percent(),
+                FileService::format_file_size(usage.available)
+            );
+            (plain(model, &warning).into_owned(), Style::default().fg(Color::LightRed))
+        }),
+        StatusSegment::Git => model.git_branch.as_ref().map(|branch| {
+            (plain(model, &format!("⎇ {}", branch)).into_owned(), Style::default().fg(Color::LightGreen))
+        }),
+        StatusSegment::Clock => Some((chrono::Local::now().format("%H:%M").to_string(), plain_style)),
+        StatusSegment::Message if accessible => model.status_message.as_ref().map(|message| (plain(model, message).into_owned(), plain_style)),
+        StatusSegment::Message => model.status_message.as_ref().map(|message| (message.clone(), Style::default().fg(Color::White))),
+    }
+}
@@ -0,0 +1,191 @@
+use std::borrow::Cow;
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier, Style},
+};
+use crate::{core::display_path, handlers::Handler, model::{AppModel, FileEntry}, services::ColorDepth};
+
+/// Textual stand-ins for the emoji used in titles, previews and the status bar
+/// Purely decorative ones (followed by a space in the source text) are dropped with that space
+const EMOJI_TEXT: &[(&str, &str)] = &[
+    ("📁", "[dir]"), ("📄", "[file]"), ("🌐", "network:"), ("⎇", "branch"), ("⚠️", "Warning:"), ("⚠", "Warning:"),
+    ("🔒", "Encrypted:"), ("🔓", "Decrypted:"), ("🔐", "Checksum:"),
+    ("🔍", ""), ("⬇", ""), ("🖼", ""), ("📝", ""), ("📊", ""), ("🧲", ""), ("🔤", ""), ("🎬", ""),
+    ("▲", "ascending"), ("▼", "descending"), ("…", "..."), ("──", "--"),
+];
+
+/// Whether the accessibility mode is on: no emoji, monochrome high-contrast colors, textual markers
+pub fn accessible(model: &AppModel) -> bool {
+    model.config.accessibility.enabled
+}
+
+/// Whether emoji are shown: not in accessibility mode, nor where the terminal draws them narrower than
+/// the two columns the layout reserves for them
+pub fn emoji(model: &AppModel) -> bool {
+    !accessible(model) && model.capabilities().wide_emoji != Some(false)
+}
+
+/// `📁 name` normally; `name/` or plain `name` without emoji, so entry kinds are read out as text
+pub fn entry_label(model: &AppModel, is_directory: bool, name: &str) -> String {
+    match (!emoji(model), is_directory) {
+        (false, true) => format!("📁 {}", name),
+        (false, false) => format!("📄 {}", name),
+        (true, true) => format!("{}/", name.trim_end_matches('/')),
+        (true, false) => name.to_string(),
+    }
+}
+
+/// Label of a listed entry: `entry_label` of `name`, followed by ` -> target` for a symlink
+pub fn file_label(model: &AppModel, file: &FileEntry, name: &str) -> String {
+    let label = entry_label(model, file.is_directory, name);
+    match &file.link_target {
+        Some(target) => format!("{} -> {}", label, display_path(target)),
+        None => label,
+    }
+}
+
+/// `text` with emoji spelled out where they aren't shown, unchanged otherwise
+pub fn plain<'a>(model: &AppModel, text: &'a str) -> Cow<'a, str> {
+    if emoji(model) || text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    let mut text = text.to_string();
+    for (emoji, replacement) in EMOJI_TEXT {
+        if replacement.is_empty() {
+            text = text.replace(&format!("{} ", emoji), "");
+        }
+        text = text.replace(emoji, replacement);
+    }
+    Cow::Owned(text)
+}
+
+/// Name of the mode `handler` is in, as the status bar shows it
+pub fn mode_label(model: &AppModel, handler: &Handler) -> &'static str {
+    match handler {
+        Handler::Explore(_) if model.visual_anchor.is_some() => "VISUAL",
+        Handler::Explore(_) => "EXPLORE",
+        Handler::Search(_) => "SEARCH",
+        Handler::FuzzyFind(_) => "FUZZY",
+        Handler::Command(_) => "COMMAND",
+        Handler::Marks(_) => "MARKS",
+        Handler::Recents(_) => "RECENTS",
+        Handler::Downloads(_) => "DOWNLOADS",
+        Handler::Bookmarks(_) => "BOOKMARKS",
+        Handler::BookmarkSet(_) => "BOOKMARK",
+        Handler::SendTo(_) => "SEND TO",
+        Handler::PreviewLinks(_) => "LINKS",
+        Handler::Grid(_) => "GRID",
+        Handler::Properties(_) => "PROPERTIES",
+        Handler::About(_) => "VERSION",
+        Handler::Diagnostics(_) => "PROBLEMS",
+        Handler::BatchReport(_) => "FAILURES",
+        Handler::ConfirmDelete(_) => "DELETE",
+        Handler::ConfirmProtected(_) => "PROTECTED",
+        Handler::PasteConflict(_) => "CONFLICT",
+        Handler::Jobs(_) => "JOBS",
+        Handler::Rename(_) => "RENAME",
+        Handler::Archive(_) => "ARCHIVE",
+        Handler::Chmod(_) => "CHMOD",
+        Handler::Chown(_) => "CHOWN",
+        Handler::HardLinks(_) => "HARD LINKS",
+        Handler::DiskImage(_) => "DISK IMAGE",
+        Handler::Cleanup(_) => "CLEANUP",
+        Handler::OldFiles(_) => "OLD FILES",
+    }
+}
+
+/// Color of the mode `handler` is in, from `[accents]` in the config
+pub fn accent(model: &AppModel, handler: &Handler) -> Color {
+    let accents = &model.config.accents;
+    match handler {
+        Handler::Explore(_) if model.visual_anchor.is_some() => accents.visual,
+        Handler::Explore(_) | Handler::Grid(_) => accents.explore,
+        Handler::Search(_) => accents.search,
+        Handler::FuzzyFind(_) => accents.fuzzy,
+        Handler::Command(_) | Handler::Rename(_) | Handler::Archive(_) => accents.command,
+        _ => accents.popup,
+    }
+}
+
+/// An RGB color as close as the terminal can show it
+pub fn rgb(depth: ColorDepth, [r, g, b]: [u8; 3]) -> Color {
+    match depth {
+        ColorDepth::TrueColor => Color::Rgb(r, g, b),
+        ColorDepth::Indexed256 => Color::Indexed(xterm_index(r, g, b)),
+        ColorDepth::Basic => ansi_color(r, g, b),
+    }
+}
+
+/// Nearest entry of the xterm palette: its 6x6x6 color cube or its 24-step gray ramp
+fn xterm_index(r: u8, g: u8, b: u8) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let level = |value: u8| LEVELS.iter().enumerate().min_by_key(|(_, level)| level.abs_diff(value)).map_or(0, |(index, _)| index);
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+
+    let average = (r as u16 + g as u16 + b as u16) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + 10 * gray_step;
+
+    let distance = |(cr, cg, cb): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(cr, r) + d(cg, g) + d(cb, b)
+    };
+    if distance((gray, gray, gray)) < distance(cube) {
+        232 + gray_step
+    } else {
+        16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8
+    }
+}
+
+/// Nearest of the 16 ANSI colors, whose exact shades are up to the terminal's palette
+fn ansi_color(r: u8, g: u8, b: u8) -> Color {
+    let brightest = r.max(g).max(b);
+    if brightest < 48 {
+        return Color::Black;
+    }
+    // Channels near the brightest one make up the hue
+    let lit = |value: u8| value as u16 * 2 > brightest as u16;
+    let bright = brightest > 170;
+    match (lit(r), lit(g), lit(b), bright) {
+        (true, false, false, false) => Color::Red,
+        (true, false, false, true) => Color::LightRed,
+        (false, true, false, false) => Color::Green,
+        (false, true, false, true) => Color::LightGreen,
+        (true, true, false, false) => Color::Yellow,
+        (true, true, false, true) => Color::LightYellow,
+        (false, false, true, false) => Color::Blue,
+        (false, false, true, true) => Color::LightBlue,
+        (true, false, true, false) => Color::Magenta,
+        (true, false, true, true) => Color::LightMagenta,
+        (false, true, true, false) => Color::Cyan,
+        (false, true, true, true) => Color::LightCyan,
+        (_, _, _, false) => Color::DarkGray,
+        (_, _, _, true) if brightest > 220 => Color::White,
+        _ => Color::Gray,
+    }
+}
+
+/// Style of the selected row: reverse video in accessibility mode, which works on any palette
+pub fn highlight_style(model: &AppModel) -> Style {
+    if accessible(model) {
+        Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+    } else {
+        Style::default().bg(Color::DarkGray).fg(Color::Yellow)
+    }
+}
+
+/// Repaint a finished frame in the terminal's own foreground and background colors
+/// Anything that stood out by its background (selection, badges) is shown in reverse video instead,
+/// so no state is carried by color alone
+pub fn apply_high_contrast(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        let highlighted = !matches!(cell.bg, Color::Reset) || cell.modifier.contains(Modifier::REVERSED);
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+        cell.modifier.remove(Modifier::DIM);
+        if highlighted {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+    }
+}
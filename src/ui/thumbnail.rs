@@ -0,0 +1,244 @@
+use std::{
+    io::{self, Write},
+    rc::Rc,
+};
+use flate2::{write::ZlibEncoder, Compression};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use crate::{
+    core::base64_encode,
+    model::AppModel,
+    services::{image_service::{ImagePlacement, Thumbnail}, ColorDepth, GraphicsProtocol},
+    ui::theme::rgb,
+};
+
+/// Cell size assumed when the terminal does not report its size in pixels
+const FALLBACK_CELL_PIXELS: (u32, u32) = (10, 20);
+
+/// Base64 bytes per kitty graphics escape, the most the protocol accepts
+const KITTY_CHUNK: usize = 4096;
+
+/// Converts a thumbnail into lines of upper-half blocks (foreground = top pixel, background = bottom pixel)
+/// Colors are reduced to what the terminal can show
+pub fn thumbnail_lines(thumbnail: &Thumbnail, depth: ColorDepth) -> Vec<Line<'static>> {
+    (0..thumbnail.height)
+        .step_by(2)
+        .map(|y| {
+            let spans = (0..thumbnail.width)
+                .map(|x| {
+                    let mut style = Style::default().fg(rgb(depth, thumbnail.pixel(x, y)));
+                    if y + 1 < thumbnail.height {
+                        style = style.bg(rgb(depth, thumbnail.pixel(x, y + 1)));
+                    }
+                    Span::styled("▀", style)
+                })
+                .collect::<Vec<_>>();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Pixel bounds of a thumbnail filling `cols` x `rows` cells with however the terminal draws images
+pub fn thumbnail_bounds(model: &AppModel, cols: u16, rows: u16) -> (u32, u32) {
+    if model.capabilities().graphics().is_none() {
+        return (cols as u32, rows as u32 * 2);
+    }
+    let (cell_width, cell_height) = cell_pixels();
+    (cols as u32 * cell_width, rows as u32 * cell_height)
+}
+
+/// Draws a thumbnail centered at the top of `area` and returns the rows it took
+/// With a graphics protocol the cells are only reserved here; the image goes out after the frame
+pub fn render_thumbnail(frame: &mut Frame, area: Rect, thumbnail: Rc<Thumbnail>, model: &AppModel) -> u16 {
+    if model.capabilities().graphics().is_none() {
+        let lines = thumbnail_lines(&thumbnail, model.capabilities().colors);
+        let image_area = Rect { height: (lines.len() as u16).min(area.height), ..area };
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), image_area);
+        return image_area.height;
+    }
+
+    let (cell_width, cell_height) = cell_pixels();
+    let cols = (thumbnail.width.div_ceil(cell_width) as u16).min(area.width);
+    let rows = (thumbnail.height.div_ceil(cell_height) as u16).min(area.height);
+    let image_area = Rect { x: area.x + (area.width - cols) / 2, y: area.y, width: cols, height: rows };
+    // Skipped cells are left alone by the diff, so the terminal keeps the picture drawn over them
+    let buffer = frame.buffer_mut();
+    for position in image_area.positions() {
+        buffer[position].reset();
+        buffer[position].set_skip(true);
+    }
+    model.place_image(ImagePlacement { x: image_area.x, y: image_area.y, cols, rows, thumbnail });
+    rows
+}
+
+/// Size of one terminal cell in pixels
+fn cell_pixels() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.columns > 0 && size.rows > 0 && size.width >= size.columns && size.height >= size.rows => {
+            ((size.width / size.columns) as u32, (size.height / size.rows) as u32)
+        }
+        _ => FALLBACK_CELL_PIXELS,
+    }
+}
+
+/// Kitty graphics escapes transmitting and displaying a thumbnail at the cursor
+/// Pixels go as zlib-compressed RGB, split into chunks; the cursor does not move
+pub fn kitty_sequence(thumbnail: &Thumbnail) -> String {
+    let raw: Vec<u8> = thumbnail.pixels.iter().flatten().copied().collect();
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::fast());
+    let (payload, compression) = match encoder.write_all(&raw).and_then(|_| encoder.finish()) {
+        Ok(compressed) => (base64_encode(&compressed), ",o=z"),
+        Err(_) => (base64_encode(&raw), ""),
+    };
+
+    let mut sequence = String::new();
+    let mut start = 0;
+    while start < payload.len() {
+        let end = (start + KITTY_CHUNK).min(payload.len());
+        let more = u8::from(end < payload.len());
+        if start == 0 {
+            sequence.push_str(&format!(
+                "\x1b_Ga=T,f=24,s={},v={}{},C=1,q=2,m={};",
+                thumbnail.width, thumbnail.height, compression, more
+            ));
+        } else {
+            sequence.push_str(&format!("\x1b_Gm={};", more));
+        }
+        sequence.push_str(&payload[start..end]);
+        sequence.push_str("\x1b\\");
+        start = end;
+    }
+    sequence
+}
+
+/// Sixel escape drawing a thumbnail at the cursor
+/// Colors are mapped to a 6x6x6 cube and each band of six rows is run-length encoded per color
+pub fn sixel_sequence(thumbnail: &Thumbnail) -> String {
+    let level = |value: u8| (value as usize * 5 + 127) / 255;
+    let colors: Vec<usize> = thumbnail.pixels.iter().map(|&[r, g, b]| level(r) * 36 + level(g) * 6 + level(b)).collect();
+    let (width, height) = (thumbnail.width as usize, thumbnail.height as usize);
+
+    let mut sequence = format!("\x1bPq\"1;1;{};{}", width, height);
+    let mut used = [false; 216];
+    for &color in &colors {
+        used[color] = true;
+    }
+    for color in (0..216).filter(|&color| used[color]) {
+        sequence.push_str(&format!("#{};2;{};{};{}", color, color / 36 * 20, color / 6 % 6 * 20, color % 6 * 20));
+    }
+
+    for top in (0..height).step_by(6) {
+        let band_rows = (height - top).min(6);
+        let mut in_band = [false; 216];
+        let mut band_colors = Vec::new();
+        for &color in &colors[top * width..(top + band_rows) * width] {
+            if !in_band[color] {
+                in_band[color] = true;
+                band_colors.push(color);
+            }
+        }
+        for (index, &color) in band_colors.iter().enumerate() {
+            if index > 0 {
+                sequence.push('$');
+            }
+            sequence.push_str(&format!("#{}", color));
+            let mut run: Option<(char, usize)> = None;
+            for x in 0..width {
+                let bits = (0..band_rows)
+                    .filter(|row| colors[(top + row) * width + x] == color)
+                    .fold(0u8, |bits, row| bits | 1 << row);
+                let sixel = (63 + bits) as char;
+                run = match run {
+                    Some((current, count)) if current == sixel => Some((current, count + 1)),
+                    Some((current, count)) => {
+                        push_sixel_run(&mut sequence, current, count);
+                        Some((sixel, 1))
+                    }
+                    None => Some((sixel, 1)),
+                };
+            }
+            if let Some((current, count)) = run {
+                push_sixel_run(&mut sequence, current, count);
+            }
+        }
+        // Moving down after the last band could scroll the screen
+        if top + 6 < height {
+            sequence.push('-');
+        }
+    }
+    sequence.push_str("\x1b\\");
+    sequence
+}
+
+fn push_sixel_run(sequence: &mut String, sixel: char, count: usize) {
+    if count > 3 {
+        sequence.push_str(&format!("!{}{}", count, sixel));
+    } else {
+        sequence.extend(std::iter::repeat_n(sixel, count));
+    }
+}
+
+/// Images currently on screen through a graphics protocol
+/// They are only sent again when the placements or the screen change, since the diff never touches their cells
+#[derive(Debug, Default)]
+pub struct GraphicsLayer {
+    shown: Vec<ImagePlacement>,
+    screen: Rect,
+}
+
+impl GraphicsLayer {
+    /// Draws the placements of a finished frame, dropping any whose cells something was drawn over
+    pub fn present(
+        &mut self,
+        out: &mut impl Write,
+        protocol: GraphicsProtocol,
+        buffer: &Buffer,
+        placements: Vec<ImagePlacement>,
+    ) -> io::Result<()> {
+        let placements: Vec<ImagePlacement> = placements
+            .into_iter()
+            .filter(|placement| {
+                let area = Rect { x: placement.x, y: placement.y, width: placement.cols, height: placement.rows };
+                area.positions().all(|position| buffer.cell(position).is_some_and(|cell| cell.skip))
+            })
+            .collect();
+        let unchanged = self.screen == buffer.area
+            && self.shown.len() == placements.len()
+            && self.shown.iter().zip(&placements).all(|(shown, placement)| same_placement(shown, placement));
+        if unchanged {
+            return Ok(());
+        }
+
+        if protocol == GraphicsProtocol::Kitty {
+            write!(out, "\x1b_Ga=d,d=A,q=2\x1b\\")?;
+        }
+        for placement in &placements {
+            let sequence = match protocol {
+                GraphicsProtocol::Kitty => kitty_sequence(&placement.thumbnail),
+                GraphicsProtocol::Sixel => sixel_sequence(&placement.thumbnail),
+            };
+            write!(out, "\x1b[{};{}H{}", placement.y + 1, placement.x + 1, sequence)?;
+        }
+        out.flush()?;
+
+        self.shown = placements;
+        self.screen = buffer.area;
+        Ok(())
+    }
+
+    /// Forget what is on screen after it was cleared, so the next frame sends everything again
+    pub fn forget(&mut self) {
+        self.shown.clear();
+        self.screen = Rect::default();
+    }
+}
+
+fn same_placement(a: &ImagePlacement, b: &ImagePlacement) -> bool {
+    (a.x, a.y, a.cols, a.rows) == (b.x, b.y, b.cols, b.rows) && Rc::ptr_eq(&a.thumbnail, &b.thumbnail)
+}
@@ -0,0 +1,41 @@
+use std::time::Duration;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::LineGauge,
+    Frame,
+};
+use crate::{batch::Transfer, model::AppModel, services::FileService, ui::progress::spinner};
+
+/// Renders the progress of a running copy, move or archive by the data it got through, above the status bar
+pub fn render_transfer_gauge(frame: &mut Frame, area: Rect, model: &AppModel, transfer: Transfer) {
+    let spinner = match spinner(model) {
+        "" => String::new(),
+        frame => format!("{} ", frame),
+    };
+    let eta = transfer.eta.map(|eta| format!(", {} left", format_eta(eta))).unwrap_or_default();
+    let text = format!(
+        " {}{} {} of {}{} - Esc: cancel ",
+        spinner,
+        transfer.label,
+        FileService::format_file_size(transfer.done),
+        FileService::format_file_size(transfer.total),
+        eta
+    );
+    let gauge = LineGauge::default()
+        .ratio(transfer.ratio())
+        .label(text)
+        .filled_style(Style::default().fg(Color::LightYellow))
+        .unfilled_style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(gauge, area);
+}
+
+/// `45s`, `3m 20s` or `1h 05m`
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
@@ -0,0 +1,382 @@
+use std::path::PathBuf;
+use crate::{
+    actions::Action, commands::Command, core::display_path, messages::AppMessage,
+    model::{AppModel, ConflictChoice, DeleteMode, JobKind, LinkTarget, PasteMode},
+    services::{Account, Bookmark, DiskImageKind, EncryptionService, ImageEntry},
+};
+
+/// Side effects of an update, carried out by App after the model has changed
+/// Updates only change the model in memory; reading directories, running git or other programs,
+/// and writing files are effects, so updates can be replayed without touching anything else.
+/// An effect that fails puts the error in the status bar and drops the effects queued after it,
+/// which are meant to follow its success, like closing the popup that asked for it
+#[derive(Debug, PartialEq)]
+pub enum Effect {
+    App(AppMessage),               // Handled like a message from any handler: mode switches, the editor, quitting
+    OpenWithFileManager(PathBuf),  // Launch the configured file manager on the path, detached
+    PasteEntries,                  // Copy or move the yanked entries into the current directory
+    UndoOperation,                 // Revert the last recorded copy, move, rename or trash
+    RedoOperation,                 // Do the last undone operation again
+    PasteClipboardImage,           // Save the clipboard image into the current directory
+
+    // Reading directories
+    GoToParent,                    // List the parent directory with the cursor on the one we came from
+    EnterDirectory(PathBuf),       // List a directory
+    GoToJobDestination,            // List where the last copy or move put its entries, marking them
+    Reveal(PathBuf),               // List the entry's directory with the cursor on it
+    RevealOpened(PathBuf),         // Same for a file from the history, failing if it is gone
+    RevealLastOpened,              // Reveal the file opened last
+    FollowLink(LinkTarget),        // Open a URL from the preview in the browser, or reveal a path
+    Refresh,                       // Read the directory again and drop cached previews
+    ReloadDownloads,               // Read the downloads directory again
+    FindHardLinks,                 // Search the current directory for other links to the selected file
+    BrowseDiskImage(PathBuf, Vec<ImageEntry>),  // List the directory of an ISO image reached through these from its root
+
+    // Asking git, other programs and file metadata
+    ToggleProjectView,             // Hide or show gitignored entries
+    CycleGrouping,                 // Group by the next key, with git statuses when that is by git
+    ComputeDigests,                // Hash the selected file in the background
+    VerifyChecksums,               // Check the selected file against its checksum files again
+    EditPermissions(PathBuf),      // Read the entry's mode for the permission editor
+    ConfirmShred(Vec<PathBuf>),    // Make sure the entries can be shredded before asking
+
+    // Writing
+    SetBookmark(char),             // Bookmark the current directory on the key
+    RemoveBookmark(Bookmark),
+    ArchiveEntries(String),        // Pack the marked entries (or the selected one) into the named archive
+    SendEntriesTo(PathBuf, PasteMode),
+    LinkEntriesTo(PathBuf),
+    SetPermissions(PathBuf, u32),
+    ChownEntries(Account, Account),  // User, then group
+    ExtractFromImage(PathBuf, ImageEntry),  // Copy a file out of an ISO image into the current directory
+    ResolvePasteConflict(ConflictChoice, bool),  // For the entry asked about, or for all of those left
+
+    Confirmed(Box<Effect>),        // Let through after the name of the protected directory it changes was typed
+}
+
+impl Effect {
+    /// The protected directory whose entries this effect would change, and what it would do to them,
+    /// so it waits for the directory's name to be typed like deleting there does
+    pub fn protected_root(&self, model: &AppModel) -> Option<(PathBuf, &'static str)> {
+        let selected = || model.get_selected_file().map(|file| file.path.clone()).into_iter().collect();
+        let (targets, what): (Vec<PathBuf>, _) = match self {
+            Effect::ChownEntries(..) => (model.delete_targets(), "change the owner of entries in"),
+            Effect::SetPermissions(path, _) => (vec![path.clone()], "change the permissions of entries in"),
+            Effect::PasteEntries => match &model.file_clipboard {
+                Some(clipboard) if clipboard.mode == PasteMode::Move => (clipboard.paths.clone(), "move entries out of"),
+                _ => return None,
+            },
+            Effect::SendEntriesTo(_, PasteMode::Move) => (model.delete_targets(), "move entries out of"),
+            Effect::ResolvePasteConflict(ConflictChoice::Overwrite, all) => (model.paste_overwrites(*all), "overwrite entries in"),
+            Effect::App(AppMessage::RenameSelected(_)) => (selected(), "rename entries in"),
+            Effect::App(AppMessage::ExecuteCommand(input)) => match Command::parse(input) {
+                Ok(Command::Rename(_)) => (selected(), "rename entries in"),
+                Ok(Command::BulkRename) => (model.delete_targets(), "rename entries in"),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        model.protected_root(&targets).map(|root| (root, what))
+    }
+}
+
+/// What an update applies: an action bound to a key, or what a prompt or popup made of a key
+/// Handlers read the model but only change it through these, so every interaction can be replayed in a test
+#[derive(Debug, PartialEq)]
+pub enum Msg {
+    Action(Action),        // With its explore mode meaning
+    App(AppMessage),       // Passed on to App as is
+    Do(Effect),            // Work only App can do, passed on as is
+    Status(String),
+
+    // The search or fuzzy find query
+    QueryInsert(char),
+    QueryBackspace,
+    QueryDeleteWord,
+    QueryDeleteToEnd,
+    QueryClear,
+
+    MoveSelectionBy(isize),  // Rows of the thumbnail grid
+    Unmark(PathBuf),
+    ClearMarks,
+    MarkOnly(Vec<PathBuf>),
+    Yank(PasteMode),         // The marked entries, wherever they are
+    Cut(PathBuf),
+    MarkDownloadsSeen,
+
+    // Bulk operations and background jobs
+    RetryFailures,
+    SkipFailure(usize),      // Give up on one entry that failed, keeping the others for a retry
+    DismissBatch,
+    ToggleJobPause(JobKind),
+    CancelPaste,
+}
+
+impl From<Action> for Msg {
+    fn from(action: Action) -> Self {
+        Msg::Action(action)
+    }
+}
+
+/// Apply a message to the model, returning what App has to do next
+pub fn update(model: &mut AppModel, msg: impl Into<Msg>) -> Vec<Effect> {
+    match msg.into() {
+        Msg::Action(action) => return perform(model, action),
+        Msg::App(message) => return vec![Effect::App(message)],
+        Msg::Do(effect) => return vec![effect],
+        Msg::Status(status) => model.set_status(status),
+
+        Msg::QueryInsert(c) => model.append_to_query(c),
+        Msg::QueryBackspace => model.pop_from_query(),
+        Msg::QueryDeleteWord => model.delete_word_backward(),
+        Msg::QueryDeleteToEnd => model.delete_to_end(),
+        Msg::QueryClear => model.clear_query(),
+
+        Msg::MoveSelectionBy(delta) => model.move_selection_by(delta),
+        Msg::Unmark(path) => {
+            model.marked_paths.remove(&path);
+        },
+        Msg::ClearMarks => model.marked_paths.clear(),
+        Msg::MarkOnly(paths) => model.marked_paths = paths.into_iter().collect(),
+        Msg::Yank(mode) => {
+            model.yank_entries(mode);
+        },
+        Msg::Cut(path) => {
+            model.set_status(format!("Cut {} - paste it with p where it belongs", display_path(&path)));
+            model.cut_entry(path);
+        },
+        Msg::MarkDownloadsSeen => model.mark_downloads_seen(),
+
+        Msg::RetryFailures => {
+            if let Some(batch) = model.batch.as_mut() {
+                batch.retry();
+            }
+        },
+        // The report closes once nothing is left to retry
+        Msg::SkipFailure(index) => {
+            let Some(batch) = model.batch.as_mut() else { return Vec::new() };
+            batch.skip(index);
+            if batch.failures.is_empty() {
+                model.dismiss_batch();
+                return vec![Effect::App(AppMessage::SwitchToExploreHandlerKeepQuery)];
+            }
+        },
+        Msg::DismissBatch => model.dismiss_batch(),
+        Msg::ToggleJobPause(kind) => model.toggle_job_pause(kind),
+        Msg::CancelPaste => model.cancel_pending_paste(),
+    }
+    Vec::new()
+}
+
+/// Apply an explore mode action; actions of other modes leave the model unchanged
+fn perform(model: &mut AppModel, action: Action) -> Vec<Effect> {
+    let message = |message| vec![Effect::App(message)];
+    // In visual mode the keys that would leave the directory or open an entry mark the range instead
+    if model.visual_anchor.is_some() && matches!(action, Action::NavOpen | Action::NavParent | Action::MarksVisual) {
+        let count = model.end_visual();
+        model.set_status(format!("Marked {} entries", count));
+        return Vec::new();
+    }
+    match action {
+        // Navigation
+        Action::NavDown => model.move_selection_down(),
+        Action::NavUp => model.move_selection_up(),
+
+        // Directory navigation
+        Action::NavParent => return vec![Effect::GoToParent],
+
+        // Smart selection: directory navigation or file opening
+        Action::NavOpen => match model.get_selected_file() {
+            Some(selected) if selected.is_directory => return vec![Effect::EnterDirectory(selected.path.clone())],
+            Some(_) => return message(AppMessage::OpenFile),
+            None => {}
+        },
+
+        // Jump to the entries a finished copy or move put in place
+        Action::NavJobDestination => return vec![Effect::GoToJobDestination],
+
+        // Decrypt .gpg/.age files into the preview
+        Action::FileDecrypt => match model.get_selected_file() {
+            Some(selected) if !selected.is_directory && EncryptionService::is_encrypted(&selected.path) => {
+                return message(AppMessage::DecryptPreview);
+            }
+            _ => model.set_status("Not an encrypted file"),
+        },
+
+        // Checksums for comparing against a published digest, and a fresh check against the sidecar
+        Action::FileChecksums => return vec![Effect::ComputeDigests],
+        Action::FileVerify => return vec![Effect::VerifyChecksums],
+
+        // Yank or cut entries, then paste them into another directory
+        Action::ClipboardCopy | Action::ClipboardCut => {
+            let (mode, verb) = if action == Action::ClipboardCopy { (PasteMode::Copy, "Yanked") } else { (PasteMode::Move, "Cut") };
+            match model.yank_entries(mode) {
+                0 => model.set_status("Nothing selected"),
+                1 => model.set_status(format!("{} 1 entry", verb)),
+                count => model.set_status(format!("{} {} entries", verb, count)),
+            }
+        },
+        Action::ClipboardPaste => {
+            if model.file_clipboard.is_none() {
+                model.set_status("Nothing yanked to paste");
+            } else {
+                return vec![Effect::PasteEntries];
+            }
+        },
+
+        // Save a clipboard image into the current directory
+        Action::ClipboardPasteImage => return vec![Effect::PasteClipboardImage],
+
+        // Download/drag the selected file out through the terminal (iTerm2, WezTerm)
+        Action::FileSendToTerminal => return message(AppMessage::SendToTerminal),
+
+        // Hand off to the configured file manager, falling back to the current directory when nothing is selected
+        Action::FileOpenWithFileManager => {
+            let target = model.get_selected_file()
+                .map(|file| file.path.clone())
+                .unwrap_or_else(|| model.current_dir.clone());
+            return vec![Effect::OpenWithFileManager(target)];
+        },
+        Action::DirOpenWithFileManager => return vec![Effect::OpenWithFileManager(model.current_dir.clone())],
+
+        // Refresh
+        Action::ViewRefresh => return vec![Effect::Refresh],
+
+        // Listing presentation: columns view, grouping and sorting by column
+        Action::ViewToggleDetails => model.detail_view = !model.detail_view,
+        Action::ViewCycleGrouping => return vec![Effect::CycleGrouping],
+        Action::SortBy(key) => model.sort_by(key),
+
+        // Hide gitignored clutter like target/ and node_modules/
+        Action::ViewToggleProject => return vec![Effect::ToggleProjectView],
+        Action::ViewToggleReadme => model.toggle_readme_preview(),
+
+        // Marking
+        Action::MarksToggle => model.toggle_mark(),
+        Action::MarksAll => model.mark_all(),
+        Action::MarksVisual => model.start_visual(),
+        Action::MarksInvert => model.invert_marks(),
+        Action::MarksReview => {
+            if model.marked_paths.is_empty() {
+                model.set_status("No marked files");
+            } else {
+                return message(AppMessage::SwitchToMarksHandler);
+            }
+        },
+
+        // Open history
+        Action::HistoryRecents => {
+            if model.recent_opens(1).is_empty() {
+                model.set_status("No files opened yet");
+            } else {
+                return message(AppMessage::SwitchToRecentsHandler);
+            }
+        },
+        Action::HistoryOpenLast => return vec![Effect::RevealLastOpened, Effect::App(AppMessage::OpenFile)],
+
+        // Other modes and popups
+        Action::ModeCommand => return message(AppMessage::SwitchToCommandHandler(String::new())),
+        Action::FileNew => return message(AppMessage::SwitchToCommandHandler("touch ".to_string())),
+        Action::DirNew => return message(AppMessage::SwitchToCommandHandler("mkdir ".to_string())),
+        // The path's own name, since collapsed chains are listed as `a/b/c`
+        Action::FileRename => {
+            if let Some(name) = model.get_selected_file().and_then(|selected| selected.path.file_name()) {
+                return message(AppMessage::SwitchToRenameHandler(name.to_string_lossy().to_string()));
+            }
+        },
+        Action::FileDelete | Action::FileDeletePermanently => {
+            let targets = model.delete_targets();
+            if targets.is_empty() {
+                model.set_status("Nothing selected");
+            } else {
+                let mode = if action == Action::FileDeletePermanently { DeleteMode::Permanent } else { DeleteMode::Normal };
+                return message(AppMessage::SwitchToConfirmDeleteHandler(targets, mode));
+            }
+        },
+        // Refused up front when off or pointless, so the prompt only ever asks about what will happen
+        Action::FileShred => {
+            let targets = model.delete_targets();
+            if targets.is_empty() {
+                model.set_status("Nothing selected");
+            } else {
+                return vec![Effect::ConfirmShred(targets)];
+            }
+        },
+        Action::FileSendTo => {
+            if model.delete_targets().is_empty() {
+                model.set_status("Nothing selected");
+            } else {
+                return message(AppMessage::SwitchToSendToHandler);
+            }
+        },
+        // Named after the entry, or after the directory the marked entries are in
+        Action::FileArchive => {
+            let targets = model.delete_targets();
+            let stem = match targets.as_slice() {
+                [] => {
+                    model.set_status("Nothing selected");
+                    return Vec::new();
+                },
+                [path] => path.file_stem(),
+                _ => model.current_dir.file_name(),
+            };
+            let stem = stem.map(|stem| stem.to_string_lossy().to_string()).unwrap_or_else(|| "archive".to_string());
+            return message(AppMessage::SwitchToArchiveHandler(stem));
+        },
+        Action::FileChmod => match model.get_selected_file() {
+            Some(selected) => return vec![Effect::EditPermissions(selected.path.clone())],
+            None => model.set_status("Nothing selected"),
+        },
+        Action::FileChown => {
+            if model.delete_targets().is_empty() {
+                model.set_status("Nothing selected");
+            } else {
+                return message(AppMessage::SwitchToChownHandler);
+            }
+        },
+        Action::FileHardLinks => return vec![Effect::FindHardLinks],
+        Action::FileBrowseImage => match model.get_selected_file() {
+            Some(selected) if !selected.is_directory && DiskImageKind::from_path(&selected.path) == Some(DiskImageKind::Iso) => {
+                return vec![Effect::BrowseDiskImage(selected.path.clone(), Vec::new())];
+            }
+            _ => model.set_status("Not an ISO image"),
+        },
+        Action::FileUndo => return vec![Effect::UndoOperation],
+        Action::FileRedo => return vec![Effect::RedoOperation],
+        Action::ModeLinks => return message(AppMessage::SwitchToPreviewLinksHandler),
+        Action::ModeGrid => return message(AppMessage::SwitchToGridHandler),
+        Action::ModeProperties => return message(AppMessage::SwitchToPropertiesHandler),
+        Action::ModeJobs => {
+            if model.jobs().is_empty() {
+                model.set_status("No background jobs");
+            } else {
+                return message(AppMessage::SwitchToJobsHandler);
+            }
+        },
+        Action::JobCancel => model.cancel_batch(),
+        Action::ModeBookmarks => {
+            if model.bookmarks().is_empty() {
+                model.set_status("No bookmarks - m then a key bookmarks this directory");
+            } else {
+                return message(AppMessage::SwitchToBookmarksHandler);
+            }
+        },
+        Action::BookmarkSet => {
+            model.set_status(format!("Bookmark {} on which key? ESC: cancel", display_path(&model.current_dir)));
+            return message(AppMessage::SwitchToBookmarkSetHandler);
+        },
+        Action::ModeDownloads => {
+            if model.downloads_dir().is_none() {
+                model.set_status("No downloads directory - set downloads.dir in the config");
+            } else {
+                return vec![Effect::ReloadDownloads, Effect::App(AppMessage::SwitchToDownloadsHandler)];
+            }
+        },
+        Action::ModeSearch => return message(AppMessage::SwitchToSearchHandler),
+        Action::ModeFuzzyFind => return message(AppMessage::SwitchToFuzzyFindHandler),
+        Action::AppQuit => return message(AppMessage::Quit),
+
+        _ => {}
+    }
+    Vec::new()
+}
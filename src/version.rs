@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+use crate::config::Config;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const GIT_HASH: &str = env!("CLAZYFILER_GIT_HASH");
+pub const TARGET: &str = env!("CLAZYFILER_TARGET");
+pub const PROFILE: &str = env!("CLAZYFILER_PROFILE");
+
+/// Platform-dependent features and whether this build has them
+pub const FEATURES: &[(&str, bool)] = &[
+    ("inotify", cfg!(target_os = "linux")),          // Fuzzy index patched from file system events
+    ("job-control", cfg!(unix)),                     // Ctrl-Z, SIGTERM and SIGHUP handling
+    ("single-instance", cfg!(unix)),                 // Reveal forwarding over a Unix socket
+];
+
+/// Names of the features this build has
+pub fn enabled_features() -> Vec<&'static str> {
+    FEATURES.iter().filter(|(_, enabled)| *enabled).map(|(name, _)| *name).collect()
+}
+
+/// One line for `--version`, e.g. "clazyfiler 0.1.0 (3f2a9c1d0b4e, x86_64-unknown-linux-gnu, release)"
+pub fn version_line() -> String {
+    format!("clazyfiler {} ({}, {}, {})", VERSION, GIT_HASH, TARGET, PROFILE)
+}
+
+/// Build metadata and the paths the program uses, as label/value pairs for `--about` and `:version`
+pub fn about() -> Vec<(&'static str, String)> {
+    let path = |path: Option<PathBuf>| path.map(|path| path.display().to_string()).unwrap_or_else(|| "-".to_string());
+    let config_path = Config::config_path();
+    let config_note = match &config_path {
+        Some(path) if !path.exists() => " (not found, using defaults)",
+        _ => "",
+    };
+    let features = enabled_features();
+    vec![
+        ("Version", VERSION.to_string()),
+        ("Git commit", GIT_HASH.to_string()),
+        ("Target", TARGET.to_string()),
+        ("Build", PROFILE.to_string()),
+        ("Features", if features.is_empty() { "none".to_string() } else { features.join(", ") }),
+        ("Config", format!("{}{}", path(config_path), config_note)),
+        ("Cache", path(Config::cache_dir())),
+        ("Data", path(Config::data_dir())),
+        ("Socket", path(Config::instance_socket_path())),
+    ]
+}
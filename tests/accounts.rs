@@ -0,0 +1,13 @@
+//! Users and groups for the owner picker, read from passwd and group listings
+
+use clazyfiler::services::account_service::{parse_accounts, Account};
+
+#[test]
+fn passwd_and_group_lines_give_names_and_ids_sorted() {
+    let passwd = "# comment\nroot:x:0:0:root:/root:/bin/bash\nwww-data:x:33:33::/var/www:/usr/sbin/nologin\n+nisuser::::::\nalice:x:1000:1000:Alice:/home/alice:/bin/zsh\nbroken:x:notanumber:1\nalice:x:1000:1000:dup:/home/alice:/bin/zsh\n";
+    let account = |name: &str, id| Account { name: name.to_string(), id };
+    assert_eq!(parse_accounts(passwd), vec![account("alice", 1000), account("root", 0), account("www-data", 33)]);
+
+    let group = "wheel:x:10:alice\nstaff:*:50:\n";
+    assert_eq!(parse_accounts(group), vec![account("staff", 50), account("wheel", 10)]);
+}
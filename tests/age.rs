@@ -0,0 +1,22 @@
+//! Ages typed for `:older`, which finds files not modified for that long
+
+use std::time::Duration;
+use clazyfiler::core::parse_age;
+
+#[test]
+fn ages_take_a_unit_and_default_to_days() {
+    assert_eq!(parse_age("30d"), Some(Duration::from_secs(30 * 86_400)));
+    assert_eq!(parse_age("30"), Some(Duration::from_secs(30 * 86_400)));
+    assert_eq!(parse_age("12h"), Some(Duration::from_secs(12 * 3600)));
+    assert_eq!(parse_age("2w"), Some(Duration::from_secs(14 * 86_400)));
+    assert_eq!(parse_age("90m"), Some(Duration::from_secs(90 * 60)));
+    assert_eq!(parse_age(" 5s "), Some(Duration::from_secs(5)));
+}
+
+#[test]
+fn ages_without_a_number_or_with_an_unknown_unit_are_refused() {
+    assert_eq!(parse_age(""), None);
+    assert_eq!(parse_age("d"), None);
+    assert_eq!(parse_age("3y"), None);
+    assert_eq!(parse_age("-3d"), None);
+}
@@ -0,0 +1,67 @@
+//! Archives packed from real directory trees read back with what went in
+
+mod common;
+
+use std::fs;
+use std::io::Read;
+use clazyfiler::core::{IoThrottle, JobControl, ZipArchive};
+use clazyfiler::services::ArchiveFormat;
+use clazyfiler::services::archive_service::write_archive;
+use common::TempTree;
+
+/// Two files, one of them two directories deep
+fn tree(test: &str) -> TempTree {
+    let tree = TempTree::new(test);
+    tree.file("notes.txt", "hello archive");
+    tree.file("docs/nested/deep.txt", "x".repeat(10_000));
+    tree
+}
+
+#[test]
+fn zip_holds_files_and_directories() {
+    let tree = tree("archive-zip");
+    let root = &tree.root;
+    let control = JobControl::new();
+    let sources = vec![root.join("notes.txt"), root.join("docs")];
+    write_archive(&sources, &root.join("out.zip"), ArchiveFormat::Zip, &control, &IoThrottle::unlimited()).unwrap();
+    assert_eq!(control.bytes_done(), 13 + 10_000);
+
+    let data = fs::read(root.join("out.zip")).unwrap();
+    let archive = ZipArchive::parse(&data).unwrap();
+    let names: Vec<&str> = archive.entries.iter().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(names, ["notes.txt", "docs/", "docs/nested/", "docs/nested/deep.txt"]);
+    assert_eq!(archive.read_text("notes.txt").as_deref(), Some("hello archive"));
+    assert_eq!(archive.read_text("docs/nested/deep.txt").unwrap().len(), 10_000);
+
+    // The archive is never overwritten
+    assert!(write_archive(&sources, &root.join("out.zip"), ArchiveFormat::Zip, &control, &IoThrottle::unlimited()).is_err());
+}
+
+#[test]
+fn tar_gz_holds_files_in_blocks() {
+    let tree = tree("archive-tar");
+    let root = &tree.root;
+    let control = JobControl::new();
+    let long_name = "n".repeat(120);
+    fs::write(root.join(&long_name), "long").unwrap();
+    let sources = vec![root.join("notes.txt"), root.join(&long_name)];
+    write_archive(&sources, &root.join("out.tar.gz"), ArchiveFormat::TarGz, &control, &IoThrottle::unlimited()).unwrap();
+
+    let mut tar = Vec::new();
+    flate2::read::GzDecoder::new(fs::File::open(root.join("out.tar.gz")).unwrap()).read_to_end(&mut tar).unwrap();
+    assert_eq!(tar.len() % 512, 0);
+    assert_eq!(&tar[..9], b"notes.txt");
+    assert_eq!(&tar[257..262], b"ustar");
+    assert_eq!(&tar[512..525], b"hello archive");
+    // The long name goes in a record of its own before the header
+    assert_eq!(tar[1024 + 156], b'L');
+    assert_eq!(&tar[1536..1656], long_name.as_bytes());
+}
+
+#[test]
+fn format_follows_the_name() {
+    assert_eq!(ArchiveFormat::from_name("backup.ZIP"), Some(ArchiveFormat::Zip));
+    assert_eq!(ArchiveFormat::from_name("backup.tgz"), Some(ArchiveFormat::TarGz));
+    assert_eq!(ArchiveFormat::from_name("backup.tar.gz"), Some(ArchiveFormat::TarGz));
+    assert_eq!(ArchiveFormat::from_name("backup.rar"), None);
+}
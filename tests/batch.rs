@@ -0,0 +1,154 @@
+//! Bulk operations keep going past failures, can retry or skip them, and can be paused
+
+mod common;
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use clazyfiler::batch::{copy_step, Batch};
+use clazyfiler::core::{ClazyfilerError, Result};
+use clazyfiler::services::FileService;
+use common::TempTree;
+
+/// Fails for entries whose name starts with "locked" while the destination is named "busy"
+fn copy_unless_locked(_: &FileService, path: &Path, destination: &Path) -> Result<PathBuf> {
+    let locked = path.file_name().is_some_and(|name| name.to_string_lossy().starts_with("locked"));
+    if locked && destination.ends_with("busy") {
+        return Err(ClazyfilerError::content(path.to_string_lossy().as_ref(), "in use"));
+    }
+    Ok(destination.join(path))
+}
+
+/// Takes a while per entry, so a cancel lands halfway through
+fn slow_step(_: &FileService, path: &Path, destination: &Path) -> Result<PathBuf> {
+    std::thread::sleep(Duration::from_millis(30));
+    Ok(destination.join(path))
+}
+
+fn paths(names: &[&str]) -> Vec<PathBuf> {
+    names.iter().map(PathBuf::from).collect()
+}
+
+/// Collect results until the worker is done with every entry
+fn finish(batch: &mut Batch, service: &FileService) {
+    while batch.is_running() {
+        batch.run_for(service, Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn failures_are_collected_without_stopping_the_batch() {
+    let service = FileService::new();
+    let mut batch = Batch::new("Copy", paths(&["a", "locked-1", "b", "locked-2", "c"]), PathBuf::from("busy"), copy_unless_locked);
+    assert!(batch.is_running());
+
+    finish(&mut batch, &service);
+    assert_eq!(batch.done, 3);
+    let failed: Vec<&Path> = batch.failures.iter().map(|failure| failure.path.as_path()).collect();
+    assert_eq!(failed, [Path::new("locked-1"), Path::new("locked-2")]);
+    assert!(batch.failures[0].error.contains("in use"));
+    assert_eq!(batch.progress(), "Copy 5/5, 2 failed");
+    assert_eq!(batch.ratio(), 1.0);
+    let completed: Vec<&Path> = batch.completed.iter().map(|(_, result)| result.as_path()).collect();
+    assert_eq!(completed, [Path::new("busy/a"), Path::new("busy/b"), Path::new("busy/c")]);
+}
+
+#[test]
+fn paused_batch_holds_until_resumed() {
+    let service = FileService::new();
+    let mut batch = Batch::new("Copy", paths(&["a", "b"]), PathBuf::from("dest"), copy_unless_locked);
+    batch.set_paused(true);
+    batch.run_for(&service, Duration::from_millis(200));
+    assert!(batch.is_running());
+    assert_eq!(batch.done, 0);
+    assert_eq!(batch.progress(), "Copy 0/2, paused");
+    assert_eq!(batch.ratio(), 0.0);
+
+    batch.set_paused(false);
+    finish(&mut batch, &service);
+    assert_eq!(batch.done, 2);
+    assert_eq!(batch.progress(), "Copy 2/2");
+}
+
+#[test]
+fn retry_queues_failures_again_and_skip_drops_one() {
+    let service = FileService::new();
+    let mut batch = Batch::new("Move", paths(&["locked-1", "locked-2", "locked-3"]), PathBuf::from("busy"), copy_unless_locked);
+    finish(&mut batch, &service);
+    assert_eq!(batch.failures.len(), 3);
+
+    batch.skip(1);
+    batch.skip(7);
+    assert_eq!(batch.failures.len(), 2);
+    assert_eq!(batch.progress(), "Move 3/3, 2 failed, 1 skipped");
+
+    // Still locked: the retried entries fail again
+    batch.retry();
+    assert!(batch.is_running() && batch.failures.is_empty());
+    finish(&mut batch, &service);
+    assert_eq!(batch.failures.len(), 2);
+
+    // Unlocked by now
+    batch.destination = PathBuf::from("free");
+    batch.retry();
+    finish(&mut batch, &service);
+    assert!(batch.failures.is_empty());
+    assert_eq!(batch.done, 2);
+    assert_eq!(batch.progress(), "Move 3/3, 1 skipped");
+}
+
+#[test]
+fn cancel_skips_what_is_left() {
+    let service = FileService::new();
+    let mut batch = Batch::new("Copy", paths(&["a", "b", "c", "d", "e", "f"]), PathBuf::from("dest"), slow_step);
+    batch.run_for(&service, Duration::from_millis(40));
+    batch.cancel();
+    finish(&mut batch, &service);
+    assert!(batch.done < 6);
+    assert!(batch.failures.is_empty());
+    assert_eq!(batch.done + batch.skipped, 6);
+    assert!(batch.progress().ends_with(", cancelled"));
+}
+
+#[test]
+fn copies_count_their_data() {
+    let tree = TempTree::new("batch-bytes");
+    let root = tree.root.clone();
+    tree.dir("dest");
+    tree.file("source/big.bin", vec![7u8; 600 * 1024]);
+    tree.file("source/tree/small.txt", "12345");
+
+    let service = FileService::new();
+    let sources = vec![root.join("source/big.bin"), root.join("source/tree")];
+    let mut batch = Batch::new("Copy", sources, root.join("dest"), copy_step).measuring_bytes();
+    finish(&mut batch, &service);
+    assert_eq!(batch.done, 2);
+    let transfer = batch.transfer().unwrap();
+    assert_eq!((transfer.done, transfer.total), (600 * 1024 + 5, 600 * 1024 + 5));
+    assert_eq!(transfer.ratio(), 1.0);
+    assert!(root.join("dest/tree/small.txt").exists());
+
+    // Without measuring there is no byte progress to show
+    let unmeasured = Batch::new("Copy", paths(&["a"]), PathBuf::from("dest"), copy_unless_locked);
+    assert!(unmeasured.transfer().is_none());
+}
+
+#[test]
+fn replacing_entries_take_the_existing_name() {
+    let tree = TempTree::new("batch-replace");
+    let root = tree.root.clone();
+    for name in ["kept.txt", "replaced.txt"] {
+        tree.file(&format!("source/{}", name), "new");
+        tree.file(&format!("dest/{}", name), "old");
+    }
+
+    let service = FileService::new();
+    let sources = vec![root.join("source/kept.txt"), root.join("source/replaced.txt")];
+    let replace = [root.join("source/replaced.txt")].into_iter().collect();
+    let mut batch = Batch::new("Copy", sources, root.join("dest"), copy_step).replacing(replace, None);
+    finish(&mut batch, &service);
+    assert_eq!(batch.done, 2);
+    assert_eq!(std::fs::read_to_string(root.join("dest/replaced.txt")).unwrap(), "new");
+    // Not replacing keeps both, the copy under a numbered name
+    assert_eq!(std::fs::read_to_string(root.join("dest/kept.txt")).unwrap(), "old");
+    assert_eq!(std::fs::read_dir(root.join("dest")).unwrap().count(), 3);
+}
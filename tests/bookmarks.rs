@@ -0,0 +1,34 @@
+//! Bookmarks set while browsing survive a restart, keyed ones replacing what was on their key
+
+mod common;
+
+use std::path::{Path, PathBuf};
+use clazyfiler::services::BookmarkService;
+use common::TempTree;
+
+#[test]
+fn bookmarks_persist_across_reloads() {
+    let tree = TempTree::new("bookmarks");
+    let dir = tree.root.clone();
+    let mut service = BookmarkService::new(Some(dir.clone()));
+    assert!(service.bookmarks().is_empty());
+
+    service.set('d', Path::new("/home/me/Documents")).unwrap();
+    service.add("notes", Path::new("/home/me/notes")).unwrap();
+    service.set('d', Path::new("/home/me/Downloads")).unwrap();
+    assert!(service.set(' ', Path::new("/tmp")).is_err());
+    // The popup closes on q and starts moving on v before looking for a bookmark
+    assert!(service.set('q', Path::new("/tmp")).is_err());
+    assert!(service.set('v', Path::new("/tmp")).is_err());
+    assert!(service.add("bad\tname", Path::new("/tmp")).is_err());
+
+    let mut service = BookmarkService::new(Some(dir.clone()));
+    let saved: Vec<(Option<char>, &str, &Path)> = service.bookmarks().iter().map(|bookmark| (bookmark.key, bookmark.name.as_str(), bookmark.path.as_path())).collect();
+    assert_eq!(saved, vec![(None, "notes", Path::new("/home/me/notes")), (Some('d'), "Downloads", Path::new("/home/me/Downloads"))]);
+    assert_eq!(service.get('d').map(|bookmark| bookmark.path.clone()), Some(PathBuf::from("/home/me/Downloads")));
+
+    let removed = service.remove(0).unwrap();
+    assert_eq!(removed.map(|bookmark| bookmark.name), Some("notes".to_string()));
+    assert!(service.remove(5).unwrap().is_none());
+    assert_eq!(BookmarkService::new(Some(dir.clone())).bookmarks().len(), 1);
+}
@@ -0,0 +1,46 @@
+//! Renames planned from the edited list of names, checked before anything is renamed
+
+use std::path::{Path, PathBuf};
+use clazyfiler::core::{bulk_rename_line, bulk_rename_plan};
+
+fn paths(base: &Path, names: &[&str]) -> Vec<PathBuf> {
+    names.iter().map(|name| base.join(name)).collect()
+}
+
+#[test]
+fn changed_lines_become_renames() {
+    let base = Path::new("/work");
+    let sources = paths(base, &["a.txt", "b.txt", "c.txt"]);
+    let renames = bulk_rename_plan(base, &sources, "a.txt\nbee.txt\nc.md\n", |_| false).unwrap();
+    assert_eq!(renames, [
+        (base.join("b.txt"), base.join("bee.txt")),
+        (base.join("c.txt"), base.join("c.md")),
+    ]);
+    // Entries marked elsewhere are listed by their full path
+    assert_eq!(bulk_rename_line(base, Path::new("/other/d.txt")), "/other/d.txt");
+    assert_eq!(bulk_rename_line(base, &base.join("a.txt")), "a.txt");
+}
+
+#[test]
+fn swapped_names_are_not_collisions() {
+    let base = Path::new("/work");
+    let sources = paths(base, &["a", "b"]);
+    let renames = bulk_rename_plan(base, &sources, "b\na", |_| true).unwrap();
+    assert_eq!(renames.len(), 2);
+}
+
+#[test]
+fn bad_edits_are_refused() {
+    let base = Path::new("/work");
+    let sources = paths(base, &["a", "b"]);
+    let taken = |path: &Path| path.ends_with("existing");
+    // A line removed
+    assert!(bulk_rename_plan(base, &sources, "a\n", |_| false).is_err());
+    // Both entries given one name
+    assert!(bulk_rename_plan(base, &sources, "same\nsame\n", |_| false).is_err());
+    // A name of an entry that stays
+    assert!(bulk_rename_plan(base, &sources, "existing\nb\n", taken).is_err());
+    // Moved into another directory, or an empty name
+    assert!(bulk_rename_plan(base, &sources, "sub/a\nb\n", |_| false).is_err());
+    assert!(bulk_rename_plan(base, &sources, "\nb\n", |_| false).is_err());
+}
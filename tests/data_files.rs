@@ -0,0 +1,97 @@
+//! Parquet footers (Thrift) and Arrow IPC metadata (flatbuffers) are decoded natively,
+//! on background threads collected by polling when previewing a file
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use clazyfiler::services::DataFileService;
+use clazyfiler::services::data_file::{DataFormat, DataSummary};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+/// Summary of a file once the worker reading it is collected
+fn summary_of(service: &DataFileService, path: &Path) -> Result<DataSummary, String> {
+    let started = Instant::now();
+    loop {
+        if let Some(summary) = service.summary(path) {
+            return summary;
+        }
+        assert!(started.elapsed() < Duration::from_secs(10), "no summary of {}", path.display());
+        service.poll();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+fn detail<'a>(details: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    details.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str())
+}
+
+#[test]
+fn parquet_footers_give_schema_and_row_count() {
+    let data = std::fs::read(fixture("people.parquet")).unwrap();
+    let summary = DataFileService::parse(DataFormat::Parquet, &data).unwrap();
+    assert_eq!(summary.format, "Parquet");
+    assert_eq!(summary.row_count, Some(3));
+    assert_eq!(summary.schema, [
+        "id: INT64 (required)",
+        "name: BYTE_ARRAY UTF8 (optional)",
+        "price: FIXED_LEN_BYTE_ARRAY(8) DECIMAL(10,2) (optional)",
+        "address: group (optional)",
+        "│ city: BYTE_ARRAY STRING (optional)",
+    ]);
+    assert_eq!(detail(&summary.details, "Columns"), Some("4"));
+    assert_eq!(detail(&summary.details, "Row groups"), Some("1"));
+    assert_eq!(detail(&summary.details, "Created by"), Some("clazyfiler fixture"));
+}
+
+#[test]
+fn arrow_files_give_schema_and_sample_rows() {
+    let summary = summary_of(&DataFileService::new(), &fixture("people.arrow")).unwrap();
+    assert_eq!(summary.format, "Arrow IPC");
+    assert_eq!(summary.row_count, Some(3));
+    assert_eq!(summary.schema, ["id: int32 not null", "name: utf8", "flag: bool"]);
+    assert_eq!(detail(&summary.details, "Record batches"), Some("1"));
+
+    let sample = summary.sample.expect("the first record batch is decoded");
+    assert_eq!(sample.columns, ["id", "name", "flag"]);
+    assert_eq!(sample.rows, [
+        ["1", "alpha", "true"],
+        ["2", "null", "false"],
+        ["3", "gamma", "true"],
+    ]);
+}
+
+#[test]
+fn truncated_files_are_errors_rather_than_panics() {
+    for (name, format) in [("people.parquet", DataFormat::Parquet), ("people.arrow", DataFormat::Arrow)] {
+        let data = std::fs::read(fixture(name)).unwrap();
+        for len in 0..data.len() {
+            let _ = DataFileService::parse(format, &data[..len]);
+        }
+        // Cutting off the front keeps the footer magic but leaves offsets pointing nowhere
+        for start in 1..data.len() {
+            let _ = DataFileService::parse(format, &data[start..]);
+        }
+    }
+    assert!(DataFileService::parse(DataFormat::Parquet, b"PAR1").is_err());
+    assert!(DataFileService::parse(DataFormat::Arrow, b"FEA1 feather v1").is_err());
+}
+
+#[test]
+fn summaries_start_pending_and_arrive_by_polling() {
+    let service = DataFileService::new();
+    let path = fixture("people.parquet");
+    assert!(service.summary(&path).is_none());
+    assert!(service.is_reading(&path));
+
+    // Clearing drops the result still on its way; asking again starts a new read
+    service.clear_cache();
+    assert!(!service.is_reading(&path));
+    let summary = summary_of(&service, &path).unwrap();
+    assert!(!service.is_reading(&path));
+    assert_eq!(summary.row_count, Some(3));
+    // Sample rows come from duckdb when it is installed, and a note says why not otherwise
+    assert!(summary.sample.is_some() || summary.sample_note.is_some());
+    assert!(service.summary(&path).is_some());
+}
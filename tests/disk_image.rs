@@ -0,0 +1,160 @@
+//! Disk images are read without mounting: an ISO's directories and files, a QCOW2
+//! header and the partition table of a raw image
+
+mod common;
+
+use clazyfiler::services::{DiskImageKind, DiskImageService};
+use common::TempTree;
+
+/// An ISO 9660 directory record for an extent at `lba`
+fn directory_record(name: &[u8], lba: u32, size: u32, is_directory: bool) -> Vec<u8> {
+    let len = 33 + name.len() + (name.len() + 1) % 2;
+    let mut record = vec![0; len];
+    record[0] = len as u8;
+    record[2..6].copy_from_slice(&lba.to_le_bytes());
+    record[10..14].copy_from_slice(&size.to_le_bytes());
+    record[25] = if is_directory { 2 } else { 0 };
+    record[32] = name.len() as u8;
+    record[33..33 + name.len()].copy_from_slice(name);
+    record
+}
+
+/// An ISO image of volume RESCUE: the root (sector 18) holds the directory BOOT (sector 19) and README.TXT
+/// (sector 21), and BOOT holds GRUB.CFG (sector 20) and a record whose name is a path
+fn iso_image() -> Vec<u8> {
+    let mut bytes = vec![0; 22 * 2048];
+    let descriptor = &mut bytes[16 * 2048..17 * 2048];
+    descriptor[0] = 1;
+    descriptor[1..6].copy_from_slice(b"CD001");
+    descriptor[40..72].copy_from_slice(format!("{:<32}", "RESCUE").as_bytes());
+    descriptor[80..84].copy_from_slice(&22u32.to_le_bytes());
+    descriptor[128..130].copy_from_slice(&2048u16.to_le_bytes());
+    descriptor[156..190].copy_from_slice(&directory_record(&[0], 18, 2048, true));
+
+    let root = [
+        directory_record(&[0], 18, 2048, true),
+        directory_record(&[1], 18, 2048, true),
+        directory_record(b"BOOT", 19, 2048, true),
+        directory_record(b"README.TXT;1", 21, 42, false),
+    ].concat();
+    bytes[18 * 2048..18 * 2048 + root.len()].copy_from_slice(&root);
+    let boot = [
+        directory_record(&[0], 19, 2048, true),
+        directory_record(&[1], 18, 2048, true),
+        directory_record(b"GRUB.CFG;1", 20, 13, false),
+        directory_record(b"../ESCAPE;1", 20, 13, false),
+    ].concat();
+    bytes[19 * 2048..19 * 2048 + boot.len()].copy_from_slice(&boot);
+    bytes[20 * 2048..20 * 2048 + 13].copy_from_slice(b"set timeout=5");
+    bytes
+}
+
+#[test]
+fn iso_root_directory_is_listed() {
+    let tree = TempTree::new("iso");
+    let path = tree.file("rescue.iso", iso_image());
+    assert_eq!(DiskImageKind::from_path(&path), Some(DiskImageKind::Iso));
+    let info = DiskImageService::new().inspect(&path, DiskImageKind::Iso).unwrap();
+    assert_eq!(info.details[0], ("Volume", "RESCUE".to_string()));
+    let entries: Vec<(&str, u64, bool)> = info.entries.iter().map(|entry| (entry.name.as_str(), entry.size, entry.is_directory)).collect();
+    assert_eq!(entries, [("BOOT", 2048, true), ("README.TXT", 42, false)]);
+}
+
+#[test]
+fn iso_directories_are_browsed_and_files_extracted() {
+    let tree = TempTree::new("iso-browse");
+    let path = tree.file("rescue.iso", iso_image());
+    let service = DiskImageService::new();
+    let root = service.list_directory(&path, Vec::new()).unwrap();
+    assert_eq!(root.location(), "/");
+
+    let boot = service.list_directory(&path, vec![root.entries[0].clone()]).unwrap();
+    assert_eq!(boot.location(), "/BOOT");
+    let names: Vec<&str> = boot.entries.iter().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(names, ["GRUB.CFG", "../ESCAPE"]);
+
+    let out = tree.dir("out");
+    service.extract(&path, &boot.entries[0], &out.join("GRUB.CFG")).unwrap();
+    assert_eq!(std::fs::read(out.join("GRUB.CFG")).unwrap(), b"set timeout=5");
+    // Never over an existing file, and never a directory or a name that is a path
+    assert!(service.extract(&path, &boot.entries[0], &out.join("GRUB.CFG")).is_err());
+    assert!(service.extract(&path, &root.entries[0], &out.join("BOOT")).is_err());
+    assert!(service.extract(&path, &boot.entries[1], &out.join("ESCAPE")).is_err());
+    assert_eq!(std::fs::read_dir(&out).unwrap().count(), 1);
+
+    // An extent past the end of the image leaves nothing behind
+    let mut truncated = boot.entries[0].clone();
+    truncated.offset = 22 * 2048 - 5;
+    assert!(service.extract(&path, &truncated, &out.join("SHORT")).is_err());
+    assert!(!out.join("SHORT").exists());
+}
+
+#[test]
+fn qcow2_header_and_mbr_partitions_are_read() {
+    let mut header = vec![0; 512];
+    header[0..4].copy_from_slice(b"QFI\xfb");
+    header[4..8].copy_from_slice(&3u32.to_be_bytes());
+    header[20..24].copy_from_slice(&16u32.to_be_bytes());
+    header[24..32].copy_from_slice(&(1u64 << 30).to_be_bytes());
+    let tree = TempTree::new("disk-images");
+    let path = tree.file("vm.qcow2", &header);
+    let info = DiskImageService::new().inspect(&path, DiskImageKind::Qcow2).unwrap();
+    assert!(info.details.contains(&("Version", "3".to_string())));
+    assert!(info.details.contains(&("Virtual size", "1.0 GB".to_string())));
+
+    let mut mbr = vec![0; 512];
+    mbr[446 + 4] = 0x83;
+    mbr[446 + 12..446 + 16].copy_from_slice(&2048u32.to_le_bytes());
+    mbr[510..512].copy_from_slice(&[0x55, 0xAA]);
+    let path = tree.file("disk.img", &mbr);
+    let info = DiskImageService::new().inspect(&path, DiskImageKind::Raw).unwrap();
+    assert_eq!(info.details, [("Partition table", "MBR".to_string())]);
+    assert_eq!(info.entries[0].name, "Partition 1 (type 0x83)");
+    assert_eq!(info.entries[0].size, 1024 * 1024);
+
+    assert!(DiskImageService::new().inspect(&path, DiskImageKind::Iso).is_err());
+}
+
+/// A raw image with a protective MBR and a GPT header pointing at `entries_lba`, `count` entries of `entry_size`
+fn gpt_image(entries_lba: u64, count: u32, entry_size: u32) -> Vec<u8> {
+    let mut bytes = vec![0; 34 * 512];
+    bytes[446 + 4] = 0xEE;
+    bytes[510..512].copy_from_slice(&[0x55, 0xAA]);
+    let header = &mut bytes[512..1024];
+    header[0..8].copy_from_slice(b"EFI PART");
+    header[72..80].copy_from_slice(&entries_lba.to_le_bytes());
+    header[80..84].copy_from_slice(&count.to_le_bytes());
+    header[84..88].copy_from_slice(&entry_size.to_le_bytes());
+    bytes
+}
+
+#[test]
+fn gpt_partitions_are_read() {
+    let mut bytes = gpt_image(2, 128, 128);
+    let entry = &mut bytes[1024..1152];
+    entry[0] = 1;
+    entry[32..40].copy_from_slice(&2048u64.to_le_bytes());
+    entry[40..48].copy_from_slice(&4095u64.to_le_bytes());
+    let name: Vec<u8> = "EFI".encode_utf16().flat_map(u16::to_le_bytes).collect();
+    entry[56..56 + name.len()].copy_from_slice(&name);
+
+    let info = DiskImageService::parse(DiskImageKind::Raw, &bytes).unwrap();
+    assert_eq!(info.details, [("Partition table", "GPT".to_string())]);
+    assert_eq!(info.entries.len(), 1);
+    assert_eq!((info.entries[0].name.as_str(), info.entries[0].size), ("EFI", 1024 * 1024));
+}
+
+#[test]
+fn gpt_headers_with_impossible_sizes_are_rejected() {
+    for (entries_lba, entry_size) in [(2, 0x0100_0001), (2, 100), (2, 200), (2, 8192), (u64::MAX, 128)] {
+        let bytes = gpt_image(entries_lba, 128, entry_size);
+        assert!(DiskImageService::parse(DiskImageKind::Raw, &bytes).is_err(), "{} {}", entries_lba, entry_size);
+    }
+
+    // An entry ending before it starts, or spanning the whole disk, is reported without overflowing
+    let mut bytes = gpt_image(2, 1, 128);
+    bytes[1024] = 1;
+    bytes[1024 + 40..1024 + 48].copy_from_slice(&u64::MAX.to_le_bytes());
+    let info = DiskImageService::parse(DiskImageKind::Raw, &bytes).unwrap();
+    assert_eq!(info.entries[0].size, u64::MAX);
+}
@@ -0,0 +1,26 @@
+//! Connections are only mounted when nothing is mounted at their path yet
+
+mod common;
+
+use std::path::Path;
+use clazyfiler::services::DiskService;
+use common::TempTree;
+
+#[cfg(target_os = "linux")]
+#[test]
+fn mount_points_are_told_from_plain_directories() {
+    let service = DiskService::new();
+    assert!(service.is_mount_point(Path::new("/")));
+    let tree = TempTree::new("plain-directory");
+    assert!(!service.is_mount_point(&tree.root));
+}
+
+#[test]
+fn failing_mount_commands_are_reported() {
+    let service = DiskService::new();
+    let tree = TempTree::new("mount");
+    let path = tree.path("remote");
+    assert!(service.mount("", &path).is_err());
+    assert!(service.mount("clazyfiler-no-such-mount-tool {path}", &path).is_err());
+    assert!(path.is_dir());
+}
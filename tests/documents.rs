@@ -0,0 +1,59 @@
+//! Office documents are previewed by pulling the text out of their zipped XML
+
+use std::path::{Path, PathBuf};
+use clazyfiler::services::DocumentService;
+use clazyfiler::services::document_service::DocumentKind;
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+fn parse(name: &str, kind: DocumentKind) -> String {
+    DocumentService::parse(kind, &std::fs::read(fixture(name)).unwrap()).unwrap()
+}
+
+#[test]
+fn word_paragraphs_tabs_and_breaks_become_lines() {
+    // Field codes are skipped and runs of empty paragraphs collapse to one blank line
+    assert_eq!(
+        DocumentService::new().extract_text(&fixture("report.docx")).unwrap(),
+        "Quarterly report\nRevenue & costs\nName\tTotal\nNext line\n\nEnd",
+    );
+}
+
+#[test]
+fn excel_sheets_follow_the_workbook_with_shared_strings_resolved() {
+    // Sheet files come from the relationships, so "Summary" is read first though it is rId2;
+    // the phonetic reading of 東京 is left out and the empty B1 keeps later cells in their column
+    assert_eq!(
+        parse("budget.xlsx", DocumentKind::Excel),
+        "── Summary ──\nItem |  | Total\n東京 | TRUE | 42.5\n\n── Raw data ──\ninline",
+    );
+}
+
+#[test]
+fn powerpoint_slides_are_in_numeric_order() {
+    assert_eq!(
+        parse("deck.pptx", DocumentKind::PowerPoint),
+        "── Slide 1 ──\nWelcome\n\n── Slide 2 ──\nAgenda\nNumbers\n\n── Slide 10 ──\nQuestions?",
+    );
+}
+
+#[test]
+fn open_document_spaces_and_line_breaks_are_expanded() {
+    assert_eq!(
+        parse("notes.odt", DocumentKind::OpenDocumentText),
+        "Meeting notes\na   b\tc\nfirst\nsecond\n\nstyled text",
+    );
+}
+
+#[test]
+fn missing_parts_and_damaged_archives_are_errors() {
+    let workbook = std::fs::read(fixture("budget.xlsx")).unwrap();
+    assert!(DocumentService::parse(DocumentKind::Word, &workbook).unwrap_err().to_string().contains("word/document.xml"));
+    assert!(DocumentService::parse(DocumentKind::Word, b"PK\x03\x04 not really").is_err());
+
+    for len in 0..workbook.len() {
+        let _ = DocumentService::parse(DocumentKind::Excel, &workbook[..len]);
+    }
+}
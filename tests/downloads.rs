@@ -0,0 +1,41 @@
+//! The downloads panel lists the newest entries first and counts arrivals since it was last open
+
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use clazyfiler::services::DownloadsService;
+use common::TempTree;
+
+#[test]
+fn arrivals_are_new_until_seen() {
+    let tree = TempTree::new("downloads");
+    let dir = tree.root.clone();
+    fs::write(dir.join("old.pdf"), "old").unwrap();
+    fs::write(dir.join(".hidden"), "").unwrap();
+    let mut service = DownloadsService::new(Some(dir.clone()));
+    assert_eq!(service.dir(), Some(dir.as_path()));
+    assert_eq!(service.new_count(), 0);
+
+    std::thread::sleep(Duration::from_millis(20));
+    fs::write(dir.join("report.pdf"), "new").unwrap();
+    fs::write(dir.join("movie.mkv.part"), "partial").unwrap();
+    service.reload();
+    let names: Vec<String> = service.entries().iter().map(|download| download.path.file_name().unwrap().to_string_lossy().to_string()).collect();
+    assert_eq!(names.len(), 3);
+    assert_eq!(names.last().map(String::as_str), Some("old.pdf"));
+    // Still coming in: listed, but not new yet
+    assert!(service.entries().iter().any(|download| download.in_progress && download.path.ends_with("movie.mkv.part")));
+    assert_eq!(service.new_count(), 1);
+
+    service.mark_seen();
+    assert_eq!(service.new_count(), 0);
+}
+
+#[test]
+fn missing_directory_is_not_watched() {
+    let service = DownloadsService::new(Some(PathBuf::from("/nonexistent/clazyfiler-downloads")));
+    assert!(service.dir().is_none());
+    assert!(service.entries().is_empty());
+}
@@ -0,0 +1,736 @@
+//! FileService against real directory trees built in the system temp directory
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use clazyfiler::core::{CreationMode, JobControl, DEFAULT_DIR_MODE, DEFAULT_FILE_MODE};
+use clazyfiler::model::FileEntry;
+use clazyfiler::services::{file_service::{Leftover, LeftoverKind}, FileService};
+use common::TempTree;
+
+fn names(files: &[FileEntry]) -> Vec<&str> {
+    files.iter().map(|file| file.name.as_str()).collect()
+}
+
+/// Scanned paths relative to the root, with `/` separators on every platform, sorted
+fn relative_paths(root: &Path, files: &[FileEntry]) -> Vec<String> {
+    let mut paths: Vec<String> = files
+        .iter()
+        .map(|file| {
+            let relative = file.path.strip_prefix(root).unwrap();
+            relative.components().map(|part| part.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn read_directory_lists_directories_first_then_files_by_name() {
+    let tree = TempTree::new("listing-order");
+    tree.file("b.txt", "bb");
+    tree.file("A.txt", "a");
+    tree.dir("zeta");
+    tree.dir("Alpha");
+
+    let files = FileService::new().read_directory(&tree.root).unwrap();
+    assert_eq!(names(&files), ["Alpha", "zeta", "A.txt", "b.txt"]);
+
+    let b = files.iter().find(|file| file.name == "b.txt").unwrap();
+    assert!(!b.is_directory);
+    assert_eq!(b.size, Some(2));
+    assert!(b.modified.is_some());
+    let alpha = files.iter().find(|file| file.name == "Alpha").unwrap();
+    assert!(alpha.is_directory);
+    assert_eq!(alpha.size, None);
+}
+
+#[test]
+fn directory_stats_count_children_by_kind_and_extension_until_the_directory_changes() {
+    let tree = TempTree::new("directory-stats");
+    tree.file("a.rs", "fn main() {}");
+    tree.file("b.RS", "mod b;");
+    tree.file("notes.md", "# notes");
+    tree.file("Makefile", "all:");
+    tree.file("nested/deep.rs", "not counted");
+
+    let service = FileService::new();
+    let stats = service.directory_stats(&tree.root).unwrap();
+    assert_eq!((stats.files, stats.directories), (4, 1));
+    assert_eq!(stats.total_size, 12 + 6 + 7 + 4);
+    assert_eq!(stats.extensions, vec![("rs".to_string(), 2), ("".to_string(), 1), ("md".to_string(), 1)]);
+    assert!(stats.newest.is_some());
+
+    // Adding a child changes the directory, so the cached counts are not reused
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    tree.file("c.rs", "");
+    assert_eq!(service.directory_stats(&tree.root).unwrap().files, 5);
+}
+
+#[test]
+fn read_directory_lists_only_the_top_level_of_nested_trees() {
+    let tree = TempTree::new("nested");
+    tree.file("outer/inner/deep/file.rs", "fn main() {}");
+    tree.file("outer/sibling.md", "# hi");
+
+    let service = FileService::new();
+    assert_eq!(names(&service.read_directory(&tree.root).unwrap()), ["outer"]);
+    assert_eq!(names(&service.read_directory(&tree.path("outer")).unwrap()), ["inner", "sibling.md"]);
+    let deep = service.read_directory(&tree.path("outer/inner/deep")).unwrap();
+    assert_eq!(deep[0].path, tree.path("outer/inner/deep/file.rs"));
+}
+
+#[test]
+fn read_directory_fails_for_missing_directories() {
+    let tree = TempTree::new("missing");
+    assert!(FileService::new().read_directory(&tree.path("nope")).is_err());
+}
+
+#[test]
+fn lazy_listing_knows_types_but_not_sizes() {
+    let tree = TempTree::new("lazy");
+    tree.file("file.txt", "contents");
+    tree.dir("dir");
+
+    let service = FileService::new();
+    service.set_lazy_metadata(true);
+    let files = service.read_directory(&tree.root).unwrap();
+    assert_eq!(names(&files), ["dir", "file.txt"]);
+    assert!(files[0].is_directory);
+    assert!(files.iter().all(|file| file.size.is_none() && file.modified.is_none()));
+
+    service.set_lazy_metadata(false);
+    let files = service.read_directory(&tree.root).unwrap();
+    assert_eq!(files[1].size, Some(8));
+}
+
+#[test]
+fn weird_names_are_listed_and_scanned() {
+    let tree = TempTree::new("weird-names");
+    let mut expected = vec!["with space.txt", "-leading-dash", "ünïcödé 日本.md", "emoji 📁", "trailing.dot.", "#hash"];
+    // Newlines are not allowed in Windows file names
+    if cfg!(unix) {
+        expected.push("new\nline");
+    }
+    for name in &expected {
+        tree.file(&format!("dir/{}", name), "x");
+    }
+
+    let service = FileService::new();
+    let mut listed = names(&service.read_directory(&tree.path("dir")).unwrap()).into_iter().map(String::from).collect::<Vec<_>>();
+    listed.sort();
+    let mut expected: Vec<String> = expected.into_iter().map(String::from).collect();
+    expected.sort();
+    assert_eq!(listed, expected);
+
+    let scanned = service.scan_directory_tree(&tree.root).unwrap();
+    assert_eq!(scanned.len(), expected.len() + 1);
+    assert!(scanned.iter().all(|file| file.path.exists()));
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_names_are_listed_lossily_with_exact_paths() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let tree = TempTree::new("non-utf8");
+    let name = OsStr::from_bytes(b"bad\xffname");
+    // Some filesystems (e.g. macOS APFS) refuse names that are not UTF-8
+    if fs::write(tree.root.join(name), "x").is_err() {
+        return;
+    }
+
+    let files = FileService::new().read_directory(&tree.root).unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].name, "bad\u{fffd}name");
+    assert_eq!(files[0].path.file_name(), Some(name));
+    assert_eq!(files[0].size, Some(1));
+}
+
+#[test]
+fn scan_descends_into_subdirectories_but_not_skipped_ones() {
+    let tree = TempTree::new("scan");
+    tree.file("src/main.rs", "");
+    tree.file("src/core/mod.rs", "");
+    tree.file("README.md", "");
+    tree.file("target/debug/build.log", "");
+    tree.file("node_modules/pkg/index.js", "");
+    tree.file(".git/HEAD", "");
+    tree.file(".hidden/secret", "");
+
+    let files = FileService::new().scan_directory_tree(&tree.root).unwrap();
+    // Skipped directories are still listed themselves
+    assert_eq!(
+        relative_paths(&tree.root, &files),
+        [".git", ".hidden", "README.md", "node_modules", "src", "src/core", "src/core/mod.rs", "src/main.rs", "target"]
+    );
+}
+
+#[test]
+fn skipped_directories() {
+    for name in [".git", ".cache", "node_modules", "target", "build", "dist"] {
+        assert!(FileService::is_skipped_directory(name), "{}", name);
+    }
+    for name in ["src", "targets", "distro", "my.build"] {
+        assert!(!FileService::is_skipped_directory(name), "{}", name);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinks_are_listed_but_not_followed() {
+    use std::os::unix::fs::symlink;
+
+    let tree = TempTree::new("symlinks");
+    tree.file("real/file.txt", "data");
+    symlink(tree.path("real"), tree.path("link-to-dir")).unwrap();
+    symlink(tree.path("real/file.txt"), tree.path("link-to-file")).unwrap();
+    symlink(tree.path("gone"), tree.path("broken")).unwrap();
+    // A link back to the root would make a following scan loop forever
+    symlink(&tree.root, tree.path("real/loop")).unwrap();
+
+    let service = FileService::new();
+    let files = service.read_directory(&tree.root).unwrap();
+    assert_eq!(names(&files), ["real", "broken", "link-to-dir", "link-to-file"]);
+    assert!(files.iter().filter(|file| file.name != "real").all(|file| !file.is_directory));
+
+    let scanned = service.scan_directory_tree(&tree.root).unwrap();
+    assert_eq!(
+        relative_paths(&tree.root, &scanned),
+        ["broken", "link-to-dir", "link-to-file", "real", "real/file.txt", "real/loop"]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn lazy_listing_resolves_symlinked_directories() {
+    use std::os::unix::fs::symlink;
+
+    let tree = TempTree::new("lazy-symlinks");
+    tree.dir("real");
+    symlink(tree.path("real"), tree.path("link")).unwrap();
+    symlink(tree.path("gone"), tree.path("broken")).unwrap();
+
+    let service = FileService::new();
+    service.set_lazy_metadata(true);
+    let files = service.read_directory(&tree.root).unwrap();
+    assert_eq!(names(&files), ["link", "real", "broken"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn unreadable_directories_are_listed_and_skipped_by_scans() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tree = TempTree::new("unreadable");
+    tree.file("locked/inside.txt", "");
+    tree.file("open/visible.txt", "");
+    fs::set_permissions(tree.path("locked"), fs::Permissions::from_mode(0o000)).unwrap();
+    // Root reads everything regardless of permissions
+    if fs::read_dir(tree.path("locked")).is_ok() {
+        return;
+    }
+
+    let service = FileService::new();
+    assert_eq!(names(&service.read_directory(&tree.root).unwrap()), ["locked", "open"]);
+    assert!(service.read_directory(&tree.path("locked")).is_err());
+
+    let scanned = service.scan_directory_tree(&tree.root).unwrap();
+    assert_eq!(relative_paths(&tree.root, &scanned), ["locked", "open", "open/visible.txt"]);
+    let (unreadable, error) = service.unreadable_entries();
+    assert_eq!(unreadable, 1);
+    assert!(error.unwrap().contains("locked"));
+
+    service.read_directory(&tree.root).unwrap();
+    assert_eq!(service.unreadable_entries(), (0, None));
+}
+
+#[cfg(unix)]
+#[test]
+fn entries_without_metadata_are_listed_with_their_error() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tree = TempTree::new("no-metadata");
+    tree.file("listable/file.txt", "data");
+    tree.dir("listable/sub");
+    // Readable but not searchable: names can be listed, entries cannot be stat'ed
+    fs::set_permissions(tree.path("listable"), fs::Permissions::from_mode(0o444)).unwrap();
+    if fs::metadata(tree.path("listable/file.txt")).is_ok() {
+        return;
+    }
+
+    let service = FileService::new();
+    let files = service.read_directory(&tree.path("listable")).unwrap();
+    assert_eq!(names(&files), ["sub", "file.txt"]);
+    assert!(files[0].is_directory);
+    assert!(files.iter().all(|file| file.metadata_error.is_some() && file.size.is_none() && file.modified.is_none()));
+    assert_eq!(service.unreadable_entries().0, 2);
+
+    let scanned = service.scan_directory_tree(&tree.root).unwrap();
+    assert_eq!(relative_paths(&tree.root, &scanned), ["listable", "listable/file.txt", "listable/sub"]);
+    assert_eq!(service.unreadable_entries().0, 2);
+}
+
+#[test]
+fn collapse_directory_chains_points_at_the_deepest_directory() {
+    let tree = TempTree::new("chains");
+    tree.file("src/main/java/App.java", "");
+    tree.file("docs/a.md", "");
+    tree.file("docs/b.md", "");
+
+    let service = FileService::new();
+    let mut files = service.read_directory(&tree.root).unwrap();
+    service.collapse_directory_chains(&mut files, 10);
+    assert_eq!(names(&files), ["docs", "src/main/java"]);
+    assert_eq!(files[1].path, tree.path("src/main/java"));
+
+    let mut files = service.read_directory(&tree.root).unwrap();
+    service.collapse_directory_chains(&mut files, 1);
+    assert_eq!(names(&files), ["docs", "src"]);
+}
+
+#[test]
+fn write_new_file_never_overwrites() {
+    let tree = TempTree::new("write-new");
+    tree.file("clip.png", "old");
+    tree.file("clip-1.png", "old");
+
+    let service = FileService::new();
+    let written = service.write_new_file(&tree.root, "clip", "png", b"new").unwrap();
+    assert_eq!(written, tree.path("clip-2.png"));
+    assert_eq!(fs::read_to_string(&written).unwrap(), "new");
+    assert_eq!(fs::read_to_string(tree.path("clip.png")).unwrap(), "old");
+
+    let fresh = service.write_new_file(&tree.root, "other", "txt", b"").unwrap();
+    assert_eq!(fresh, tree.path("other.txt"));
+}
+
+#[test]
+fn write_new_file_fails_for_missing_directories() {
+    let tree = TempTree::new("write-missing");
+    assert!(FileService::new().write_new_file(&tree.path("nope"), "clip", "png", b"").is_err());
+}
+
+#[test]
+fn create_file_and_directory_refuse_existing_entries() {
+    let tree = TempTree::new("create");
+    let service = FileService::new();
+    let file_mode = CreationMode::resolve(None, DEFAULT_FILE_MODE);
+    let dir_mode = CreationMode::resolve(None, DEFAULT_DIR_MODE);
+
+    service.create_file(&tree.path("new.txt"), file_mode).unwrap();
+    assert!(tree.path("new.txt").is_file());
+    service.create_directory(&tree.path("new dir"), dir_mode).unwrap();
+    assert!(tree.path("new dir").is_dir());
+
+    tree.file("taken.txt", "keep");
+    assert!(service.create_file(&tree.path("taken.txt"), file_mode).is_err());
+    assert_eq!(fs::read_to_string(tree.path("taken.txt")).unwrap(), "keep");
+    assert!(service.create_directory(&tree.path("taken.txt"), dir_mode).is_err());
+    assert!(service.create_file(&tree.path("new dir"), file_mode).is_err());
+    assert!(service.create_file(&tree.path("missing/parent.txt"), file_mode).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn configured_modes_are_applied_despite_the_umask() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tree = TempTree::new("modes");
+    let service = FileService::new();
+    let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o7777;
+
+    service.create_file(&tree.path("script.sh"), CreationMode::resolve(Some(0o777), DEFAULT_FILE_MODE)).unwrap();
+    assert_eq!(mode(&tree.path("script.sh")), 0o777);
+    service.create_directory(&tree.path("private"), CreationMode::resolve(Some(0o700), DEFAULT_DIR_MODE)).unwrap();
+    assert_eq!(mode(&tree.path("private")), 0o700);
+
+    let default = CreationMode::resolve(None, DEFAULT_FILE_MODE);
+    service.create_file(&tree.path("plain.txt"), default).unwrap();
+    assert_eq!(mode(&tree.path("plain.txt")), default.mode);
+}
+
+#[test]
+fn rename_moves_entries_and_refuses_to_replace() {
+    let tree = TempTree::new("rename");
+    let service = FileService::new();
+    tree.file("a.txt", "a");
+    tree.file("b.txt", "b");
+    tree.file("dir/inner.txt", "");
+
+    service.rename(&tree.path("a.txt"), &tree.path("c.txt")).unwrap();
+    assert!(!tree.path("a.txt").exists());
+    assert_eq!(fs::read_to_string(tree.path("c.txt")).unwrap(), "a");
+
+    assert!(service.rename(&tree.path("c.txt"), &tree.path("b.txt")).is_err());
+    assert_eq!(fs::read_to_string(tree.path("b.txt")).unwrap(), "b");
+    assert_eq!(fs::read_to_string(tree.path("c.txt")).unwrap(), "a");
+    assert!(service.rename(&tree.path("c.txt"), &tree.path("dir")).is_err());
+
+    service.rename(&tree.path("dir"), &tree.path("renamed dir")).unwrap();
+    assert!(tree.path("renamed dir/inner.txt").is_file());
+    assert!(service.rename(&tree.path("gone.txt"), &tree.path("d.txt")).is_err());
+
+    service.rename(&tree.path("b.txt"), &tree.path("b.txt")).unwrap();
+    assert!(tree.path("b.txt").is_file());
+}
+
+#[test]
+fn rename_changes_only_the_case() {
+    let tree = TempTree::new("rename-case");
+    let service = FileService::new();
+    tree.file("readme.md", "docs");
+
+    service.rename(&tree.path("readme.md"), &tree.path("README.md")).unwrap();
+    // Listed under the new name on case-sensitive and case-insensitive filesystems alike
+    let files = service.read_directory(&tree.root).unwrap();
+    assert_eq!(names(&files), ["README.md"]);
+    assert_eq!(fs::read_to_string(tree.path("README.md")).unwrap(), "docs");
+}
+
+#[cfg(unix)]
+#[test]
+fn rename_refuses_to_replace_a_different_file_differing_in_case() {
+    let tree = TempTree::new("rename-case-clash");
+    tree.file("notes.txt", "lower");
+    // Only a case-sensitive filesystem can hold both
+    if fs::write(tree.path("NOTES.txt"), "upper").is_err() || FileService::new().read_directory(&tree.root).unwrap().len() != 2 {
+        return;
+    }
+
+    assert!(FileService::new().rename(&tree.path("notes.txt"), &tree.path("NOTES.txt")).is_err());
+    assert_eq!(fs::read_to_string(tree.path("NOTES.txt")).unwrap(), "upper");
+    assert_eq!(fs::read_to_string(tree.path("notes.txt")).unwrap(), "lower");
+}
+
+#[test]
+fn copy_file_copies_trees_and_renames_on_a_clash() {
+    let tree = TempTree::new("copy");
+    let service = FileService::new();
+    tree.file("src/notes.txt", "first");
+    tree.file("src/sub/deep.txt", "deep");
+    tree.file("dest/notes.txt", "taken");
+    let dest = tree.path("dest");
+
+    assert_eq!(service.copy_file(&tree.path("src/notes.txt"), &dest).unwrap(), tree.path("dest/notes-1.txt"));
+    assert_eq!(service.copy_file(&tree.path("src/notes.txt"), &dest).unwrap(), tree.path("dest/notes-2.txt"));
+    assert_eq!(fs::read_to_string(tree.path("dest/notes.txt")).unwrap(), "taken");
+    assert_eq!(fs::read_to_string(tree.path("dest/notes-2.txt")).unwrap(), "first");
+
+    assert_eq!(service.copy_file(&tree.path("src"), &dest).unwrap(), tree.path("dest/src"));
+    assert_eq!(fs::read_to_string(tree.path("dest/src/sub/deep.txt")).unwrap(), "deep");
+    assert!(tree.path("src/sub/deep.txt").is_file());
+
+    // Into itself it would never end
+    assert!(service.copy_file(&tree.path("src"), &tree.path("src/sub")).is_err());
+    assert!(!tree.path("src/sub/src").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn copy_file_keeps_symlinks_and_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+    let tree = TempTree::new("copy-links");
+    let service = FileService::new();
+    let script = tree.file("src/run.sh", "#!/bin/sh");
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o750)).unwrap();
+    std::os::unix::fs::symlink("run.sh", tree.path("src/link")).unwrap();
+
+    service.copy_file(&tree.path("src"), &tree.dir("dest")).unwrap();
+    assert_eq!(fs::read_link(tree.path("dest/src/link")).unwrap(), Path::new("run.sh"));
+    let mode = fs::metadata(tree.path("dest/src/run.sh")).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o750);
+}
+
+#[test]
+fn move_file_moves_and_renames_on_a_clash() {
+    let tree = TempTree::new("move");
+    let service = FileService::new();
+    let from = tree.file("a/report.txt", "new");
+    tree.file("b/report.txt", "old");
+
+    assert_eq!(service.move_file(&from, &tree.path("b")).unwrap(), tree.path("b/report-1.txt"));
+    assert!(!from.exists());
+    assert_eq!(fs::read_to_string(tree.path("b/report.txt")).unwrap(), "old");
+    assert_eq!(fs::read_to_string(tree.path("b/report-1.txt")).unwrap(), "new");
+
+    // Pasting where it already is leaves it alone
+    assert_eq!(service.move_file(&tree.path("b/report.txt"), &tree.path("b")).unwrap(), tree.path("b/report.txt"));
+    assert!(service.move_file(&tree.path("a"), &tree.path("a")).is_err());
+}
+
+#[test]
+fn delete_entry_removes_files_and_whole_directories() {
+    let tree = TempTree::new("delete");
+    let service = FileService::new();
+    let file = tree.file("notes.txt", "bye");
+    tree.file("dir/sub/deep.txt", "deep");
+
+    service.delete_entry(&file).unwrap();
+    service.delete_entry(&tree.path("dir")).unwrap();
+    assert!(service.read_directory(&tree.root).unwrap().is_empty());
+    assert!(service.delete_entry(&file).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn delete_entry_removes_a_symlink_without_following_it() {
+    let tree = TempTree::new("delete-link");
+    let service = FileService::new();
+    tree.file("real/keep.txt", "kept");
+    std::os::unix::fs::symlink(tree.path("real"), tree.path("link")).unwrap();
+
+    service.delete_entry(&tree.path("link")).unwrap();
+    assert!(fs::symlink_metadata(tree.path("link")).is_err());
+    assert_eq!(fs::read_to_string(tree.path("real/keep.txt")).unwrap(), "kept");
+}
+
+#[cfg(unix)]
+#[test]
+fn trash_entry_moves_into_the_trash_with_its_origin_recorded() {
+    let tree = TempTree::new("trash");
+    let service = FileService::new();
+    let trash = tree.path("share/Trash");
+    let first = tree.file("work/my notes.txt", "first");
+    let trashed = service.trash_entry(&first, &trash).unwrap();
+    assert_eq!(trashed, trash.join("files/my notes.txt"));
+    assert_eq!(fs::read_to_string(&trashed).unwrap(), "first");
+    assert!(fs::symlink_metadata(&first).is_err());
+
+    let info = fs::read_to_string(trash.join("info/my notes.txt.trashinfo")).unwrap();
+    let expected_path = format!("Path={}/work/my%20notes.txt\n", tree.root.to_string_lossy());
+    assert!(info.starts_with("[Trash Info]\n"), "{}", info);
+    assert!(info.contains(&expected_path), "{}", info);
+    assert!(info.contains("DeletionDate="), "{}", info);
+
+    // A second entry of the same name is kept alongside under a numbered name
+    let second = tree.file("work/my notes.txt", "second");
+    tree.file("work/dir/inside.txt", "inside");
+    assert_eq!(service.trash_entry(&second, &trash).unwrap(), trash.join("files/my notes-1.txt"));
+    assert!(trash.join("info/my notes-1.txt.trashinfo").exists());
+    assert_eq!(service.trash_entry(&tree.path("work/dir"), &trash).unwrap(), trash.join("files/dir"));
+    assert!(service.read_directory(&tree.path("work")).unwrap().is_empty());
+    assert!(service.trash_entry(&second, &trash).is_err());
+}
+
+#[test]
+fn find_readme_ignores_case_and_prefers_markdown() {
+    let tree = TempTree::new("readme");
+    let service = FileService::new();
+    tree.file("plain/notes.md", "");
+    assert_eq!(service.find_readme(&tree.path("plain")), None);
+
+    tree.file("project/readme.txt", "text");
+    tree.file("project/README.md", "markdown");
+    tree.dir("project/Readme");
+    assert_eq!(service.find_readme(&tree.path("project")), Some(tree.path("project/README.md")));
+    tree.file("other/ReadMe", "bare");
+    assert_eq!(service.find_readme(&tree.path("other")), Some(tree.path("other/ReadMe")));
+}
+
+/// A copy of a resumable file cancelled after its first chunk, leaving the partial copy behind
+fn interrupted_copy(service: &FileService, source: &Path, dest: &Path) -> u64 {
+    let job = JobControl::new();
+    job.cancel();
+    assert!(service.for_job(job).copy_file(source, dest).is_err());
+    let part = FileService::partial_copy_path(&dest.join(source.file_name().unwrap()));
+    fs::metadata(part).unwrap().len()
+}
+
+#[test]
+fn large_copies_resume_from_a_partial_copy_of_the_same_source() {
+    let tree = TempTree::new("resume");
+    let service = FileService::new();
+    let source = tree.file("src/disk.img", "");
+    let length = 64 * 1024 * 1024 + 5;
+    let contents: Vec<u8> = (0..length).map(|i| (i % 251) as u8).collect();
+    fs::write(&source, &contents).unwrap();
+
+    let dest = tree.dir("dest");
+    let part = FileService::partial_copy_path(&dest.join("disk.img"));
+    assert_eq!(part, dest.join(".disk.img.clazyfiler-part"));
+    let chunk = interrupted_copy(&service, &source, &dest);
+    assert!(chunk > 0 && chunk < length);
+
+    // Interrupted again, it carries on where it stopped rather than starting over
+    assert_eq!(interrupted_copy(&service, &source, &dest), 2 * chunk);
+    let copied = service.copy_file(&source, &dest).unwrap();
+    assert_eq!(copied, dest.join("disk.img"));
+    assert!(!part.exists());
+    assert!(fs::read(&copied).unwrap() == contents);
+    assert_eq!(fs::read_dir(&dest).unwrap().count(), 1);
+}
+
+#[test]
+fn partial_copies_of_another_version_of_the_source_start_over() {
+    let tree = TempTree::new("resume-stale");
+    let service = FileService::new();
+    let source = tree.file("src/disk.img", "");
+    let length = 64 * 1024 * 1024 + 5;
+    let contents: Vec<u8> = (0..length).map(|i| (i % 251) as u8).collect();
+    fs::write(&source, &contents).unwrap();
+    let dest = tree.dir("dest");
+    let chunk = interrupted_copy(&service, &source, &dest);
+
+    // The same bytes where the copy stopped, but rewritten since: the kept data can't be trusted
+    let mut changed = contents.clone();
+    changed[..7].copy_from_slice(b"updated");
+    fs::write(&source, &changed).unwrap();
+    fs::File::options().write(true).open(&source).unwrap()
+        .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(60)).unwrap();
+    assert_eq!(interrupted_copy(&service, &source, &dest), chunk);
+    let copied = service.copy_file(&source, &dest).unwrap();
+    assert!(fs::read(&copied).unwrap() == changed);
+
+    // A part left without the identity of its source is not trusted either
+    let other = tree.dir("other");
+    let part = FileService::partial_copy_path(&other.join("disk.img"));
+    fs::write(&part, &changed[..1024 * 1024]).unwrap();
+    assert_eq!(interrupted_copy(&service, &source, &other), chunk);
+    let copied = service.copy_file(&source, &other).unwrap();
+    assert!(fs::read(&copied).unwrap() == changed);
+}
+
+#[cfg(unix)]
+#[test]
+fn listings_carry_permission_bits_and_set_permissions_changes_them() {
+    let tree = TempTree::new("chmod");
+    let service = FileService::new();
+    let script = tree.file("run.sh", "#!/bin/sh");
+    service.set_permissions(&script, 0o640).unwrap();
+    assert_eq!(service.read_directory(&tree.root).unwrap()[0].mode, Some(0o640));
+
+    service.set_permissions(&script, 0o4755).unwrap();
+    assert_eq!(service.read_directory(&tree.root).unwrap()[0].mode, Some(0o4755));
+    assert!(service.set_permissions(&tree.path("missing"), 0o644).is_err());
+}
+
+#[test]
+fn symlinks_are_created_without_replacing_and_listed_with_their_target() {
+    let tree = TempTree::new("symlink");
+    let service = FileService::new();
+    let notes = tree.file("notes.txt", "notes");
+    let links = tree.dir("links");
+    service.create_symlink(&notes, &links.join("notes.txt")).unwrap();
+    assert!(service.create_symlink(&notes, &links.join("notes.txt")).is_err());
+
+    let listed = service.read_directory(&links).unwrap();
+    assert_eq!(listed[0].link_target.as_deref(), Some(notes.as_path()));
+    let plain = service.read_directory(&tree.root).unwrap();
+    assert!(plain.iter().all(|entry| entry.link_target.is_none()));
+}
+
+#[test]
+fn shredding_removes_files_and_refuses_anything_else() {
+    let tree = TempTree::new("shred");
+    let service = FileService::new();
+    let secret = tree.file("secret.txt", "password\n".repeat(100_000));
+    let dir = tree.dir("keys");
+    service.shred_file(&secret).unwrap();
+    assert!(secret.symlink_metadata().is_err());
+    assert_eq!(fs::read_dir(&tree.root).unwrap().count(), 1);
+
+    assert!(service.shred_file(&dir).is_err());
+    assert!(dir.is_dir());
+    assert!(service.shred_file(&tree.path("missing")).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn subtrees_list_everything_and_owners_can_be_set_to_the_current_ones() {
+    use std::os::unix::fs::MetadataExt;
+    let tree = TempTree::new("chown");
+    let service = FileService::new();
+    tree.file("project/.git/HEAD", "ref");
+    tree.file("project/node_modules/x/index.js", "");
+    let top = tree.path("project");
+
+    let paths = FileService::subtree(&top);
+    assert_eq!(paths[0], top);
+    assert_eq!(paths.len(), 6);
+    assert!(paths.contains(&tree.path("project/node_modules/x/index.js")));
+
+    // Giving entries to their own owner needs no privileges
+    let metadata = fs::metadata(&top).unwrap();
+    for path in &paths {
+        service.set_owner(path, metadata.uid(), metadata.gid()).unwrap();
+    }
+    assert!(service.set_owner(&tree.path("missing"), metadata.uid(), metadata.gid()).is_err());
+}
+
+#[test]
+fn touch_bumps_the_modification_time_of_existing_entries() {
+    let tree = TempTree::new("touch");
+    let service = FileService::new();
+    let notes = tree.file("notes.txt", "notes");
+    let old = std::time::SystemTime::now() - std::time::Duration::from_secs(86_400);
+    fs::File::options().write(true).open(&notes).unwrap().set_modified(old).unwrap();
+
+    service.touch(&notes).unwrap();
+    let modified = fs::metadata(&notes).unwrap().modified().unwrap();
+    assert!(modified > old + std::time::Duration::from_secs(3600));
+    assert_eq!(fs::read_to_string(&notes).unwrap(), "notes");
+    assert!(service.touch(&tree.path("missing.txt")).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn hard_links_are_found_by_inode_anywhere_under_the_root() {
+    let tree = TempTree::new("hard-links");
+    let original = tree.file("a/original.txt", "shared");
+    tree.file("b/unrelated.txt", "shared");
+    fs::create_dir_all(tree.path("c/deeper")).unwrap();
+    fs::hard_link(&original, tree.path("c/deeper/alias.txt")).unwrap();
+    fs::hard_link(&original, tree.path("twin.txt")).unwrap();
+
+    let links = FileService::find_hard_links(&original, &tree.root).unwrap();
+    assert_eq!(links, vec![tree.path("c/deeper/alias.txt"), tree.path("twin.txt")]);
+
+    let lonely = tree.path("b/unrelated.txt");
+    assert!(FileService::find_hard_links(&lonely, &tree.root).unwrap().is_empty());
+    assert!(FileService::find_hard_links(&tree.path("a"), &tree.root).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn leftovers_are_broken_links_empty_files_and_empty_directories_but_not_markers() {
+    let tree = TempTree::new("leftovers");
+    tree.file("kept.txt", "contents");
+    tree.file("pkg/__init__.py", "");
+    tree.file("pkg/stub.py", "");
+    tree.dir("pkg/empty");
+    tree.file(".git/HEAD", "");
+    std::os::unix::fs::symlink(tree.path("gone"), tree.path("dangling")).unwrap();
+    std::os::unix::fs::symlink(tree.path("kept.txt"), tree.path("working")).unwrap();
+
+    let leftovers = FileService::new().find_leftovers(&tree.root).unwrap();
+    assert_eq!(leftovers, vec![
+        Leftover { path: tree.path("dangling"), kind: LeftoverKind::BrokenSymlink },
+        Leftover { path: tree.path("pkg/empty"), kind: LeftoverKind::EmptyDirectory },
+        Leftover { path: tree.path("pkg/stub.py"), kind: LeftoverKind::EmptyFile },
+    ]);
+}
+
+#[test]
+fn empty_directories_include_parents_of_nothing_but_empty_directories() {
+    let tree = TempTree::new("prune-empty");
+    tree.dir("a/b/c");
+    tree.dir("a/d");
+    tree.file("kept/file.txt", "contents");
+    tree.dir("kept/empty");
+    tree.dir(".git/refs/tags");
+
+    let service = FileService::new();
+    let empty = service.find_empty_directories(&tree.root);
+    assert_eq!(empty, vec![tree.path("a/b/c"), tree.path("a/b"), tree.path("a/d"), tree.path("a"), tree.path("kept/empty")]);
+
+    for dir in &empty {
+        service.remove_empty_directory(dir).unwrap();
+    }
+    assert!(!tree.path("a").exists());
+    assert!(tree.path("kept/file.txt").exists());
+    assert!(tree.path(".git/refs/tags").exists());
+    assert!(service.remove_empty_directory(&tree.path("kept")).is_err());
+}
@@ -0,0 +1,74 @@
+//! Font names and metrics are read from the sfnt tables without any font library
+
+mod common;
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use clazyfiler::services::FontService;
+use common::TempTree;
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+#[test]
+fn names_prefer_windows_english_and_skip_blank_entries() {
+    let info = FontService::new(None).info(&fixture("fixture.ttf")).unwrap();
+    assert_eq!(info.format, "TrueType");
+    // The Mac Roman and Japanese family names lose to the Windows English one,
+    // and the blank typographic subfamily falls back to the legacy style
+    assert_eq!(info.family.as_deref(), Some("Fixture Sans"));
+    assert_eq!(info.style.as_deref(), Some("Bold"));
+    assert_eq!(info.full_name.as_deref(), Some("Fixture Sans Bold"));
+    assert_eq!(info.postscript_name.as_deref(), Some("FixtureSans-Bold"));
+    assert_eq!(info.version.as_deref(), Some("Version 1.002"));
+    assert_eq!(info.designer.as_deref(), Some("Ada Designer"));
+    assert_eq!((info.weight, info.glyph_count, info.units_per_em), (Some(700), Some(42), Some(1000)));
+    assert_eq!(info.faces, 1);
+}
+
+#[test]
+fn collections_describe_their_first_face() {
+    let info = FontService::parse(&std::fs::read(fixture("fixture.ttc")).unwrap()).unwrap();
+    assert_eq!(info.format, "OpenType (CFF outlines)");
+    assert_eq!(info.family.as_deref(), Some("Fixture Sans"));
+    assert_eq!(info.faces, 2);
+}
+
+#[test]
+fn truncated_fonts_are_rejected_rather_than_panicking() {
+    for name in ["fixture.ttf", "fixture.ttc"] {
+        let data = std::fs::read(fixture(name)).unwrap();
+        for len in 0..data.len() {
+            let _ = FontService::parse(&data[..len]);
+        }
+    }
+    assert!(FontService::parse(b"not a font at all").is_none());
+    assert!(FontService::new(None).info(&fixture("people.parquet")).is_err());
+}
+
+#[test]
+fn specimens_render_in_the_background() {
+    let tree = TempTree::new("font-specimen");
+    let path = tree.file("broken.ttf", "not a font");
+    let service = FontService::new(Some(tree.dir("cache")));
+    // Nothing is there on the first request; a render only starts when hb-view or ImageMagick is installed
+    assert_eq!(service.specimen(&path), None);
+    assert_eq!(service.is_rendering(&path), service.can_render());
+
+    let started = Instant::now();
+    while service.is_rendering(&path) && started.elapsed() < Duration::from_secs(10) {
+        service.poll();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(!service.is_rendering(&path));
+}
+
+#[test]
+fn specimens_need_a_cache_directory() {
+    let tree = TempTree::new("font-no-cache");
+    let path = tree.file("font.otf", "not a font");
+    let service = FontService::new(None);
+    assert_eq!(service.specimen(&path), None);
+    assert!(!service.is_rendering(&path));
+}
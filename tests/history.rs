@@ -0,0 +1,32 @@
+//! Every open is logged, so the detail panel can tell when a file was last opened and how often
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use clazyfiler::services::{history_service::format_modified, HistoryService};
+
+#[test]
+fn opens_of_a_file_count_every_open_and_keep_the_latest() {
+    let mut service = HistoryService::new(None);
+    assert!(service.opens_of(Path::new("/notes/a.md")).is_none());
+
+    service.record_open(Path::new("/notes/a.md"));
+    service.record_open(Path::new("/notes/b.md"));
+    service.record_open(Path::new("/notes/a.md"));
+
+    let open = service.opens_of(Path::new("/notes/a.md")).unwrap();
+    assert_eq!(open.count, 2);
+    assert_eq!(service.opens_of(Path::new("/notes/b.md")).unwrap().count, 1);
+    assert_eq!(service.recent(1)[0].opened_at, open.opened_at);
+}
+
+#[test]
+fn modification_times_read_as_ages_until_they_are_a_month_old() {
+    let now = SystemTime::now();
+    assert_eq!(format_modified(now - Duration::from_secs(3 * 3600 + 60)), "3h ago");
+    assert_eq!(format_modified(now - Duration::from_secs(2 * 86_400 + 60)), "2d ago");
+
+    let old = now - Duration::from_secs(90 * 86_400);
+    let date = format_modified(old);
+    assert_eq!(date.len(), "2024-03-01".len());
+    assert!(!date.ends_with("ago"));
+}
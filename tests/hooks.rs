@@ -0,0 +1,38 @@
+//! Hooks fill in their placeholders per argument and report failures without blocking
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+use clazyfiler::config::Hooks;
+use clazyfiler::services::{hook_service::expand, HookEvent, HookService};
+
+#[test]
+fn placeholders_expand_and_names_with_spaces_stay_one_argument() {
+    let args = expand("git mv {old} {path}", Path::new("/repo/my notes.md"), Some(Path::new("/repo/notes.md")));
+    assert_eq!(args, ["git", "mv", "/repo/notes.md", "/repo/my notes.md"]);
+
+    let args = expand("tag --name={name} --in {dir}", Path::new("/repo/src/main.rs"), None);
+    assert_eq!(args, ["tag", "--name=main.rs", "--in", "/repo/src"]);
+}
+
+#[test]
+fn blank_commands_count_as_unset() {
+    let hooks = Hooks { after_create: Some("  ".to_string()), after_delete: Some("trash-log {path}".to_string()), ..Hooks::default() };
+    assert_eq!(HookEvent::Create.command(&hooks), None);
+    assert_eq!(HookEvent::Rename.command(&hooks), None);
+    assert_eq!(HookEvent::Delete.command(&hooks), Some("trash-log {path}"));
+}
+
+#[test]
+fn failing_hook_is_reported_by_poll() {
+    let service = HookService::new();
+    service.run(HookEvent::Copy, "clazyfiler-no-such-program {path}", Path::new("/tmp/a"), None);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let failure = loop {
+        if let Some(failure) = service.poll() {
+            break failure;
+        }
+        assert!(Instant::now() < deadline, "hook failure was never reported");
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    assert!(failure.starts_with("Hook after_copy failed: clazyfiler-no-such-program"), "{}", failure);
+}
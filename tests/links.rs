@@ -0,0 +1,17 @@
+//! Import references in previews point at the module's bytes, even after extra spaces or non-ASCII text
+
+use clazyfiler::core::{find_import_references, SourceLanguage};
+
+#[test]
+fn from_imports_survive_extra_spaces() {
+    let line = "from  os import path";
+    let references = find_import_references(line, SourceLanguage::Python, "app.py");
+    assert_eq!(references.len(), 1);
+    assert_eq!(&line[references[0].start..references[0].end], "os");
+    assert!(references[0].candidates.contains(&"os/path.py".to_string()));
+
+    let line = "from  é import x";
+    let references = find_import_references(line, SourceLanguage::Python, "app.py");
+    assert_eq!(references.len(), 1);
+    assert_eq!(&line[references[0].start..references[0].end], "é");
+}
@@ -0,0 +1,83 @@
+//! Recorded file operations can be undone and redone against real files in the system temp directory
+
+mod common;
+
+use std::fs;
+use clazyfiler::services::{FileService, OperationHistory, OperationKind};
+use common::TempTree;
+
+#[test]
+fn rename_and_move_are_undone_and_redone() {
+    let tree = TempTree::new("history-rename");
+    let root = tree.root.clone();
+    let service = FileService::new();
+    let mut history = OperationHistory::new();
+    fs::create_dir(root.join("dest")).unwrap();
+    fs::write(root.join("a.txt"), "a").unwrap();
+    fs::write(root.join("b.txt"), "b").unwrap();
+
+    service.rename(&root.join("a.txt"), &root.join("renamed.txt")).unwrap();
+    history.record(OperationKind::Rename, vec![(root.join("a.txt"), root.join("renamed.txt"))]);
+    let moved = service.move_file(&root.join("b.txt"), &root.join("dest")).unwrap();
+    history.record(OperationKind::Move, vec![(root.join("b.txt"), moved)]);
+
+    assert_eq!(history.undo(&service, None).unwrap().describe(), "move of b.txt");
+    assert!(root.join("b.txt").exists() && !root.join("dest/b.txt").exists());
+    history.undo(&service, None).unwrap();
+    assert!(root.join("a.txt").exists() && !root.join("renamed.txt").exists());
+    assert!(!history.can_undo());
+
+    assert_eq!(history.redo(&service, None).unwrap().describe(), "rename of a.txt");
+    assert!(root.join("renamed.txt").exists());
+    assert!(history.can_redo());
+
+    // A new operation drops what could still be redone
+    history.record(OperationKind::Rename, vec![(root.join("x"), root.join("y"))]);
+    assert!(!history.can_redo());
+}
+
+#[test]
+fn trashed_entries_come_back_and_copies_go_to_the_trash() {
+    let tree = TempTree::new("history-trash");
+    let root = tree.root.clone();
+    let service = FileService::new();
+    let mut history = OperationHistory::new();
+    let trash = root.join("Trash");
+    fs::write(root.join("gone.txt"), "gone").unwrap();
+    fs::create_dir(root.join("copies")).unwrap();
+
+    let trashed = service.trash_entry(&root.join("gone.txt"), &trash).unwrap();
+    history.record(OperationKind::Trash, vec![(root.join("gone.txt"), trashed)]);
+
+    history.undo(&service, Some(&trash)).unwrap();
+    assert_eq!(fs::read_to_string(root.join("gone.txt")).unwrap(), "gone");
+    assert!(!trash.join("info/gone.txt.trashinfo").exists());
+
+    let copy = service.copy_file(&root.join("gone.txt"), &root.join("copies")).unwrap();
+    history.record(OperationKind::Copy, vec![(root.join("gone.txt"), copy.clone())]);
+    history.undo(&service, Some(&trash)).unwrap();
+    assert!(!copy.exists());
+    assert!(trash.join("files/gone.txt").exists());
+    history.redo(&service, Some(&trash)).unwrap();
+    assert_eq!(fs::read_to_string(&copy).unwrap(), "gone");
+}
+
+#[test]
+fn entries_that_cannot_be_reverted_stay_undoable() {
+    let tree = TempTree::new("history-blocked");
+    let root = tree.root.clone();
+    let service = FileService::new();
+    let mut history = OperationHistory::new();
+    fs::write(root.join("new.txt"), "renamed").unwrap();
+    history.record(OperationKind::Rename, vec![(root.join("old.txt"), root.join("new.txt"))]);
+    // Something took the old name meanwhile
+    fs::write(root.join("old.txt"), "other").unwrap();
+
+    assert!(history.undo(&service, None).is_err());
+    assert!(history.can_undo() && !history.can_redo());
+    assert_eq!(fs::read_to_string(root.join("old.txt")).unwrap(), "other");
+
+    fs::remove_file(root.join("old.txt")).unwrap();
+    history.undo(&service, None).unwrap();
+    assert_eq!(fs::read_to_string(root.join("old.txt")).unwrap(), "renamed");
+}
@@ -0,0 +1,39 @@
+//! Tab completion of directories in prompts cycles through matches and keeps hidden ones out unless asked
+
+mod common;
+
+use std::path::Path;
+use clazyfiler::core::PathCompletion;
+use common::TempTree;
+
+#[test]
+fn tab_cycles_through_matching_directories() {
+    let tree = TempTree::new("path-completion");
+    let root = tree.root.clone();
+    for dir in ["docs", "downloads/new", "music", ".dotfiles", ".drafts"] {
+        tree.dir(dir);
+    }
+    tree.file("dossier.txt", "not a directory");
+
+    let mut completion = PathCompletion::default();
+    assert_eq!(completion.complete(&root, "do", false).as_deref(), Some("docs/"));
+    assert_eq!(completion.position().as_deref(), Some("1/2"));
+    assert_eq!(completion.complete(&root, "docs/", false).as_deref(), Some("downloads/"));
+    assert_eq!(completion.complete(&root, "downloads/", false).as_deref(), Some("docs/"));
+    assert_eq!(completion.complete(&root, "docs/", true).as_deref(), Some("downloads/"));
+
+    // Edited by hand: completes what is typed now, a level further down
+    completion.reset();
+    assert_eq!(completion.complete(&root, "downloads/", false).as_deref(), Some("downloads/new/"));
+    assert_eq!(completion.position(), None);
+
+    // Hidden directories only once the name starts with a dot
+    assert_eq!(completion.complete(&root, "", false).as_deref(), Some("docs/"));
+    assert_eq!(completion.complete(&root, ".d", false).as_deref(), Some(".dotfiles/"));
+    assert_eq!(completion.complete(&root, "x", false), None);
+
+    // Absolute paths complete where they point
+    let absolute = format!("{}/mu", root.display());
+    let expected = format!("{}/music/", root.display());
+    assert_eq!(completion.complete(Path::new("/"), &absolute, false), Some(expected));
+}
@@ -0,0 +1,49 @@
+//! Permission bits worth a warning badge while browsing, chattr attributes and POSIX ACLs
+
+use clazyfiler::core::{is_extended_acl, parse_acl, AclEntry, AclTag, attribute_name, attribute_names, has_attribute, risky_permissions};
+
+#[test]
+fn setuid_setgid_and_world_writable_entries_are_flagged() {
+    assert_eq!(risky_permissions(0o4755, false), vec!["setuid"]);
+    assert_eq!(risky_permissions(0o2755, false), vec!["setgid"]);
+    assert_eq!(risky_permissions(0o6777, false), vec!["setuid", "setgid", "world-writable"]);
+    assert!(risky_permissions(0o644, false).is_empty());
+
+    // Shared directories: setgid inherits the group, sticky makes world-writable safe
+    assert!(risky_permissions(0o2775, true).is_empty());
+    assert!(risky_permissions(0o1777, true).is_empty());
+    assert_eq!(risky_permissions(0o777, true), vec!["world-writable"]);
+}
+
+#[test]
+fn attribute_flags_are_named_like_lsattr_letters() {
+    let flags = 0x10 | 0x20 | 0x80;
+    assert_eq!(attribute_names(flags), vec!["immutable", "append-only", "no atime"]);
+    assert!(has_attribute(flags, 'i'));
+    assert!(!has_attribute(flags, 'd'));
+    assert_eq!(attribute_name('a'), Some("append-only"));
+    assert_eq!(attribute_name('z'), None);
+    assert!(attribute_names(0).is_empty());
+}
+
+#[test]
+fn acl_attributes_read_like_getfacl_and_only_count_when_extended() {
+    let mut data = 2u32.to_le_bytes().to_vec();
+    for (tag, perm, id) in [(0x01u16, 6u16, u32::MAX), (0x02, 7, 1000), (0x04, 4, u32::MAX), (0x10, 5, u32::MAX), (0x20, 0, u32::MAX)] {
+        data.extend(tag.to_le_bytes());
+        data.extend(perm.to_le_bytes());
+        data.extend(id.to_le_bytes());
+    }
+    let entries = parse_acl(&data).unwrap();
+    assert_eq!(entries[1].tag, AclTag::User(1000));
+    let names = |tag: &AclTag| (*tag == AclTag::User(1000)).then(|| "alice".to_string());
+    let described: Vec<String> = entries.iter().map(|entry| entry.describe(names)).collect();
+    assert_eq!(described, vec!["user::rw-", "user:alice:rwx", "group::r--", "mask::r-x", "other::---"]);
+    assert!(is_extended_acl(&entries));
+
+    // Only the three entries mirroring the mode bits
+    let base: Vec<AclEntry> = entries.into_iter().filter(|entry| !matches!(entry.tag, AclTag::User(_) | AclTag::Mask)).collect();
+    assert!(!is_extended_acl(&base));
+    assert_eq!(parse_acl(&data[..data.len() - 1]), None);
+    assert_eq!(parse_acl(&1u32.to_le_bytes()), None);
+}
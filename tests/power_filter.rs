@@ -0,0 +1,54 @@
+//! `owner:`, `group:`, `perm:` and `type:` words narrow searches down by metadata
+
+mod common;
+
+use std::fs;
+use clazyfiler::core::power_filter::{EntryType, PermCheck};
+use clazyfiler::core::{PowerFilter, Predicate};
+use common::TempTree;
+
+fn lookup(name: &str) -> Option<u32> {
+    (name == "root").then_some(0)
+}
+
+#[test]
+fn predicates_are_split_from_the_text() {
+    let filter = PowerFilter::parse("owner:root perm:setuid my bin type:file", lookup, lookup);
+    assert_eq!(filter.text, "my bin");
+    assert_eq!(filter.predicates, vec![
+        Predicate::Owner(Some(0)),
+        Predicate::Perm(PermCheck::Setuid),
+        Predicate::Type(EntryType::File),
+    ]);
+
+    // Unknown users match nothing; words that are not predicates stay text
+    let filter = PowerFilter::parse("owner:nobody-here group:50 perm:nonsense note:todo", lookup, lookup);
+    assert_eq!(filter.predicates, vec![Predicate::Owner(None), Predicate::Group(Some(50))]);
+    assert_eq!(filter.text, "perm:nonsense note:todo");
+    assert_eq!(PowerFilter::parse("perm:0644", lookup, lookup).predicates, vec![Predicate::Perm(PermCheck::Exactly(0o644))]);
+}
+
+#[cfg(unix)]
+#[test]
+fn entries_are_checked_against_their_own_metadata() {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+    let tree = TempTree::new("power-filter");
+    let root = tree.root.clone();
+    let open = tree.file("open.txt", "");
+    let closed = tree.file("closed.txt", "");
+    fs::set_permissions(&open, fs::Permissions::from_mode(0o666)).unwrap();
+    fs::set_permissions(&closed, fs::Permissions::from_mode(0o600)).unwrap();
+    std::os::unix::fs::symlink(&open, root.join("link")).unwrap();
+    let uid = fs::metadata(&root).unwrap().uid();
+
+    let filter = |query: &str| PowerFilter::parse(query, lookup, lookup);
+    assert!(filter("perm:world-writable").matches("open.txt", &open));
+    assert!(!filter("perm:world-writable").matches("closed.txt", &closed));
+    assert!(!filter("perm:world-writable OPEN").matches("link", &root.join("link")));
+    assert!(filter("type:symlink").matches("link", &root.join("link")));
+    assert!(!filter("type:symlink").matches("open.txt", &open));
+    assert!(filter("type:dir").matches("root", &root));
+    assert!(filter(&format!("owner:{} closed", uid)).matches("closed.txt", &closed));
+    assert!(!filter(&format!("owner:{}", uid + 1)).matches("closed.txt", &closed));
+    assert!(!filter("owner:nobody-here").matches("closed.txt", &closed));
+}
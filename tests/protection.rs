@@ -0,0 +1,121 @@
+//! Protected paths make deleting, moving, overwriting, renaming and changing owners or permissions
+//! inside them ask for the directory's name to be typed
+
+mod common;
+
+use std::path::{Path, PathBuf};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use clazyfiler::actions::Action;
+use clazyfiler::config::Config;
+use clazyfiler::core::{confirmation_word, protected_root};
+use clazyfiler::handlers::Handler;
+use clazyfiler::messages::AppMessage;
+use clazyfiler::model::{AppModel, FileEntry, PasteMode};
+use clazyfiler::services::Account;
+use clazyfiler::update::{update, Effect};
+use common::TempTree;
+
+/// A model listing the file `app.conf` in the protected directory `/srv/production`
+fn production() -> AppModel {
+    let file = FileEntry {
+        name: "app.conf".to_string(),
+        path: PathBuf::from("/srv/production/app.conf"),
+        is_directory: false,
+        size: None,
+        modified: None,
+        mode: None,
+        link_target: None,
+        metadata_error: None,
+    };
+    let mut config = Config::default();
+    config.general.protected_paths = vec!["/srv/production".to_string()];
+    AppModel::with_listing(PathBuf::from("/srv/production"), vec![file], config)
+}
+
+fn chown() -> Effect {
+    Effect::ChownEntries(Account { name: "deploy".to_string(), id: 1000 }, Account { name: "deploy".to_string(), id: 1000 })
+}
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+#[test]
+fn entries_in_or_below_protected_directories_are_guarded() {
+    let tree = TempTree::new("protection");
+    let root = tree.root.clone();
+    tree.dir("production/releases");
+    tree.dir("scratch");
+    let protected = vec![root.join("production").to_string_lossy().into_owned()];
+
+    let guarded = protected_root(&protected, &[root.join("scratch/a"), root.join("production/releases/v1")]);
+    assert_eq!(guarded, Some(root.join("production")));
+    assert_eq!(protected_root(&protected, &[root.join("production")]), Some(root.join("production")));
+    assert_eq!(protected_root(&protected, &[root.join("scratch/a")]), None);
+    // A sibling sharing the prefix is not inside
+    assert_eq!(protected_root(&protected, &[root.join("production-old")]), None);
+
+    // Reached through a symlink to the protected directory
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(root.join("production"), root.join("link")).unwrap();
+        assert_eq!(protected_root(&protected, &[root.join("link/releases")]), Some(root.join("production")));
+    }
+
+    assert_eq!(confirmation_word(&root.join("production")), "production");
+    assert_eq!(confirmation_word(Path::new("/")), "/");
+    assert_eq!(protected_root(&[String::new()], &[PathBuf::from("/etc")]), None);
+}
+
+#[test]
+fn changing_owners_or_moving_out_of_protected_directories_needs_confirming() {
+    let mut model = production();
+    let root = PathBuf::from("/srv/production");
+    assert_eq!(chown().protected_root(&model), Some((root.clone(), "change the owner of entries in")));
+    assert_eq!(Effect::SetPermissions(root.join("app.conf"), 0o600).protected_root(&model).map(|(root, _)| root), Some(root.clone()));
+    assert_eq!(Effect::SendEntriesTo(PathBuf::from("/tmp"), PasteMode::Move).protected_root(&model).map(|(_, what)| what), Some("move entries out of"));
+    assert_eq!(Effect::SendEntriesTo(PathBuf::from("/tmp"), PasteMode::Copy).protected_root(&model), None);
+    assert_eq!(Effect::App(AppMessage::RenameSelected("app.yaml".to_string())).protected_root(&model).map(|(_, what)| what), Some("rename entries in"));
+    assert_eq!(Effect::App(AppMessage::ExecuteCommand("bulkrename".to_string())).protected_root(&model).map(|(_, what)| what), Some("rename entries in"));
+    assert_eq!(Effect::App(AppMessage::ExecuteCommand("mkdir releases".to_string())).protected_root(&model), None);
+
+    // Copying out is harmless; cutting and pasting elsewhere takes the entry away
+    update(&mut model, Action::ClipboardCopy);
+    assert_eq!(Effect::PasteEntries.protected_root(&model), None);
+    update(&mut model, Action::ClipboardCut);
+    assert_eq!(Effect::PasteEntries.protected_root(&model), Some((root, "move entries out of")));
+
+    // Once confirmed it goes through
+    assert_eq!(Effect::Confirmed(Box::new(chown())).protected_root(&model), None);
+}
+
+#[test]
+fn the_typed_name_lets_held_back_effects_through_and_the_previous_popup_returns() {
+    let mut model = production();
+    let deferred = vec![chown(), Effect::App(AppMessage::SwitchToExploreHandlerKeepQuery)];
+    let previous = Handler::new_hard_links_handler(Vec::new());
+    let mut handler = Handler::new_confirm_protected_handler(PathBuf::from("/srv/production"), "change the owner of entries in", deferred, previous);
+
+    for c in "prod".chars() {
+        assert_eq!(handler.handle_key(key(KeyCode::Char(c)), &mut model), []);
+    }
+    assert_eq!(handler.handle_key(key(KeyCode::Enter), &mut model), []);
+    assert_eq!(model.status_message.as_deref(), Some("Type 'production' to proceed"));
+    assert!(matches!(handler, Handler::ConfirmProtected(_)));
+
+    for c in "uction".chars() {
+        handler.handle_key(key(KeyCode::Char(c)), &mut model);
+    }
+    assert_eq!(
+        handler.handle_key(key(KeyCode::Enter), &mut model),
+        [Effect::Confirmed(Box::new(chown())), Effect::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+    );
+    assert!(matches!(handler, Handler::HardLinks(_)));
+
+    // Esc drops what was held back
+    let previous = Handler::new_hard_links_handler(Vec::new());
+    let mut handler = Handler::new_confirm_protected_handler(PathBuf::from("/srv/production"), "move entries out of", vec![Effect::PasteEntries], previous);
+    assert_eq!(handler.handle_key(key(KeyCode::Esc), &mut model), []);
+    assert_eq!(model.status_message.as_deref(), Some("Nothing changed"));
+    assert!(matches!(handler, Handler::HardLinks(_)));
+}
@@ -0,0 +1,54 @@
+//! Transient I/O errors are retried with backoff, others are returned right away
+
+use std::io;
+use std::time::{Duration, Instant};
+use clazyfiler::core::{is_transient, RetryPolicy};
+
+fn policy(retries: u32) -> RetryPolicy {
+    RetryPolicy { retries, backoff: Duration::from_millis(1) }
+}
+
+/// An operation failing with `error` for its first `failures` calls
+fn flaky(failures: u32, error: fn() -> io::Error) -> impl FnMut() -> io::Result<u32> {
+    let mut calls = 0;
+    move || {
+        calls += 1;
+        if calls <= failures { Err(error()) } else { Ok(calls) }
+    }
+}
+
+#[test]
+fn transient_errors_are_retried_until_they_pass() {
+    assert_eq!(policy(3).run(flaky(2, || io::Error::from(io::ErrorKind::Interrupted))).unwrap(), 3);
+    // EIO, which is 5 on every unix
+    #[cfg(unix)]
+    assert_eq!(policy(3).run(flaky(3, || io::Error::from_raw_os_error(5))).unwrap(), 4);
+}
+
+#[test]
+fn retries_run_out() {
+    let error = policy(2).run(flaky(5, || io::Error::from(io::ErrorKind::TimedOut))).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    assert!(RetryPolicy::NONE.run(flaky(1, || io::Error::from(io::ErrorKind::WouldBlock))).is_err());
+}
+
+#[test]
+fn permanent_errors_are_not_retried() {
+    let mut calls = 0;
+    let result: io::Result<()> = policy(5).run(|| {
+        calls += 1;
+        Err(io::Error::from(io::ErrorKind::NotFound))
+    });
+    assert!(result.is_err());
+    assert_eq!(calls, 1);
+    assert!(!is_transient(&io::Error::from(io::ErrorKind::PermissionDenied)));
+}
+
+#[test]
+fn backoff_doubles() {
+    let started = Instant::now();
+    let slow = RetryPolicy { retries: 3, backoff: Duration::from_millis(10) };
+    assert!(slow.run(flaky(3, || io::Error::from(io::ErrorKind::Interrupted))).is_ok());
+    // 10 + 20 + 40 ms
+    assert!(started.elapsed() >= Duration::from_millis(70));
+}
@@ -0,0 +1,26 @@
+//! The status bar is made of the segments named in the config, in their order, the mode in its accent color
+
+use clazyfiler::config::{Config, StatusSegment};
+use ratatui::style::Color;
+
+#[test]
+fn segments_come_from_the_config() {
+    let config: Config = toml::from_str("").unwrap();
+    assert_eq!(config.status_bar.segments, StatusSegment::DEFAULT);
+
+    let config: Config = toml::from_str("[status_bar]\nsegments = [\"mode\", \"path\", \"git\", \"clock\"]").unwrap();
+    assert_eq!(config.status_bar.segments, vec![StatusSegment::Mode, StatusSegment::Path, StatusSegment::Git, StatusSegment::Clock]);
+
+    assert!(toml::from_str::<Config>("[status_bar]\nsegments = [\"weather\"]").is_err());
+}
+
+#[test]
+fn accents_take_names_hex_and_indexes() {
+    let config: Config = toml::from_str("[accents]\nsearch = \"light-red\"\nfuzzy = \"#5f87ff\"\ncommand = \"208\"").unwrap();
+    assert_eq!(config.accents.search, Color::LightRed);
+    assert_eq!(config.accents.fuzzy, Color::Rgb(0x5f, 0x87, 0xff));
+    assert_eq!(config.accents.command, Color::Indexed(208));
+    assert_eq!(config.accents.explore, Color::Yellow);
+
+    assert!(toml::from_str::<Config>("[accents]\nexplore = \"plaid\"").is_err());
+}
@@ -0,0 +1,37 @@
+//! The background I/O throttle paces transfers and shares its budget between clones
+
+use std::time::{Duration, Instant};
+use clazyfiler::core::IoThrottle;
+
+const MB: u64 = 1024 * 1024;
+
+#[test]
+fn unlimited_never_waits() {
+    let throttle = IoThrottle::unlimited();
+    let started = Instant::now();
+    for _ in 0..1000 {
+        throttle.consume(100 * MB);
+    }
+    assert!(started.elapsed() < Duration::from_millis(50));
+}
+
+#[test]
+fn clones_share_the_limit() {
+    // 2 MB at 20 MB/s from two handles is 100 ms
+    let throttle = IoThrottle::new(20 * MB);
+    let other = throttle.clone();
+    let started = Instant::now();
+    throttle.consume(MB);
+    other.consume(MB);
+    assert!(started.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn changing_the_limit_applies_to_clones() {
+    let throttle = IoThrottle::new(MB);
+    let other = throttle.clone();
+    throttle.set_limit(0);
+    let started = Instant::now();
+    other.consume(10 * MB);
+    assert!(started.elapsed() < Duration::from_millis(50));
+}
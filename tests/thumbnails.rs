@@ -0,0 +1,165 @@
+//! Thumbnails are decoded in the background and go out as kitty graphics or sixel escapes
+//! over cells the frame left untouched
+
+mod common;
+
+use std::io::Read;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use clazyfiler::core::base64_decode;
+use clazyfiler::services::image_service::{ImagePlacement, Thumbnail};
+use clazyfiler::services::{GraphicsProtocol, ImageService};
+use clazyfiler::ui::{kitty_sequence, sixel_sequence, GraphicsLayer};
+use common::TempTree;
+use ratatui::{buffer::Buffer, layout::Rect};
+
+fn thumbnail(width: u32, height: u32, pixels: Vec<[u8; 3]>) -> Rc<Thumbnail> {
+    Rc::new(Thumbnail { width, height, pixels })
+}
+
+fn placement(x: u16, y: u16, thumbnail: &Rc<Thumbnail>) -> ImagePlacement {
+    ImagePlacement { x, y, cols: 2, rows: 1, thumbnail: thumbnail.clone() }
+}
+
+/// A 10x3 screen whose cells at (x, y) and (x + 1, y) were reserved for an image
+fn screen(reserved: &[(u16, u16)]) -> Buffer {
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 10, 3));
+    for &(x, y) in reserved {
+        buffer[(x, y)].set_skip(true);
+        buffer[(x + 1, y)].set_skip(true);
+    }
+    buffer
+}
+
+fn present(layer: &mut GraphicsLayer, protocol: GraphicsProtocol, buffer: &Buffer, placements: Vec<ImagePlacement>) -> String {
+    let mut out = Vec::new();
+    layer.present(&mut out, protocol, buffer, placements).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn kitty_sends_compressed_rgb_in_bounded_chunks() {
+    let small = thumbnail(2, 1, vec![[255, 0, 0], [0, 0, 255]]);
+    let sequence = kitty_sequence(&small);
+    let payload = sequence
+        .strip_prefix("\x1b_Ga=T,f=24,s=2,v=1,o=z,C=1,q=2,m=0;")
+        .and_then(|rest| rest.strip_suffix("\x1b\\"))
+        .expect("a single escape for a small image");
+    let mut pixels = Vec::new();
+    flate2::read::ZlibDecoder::new(&base64_decode(payload)[..]).read_to_end(&mut pixels).unwrap();
+    assert_eq!(pixels, [255, 0, 0, 0, 0, 255]);
+
+    // Noise does not compress, so a large image needs several escapes
+    let noise = (0..200 * 200u32).map(|i| [(i * 7919 % 251) as u8, (i * 104729 % 241) as u8, (i % 239) as u8]).collect();
+    let sequence = kitty_sequence(&thumbnail(200, 200, noise));
+    let chunks: Vec<&str> = sequence.split_terminator("\x1b\\").collect();
+    assert!(chunks.len() > 1);
+    assert!(chunks[0].starts_with("\x1b_Ga=T,f=24,s=200,v=200,"));
+    for chunk in &chunks[1..chunks.len() - 1] {
+        assert!(chunk.starts_with("\x1b_Gm=1;"));
+    }
+    assert!(chunks[chunks.len() - 1].starts_with("\x1b_Gm=0;"));
+    assert!(chunks.iter().all(|chunk| chunk.split_once(';').unwrap().1.len() <= 4096));
+}
+
+#[test]
+fn sixel_bands_are_run_length_encoded_per_color() {
+    let red = thumbnail(3, 7, vec![[255, 0, 0]; 21]);
+    assert_eq!(sixel_sequence(&red), "\x1bPq\"1;1;3;7#180;2;100;0;0#180~~~-#180@@@\x1b\\");
+
+    let mut pixels = vec![[0, 0, 0]; 4];
+    pixels.push([255, 255, 255]);
+    assert_eq!(
+        sixel_sequence(&thumbnail(5, 1, pixels)),
+        "\x1bPq\"1;1;5;1#0;2;0;0;0#215;2;100;100;100#0!4@?$#215!4?@\x1b\\",
+    );
+}
+
+#[test]
+fn images_are_only_sent_again_when_something_changed() {
+    let image = thumbnail(1, 1, vec![[0, 255, 0]]);
+    let buffer = screen(&[(1, 1)]);
+    let mut layer = GraphicsLayer::default();
+
+    let first = present(&mut layer, GraphicsProtocol::Sixel, &buffer, vec![placement(1, 1, &image)]);
+    assert!(first.starts_with("\x1b[2;2H\x1bPq"));
+    assert_eq!(present(&mut layer, GraphicsProtocol::Sixel, &buffer, vec![placement(1, 1, &image)]), "");
+
+    layer.forget();
+    assert_eq!(present(&mut layer, GraphicsProtocol::Sixel, &buffer, vec![placement(1, 1, &image)]), first);
+}
+
+#[test]
+fn images_drawn_over_are_dropped_and_kitty_clears_the_old_ones() {
+    let image = thumbnail(1, 1, vec![[0, 255, 0]]);
+    let mut layer = GraphicsLayer::default();
+    present(&mut layer, GraphicsProtocol::Kitty, &screen(&[(1, 1), (5, 2)]), vec![placement(1, 1, &image), placement(5, 2, &image)]);
+
+    // A popup now covers the second image, so its cells were drawn rather than skipped
+    let covered = present(&mut layer, GraphicsProtocol::Kitty, &screen(&[(1, 1)]), vec![placement(1, 1, &image), placement(5, 2, &image)]);
+    assert!(covered.starts_with("\x1b_Ga=d,d=A,q=2\x1b\\\x1b[2;2H\x1b_Ga=T"));
+    assert!(!covered.contains("\x1b[3;6H"));
+}
+
+/// Poll until nothing of `path` is being decoded
+fn settle(service: &ImageService, path: &Path) {
+    let started = Instant::now();
+    while service.is_decoding(path) && started.elapsed() < Duration::from_secs(10) {
+        service.poll();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(!service.is_decoding(path));
+}
+
+#[test]
+fn thumbnails_are_decoded_in_the_background() {
+    let tree = TempTree::new("thumbnail-decode");
+    let path = tree.file("red.png", "");
+    image::RgbImage::from_pixel(8, 4, image::Rgb([255, 0, 0])).save(&path).unwrap();
+    let broken = tree.file("broken.jpg", "not an image");
+    let service = ImageService::new();
+
+    assert!(service.thumbnail(&path, 4, 4).is_none());
+    assert!(service.is_decoding(&path));
+    settle(&service, &path);
+    let thumbnail = service.thumbnail(&path, 4, 4).expect("decoded once collected");
+    assert_eq!((thumbnail.width, thumbnail.height), (4, 2));
+    assert_eq!(thumbnail.pixel(0, 0), [255, 0, 0]);
+
+    // A failure is remembered rather than decoded again
+    assert!(service.thumbnail(&broken, 4, 4).is_none());
+    settle(&service, &broken);
+    assert!(service.thumbnail(&broken, 4, 4).is_none());
+    assert!(!service.is_decoding(&broken));
+}
+
+#[test]
+fn waiting_images_start_on_later_draws_and_clearing_drops_late_results() {
+    let tree = TempTree::new("thumbnail-limit");
+    let paths: Vec<_> = (0..6).map(|index| tree.file(&format!("{}.png", index), "not an image")).collect();
+    let service = ImageService::new();
+    for path in &paths {
+        assert!(service.thumbnail(path, 4, 4).is_none());
+        assert!(service.is_decoding(path));
+    }
+
+    // The ones beyond the limit wait for a draw after a decode finished
+    let started = Instant::now();
+    while paths.iter().any(|path| service.is_decoding(path)) && started.elapsed() < Duration::from_secs(10) {
+        if service.poll() {
+            for path in &paths {
+                service.thumbnail(path, 4, 4);
+            }
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(paths.iter().all(|path| !service.is_decoding(path)));
+
+    service.clear_cache();
+    assert!(service.thumbnail(&paths[0], 4, 4).is_none());
+    service.clear_cache();
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(!service.poll());
+    assert!(!service.is_decoding(&paths[0]));
+}
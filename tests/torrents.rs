@@ -0,0 +1,50 @@
+//! Torrent previews list files and trackers and compute the info hashes from the raw info dictionary
+
+use std::path::{Path, PathBuf};
+use clazyfiler::services::TorrentService;
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+#[test]
+fn single_file_torrents_carry_their_length_and_v1_hash() {
+    let info = TorrentService::new().info(&fixture("single.torrent")).unwrap();
+    assert_eq!(info.name, "ubuntu.iso");
+    assert_eq!(info.files, [("ubuntu.iso".to_string(), 3_000_000_000)]);
+    assert_eq!(info.info_hash_v1.as_deref(), Some("6108d1733f761036b295ad1617f82c340d339903"));
+    assert_eq!(info.info_hash_v2, None);
+    assert_eq!(info.piece_length, Some(262144));
+    assert!(info.private);
+    assert_eq!(info.trackers, ["https://tracker.example/announce"]);
+    assert_eq!(info.comment.as_deref(), Some("Fixture release"));
+    assert_eq!(info.created_by.as_deref(), Some("mktorrent 1.1"));
+    assert_eq!(info.creation_date, Some(1700000000));
+}
+
+#[test]
+fn multi_file_torrents_skip_padding_and_repeated_trackers() {
+    let info = TorrentService::parse(&std::fs::read(fixture("album.torrent")).unwrap()).unwrap();
+    assert_eq!(info.files, [("01 - Intro.flac".to_string(), 1000), ("art/cover.jpg".to_string(), 2000)]);
+    assert_eq!(info.total_size(), 3000);
+    assert_eq!(info.info_hash_v1.as_deref(), Some("6495f57476c32ea9ef4d903988889d369fc5db65"));
+    assert_eq!(info.trackers, ["udp://one.example:80", "udp://two.example:80", "udp://three.example:80"]);
+    assert!(!info.private);
+}
+
+#[test]
+fn hybrid_torrents_have_both_hashes_and_a_flattened_file_tree() {
+    let info = TorrentService::parse(&std::fs::read(fixture("hybrid.torrent")).unwrap()).unwrap();
+    assert_eq!(info.files, [("README".to_string(), 12), ("data/part1.bin".to_string(), 70000)]);
+    assert_eq!(info.info_hash_v1.as_deref(), Some("c5c9cd6d4853cc63fd4d62efa8b2bc515b544894"));
+    assert_eq!(info.info_hash_v2.as_deref(), Some("c364de999cd70e8224ea398f57b3e8325386c468d5dba4900b7355a716192551"));
+}
+
+#[test]
+fn damaged_torrents_are_errors() {
+    let data = std::fs::read(fixture("album.torrent")).unwrap();
+    for len in 0..data.len() {
+        assert!(TorrentService::parse(&data[..len]).is_err());
+    }
+    assert!(TorrentService::parse(b"d8:announce3:urle").unwrap_err().to_string().contains("info"));
+}
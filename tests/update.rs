@@ -0,0 +1,217 @@
+//! Updates change the model in memory and hand anything that touches the disk back as effects
+
+use std::path::{Path, PathBuf};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use clazyfiler::actions::Action;
+use clazyfiler::batch::Batch;
+use clazyfiler::config::Config;
+use clazyfiler::core::{ClazyfilerError, Result};
+use clazyfiler::handlers::Handler;
+use clazyfiler::messages::AppMessage;
+use clazyfiler::model::{AppModel, FileEntry, PasteMode};
+use clazyfiler::services::{FileService, ImageEntry, ImageListing};
+use clazyfiler::update::{update, Effect, Msg};
+
+/// A model listing `/work` without reading it: the directories `docs` and `src`, then the files `notes.txt` and `todo.md`
+fn model() -> AppModel {
+    let files = vec![entry("docs", true), entry("src", true), entry("notes.txt", false), entry("todo.md", false)];
+    AppModel::with_listing(PathBuf::from("/work"), files, Config::default())
+}
+
+fn entry(name: &str, is_directory: bool) -> FileEntry {
+    FileEntry {
+        name: name.to_string(),
+        path: Path::new("/work").join(name),
+        is_directory,
+        size: None,
+        modified: None,
+        mode: None,
+        link_target: None,
+        metadata_error: None,
+    }
+}
+
+fn listed(model: &AppModel) -> Vec<&str> {
+    model.files.iter().map(|file| file.name.as_str()).collect()
+}
+
+fn marked(model: &AppModel) -> Vec<String> {
+    let mut names: Vec<String> = model.marked_paths.iter().map(|path| path.file_name().unwrap().to_string_lossy().to_string()).collect();
+    names.sort();
+    names
+}
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn always_fails(_: &FileService, path: &Path, _: &Path) -> Result<PathBuf> {
+    Err(ClazyfilerError::content(path.to_string_lossy().as_ref(), "in use"))
+}
+
+#[test]
+fn moving_and_marking_only_change_the_model() {
+    let mut model = model();
+    assert_eq!(update(&mut model, Action::NavDown), []);
+    assert_eq!(model.selected_index, 1);
+
+    assert_eq!(update(&mut model, Action::MarksToggle), []);
+    assert_eq!(marked(&model), ["src"]);
+    assert_eq!(model.selected_index, 2);
+
+    update(&mut model, Action::MarksInvert);
+    assert_eq!(marked(&model), ["docs", "notes.txt", "todo.md"]);
+    update(&mut model, Action::MarksAll);
+    assert_eq!(marked(&model), ["docs", "notes.txt", "src", "todo.md"]);
+}
+
+#[test]
+fn reading_directories_is_left_to_effects() {
+    let mut model = model();
+    assert_eq!(update(&mut model, Action::NavParent), [Effect::GoToParent]);
+    assert_eq!(update(&mut model, Action::NavOpen), [Effect::EnterDirectory(PathBuf::from("/work/docs"))]);
+    assert_eq!(update(&mut model, Action::ViewRefresh), [Effect::Refresh]);
+    assert_eq!(update(&mut model, Action::ViewToggleProject), [Effect::ToggleProjectView]);
+
+    model.selected_index = 2;
+    assert_eq!(update(&mut model, Action::NavOpen), [Effect::App(AppMessage::OpenFile)]);
+    assert_eq!(update(&mut model, Action::FileChecksums), [Effect::ComputeDigests]);
+    assert_eq!(update(&mut model, Action::FileShred), [Effect::ConfirmShred(vec![PathBuf::from("/work/notes.txt")])]);
+    assert_eq!(update(&mut model, Action::FileChmod), [Effect::EditPermissions(PathBuf::from("/work/notes.txt"))]);
+
+    // None of it happened yet
+    assert_eq!(model.current_dir, Path::new("/work"));
+    assert_eq!(listed(&model), ["docs", "src", "notes.txt", "todo.md"]);
+}
+
+#[test]
+fn visual_mode_marks_the_range_instead_of_leaving() {
+    let mut model = model();
+    update(&mut model, Action::MarksVisual);
+    update(&mut model, Action::NavDown);
+    update(&mut model, Action::NavDown);
+    assert_eq!(update(&mut model, Action::NavParent), []);
+    assert_eq!(marked(&model), ["docs", "notes.txt", "src"]);
+    assert_eq!(model.visual_anchor, None);
+    assert_eq!(model.status_message.as_deref(), Some("Marked 3 entries"));
+}
+
+#[test]
+fn yanking_and_pasting() {
+    let mut model = model();
+    assert_eq!(update(&mut model, Action::ClipboardPaste), []);
+    assert_eq!(model.status_message.as_deref(), Some("Nothing yanked to paste"));
+
+    update(&mut model, Action::MarksToggle);
+    update(&mut model, Action::MarksToggle);
+    update(&mut model, Action::ClipboardCut);
+    assert_eq!(model.status_message.as_deref(), Some("Cut 2 entries"));
+    let clipboard = model.file_clipboard.as_ref().unwrap();
+    assert_eq!(clipboard.mode, PasteMode::Move);
+    assert_eq!(clipboard.paths.len(), 2);
+    assert!(model.marked_paths.is_empty());
+    assert_eq!(update(&mut model, Action::ClipboardPaste), [Effect::PasteEntries]);
+}
+
+#[test]
+fn typing_a_search_filters_the_listing() {
+    let mut model = model();
+    let mut handler = Handler::new_search_handler();
+    for c in "to".chars() {
+        assert_eq!(handler.handle_key(key(KeyCode::Char(c)), &mut model), []);
+    }
+    assert_eq!(model.query_text, "to");
+    assert_eq!(listed(&model), ["todo.md"]);
+
+    update(&mut model, Msg::QueryBackspace);
+    assert_eq!(listed(&model), ["notes.txt", "todo.md"]);
+    assert_eq!(
+        handler.handle_key(key(KeyCode::Esc), &mut model),
+        [Effect::App(AppMessage::SwitchToExploreHandler)],
+    );
+    assert_eq!(model.query_text, "");
+    assert_eq!(listed(&model), ["docs", "src", "notes.txt", "todo.md"]);
+}
+
+#[test]
+fn fuzzy_find_enters_a_directory_before_returning() {
+    let mut model = model();
+    let mut handler = Handler::new_fuzzy_find_handler();
+    assert_eq!(
+        handler.handle_key(key(KeyCode::Enter), &mut model),
+        [Effect::EnterDirectory(PathBuf::from("/work/docs")), Effect::App(AppMessage::SwitchToExploreHandler)],
+    );
+}
+
+#[test]
+fn the_marks_popup_unmarks_reveals_and_pastes() {
+    let mut model = model();
+    update(&mut model, Action::MarksAll);
+    let mut handler = Handler::new_marks_handler();
+
+    // Sorted by path, so the cursor starts on docs
+    assert_eq!(handler.perform(Action::MarksUnmark, &mut model), []);
+    assert_eq!(marked(&model), ["notes.txt", "src", "todo.md"]);
+    assert_eq!(
+        handler.perform(Action::MarksReveal, &mut model),
+        [Effect::Reveal(PathBuf::from("/work/notes.txt")), Effect::App(AppMessage::SwitchToExploreHandlerKeepQuery)],
+    );
+    assert_eq!(
+        handler.perform(Action::MarksCopyHere, &mut model),
+        [Effect::App(AppMessage::SwitchToExploreHandlerKeepQuery), Effect::PasteEntries],
+    );
+    assert!(model.marked_paths.is_empty());
+    assert_eq!(model.file_clipboard.as_ref().unwrap().paths.len(), 3);
+}
+
+#[test]
+fn unmarking_the_last_entry_closes_the_marks_popup() {
+    let mut model = model();
+    update(&mut model, Action::MarksToggle);
+    let mut handler = Handler::new_marks_handler();
+    assert_eq!(handler.perform(Action::MarksUnmark, &mut model), [Effect::App(AppMessage::SwitchToExploreHandler)]);
+    assert!(model.marked_paths.is_empty());
+}
+
+#[test]
+fn skipping_the_last_failure_dismisses_the_batch() {
+    let mut model = model();
+    let service = FileService::new();
+    let mut batch = Batch::new("Copy", vec![PathBuf::from("a"), PathBuf::from("b")], PathBuf::from("dest"), always_fails);
+    while batch.is_running() {
+        batch.run_for(&service, std::time::Duration::from_millis(10));
+    }
+    model.batch = Some(batch);
+
+    assert_eq!(update(&mut model, Msg::SkipFailure(0)), []);
+    assert_eq!(model.batch.as_ref().unwrap().failures.len(), 1);
+    assert_eq!(update(&mut model, Msg::SkipFailure(0)), [Effect::App(AppMessage::SwitchToExploreHandlerKeepQuery)]);
+    assert!(model.batch.is_none());
+    assert_eq!(model.status_message.as_deref(), Some("Copy 2/2, 2 skipped"));
+}
+
+#[test]
+fn iso_images_are_browsed_in_a_popup() {
+    let mut model = model();
+    assert_eq!(update(&mut model, Action::FileBrowseImage), []);
+    assert_eq!(model.status_message.as_deref(), Some("Not an ISO image"));
+
+    let mut model = AppModel::with_listing(PathBuf::from("/work"), vec![entry("rescue.iso", false)], Config::default());
+    let image = PathBuf::from("/work/rescue.iso");
+    assert_eq!(update(&mut model, Action::FileBrowseImage), [Effect::BrowseDiskImage(image.clone(), Vec::new())]);
+
+    let image_entry = |name: &str, is_directory: bool| ImageEntry { name: name.to_string(), size: 2048, is_directory, offset: 0 };
+    let boot = image_entry("BOOT", true);
+    let readme = image_entry("README.TXT", false);
+    let listing = ImageListing { image: image.clone(), trail: Vec::new(), entries: vec![boot.clone(), readme.clone()] };
+    let mut handler = Handler::new_disk_image_handler(listing);
+    assert_eq!(handler.handle_key(key(KeyCode::Enter), &mut model), [Effect::BrowseDiskImage(image.clone(), vec![boot.clone()])]);
+    handler.handle_key(key(KeyCode::Char('j')), &mut model);
+    assert_eq!(handler.handle_key(key(KeyCode::Enter), &mut model), [Effect::ExtractFromImage(image.clone(), readme)]);
+    // At the root, going up closes the popup
+    assert_eq!(handler.handle_key(key(KeyCode::Char('h')), &mut model), [Effect::App(AppMessage::SwitchToExploreHandlerKeepQuery)]);
+
+    let listing = ImageListing { image: image.clone(), trail: vec![boot], entries: Vec::new() };
+    let mut handler = Handler::new_disk_image_handler(listing);
+    assert_eq!(handler.handle_key(key(KeyCode::Char('h')), &mut model), [Effect::BrowseDiskImage(image, Vec::new())]);
+}
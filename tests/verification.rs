@@ -0,0 +1,44 @@
+//! Checksums computed on request run in the background and are collected by polling
+
+mod common;
+
+use std::time::{Duration, Instant};
+use clazyfiler::core::{to_hex, Md5};
+use clazyfiler::services::VerificationService;
+use clazyfiler::services::verification_service::{DigestState, Digests};
+use common::TempTree;
+
+#[test]
+fn md5_matches_the_reference_vectors() {
+    let digest = |data: &[u8]| {
+        let mut md5 = Md5::new();
+        md5.update(data);
+        to_hex(&md5.finish())
+    };
+    assert_eq!(digest(b""), "d41d8cd98f00b204e9800998ecf8427e");
+    assert_eq!(digest(b"The quick brown fox jumps over the lazy dog"), "9e107d9d372bb6826bd81d3542a419d6");
+    assert_eq!(digest(&[b'a'; 1000]), "cabe45dcc9ae5b66ba86600cca6b8ba8");
+}
+
+#[test]
+fn digests_are_computed_in_the_background() {
+    let tree = TempTree::new("digests");
+    let path = tree.file("release.tar", "abc");
+    let service = VerificationService::new();
+    assert_eq!(service.digests(&path), None);
+    assert!(service.compute_digests(&path));
+    assert!(!service.compute_digests(&path));
+    assert_eq!(service.pending_checksums(), 1);
+
+    let started = Instant::now();
+    while service.digests(&path) == Some(DigestState::Pending) && started.elapsed() < Duration::from_secs(10) {
+        service.poll();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!(service.digests(&path), Some(DigestState::Done(Digests {
+        md5: "900150983cd24fb0d6963f7d28e17f72".to_string(),
+        sha1: "a9993e364706816aba3e25717850c26c9cd0d89d".to_string(),
+        sha256: "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string(),
+    })));
+    assert_eq!(service.pending_checksums(), 0);
+}
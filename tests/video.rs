@@ -0,0 +1,50 @@
+//! ffprobe and ffmpeg run in the background and are collected by polling, so previews never wait on them
+
+mod common;
+
+use std::time::{Duration, Instant};
+use clazyfiler::services::{VideoService, VideoState};
+use common::TempTree;
+
+#[test]
+fn probes_start_pending_and_settle_after_polling() {
+    let tree = TempTree::new("video-probe");
+    // Not a video at all, so the probe fails whether or not ffprobe is installed
+    let path = tree.file("clip.mp4", "not a video");
+    let service = VideoService::new(Some(tree.dir("cache")));
+    assert!(matches!(service.info(&path), VideoState::Pending));
+    assert!(matches!(service.frame(&path), VideoState::Pending));
+
+    let started = Instant::now();
+    let settled = |service: &VideoService| {
+        !matches!(service.info(&path), VideoState::Pending) && !matches!(service.frame(&path), VideoState::Pending)
+    };
+    while !settled(&service) && started.elapsed() < Duration::from_secs(10) {
+        service.poll();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(matches!(service.info(&path), VideoState::Failed));
+    assert!(matches!(service.frame(&path), VideoState::Failed));
+}
+
+#[test]
+fn frames_need_a_cache_directory() {
+    let tree = TempTree::new("video-no-cache");
+    let path = tree.file("clip.mkv", "not a video");
+    let service = VideoService::new(None);
+    assert!(matches!(service.frame(&path), VideoState::Failed));
+}
+
+#[test]
+fn clearing_drops_results_still_on_their_way() {
+    let tree = TempTree::new("video-clear");
+    let path = tree.file("clip.webm", "not a video");
+    let service = VideoService::new(Some(tree.dir("cache")));
+    assert!(matches!(service.info(&path), VideoState::Pending));
+    service.clear_cache();
+
+    // The first probe's result is dropped; asking again starts a new one
+    std::thread::sleep(Duration::from_millis(50));
+    service.poll();
+    assert!(matches!(service.info(&path), VideoState::Pending));
+}